@@ -0,0 +1,251 @@
+use crate::observer::{GameEvent, GameObserver};
+use crate::allocator::Allocator;
+use std::alloc::{GlobalAlloc, Layout};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The parts of the engine allocations are counted against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    /// Anything not inside one of the others, such as the bots and the game loop
+    Other,
+    /// Placing tiles on the board, other than joining up their regions
+    Board,
+    /// Joining a placed tile's regions to the connected regions beside it, and scoring them
+    Regions,
+    /// Working out the moves available for a tile
+    Hints,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 4] = [Subsystem::Other, Subsystem::Board, Subsystem::Regions, Subsystem::Hints];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Subsystem::Other => "other",
+            Subsystem::Board => "board",
+            Subsystem::Regions => "regions",
+            Subsystem::Hints => "hints",
+        }
+    }
+}
+
+/// The build's allocator (see [crate::ALLOCATOR_NAME]), counting the bytes live and the
+/// allocations made in each [Subsystem]. Installed as the global allocator by the `alloc-stats`
+/// feature in this crate's binary, and by a binary of your own with
+/// `#[global_allocator] static GLOBAL: CountingAllocator = CountingAllocator;`
+pub struct CountingAllocator;
+
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: [AtomicUsize; 4] = [const { AtomicUsize::new(0) }; 4];
+static ALLOCATIONS: [AtomicUsize; 4] = [const { AtomicUsize::new(0) }; 4];
+
+thread_local! {
+    static SUBSYSTEM: Cell<Subsystem> = const { Cell::new(Subsystem::Other) };
+}
+
+fn record_allocation(size: usize) {
+    let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+
+    // the thread's subsystem is gone once it has started shutting down
+    let subsystem = SUBSYSTEM.try_with(Cell::get).unwrap_or(Subsystem::Other) as usize;
+    ALLOCATED_BYTES[subsystem].fetch_add(size, Ordering::Relaxed);
+    ALLOCATIONS[subsystem].fetch_add(1, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = Allocator.alloc(layout);
+
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Allocator.dealloc(ptr, layout);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = Allocator.alloc_zeroed(layout);
+
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = Allocator.realloc(ptr, layout, new_size);
+
+        if !new_ptr.is_null() {
+            LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+
+        new_ptr
+    }
+}
+
+/// Counts allocations on this thread against a subsystem until it is dropped
+pub struct SubsystemScope {
+    previous: Subsystem,
+}
+
+impl Drop for SubsystemScope {
+    fn drop(&mut self) {
+        SUBSYSTEM.set(self.previous);
+    }
+}
+
+pub fn enter(subsystem: Subsystem) -> SubsystemScope {
+    SubsystemScope { previous: SUBSYSTEM.replace(subsystem) }
+}
+
+/// Bytes and allocations made in a subsystem, counting every allocation however short lived
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SubsystemUsage {
+    pub bytes: usize,
+    pub allocations: usize,
+}
+
+impl std::ops::Sub for SubsystemUsage {
+    type Output = SubsystemUsage;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        SubsystemUsage { bytes: self.bytes - rhs.bytes, allocations: self.allocations - rhs.allocations }
+    }
+}
+
+pub fn usage(subsystem: Subsystem) -> SubsystemUsage {
+    SubsystemUsage {
+        bytes: ALLOCATED_BYTES[subsystem as usize].load(Ordering::Relaxed),
+        allocations: ALLOCATIONS[subsystem as usize].load(Ordering::Relaxed),
+    }
+}
+
+pub fn live_bytes() -> usize {
+    LIVE_BYTES.load(Ordering::Relaxed)
+}
+
+/// Most bytes live at once since the peak was last reset
+pub fn peak_bytes() -> usize {
+    PEAK_BYTES.load(Ordering::Relaxed)
+}
+
+pub fn reset_peak() {
+    PEAK_BYTES.store(live_bytes(), Ordering::Relaxed);
+}
+
+/// The memory a game used
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameMemory {
+    pub game_index: usize,
+    /// Most bytes live at once during the game, over what was live when it started
+    pub peak_bytes: usize,
+    /// What each subsystem allocated during the game, in [Subsystem::ALL] order
+    pub subsystems: [SubsystemUsage; 4],
+}
+
+/// Records the [GameMemory] of every game it observes. The counts are for the whole process, so
+/// are only a game's own when games are played one at a time, as [crate::simulate::run] does
+#[derive(Debug, Default)]
+pub struct MemoryObserver {
+    started: Option<(usize, usize, [SubsystemUsage; 4])>,
+    pub games: Vec<GameMemory>,
+}
+
+impl MemoryObserver {
+    /// The highest peak of any game
+    pub fn peak_bytes(&self) -> usize {
+        self.games.iter().map(|game| game.peak_bytes).max().unwrap_or(0)
+    }
+
+    /// What each subsystem allocated on average over the games
+    pub fn mean_usage(&self, subsystem: Subsystem) -> SubsystemUsage {
+        let games = self.games.len().max(1);
+        let total = self.games.iter().map(|game| game.subsystems[subsystem as usize]).fold(SubsystemUsage::default(), |total, usage| SubsystemUsage {
+            bytes: total.bytes + usage.bytes,
+            allocations: total.allocations + usage.allocations,
+        });
+
+        SubsystemUsage { bytes: total.bytes / games, allocations: total.allocations / games }
+    }
+}
+
+impl GameObserver for MemoryObserver {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, .. } => {
+                reset_peak();
+                self.started = Some((*game_index, live_bytes(), Subsystem::ALL.map(usage)));
+            }
+            GameEvent::GameCompleted { game_index, .. } => {
+                let Some((started_index, live_at_start, usage_at_start)) = self.started.take() else {
+                    return;
+                };
+
+                if started_index != *game_index {
+                    return;
+                }
+
+                self.games.push(GameMemory {
+                    game_index: *game_index,
+                    peak_bytes: peak_bytes().saturating_sub(live_at_start),
+                    subsystems: Subsystem::ALL.map(|subsystem| usage(subsystem) - usage_at_start[subsystem as usize]),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+    use crate::player::Player;
+    use crate::simulate::{self, SimulationConfig};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn should_count_allocations_against_the_subsystem_entered() {
+        let before = usage(Subsystem::Hints);
+
+        {
+            let _scope = enter(Subsystem::Hints);
+            std::hint::black_box(vec![0u8; 4096]);
+        }
+
+        let after = usage(Subsystem::Hints) - before;
+
+        assert!(after.bytes >= 4096);
+        assert!(after.allocations >= 1);
+        assert_eq!(SUBSYSTEM.with(Cell::get), Subsystem::Other);
+    }
+
+    #[test]
+    fn should_record_the_memory_each_game_used() {
+        let players = [Player::red(), Player::green()].map(|player| player.with_bot(BotStrategy::Lazy(LazyBot)));
+        let memory = Rc::new(RefCell::new(MemoryObserver::default()));
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(Rc::clone(&memory))];
+
+        simulate::run_observed(SimulationConfig::new(players, 2, [5; 32]).unwrap(), &mut observers);
+
+        let memory = memory.borrow();
+
+        assert_eq!(memory.games.len(), 2);
+        assert!(memory.peak_bytes() > 0);
+        assert!(memory.games.iter().all(|game| game.subsystems[Subsystem::Regions as usize].allocations > 0));
+    }
+}
@@ -0,0 +1,18 @@
+// Only one allocator can be the global one, so with both features on jemalloc is used, and with
+// alloc-stats the counting allocator wraps whichever is chosen. The library only names the
+// allocator; a binary installs it with #[global_allocator], as this crate's own does
+#[cfg(feature = "jemalloc")]
+pub use tikv_jemallocator::Jemalloc as Allocator;
+#[cfg(all(feature = "mimalloc", not(feature = "jemalloc")))]
+pub use mimalloc::MiMalloc as Allocator;
+#[cfg(all(feature = "alloc-stats", not(any(feature = "jemalloc", feature = "mimalloc"))))]
+pub use std::alloc::System as Allocator;
+
+/// The allocator chosen by the `jemalloc` and `mimalloc` features, which this crate's binary
+/// uses, so timings from builds with different allocators can be told apart
+#[cfg(feature = "jemalloc")]
+pub const ALLOCATOR_NAME: &str = "jemalloc";
+#[cfg(all(feature = "mimalloc", not(feature = "jemalloc")))]
+pub const ALLOCATOR_NAME: &str = "mimalloc";
+#[cfg(not(any(feature = "jemalloc", feature = "mimalloc")))]
+pub const ALLOCATOR_NAME: &str = "system";
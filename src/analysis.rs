@@ -0,0 +1,733 @@
+use crate::board::Board;
+use crate::bot_strategy::{Bot, BotStrategy};
+use crate::move_hints::MoveHint;
+use crate::player::{Player, PlayerIdentifier};
+use crate::replay::{Replay, ReplayError, ReplayPlacement, ReplayTurn};
+use crate::rng::GameRng;
+use crate::tie_break;
+use crate::connected_regions::{ConnectedRegion, ConnectedRegionId};
+use crate::tile::{PlacedTile, RegionType, Rotation, TileDefinition};
+use crate::tile_definitions::ALL_TILE_DEFINITIONS;
+use indexmap::IndexMap;
+use rand::SeedableRng;
+use std::collections::{HashMap, HashSet};
+
+/// Bot used to play out a branch for players whose replay doesn't say which bot they were
+pub const DEFAULT_BRANCH_BOT: &str = "myopic";
+
+/// A recorded move that was worth noticeably less than the best move available at the time
+#[derive(Debug, Clone)]
+pub struct Blunder {
+    pub turn: usize,
+    pub player: PlayerIdentifier,
+    pub tile: &'static TileDefinition,
+    pub played_value: i32,
+    pub best_value: i32,
+    pub best_move: ReplayPlacement,
+}
+
+impl Blunder {
+    /// How many points worse the played move was than the best move
+    pub fn cost(&self) -> i32 {
+        self.best_value - self.played_value
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BlunderReport {
+    pub threshold: i32,
+    /// Number of moves evaluated for each player, in turn order
+    pub moves: IndexMap<PlayerIdentifier, usize>,
+    pub blunders: Vec<Blunder>,
+}
+
+impl BlunderReport {
+    pub fn blunders_by(&self, player: PlayerIdentifier) -> impl Iterator<Item = &Blunder> {
+        self.blunders.iter().filter(move |blunder| blunder.player == player)
+    }
+
+    /// Total points given away through blunders by a player
+    pub fn points_lost(&self, player: PlayerIdentifier) -> i32 {
+        self.blunders_by(player).map(Blunder::cost).sum()
+    }
+}
+
+/// Value of a move to the player: their own score change (treating open regions as if the game
+/// ended now) less the best score change it gives any opponent
+pub fn move_value(board: &Board, player: &Player, opponents: &[PlayerIdentifier], hint: &MoveHint) -> i32 {
+    let score = hint.score_delta(board, player, true);
+
+    let points = |id: &PlayerIdentifier| score.iter().find(|(other, _)| *other == id).map(|(_, s)| *s).unwrap_or(0);
+
+    let best_opponent = opponents.iter().map(points).max().unwrap_or(0);
+
+    points(&player.meeple_color) - best_opponent
+}
+
+/// The best of `hints` by [move_value], with its value
+pub fn best_move<'a>(board: &Board, player: &Player, opponents: &[PlayerIdentifier], hints: &'a [MoveHint]) -> Option<(i32, &'a MoveHint)> {
+    let values: Vec<i32> = hints.iter().map(|hint| move_value(board, player, opponents, hint)).collect();
+
+    tie_break::best_index(hints, &values, None).map(|index| (values[index], &hints[index]))
+}
+
+/// How closely a player's moves matched the best move by [move_value], over a game
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Accuracy {
+    pub moves: usize,
+    /// Moves as good as the best move
+    pub best_moves: usize,
+    /// Points given away by the other moves
+    pub points_lost: i32,
+}
+
+impl Accuracy {
+    pub fn record(&mut self, played_value: i32, best_value: i32) {
+        self.moves += 1;
+
+        if played_value >= best_value {
+            self.best_moves += 1;
+        } else {
+            self.points_lost += best_value - played_value;
+        }
+    }
+
+    /// Share of moves that were as good as the best move, as a percentage
+    pub fn percentage(&self) -> f64 {
+        match self.moves {
+            0 => 100.0,
+            moves => self.best_moves as f64 * 100.0 / moves as f64,
+        }
+    }
+}
+
+/// Re-evaluates every recorded move against the best move available at the time, flagging those
+/// that were worse by more than `threshold` points
+pub fn find_blunders(replay: &Replay, threshold: i32) -> Result<BlunderReport, ReplayError> {
+    let mut state = replay.initial_state()?;
+
+    let mut report = BlunderReport {
+        threshold,
+        moves: replay.players.iter().map(|p| (p.color, 0)).collect(),
+        blunders: vec![],
+    };
+
+    for turn in &replay.turns {
+        let turn_index = state.turn;
+
+        if let Some(placement) = &turn.placement {
+            let tile = turn.tile()?;
+            let player = state.players.get(&turn.player).ok_or(ReplayError::UnknownPlayer(turn.player))?;
+            let opponents: Vec<_> = state.players.keys().filter(|id| **id != turn.player).copied().collect();
+
+            // check the recorded move is legal before trying to value it
+            let mut validated = state.clone();
+            validated.apply(turn)?;
+
+            let played = MoveHint {
+                tile,
+                tile_placement: placement.tile_placement(),
+                meeple_placement: placement.meeple_region(),
+            };
+
+            let played_value = move_value(&state.board, player, &opponents, &played);
+
+            let hints = state.board.get_move_hints(tile, !player.meeple.is_empty());
+            let best = best_move(&state.board, player, &opponents, &hints);
+
+            *report.moves.entry(turn.player).or_insert(0) += 1;
+
+            if let Some((best_value, best)) = best {
+                if best_value - played_value > threshold {
+                    report.blunders.push(Blunder {
+                        turn: turn_index,
+                        player: turn.player,
+                        tile,
+                        played_value,
+                        best_value,
+                        best_move: ReplayPlacement::new(&best.tile_placement, best.meeple_placement),
+                    });
+                }
+            }
+
+            state = validated;
+        } else {
+            state.apply(turn)?;
+        }
+    }
+
+    Ok(report)
+}
+
+/// How much luckier each player's draws were than average over the game, in points of
+/// [move_value]. Each draw counts the best move it allowed less the best move expected from a
+/// draw out of the unseen tiles, so a draw that finishes the player's open features is lucky and
+/// one that fits nowhere useful is not. River tiles are left out, as everyone draws the same ones
+pub fn luck(replay: &Replay) -> Result<IndexMap<PlayerIdentifier, f64>, ReplayError> {
+    let mut state = replay.initial_state()?;
+    let mut luck: IndexMap<PlayerIdentifier, f64> = replay.players.iter().map(|p| (p.color, 0.0)).collect();
+
+    for turn in &replay.turns {
+        let tile = turn.tile()?;
+
+        if tile.expansion.is_none() {
+            let player = state.players.get(&turn.player).ok_or(ReplayError::UnknownPlayer(turn.player))?;
+            let opponents: Vec<_> = state.players.keys().filter(|id| **id != turn.player).copied().collect();
+
+            *luck.entry(turn.player).or_insert(0.0) += draw_luck(&state.board, player, &opponents, tile);
+        }
+
+        state.apply(turn)?;
+    }
+
+    Ok(luck)
+}
+
+/// The best [move_value] `tile` allows, less that expected from a draw out of the unseen tiles
+fn draw_luck(board: &Board, player: &Player, opponents: &[PlayerIdentifier], tile: &'static TileDefinition) -> f64 {
+    let best_value = |tile: &'static TileDefinition| {
+        let hints = board.get_move_hints(tile, !player.meeple.is_empty());
+        best_move(board, player, opponents, &hints).map(|(value, _)| value).unwrap_or(0)
+    };
+
+    let unseen = unseen_tiles(board);
+
+    if unseen.is_empty() {
+        return 0.0;
+    }
+
+    let mut draws: Vec<(&'static TileDefinition, usize)> = vec![];
+
+    for unseen_tile in unseen.iter() {
+        match draws.iter_mut().find(|(drawn, _)| drawn.name == unseen_tile.name) {
+            Some((_, count)) => *count += 1,
+            None => draws.push((unseen_tile, 1)),
+        }
+    }
+
+    let expected: f64 = draws.into_iter().map(|(drawn, count)| best_value(drawn) as f64 * count as f64).sum::<f64>() / unseen.len() as f64;
+
+    best_value(tile) as f64 - expected
+}
+
+/// Forks `replay` at `turn`, playing `alternative_move` with the tile drawn on that turn instead of
+/// the recorded move. The rest of the game is played out by each player's bot (or
+/// [DEFAULT_BRANCH_BOT]) drawing the same remaining tiles in the same order
+pub fn branch(replay: &Replay, turn: usize, alternative_move: ReplayPlacement) -> Result<Replay, ReplayError> {
+    let forked_turn = replay.turns.get(turn).ok_or(ReplayError::TurnOutOfRange(turn))?;
+
+    let mut state = replay.state_at(turn)?;
+
+    let mut branched = Replay {
+        turns: replay.turns[..turn].to_vec(),
+        final_score: vec![],
+        ..replay.clone()
+    };
+
+    let alternative_turn = ReplayTurn {
+        placement: Some(alternative_move),
+        ..forked_turn.clone()
+    };
+
+    state.apply(&alternative_turn)?;
+    branched.turns.push(alternative_turn);
+
+    let mut rng = GameRng::from_seed(replay.seed().unwrap_or_default());
+
+    let mut bots = IndexMap::new();
+
+    for player in &replay.players {
+        let name = player.bot.as_deref().unwrap_or(DEFAULT_BRANCH_BOT);
+        let bot = BotStrategy::from_name(name, &mut rng)
+            .or_else(|| BotStrategy::from_name(DEFAULT_BRANCH_BOT, &mut rng))
+            .expect("default bot should exist");
+
+        bots.insert(player.color, bot);
+    }
+
+    for recorded in &replay.turns[turn + 1..] {
+        let tile = recorded.tile()?;
+        let player = state.players.get(&recorded.player).ok_or(ReplayError::UnknownPlayer(recorded.player))?;
+        let bot = bots.get_mut(&recorded.player).ok_or(ReplayError::UnknownPlayer(recorded.player))?;
+
+        let hints = state.board.get_move_hints(tile, !player.meeple.is_empty());
+
+        let placement = bot
+            .select_hint(&state.board, player, &hints)
+            .map(|hint| ReplayPlacement::new(&hint.tile_placement, hint.meeple_placement));
+
+        let branched_turn = ReplayTurn {
+            placement,
+            ..recorded.clone()
+        };
+
+        state.apply(&branched_turn)?;
+        branched.turns.push(branched_turn);
+    }
+
+    branched.record_final_score(&state.final_score());
+
+    Ok(branched)
+}
+
+/// A meeple in hand is worth this much while there are plenty of tiles left to play it on
+pub const MEEPLE_IN_HAND_VALUE: f64 = 1.5;
+/// Below this many remaining tiles, meeple in hand lose value as the chances to use them run out
+const LATE_GAME_TILES: usize = 12;
+
+/// Expected end of game value of the board to `player`. Combines the points their meeple would
+/// score if the game ended now, the extra points from completing their open features (weighted by
+/// the chance `deck_remaining` lets them be completed), the extra points their farmers would get
+/// from bordering cities being completed, and the meeple they still have in hand.
+///
+/// Points already scored and banked are not on the board, so aren't included
+pub fn evaluate(board: &Board, player: &Player, deck_remaining: &[&'static TileDefinition]) -> f64 {
+    let realized = board
+        .calculate_board_score()
+        .iter()
+        .find(|(id, _)| **id == player.meeple_color)
+        .map(|(_, points)| *points as f64)
+        .unwrap_or(0.0);
+
+    let mut potential = 0.0;
+    let mut farm_equity = 0.0;
+
+    for region in board.get_connected_regions() {
+        if !region.majority_meeple_player_ids(board).contains(&player.meeple_color) {
+            continue;
+        }
+
+        match region.region_type {
+            RegionType::City => {
+                // a completed city scores double
+                potential += region.score(board) as f64 * completion_probability(board, region, deck_remaining);
+            }
+            RegionType::Cloister => {
+                potential += (9 - region.score(board)) as f64 * completion_probability(board, region, deck_remaining);
+            }
+            RegionType::Field => farm_equity += field_equity(board, region, deck_remaining),
+            // roads score the same open or closed
+            RegionType::Road | RegionType::Water => {}
+        }
+    }
+
+    let meeple_in_hand = player.meeple.len() as f64 * meeple_in_hand_value(deck_remaining.len());
+
+    realized + potential + farm_equity + meeple_in_hand
+}
+
+/// What the open cities, roads and cloisters `player` has a share of would score them once
+/// completed, however likely that is. Fields are never completed, so aren't counted
+pub fn open_feature_potential(board: &Board, player: PlayerIdentifier) -> u32 {
+    board
+        .get_connected_regions()
+        .into_iter()
+        .filter(|region| !region.is_closed() && region.majority_meeple_player_ids(board).contains(&player))
+        .map(|region| completed_points(board, region))
+        .sum()
+}
+
+/// What an open feature would score once completed, as it stands
+fn completed_points(board: &Board, region: &ConnectedRegion) -> u32 {
+    match region.region_type {
+        RegionType::City | RegionType::Road => {
+            let mut completed = region.clone();
+            completed.open_edge_count = 0;
+
+            completed.score(board)
+        }
+        // the cloister and the eight tiles around it
+        RegionType::Cloister => 9,
+        RegionType::Field | RegionType::Water => 0,
+    }
+}
+
+/// An open city, road or cloister a player has meeple in, and how likely it is to be completed
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenFeatureRisk {
+    /// The feature, see [Board::connected_region]
+    pub region: ConnectedRegionId,
+    pub region_type: RegionType,
+    /// The player's meeple in the feature, which stay there until it is completed
+    pub meeple: usize,
+    /// Chance the tiles left complete the feature, nothing if one of its gaps can't be filled by
+    /// any of them
+    pub completion_probability: f64,
+}
+
+impl OpenFeatureRisk {
+    /// How many of the player's meeple can be expected to be stranded in the feature
+    pub fn stranded_meeple(&self) -> f64 {
+        self.meeple as f64 * (1.0 - self.completion_probability)
+    }
+}
+
+/// Each player's meeple in open cities, roads and cloisters, with the chance the tiles in
+/// `deck_remaining` complete each feature and free them. Players are in seat order, and their
+/// features least likely to be completed first. Farmers are left out, as fields are never completed
+pub fn open_feature_risk(board: &Board, deck_remaining: &[&'static TileDefinition]) -> IndexMap<PlayerIdentifier, Vec<OpenFeatureRisk>> {
+    let mut risks: IndexMap<PlayerIdentifier, Vec<OpenFeatureRisk>> = IndexMap::new();
+
+    for region in board.get_connected_regions() {
+        if region.is_closed() || !matches!(region.region_type, RegionType::City | RegionType::Road | RegionType::Cloister) {
+            continue;
+        }
+
+        let residents = region.residents(board);
+
+        if residents.is_empty() {
+            continue;
+        }
+
+        let completion_probability = match can_be_completed(board, region, deck_remaining) {
+            true => completion_probability(board, region, deck_remaining),
+            false => 0.0,
+        };
+
+        let mut meeple: IndexMap<PlayerIdentifier, usize> = IndexMap::new();
+
+        for (_, _, resident) in residents {
+            *meeple.entry(resident.color).or_default() += 1;
+        }
+
+        for (player, meeple) in meeple {
+            risks.entry(player).or_default().push(OpenFeatureRisk { region: region.id, region_type: region.region_type.clone(), meeple, completion_probability });
+        }
+    }
+
+    risks.sort_by(|player, _, other, _| player.seat().cmp(&other.seat()));
+
+    for features in risks.values_mut() {
+        // ids break ties, as the board's regions come in no set order
+        features.sort_by(|a, b| a.completion_probability.total_cmp(&b.completion_probability).then(a.region.cmp(&b.region)));
+    }
+
+    risks
+}
+
+/// What one meeple in hand is worth with `tiles_remaining` tiles left to play it on
+pub(crate) fn meeple_in_hand_value(tiles_remaining: usize) -> f64 {
+    MEEPLE_IN_HAND_VALUE * (tiles_remaining.min(LATE_GAME_TILES) as f64 / LATE_GAME_TILES as f64)
+}
+
+/// The farm points `player` can expect at the end of the game: what the fields they hold score
+/// now, and three for each open city bordering them, weighted by the chance `deck_remaining`
+/// completes it. With nothing left to deal, the points their farms will score
+pub fn farm_equity(board: &Board, player: PlayerIdentifier, deck_remaining: &[&'static TileDefinition]) -> f64 {
+    board
+        .get_connected_regions()
+        .into_iter()
+        .filter(|region| region.region_type == RegionType::Field && region.majority_meeple_player_ids(board).contains(&player))
+        .map(|field| field.score(board) as f64 + field_equity(board, field, deck_remaining))
+        // summing nothing gives -0.0, which prints with its sign
+        .fold(0.0, |total, equity| total + equity)
+}
+
+/// The extra points a field's farmers expect from the open cities bordering it being completed
+pub(crate) fn field_equity(board: &Board, field: &ConnectedRegion, deck_remaining: &[&'static TileDefinition]) -> f64 {
+    board
+        .adjacent_regions(field)
+        .into_iter()
+        .filter(|city| city.region_type == RegionType::City && !city.is_closed())
+        .map(|city| 3.0 * completion_probability(board, city, deck_remaining))
+        .sum()
+}
+
+/// Rough chance an open feature is completed before the deck runs out. Each tile still needed must
+/// be one that can continue the feature, and there must be enough turns left to place them all
+fn completion_probability(board: &Board, region: &ConnectedRegion, deck_remaining: &[&'static TileDefinition]) -> f64 {
+    let (tiles_needed, supply) = match region.region_type {
+        RegionType::Cloister => {
+            let coordinate = region.tile_regions[0].tile_position;
+
+            (8 - board.list_surrounding_tiles(&coordinate).len(), deck_remaining.len())
+        }
+        _ => {
+            let tiles_needed = region
+                .connected_edges
+                .iter()
+                .filter(|(_, connection)| connection.is_none())
+                .map(|(edge, _)| edge.opposing_tile_edge(board.topology()).coordinate)
+                .collect::<HashSet<_>>()
+                .len();
+
+            let supply = deck_remaining
+                .iter()
+                .filter(|tile| tile.list_oriented_region_types(Rotation::R0).contains(&region.region_type))
+                .count();
+
+            (tiles_needed, supply)
+        }
+    };
+
+    if tiles_needed == 0 {
+        return 1.0;
+    }
+
+    if deck_remaining.is_empty() {
+        return 0.0;
+    }
+
+    let remaining = deck_remaining.len() as f64;
+    let per_tile = supply as f64 / remaining;
+
+    per_tile.powi(tiles_needed as i32) * remaining / (remaining + tiles_needed as f64)
+}
+
+/// Whether every gap in an open feature could still be filled by one of the tiles in
+/// `deck_remaining`. Gaps are checked one at a time, so this doesn't rule out a feature whose gaps
+/// all need the last copy of the same tile
+pub(crate) fn can_be_completed(board: &Board, region: &ConnectedRegion, deck_remaining: &[&'static TileDefinition]) -> bool {
+    let gaps: HashSet<_> = region
+        .connected_edges
+        .iter()
+        .filter(|(_, connection)| connection.is_none())
+        .map(|(edge, _)| edge.opposing_tile_edge(board.topology()).coordinate)
+        .collect();
+
+    let mut tiles: Vec<&'static TileDefinition> = deck_remaining.to_vec();
+    tiles.dedup_by_key(|tile| tile.name);
+
+    gaps.iter().all(|gap| {
+        tiles.iter().any(|tile| {
+            tile.distinct_rotations().iter().any(|rotations| board.validate_tile_placement(&PlacedTile::new(tile, gap.x, gap.y, *rotations)).is_ok())
+        })
+    })
+}
+
+/// Turns likely left in a game with `deck_remaining` still to be dealt: every tile, less those
+/// whose edges fit nowhere on the frontier of the board as it is. Tiles that can't be placed are
+/// set aside when drawn, so aren't turns, though some may find a place as the board grows
+pub fn turns_remaining_estimate(board: &Board, deck_remaining: &[&'static TileDefinition]) -> usize {
+    let mut fits: HashMap<&'static str, bool> = HashMap::new();
+
+    deck_remaining.iter().filter(|tile| *fits.entry(tile.name).or_insert_with(|| board.fits_frontier(tile))).count()
+}
+
+/// Base game tiles not yet on the board; all a player can know about what is left in the deck
+pub fn unseen_tiles(board: &Board) -> Vec<&'static TileDefinition> {
+    ALL_TILE_DEFINITIONS
+        .iter()
+        .filter(|definition| definition.expansion.is_none())
+        .flat_map(|definition| {
+            let placed = board.placed_tiles.values().filter(|tile| tile.tile.name == definition.name).count();
+
+            std::iter::repeat_n(definition, (definition.count as usize).saturating_sub(placed))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::{ReplayPlayer, ReplayRules, ReplayTurn};
+    use crate::player::MeepleColor;
+    use crate::test_util::TestPlayer;
+    use crate::tile_definitions::{CENTRE_CITY_WITH_PENNANT, SIDE_CITY, STRAIGHT_ROAD};
+
+    fn turn(player: PlayerIdentifier, x: i8, y: i8, rotation: Rotation, meeple: Option<usize>) -> ReplayTurn {
+        ReplayTurn {
+            player,
+            tile: "Side city".to_string(),
+            placement: Some(ReplayPlacement { x, y, rotation, meeple, recalled: None }),
+        }
+    }
+
+    fn side_city_replay(third_turn: ReplayTurn) -> Replay {
+        Replay {
+            seed: String::new(),
+            deck: vec![],
+            players: vec![
+                ReplayPlayer { color: MeepleColor::Red, name: Some("Alice".to_string()), bot: None },
+                ReplayPlayer { color: MeepleColor::Green, name: Some("Bob".to_string()), bot: None },
+            ],
+            turns: vec![
+                // Alice claims a city opening to the south
+                turn(MeepleColor::Red, 0, 0, Rotation::R0, Some(1)),
+                turn(MeepleColor::Green, 1, 0, Rotation::R0, None),
+                third_turn,
+            ],
+            final_score: vec![],
+            handovers: vec![],
+            rules: ReplayRules::default(),
+        }
+    }
+
+    #[test]
+    fn should_flag_a_move_that_ignores_closing_your_own_city() {
+        let replay = side_city_replay(turn(MeepleColor::Red, -1, 0, Rotation::R0, None));
+
+        let report = find_blunders(&replay, 2).unwrap();
+
+        let blunders: Vec<_> = report.blunders_by(MeepleColor::Red).collect();
+
+        assert_eq!(blunders.len(), 1);
+        assert_eq!(blunders[0].turn, 2);
+        assert!(blunders[0].cost() > 2);
+        assert_eq!(report.moves[&MeepleColor::Red], 2);
+    }
+
+    #[test]
+    fn should_count_drawing_the_tile_that_closes_your_city_as_lucky() {
+        let mut alice = Player::red();
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+        let opponents = [MeepleColor::Green];
+
+        assert!(draw_luck(&board, &alice, &opponents, &SIDE_CITY) > 0.0);
+        assert!(draw_luck(&board, &alice, &opponents, &STRAIGHT_ROAD) < 0.0);
+
+        let luck = luck(&side_city_replay(turn(MeepleColor::Red, 0, 1, Rotation::R180, Some(0)))).unwrap();
+
+        assert_eq!(luck.keys().copied().collect::<Vec<_>>(), vec![MeepleColor::Red, MeepleColor::Green]);
+        assert!(luck[&MeepleColor::Red] > 0.0);
+    }
+
+    #[test]
+    fn should_expect_farm_points_from_the_cities_likely_to_be_completed() {
+        let mut alice = Player::red();
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0)]).unwrap();
+
+        // nothing left to close the city with
+        assert_eq!(farm_equity(&board, MeepleColor::Red, &[]), 0.0);
+
+        let expected = farm_equity(&board, MeepleColor::Red, &[&SIDE_CITY]);
+        assert!(expected > 0.0 && expected <= 3.0);
+        assert_eq!(farm_equity(&board, MeepleColor::Green, &[&SIDE_CITY]), 0.0);
+
+        let closed = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0), alice.move_no_meeple(&SIDE_CITY, 0, 1, Rotation::R180)]).unwrap();
+
+        assert_eq!(farm_equity(&closed, MeepleColor::Red, &[]), 3.0);
+    }
+
+    #[test]
+    fn should_expect_no_farm_points_for_a_player_without_fields() {
+        let mut alice = Player::red();
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+
+        let equity = farm_equity(&board, MeepleColor::Green, &[&SIDE_CITY]);
+
+        assert_eq!(format!("{:.1}", equity), "0.0");
+        assert!(equity.is_sign_positive());
+    }
+
+    #[test]
+    fn should_list_open_features_by_how_likely_they_are_to_be_stranded() {
+        let mut alice = Player::red();
+        let mut bob = Player::green();
+
+        let board = Board::new_with_tiles([
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1),
+            bob.move_with_meeple(&STRAIGHT_ROAD, 1, 0, Rotation::R0, 0),
+        ])
+        .unwrap();
+
+        let risk = open_feature_risk(&board, &[&SIDE_CITY, &STRAIGHT_ROAD]);
+
+        assert_eq!(risk.keys().copied().collect::<Vec<_>>(), vec![MeepleColor::Red, MeepleColor::Green]);
+        assert_eq!(risk[&MeepleColor::Red].len(), 1);
+        assert_eq!(risk[&MeepleColor::Red][0].region_type, RegionType::City);
+        assert!(risk[&MeepleColor::Red][0].completion_probability > 0.0);
+
+        // with no city tiles left, the city can't be completed
+        let risk = open_feature_risk(&board, &[&STRAIGHT_ROAD]);
+
+        assert_eq!(risk[&MeepleColor::Red][0].completion_probability, 0.0);
+        assert_eq!(risk[&MeepleColor::Red][0].stranded_meeple(), 1.0);
+    }
+
+    #[test]
+    fn should_not_flag_the_best_move() {
+        // closing the city and farming the field beside it
+        let replay = side_city_replay(turn(MeepleColor::Red, 0, 1, Rotation::R180, Some(0)));
+
+        let report = find_blunders(&replay, 0).unwrap();
+
+        assert_eq!(report.blunders_by(MeepleColor::Red).count(), 0);
+        assert_eq!(report.points_lost(MeepleColor::Red), 0);
+    }
+
+    #[test]
+    fn should_branch_with_the_same_remaining_tiles() {
+        let mut replay = side_city_replay(turn(MeepleColor::Red, -1, 0, Rotation::R0, None));
+        replay.turns.push(turn(MeepleColor::Green, 2, 0, Rotation::R0, None));
+
+        let branched = branch(&replay, 2, ReplayPlacement { x: 0, y: 1, rotation: Rotation::R180, meeple: Some(0), recalled: None }).unwrap();
+
+        assert_eq!(branched.turns[..2], replay.turns[..2]);
+        assert_eq!(branched.turns[2].placement, Some(ReplayPlacement { x: 0, y: 1, rotation: Rotation::R180, meeple: Some(0), recalled: None }));
+        assert_eq!(
+            branched.turns.iter().map(|t| &t.tile).collect::<Vec<_>>(),
+            replay.turns.iter().map(|t| &t.tile).collect::<Vec<_>>()
+        );
+
+        // the closed city is worth 4, and the farmer beside it at least 3
+        assert!(branched.final_score().iter().any(|(id, points)| *id == MeepleColor::Red && *points >= 7));
+        assert!(branched.turns[3].placement.is_some());
+        assert!(branched.state_at(branched.turns.len()).is_ok());
+    }
+
+    #[test]
+    fn should_reject_an_illegal_alternative_move() {
+        let replay = side_city_replay(turn(MeepleColor::Red, -1, 0, Rotation::R0, None));
+
+        let result = branch(&replay, 2, ReplayPlacement { x: 5, y: 5, rotation: Rotation::R0, meeple: None, recalled: None });
+
+        assert!(matches!(result, Err(ReplayError::InvalidPlacement { turn: 2, .. })));
+        assert!(matches!(branch(&replay, 3, ReplayPlacement { x: 0, y: 1, rotation: Rotation::R180, meeple: None, recalled: None }), Err(ReplayError::TurnOutOfRange(3))));
+    }
+
+    #[test]
+    fn should_value_an_empty_board_by_meeple_in_hand() {
+        let board = Board::new();
+        let deck = unseen_tiles(&board);
+
+        assert_eq!(evaluate(&board, &Player::red(), &deck), 7.0 * MEEPLE_IN_HAND_VALUE);
+        assert_eq!(evaluate(&board, &Player::red(), &[]), 0.0);
+    }
+
+    #[test]
+    fn should_value_an_open_city_by_its_chance_of_completion() {
+        let state = side_city_replay(turn(MeepleColor::Red, -1, 0, Rotation::R0, None)).state_at(1).unwrap();
+        let red = &state.players[&MeepleColor::Red];
+        let green = &state.players[&MeepleColor::Green];
+
+        let deck = unseen_tiles(&state.board);
+        let late_deck = &deck[..3];
+
+        let early = evaluate(&state.board, red, &deck) - 6.0 * MEEPLE_IN_HAND_VALUE;
+        let late = evaluate(&state.board, red, late_deck) - 6.0 * MEEPLE_IN_HAND_VALUE * 3.0 / 12.0;
+
+        // one point for the open city, plus up to one more if it is completed
+        assert!(early > late, "{} should be more than {}", early, late);
+        assert!(late > 1.0 && early < 2.0);
+        assert_eq!(evaluate(&state.board, red, &[]), 1.0);
+
+        // only the player's own features count
+        assert_eq!(evaluate(&state.board, green, &[]), 0.0);
+    }
+
+    #[test]
+    fn should_not_count_placed_tiles_as_unseen() {
+        let state = side_city_replay(turn(MeepleColor::Red, -1, 0, Rotation::R0, None)).state_at(3).unwrap();
+        let board = Board::new();
+
+        assert_eq!(unseen_tiles(&board).len() - unseen_tiles(&state.board).len(), 3);
+        assert!(unseen_tiles(&board).iter().all(|tile| tile.expansion.is_none()));
+    }
+
+    #[test]
+    fn should_reject_an_illegal_recorded_move() {
+        let replay = side_city_replay(turn(MeepleColor::Red, 5, 5, Rotation::R0, None));
+
+        assert!(matches!(find_blunders(&replay, 0), Err(ReplayError::InvalidPlacement { turn: 2, .. })));
+    }
+
+    #[test]
+    fn should_leave_tiles_that_fit_nowhere_out_of_the_turns_remaining() {
+        let board = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+        let deck = [&CENTRE_CITY_WITH_PENNANT, &SIDE_CITY, &STRAIGHT_ROAD, &SIDE_CITY];
+
+        assert_eq!(turns_remaining_estimate(&board, &deck), 3);
+        assert_eq!(turns_remaining_estimate(&Board::new(), &deck), 4);
+    }
+}
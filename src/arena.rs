@@ -0,0 +1,296 @@
+use crate::bot_strategy::Bot;
+use crate::crash_report::{self, SuspendedGame};
+use crate::controller::{GameView, PlayerAction, PlayerController};
+use crate::game_logic::{DeckSource, GamePhase, GameRun, MAX_HAND_OVERS};
+use crate::move_hints::MoveHint;
+use crate::player::{Player, PlayerIdentifier};
+use crate::rules::RuleSet;
+use crate::simulate::{GameResult, Seed};
+use crate::tile::{Expansion, TileDefinition};
+use indexmap::IndexMap;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+/// One game's turn in a batch handed to a [BatchPolicy]
+pub struct ArenaTurn<'a> {
+    /// Which of the arena's games this is
+    pub game_index: usize,
+    pub view: GameView<'a>,
+    pub hints: Vec<MoveHint>,
+}
+
+/// Makes the moves for every player in an [Arena], a batch of games at a time, so e.g. a learned
+/// evaluation can score all of their positions in one go
+pub trait BatchPolicy {
+    /// Picks a move for each of `turns`, which all drew `tile`, in the same order. Games left
+    /// without a move pass. A [PlayerAction::HandOver] gives that seat of that game to the
+    /// controller, which makes its moves from then on, as in a normal game
+    fn choose_moves(&mut self, tile: &'static TileDefinition, turns: &[ArenaTurn]) -> Vec<PlayerAction>;
+}
+
+/// Any bot can play in an arena, choosing each game's move in turn
+impl<B: Bot> BatchPolicy for B {
+    fn choose_moves(&mut self, _tile: &'static TileDefinition, turns: &[ArenaTurn]) -> Vec<PlayerAction> {
+        turns
+            .iter()
+            .map(|turn| match self.select_hint(turn.view.board, turn.view.player, &turn.hints) {
+                Some(hint) => PlayerAction::Place(hint),
+                None => PlayerAction::Pass,
+            })
+            .collect()
+    }
+}
+
+/// Many independent games between the same players, played in lock step: every game takes its
+/// next turn before any takes the one after. Each step the games are grouped by the tile they
+/// drew, their move hints are found in parallel, and the policy is asked for the whole group's
+/// moves at once, which suits self-play far better than playing whole games one after another
+pub struct Arena {
+    games: Vec<Option<GameRun<'static>>>,
+    results: Vec<Option<GameResult>>,
+    /// The controllers the policy has handed seats over to, for each game
+    stand_ins: Vec<HashMap<PlayerIdentifier, Box<dyn PlayerController>>>,
+    /// Each game's moves so far, for a crash report should one of its turns panic
+    tracked: Vec<SuspendedGame>,
+}
+
+impl Arena {
+    /// A game for each seed, each played by `players` with the standard rules
+    pub fn new<S: IntoIterator<Item = Seed>>(players: IndexMap<PlayerIdentifier, Player>, seeds: S, expansions: Vec<Expansion>) -> Self {
+        let rules = RuleSet::standard();
+
+        let players: IndexMap<_, _> = players.into_iter().map(|(id, player)| (id, player.with_meeple_count(rules.meeple_per_player))).collect();
+
+        let (games, tracked): (Vec<_>, Vec<_>) = seeds
+            .into_iter()
+            .enumerate()
+            .map(|(game_index, seed)| {
+                let deck = DeckSource::Shuffled(expansions.clone());
+                let game = GameRun::start(players.clone(), |_| None, rules.clone(), seed, deck, game_index, &mut []).without_player_events();
+                let tracked = crash_report::suspended(game_index, seed, &game.board);
+
+                (Some(game), tracked)
+            })
+            .unzip();
+
+        let results = games.iter().map(|_| None).collect();
+        let stand_ins = games.iter().map(|_| HashMap::new()).collect();
+
+        Self { games, results, stand_ins, tracked }
+    }
+
+    /// Games still being played
+    pub fn active(&self) -> usize {
+        self.games.iter().filter(|game| game.is_some()).count()
+    }
+
+    /// Plays the next turn of every game still going, returning whether any are left
+    pub fn step<P: BatchPolicy>(&mut self, policy: &mut P) -> bool {
+        let mut draws: Vec<(&'static TileDefinition, Vec<(usize, PlayerIdentifier)>)> = vec![];
+
+        for (game_index, ((slot, stand_ins), tracked)) in self.games.iter_mut().zip(&mut self.stand_ins).zip(&mut self.tracked).enumerate() {
+            let Some(game) = slot else {
+                continue;
+            };
+
+            match tracked.resume(|| game.draw()) {
+                // seats that have been handed over are played by their controllers, not the policy
+                Some((player_id, tile)) if stand_ins.contains_key(&player_id) => tracked.resume(|| {
+                    let action = stand_in_move(game, stand_ins, player_id, tile);
+                    game.apply(player_id, tile, action);
+                }),
+                Some((player_id, tile)) => match draws.iter_mut().find(|(drawn, _)| drawn.name == tile.name) {
+                    Some((_, games)) => games.push((game_index, player_id)),
+                    None => draws.push((tile, vec![(game_index, player_id)])),
+                },
+                None => {
+                    let mut game = slot.take().expect("game should still be going");
+                    self.results[game_index] = Some(game.finish(vec![]));
+                }
+            }
+        }
+
+        for (tile, group) in draws {
+            let actions = self.choose_moves(policy, tile, &group);
+
+            for ((game_index, player_id), (action, hint_count)) in group.into_iter().zip(actions) {
+                let game = self.games[game_index].as_mut().expect("game should still be going");
+                let stand_ins = &mut self.stand_ins[game_index];
+                game.set_offered(hint_count);
+
+                self.tracked[game_index].resume(|| {
+                    let action = match action {
+                        PlayerAction::HandOver(controller) => {
+                            stand_ins.insert(player_id, controller);
+                            stand_in_move(game, stand_ins, player_id, tile)
+                        }
+                        action => action,
+                    };
+
+                    game.apply(player_id, tile, action);
+                });
+            }
+        }
+
+        self.active() > 0
+    }
+
+    /// The policy's moves for a group of games that drew `tile`, one for each game, with how many
+    /// moves each game offered
+    fn choose_moves<P: BatchPolicy>(&self, policy: &mut P, tile: &'static TileDefinition, group: &[(usize, PlayerIdentifier)]) -> Vec<(PlayerAction, usize)> {
+        let games: Vec<_> = group
+            .iter()
+            .map(|(game_index, player_id)| (*game_index, self.games[*game_index].as_ref().expect("game should still be going"), *player_id))
+            .collect();
+
+        let boards: Vec<_> = games.iter().map(|(_, game, _)| game.board.read().unwrap()).collect();
+        let has_meeple: Vec<bool> = games.iter().map(|(_, game, player_id)| !game.players[player_id].meeple.is_empty()).collect();
+
+        // meeple placements are only offered to players with a meeple to place
+        let hints: Vec<Vec<MoveHint>> = boards.par_iter().zip(has_meeple).map(|(board, has_meeple)| board.get_move_hints(tile, has_meeple)).collect();
+
+        let hint_counts: Vec<usize> = hints.iter().map(Vec::len).collect();
+
+        let turns: Vec<ArenaTurn> = games
+            .iter()
+            .zip(&boards)
+            .zip(hints)
+            .map(|(((game_index, game, player_id), board), hints)| ArenaTurn {
+                game_index: *game_index,
+                view: GameView { board, player: &game.players[player_id], players: &game.players, tile, score: &game.score, phase: GamePhase::of(tile) },
+                hints,
+            })
+            .collect();
+
+        // as in a normal game, players with no legal move pass without being asked
+        let (playable, _): (Vec<(usize, ArenaTurn)>, Vec<_>) = turns.into_iter().enumerate().partition(|(_, turn)| !turn.hints.is_empty());
+        let (indices, playable): (Vec<usize>, Vec<ArenaTurn>) = playable.into_iter().unzip();
+
+        let mut actions: Vec<PlayerAction> = group.iter().map(|_| PlayerAction::Pass).collect();
+
+        for (index, action) in indices.into_iter().zip(policy.choose_moves(tile, &playable)) {
+            actions[index] = action;
+        }
+
+        actions.into_iter().zip(hint_counts).collect()
+    }
+
+    /// Plays every game to the end
+    pub fn play<P: BatchPolicy>(mut self, policy: &mut P) -> Vec<GameResult> {
+        while self.step(policy) {}
+
+        self.results.into_iter().map(|result| result.expect("every game should have finished")).collect()
+    }
+}
+
+/// The move of the controller the seat was handed over to, or of whoever it hands the seat on to.
+/// Passes once the seat has changed hands [MAX_HAND_OVERS] times in the turn, as in a normal game
+fn stand_in_move(
+    game: &mut GameRun,
+    stand_ins: &mut HashMap<PlayerIdentifier, Box<dyn PlayerController>>,
+    player_id: PlayerIdentifier,
+    tile: &'static TileDefinition,
+) -> PlayerAction {
+    for _ in 0..MAX_HAND_OVERS {
+        let controller = stand_ins.get_mut(&player_id).expect("seat should have been handed over");
+
+        match game.with_view(player_id, tile, |view, hints| controller.choose_move(view, hints)) {
+            PlayerAction::HandOver(controller) => {
+                stand_ins.insert(player_id, controller);
+            }
+            action => return action,
+        }
+    }
+
+    PlayerAction::Pass
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+
+    /// Counts how many games it is asked about at once
+    #[derive(Default)]
+    struct BatchSizes(Vec<usize>);
+
+    impl BatchPolicy for BatchSizes {
+        fn choose_moves(&mut self, _tile: &'static TileDefinition, turns: &[ArenaTurn]) -> Vec<PlayerAction> {
+            self.0.push(turns.len());
+            turns.iter().map(|turn| PlayerAction::Place(turn.hints[0].clone())).collect()
+        }
+    }
+
+    /// Hands every seat it is asked about over to `stand_in`, counting how many times it is asked
+    struct HandsOver<C: PlayerController + Clone + 'static> {
+        stand_in: C,
+        asked: usize,
+    }
+
+    impl<C: PlayerController + Clone + 'static> BatchPolicy for HandsOver<C> {
+        fn choose_moves(&mut self, _tile: &'static TileDefinition, turns: &[ArenaTurn]) -> Vec<PlayerAction> {
+            self.asked += turns.len();
+            turns.iter().map(|_| PlayerAction::HandOver(Box::new(self.stand_in.clone()))).collect()
+        }
+    }
+
+    /// Never makes a move, handing the seat on to another of itself instead
+    #[derive(Clone)]
+    struct HandsItOn;
+
+    impl PlayerController for HandsItOn {
+        fn choose_move(&mut self, _view: &GameView, _hints: &[MoveHint]) -> PlayerAction {
+            PlayerAction::HandOver(Box::new(HandsItOn))
+        }
+    }
+
+    fn players() -> IndexMap<PlayerIdentifier, Player> {
+        [Player::red(), Player::green()].into_iter().map(|player| (player.meeple_color, player)).collect()
+    }
+
+    #[test]
+    fn should_play_every_game_to_the_end() {
+        let results = Arena::new(players(), (0..4).map(|seed| [seed; 32]), vec![]).play(&mut LazyBot);
+
+        assert_eq!(results.len(), 4);
+
+        for (seed, result) in results.iter().enumerate() {
+            assert_eq!(result.seed, [seed as u8; 32]);
+            assert!(result.placed_tile_count > 60);
+        }
+    }
+
+    #[test]
+    fn should_ask_for_games_that_drew_the_same_tile_together() {
+        // every game draws the same tiles in the same order
+        let mut arena = Arena::new(players(), [[7; 32]; 3], vec![]);
+        let mut policy = BatchSizes::default();
+
+        assert!(arena.step(&mut policy));
+        assert_eq!(policy.0, vec![3]);
+
+        let results = arena.play(&mut policy);
+
+        assert!(policy.0.iter().all(|size| *size <= 3));
+        assert_eq!(results.len(), 3);
+    }
+
+    #[test]
+    fn should_leave_a_handed_over_seat_to_its_new_controller() {
+        let mut policy = HandsOver { stand_in: BotStrategy::Lazy(LazyBot), asked: 0 };
+        let results = Arena::new(players(), [[7; 32], [8; 32]], vec![]).play(&mut policy);
+
+        // each seat of each game is asked about once, and the bot plays every turn after
+        assert_eq!(policy.asked, 4);
+        assert!(results.iter().all(|result| result.placed_tile_count > 60));
+    }
+
+    #[test]
+    fn should_pass_the_turn_of_a_seat_handed_over_endlessly() {
+        let mut policy = HandsOver { stand_in: HandsItOn, asked: 0 };
+        let results = Arena::new(players(), [[7; 32]], vec![]).play(&mut policy);
+
+        assert_eq!(policy.asked, 2);
+        assert_eq!(results[0].placed_tile_count, 0);
+    }
+}
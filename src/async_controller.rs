@@ -0,0 +1,142 @@
+use crate::controller::{GameView, PlayerAction, PlayerController};
+use crate::move_hints::MoveHint;
+use crate::observer::GameEvent;
+use std::future::{self, Future};
+use std::pin::Pin;
+use std::time::Duration;
+
+/// A move being decided; see [AsyncPlayerController::choose_move]
+pub type MoveFuture<'a> = Pin<Box<dyn Future<Output = PlayerAction> + 'a>>;
+
+/// As [PlayerController], for players whose moves take a while to arrive (e.g. over the network,
+/// or from an external bot process) so the game can await them rather than block on them
+pub trait AsyncPlayerController {
+    fn choose_move<'a>(&'a mut self, view: &'a GameView<'a>, hints: &'a [MoveHint]) -> MoveFuture<'a>;
+
+    /// Name of the bot making the moves, if this is a bot
+    fn bot_name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Called with each event of the game; see [PlayerController::observe]
+    fn observe(&mut self, _event: &GameEvent) {}
+}
+
+/// Adapts a synchronous controller, such as a local bot, to the async interface
+pub struct SyncAdapter<C>(pub C);
+
+impl<C: PlayerController> AsyncPlayerController for SyncAdapter<C> {
+    fn choose_move<'a>(&'a mut self, view: &'a GameView<'a>, hints: &'a [MoveHint]) -> MoveFuture<'a> {
+        Box::pin(future::ready(self.0.choose_move(view, hints)))
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        self.0.bot_name()
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        self.0.observe(event);
+    }
+}
+
+/// Gives a player a time limit for each move, passing on their behalf if they run out of time
+pub struct TimeLimit<C> {
+    controller: C,
+    limit: Duration,
+}
+
+impl<C: AsyncPlayerController> TimeLimit<C> {
+    pub fn new(controller: C, limit: Duration) -> Self {
+        Self { controller, limit }
+    }
+}
+
+impl<C: AsyncPlayerController> AsyncPlayerController for TimeLimit<C> {
+    fn choose_move<'a>(&'a mut self, view: &'a GameView<'a>, hints: &'a [MoveHint]) -> MoveFuture<'a> {
+        let limit = self.limit;
+
+        Box::pin(async move {
+            tokio::time::timeout(limit, self.controller.choose_move(view, hints))
+                .await
+                .unwrap_or(PlayerAction::Pass)
+        })
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        self.controller.bot_name()
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        self.controller.observe(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+    use crate::game_logic::Game;
+    use crate::observer::{GameEvent, GameObserver};
+    use crate::player::Player;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Thinks for a while before making the lazy bot's move
+    struct SlowBot(Duration);
+
+    impl AsyncPlayerController for SlowBot {
+        fn choose_move<'a>(&'a mut self, view: &'a GameView<'a>, hints: &'a [MoveHint]) -> MoveFuture<'a> {
+            Box::pin(async move {
+                tokio::time::sleep(self.0).await;
+                BotStrategy::Lazy(LazyBot).choose_move(view, hints)
+            })
+        }
+    }
+
+    /// Counts the tiles each game's players passed on
+    struct PassCounter(Rc<RefCell<usize>>);
+
+    impl GameObserver for PassCounter {
+        fn notify(&mut self, event: &GameEvent) {
+            if let GameEvent::TurnCompleted { placement: None, .. } = event {
+                *self.0.borrow_mut() += 1;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn should_await_async_players() {
+        let passes = Rc::new(RefCell::new(0));
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(PassCounter(passes.clone()))];
+
+        let result = Game::builder()
+            .with_async_player(Player::red(), SlowBot(Duration::from_micros(1)))
+            .with_async_player(Player::green(), SyncAdapter(BotStrategy::Lazy(LazyBot)))
+            .with_seed([5; 32])
+            .build()
+            .unwrap()
+            .play_async(0, &mut observers)
+            .await;
+
+        assert!(result.placed_tile_count > 0);
+        assert_eq!(*passes.borrow(), 0);
+    }
+
+    #[test]
+    fn should_pass_for_players_who_run_out_of_time() {
+        let passes = Rc::new(RefCell::new(0));
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(PassCounter(passes.clone()))];
+
+        // played from sync code, which starts a runtime for the async player
+        let result = Game::builder()
+            .with_async_player(Player::red(), TimeLimit::new(SlowBot(Duration::from_secs(10)), Duration::from_millis(1)))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_seed([5; 32])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut observers);
+
+        assert!(*passes.borrow() > 0);
+        assert!(result.placed_tile_count > 0);
+    }
+}
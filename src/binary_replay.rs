@@ -0,0 +1,466 @@
+use crate::player::{MeepleColor, PlayerIdentifier};
+use crate::replay::{Replay, ReplayError, ReplayHandover, ReplayPlacement, ReplayPlayer, ReplayRecall, ReplayRules, ReplayScore, ReplayTurn};
+use crate::tile::Rotation;
+use crate::tile_definitions;
+
+/// Every binary replay starts with these bytes, followed by the format version
+pub(crate) const MAGIC: &[u8; 4] = b"CRPL";
+pub const BINARY_FORMAT_VERSION: u8 = 6;
+/// The first version, before the deck order was recorded, which can still be read
+const DECKLESS_FORMAT_VERSION: u8 = 1;
+/// The last version before the tile set hash was recorded, read on trust that the tile ids match
+const UNHASHED_FORMAT_VERSION: u8 = 2;
+/// The last version before hand overs between controllers were recorded
+const HANDOVERLESS_FORMAT_VERSION: u8 = 4;
+/// The last version before the rules were recorded, read as played under the standard rules
+const RULELESS_FORMAT_VERSION: u8 = 5;
+
+const PLACED: u8 = 0b0001;
+const WITH_MEEPLE: u8 = 0b0010;
+const ROTATION_SHIFT: u8 = 2;
+const ROTATION_MASK: u8 = 0b11;
+/// Above the rotation, from version 4
+const WITH_RECALL: u8 = 0b1_0000;
+
+impl Replay {
+    /// Encodes the replay in the compact binary format: tiles as their [crate::tile::TileId],
+    /// coordinates and scores as variable length integers, behind a versioned header. A full game
+    /// takes a few hundred bytes, against tens of kilobytes as JSON
+    pub fn to_binary(&self) -> Result<Vec<u8>, ReplayError> {
+        let mut writer = Writer(Vec::with_capacity(64 + self.turns.len() * 4));
+
+        writer.0.extend_from_slice(MAGIC);
+        writer.0.push(BINARY_FORMAT_VERSION);
+        writer.string(&tile_definitions::tile_set_hash());
+
+        writer.string(&self.seed);
+
+        writer.unsigned(self.deck.len() as u64);
+        for tile in &self.deck {
+            writer.0.push(tile_definitions::find_by_name(tile).ok_or_else(|| ReplayError::UnknownTile(tile.clone()))?.id());
+        }
+
+        writer.unsigned(self.players.len() as u64);
+        for player in &self.players {
+            writer.0.push(player.color.to_byte());
+            writer.optional_string(player.name.as_deref());
+            writer.optional_string(player.bot.as_deref());
+        }
+
+        writer.unsigned(self.turns.len() as u64);
+        for turn in &self.turns {
+            writer.0.push(turn.player.to_byte());
+            writer.0.push(turn.tile()?.id());
+
+            match &turn.placement {
+                None => writer.0.push(0),
+                Some(placement) => {
+                    let meeple_flag = if placement.meeple.is_some() { WITH_MEEPLE } else { 0 };
+                    let recall_flag = if placement.recalled.is_some() { WITH_RECALL } else { 0 };
+
+                    writer.0.push(PLACED | meeple_flag | recall_flag | (placement.rotation.quarter_turns() << ROTATION_SHIFT));
+                    writer.signed(placement.x as i64);
+                    writer.signed(placement.y as i64);
+
+                    if let Some(region_index) = placement.meeple {
+                        writer.unsigned(region_index as u64);
+                    }
+
+                    if let Some(recall) = &placement.recalled {
+                        writer.signed(recall.x as i64);
+                        writer.signed(recall.y as i64);
+                        writer.unsigned(recall.meeple as u64);
+                    }
+                }
+            }
+        }
+
+        writer.unsigned(self.final_score.len() as u64);
+        for score in &self.final_score {
+            writer.0.push(score.player.to_byte());
+            writer.signed(score.points as i64);
+        }
+
+        writer.unsigned(self.handovers.len() as u64);
+        for handover in &self.handovers {
+            writer.unsigned(handover.turn as u64);
+            writer.0.push(handover.player.to_byte());
+            writer.optional_string(handover.bot.as_deref());
+        }
+
+        // rules are rarely anything but the standard ones, so are left as JSON rather than packed
+        let rules = (!self.rules.is_standard()).then(|| serde_json::to_string(&self.rules).expect("rules should serialize"));
+        writer.optional_string(rules.as_deref());
+
+        Ok(writer.0)
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, ReplayError> {
+        if !is_binary(bytes) {
+            return Err(ReplayError::InvalidBinary("missing header"));
+        }
+
+        let version = bytes[MAGIC.len()];
+
+        if version == 0 || version > BINARY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(version));
+        }
+
+        let mut reader = Reader(&bytes[MAGIC.len() + 1..]);
+
+        if version > UNHASHED_FORMAT_VERSION {
+            // tiles are stored by id, which only mean the same tiles under the same tile set
+            let saved = reader.string()?;
+            let current = tile_definitions::tile_set_hash();
+
+            if saved != current {
+                return Err(ReplayError::TileSetMismatch { saved, current });
+            }
+        }
+
+        let seed = reader.string()?;
+
+        let deck = if version == DECKLESS_FORMAT_VERSION {
+            vec![]
+        } else {
+            (0..reader.unsigned()?).map(|_| reader.tile_name()).collect::<Result<_, ReplayError>>()?
+        };
+
+        let players = (0..reader.unsigned()?)
+            .map(|_| {
+                Ok(ReplayPlayer {
+                    color: reader.color()?,
+                    name: reader.optional_string()?,
+                    bot: reader.optional_string()?,
+                })
+            })
+            .collect::<Result<_, ReplayError>>()?;
+
+        let turns = (0..reader.unsigned()?)
+            .map(|_| {
+                let player = reader.color()?;
+                let tile = reader.tile_name()?;
+                let flags = reader.byte()?;
+
+                let placement = if flags & PLACED == 0 {
+                    None
+                } else {
+                    Some(ReplayPlacement {
+                        rotation: Rotation::ALL[((flags >> ROTATION_SHIFT) & ROTATION_MASK) as usize],
+                        x: reader.coordinate()?,
+                        y: reader.coordinate()?,
+                        meeple: if flags & WITH_MEEPLE == 0 { None } else { Some(reader.unsigned()? as usize) },
+                        recalled: if flags & WITH_RECALL == 0 {
+                            None
+                        } else {
+                            Some(ReplayRecall { x: reader.coordinate()?, y: reader.coordinate()?, meeple: reader.unsigned()? as usize })
+                        },
+                    })
+                };
+
+                Ok(ReplayTurn { player, tile, placement })
+            })
+            .collect::<Result<_, ReplayError>>()?;
+
+        let final_score = (0..reader.unsigned()?)
+            .map(|_| {
+                Ok(ReplayScore {
+                    player: reader.color()?,
+                    points: i32::try_from(reader.signed()?).map_err(|_| ReplayError::InvalidBinary("score out of range"))?,
+                })
+            })
+            .collect::<Result<_, ReplayError>>()?;
+
+        let handovers = if version <= HANDOVERLESS_FORMAT_VERSION {
+            vec![]
+        } else {
+            (0..reader.unsigned()?)
+                .map(|_| {
+                    Ok(ReplayHandover {
+                        turn: reader.unsigned()? as usize,
+                        player: reader.color()?,
+                        bot: reader.optional_string()?,
+                    })
+                })
+                .collect::<Result<_, ReplayError>>()?
+        };
+
+        let rules = if version <= RULELESS_FORMAT_VERSION {
+            ReplayRules::default()
+        } else {
+            match reader.optional_string()? {
+                Some(rules) => serde_json::from_str(&rules).map_err(|_| ReplayError::InvalidBinary("malformed rules"))?,
+                None => ReplayRules::default(),
+            }
+        };
+
+        if !reader.0.is_empty() {
+            return Err(ReplayError::InvalidBinary("trailing bytes"));
+        }
+
+        Ok(Replay { seed, deck, players, turns, final_score, handovers, rules })
+    }
+}
+
+/// Whether the bytes look like a binary replay (of any version) rather than JSON
+pub(crate) fn is_binary(bytes: &[u8]) -> bool {
+    bytes.len() > MAGIC.len() && bytes.starts_with(MAGIC)
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    /// LEB128: seven bits at a time, low bits first, with the top bit set on all but the last byte
+    fn unsigned(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+
+            if value == 0 {
+                self.0.push(byte);
+                return;
+            }
+
+            self.0.push(byte | 0x80);
+        }
+    }
+
+    /// Zigzag encoded, so small negative numbers stay small
+    fn signed(&mut self, value: i64) {
+        self.unsigned(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    fn string(&mut self, value: &str) {
+        self.unsigned(value.len() as u64);
+        self.0.extend_from_slice(value.as_bytes());
+    }
+
+    fn optional_string(&mut self, value: Option<&str>) {
+        match value {
+            None => self.0.push(0),
+            Some(value) => {
+                self.0.push(1);
+                self.string(value);
+            }
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, ReplayError> {
+        let (&byte, rest) = self.0.split_first().ok_or(ReplayError::InvalidBinary("unexpected end of data"))?;
+        self.0 = rest;
+        Ok(byte)
+    }
+
+    fn unsigned(&mut self) -> Result<u64, ReplayError> {
+        let mut value = 0;
+
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(ReplayError::InvalidBinary("variable length integer too long"))
+    }
+
+    fn signed(&mut self) -> Result<i64, ReplayError> {
+        let value = self.unsigned()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    fn coordinate(&mut self) -> Result<i8, ReplayError> {
+        i8::try_from(self.signed()?).map_err(|_| ReplayError::InvalidBinary("coordinate out of range"))
+    }
+
+    fn color(&mut self) -> Result<PlayerIdentifier, ReplayError> {
+        MeepleColor::from_byte(self.byte()?).ok_or(ReplayError::InvalidBinary("unknown player color"))
+    }
+
+    /// A tile id, as the name of its definition
+    fn tile_name(&mut self) -> Result<String, ReplayError> {
+        let tile = tile_definitions::find_by_id(self.byte()?).ok_or(ReplayError::InvalidBinary("unknown tile id"))?;
+        Ok(tile.name().to_string())
+    }
+
+    fn string(&mut self) -> Result<String, ReplayError> {
+        let length = self.unsigned()? as usize;
+
+        if length > self.0.len() {
+            return Err(ReplayError::InvalidBinary("unexpected end of data"));
+        }
+
+        let (value, rest) = self.0.split_at(length);
+        self.0 = rest;
+
+        String::from_utf8(value.to_vec()).map_err(|_| ReplayError::InvalidBinary("string is not utf-8"))
+    }
+
+    fn optional_string(&mut self) -> Result<Option<String>, ReplayError> {
+        match self.byte()? {
+            0 => Ok(None),
+            _ => self.string().map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay() -> Replay {
+        Replay {
+            seed: "AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE=".to_string(),
+            deck: vec!["Side city".to_string(), "Corner road".to_string(), "Straight road".to_string()],
+            players: vec![
+                ReplayPlayer { color: MeepleColor::Red, name: Some("Alice".to_string()), bot: Some("lazy".to_string()) },
+                ReplayPlayer { color: MeepleColor::Yellow, name: None, bot: None },
+            ],
+            turns: vec![
+                ReplayTurn {
+                    player: MeepleColor::Red,
+                    tile: "Side city".to_string(),
+                    placement: Some(ReplayPlacement { x: 0, y: 0, rotation: Rotation::R0, meeple: Some(1), recalled: None }),
+                },
+                ReplayTurn {
+                    player: MeepleColor::Yellow,
+                    tile: "Corner road".to_string(),
+                    placement: Some(ReplayPlacement { x: -100, y: 70, rotation: Rotation::R270, meeple: None, recalled: Some(ReplayRecall { x: 0, y: 0, meeple: 1 }) }),
+                },
+                ReplayTurn { player: MeepleColor::Red, tile: "Straight road".to_string(), placement: None },
+            ],
+            final_score: vec![
+                ReplayScore { player: MeepleColor::Red, points: 300 },
+                ReplayScore { player: MeepleColor::Yellow, points: -2 },
+            ],
+            handovers: vec![ReplayHandover { turn: 2, player: MeepleColor::Yellow, bot: Some("myopic".to_string()) }],
+            rules: ReplayRules::default(),
+        }
+    }
+
+    /// The replay without the parts versions before the current one couldn't record
+    fn handoverless_replay() -> Replay {
+        Replay { handovers: vec![], ..replay() }
+    }
+
+    /// The bytes of the version before the rules were recorded, which ended at the hand overs
+    fn without_rules(mut bytes: Vec<u8>) -> Vec<u8> {
+        assert_eq!(bytes.pop(), Some(0), "should have the standard rules to drop");
+        bytes[4] = RULELESS_FORMAT_VERSION;
+        bytes
+    }
+
+    /// The bytes of the version before hand overs were recorded, which ended at the final score
+    fn without_handovers(bytes: Vec<u8>) -> Vec<u8> {
+        let mut bytes = without_rules(bytes);
+        assert_eq!(bytes.pop(), Some(0), "should have no hand overs to drop");
+        bytes[4] = HANDOVERLESS_FORMAT_VERSION;
+        bytes
+    }
+
+    #[test]
+    fn should_round_trip_through_binary() {
+        let replay = replay();
+        let bytes = replay.to_binary().unwrap();
+
+        assert_eq!(&bytes[..5], b"CRPL\x06");
+        assert!(bytes.len() < replay.to_json().len() / 4);
+        assert_eq!(Replay::from_binary(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_round_trip_the_rules_through_binary() {
+        let rules = ReplayRules { scoring: "first edition".to_string(), meeple_per_player: 5, torus: Some((9, 9)), ..ReplayRules::default() };
+        let replay = Replay { rules, ..replay() };
+
+        assert_eq!(Replay::from_binary(&replay.to_binary().unwrap()).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_read_replays_written_before_the_rules_were_recorded() {
+        let replay = replay();
+        let bytes = without_rules(replay.to_binary().unwrap());
+
+        assert_eq!(Replay::from_binary(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_reject_other_versions_and_truncated_data() {
+        let mut bytes = replay().to_binary().unwrap();
+
+        assert!(matches!(Replay::from_binary(&bytes[..bytes.len() - 1]), Err(ReplayError::InvalidBinary(_))));
+        assert!(matches!(Replay::from_binary(b"{}"), Err(ReplayError::InvalidBinary(_))));
+
+        bytes[4] = 7;
+        assert!(matches!(Replay::from_binary(&bytes), Err(ReplayError::UnsupportedVersion(7))));
+    }
+
+    #[test]
+    fn should_read_replays_written_before_hand_overs_were_recorded() {
+        let replay = handoverless_replay();
+        let bytes = without_handovers(replay.to_binary().unwrap());
+
+        assert_eq!(Replay::from_binary(&bytes).unwrap(), replay);
+    }
+
+    /// The bytes of an older version, which had no tile set hash after the version
+    fn without_tile_set_hash(mut bytes: Vec<u8>, version: u8) -> Vec<u8> {
+        let hash_length = 1 + tile_definitions::tile_set_hash().len();
+        bytes.drain(MAGIC.len() + 1..MAGIC.len() + 1 + hash_length);
+        bytes[4] = version;
+        bytes
+    }
+
+    #[test]
+    fn should_read_replays_written_before_the_tile_set_was_recorded() {
+        let replay = handoverless_replay();
+        let bytes = without_tile_set_hash(without_handovers(replay.to_binary().unwrap()), 2);
+
+        assert_eq!(Replay::from_binary(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_reject_replays_saved_with_another_tile_set() {
+        let mut bytes = replay().to_binary().unwrap();
+        // the hash's first hex digit, after its length
+        bytes[MAGIC.len() + 2] = if bytes[MAGIC.len() + 2] == b'0' { b'1' } else { b'0' };
+
+        let error = Replay::from_binary(&bytes).unwrap_err();
+
+        assert!(matches!(error, ReplayError::TileSetMismatch { .. }));
+        assert!(error.to_string().starts_with("saved with tile set "));
+    }
+
+    #[test]
+    fn should_read_replays_written_before_the_deck_was_recorded() {
+        let replay = Replay { deck: vec![], ..handoverless_replay() };
+
+        let mut bytes = without_tile_set_hash(without_handovers(replay.to_binary().unwrap()), 1);
+        // the empty deck's length is the byte after the seed
+        let seed_end = MAGIC.len() + 2 + replay.seed.len();
+        bytes.remove(seed_end);
+
+        assert_eq!(Replay::from_binary(&bytes).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_encode_varints() {
+        let mut writer = Writer(vec![]);
+        writer.unsigned(300);
+        writer.signed(-1);
+        writer.signed(i64::MIN);
+
+        assert_eq!(&writer.0[..3], &[0xac, 0x02, 0x01]);
+
+        let mut reader = Reader(&writer.0);
+        assert_eq!(reader.unsigned().unwrap(), 300);
+        assert_eq!(reader.signed().unwrap(), -1);
+        assert_eq!(reader.signed().unwrap(), i64::MIN);
+    }
+}
@@ -1,47 +1,288 @@
 use crate::connected_regions::{
     ConnectedRegion, ConnectedRegionId, PlacedTileEdge,
 };
-use crate::player::{Meeple, Player, PlayerIdentifier, RegionIndex};
-use crate::tile::{BoardCoordinate, CardinalDirection, PlacedTile, Region, RegionType, RenderStyle, TileDefinition, TilePlacement, TILE_WIDTH};
-use crate::tile_definitions::RIVER_TERMINATOR;
-use indexmap::{IndexMap, IndexSet};
+use crate::locale::Language;
+use crate::player::{Meeple, MeepleKind, PlayerIdentifier, RegionIndex};
+#[cfg(any(feature = "debug-validate", feature = "render"))]
+use crate::player::Player;
+use crate::tile::{BoardCoordinate, BoardTopology, CardinalDirection, EdgeConstraints, EdgeSignature, PlacedTile, Region, RegionType, TileDefinition, TilePlacement, PERIMETER_REGION_DIRECTIONS, PRIMARY_CARDINAL_DIRECTIONS};
+#[cfg(feature = "render")]
+use crate::tile::{RenderStyle, TILE_WIDTH};
+#[cfg(feature = "render")]
+use colored::Colorize;
+use indexmap::IndexMap;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
-use rayon::prelude::*;
-use crate::score::Score;
-
-#[derive(Debug, Clone)]
-struct RegionScore {
-    region: RegionType,
-    score: u32,
-}
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
+use crate::rules::{BoardLimit, RuleSet, RuleSetError, StartingLayout};
+use crate::score::{Score, Scoring, SmallCityScoring};
+use crate::symmetry::fnv1a;
 
 #[derive(Debug, Default, Clone)]
 pub struct Board {
     pub(crate) placed_tiles: IndexMap<BoardCoordinate, PlacedTile>,
+    /// Each merged region, kept under the id of the root of its tile regions
     connected_regions: HashMap<ConnectedRegionId, ConnectedRegion>,
+    /// The region each edge belongs to. Edges are indexed as their tile is placed and moved over to
+    /// the region kept whenever theirs is merged into it, so never refer to a region merged away
     region_index: HashMap<PlacedTileEdge, ConnectedRegionId>,
-    score_record: Vec<HashMap<Player, u32>>,
-    current_score: HashMap<Player, Vec<RegionScore>>,
-    max_connected_region_id: usize
+    /// A union-find over the tile regions: the id of the region each was merged into, with roots
+    /// pointing at themselves
+    region_parents: Vec<ConnectedRegionId>,
+    max_connected_region_id: usize,
+    /// The empty coordinates next to placed tiles, with the edges a tile placed there has to match.
+    /// Kept up to date as tiles are placed so move hints don't have to search the whole board
+    frontier: HashMap<BoardCoordinate, EdgeSignature>,
+    /// How the board's features are scored
+    scoring: Scoring,
+    small_city_scoring: SmallCityScoring,
+    /// Whether the river's ends can't be laid next to each other, see [crate::rules::RiverOptions::separate_ends]
+    separate_river_ends: bool,
+    limit: Option<BoardLimit>,
+    topology: BoardTopology,
+    /// Whether meeple are kept out of fields, see [crate::rules::RuleSet::with_farmers]
+    without_farmers: bool,
+    /// The corners of [Board::bounding_box], kept up to date as tiles are placed
+    bounds: Option<(BoardCoordinate, BoardCoordinate)>,
+    version: BoardVersion,
+    /// [Board::zobrist_hash], kept up to date as tiles and meeple are placed and taken off
+    zobrist: u64,
+}
+
+/// Tells apart the states a board has been in: every board starts on a version of its own and
+/// moves to a new one whenever it changes, while a clone shares its version until either changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct BoardVersion(u64);
+
+impl Default for BoardVersion {
+    fn default() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+
+/// How many entries the board's bookkeeping holds, to follow turn by turn when hunting for state
+/// that grows without bound. See [Board::container_sizes]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoardSizes {
+    pub tiles: usize,
+    /// Merged regions kept on the board
+    pub regions: usize,
+    /// Regions kept under an id that has since been merged into another, which should be none
+    pub stale_regions: usize,
+    /// Edges indexed to a region no longer on the board, which should be none
+    pub stale_index_entries: usize,
+    /// Edges held by the regions, joined or open
+    pub region_edges: usize,
+    pub region_index: usize,
+    pub region_parents: usize,
+    pub frontier: usize,
+}
+
+impl BoardSizes {
+    /// What looks to have outgrown the tiles on the board. Every placed edge is indexed exactly once
+    /// and held by exactly one region, so anything over that was left behind by a merge
+    pub fn problems(&self) -> Vec<String> {
+        let edges = self.tiles * PERIMETER_REGION_DIRECTIONS.len();
+        let mut problems = vec![];
+
+        if self.region_index != edges {
+            problems.push(format!("region_index has {} entries for the {} edges of {} tiles", self.region_index, edges, self.tiles));
+        }
+
+        if self.region_edges != edges {
+            problems.push(format!("regions hold {} edges for the {} edges of {} tiles", self.region_edges, edges, self.tiles));
+        }
+
+        if self.stale_regions > 0 {
+            problems.push(format!("{} regions are kept under ids merged away", self.stale_regions));
+        }
+
+        if self.stale_index_entries > 0 {
+            problems.push(format!("{} edges are indexed to regions merged away", self.stale_index_entries));
+        }
+
+        if self.frontier > self.tiles * 4 {
+            problems.push(format!("frontier has {} coordinates beside {} tiles", self.frontier, self.tiles));
+        }
+
+        problems
+    }
 }
 
 
-#[derive(Debug)]
+/// Why a tile, or the meeple on it, can't be placed where it was put. Conformance cases name one
+/// by its variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[non_exhaustive]
 pub enum InvalidTilePlacement {
+    /// Another tile is already at the coordinate
     TileAlreadyAtCoordinate,
+    /// None of the tiles around the coordinate share an edge with it
     TileDoesNotContactPlacedTiles,
+    /// An edge of the tile, as rotated, doesn't match the edge of the tile beside it
     TileEdgesDoNotMatchPlacedTiles,
+    /// The meeple's feature joins one another meeple is already on
     OtherMeepleAlreadyInConnectedRegion,
+    /// A river tile doesn't carry on from the last river tile laid
     RiverMustBeConnected,
+    /// A river tile turns the river back towards where it has just been
     RiverMustNotImmediatelyTurnOnItself,
+    /// The river can't end next to its spring under [crate::rules::RiverOptions::separate_ends]
+    RiverEndsMustNotTouch,
+    /// The board would no longer fit within the [BoardLimit]
+    OutsideBoardLimit,
+    /// The tile has no region with the meeple's region index
     InvalidMeeplePlacementIndex,
-    MeepleCannotBePlacedInRiver
+    /// Nothing can stand in the river
+    MeepleCannotBePlacedInRiver,
+    /// e.g. a pig outside a field
+    MeepleKindCannotOccupyRegion,
+    /// A builder or pig placed where its player has no follower to join
+    MeepleKindNeedsOwnFollower,
+    /// The board's record of which regions join up doesn't match its tiles, e.g. one rebuilt from
+    /// a corrupted save. The board shouldn't be played on any further
+    RegionsOutOfSync,
+    /// A meeple placed in a field under rules without farmers
+    NoFarmers,
+}
+
+/// Where a tile's edge would meet a different feature on the tile beside it, to explain an
+/// [InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles] to a player
+#[derive(Debug, Clone)]
+pub(crate) struct EdgeConflict<'a> {
+    /// The side of the tile, as rotated, that doesn't match
+    pub(crate) direction: CardinalDirection,
+    /// The feature on that side of the tile
+    pub(crate) own: RegionType,
+    #[cfg_attr(not(feature = "render"), allow(dead_code))] // only read to draw the two tiles
+    pub(crate) neighbour: &'a PlacedTile,
+    /// The feature the tile beside it has where they meet
+    pub(crate) neighbour_region: RegionType,
+}
+
+/// A move being checked for a hint: the tile, where it would go, and the region a follower would
+/// go in. Borrows what a [PlacedTile] would own, so each candidate costs nothing to make
+pub(crate) struct CandidatePlacement<'a> {
+    pub(crate) tile: &'static TileDefinition,
+    pub(crate) placement: &'a TilePlacement,
+    pub(crate) meeple_region: Option<RegionIndex>,
 }
 
 #[derive(Debug, Default)]
 pub struct TilePlacementSuccess {
     pub score_delta: Score,
-    pub liberated_meeple: Vec<Meeple>,
+    pub liberated_meeple: Vec<LiberatedMeeple>,
+    pub completed_features: Vec<CompletedFeature>,
+    /// The connected regions the tile's regions are now part of, found again after a later
+    /// placement merges them with [Board::connected_region]
+    pub joined_regions: Vec<ConnectedRegionId>,
+    /// Those of the joined regions the tile closed
+    pub closed_regions: Vec<ConnectedRegionId>,
+    /// Cloisters the tile completed the surroundings of, whether or not a meeple was on them
+    pub completed_cloisters: Vec<BoardCoordinate>,
+}
+
+/// A meeple returned to its player, with the feature it was returned from
+#[derive(Debug, Clone)]
+pub struct LiberatedMeeple {
+    pub meeple: Meeple,
+    pub(crate) region_type: RegionType,
+    pub(crate) tile_count: usize,
+    /// Points the feature scored, whether or not this meeple's player scored them
+    pub(crate) points: u32,
+}
+
+impl LiberatedMeeple {
+    /// The kind of feature the meeple was returned from
+    pub fn region_type(&self) -> &RegionType {
+        &self.region_type
+    }
+
+    pub fn tile_count(&self) -> usize {
+        self.tile_count
+    }
+
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+}
+
+impl Display for LiberatedMeeple {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let feature = format!("{:?}", self.region_type).to_lowercase();
+
+        write!(f, "meeple returned from {}-tile {} ({} pts)", self.tile_count, feature, self.points)
+    }
+}
+
+impl Display for InvalidTilePlacement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(Language::English.placement_error(self))
+    }
+}
+
+impl std::error::Error for InvalidTilePlacement {}
+
+/// A city, road or cloister finished by a tile placement
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompletedFeature {
+    pub(crate) region_type: RegionType,
+    /// The tiles the feature covers, or for a cloister, the cloister and the tiles around it
+    pub(crate) tiles: Vec<BoardCoordinate>,
+    pub(crate) pennant_count: usize,
+    pub(crate) points: u32,
+    /// Players with the most meeple in the feature, who each score its points
+    pub(crate) scored_by: Vec<PlayerIdentifier>,
+    /// The weighted meeple each player had in the feature, strongest first
+    pub(crate) strength: Vec<(PlayerIdentifier, u32)>,
+}
+
+impl CompletedFeature {
+    pub fn region_type(&self) -> &RegionType {
+        &self.region_type
+    }
+
+    /// The tiles the feature covers, or for a cloister, the cloister and the tiles around it
+    pub fn tiles(&self) -> &[BoardCoordinate] {
+        &self.tiles
+    }
+
+    pub fn pennant_count(&self) -> usize {
+        self.pennant_count
+    }
+
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+
+    /// Players with the most meeple in the feature, who each score its points
+    pub fn scored_by(&self) -> &[PlayerIdentifier] {
+        &self.scored_by
+    }
+
+    /// The weighted meeple each player had in the feature, strongest first
+    pub fn strength(&self) -> &[(PlayerIdentifier, u32)] {
+        &self.strength
+    }
+
+    /// Whether more than one player had meeple in the feature
+    pub fn is_contested(&self) -> bool {
+        self.strength.len() > 1
+    }
+
+    /// How many more weighted meeple the scorers had than the strongest of the other players, or
+    /// than nobody when no one else had any. Zero when it was shared
+    pub fn margin(&self) -> u32 {
+        match self.strength.as_slice() {
+            [] => 0,
+            [(_, only)] => *only,
+            [(_, first), (_, second), ..] => first - second,
+        }
+    }
 }
 
 impl Board {
@@ -52,19 +293,201 @@ impl Board {
         self.placed_tiles.get(coordinate)
     }
 
-    pub(crate) fn get_connected_region(
-        &self,
-        id: &ConnectedRegionId,
-    ) -> Option<&ConnectedRegion> {
-        self.connected_regions.get(id)
+    /// The connected region with the id, or the one it has since been merged into, so an id
+    /// taken before a tile joined two regions still finds them. `None` for an id this board never
+    /// gave out
+    pub fn connected_region(&self, id: ConnectedRegionId) -> Option<&ConnectedRegion> {
+        self.connected_regions.get(&self.find_region(id))
     }
-    pub(crate) fn new() -> Self {
+
+    /// The id the region is kept under, once it has been merged with any others
+    pub(crate) fn find_region(&self, mut id: ConnectedRegionId) -> ConnectedRegionId {
+        while let Some(&parent) = self.region_parents.get(id) {
+            if parent == id {
+                break;
+            }
+
+            id = parent;
+        }
+
+        id
+    }
+
+    /// As [Board::find_region], pointing the region straight at its root so it's found faster next time
+    fn compress_region_path(&mut self, id: ConnectedRegionId) -> ConnectedRegionId {
+        let root = self.find_region(id);
+
+        if let Some(parent) = self.region_parents.get_mut(id) {
+            *parent = root;
+        }
+
+        root
+    }
+
+    /// A copy of the board whose only connected regions are those given, each kept under its
+    /// position in the list, for scoring regions found again without the board's bookkeeping
+    #[cfg(feature = "score-check")]
+    pub(crate) fn with_only_regions(&self, regions: Vec<ConnectedRegion>) -> Board {
+        let mut board = self.clone();
+
+        board.region_parents = (0..regions.len()).collect();
+        board.region_index.clear();
+        board.connected_regions = regions.into_iter().enumerate().map(|(id, region)| (id, ConnectedRegion { id, ..region })).collect();
+
+        board
+    }
+
+    /// Merges two regions, keeping the larger under its id so fewer tile regions change root
+    fn union_regions(&mut self, region: ConnectedRegion, other: ConnectedRegion) -> Result<ConnectedRegion, InvalidTilePlacement> {
+        let (mut kept, absorbed) = if other.tile_regions.len() > region.tile_regions.len() { (other, region) } else { (region, other) };
+
+        let absorbed_id = absorbed.id;
+        let absorbed_edges: Vec<_> = absorbed.connected_edges.iter().flat_map(|(edge, opposing)| std::iter::once(edge).chain(opposing)).cloned().collect();
+
+        kept.merge_mut(absorbed, &self.topology).map_err(|_| InvalidTilePlacement::RegionsOutOfSync)?;
+
+        // only once merged, so a failed merge leaves the bookkeeping as it was
+        self.region_parents[absorbed_id] = kept.id;
+
+        for edge in absorbed_edges {
+            self.region_index.insert(edge, kept.id);
+        }
+
+        Ok(kept)
+    }
+
+    /// The distinct regions bordering the region, e.g. the cities a field's farmers supply
+    pub fn adjacent_regions(&self, region: &ConnectedRegion) -> Vec<&ConnectedRegion> {
+        let mut roots: Vec<_> = region.adjacent_regions.iter().map(|id| self.find_region(*id)).filter(|id| *id != region.id).collect();
+        roots.sort();
+        roots.dedup();
+
+        roots.into_iter().filter_map(|id| self.connected_regions.get(&id)).collect()
+    }
+    pub fn new() -> Self {
         Self {
             ..Default::default()
         }
     }
 
-    pub(crate) fn new_with_tiles<T: IntoIterator<Item=PlacedTile>>(
+    /// Scores the board's features under `scoring` rather than the base game's rules
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self.version = BoardVersion::default();
+        self
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    pub fn with_small_city_scoring(mut self, small_city_scoring: SmallCityScoring) -> Self {
+        self.small_city_scoring = small_city_scoring;
+        self.version = BoardVersion::default();
+        self
+    }
+
+    pub fn small_city_scoring(&self) -> SmallCityScoring {
+        self.small_city_scoring
+    }
+
+    /// A board that scores as `rules` say, with their starting layout laid. Rules that haven't
+    /// been validated may have a layout that can't be laid
+    pub(crate) fn under_rules(rules: &RuleSet) -> Result<Self, RuleSetError> {
+        let mut board = Self::new()
+            .with_scoring(rules.scoring.clone())
+            .with_small_city_scoring(rules.small_city_scoring)
+            .with_separate_river_ends(rules.river.separate_ends)
+            .with_limit(rules.board_limit)
+            .with_topology(rules.topology)
+            .with_farmers(rules.farmers);
+
+        board.lay(&rules.starting_layout).map_err(|(index, reason)| RuleSetError::InvalidStartingLayout(index, reason))?;
+
+        Ok(board)
+    }
+
+    /// Places the layout's tiles in order, returning the index of the first that can't be placed
+    /// and why
+    pub(crate) fn lay(&mut self, layout: &StartingLayout) -> Result<&mut Self, (usize, InvalidTilePlacement)> {
+        for (index, tile) in layout.tiles().iter().enumerate() {
+            self.place_tile(tile.clone()).map_err(|reason| (index, reason))?;
+        }
+
+        Ok(self)
+    }
+
+    pub(crate) fn with_topology(mut self, topology: BoardTopology) -> Self {
+        self.topology = topology;
+        self.version = BoardVersion::default();
+        self
+    }
+
+    /// Hash of the tiles and meeple on the board, kept up to date as they are placed and taken
+    /// off. Unlike [Board::canonical_hash] it tells a board from its rotations and costs nothing
+    /// to read, so suits checking after every turn that two copies of a game haven't drifted apart
+    pub fn zobrist_hash(&self) -> u64 {
+        self.zobrist
+    }
+
+    /// The state the board is in, see [BoardVersion]
+    pub(crate) fn version(&self) -> BoardVersion {
+        self.version
+    }
+
+    pub(crate) fn topology(&self) -> &BoardTopology {
+        &self.topology
+    }
+
+    pub(crate) fn with_limit(mut self, limit: Option<BoardLimit>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Whether a tile at `coordinate` would keep the board within its limit
+    pub(crate) fn within_limit(&self, coordinate: BoardCoordinate) -> bool {
+        let (Some(limit), Some((min, max))) = (self.limit, self.bounds) else {
+            return true;
+        };
+
+        let width = max.x.max(coordinate.x) as i16 - min.x.min(coordinate.x) as i16 + 1;
+        let height = max.y.max(coordinate.y) as i16 - min.y.min(coordinate.y) as i16 + 1;
+
+        width <= limit.width as i16 && height <= limit.height as i16
+    }
+
+    pub(crate) fn with_farmers(mut self, farmers: bool) -> Self {
+        self.without_farmers = !farmers;
+        self
+    }
+
+    /// Whether meeple may be placed in the board's fields
+    pub(crate) fn has_farmers(&self) -> bool {
+        !self.without_farmers
+    }
+
+    /// As [validate_meeple_region], also keeping meeple out of fields on boards without farmers
+    fn validate_meeple_region(&self, tile: &TileDefinition, region_index: &RegionIndex, kind: &MeepleKind) -> Result<(), InvalidTilePlacement> {
+        validate_meeple_region(tile, region_index, kind)?;
+
+        if self.without_farmers && tile.regions[**region_index].region_type() == RegionType::Field {
+            return Err(InvalidTilePlacement::NoFarmers);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn with_separate_river_ends(mut self, separate_river_ends: bool) -> Self {
+        self.separate_river_ends = separate_river_ends;
+        self
+    }
+
+    /// Whether a tile next to `coordinate` is one of the river's ends
+    fn touches_river_end(&self, coordinate: BoardCoordinate) -> bool {
+        self.placed_tiles.values().any(|placed| placed.tile.is_river_end() && self.topology.direction_to_adjacent_coordinate(&coordinate, placed.placement.coordinate).is_some())
+    }
+
+    pub fn new_with_tiles<T: IntoIterator<Item=PlacedTile>>(
         tiles: T,
     ) -> Result<Self, InvalidTilePlacement> {
         let mut board = Board::default();
@@ -80,6 +503,32 @@ impl Board {
         self.placed_tiles.len()
     }
 
+    /// Every tile on the board with its coordinate, in the order they were placed
+    pub fn placed_tiles(&self) -> impl Iterator<Item = (&BoardCoordinate, &PlacedTile)> {
+        self.placed_tiles.iter()
+    }
+
+    /// The north west and south east corners of the smallest rectangle holding every placed tile,
+    /// or `None` for an empty board
+    pub fn bounding_box(&self) -> Option<(BoardCoordinate, BoardCoordinate)> {
+        self.bounds
+    }
+
+    /// How many entries each of the board's containers holds, see [BoardSizes]
+    pub fn container_sizes(&self) -> BoardSizes {
+        BoardSizes {
+            tiles: self.placed_tiles.len(),
+            regions: self.connected_regions.len(),
+            stale_regions: self.connected_regions.keys().filter(|id| self.find_region(**id) != **id).count(),
+            region_edges: self.connected_regions.values().flat_map(|region| &region.connected_edges).map(|(_, opposing)| 1 + opposing.is_some() as usize).sum(),
+            stale_index_entries: self.region_index.values().filter(|id| !self.connected_regions.contains_key(id)).count(),
+            region_index: self.region_index.len(),
+            region_parents: self.region_parents.len(),
+            frontier: self.frontier.len(),
+        }
+    }
+
+    /// The tile regions across the region's edges, which may since have been merged into others
     fn get_candidate_regions_to_merge(
         &self,
         connected_region: &ConnectedRegion,
@@ -87,7 +536,7 @@ impl Board {
         let mut regions_to_merge: HashSet<ConnectedRegionId> = Default::default();
 
         for edge in connected_region.connected_edges.keys() {
-            let opposite = edge.opposing_tile_edge();
+            let opposite = edge.opposing_tile_edge(&self.topology);
             if let Some(connected_region_id) = self.region_index.get(&opposite) {
                 regions_to_merge.insert(*connected_region_id);
             }
@@ -100,58 +549,115 @@ impl Board {
         &mut self,
         tile: PlacedTile,
     ) -> Result<TilePlacementSuccess, InvalidTilePlacement> {
+        #[cfg(feature = "alloc-stats")]
+        let _scope = crate::alloc_stats::enter(crate::alloc_stats::Subsystem::Board);
+
+        // even a placement that fails has already changed the region bookkeeping
+        self.version = BoardVersion::default();
+
         let tile_connected_regions = tile.own_connected_regions(self.max_connected_region_id);
 
         self.max_connected_region_id += tile_connected_regions.len();
+        self.region_parents.extend(self.region_parents.len()..=self.max_connected_region_id);
 
-        self.validate_tile_placement(&tile, Some(&tile_connected_regions))?;
+        self.validate_tile_placement_with_regions(&tile, Some(&tile_connected_regions))?;
 
-        let mut liberated_meeple: Vec<Meeple> = Vec::new();
+        let mut liberated_meeple: Vec<LiberatedMeeple> = Vec::new();
+        let mut completed_features: Vec<CompletedFeature> = Vec::new();
         let mut score_delta = Score::new();
+        let mut joined_regions = Vec::new();
+        let mut closed_regions = Vec::new();
 
         let coordinate = tile.placement.coordinate;
 
-        self.placed_tiles.insert(coordinate.clone(), tile);
+        self.zobrist ^= tile_key(&tile);
+        if let Some((region_index, meeple)) = &tile.meeple {
+            self.zobrist ^= meeple_key(coordinate, *region_index, meeple);
+        }
 
-        for mut connected_region in tile_connected_regions {
-            let regions_to_merge = self.get_candidate_regions_to_merge(&connected_region);
+        self.placed_tiles.insert(coordinate, tile);
+        self.extend_frontier(coordinate);
 
-            for region_id in regions_to_merge {
-                let merge_region = self.connected_regions.remove(&region_id).expect("should exist");
+        #[cfg(feature = "score-check")]
+        let meeple_before = self.meeple_before();
 
-                for (_, mut region ) in &mut self.connected_regions {
-                    if region.adjacent_regions.remove(&merge_region.id) {
-                        region.adjacent_regions.insert(connected_region.id);
-                    }
-                }
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                BoardCoordinate::new(min.x.min(coordinate.x), min.y.min(coordinate.y)),
+                BoardCoordinate::new(max.x.max(coordinate.x), max.y.max(coordinate.y)),
+            ),
+            None => (coordinate, coordinate),
+        });
 
-                connected_region.merge_mut(merge_region).expect("should merge");
-            }
+        for mut connected_region in tile_connected_regions {
+            #[cfg(feature = "alloc-stats")]
+            let _scope = crate::alloc_stats::enter(crate::alloc_stats::Subsystem::Regions);
 
             for placed_tile_edge in connected_region.connected_edges.keys() {
                 self.region_index.insert(placed_tile_edge.clone(), connected_region.id);
             }
 
+            let candidates = self.get_candidate_regions_to_merge(&connected_region);
+            let regions_to_merge: HashSet<_> = candidates.into_iter().map(|id| self.compress_region_path(id)).collect();
+
+            for region_id in regions_to_merge {
+                let merge_region = self.connected_regions.remove(&region_id).ok_or(InvalidTilePlacement::RegionsOutOfSync)?;
+
+                connected_region = self.union_regions(connected_region, merge_region)?;
+            }
+
+            joined_regions.push(connected_region.id);
+
             if connected_region.is_closed() {
+                closed_regions.push(connected_region.id);
+
                 let resident_tile_coordinates: Vec<_> = connected_region.residents(self).iter().map(|(tile, _, _)| tile.placement.coordinate).collect();
 
                 // score the region before liberating the meeple
                 // (otherwise they won't be considered resident and will score zero!)
-                for winning_player in connected_region.majority_meeple_player_ids(self) {
-                    score_delta.add_score(winning_player, connected_region.score(self) as i32)
+                let points = connected_region.score(self);
+                let scored_by = connected_region.majority_meeple_player_ids(self);
+                let strength = connected_region.meeple_strength(self);
+                let tile_count = connected_region.tile_regions.iter().map(|r| r.tile_position).collect::<HashSet<_>>().len();
+
+                for winning_player in &scored_by {
+                    score_delta.add_score(*winning_player, points as i32)
+                }
+
+                if matches!(connected_region.region_type, RegionType::City | RegionType::Road) {
+                    let pennant_count = connected_region.pennant_count;
+
+                    let mut tiles: Vec<_> = connected_region.tile_regions.iter().map(|r| r.tile_position).collect::<HashSet<_>>().into_iter().collect();
+                    tiles.sort_by_key(|coordinate| (coordinate.y, coordinate.x));
+
+                    completed_features.push(CompletedFeature {
+                        region_type: connected_region.region_type.clone(),
+                        tiles,
+                        pennant_count,
+                        points,
+                        scored_by,
+                        strength,
+                    });
                 }
 
                 let mut liberated_meeple_for_region = Vec::new();
 
                 for coordinate in resident_tile_coordinates {
-                    let tile = self.placed_tiles.get_mut(&coordinate).expect("should exist");
+                    // a region's tiles are all on the board, unless the bookkeeping has drifted
+                    let tile = self.placed_tiles.get_mut(&coordinate).ok_or(InvalidTilePlacement::RegionsOutOfSync)?;
 
                     if let Some((region_index, _)) = &tile.meeple {
                         if connected_region.tile_regions.iter().any(|r|r.tile_position == tile.placement.coordinate && &r.region_index == region_index) {
 
-                            if let Some((_, meeple)) = tile.meeple.take() {
+                            if let Some((region_index, meeple)) = tile.meeple.take() {
+                                self.zobrist ^= meeple_key(coordinate, region_index, &meeple);
                                 assert_ne!(&connected_region.region_type, &RegionType::Water, "meeple shouldn't need to be liberated from the river. something has gone horribly wrong!");
-                                liberated_meeple_for_region.push(meeple);
+                                liberated_meeple_for_region.push(LiberatedMeeple {
+                                    meeple,
+                                    region_type: connected_region.region_type.clone(),
+                                    tile_count,
+                                    points,
+                                });
                             }
                         }
                     }
@@ -164,205 +670,646 @@ impl Board {
             self.connected_regions.insert(connected_region.id, connected_region);
         }
 
-        let adjacent_cloister_tiles: Vec<_> = self.list_surrounding_tiles(&coordinate).into_iter()
+        let completed_cloisters = self.list_surrounding_tiles(&coordinate).into_iter()
+            .chain(self.placed_tiles.get(&coordinate))
+            .filter(|tile| tile.has_cloister() && self.list_surrounding_tiles(&tile.placement.coordinate).len() == 8)
+            .map(|tile| tile.placement.coordinate)
+            .collect();
+
+        // the tile itself may be a cloister laid into a gap already surrounded
+        let occupied_cloister_tiles: Vec<_> = self.list_surrounding_tiles(&coordinate).into_iter()
+            .chain(self.placed_tiles.get(&coordinate))
             .filter_map(|tile|if tile.has_occupied_cloister() { Some(tile.placement.coordinate)} else { None })
             .collect();
 
 
-        for adjacent_coordinate in adjacent_cloister_tiles {
-            let adjacent_count = self.list_surrounding_tiles(&adjacent_coordinate).len();
+        for cloister_coordinate in occupied_cloister_tiles {
+            let adjacent_count = self.list_surrounding_tiles(&cloister_coordinate).len();
 
             if adjacent_count == 8 {
-                let tile = self.placed_tiles.get_mut(&adjacent_coordinate).expect("should exist");
-                if let Some((_, meeple)) = tile.meeple.take() {
-                    score_delta.add_score(meeple.color.clone(), 9);
-                    liberated_meeple.push(meeple);
+                let meeple = self.placed_tiles.get_mut(&cloister_coordinate).and_then(|tile| tile.meeple.take());
+                if let Some((region_index, meeple)) = meeple {
+                    self.zobrist ^= meeple_key(cloister_coordinate, region_index, &meeple);
+
+                    let cloister = self.connected_regions.values().find(|region| {
+                        region.tile_regions.iter().any(|tile_region| tile_region.tile_position == cloister_coordinate && tile_region.region_index == region_index)
+                    });
+                    let Some(cloister) = cloister.filter(|region| *region.region_type() == RegionType::Cloister) else {
+                        return Err(InvalidTilePlacement::RegionsOutOfSync);
+                    };
+                    let points = cloister.score(self);
+
+                    score_delta.add_score(meeple.color, points as i32);
+                    let mut tiles = self.topology.surrounding_coordinates(&cloister_coordinate);
+                    tiles.push(cloister_coordinate);
+                    tiles.sort_by_key(|coordinate| (coordinate.y, coordinate.x));
+                    let tile_count = tiles.len();
+
+                    completed_features.push(CompletedFeature {
+                        region_type: RegionType::Cloister,
+                        tiles,
+                        pennant_count: 0,
+                        points,
+                        scored_by: vec![meeple.color],
+                        strength: vec![(meeple.color, meeple.kind.majority_weight())],
+                    });
+                    liberated_meeple.push(LiberatedMeeple { meeple, region_type: RegionType::Cloister, tile_count, points });
                 }
             }
         }
 
-        // @todo implement scoring and meeple tracking in success result
+        #[cfg(feature = "debug-validate")]
+        self.assert_invariants();
+
+        #[cfg(feature = "score-check")]
+        self.assert_score_delta(coordinate, &meeple_before, &score_delta, &completed_features);
+
+        // the tile's later regions may have merged its earlier ones into another region
+        let current_ids = |ids: Vec<ConnectedRegionId>| {
+            let mut ids: Vec<_> = ids.into_iter().map(|id| self.find_region(id)).collect();
+            ids.sort();
+            ids.dedup();
+            ids
+        };
+
         Ok(TilePlacementSuccess {
             liberated_meeple,
-            score_delta
+            score_delta,
+            completed_features,
+            joined_regions: current_ids(joined_regions),
+            closed_regions: current_ids(closed_regions),
+            completed_cloisters,
+        })
+    }
+
+    /// Checks the tile, and the meeple on it if there is one, could be placed on the board by
+    /// exactly the rules [crate::Game] plays by, without placing it. For checking a move before
+    /// it's made, e.g. as a player drags a tile around the board
+    pub fn validate_tile_placement(&self, tile: &PlacedTile) -> Result<(), InvalidTilePlacement> {
+        self.validate_tile_placement_with_regions(tile, None)
+    }
+
+    /// The first side of the tile, clockwise from north, that meets a different feature on the tile
+    /// beside it, if any does. Of the three places along a side, the middle is the one reported
+    /// where it differs, as that's where a road or river would be
+    pub(crate) fn edge_conflict(&self, tile: &'static TileDefinition, placement: &TilePlacement) -> Option<EdgeConflict<'_>> {
+        let placed = PlacedTile { tile, placement: placement.clone(), meeple: None };
+
+        PRIMARY_CARDINAL_DIRECTIONS.iter().find_map(|direction| {
+            let neighbour = self.placed_tiles.get(&self.topology.adjacent_in_direction(&placement.coordinate, direction))?;
+
+            let own = placed.list_regions_on_edge(direction);
+            let theirs = neighbour.list_regions_on_edge(&direction.compass_opposite());
+            let index = [1, 0, 2].into_iter().find(|index| own[*index] != theirs[*index])?;
+
+            Some(EdgeConflict { direction: *direction, own: own[index].clone(), neighbour, neighbour_region: theirs[index].clone() })
         })
     }
 
-    pub(crate) fn validate_tile_placement(
+    /// As [Board::validate_tile_placement], reusing the tile's own regions when they've already
+    /// been joined up
+    fn validate_tile_placement_with_regions(
         &self,
         tile: &PlacedTile,
         tile_connected_regions: Option<&Vec<ConnectedRegion>>,
     ) -> Result<(), InvalidTilePlacement> {
 
-        if let Some((region_index, _)) = &tile.meeple {
+        if let Some((region_index, meeple)) = &tile.meeple {
+            self.validate_meeple_region(tile.tile, region_index, &meeple.kind)?;
+        }
+
+        self.validate_tile_fits(tile.tile, &tile.placement)?;
+
+        if let Some((region_index, meeple)) = &tile.meeple {
+            self.validate_meeple_residents(tile, region_index, meeple, tile_connected_regions)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks a hint's move without building the tile and meeple it would place, the follower
+    /// being a normal meeple of whoever is to play
+    pub(crate) fn validate_candidate(&self, candidate: &CandidatePlacement) -> Result<(), InvalidTilePlacement> {
+        if let Some(region_index) = &candidate.meeple_region {
+            self.validate_meeple_region(candidate.tile, region_index, &MeepleKind::Normal)?;
+        }
+
+        self.validate_tile_fits(candidate.tile, candidate.placement)?;
 
-            match tile.tile.regions.get(**region_index) {
-                None => return Err(InvalidTilePlacement::InvalidMeeplePlacementIndex),
-                Some(Region::Water {..}) => return Err(InvalidTilePlacement::MeepleCannotBePlacedInRiver),
-                _ => ()
+        if let Some(region_index) = candidate.meeple_region {
+            if self.occupied_tile_regions(candidate.tile, candidate.placement)[*region_index] {
+                return Err(InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion);
             }
         }
 
+        Ok(())
+    }
+
+    /// Checks the tile's edges match those it is placed against, and that it carries on the river
+    fn validate_tile_fits(&self, tile: &'static TileDefinition, placement: &TilePlacement) -> Result<(), InvalidTilePlacement> {
         // empty board is always valid for placement of a tile
         if self.placed_tiles.is_empty() {
             return Ok(());
         }
 
-        if self.placed_tiles.contains_key(&tile.placement.coordinate) {
+        if self.placed_tiles.contains_key(&placement.coordinate) {
             return Err(InvalidTilePlacement::TileAlreadyAtCoordinate);
         }
 
-        let surrounding_regions = self.get_surrounding_regions(&tile.placement.coordinate);
+        if !self.within_limit(placement.coordinate) {
+            return Err(InvalidTilePlacement::OutsideBoardLimit);
+        }
+
+        let frontier = self.frontier_signature(&placement.coordinate);
+
+        if frontier.is_unknown() {
+            return Err(InvalidTilePlacement::TileDoesNotContactPlacedTiles);
+        }
+
+        let own_edges = tile.edge_signature(placement.rotation);
+
+        if !own_edges.fits(&frontier) {
+            return Err(InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles);
+        }
+
+        if own_edges.has_water() {
+            if !own_edges.joins_water(&frontier) {
+                return Err(InvalidTilePlacement::RiverMustBeConnected);
+            }
+
+            if self.separate_river_ends && tile.is_river_end() && self.touches_river_end(placement.coordinate) {
+                return Err(InvalidTilePlacement::RiverEndsMustNotTouch);
+            }
+
+            if !tile.is_river_end() {
+                // the river is laid a tile at a time, so it has to carry on from the last tile placed
+                let (_, prev_tile) = self.placed_tiles.last().ok_or(InvalidTilePlacement::RiverMustBeConnected)?;
+
+                let direction_to_prev = self.topology.direction_to_adjacent_coordinate(&placement.coordinate, prev_tile.placement.coordinate)
+                    .ok_or(InvalidTilePlacement::RiverMustBeConnected)?;
+
+                let current_heading = tile.opposite_river_end_direction(placement.rotation, direction_to_prev);
+                let previous_source = prev_tile.get_opposite_river_end_direction(direction_to_prev.compass_opposite());
+
+                if previous_source == current_heading {
+                    return Err(InvalidTilePlacement::RiverMustNotImmediatelyTurnOnItself);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a follower could go in a region of `tile` placed at `tile_placement`, without
+    /// checking the tile itself fits there. For offering only the regions a meeple can go in once
+    /// the player has picked where the tile goes
+    pub fn can_place_meeple(
+        &self,
+        tile: &'static TileDefinition,
+        tile_placement: &TilePlacement,
+        region_index: RegionIndex,
+    ) -> Result<(), InvalidTilePlacement> {
+        let meeple = Meeple::dummy();
+        let placed_tile = PlacedTile { tile, placement: tile_placement.clone(), meeple: None };
+
+        self.validate_meeple_region(tile, &region_index, &meeple.kind)?;
+        self.validate_meeple_residents(&placed_tile, &region_index, &meeple, None)
+    }
+
+    /// Takes the player's meeple back off the region of the tile at `coordinate`, without scoring
+    /// it. `None` if the player has no meeple there
+    pub fn recall_meeple(&mut self, coordinate: &BoardCoordinate, region_index: RegionIndex, player: PlayerIdentifier) -> Option<Meeple> {
+        let tile = self.placed_tiles.get_mut(coordinate)?;
+
+        match &tile.meeple {
+            Some((index, meeple)) if *index == region_index && meeple.color == player => {
+                self.version = BoardVersion::default();
+                self.zobrist ^= meeple_key(*coordinate, region_index, meeple);
+                tile.meeple.take().map(|(_, meeple)| meeple)
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks nobody else is in the regions the meeple's region would join, or for meeple that
+    /// join their own followers, that one of those is there
+    fn validate_meeple_residents(
+        &self,
+        tile: &PlacedTile,
+        region_index: &RegionIndex,
+        meeple: &Meeple,
+        tile_connected_regions: Option<&Vec<ConnectedRegion>>,
+    ) -> Result<(), InvalidTilePlacement> {
+        // avoid recomputing tile regions if we have already done so previously
+        let tile_connected_regions = if let Some(tile_connected_regions) = tile_connected_regions {
+            tile_connected_regions
+        } else {
+            &tile.own_connected_regions(0)
+        };
+
+        let meeple_connected_regions = tile_connected_regions.iter().filter(|r| {
+            r.tile_regions.iter().any(|tr| tr.region_index == *region_index)
+        });
+
+        let mut joins_own_follower = false;
+
+        for connected_region in meeple_connected_regions {
+            let regions_to_merge: HashSet<_> = self.get_candidate_regions_to_merge(connected_region).into_iter().map(|id| self.find_region(id)).collect();
+            for region_id in regions_to_merge {
+                let joined_region = self.connected_regions.get(&region_id).ok_or(InvalidTilePlacement::RegionsOutOfSync)?;
+                let residents = joined_region.residents(self);
+
+                if meeple.kind.joins_own_follower() {
+                    joins_own_follower |= residents.iter().any(|(_, _, resident)| resident.color == meeple.color && resident.kind.majority_weight() > 0);
+                } else if !residents.is_empty() {
+                    return Err(InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion);
+                }
+            }
+        }
+
+        if meeple.kind.joins_own_follower() && !joins_own_follower {
+            return Err(InvalidTilePlacement::MeepleKindNeedsOwnFollower);
+        }
+
+        Ok(())
+    }
+
+    /// For each of the tile's regions, whether a follower put there would share the feature with
+    /// meeple already on the board. Worked out once for a placement, rather than joining up the
+    /// tile's regions again for every region a meeple could go in
+    pub(crate) fn occupied_tile_regions(&self, tile: &'static TileDefinition, placement: &TilePlacement) -> Vec<bool> {
+        let mut occupied = vec![false; tile.regions.len()];
+        let placed_tile = PlacedTile { tile, placement: placement.clone(), meeple: None };
+
+        for connected_region in placed_tile.own_connected_regions(0) {
+            let is_occupied = self
+                .get_candidate_regions_to_merge(&connected_region)
+                .into_iter()
+                .filter_map(|id| self.connected_regions.get(&self.find_region(id)))
+                .any(|joined_region| !joined_region.residents(self).is_empty());
+
+            if is_occupied {
+                for tile_region in &connected_region.tile_regions {
+                    occupied[*tile_region.region_index] = true;
+                }
+            }
+        }
+
+        occupied
+    }
+
+    pub(crate) fn list_adjacent_tiles(
+        &self,
+        board_coordinate: &BoardCoordinate,
+    ) -> Vec<(CardinalDirection, Option<&PlacedTile>)> {
+        self.topology.adjacent_coordinates(board_coordinate).into_iter().map(|(direction, coordinate)| (direction, self.placed_tiles.get(&coordinate))).collect()
+    }
+
+    pub fn list_surrounding_tiles(
+        &self,
+        board_coordinate: &BoardCoordinate,
+    ) -> Vec<&PlacedTile> {
+        self.topology.surrounding_coordinates(board_coordinate).into_iter().filter_map(|coordinate| self.placed_tiles.get(&coordinate)).collect()
+    }
+
+    /// The region types a tile placed at the coordinate has to match along each edge it shares
+    /// with a placed tile
+    pub(crate) fn frontier_signature(&self, board_coordinate: &BoardCoordinate) -> EdgeSignature {
+        self.frontier.get(board_coordinate).copied().unwrap_or_default()
+    }
+
+    /// Whether the tile's edges match those around any empty coordinate next to the placed tiles,
+    /// in any rotation. Quicker than looking for a move, as the river's rules aren't checked
+    pub(crate) fn fits_frontier(&self, tile: &TileDefinition) -> bool {
+        if self.placed_tiles.is_empty() {
+            return true;
+        }
+
+        let rotations: Vec<_> = tile.distinct_rotations().iter().map(|rotations| tile.edge_signature(*rotations)).collect();
+
+        self.frontier
+            .iter()
+            .filter(|(coordinate, _)| self.within_limit(**coordinate))
+            .any(|(_, frontier)| rotations.iter().any(|signature| signature.fits(frontier)))
+    }
+
+    /// Every empty coordinate a tile could go next, with the region types its edges have to match
+    /// there, ordered by row then column. Those outside the board's limit are left out
+    pub fn frontier(&self) -> Vec<(BoardCoordinate, EdgeConstraints)> {
+        let mut frontier: Vec<_> = self
+            .frontier
+            .iter()
+            .filter(|(coordinate, _)| self.within_limit(**coordinate))
+            .map(|(coordinate, signature)| (*coordinate, EdgeConstraints::new(*signature)))
+            .collect();
+
+        frontier.sort_by_key(|(coordinate, _)| (coordinate.y, coordinate.x));
+        frontier
+    }
+
+    /// The empty coordinates next to placed tiles
+    pub(crate) fn frontier_coordinates(&self) -> impl Iterator<Item = &BoardCoordinate> {
+        self.frontier.keys()
+    }
+
+    /// Takes the newly placed tile's coordinate off the frontier, and adds its edges to the
+    /// coordinates around it that are still empty
+    fn extend_frontier(&mut self, coordinate: BoardCoordinate) {
+        self.frontier.remove(&coordinate);
+
+        let tile = &self.placed_tiles[&coordinate];
+
+        for direction in PRIMARY_CARDINAL_DIRECTIONS {
+            let adjacent = self.topology.adjacent_in_direction(&coordinate, direction);
+
+            if self.placed_tiles.contains_key(&adjacent) {
+                continue;
+            }
+
+            let signature = self.frontier.entry(adjacent).or_default();
+            *signature = signature.with_edge(&direction.compass_opposite(), &tile.list_regions_on_edge(direction));
+        }
+    }
+
+    pub(crate) fn get_connected_regions(&self) -> Vec<&ConnectedRegion> {
+        self.connected_regions.values().collect()
+    }
+
+    /// The players with the most meeple in the connected region each tile region is part of
+    pub(crate) fn region_owners(&self) -> HashMap<(BoardCoordinate, RegionIndex), Vec<PlayerIdentifier>> {
+        let mut owners = HashMap::new();
+
+        for connected_region in self.get_connected_regions() {
+            let majority = connected_region.majority_meeple_player_ids(self);
+
+            if majority.is_empty() {
+                continue;
+            }
+
+            for tile_region in &connected_region.tile_regions {
+                owners.insert((tile_region.tile_position, tile_region.region_index), majority.clone());
+            }
+        }
+
+        owners
+    }
+
+    #[cfg(feature = "render")]
+    pub fn render(&self, style: &RenderStyle) -> String {
+        if self.placed_tiles.is_empty() {
+            return "[Empty board]".to_string();
+        }
+
+        match style {
+            RenderStyle::Kitty => self.render_bitmap().to_kitty(),
+            RenderStyle::Sixel => self.render_bitmap().to_sixel(),
+            _ => self.render_tiles(|tile| tile.render_to_lines(style)),
+        }
+    }
+
+    /// The board without colour, drawn the same whatever the terminal, for snapshot tests and
+    /// pasting into bug reports. Meeple are drawn with their player's initial
+    #[cfg(feature = "render")]
+    pub fn render_plain(&self) -> String {
+        self.render_tiles(PlacedTile::render_plain)
+    }
+
+    /// Lays out the text drawn for each tile as the board, leaving gaps where there are no tiles
+    #[cfg(feature = "render")]
+    pub(crate) fn render_tiles(&self, render_tile: impl Fn(&PlacedTile) -> Vec<String>) -> String {
+        self.render_tiles_with(None, render_tile)
+    }
+
+    /// As [Board::render_tiles], with `extra` laid out as if it had been placed, growing the board
+    /// to fit it if need be
+    #[cfg(feature = "render")]
+    pub(crate) fn render_tiles_with(&self, extra: Option<&PlacedTile>, render_tile: impl Fn(&PlacedTile) -> Vec<String>) -> String {
+        let extra_coordinate = extra.map(|tile| tile.placement.coordinate);
+
+        let bounding_box = match (self.bounding_box(), extra_coordinate) {
+            (Some((min, max)), Some(BoardCoordinate { x, y })) => {
+                Some((BoardCoordinate::new(min.x.min(x), min.y.min(y)), BoardCoordinate::new(max.x.max(x), max.y.max(y))))
+            }
+            (Some(bounding_box), None) => Some(bounding_box),
+            (None, Some(coordinate)) => Some((coordinate, coordinate)),
+            (None, None) => None,
+        };
+
+        let Some((BoardCoordinate { x: min_x, y: min_y }, BoardCoordinate { x: max_x, y: max_y })) = bounding_box else {
+            return "[Empty board]".to_string();
+        };
+
+        let mut output = Vec::with_capacity(((max_y - min_y) + 1) as usize * TILE_WIDTH);
+        // note we can't pre-allocate the width of the board as the color control chars make each
+        // row a different length depending on what regions are represented
+        output.extend(std::iter::repeat_n(String::new(), output.capacity()));
+
+        for (row_idx, row) in (min_y..=max_y).enumerate() {
+            for column in min_x..=max_x {
+                let coord = BoardCoordinate { x: column, y: row };
+
+                let lines = if let Some(tile) = self.placed_tiles.get(&coord).or(extra.filter(|_| extra_coordinate == Some(coord))) {
+                    render_tile(tile)
+                } else {
+                    vec![" ".repeat(TILE_WIDTH * 2); TILE_WIDTH]
+                };
+
+                for (render_row, render) in lines.iter().enumerate() {
+                    let row_idx = row_idx * TILE_WIDTH + render_row;
+
+                    output.get_mut(row_idx).unwrap().push_str(render);
+                }
+            }
+        }
+
+        output.join("\n")
+    }
+
+    /// As [Board::render], with the extras `annotations` asks for drawn under it
+    #[cfg(feature = "render")]
+    pub fn render_annotated(&self, style: &RenderStyle, players: &IndexMap<PlayerIdentifier, Player>, annotations: &RenderAnnotations) -> String {
+        annotations.annotate(self.render(style), players, style)
+    }
+}
+
+/// Extras drawn around a rendered board, so a screenshot of it makes sense on its own
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderAnnotations {
+    /// A line under the board with each player's meeple as drawn on it, their name, and how many
+    /// meeple they have left in hand
+    pub legend: bool,
+}
+
+#[cfg(feature = "render")]
+impl RenderAnnotations {
+    pub fn with_legend(mut self) -> Self {
+        self.legend = true;
+        self
+    }
 
-        if surrounding_regions.iter().all(|region| region.is_none()) {
-            return Err(InvalidTilePlacement::TileDoesNotContactPlacedTiles);
+    /// The board drawn as `rendered`, with the annotations under it
+    pub fn annotate(&self, mut rendered: String, players: &IndexMap<PlayerIdentifier, Player>, style: &RenderStyle) -> String {
+        if self.legend {
+            rendered.push('\n');
+            rendered.push_str(&render_legend(players, style));
         }
 
-        let own_regions = tile.tile.list_oriented_region_types(tile.placement.rotations);
+        rendered
+    }
+}
 
-        let region_pairings: Vec<_> = own_regions.iter().zip(surrounding_regions).collect();
+/// Each player's meeple glyph, name and meeple in hand on one line, e.g. `ꆜR Alice 5 left`. Plain
+/// ascii boards draw every meeple alike, so the player's initial is added to tell them apart
+#[cfg(feature = "render")]
+fn render_legend(players: &IndexMap<PlayerIdentifier, Player>, style: &RenderStyle) -> String {
+    players
+        .iter()
+        .map(|(id, player)| {
+            let glyph = match style {
+                RenderStyle::Ascii => format!("ꆜ{}", id.initial()),
+                _ => "ꆜ".bold().color(id.render_color(style)).to_string(),
+            };
+            let name = player.name.clone().unwrap_or_else(|| id.to_string());
 
-        if region_pairings.iter().any(|(own_region, neighbor_region)| match neighbor_region {
-            Some(region) if &region != own_region => true,
-            _ => false,
+            format!("{} {} {} left", glyph, name, player.meeple.len())
         })
-        {
-            return Err(InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles);
-        }
+        .collect::<Vec<_>>()
+        .join("   ")
+}
 
+#[cfg(feature = "debug-validate")]
+impl Board {
+    /// Panics if the region bookkeeping has drifted from the tiles on the board. Checked after
+    /// every placement when the `debug-validate` feature is on, so a bad merge is caught on the
+    /// turn it happens rather than as a wrong score many turns later
+    pub(crate) fn assert_invariants(&self) {
+        for tile in self.placed_tiles.values() {
+            for direction in PERIMETER_REGION_DIRECTIONS {
+                let edge = PlacedTileEdge { coordinate: tile.placement.coordinate, global_direction: *direction };
+
+                assert!(self.region_index.contains_key(&edge), "{:?} of {} at {:?} is not in any region", direction, tile.tile.name, tile.placement.coordinate);
+            }
+        }
 
-        if own_regions.iter().any(|r| matches!(r, RegionType::Water)) {
-            let paired_water = region_pairings.iter().filter(
-                |(own_region, neighbor_region)| match (neighbor_region, own_region) {
-                    (Some(RegionType::Water), RegionType::Water) => true,
-                    _ => false,
-                },
-            ).count();
+        for (edge, id) in &self.region_index {
+            assert!(self.connected_regions.contains_key(id), "{:?} is indexed to region {}, which was merged away", edge, id);
+        }
 
-            if paired_water < 1 {
-                return Err(InvalidTilePlacement::RiverMustBeConnected);
+        let mut owned_edges: HashMap<&PlacedTileEdge, ConnectedRegionId> = HashMap::new();
+        let mut owned_tile_regions: HashMap<(BoardCoordinate, RegionIndex), ConnectedRegionId> = HashMap::new();
+
+        for region in self.connected_regions.values() {
+            assert_eq!(self.find_region(region.id), region.id, "region {} is kept under an id that was merged away", region.id);
+            assert_eq!(
+                region.open_edge_count,
+                region.connected_edges.values().filter(|opposing| opposing.is_none()).count(),
+                "region {} has lost count of its open edges",
+                region.id
+            );
+
+            for tile_region in &region.tile_regions {
+                if let Some(other) = owned_tile_regions.insert((tile_region.tile_position, tile_region.region_index), region.id) {
+                    panic!("{:?} is in regions {} and {}", tile_region, other, region.id);
+                }
             }
 
-            if tile.tile != &RIVER_TERMINATOR {
-                let (_, prev_tile) = self.placed_tiles.last().expect("There should always be a last tile");
+            for (edge, opposing) in &region.connected_edges {
+                for edge in std::iter::once(edge).chain(opposing) {
+                    assert_eq!(self.region_index.get(edge), Some(&region.id), "{:?} is not indexed to its region", edge);
 
-                let direction_to_prev = tile.placement.coordinate.direction_to_adjacent_coordinate(prev_tile.placement.coordinate);
-
-                let current_heading = tile.get_opposite_river_end_direction(direction_to_prev);
-                let previous_source = prev_tile.get_opposite_river_end_direction(direction_to_prev.compass_opposite());
+                    if let Some(other) = owned_edges.insert(edge, region.id) {
+                        panic!("{:?} is in regions {} and {}", edge, other, region.id);
+                    }
+                }
 
-                if previous_source == current_heading {
-                    return Err(InvalidTilePlacement::RiverMustNotImmediatelyTurnOnItself);
+                match opposing {
+                    Some(opposing) => assert_eq!(opposing, &edge.opposing_tile_edge(&self.topology), "{:?} is joined to an edge it doesn't face", edge),
+                    // an edge is only left open while there's no tile on the other side of it, so a
+                    // region with a neighbour on every edge is never mistaken for an open one
+                    None => assert!(
+                        !self.placed_tiles.contains_key(&edge.opposing_tile_edge(&self.topology).coordinate),
+                        "{:?} is open with a tile on the other side of it",
+                        edge
+                    ),
                 }
             }
         }
 
-        if let Some((region_index, _)) = &tile.meeple {
-            // avoid recomputing tile regions if we have already done so previously
-            let tile_connected_regions = if let Some(tile_connected_regions) = tile_connected_regions {
-                tile_connected_regions
-            } else {
-                &tile.own_connected_regions(0)
-            };
+        let mut frontier: HashMap<BoardCoordinate, EdgeSignature> = HashMap::new();
 
-            let meeple_connected_regions = tile_connected_regions.iter().filter(|r| {
-                r.tile_regions.iter().any(|tr| tr.region_index == *region_index)
-            });
+        for tile in self.placed_tiles.values() {
+            for direction in PRIMARY_CARDINAL_DIRECTIONS {
+                let adjacent = self.topology.adjacent_in_direction(&tile.placement.coordinate, direction);
 
-            for connected_region in meeple_connected_regions {
-                let regions_to_merge = self.get_candidate_regions_to_merge(connected_region);
-                for region_id in regions_to_merge {
-                    let joined_region = self.connected_regions.get(&region_id).expect("should exist");
-                    if !joined_region.residents(self).is_empty() {
-                        return Err(InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion);
-                    }
+                if !self.placed_tiles.contains_key(&adjacent) {
+                    let signature = frontier.entry(adjacent).or_default();
+                    *signature = signature.with_edge(&direction.compass_opposite(), &tile.list_regions_on_edge(direction));
                 }
             }
         }
 
-        Ok(())
-    }
-
-    pub(crate) fn list_adjacent_tiles(
-        &self,
-        board_coordinate: &BoardCoordinate,
-    ) -> Vec<(CardinalDirection, Option<&PlacedTile>)> {
-        board_coordinate.adjacent_coordinates().into_iter().map(|(direction, coordinate)| (direction, self.placed_tiles.get(&coordinate))).collect()
-    }
-
-    pub(crate) fn list_surrounding_tiles(
-        &self,
-        board_coordinate: &BoardCoordinate,
-    ) -> Vec<&PlacedTile> {
-        board_coordinate.surrounding_coordinates().into_iter().filter_map(|coordinate| self.placed_tiles.get(&coordinate)).collect()
+        assert_eq!(self.frontier, frontier, "the frontier doesn't match the placed tiles");
     }
 
-    fn get_surrounding_regions(
-        &self,
-        board_coordinate: &BoardCoordinate,
-    ) -> Vec<Option<RegionType>> {
-        self.list_adjacent_tiles(board_coordinate).iter().flat_map(|(direction, tile)| {
-            if let Some(adjacent_tile) = tile {
-                adjacent_tile.list_regions_on_edge(&direction.compass_opposite()).into_iter().map(|region_type| Some(region_type.clone())).collect()
-            } else {
-                vec![None; 3]
-            }
-        }).collect()
+    /// Panics unless every player's meeple are either on the board or in their supply, given how
+    /// many each started with
+    pub(crate) fn assert_meeple_supply<'p, P: IntoIterator<Item = &'p Player>>(&self, players: P, starting_meeple: &HashMap<PlayerIdentifier, usize>) {
+        for player in players {
+            let on_board = self.placed_tiles.values().filter(|tile| matches!(&tile.meeple, Some((_, meeple)) if meeple.color == player.meeple_color)).count();
+
+            assert_eq!(
+                on_board + player.meeple.len(),
+                starting_meeple[&player.meeple_color],
+                "{:?} has {} meeple on the board and {} in hand",
+                player.meeple_color,
+                on_board,
+                player.meeple.len()
+            );
+        }
     }
+}
 
-    pub(crate) fn get_connected_regions(&self) -> Vec<&ConnectedRegion> {
-        self.connected_regions.values().collect()
+/// Checks the region exists and is one the meeple's kind can go in
+pub(crate) fn validate_meeple_region(tile: &TileDefinition, region_index: &RegionIndex, kind: &MeepleKind) -> Result<(), InvalidTilePlacement> {
+    match tile.regions.get(**region_index) {
+        None => Err(InvalidTilePlacement::InvalidMeeplePlacementIndex),
+        Some(Region::Water {..}) => Err(InvalidTilePlacement::MeepleCannotBePlacedInRiver),
+        Some(region) if !kind.can_occupy(&region.region_type()) => Err(InvalidTilePlacement::MeepleKindCannotOccupyRegion),
+        _ => Ok(())
     }
+}
 
-    pub(crate) fn render(&self, style: &RenderStyle) -> String {
-        if self.placed_tiles.is_empty() {
-            return "[Empty board]".to_string();
-        }
-
-        let mut min_x = i8::MAX;
-        let mut min_y = i8::MAX;
-        let mut max_x = i8::MIN;
-        let mut max_y = i8::MIN;
-
-        for &BoardCoordinate { x, y } in self.placed_tiles.keys() {
-            min_x = min_x.min(x);
-            min_y = min_y.min(y);
-            max_x = max_x.max(x);
-            max_y = max_y.max(y);
-        }
-
-        let mut output = Vec::with_capacity(((max_y - min_y) + 1) as usize * TILE_WIDTH);
-        // note we can't pre-allocate the width of the board as the color control chars make each
-        // row a different length depending on what regions are represented
-        output.extend(std::iter::repeat(String::new()).take(output.capacity()));
-
-        for (row_idx, row) in (min_y..=max_y).enumerate() {
-            for column in min_x..=max_x {
-                let coord = BoardCoordinate { x: column, y: row };
+/// Zobrist key of a tile where it lies, worked out from the tile rather than drawn from a random
+/// table so every build, on either end of a network game, agrees on it
+fn tile_key(tile: &PlacedTile) -> u64 {
+    let TilePlacement { coordinate, rotation } = &tile.placement;
+    let description: Vec<u8> = [0, coordinate.x as u8, coordinate.y as u8, rotation.quarter_turns()].into_iter().chain(tile.tile.name().bytes()).collect();
 
-                let lines = if let Some(tile) = self.placed_tiles.get(&coord) {
-                    tile.render_to_lines(&style)
-                } else {
-                    vec![" ".repeat(TILE_WIDTH * 2); TILE_WIDTH]
-                };
+    mix(fnv1a(&description))
+}
 
-                for (render_row, render) in lines.iter().enumerate() {
-                    let row_idx = row_idx * TILE_WIDTH + render_row;
+/// Zobrist key of a meeple in a region of the tile at `coordinate`, see [tile_key]
+fn meeple_key(coordinate: BoardCoordinate, region_index: RegionIndex, meeple: &Meeple) -> u64 {
+    mix(fnv1a(&[1, coordinate.x as u8, coordinate.y as u8, *region_index as u8, meeple.color.to_byte(), meeple.kind as u8]))
+}
 
-                    output.get_mut(row_idx).unwrap().push_str(render);
-                }
-            }
-        }
+/// Spreads a hash's bits, so keys of near identical descriptions share few when xor'd together
+fn mix(hash: u64) -> u64 {
+    let hash = (hash ^ (hash >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    let hash = (hash ^ (hash >> 27)).wrapping_mul(0x94d049bb133111eb);
 
-        output.join("\n")
-    }
+    hash ^ (hash >> 31)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::player::{MeepleColor, MeepleKind, RegionIndex};
+    use crate::score::{ScoringRules, StandardScoring};
     use crate::tile::RegionType::{Field, Road};
-    use crate::tile_definitions::{CLOISTER_IN_FIELD, CORNER_RIVER, CORNER_ROAD, STRAIGHT_RIVER, STRAIGHT_ROAD};
+    use crate::tile::{Rotation, TilePlacement};
+    use crate::tile_definitions::{CLOISTER_IN_FIELD, CORNER_RIVER, CORNER_ROAD, LAKE, RIVER_TERMINATOR, SIDE_CITY, STRAIGHT_RIVER, STRAIGHT_ROAD};
 
     #[test]
     fn test_valid_on_first_tile() {
@@ -373,11 +1320,10 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
-            None,
         ).is_ok())
     }
 
@@ -387,7 +1333,7 @@ mod tests {
             tile: &STRAIGHT_ROAD,
             placement: TilePlacement {
                 coordinate: BoardCoordinate { x: 0, y: 0 },
-                rotations: 0,
+                rotation: Rotation::R0,
             },
             meeple: None,
         }]).unwrap();
@@ -397,11 +1343,10 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
-            None,
         );
 
         assert!(matches!(
@@ -416,7 +1361,7 @@ mod tests {
             tile: &STRAIGHT_ROAD,
             placement: TilePlacement {
                 coordinate: BoardCoordinate { x: 0, y: 0 },
-                rotations: 0,
+                rotation: Rotation::R0,
             },
             meeple: None,
         }]).unwrap();
@@ -426,11 +1371,10 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 2, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
-            None,
         );
 
         assert!(matches!(
@@ -446,7 +1390,7 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
@@ -454,7 +1398,7 @@ mod tests {
                 tile: &CORNER_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: -1 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
@@ -462,7 +1406,7 @@ mod tests {
                 tile: &CORNER_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 1, y: -1 },
-                    rotations: 1,
+                    rotation: Rotation::R90,
                 },
                 meeple: None,
             },
@@ -470,27 +1414,108 @@ mod tests {
 
         // println!("{}", board.render());
 
-        let res = board.get_surrounding_regions(&BoardCoordinate { x: 1, y: 0 });
+        let res = board.frontier_signature(&BoardCoordinate { x: 1, y: 0 });
 
         assert_eq!(
             res,
-            vec![
-                Some(Field),
-                Some(Road),
-                Some(Field),
+            EdgeSignature::from_regions([
+                Some(&Field),
+                Some(&Road),
+                Some(&Field),
                 None,
                 None,
                 None,
                 None,
                 None,
                 None,
-                Some(Field),
-                Some(Field),
-                Some(Field)
-            ]
+                Some(&Field),
+                Some(&Field),
+                Some(&Field)
+            ])
         )
     }
 
+    #[test]
+    fn test_fields_find_the_city_their_neighbours_merged_into() {
+        // two halves of a city, each with a field behind it bordering its own half
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0),
+            PlacedTile::new(&SIDE_CITY, 0, 1, Rotation::R180),
+        ]).unwrap();
+
+        let fields: Vec<_> = board.get_connected_regions().into_iter().filter(|region| region.region_type == Field).collect();
+        assert_eq!(fields.len(), 2);
+
+        for field in fields {
+            let cities = board.adjacent_regions(field);
+
+            assert_eq!(cities.len(), 1);
+            assert!(cities[0].is_closed());
+            assert_eq!(cities[0].tile_regions.len(), 2);
+            assert_eq!(board.find_region(field.adjacent_regions.iter().next().copied().unwrap()), cities[0].id);
+        }
+    }
+
+    #[test]
+    fn test_lists_placed_tiles_and_their_bounds() {
+        assert_eq!(Board::new().bounding_box(), None);
+
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, 0, -1, Rotation::R0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0),
+        ]).unwrap();
+
+        let coordinates: Vec<_> = board.placed_tiles().map(|(coordinate, tile)| (coordinate.x(), coordinate.y(), tile.tile().name())).collect();
+
+        assert_eq!(coordinates, [(0, 0, STRAIGHT_ROAD.name()), (0, -1, STRAIGHT_ROAD.name()), (1, 0, CLOISTER_IN_FIELD.name())]);
+        assert_eq!(board.bounding_box(), Some((BoardCoordinate::new(0, -1), BoardCoordinate::new(1, 0))));
+    }
+
+    #[test]
+    fn test_frontier_follows_placed_tiles() {
+        let mut board = Board::new_with_tiles(vec![PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+
+        let frontier = |board: &Board| board.frontier_coordinates().copied().collect::<HashSet<_>>();
+
+        assert_eq!(frontier(&board), BoardCoordinate::new(0, 0).adjacent_coordinates().into_values().collect());
+
+        board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0)).unwrap();
+
+        assert_eq!(frontier(&board).len(), 6);
+        assert!(!frontier(&board).contains(&BoardCoordinate::new(1, 0)));
+
+        // the coordinate above the first road has to carry it on
+        let above = board.frontier_signature(&BoardCoordinate::new(0, -1));
+        assert!(!above.is_unknown());
+        assert!(STRAIGHT_ROAD.edge_signature(Rotation::R0).fits(&above));
+        assert!(!CLOISTER_IN_FIELD.edge_signature(Rotation::R0).fits(&above));
+    }
+
+    #[test]
+    fn test_frontier_lists_the_edges_each_coordinate_has_to_match() {
+        let board = Board::new_with_tiles(vec![PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0)]).unwrap();
+
+        let frontier = board.frontier();
+        assert_eq!(frontier.iter().map(|(coordinate, _)| *coordinate).collect::<Vec<_>>(), [
+            BoardCoordinate::new(0, -1),
+            BoardCoordinate::new(1, -1),
+            BoardCoordinate::new(-1, 0),
+            BoardCoordinate::new(2, 0),
+            BoardCoordinate::new(0, 1),
+            BoardCoordinate::new(1, 1),
+        ]);
+
+        // above the first road, only the south edge is known, and it carries the road on
+        let (_, above) = &frontier[0];
+        assert_eq!(above.south(), [Some(Field), Some(Road), Some(Field)]);
+        assert_eq!(above.north(), [None, None, None]);
+        assert_eq!(above.slots().iter().filter(|slot| slot.is_some()).count(), 3);
+        assert!(above.fits(&STRAIGHT_ROAD, Rotation::R0));
+        assert!(!above.fits(&STRAIGHT_ROAD, Rotation::R90));
+        assert!(!above.fits(&CLOISTER_IN_FIELD, Rotation::R0));
+    }
+
     #[test]
     fn test_invalid_if_tile_edges_do_not_match_placed_tiles() {
         let board = Board::new_with_tiles(vec![
@@ -498,7 +1523,7 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
@@ -506,7 +1531,7 @@ mod tests {
                 tile: &CORNER_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: -1 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
@@ -514,7 +1539,7 @@ mod tests {
                 tile: &CORNER_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 1, y: -1 },
-                    rotations: 1,
+                    rotation: Rotation::R90,
                 },
                 meeple: None,
             },
@@ -525,11 +1550,10 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 1, y: 0 },
-                    rotations: 1,
+                    rotation: Rotation::R90,
                 },
                 meeple: None,
             },
-            None,
         );
 
         assert!(matches!(
@@ -544,7 +1568,7 @@ mod tests {
             tile: &STRAIGHT_RIVER,
             placement: TilePlacement {
                 coordinate: BoardCoordinate { x: 0, y: 0 },
-                rotations: 0,
+                rotation: Rotation::R0,
             },
             meeple: None,
         }]).unwrap();
@@ -554,11 +1578,10 @@ mod tests {
                 tile: &STRAIGHT_RIVER,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 1, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: None,
             },
-            None,
         );
 
         assert!(matches!(
@@ -566,13 +1589,119 @@ mod tests {
             Err(InvalidTilePlacement::RiverMustBeConnected)
         ))
     }
+
+    #[test]
+    fn test_invalid_if_river_does_not_carry_on_from_the_last_tile() {
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_RIVER, 0, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_RIVER, 0, 1, Rotation::R0),
+        ]).unwrap();
+
+        // joins the river, but at the end that was laid first
+        let res = board.validate_tile_placement(&PlacedTile::new(&STRAIGHT_RIVER, 0, -1, Rotation::R0));
+
+        assert!(matches!(
+            res,
+            Err(InvalidTilePlacement::RiverMustBeConnected)
+        ))
+    }
+
+    #[test]
+    fn test_edges_of_a_torus_join_the_other_side() {
+        let meeple = (RegionIndex::new(1), Meeple::new(MeepleColor::Red));
+        let mut board = Board::new().with_topology(BoardTopology::Torus { width: 3, height: 3 });
+
+        board.place_tile(PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0, meeple)).unwrap();
+
+        let coordinates: HashSet<_> = board.get_move_hints(&CLOISTER_IN_FIELD, false).into_iter().map(|hint| hint.tile_placement.coordinate).collect();
+        assert_eq!(coordinates, HashSet::from([BoardCoordinate::new(1, 0), BoardCoordinate::new(2, 0), BoardCoordinate::new(0, 1), BoardCoordinate::new(0, 2)]));
+
+        let mut last = None;
+
+        for (x, y) in [(1, 0), (2, 0), (0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            last = Some(board.place_tile(PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0)).unwrap());
+        }
+
+        // every tile is surrounded once the nine are down, so the cloister is finished, and the
+        // field has no edge left open
+        let last = last.unwrap();
+        assert_eq!(last.score_delta.iter().collect::<Vec<_>>(), vec![(&MeepleColor::Red, &9)]);
+
+        let fields: Vec<_> = board.get_connected_regions().into_iter().filter(|region| region.region_type == Field).collect();
+        assert_eq!(fields.len(), 1);
+        assert!(fields[0].is_closed());
+    }
+
+    #[test]
+    fn test_tiles_are_kept_within_the_board_limit() {
+        let mut board = Board::new().with_limit(Some(BoardLimit { width: 2, height: 1 }));
+        board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R90)).unwrap();
+
+        let hints = board.get_move_hints(&STRAIGHT_ROAD, false);
+
+        assert!(!hints.is_empty());
+        assert!(hints.iter().all(|hint| hint.tile_placement.coordinate.y == 0));
+        assert!(matches!(board.validate_tile_placement(&PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R0)), Err(InvalidTilePlacement::OutsideBoardLimit)));
+
+        board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R90)).unwrap();
+
+        assert!(board.get_move_hints(&STRAIGHT_ROAD, false).is_empty());
+        assert!(!board.fits_frontier(&STRAIGHT_ROAD));
+    }
+
+    #[test]
+    fn test_failed_region_merge_leaves_the_bookkeeping_as_it_was() {
+        let mut board = Board::new();
+        board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)).unwrap();
+
+        let road = board.connected_regions.values().find(|region| *region.region_type() == Road).unwrap().clone();
+        let field = board.connected_regions.values().find(|region| *region.region_type() == Field).unwrap().clone();
+        let (parents, index) = (board.region_parents.clone(), board.region_index.clone());
+
+        assert!(matches!(board.union_regions(road, field), Err(InvalidTilePlacement::RegionsOutOfSync)));
+        assert_eq!(board.region_parents, parents);
+        assert_eq!(board.region_index, index);
+    }
+
+    #[test]
+    fn test_meeple_kept_out_of_fields_without_farmers() {
+        let mut board = Board::new().with_farmers(false);
+        board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)).unwrap();
+
+        let hints = board.get_move_hints(&STRAIGHT_ROAD, true);
+        let offered: Vec<_> = hints.iter().filter_map(|hint| hint.meeple_placement).map(|index| STRAIGHT_ROAD.regions[*index].region_type()).collect();
+
+        assert!(offered.contains(&Road));
+        assert!(!offered.contains(&Field));
+
+        let field = RegionIndex::new(STRAIGHT_ROAD.regions.iter().position(|region| region.region_type() == Field).unwrap());
+        let farmer = PlacedTile::new_with_meeple(&STRAIGHT_ROAD, 0, 1, Rotation::R0, (field, Meeple::new(MeepleColor::Red)));
+
+        assert!(matches!(board.place_tile(farmer.clone()), Err(InvalidTilePlacement::NoFarmers)));
+        assert!(Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), farmer]).is_ok());
+    }
+
+    #[test]
+    fn test_river_ends_only_kept_apart_when_the_rules_say() {
+        let spring = PlacedTile::new(&RIVER_TERMINATOR, 0, 0, Rotation::R0);
+        let lake = PlacedTile::new(&LAKE, 1, 0, Rotation::R180);
+
+        let board = Board::new_with_tiles([spring.clone()]).unwrap();
+        assert!(board.validate_tile_placement(&lake).is_ok());
+
+        let mut board = Board::new().with_separate_river_ends(true);
+        board.place_tile(spring).unwrap();
+
+        assert!(matches!(board.validate_tile_placement(&lake), Err(InvalidTilePlacement::RiverEndsMustNotTouch)));
+    }
+
     #[test]
     fn test_invalid_if_river_turns_on_itself() {
         let board = Board::new_with_tiles(vec![PlacedTile {
             tile: &CORNER_RIVER,
             placement: TilePlacement {
                 coordinate: BoardCoordinate { x: 0, y: 0 },
-                rotations: 0,
+                rotation: Rotation::R0,
             },
             meeple: None,
         }]).unwrap();
@@ -582,11 +1711,10 @@ mod tests {
                 tile: &CORNER_RIVER,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: -1 },
-                    rotations: 3,
+                    rotation: Rotation::R270,
                 },
                 meeple: None,
             },
-            None,
         );
 
         assert!(matches!(
@@ -601,7 +1729,7 @@ mod tests {
             tile: &STRAIGHT_ROAD,
             placement: TilePlacement {
                 coordinate: BoardCoordinate { x: 0, y: 0 },
-                rotations: 0,
+                rotation: Rotation::R0,
             },
             meeple: Some((RegionIndex::new(0), Meeple::dummy())),
         }]).unwrap();
@@ -611,11 +1739,10 @@ mod tests {
                 tile: &STRAIGHT_ROAD,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: -1 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: Some((RegionIndex::new(0), Meeple::dummy())),
             },
-            None,
         );
 
         assert!(matches!(
@@ -624,6 +1751,103 @@ mod tests {
         ))
     }
 
+    #[test]
+    fn test_placement_errors_compare_and_describe_themselves() {
+        let board = Board::new_with_tiles(vec![PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+
+        let error = board.validate_tile_placement(&PlacedTile::new(&STRAIGHT_ROAD, 5, 5, Rotation::R0)).unwrap_err();
+
+        assert_eq!(error, InvalidTilePlacement::TileDoesNotContactPlacedTiles);
+        assert_eq!(error.to_string(), "the tile has to be placed next to another tile");
+    }
+
+    #[test]
+    fn test_candidate_checked_like_the_tile_it_would_place() {
+        let board = Board::new_with_tiles(vec![PlacedTile::new_with_meeple(&STRAIGHT_ROAD, 0, 0, Rotation::R0, (RegionIndex::new(0), Meeple::dummy()))]).unwrap();
+
+        let placement = TilePlacement { coordinate: BoardCoordinate { x: 0, y: -1 }, rotation: Rotation::R0 };
+        let candidate = |meeple_region: Option<usize>| CandidatePlacement { tile: &STRAIGHT_ROAD, placement: &placement, meeple_region: meeple_region.map(RegionIndex::new) };
+
+        assert!(board.validate_candidate(&candidate(None)).is_ok());
+        assert!(board.validate_candidate(&candidate(Some(1))).is_ok());
+        assert!(matches!(board.validate_candidate(&candidate(Some(0))), Err(InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion)));
+        assert!(matches!(board.validate_candidate(&candidate(Some(5))), Err(InvalidTilePlacement::InvalidMeeplePlacementIndex)));
+
+        let rotated = TilePlacement { coordinate: BoardCoordinate { x: 0, y: -1 }, rotation: Rotation::R90 };
+        let candidate = CandidatePlacement { tile: &STRAIGHT_ROAD, placement: &rotated, meeple_region: None };
+
+        assert!(matches!(board.validate_candidate(&candidate), Err(InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles)));
+    }
+
+    #[test]
+    fn test_edge_conflict_found_where_the_tiles_meet() {
+        let board = Board::new_with_tiles(vec![PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+
+        // turned across the road it would meet from above
+        let rotated = TilePlacement { coordinate: BoardCoordinate { x: 0, y: -1 }, rotation: Rotation::R90 };
+        let conflict = board.edge_conflict(&STRAIGHT_ROAD, &rotated).unwrap();
+
+        assert_eq!(conflict.direction, CardinalDirection::South);
+        assert_eq!((conflict.own, conflict.neighbour_region), (RegionType::Field, RegionType::Road));
+        assert_eq!(conflict.neighbour.placement.coordinate, BoardCoordinate { x: 0, y: 0 });
+
+        let placement = TilePlacement { coordinate: BoardCoordinate { x: 0, y: -1 }, rotation: Rotation::R0 };
+        assert!(board.edge_conflict(&STRAIGHT_ROAD, &placement).is_none());
+    }
+
+    #[test]
+    fn test_builder_must_join_own_follower() {
+        let board = Board::new_with_tiles(vec![PlacedTile {
+            tile: &STRAIGHT_ROAD,
+            placement: TilePlacement {
+                coordinate: BoardCoordinate { x: 0, y: 0 },
+                rotation: Rotation::R0,
+            },
+            meeple: Some((RegionIndex::new(0), Meeple::new(MeepleColor::Red))),
+        }]).unwrap();
+
+        let extend_road = |meeple: Meeple, region_index: usize| board.validate_tile_placement(
+            &PlacedTile {
+                tile: &STRAIGHT_ROAD,
+                placement: TilePlacement {
+                    coordinate: BoardCoordinate { x: 0, y: -1 },
+                    rotation: Rotation::R0,
+                },
+                meeple: Some((RegionIndex::new(region_index), meeple)),
+            },
+        );
+
+        assert!(extend_road(Meeple::of_kind(MeepleColor::Red, MeepleKind::Builder), 0).is_ok());
+        assert!(matches!(
+            extend_road(Meeple::of_kind(MeepleColor::Green, MeepleKind::Builder), 0),
+            Err(InvalidTilePlacement::MeepleKindNeedsOwnFollower)
+        ));
+        assert!(matches!(
+            extend_road(Meeple::of_kind(MeepleColor::Red, MeepleKind::Builder), 1 /* a field */),
+            Err(InvalidTilePlacement::MeepleKindCannotOccupyRegion)
+        ));
+    }
+
+    #[test]
+    fn test_can_place_meeple_without_a_meeple_to_place() {
+        let board = Board::new_with_tiles(vec![PlacedTile::new_with_meeple(&STRAIGHT_ROAD, 0, 0, Rotation::R0, (RegionIndex::new(0), Meeple::dummy()))]).unwrap();
+
+        let placement = TilePlacement {
+            coordinate: BoardCoordinate { x: 0, y: -1 },
+            rotation: Rotation::R0,
+        };
+
+        assert!(matches!(
+            board.can_place_meeple(&STRAIGHT_ROAD, &placement, RegionIndex::new(0) /* the occupied road */),
+            Err(InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion)
+        ));
+        assert!(board.can_place_meeple(&STRAIGHT_ROAD, &placement, RegionIndex::new(1) /* a field */).is_ok());
+        assert!(matches!(
+            board.can_place_meeple(&STRAIGHT_ROAD, &placement, RegionIndex::new(3) /* no such index */),
+            Err(InvalidTilePlacement::InvalidMeeplePlacementIndex)
+        ));
+    }
+
     #[test]
     fn test_invalid_if_meeple_placed_in_invalid_region() {
         let board = Board::new();
@@ -633,11 +1857,10 @@ mod tests {
                 tile: &RIVER_TERMINATOR,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: Some((RegionIndex::new(1) /* the river */, Meeple::dummy())),
             },
-            None,
         );
 
         assert!(matches!(
@@ -650,11 +1873,10 @@ mod tests {
                 tile: &RIVER_TERMINATOR,
                 placement: TilePlacement {
                     coordinate: BoardCoordinate { x: 0, y: 0 },
-                    rotations: 0,
+                    rotation: Rotation::R0,
                 },
                 meeple: Some((RegionIndex::new(2) /* no such index */, Meeple::dummy())),
             },
-            None,
         );
 
         assert!(matches!(
@@ -666,22 +1888,204 @@ mod tests {
     #[test]
     fn test_meeple_are_liberated_when_region_closes() {
         let mut board = Board::new_with_tiles(vec![
-            PlacedTile::new_with_meeple(&CORNER_ROAD, -1, -1, 0, (RegionIndex::new(1) /* the outer field */, Meeple::dummy())),
-            PlacedTile::new_with_meeple(&STRAIGHT_ROAD, -1, 0, 0, (RegionIndex::new(1) /* the inner field */, Meeple::dummy())),
-            PlacedTile::new(&CORNER_ROAD, -1, 1, 3),
-            PlacedTile::new(&STRAIGHT_ROAD, 0, -1, 1),
-            PlacedTile::new_with_meeple(&CORNER_ROAD, 1, -1, 1, (RegionIndex::new(2) /* the road */, Meeple::dummy())),
-            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, 0),
-            PlacedTile::new(&CORNER_ROAD, 1, 1, 2),
-            PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, 0, (RegionIndex::new(1) /* the cloister */, Meeple::dummy())),
+            PlacedTile::new_with_meeple(&CORNER_ROAD, -1, -1, Rotation::R0, (RegionIndex::new(1) /* the outer field */, Meeple::dummy())),
+            PlacedTile::new_with_meeple(&STRAIGHT_ROAD, -1, 0, Rotation::R0, (RegionIndex::new(1) /* the inner field */, Meeple::dummy())),
+            PlacedTile::new(&CORNER_ROAD, -1, 1, Rotation::R270),
+            PlacedTile::new(&STRAIGHT_ROAD, 0, -1, Rotation::R90),
+            PlacedTile::new_with_meeple(&CORNER_ROAD, 1, -1, Rotation::R90, (RegionIndex::new(2) /* the road */, Meeple::dummy())),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, 1, 1, Rotation::R180),
+            PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0, (RegionIndex::new(1) /* the cloister */, Meeple::dummy())),
         ]).unwrap();
 
+        #[cfg(feature = "render")]
         println!("{}", board.render(&RenderStyle::Ascii));
 
-        let result = board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 1, 1)).expect("should succeed");
+        let result = board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R90)).expect("should succeed");
 
+        #[cfg(feature = "render")]
         println!("{}", board.render(&RenderStyle::Ascii));
 
         assert_eq!(result.liberated_meeple.len(), 3);
+
+        let mut returned: Vec<String> = result.liberated_meeple.iter().map(ToString::to_string).collect();
+        returned.sort();
+
+        // the ring of roads also encloses the inner field
+        assert_eq!(returned, [
+            "meeple returned from 8-tile road (8 pts)",
+            "meeple returned from 9-tile cloister (9 pts)",
+            "meeple returned from 9-tile field (0 pts)",
+        ]);
+    }
+
+    #[test]
+    fn test_index_moves_edges_over_to_the_region_kept_when_regions_merge() {
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, 2, 0, Rotation::R0),
+        ]).unwrap();
+
+        assert!((0..board.region_parents.len()).any(|id| board.find_region(id) != id), "no regions were merged");
+        assert!(board.region_index.values().all(|id| board.connected_regions.contains_key(id)));
+        assert_eq!(board.container_sizes().stale_index_entries, 0);
+    }
+
+    #[cfg(feature = "debug-validate")]
+    #[test]
+    #[should_panic(expected = "which was merged away")]
+    fn test_invariants_catch_an_edge_indexed_to_a_region_merged_away() {
+        let mut board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+        ]).unwrap();
+
+        let absorbed = (0..board.region_parents.len()).find(|id| board.find_region(*id) != *id).unwrap();
+        board.region_index.insert(PlacedTileEdge { coordinate: BoardCoordinate::new(1, 0), global_direction: CardinalDirection::West }, absorbed);
+
+        board.assert_invariants();
+    }
+
+    #[cfg(feature = "debug-validate")]
+    #[test]
+    #[should_panic(expected = "is not in any region")]
+    fn test_invariants_catch_an_edge_missing_from_the_index() {
+        let mut board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, 1, 0, Rotation::R0),
+        ]).unwrap();
+
+        board.region_index.remove(&PlacedTileEdge { coordinate: BoardCoordinate::new(1, 0), global_direction: CardinalDirection::West });
+
+        board.assert_invariants();
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_render_plain() {
+        let meeple = (RegionIndex::new(1), Meeple::new(MeepleColor::Red));
+        // the road's second tile leaves a gap under the city, which has to be as wide as a tile
+        let board = Board::new_with_tiles([
+            PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, meeple),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 1, Rotation::R0),
+        ])
+        .unwrap();
+
+        let rendered = board.render_plain();
+        let lines: Vec<_> = rendered.lines().collect();
+
+        assert!(!rendered.contains('\x1b'));
+        assert_eq!(lines.len(), 2 * TILE_WIDTH);
+        assert!(lines.iter().all(|line| line.chars().count() == 2 * TILE_WIDTH * 2));
+        assert_eq!(rendered.matches("ꆜR").count(), 1);
+        assert_eq!(rendered, board.render_plain());
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_draw_a_legend_of_whose_meeple_are_whose_under_the_board() {
+        let board = Board::new_with_tiles([PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Red)))]).unwrap();
+        let mut alice = Player::red().with_name("Alice");
+        alice.meeple.pop();
+        let players: IndexMap<_, _> = [(MeepleColor::Red, alice), (MeepleColor::Green, Player::green())].into_iter().collect();
+
+        let plain = board.render_annotated(&RenderStyle::Ascii, &players, &RenderAnnotations::default());
+        let annotated = board.render_annotated(&RenderStyle::Ascii, &players, &RenderAnnotations::default().with_legend());
+
+        assert_eq!(plain, board.render(&RenderStyle::Ascii));
+        assert_eq!(annotated, format!("{}\nꆜR Alice 6 left   ꆜG Green 7 left", plain));
+        assert!(board.render_annotated(&RenderStyle::TrueColor, &players, &RenderAnnotations::default().with_legend()).ends_with("Green 7 left"));
+    }
+
+    #[test]
+    fn should_report_the_regions_a_placement_joined_and_closed() {
+        let mut board = Board::new();
+
+        let opened = board.place_tile(PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)).unwrap();
+        assert_eq!(opened.joined_regions.len(), 2);
+        assert!(opened.closed_regions.is_empty());
+
+        let closed = board.place_tile(PlacedTile::new(&SIDE_CITY, 0, 1, Rotation::R180)).unwrap();
+        assert_eq!(closed.closed_regions.len(), 1);
+
+        let city = board.connected_region(closed.closed_regions[0]).unwrap();
+        assert_eq!(city.region_type(), &RegionType::City);
+        assert!(city.is_closed());
+        assert!(closed.joined_regions.contains(&closed.closed_regions[0]));
+        assert!(opened.joined_regions.iter().any(|id| board.connected_region(*id).is_some_and(|region| region.is_closed())));
+    }
+
+    #[test]
+    fn should_report_completed_cloisters_with_or_without_meeple() {
+        let mut board = Board::new();
+
+        for (x, y) in [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)] {
+            assert!(board.place_tile(PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0)).unwrap().completed_cloisters.is_empty());
+        }
+
+        let success = board.place_tile(PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0)).unwrap();
+
+        assert_eq!(success.completed_cloisters, vec![BoardCoordinate::new(0, 0)]);
+    }
+
+    #[test]
+    fn should_score_a_cloister_laid_into_a_gap_already_surrounded() {
+        let mut board = Board::new();
+
+        for (x, y) in [(-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)] {
+            board.place_tile(PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0)).unwrap();
+        }
+
+        let success = board.place_tile(PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Blue)))).unwrap();
+
+        assert_eq!(success.score_delta, [(MeepleColor::Blue, 9)].into_iter().collect::<Score>());
+        assert_eq!(success.liberated_meeple.len(), 1);
+        assert!(board.placed_tiles().all(|(_, tile)| tile.meeple.is_none()));
+    }
+
+    /// Cloisters score a point more than usual
+    struct GenerousCloisters;
+
+    impl ScoringRules for GenerousCloisters {
+        fn name(&self) -> &'static str {
+            "generous cloisters"
+        }
+
+        fn score_cloister(&self, region: &ConnectedRegion, board: &Board) -> u32 {
+            StandardScoring.score_cloister(region, board) + 1
+        }
+    }
+
+    #[test]
+    fn should_score_a_completed_cloister_under_the_boards_scoring() {
+        let mut board = Board::new().with_scoring(Scoring::new(GenerousCloisters));
+
+        board.place_tile(PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Blue)))).unwrap();
+
+        let surroundings = [(0, -1), (-1, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0)];
+        let completed = surroundings.into_iter().map(|(x, y)| board.place_tile(PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0)).unwrap()).last().unwrap();
+
+        assert_eq!(completed.score_delta, [(MeepleColor::Blue, 10)].into_iter().collect::<Score>());
+        assert_eq!(completed.completed_features[0].points, 10);
+        assert_eq!(completed.liberated_meeple[0].points, 10);
+    }
+
+    #[test]
+    fn test_zobrist_hash_follows_the_tiles_and_meeple_on_the_board() {
+        let farmer = PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(0), Meeple::new(MeepleColor::Red)));
+
+        let mut farmed = Board::new_with_tiles([farmer, PlacedTile::new(&SIDE_CITY, 1, 0, Rotation::R0)]).unwrap();
+        let unfarmed = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, 1, 0, Rotation::R0)]).unwrap();
+        let laid_the_other_way = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 1, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+
+        assert_eq!(unfarmed.zobrist_hash(), laid_the_other_way.zobrist_hash());
+        assert_ne!(farmed.zobrist_hash(), unfarmed.zobrist_hash());
+        assert_ne!(unfarmed.zobrist_hash(), Board::new().zobrist_hash());
+
+        farmed.recall_meeple(&BoardCoordinate::new(0, 0), RegionIndex::new(0), MeepleColor::Red).unwrap();
+
+        assert_eq!(farmed.zobrist_hash(), unfarmed.zobrist_hash());
     }
 }
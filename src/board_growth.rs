@@ -0,0 +1,133 @@
+use crate::board::BoardSizes;
+use crate::observer::{GameEvent, GameObserver};
+use crate::replay::ReplayState;
+use crate::stats::{follow, replay_turn};
+use std::io::{self, Write};
+
+/// Follows the board through every game, recording how many entries its containers hold after
+/// each turn, and writes them out as a table once the game ends along with any turn whose
+/// containers outgrew its tiles. For hunting state the board keeps hold of when it shouldn't, such
+/// as index entries left behind when regions merge
+pub struct BoardGrowth<W> {
+    output: W,
+    state: Option<ReplayState>,
+    /// The sizes after each turn of the game being played
+    sizes: Vec<BoardSizes>,
+    /// The game, turn and description of every problem seen, over all the games
+    pub problems: Vec<(usize, usize, String)>,
+}
+
+impl BoardGrowth<io::Stderr> {
+    pub fn stderr() -> Self {
+        Self::new(io::stderr())
+    }
+}
+
+impl<W: Write> BoardGrowth<W> {
+    pub fn new(output: W) -> Self {
+        Self { output, state: None, sizes: vec![], problems: vec![] }
+    }
+
+    fn dump(&mut self, game_index: usize) -> io::Result<()> {
+        writeln!(self.output, "Game {} board sizes", game_index)?;
+        writeln!(self.output, "{:>5} {:>6} {:>8} {:>6} {:>7} {:>6} {:>8} {:>9}", "turn", "tiles", "regions", "stale", "edges", "index", "parents", "frontier")?;
+
+        for (turn, sizes) in self.sizes.iter().enumerate() {
+            writeln!(
+                self.output,
+                "{:>5} {:>6} {:>8} {:>6} {:>7} {:>6} {:>8} {:>9}",
+                turn + 1,
+                sizes.tiles,
+                sizes.regions,
+                sizes.stale_regions,
+                sizes.region_edges,
+                sizes.region_index,
+                sizes.region_parents,
+                sizes.frontier
+            )?;
+        }
+
+        for (_, turn, problem) in self.problems.iter().filter(|(game, _, _)| *game == game_index) {
+            writeln!(self.output, "Turn {}: {}", turn, problem)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<W: Write> GameObserver for BoardGrowth<W> {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { players, rules, .. } => {
+                self.state = Some(follow(players.iter().map(|(color, _, _)| *color), rules));
+                self.sizes.clear();
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                let Some(state) = &mut self.state else {
+                    return;
+                };
+
+                if state.apply(&replay_turn(*player, tile, placement)).is_err() {
+                    return;
+                }
+
+                let sizes = state.board.container_sizes();
+
+                for problem in sizes.problems() {
+                    self.problems.push((*game_index, state.turn, problem));
+                }
+
+                self.sizes.push(sizes);
+            }
+            GameEvent::MeepleRecalled { player, coordinate, region_index, .. } => {
+                if let Some(state) = &mut self.state {
+                    state.recall_meeple(*player, coordinate, *region_index);
+                }
+            }
+            GameEvent::GameCompleted { game_index, .. } => {
+                self.state = None;
+
+                // the sizes are only for looking over, so a closed output doesn't stop the games
+                let _ = self.dump(*game_index);
+            }
+            GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::bot_strategy::{BotStrategy, MyopicBot};
+    use crate::player::Player;
+    use crate::simulate::{self, SimulationConfig};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn should_dump_the_board_sizes_after_every_turn() {
+        let players = [Player::red(), Player::green()].map(|player| player.with_bot(BotStrategy::Myopic(MyopicBot)));
+        let growth = Rc::new(RefCell::new(BoardGrowth::new(vec![])));
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(Rc::clone(&growth))];
+
+        simulate::run_observed(SimulationConfig::new(players, 1, [7; 32]).unwrap(), &mut observers);
+
+        let growth = growth.borrow();
+        let dump = String::from_utf8(growth.output.clone()).unwrap();
+
+        assert!(growth.problems.is_empty(), "{:?}", growth.problems);
+        assert!(dump.starts_with("Game 0 board sizes\n"), "{}", dump);
+        assert_eq!(dump.lines().count(), growth.sizes.len() + 2);
+        assert!(growth.sizes.windows(2).all(|pair| pair[1].tiles >= pair[0].tiles));
+    }
+
+    #[test]
+    fn should_flag_containers_that_outgrow_the_tiles() {
+        assert!(Board::default().container_sizes().problems().is_empty());
+
+        let sizes = BoardSizes { tiles: 2, region_index: 30, region_edges: 24, stale_regions: 1, ..Default::default() };
+
+        assert_eq!(sizes.problems(), ["region_index has 30 entries for the 24 edges of 2 tiles", "1 regions are kept under ids merged away"]);
+    }
+}
@@ -1,14 +1,46 @@
-use std::ops::Deref;
 use rand::prelude::SliceRandom;
-use rand::Rng;
-use rand::rngs::StdRng;
-use crate::board::Board;
-use crate::move_hints::MoveHint;
-use crate::player::Player;
-use crate::tile::{PlacedTile, TileDefinition};
+use rand::{Rng, SeedableRng};
+use crate::analysis::{can_be_completed, evaluate, farm_equity, unseen_tiles};
+use crate::board::{Board, LiberatedMeeple};
+use crate::game_logic::GamePhase;
+use crate::move_hints::{MeepleContext, MoveHint};
+use crate::observer::GameEvent;
+use crate::player::{Meeple, Player, PlayerIdentifier};
+use crate::rng::GameRng;
+use crate::tie_break;
+use crate::score::Score;
+use crate::tile::{PlacedTile, RegionType, TileDefinition};
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
 
 pub trait Bot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint>;
+    /// Picks one of the legal moves in `hints`, or `None` to leave the tile unplaced
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint>;
+
+    /// How much the bot likes each of `hints`, higher being better, so bots' opinions can be
+    /// combined. Bots that don't score moves only like the move they would pick
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        let chosen = self.select_hint(board, player, hints);
+
+        hints
+            .iter()
+            .map(|hint| match &chosen {
+                Some(chosen) if chosen.tile_placement == hint.tile_placement && chosen.meeple_placement == hint.meeple_placement => 1.0,
+                _ => 0.0,
+            })
+            .collect()
+    }
+
+    /// Called with each event of the game being played, opponents' moves included, so a bot can
+    /// learn how the others play as the game goes on. Most bots only look at the board
+    fn observe(&mut self, _event: &GameEvent) {}
+
+    /// Name of the bot, for telling players who they're up against, if it has one
+    fn bot_name(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -18,7 +50,7 @@ pub struct BotPlayer {
 }
 
 impl Player {
-    pub(crate) fn with_bot(self, bot: BotStrategy) -> BotPlayer {
+    pub fn with_bot(self, bot: BotStrategy) -> BotPlayer {
         BotPlayer { player: self, bot }
     }
 }
@@ -31,59 +63,208 @@ pub enum BotStrategy {
     Jerk(JerkBot),
     ScoreRanking(ScoreRankingBot),
     Lazy(LazyBot),
+    Team(TeamBot),
+    Blocker(BlockerBot),
+    Farmer(FarmerBot),
+    MonteCarlo(MonteCarloBot),
+    Expectimax(ExpectimaxBot),
+}
+
+/// Names the selectable bots can be looked up by, e.g. from the command line
+pub const BOT_NAMES: &[&str] = &["rando", "myopic", "fill-the-grid", "jerk", "lazy", "team", "blocker", "farmer", "monte-carlo", "expectimax"];
+
+impl BotStrategy {
+    /// Builds the bot with the given name (see [BOT_NAMES]), seeding any randomness it needs from
+    /// `rng`
+    pub fn from_name(name: &str, rng: &mut GameRng) -> Option<Self> {
+        let bot = match name {
+            "rando" => BotStrategy::Rando(RandoBot::new(GameRng::from_rng(rng).ok()?)),
+            "myopic" => BotStrategy::Myopic(MyopicBot),
+            "fill-the-grid" => BotStrategy::FillTheGrid(FillTheGridBot::new(GameRng::from_rng(rng).ok()?)),
+            "jerk" => BotStrategy::Jerk(JerkBot),
+            "lazy" => BotStrategy::Lazy(LazyBot),
+            "team" => BotStrategy::Team(TeamBot::default()),
+            "blocker" => BotStrategy::Blocker(BlockerBot),
+            "farmer" => BotStrategy::Farmer(FarmerBot),
+            "monte-carlo" => BotStrategy::MonteCarlo(MonteCarloBot::new(GameRng::from_rng(rng).ok()?)),
+            "expectimax" => BotStrategy::Expectimax(ExpectimaxBot::default()),
+            _ => return None,
+        };
+
+        Some(bot)
+    }
+
+    /// Has the bot play river tiles by the [RiverOpening] policy
+    pub fn with_river_opening(self) -> RiverOpening<Self> {
+        RiverOpening::new(self)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BotStrategy::Rando(_) => "rando",
+            BotStrategy::Myopic(_) => "myopic",
+            BotStrategy::FillTheGrid(_) => "fill-the-grid",
+            BotStrategy::Jerk(_) => "jerk",
+            BotStrategy::ScoreRanking(_) => "score-ranking",
+            BotStrategy::Lazy(_) => "lazy",
+            BotStrategy::Team(_) => "team",
+            BotStrategy::Blocker(_) => "blocker",
+            BotStrategy::Farmer(_) => "farmer",
+            BotStrategy::MonteCarlo(_) => "monte-carlo",
+            BotStrategy::Expectimax(_) => "expectimax",
+        }
+    }
+
+    /// The settings the bot plays with beyond its name, e.g. how many samples it takes, so bots
+    /// set up differently can be told apart. Empty for bots without any
+    pub fn parameters(&self) -> String {
+        match self {
+            BotStrategy::MonteCarlo(bot) => format!("samples={} horizon={}", bot.samples, bot.horizon.map_or("all".to_string(), |tiles| tiles.to_string())),
+            BotStrategy::Expectimax(bot) => format!("budget={}ms", bot.budget.as_millis()),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Why a bot's setting couldn't be changed by [BotStrategy::with_parameter]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParameterError {
+    /// The bot has no setting of that name. Lists those it has, if any
+    Unknown { bot: &'static str, parameter: String, known: &'static [&'static str] },
+    /// The setting can't take the value, e.g. a negative budget
+    InvalidValue { parameter: String, value: f64 },
+    /// The setting counts something, e.g. samples, so a fraction of one makes no sense. None of the
+    /// bots has a setting that takes fractions
+    NotWhole { parameter: String, value: f64 },
+}
+
+impl Display for ParameterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParameterError::Unknown { bot, parameter, known: [] } => write!(f, "{} has no parameters, so no [{}]", bot, parameter),
+            ParameterError::Unknown { bot, parameter, known } => write!(f, "{} has no parameter [{}], expected one of: {}", bot, parameter, known.join(", ")),
+            ParameterError::InvalidValue { parameter, value } => write!(f, "{} can't be {}", parameter, value),
+            ParameterError::NotWhole { parameter, value } => write!(f, "{} only takes whole numbers, so not {}", parameter, value),
+        }
+    }
+}
+
+impl BotStrategy {
+    /// The settings [BotStrategy::with_parameter] can change
+    pub fn parameter_names(&self) -> &'static [&'static str] {
+        match self {
+            BotStrategy::MonteCarlo(_) => &["samples", "horizon"],
+            BotStrategy::Expectimax(_) => &["budget"],
+            _ => &[],
+        }
+    }
+
+    /// The bot with one of its [BotStrategy::parameter_names] set to `value`, for tuning it. Counts
+    /// of samples and tiles have to be whole, and budgets are in milliseconds
+    pub fn with_parameter(self, parameter: &str, value: f64) -> Result<Self, ParameterError> {
+        let count = || match value {
+            _ if value.fract() != 0.0 => Err(ParameterError::NotWhole { parameter: parameter.to_string(), value }),
+            _ if value < 0.0 => Err(ParameterError::InvalidValue { parameter: parameter.to_string(), value }),
+            _ => Ok(value as usize),
+        };
+
+        match (self, parameter) {
+            (BotStrategy::MonteCarlo(bot), "samples") => Ok(BotStrategy::MonteCarlo(bot.with_samples(count()?))),
+            (BotStrategy::MonteCarlo(bot), "horizon") => Ok(BotStrategy::MonteCarlo(bot.with_horizon(count()?))),
+            (BotStrategy::Expectimax(_), "budget") => Ok(BotStrategy::Expectimax(ExpectimaxBot::with_budget(Duration::from_millis(count()? as u64)))),
+            (bot, _) => Err(ParameterError::Unknown { bot: bot.name(), parameter: parameter.to_string(), known: bot.parameter_names() }),
+        }
+    }
 }
 
 impl Bot for BotStrategy {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        match self {
+            BotStrategy::Rando(b)  => b.select_hint(board, player, hints),
+            BotStrategy::Myopic(b)  => b.select_hint(board, player, hints),
+            BotStrategy::FillTheGrid(b) => b.select_hint(board, player, hints),
+            BotStrategy::Jerk(b) => b.select_hint(board, player, hints),
+            BotStrategy::ScoreRanking(b) => b.select_hint(board, player, hints),
+            BotStrategy::Lazy(b) => b.select_hint(board, player, hints),
+            BotStrategy::Team(b) => b.select_hint(board, player, hints),
+            BotStrategy::Blocker(b) => b.select_hint(board, player, hints),
+            BotStrategy::Farmer(b) => b.select_hint(board, player, hints),
+            BotStrategy::MonteCarlo(b) => b.select_hint(board, player, hints),
+            BotStrategy::Expectimax(b) => b.select_hint(board, player, hints),
+        }
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        match self {
+            BotStrategy::Rando(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Myopic(b) => b.rate_hints(board, player, hints),
+            BotStrategy::FillTheGrid(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Jerk(b) => b.rate_hints(board, player, hints),
+            BotStrategy::ScoreRanking(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Lazy(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Team(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Blocker(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Farmer(b) => b.rate_hints(board, player, hints),
+            BotStrategy::MonteCarlo(b) => b.rate_hints(board, player, hints),
+            BotStrategy::Expectimax(b) => b.rate_hints(board, player, hints),
+        }
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
         match self {
-            BotStrategy::Rando(b)  => b.select_hint(board, player, tile),
-            BotStrategy::Myopic(b)  => b.select_hint(board, player, tile),
-            BotStrategy::FillTheGrid(b) => b.select_hint(board, player, tile),
-            BotStrategy::Jerk(b) => b.select_hint(board, player, tile),
-            BotStrategy::ScoreRanking(b) => b.select_hint(board, player, tile),
-            BotStrategy::Lazy(b) => b.select_hint(board, player, tile),
+            BotStrategy::Rando(b) => b.observe(event),
+            BotStrategy::Myopic(b) => b.observe(event),
+            BotStrategy::FillTheGrid(b) => b.observe(event),
+            BotStrategy::Jerk(b) => b.observe(event),
+            BotStrategy::ScoreRanking(b) => b.observe(event),
+            BotStrategy::Lazy(b) => b.observe(event),
+            BotStrategy::Team(b) => b.observe(event),
+            BotStrategy::Blocker(b) => b.observe(event),
+            BotStrategy::Farmer(b) => b.observe(event),
+            BotStrategy::MonteCarlo(b) => b.observe(event),
+            BotStrategy::Expectimax(b) => b.observe(event),
         }
     }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        Some(self.name())
+    }
 }
 
 /// This bot picks a hint entirely at random
 #[derive(Clone)]
-pub(crate) struct RandoBot(StdRng);
+pub struct RandoBot(GameRng);
 
 impl RandoBot {
-    pub(crate) fn new(rng: StdRng) -> Self {
+    pub fn new(rng: GameRng) -> Self {
         Self(rng)
     }
 }
 
 impl Bot for RandoBot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
-        let mut move_hints = board.get_move_hints(tile, true);
-        move_hints.shuffle(&mut self.0);
-        move_hints.pop()
+    fn select_hint(&mut self, _board: &Board, _player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        tie_break::random(hints, &mut self.0).cloned()
     }
 
 }
 
-/// This bot is only interested in filling gaps in the grid. It otherwise places meeples and tiles
-/// at random
+/// This bot is only interested in filling gaps in the grid. It otherwise places tiles at random,
+/// and meeple where they score more than they are worth kept in hand
 #[derive(Clone)]
-pub(crate) struct FillTheGridBot(StdRng);
+pub struct FillTheGridBot(GameRng);
 
 impl FillTheGridBot {
-    pub(crate) fn new(rng: StdRng) -> Self {
+    pub fn new(rng: GameRng) -> Self {
         Self(rng)
     }
 }
 
 impl Bot for FillTheGridBot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
-
-        let mut move_hints = board.get_move_hints(tile, true);
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
 
-        move_hints.shuffle(&mut self.0);
+        let context = MeepleContext::new(board, player);
 
-        move_hints.into_iter().max_by_key(|hint| {
+        tie_break::best_by_key(hints, Some(&mut self.0), |hint| {
 
             let adjacent_region_count = board
                 .list_adjacent_tiles(&hint.tile_placement.coordinate)
@@ -91,13 +272,13 @@ impl Bot for FillTheGridBot {
                 .filter_map(|(_, t)| *t)
                 .count();
 
-            let meeple_placement = match hint.meeple_placement {
-                Some(_) => 1,
-                None => 0,
+            let meeple_placement = match hint.meeple_worth_placing(&context) {
+                true => 1,
+                false => 0,
             };
 
             adjacent_region_count + meeple_placement
-        })
+        }).cloned()
 
     }
 
@@ -106,78 +287,977 @@ impl Bot for FillTheGridBot {
 /// This bot looks only at its own score change on a single tile placement; ignoring all other
 /// player scores
 #[derive(Clone)]
-pub(crate) struct MyopicBot;
-
-impl Bot for MyopicBot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
-        let move_hints = board.get_move_hints(tile, true);
+pub struct MyopicBot;
 
-        move_hints.into_iter().max_by_key(|hint|{
+impl MyopicBot {
+    fn rating(board: &Board, player: &Player, hint: &MoveHint) -> Option<i32> {
+        let score = hint.score_delta(board, player, true);
 
-            let score = hint.score_delta(board, player, true);
-
-            score.get_player(player).copied()
+        score.get_player(player).copied()
+    }
+}
 
-        })
+impl Bot for MyopicBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        tie_break::best_by_key(hints, None, |hint| Self::rating(board, player, hint)).cloned()
+    }
 
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        hints.iter().map(|hint| Self::rating(board, player, hint).unwrap_or(0) as f64).collect()
     }
 }
 
 
 #[derive(Clone)]
-pub(crate) struct ScoreRankingBot;
+pub struct ScoreRankingBot;
 
 impl Bot for ScoreRankingBot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
+    fn select_hint(&mut self, _board: &Board, _player: &Player, _hints: &[MoveHint]) -> Option<MoveHint> {
         todo!()
     }
 }
 
 /// This bot looks only at how it can make other's score worse. It won't place meeple otherwise
 #[derive(Clone)]
-pub(crate) struct JerkBot;
+pub struct JerkBot;
+
+impl JerkBot {
+    fn rating(board: &Board, player: &Player, hint: &MoveHint) -> (i32, i32) {
+        let score = hint.score_delta(board, player, true);
+
+        let mut weight = 0;
+
+        for (player_id, score) in score.iter() {
+            if player_id != &player.meeple_color {
+                weight += score
+            }
+        }
+
+        let meeple_modifier = if hint.meeple_placement.is_some() && weight > 0 {
+            1
+        } else {
+            0
+        };
+
+        (weight, meeple_modifier)
+    }
+}
 
 impl Bot for JerkBot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
-        let move_hints = board.get_move_hints(tile, true);
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        tie_break::best_by_key(hints, None, |hint| Self::rating(board, player, hint)).cloned()
+    }
 
-        move_hints.into_iter().max_by_key(|hint|{
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        // the meeple modifier only breaks ties
+        hints
+            .iter()
+            .map(|hint| {
+                let (weight, meeple_modifier) = Self::rating(board, player, hint);
+                weight as f64 + meeple_modifier as f64 / 2.0
+            })
+            .collect()
+    }
+}
 
-            let score = hint.score_delta(board, player, true);
 
-            let mut weight = 0;
+/// This bot finds the first valid move it spots, biasing to place meeple (otherwise it would never
+/// score) where they score more than they are worth kept in hand
+#[derive(Clone)]
+pub struct LazyBot;
 
-            for (player_id, score) in score.iter() {
-                if player_id != &player.meeple_color {
-                    weight += score
-                }
-            }
+impl LazyBot {
+    fn rating(hint: &MoveHint, context: &MeepleContext) -> i32 {
+        if hint.meeple_worth_placing(context) {
+            1
+        } else {
+            0
+        }
+    }
+}
 
-            let meeple_modifier = if hint.meeple_placement.is_some() && weight > 0 {
-                1
-            } else {
-                0
-            };
+impl Bot for LazyBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        let context = MeepleContext::new(board, player);
+
+        tie_break::best_by_key(hints, None, |hint| Self::rating(hint, &context)).cloned()
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        let context = MeepleContext::new(board, player);
+
+        hints.iter().map(|hint| Self::rating(hint, &context) as f64).collect()
+    }
+}
+
+/// This bot plays for its team: it weighs the points a move gives it and its partners against the
+/// most it gives any opponent, so it won't take points from a partner to deny an opponent. Without
+/// partners it plays for itself alone
+#[derive(Clone, Default)]
+pub struct TeamBot {
+    partners: Vec<PlayerIdentifier>,
+}
+
+impl TeamBot {
+    pub fn with_partners<I: IntoIterator<Item = PlayerIdentifier>>(mut self, partners: I) -> Self {
+        self.partners = partners.into_iter().collect();
+        self
+    }
+}
+
+impl TeamBot {
+    fn rating(&self, board: &Board, player: &Player, hint: &MoveHint) -> i32 {
+        let score = hint.score_delta(board, player, true);
+
+        let team = score
+            .iter()
+            .filter(|(id, _)| **id == player.meeple_color || self.partners.contains(id))
+            .map(|(_, points)| *points)
+            .sum::<i32>();
+
+        let best_opponent = score
+            .iter()
+            .filter(|(id, _)| **id != player.meeple_color && !self.partners.contains(id))
+            .map(|(_, points)| *points)
+            .max()
+            .unwrap_or(0);
+
+        team - best_opponent
+    }
+}
+
+impl Bot for TeamBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        tie_break::best_by_key(hints, None, |hint| self.rating(board, player, hint)).cloned()
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        hints.iter().map(|hint| self.rating(board, player, hint) as f64).collect()
+    }
+}
+
+/// The board once `hint` is played, with the points it scores each player if the game then ended
+fn after_move(board: &Board, player: &Player, hint: &MoveHint) -> (Board, Score) {
+    let mut after = board.clone();
 
-            (weight, meeple_modifier)
+    let success = after
+        .place_tile(PlacedTile {
+            tile: hint.tile,
+            placement: hint.tile_placement.clone(),
+            meeple: hint.meeple_placement.map(|region_index| (region_index, Meeple::new(player.meeple_color))),
         })
+        .expect("should be a valid move");
+
+    let score = (after.calculate_board_score() + success.score_delta) - board.calculate_board_score();
+
+    (after, score)
+}
+
+/// This bot plays to stop opponents finishing their cities. Beyond the points a move scores it
+/// against the most it scores any opponent, it counts the points in opponents' open cities that the
+/// move leaves with a gap none of the unseen tiles would fit, as those cities can never score double
+#[derive(Clone)]
+pub struct BlockerBot;
+
+impl BlockerBot {
+    fn rating(board: &Board, player: &Player, hint: &MoveHint) -> i32 {
+        let (after, score) = after_move(board, player, hint);
+
+        let own = score.get_player(player).copied().unwrap_or(0);
+        let best_opponent = score.iter().filter(|(id, _)| **id != player.meeple_color).map(|(_, points)| *points).max().unwrap_or(0);
 
+        let deck_remaining = unseen_tiles(&after);
+
+        let blocked: u32 = after
+            .get_connected_regions()
+            .into_iter()
+            .filter(|region| region.region_type == RegionType::City && !region.is_closed())
+            .filter(|region| {
+                let majority = region.majority_meeple_player_ids(&after);
+                !majority.is_empty() && !majority.contains(&player.meeple_color)
+            })
+            .filter(|region| !can_be_completed(&after, region, &deck_remaining))
+            .map(|region| region.score(&after))
+            .sum();
+
+        own - best_opponent + blocked as i32
     }
 }
 
+impl Bot for BlockerBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        tie_break::best_by_key(hints, None, |hint| Self::rating(board, player, hint)).cloned()
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        hints.iter().map(|hint| Self::rating(board, player, hint) as f64).collect()
+    }
+}
 
-/// This bot finds the first valid move it spots, biasing to place meeple (otherwise it would never score)
+/// This bot plays for the farms. On top of the points a move would score it if the game ended now,
+/// it counts the points its farmers expect from the open cities bordering their fields, weighted by
+/// the chance the unseen tiles complete each one, so it will put farmers down early
 #[derive(Clone)]
-pub(crate) struct LazyBot;
+pub struct FarmerBot;
 
-impl Bot for LazyBot {
-    fn select_hint(&mut self, board: &Board, player: &Player, tile: &'static TileDefinition) -> Option<MoveHint> {
-        board.get_move_hints(tile, true).into_iter().max_by_key(|hint|{
-            if hint.meeple_placement.is_some() {
-                1
+impl FarmerBot {
+    fn rating(board: &Board, player: &Player, hint: &MoveHint) -> f64 {
+        let (after, score) = after_move(board, player, hint);
+        let own = score.get_player(player).copied().unwrap_or(0) as f64;
+
+        // the fields' points so far are already in the score, as if the game ended now
+        let farms_now = farm_equity(&after, player.meeple_color, &[]);
+
+        own + farm_equity(&after, player.meeple_color, &unseen_tiles(&after)) - farms_now
+    }
+}
+
+impl Bot for FarmerBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        tie_break::best_by_key(hints, None, |hint| Self::rating(board, player, hint)).cloned()
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        hints.iter().map(|hint| Self::rating(board, player, hint)).collect()
+    }
+}
+
+/// Rollouts played for each move by default
+const MONTE_CARLO_SAMPLES: usize = 8;
+
+/// This bot tries each move against several random orderings of the unseen tiles, playing each out
+/// with every player making random moves, and plays the move with the best average final margin
+/// over the strongest opponent.
+///
+/// Played through [crate::controller::PlayerController] it knows the players and their meeple; as a
+/// plain [Bot] it can only assume its opponents are the other colours on the board
+#[derive(Clone)]
+pub struct MonteCarloBot {
+    samples: usize,
+    /// Tiles played out before the board is scored, or the whole deck if `None`
+    horizon: Option<usize>,
+    rng: GameRng,
+}
+
+impl MonteCarloBot {
+    pub fn new(rng: GameRng) -> Self {
+        Self { samples: MONTE_CARLO_SAMPLES, horizon: None, rng }
+    }
+
+    pub fn with_samples(mut self, samples: usize) -> Self {
+        self.samples = samples.max(1);
+        self
+    }
+
+    /// Stops each rollout after `tiles` tiles, for quicker but shorter sighted moves
+    pub fn with_horizon(mut self, tiles: usize) -> Self {
+        self.horizon = Some(tiles);
+        self
+    }
+
+    /// As [Bot::select_hint], knowing every player in turn order
+    pub(crate) fn select_hint_among(
+        &mut self,
+        board: &Board,
+        player: &Player,
+        players: &IndexMap<PlayerIdentifier, Player>,
+        hints: &[MoveHint],
+    ) -> Option<MoveHint> {
+        let ratings = self.rate_hints_among(board, player, players, hints);
+
+        tie_break::best_index(hints, &ratings, Some(&mut self.rng)).map(|index| hints[index].clone())
+    }
+
+    fn rate_hints_among(&mut self, board: &Board, player: &Player, players: &IndexMap<PlayerIdentifier, Player>, hints: &[MoveHint]) -> Vec<f64> {
+        hints
+            .iter()
+            .map(|hint| (0..self.samples).map(|_| self.rollout(board, player, players, hint)).sum::<f64>() / self.samples as f64)
+            .collect()
+    }
+
+    /// Plays `hint`, then the rest of a random ordering of the unseen tiles, returning the player's
+    /// margin over the best opponent in the points scored from here on
+    fn rollout(&mut self, board: &Board, player: &Player, players: &IndexMap<PlayerIdentifier, Player>, hint: &MoveHint) -> f64 {
+        let mut board = board.clone();
+        let mut players = players.clone();
+        let mut score = Score::new();
+
+        score += play_rollout_move(&mut board, &mut players, player.meeple_color, hint);
+
+        let mut deck = unseen_tiles(&board);
+        deck.shuffle(&mut self.rng);
+        deck.truncate(self.horizon.unwrap_or(deck.len()));
+
+        let order: Vec<PlayerIdentifier> = players.keys().copied().collect();
+        let seat = order.iter().position(|id| *id == player.meeple_color).unwrap_or(0);
+
+        for (turn, tile) in deck.into_iter().enumerate() {
+            let id = order[(seat + 1 + turn) % order.len()];
+            let hints = board.get_move_hints(tile, !players[&id].meeple.is_empty());
+
+            if let Some(hint) = tie_break::random(&hints, &mut self.rng) {
+                score += play_rollout_move(&mut board, &mut players, id, hint);
+            }
+        }
+
+        score += board.calculate_board_score();
+
+        let own = score.get_player(player).copied().unwrap_or(0);
+        let best_opponent = score.iter().filter(|(id, _)| **id != player.meeple_color).map(|(_, points)| *points).max().unwrap_or(0);
+
+        (own - best_opponent) as f64
+    }
+
+    /// The player and the other colours with meeple on the board, each with the meeple they aren't
+    /// using, for when the players aren't known
+    fn players_on_board(board: &Board, player: &Player) -> IndexMap<PlayerIdentifier, Player> {
+        let mut players = IndexMap::from([(player.meeple_color, player.clone())]);
+
+        for meeple in board.placed_tiles.values().filter_map(|tile| tile.meeple.as_ref().map(|(_, meeple)| meeple)) {
+            if meeple.color != player.meeple_color {
+                let opponent = players.entry(meeple.color).or_insert_with(|| Player::new(meeple.color));
+                opponent.meeple.pop();
+            }
+        }
+
+        players
+    }
+}
+
+/// Places a rollout move, keeping the players' meeple in step with the board
+fn play_rollout_move(board: &mut Board, players: &mut IndexMap<PlayerIdentifier, Player>, id: PlayerIdentifier, hint: &MoveHint) -> Score {
+    let meeple = hint.meeple_placement.and_then(|region_index| players.get_mut(&id)?.meeple.pop().map(|meeple| (region_index, meeple)));
+
+    let success = board
+        .place_tile(PlacedTile { tile: hint.tile, placement: hint.tile_placement.clone(), meeple })
+        .expect("should be a valid move");
+
+    for LiberatedMeeple { meeple, .. } in success.liberated_meeple {
+        if let Some(owner) = players.get_mut(&meeple.color) {
+            owner.meeple.push(meeple);
+        }
+    }
+
+    success.score_delta
+}
+
+impl Bot for MonteCarloBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        self.select_hint_among(board, player, &Self::players_on_board(board, player), hints)
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        self.rate_hints_among(board, player, &Self::players_on_board(board, player), hints)
+    }
+}
+
+/// Thinking time an [ExpectimaxBot] has for each move by default
+const EXPECTIMAX_BUDGET: Duration = Duration::from_millis(500);
+
+/// This bot looks ahead over its own next tiles, averaging over which of the unseen tiles each could
+/// be and taking its best move for each, and values the positions it reaches with
+/// [crate::analysis::evaluate]. It searches one move deeper at a time until its time runs out, then
+/// plays the best move of the deepest search it finished. Opponents' moves aren't searched.
+///
+/// Boards reached by different orders of moves are only evaluated once a turn, looked up by
+/// [Board::canonical_hash]
+#[derive(Clone)]
+pub struct ExpectimaxBot {
+    budget: Duration,
+}
+
+impl Default for ExpectimaxBot {
+    fn default() -> Self {
+        Self { budget: EXPECTIMAX_BUDGET }
+    }
+}
+
+/// Values of the searched positions, by board hash, meeple in hand and depth searched below them
+type TranspositionTable = HashMap<(u64, usize, usize), f64>;
+
+impl ExpectimaxBot {
+    pub fn with_budget(budget: Duration) -> Self {
+        Self { budget }
+    }
+
+    /// The value of each of `hints` from the deepest search finished in time. Every move is
+    /// searched at least one move deep, however short the budget
+    fn search(&self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        let deadline = Instant::now() + self.budget;
+        let mut table = TranspositionTable::new();
+
+        let mut ratings = self.rate_at_depth(board, player, hints, 0, None, &mut table).expect("shouldn't stop without a deadline");
+
+        for depth in 1..=unseen_tiles(board).len() {
+            match self.rate_at_depth(board, player, hints, depth, Some(deadline), &mut table) {
+                Some(deeper) => ratings = deeper,
+                None => break,
+            }
+        }
+
+        ratings
+    }
+
+    fn rate_at_depth(
+        &self,
+        board: &Board,
+        player: &Player,
+        hints: &[MoveHint],
+        depth: usize,
+        deadline: Option<Instant>,
+        table: &mut TranspositionTable,
+    ) -> Option<Vec<f64>> {
+        hints
+            .iter()
+            .map(|hint| {
+                let (after, player, banked) = Self::play(board, player, hint);
+                Some(banked + Self::value(&after, &player, depth, deadline, table)?)
+            })
+            .collect()
+    }
+
+    /// Expected value of the board to the player with `depth` more of their tiles still to be
+    /// searched, or `None` if the deadline passes first
+    fn value(board: &Board, player: &Player, depth: usize, deadline: Option<Instant>, table: &mut TranspositionTable) -> Option<f64> {
+        let unseen = unseen_tiles(board);
+
+        if depth == 0 || unseen.is_empty() {
+            return Some(evaluate(board, player, &unseen));
+        }
+
+        let key = (board.canonical_hash(), player.meeple.len(), depth);
+
+        if let Some(value) = table.get(&key) {
+            return Some(*value);
+        }
+
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return None;
+        }
+
+        let mut draws: Vec<(&'static TileDefinition, usize)> = vec![];
+
+        for tile in &unseen {
+            match draws.iter_mut().find(|(drawn, _)| drawn.name == tile.name) {
+                Some((_, count)) => *count += 1,
+                None => draws.push((tile, 1)),
+            }
+        }
+
+        let mut value = 0.0;
+
+        for (tile, count) in draws {
+            let hints = board.get_move_hints(tile, !player.meeple.is_empty());
+
+            let best = if hints.is_empty() {
+                // the tile is discarded and another drawn in its place
+                Self::value(board, player, depth - 1, deadline, table)?
             } else {
-                0
+                let mut best = f64::NEG_INFINITY;
+
+                for hint in &hints {
+                    let (after, player, banked) = Self::play(board, player, hint);
+                    best = best.max(banked + Self::value(&after, &player, depth - 1, deadline, table)?);
+                }
+
+                best
+            };
+
+            value += best * count as f64 / unseen.len() as f64;
+        }
+
+        table.insert(key, value);
+
+        Some(value)
+    }
+
+    /// The board after `hint`, the player with the meeple they have left, and the points they
+    /// banked from features the move completed
+    fn play(board: &Board, player: &Player, hint: &MoveHint) -> (Board, Player, f64) {
+        let mut board = board.clone();
+        let mut player = player.clone();
+
+        let meeple = hint.meeple_placement.and_then(|region_index| player.meeple.pop().map(|meeple| (region_index, meeple)));
+
+        let success = board
+            .place_tile(PlacedTile { tile: hint.tile, placement: hint.tile_placement.clone(), meeple })
+            .expect("should be a valid move");
+
+        for LiberatedMeeple { meeple, .. } in success.liberated_meeple {
+            if meeple.color == player.meeple_color {
+                player.meeple.push(meeple);
             }
-        })
+        }
+
+        let banked = success.score_delta.get_player(&player).copied().unwrap_or(0);
+
+        (board, player, banked as f64)
+    }
+}
+
+impl Bot for ExpectimaxBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        let ratings = self.search(board, player, hints);
+
+        tie_break::best_index(hints, &ratings, None).map(|index| hints[index].clone())
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        self.search(board, player, hints)
+    }
+}
+
+/// This bot asks several bots to rate every move and plays the move with the highest weighted total.
+/// Each bot's ratings are scaled to between 0 and 1 first, so none outweighs the others just by
+/// rating in bigger numbers
+#[derive(Default)]
+pub struct EnsembleBot {
+    members: Vec<(Box<dyn Bot>, f64)>,
+}
+
+impl EnsembleBot {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_member(mut self, bot: Box<dyn Bot>, weight: f64) -> Self {
+        self.members.push((bot, weight));
+        self
+    }
+}
+
+/// Scales ratings so the worst is 0 and the best is 1. Ratings that are all the same express no
+/// preference, so are all 0
+fn normalised(ratings: &[f64]) -> Vec<f64> {
+    let min = ratings.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = ratings.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    ratings
+        .iter()
+        .map(|rating| if max > min { (rating - min) / (max - min) } else { 0.0 })
+        .collect()
+}
+
+impl Bot for EnsembleBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        let ratings = self.rate_hints(board, player, hints);
+
+        tie_break::best_index(hints, &ratings, None).map(|index| hints[index].clone())
+    }
+
+    fn rate_hints(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Vec<f64> {
+        let mut totals = vec![0.0; hints.len()];
+
+        for (bot, weight) in &mut self.members {
+            for (total, rating) in totals.iter_mut().zip(normalised(&bot.rate_hints(board, player, hints))) {
+                *total += *weight * rating;
+            }
+        }
+
+        totals
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        for (bot, _) in &mut self.members {
+            bot.observe(event);
+        }
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        Some("ensemble")
+    }
+}
+
+/// Plays river tiles to spread the river out across the table rather than let it coil up against the
+/// tiles already placed, which would leave little room for the fields, cities and roads that come
+/// after it. The inner bot plays every other tile, and chooses any meeple placement on the river
+#[derive(Clone)]
+pub struct RiverOpening<B> {
+    inner: B,
+}
+
+impl<B: Bot> RiverOpening<B> {
+    pub fn new(inner: B) -> Self {
+        Self { inner }
+    }
+
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+}
+
+/// How much room a river placement leaves: how far the river's next tile would be from the middle
+/// of the board, less how many tiles would already crowd it
+fn river_opening_rating(board: &Board, hint: &MoveHint) -> f64 {
+    let Some((_, river_end)) = board.placed_tiles.last() else {
+        return 0.0;
+    };
+
+    let coordinate = hint.tile_placement.coordinate;
+    let placed = PlacedTile { tile: hint.tile, placement: hint.tile_placement.clone(), meeple: None };
+
+    // the terminator has nowhere further for the river to go, nor does a river that doesn't carry on
+    // from the last tile
+    let Some(heading) = board.topology().direction_to_adjacent_coordinate(&coordinate, river_end.placement.coordinate).and_then(|direction| placed.get_opposite_river_end_direction(direction)) else {
+        return 0.0;
+    };
+
+    let next = board.topology().adjacent_in_direction(&coordinate, &heading);
+
+    let tile_count = board.placed_tiles.len() as f64;
+    let (sum_x, sum_y) = board.placed_tiles.keys().fold((0.0, 0.0), |(x, y), c| (x + c.x as f64, y + c.y as f64));
+    let spread = (next.x as f64 - sum_x / tile_count).abs() + (next.y as f64 - sum_y / tile_count).abs();
+
+    let crowding = board.list_surrounding_tiles(&next).len() + board.placed_tiles.contains_key(&next) as usize;
+
+    spread - 2.0 * crowding as f64
+}
+
+impl<B: Bot> Bot for RiverOpening<B> {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        if !hints.first().is_some_and(|hint| GamePhase::of(hint.tile) == GamePhase::River) {
+            return self.inner.select_hint(board, player, hints);
+        }
+
+        let best = tie_break::best_by_key(hints, None, |hint| river_opening_rating(board, hint))?;
+
+        let placements: Vec<MoveHint> = hints.iter().filter(|hint| hint.tile_placement == best.tile_placement).cloned().collect();
+
+        self.inner.select_hint(board, player, &placements)
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        self.inner.observe(event);
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        self.inner.bot_name()
+    }
+}
+
+/// How often an opponent's moves are made at random, for [NoisyBot]
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum Difficulty {
+    /// Half of the moves are random
+    Easy,
+    /// A fifth of the moves are random
+    Medium,
+    /// No moves are random
+    #[default]
+    Hard,
+}
+
+impl Difficulty {
+    pub fn epsilon(&self) -> f64 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Medium => 0.2,
+            Difficulty::Hard => 0.0,
+        }
+    }
+}
+
+/// This bot plays a random legal move with probability `epsilon`, and otherwise the move its inner
+/// bot picks, to weaken a strong bot by a chosen amount
+pub struct NoisyBot {
+    inner: Box<dyn Bot>,
+    epsilon: f64,
+    rng: GameRng,
+}
+
+impl NoisyBot {
+    pub fn new(inner: Box<dyn Bot>, epsilon: f64, rng: GameRng) -> Self {
+        Self { inner, epsilon: epsilon.clamp(0.0, 1.0), rng }
+    }
+
+    pub fn with_difficulty(inner: Box<dyn Bot>, difficulty: Difficulty, rng: GameRng) -> Self {
+        Self::new(inner, difficulty.epsilon(), rng)
+    }
+}
+
+impl Bot for NoisyBot {
+    fn select_hint(&mut self, board: &Board, player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+        if self.rng.gen_bool(self.epsilon) {
+            return tie_break::random(hints, &mut self.rng).cloned();
+        }
+
+        self.inner.select_hint(board, player, hints)
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        self.inner.observe(event);
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        self.inner.bot_name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TestPlayer;
+    use crate::tile::{BoardCoordinate, Rotation};
+    use crate::tile_definitions::{CLOISTER_IN_FIELD, CORNER_RIVER, RIVER_TERMINATOR, SIDE_CITY, STRAIGHT_RIVER, STRAIGHT_ROAD};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn should_build_every_named_bot() {
+        let mut rng = GameRng::seed_from_u64(0);
+
+        for name in BOT_NAMES {
+            let bot = BotStrategy::from_name(name, &mut rng).expect("should be a known bot");
+            assert_eq!(&bot.name(), name);
+        }
+
+        assert!(BotStrategy::from_name("clippy", &mut rng).is_none());
+    }
+
+    #[test]
+    fn should_finish_a_partners_city_but_not_an_opponents() {
+        let alice = Player::red();
+        let mut bob = Player::green();
+
+        let board = Board::new_with_tiles([bob.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+
+        let closes_the_city = |hint: &MoveHint| hint.tile_placement.coordinate.y == 1 && hint.tile_placement.rotation == Rotation::R180;
+
+        let mut partner = TeamBot::default().with_partners([bob.meeple_color]);
+        let mut opponent = TeamBot::default();
+
+        assert!(closes_the_city(&partner.select_hint(&board, &alice, &hints).unwrap()));
+        assert!(!closes_the_city(&opponent.select_hint(&board, &alice, &hints).unwrap()));
+    }
+
+    /// Counts the moves asked of it, always taking the first
+    struct CountingBot(Rc<Cell<usize>>);
+
+    impl Bot for CountingBot {
+        fn select_hint(&mut self, _board: &Board, _player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+            self.0.set(self.0.get() + 1);
+            hints.first().cloned()
+        }
+    }
+
+    #[test]
+    fn should_defer_to_the_inner_bot_unless_playing_at_random() {
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+        let player = Player::red();
+
+        let consulted = Rc::new(Cell::new(0));
+
+        let mut hard = NoisyBot::with_difficulty(Box::new(CountingBot(Rc::clone(&consulted))), Difficulty::Hard, GameRng::seed_from_u64(0));
+        let mut random = NoisyBot::new(Box::new(CountingBot(Rc::clone(&consulted))), 1.0, GameRng::seed_from_u64(0));
+
+        for _ in 0..20 {
+            assert!(hard.select_hint(&board, &player, &hints).is_some());
+            assert!(random.select_hint(&board, &player, &hints).is_some());
+        }
+
+        assert_eq!(consulted.get(), 20);
+
+        let lazy = NoisyBot::with_difficulty(Box::new(BotStrategy::Lazy(LazyBot)), Difficulty::Easy, GameRng::seed_from_u64(0));
+        assert_eq!(Bot::bot_name(&lazy), Some("lazy"));
+    }
+
+    #[test]
+    fn should_scale_ratings_between_zero_and_one() {
+        assert_eq!(normalised(&[2.0, 4.0, 6.0]), vec![0.0, 0.5, 1.0]);
+        assert_eq!(normalised(&[3.0, 3.0]), vec![0.0, 0.0]);
+    }
+
+    /// Rates moves as it's told to
+    struct FixedRatings(Vec<f64>);
+
+    impl Bot for FixedRatings {
+        fn select_hint(&mut self, _board: &Board, _player: &Player, _hints: &[MoveHint]) -> Option<MoveHint> {
+            None
+        }
+
+        fn rate_hints(&mut self, _board: &Board, _player: &Player, _hints: &[MoveHint]) -> Vec<f64> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn should_play_the_move_the_weightiest_members_agree_on() {
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let hints = &board.get_move_hints(&SIDE_CITY, false)[..3];
+        let player = Player::red();
+
+        // the second rates in bigger numbers, but that alone shouldn't let it win
+        let first = || Box::new(FixedRatings(vec![0.0, 1.0, 0.5]));
+        let second = || Box::new(FixedRatings(vec![100.0, 0.0, 60.0]));
+
+        let mut first_outweighs = EnsembleBot::new().with_member(first(), 2.0).with_member(second(), 1.0);
+        let mut second_outweighs = EnsembleBot::new().with_member(first(), 1.0).with_member(second(), 2.0);
+        let mut evenly = EnsembleBot::new().with_member(first(), 1.0).with_member(second(), 1.0);
+
+        let chosen = |bot: &mut EnsembleBot| {
+            let hint = bot.select_hint(&board, &player, hints).unwrap();
+            hints.iter().position(|h| h.tile_placement == hint.tile_placement).unwrap()
+        };
+
+        assert_eq!(chosen(&mut first_outweighs), 1);
+        assert_eq!(chosen(&mut second_outweighs), 0);
+        // the compromise is best liked overall
+        assert_eq!(chosen(&mut evenly), 2);
+    }
+
+    #[test]
+    fn should_turn_the_river_away_from_the_tiles_already_placed() {
+        // the river runs east, then turns south
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&RIVER_TERMINATOR, 0, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_RIVER, 1, 0, Rotation::R90),
+            PlacedTile::new(&CORNER_RIVER, 2, 0, Rotation::R270),
+            PlacedTile::new(&STRAIGHT_RIVER, 2, 1, Rotation::R0),
+        ])
+        .unwrap();
+
+        let hints = board.get_move_hints(&CORNER_RIVER, true);
+        let consulted = Rc::new(Cell::new(0));
+        let mut bot = RiverOpening::new(CountingBot(Rc::clone(&consulted)));
+
+        // turning back west would bring the river alongside the tiles it came from
+        let chosen = bot.select_hint(&board, &Player::red(), &hints).unwrap();
+        assert_eq!(chosen.tile_placement.rotation, Rotation::R90);
+
+        // meeple placements on the chosen tile are left to the inner bot, as are other tiles
+        bot.select_hint(&board, &Player::red(), &board.get_move_hints(&SIDE_CITY, true));
+        assert_eq!(consulted.get(), 2);
+    }
+
+    #[test]
+    fn should_leave_an_opponents_city_with_a_gap_no_tile_fits() {
+        let alice = Player::red();
+        let mut bob = Player::green();
+
+        // the gap south of Bob's city is walled in by fields, so a road leading into it leaves a gap
+        // that needs a tile with a city opposite a road and fields either side, which there isn't
+        let board = Board::new_with_tiles([
+            bob.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, -1, 0, Rotation::R0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 1, Rotation::R0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, -1, 1, Rotation::R0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 2, Rotation::R0),
+        ])
+        .unwrap();
+
+        let hints = board.get_move_hints(&STRAIGHT_ROAD, true);
+        let chosen = BlockerBot.select_hint(&board, &alice, &hints).unwrap();
+
+        assert_eq!(chosen.tile_placement.coordinate, BoardCoordinate::new(0, 2));
+        assert!(matches!(chosen.tile_placement.rotation, Rotation::R0 | Rotation::R180));
+    }
+
+    #[test]
+    fn should_put_a_farmer_next_to_an_open_city() {
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let hints: Vec<MoveHint> = board
+            .get_move_hints(&STRAIGHT_ROAD, true)
+            .into_iter()
+            .filter(|hint| hint.tile_placement.coordinate == BoardCoordinate::new(1, 0))
+            .collect();
+
+        let region_type = |hint: &MoveHint| hint.meeple_placement.map(|region_index| hint.tile.regions[*region_index].region_type());
+
+        // a road is worth a point now, but a farm beside the city is worth three if it is completed
+        let myopic = MyopicBot.select_hint(&board, &Player::red(), &hints).unwrap();
+        let farmer = FarmerBot.select_hint(&board, &Player::red(), &hints).unwrap();
+
+        assert_eq!(region_type(&myopic), Some(RegionType::Road));
+        assert_eq!(region_type(&farmer), Some(RegionType::Field));
+    }
+
+    #[test]
+    fn should_play_out_the_rest_of_the_game_from_each_move() {
+        let mut alice = Player::red();
+        let mut bob = Player::green();
+
+        let board = Board::new_with_tiles([
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1),
+            bob.move_with_meeple(&STRAIGHT_ROAD, 1, 0, Rotation::R0, 0),
+        ])
+        .unwrap();
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+
+        // the meeple Bob has on the board is one fewer to play with
+        let players = MonteCarloBot::players_on_board(&board, &alice);
+        assert_eq!(players.keys().copied().collect::<Vec<_>>(), vec![alice.meeple_color, bob.meeple_color]);
+        assert_eq!(players[&bob.meeple_color].meeple.len(), Player::green().meeple.len() - 1);
+
+        // with nothing played out, closing the city is worth the most
+        let mut bot = MonteCarloBot::new(GameRng::seed_from_u64(0)).with_samples(1).with_horizon(0);
+        let chosen = bot.select_hint(&board, &alice, &hints).unwrap();
+
+        assert_eq!(chosen.tile_placement.coordinate, BoardCoordinate::new(0, 1));
+        assert_eq!(chosen.tile_placement.rotation, Rotation::R180);
+
+        let mut bot = MonteCarloBot::new(GameRng::seed_from_u64(0)).with_samples(2).with_horizon(5);
+        assert_eq!(bot.rate_hints(&board, &alice, &hints).len(), hints.len());
+    }
+
+    #[test]
+    fn should_only_take_a_meeple_for_a_rollout_move_that_places_one() {
+        let mut alice = Player::red();
+
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+        let hints = board.get_move_hints(&STRAIGHT_ROAD, true);
+        let supply = alice.meeple.len();
+
+        for hint in &hints {
+            let mut players = IndexMap::from([(alice.meeple_color, alice.clone())]);
+            play_rollout_move(&mut board.clone(), &mut players, alice.meeple_color, hint);
+
+            let placed = usize::from(hint.meeple_placement.is_some());
+            assert_eq!(players[&alice.meeple_color].meeple.len(), supply - placed);
+        }
+    }
+
+    #[test]
+    fn should_search_deeper_while_there_is_time() {
+        let mut alice = Player::red();
+
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+
+        // out of time straight away, every move is still looked at one move deep
+        let mut hasty = ExpectimaxBot::with_budget(Duration::ZERO);
+        let chosen = hasty.select_hint(&board, &alice, &hints).unwrap();
+
+        assert_eq!(chosen.tile_placement.coordinate, BoardCoordinate::new(0, 1));
+        assert_eq!(chosen.tile_placement.rotation, Rotation::R180);
+
+        // a position reached again is looked up rather than searched
+        let mut table = TranspositionTable::new();
+        let (after, player, _) = ExpectimaxBot::play(&board, &alice, &chosen);
+
+        let value = ExpectimaxBot::value(&after, &player, 1, None, &mut table).unwrap();
+        assert_eq!(table.len(), 1);
+
+        table.insert((after.canonical_hash(), player.meeple.len(), 1), value + 100.0);
+        assert_eq!(ExpectimaxBot::value(&after, &player, 1, None, &mut table), Some(value + 100.0));
+
+        let stopped = ExpectimaxBot::value(&board, &alice, 1, Some(Instant::now()), &mut TranspositionTable::new());
+        assert_eq!(stopped, None);
+    }
+
+    #[test]
+    fn should_only_take_a_meeple_for_a_searched_move_that_places_one() {
+        let mut alice = Player::red();
+
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+        let supply = alice.meeple.len();
+
+        for hint in board.get_move_hints(&STRAIGHT_ROAD, true) {
+            let (_, player, _) = ExpectimaxBot::play(&board, &alice, &hint);
+
+            assert_eq!(player.meeple.len(), supply - usize::from(hint.meeple_placement.is_some()));
+        }
     }
 }
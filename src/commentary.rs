@@ -0,0 +1,378 @@
+use crate::board::CompletedFeature;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::PlayerIdentifier;
+use crate::replay::{Replay, ReplayError, ReplayPlacement, ReplayState, ReplayTurn};
+use crate::tile::{BoardCoordinate, RegionType};
+use std::collections::{HashMap, HashSet};
+
+/// Commentary on a single turn of a game
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnCommentary {
+    pub game_index: usize,
+    pub turn: usize,
+    pub player: PlayerIdentifier,
+    pub text: String,
+}
+
+/// Describes `turn` in plain English, advancing `state` past it
+pub fn describe_turn(state: &mut ReplayState, turn: &ReplayTurn) -> Result<String, ReplayError> {
+    let tile = turn.tile()?;
+    let name = player_name(state, turn.player);
+
+    let Some(placement) = &turn.placement else {
+        state.apply(turn)?;
+        return Ok(format!("{} draws {} but cannot place it.", name, tile.name()));
+    };
+
+    let mut text = format!("{} places {} at ({}, {})", name, tile.name(), placement.x, placement.y);
+
+    if let Some(region) = placement.meeple.and_then(|index| tile.regions.get(index)) {
+        text += match region.region_type() {
+            RegionType::City => " and claims the city",
+            RegionType::Road => " and claims the road",
+            RegionType::Field => " and puts a farmer in the field",
+            RegionType::Cloister => " and claims the cloister",
+            RegionType::Water => "",
+        };
+    }
+
+    let success = state.apply(turn)?.expect("a placed tile should have a placement result");
+
+    let mut remarks: Vec<String> = success
+        .completed_features
+        .iter()
+        .map(|feature| describe_completed_feature(state, turn.player, feature))
+        .collect();
+
+    if success.completed_features.iter().any(|f| f.region_type == RegionType::City) {
+        remarks.extend(describe_farmers(state, placement));
+    }
+
+    if !remarks.is_empty() {
+        text += ". ";
+        text += &remarks.join("; ");
+    }
+
+    text += ".";
+
+    Ok(text)
+}
+
+/// Commentary for every turn of a recorded game
+pub fn commentate(replay: &Replay) -> Result<Vec<String>, ReplayError> {
+    let mut state = replay.initial_state()?;
+
+    replay.turns.iter().map(|turn| describe_turn(&mut state, turn)).collect()
+}
+
+fn player_name(state: &ReplayState, player: PlayerIdentifier) -> String {
+    state
+        .players
+        .get(&player)
+        .and_then(|p| p.name.clone())
+        .unwrap_or_else(|| player.to_string())
+}
+
+fn describe_completed_feature(state: &ReplayState, player: PlayerIdentifier, feature: &CompletedFeature) -> String {
+    let mut text = format!("{} completes ", player_name(state, player));
+
+    text += &match feature.region_type {
+        RegionType::Cloister => "a cloister".to_string(),
+        RegionType::City => format!("a {}-tile city", feature.tiles.len()),
+        _ => format!("a {}-tile road", feature.tiles.len()),
+    };
+
+    match feature.pennant_count {
+        0 => {}
+        1 => text += " with a pennant",
+        count => text += &format!(" with {} pennants", count),
+    }
+
+    match feature.scored_by.as_slice() {
+        [] => text += ", but nobody had claimed it",
+        [only] if *only == player => text += &format!(" for {} points", feature.points),
+        scorers => {
+            let names: Vec<_> = scorers.iter().map(|id| player_name(state, *id)).collect();
+            text += &format!(", scoring {} points for {}", feature.points, names.join(" and "))
+        }
+    }
+
+    if let [(_, first), (_, second), ..] = feature.strength.as_slice() {
+        match feature.margin() {
+            0 => text += &format!(", contested {} meeple each", first),
+            _ => text += &format!(", contested {} meeple to {}", first, second),
+        }
+    }
+
+    text
+}
+
+/// Notes farmers bordering a city that was completed by the tile at `placement`
+fn describe_farmers(state: &ReplayState, placement: &ReplayPlacement) -> Vec<String> {
+    let coordinate = BoardCoordinate::new(placement.x, placement.y);
+    let board = &state.board;
+
+    // any city closed by this tile must include it, and it can't have been closed before
+    let completed_city_ids: HashSet<_> = board
+        .get_connected_regions()
+        .into_iter()
+        .filter(|region| {
+            region.region_type == RegionType::City
+                && region.is_closed()
+                && region.tile_regions.iter().any(|r| r.tile_position == coordinate)
+        })
+        .map(|region| region.id)
+        .collect();
+
+    let mut remarks = vec![];
+
+    for field in board.get_connected_regions() {
+        if field.region_type != RegionType::Field {
+            continue;
+        }
+
+        let adjacent_regions = board.adjacent_regions(field);
+
+        if !adjacent_regions.iter().any(|region| completed_city_ids.contains(&region.id)) {
+            continue;
+        }
+
+        let bordered_cities = adjacent_regions
+            .into_iter()
+            .filter(|region| region.region_type == RegionType::City && region.is_closed())
+            .count();
+
+        let mut farmers: Vec<_> = field.residents(board).into_iter().map(|(_, _, meeple)| meeple.color).collect();
+        farmers.dedup();
+
+        for farmer in farmers {
+            remarks.push((farmer, format!(
+                "{}'s farmer now borders {} completed {}",
+                player_name(state, farmer),
+                bordered_cities,
+                if bordered_cities == 1 { "city" } else { "cities" }
+            )));
+        }
+    }
+
+    // fields come out of the board in no particular order, so list farmers in turn order
+    remarks.sort_by_key(|(farmer, _)| state.players.get_index_of(farmer));
+    remarks.dedup();
+
+    remarks.into_iter().map(|(_, remark)| remark).collect()
+}
+
+/// Observer that writes commentary on each turn as games are played. Commentary is either printed
+/// as it happens or collected
+#[derive(Default)]
+pub struct Commentator {
+    games: HashMap<usize, ReplayState>,
+    print: bool,
+    commentary: Vec<TurnCommentary>,
+}
+
+impl Commentator {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn printing() -> Self {
+        Self {
+            print: true,
+            ..Default::default()
+        }
+    }
+
+    /// Collected commentary, when not printing
+    pub fn commentary(&self) -> &[TurnCommentary] {
+        &self.commentary
+    }
+}
+
+impl GameObserver for Commentator {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, players, rules, .. } => {
+                let players = players.iter().map(|(color, name, _)| (*color, name.clone()));
+
+                self.games.insert(*game_index, ReplayState::new(players, rules).expect("a game's rules are validated before it starts"));
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                let Some(state) = self.games.get_mut(game_index) else {
+                    return;
+                };
+
+                let turn_index = state.turn;
+
+                let turn = ReplayTurn {
+                    player: *player,
+                    tile: tile.name().to_string(),
+                    placement: placement.as_ref().map(|(placement, meeple)| ReplayPlacement::new(placement, *meeple)),
+                };
+
+                let text = describe_turn(state, &turn).unwrap_or_else(|error| format!("(no commentary: {:?})", error));
+
+                if self.print {
+                    println!("Turn {}: {}", turn_index + 1, text);
+                } else {
+                    self.commentary.push(TurnCommentary { game_index: *game_index, turn: turn_index, player: *player, text });
+                }
+            }
+            // the turn's description covers what it completed
+            GameEvent::RegionCompleted { .. } => {}
+            GameEvent::MeepleRecalled { game_index, player, coordinate, region_index } => {
+                let Some(state) = self.games.get_mut(game_index) else {
+                    return;
+                };
+
+                state.recall_meeple(*player, coordinate, *region_index);
+
+                let name = state.players.get(player).and_then(|p| p.name.clone()).unwrap_or_else(|| player.to_string());
+                let text = format!("{} takes back the meeple at ({}, {}).", name, coordinate.x, coordinate.y);
+
+                if self.print {
+                    println!("{}", text);
+                } else {
+                    self.commentary.push(TurnCommentary { game_index: *game_index, turn: state.turn - 1, player: *player, text });
+                }
+            }
+            // only what players do is commented on, and the obvious move is commented on as their turn
+            GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } => {}
+            GameEvent::ControllerChanged { game_index, player, bot } => {
+                let Some(state) = self.games.get(game_index) else {
+                    return;
+                };
+
+                let name = state.players.get(player).and_then(|p| p.name.clone()).unwrap_or_else(|| player.to_string());
+                let text = match bot {
+                    Some(bot) => format!("{} hands over to the {} bot.", name, bot),
+                    None => format!("{} takes over from the bot.", name),
+                };
+
+                if self.print {
+                    println!("{}", text);
+                } else {
+                    self.commentary.push(TurnCommentary { game_index: *game_index, turn: state.turn, player: *player, text });
+                }
+            }
+            GameEvent::PlayerResigned { game_index, player } => {
+                let Some(state) = self.games.get(game_index) else {
+                    return;
+                };
+
+                let name = state.players.get(player).and_then(|p| p.name.clone()).unwrap_or_else(|| player.to_string());
+                let text = format!("{} resigns.", name);
+
+                if self.print {
+                    println!("{}", text);
+                } else {
+                    self.commentary.push(TurnCommentary { game_index: *game_index, turn: state.turn - 1, player: *player, text });
+                }
+            }
+            GameEvent::GameCompleted { game_index, .. } => {
+                self.games.remove(game_index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::MeepleColor;
+    use crate::replay::{ReplayPlayer, ReplayRules};
+    use crate::tile::Rotation;
+
+    fn side_city_turn(player: PlayerIdentifier, x: i8, y: i8, rotation: Rotation, meeple: Option<usize>) -> ReplayTurn {
+        ReplayTurn {
+            player,
+            tile: "Side city".to_string(),
+            placement: Some(ReplayPlacement { x, y, rotation, meeple, recalled: None }),
+        }
+    }
+
+    fn replay(turns: Vec<ReplayTurn>) -> Replay {
+        Replay {
+            seed: String::new(),
+            deck: vec![],
+            players: vec![
+                ReplayPlayer { color: MeepleColor::Red, name: Some("Alice".to_string()), bot: None },
+                ReplayPlayer { color: MeepleColor::Green, name: Some("Bob".to_string()), bot: None },
+            ],
+            turns,
+            final_score: vec![],
+            handovers: vec![],
+            rules: ReplayRules::default(),
+        }
+    }
+
+    #[test]
+    fn should_describe_placements_and_meeple() {
+        let commentary = commentate(&replay(vec![
+            side_city_turn(MeepleColor::Red, 0, 0, Rotation::R0, Some(1)),
+            side_city_turn(MeepleColor::Green, 1, 0, Rotation::R0, None),
+        ]))
+        .unwrap();
+
+        assert_eq!(commentary, vec![
+            "Alice places Side city at (0, 0) and claims the city.",
+            "Bob places Side city at (1, 0).",
+        ]);
+    }
+
+    #[test]
+    fn should_describe_completed_cities_and_farmers() {
+        let commentary = commentate(&replay(vec![
+            side_city_turn(MeepleColor::Red, 0, 0, Rotation::R0, Some(0)),
+            side_city_turn(MeepleColor::Green, 0, 1, Rotation::R180, Some(0)),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            commentary[1],
+            "Bob places Side city at (0, 1) and puts a farmer in the field. \
+             Bob completes a 2-tile city, but nobody had claimed it; \
+             Alice's farmer now borders 1 completed city; Bob's farmer now borders 1 completed city."
+        );
+    }
+
+    #[test]
+    fn should_credit_the_player_who_claimed_the_city() {
+        let commentary = commentate(&replay(vec![
+            side_city_turn(MeepleColor::Red, 0, 0, Rotation::R0, Some(1)),
+            side_city_turn(MeepleColor::Green, 0, 1, Rotation::R180, None),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            commentary[1],
+            "Bob places Side city at (0, 1). Bob completes a 2-tile city, scoring 4 points for Alice."
+        );
+    }
+
+    #[test]
+    fn should_describe_how_a_contested_feature_was_won() {
+        let state = replay(vec![]).initial_state().unwrap();
+        let mut feature = CompletedFeature {
+            region_type: RegionType::City,
+            tiles: vec![BoardCoordinate::new(0, 0), BoardCoordinate::new(0, 1), BoardCoordinate::new(0, 2)],
+            pennant_count: 0,
+            points: 6,
+            scored_by: vec![MeepleColor::Red],
+            strength: vec![(MeepleColor::Red, 2), (MeepleColor::Green, 1)],
+        };
+
+        assert_eq!(
+            describe_completed_feature(&state, MeepleColor::Green, &feature),
+            "Bob completes a 3-tile city, scoring 6 points for Alice, contested 2 meeple to 1"
+        );
+
+        feature.scored_by.push(MeepleColor::Green);
+        feature.strength[1].1 = 2;
+
+        assert_eq!(
+            describe_completed_feature(&state, MeepleColor::Green, &feature),
+            "Bob completes a 3-tile city, scoring 6 points for Alice and Bob, contested 2 meeple each"
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use crate::board::{Board, InvalidTilePlacement};
+use crate::player::{Meeple, PlayerIdentifier, RegionIndex, SEATS};
+use crate::rules::{RuleSet, RuleSetError};
+use crate::sketch::{self, SketchError};
+use crate::tile::PlacedTile;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A ruling on one move, kept as data so it goes on being checked after the engine changes: the
+/// position, the move played in it, and what the rules say should come of it. A file of the suite
+/// holds a list of them, written in YAML:
+///
+/// ```yaml
+/// - name: closing a four tile city scores two points a tile
+///   position: |
+///     corner-city@270  corner-city
+///     .                corner-city@90
+///   meeple:
+///     - { x: 0, y: 0, region: 1, player: Red }
+///   move: { tile: corner-city@180, x: 0, y: 1 }
+///   expect:
+///     score: { Red: 8 }
+/// ```
+///
+/// The position is a sketch, as [sketch::parse] reads it
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConformanceCase {
+    pub name: String,
+    /// One of [crate::rules::PRESET_NAMES], the standard rules if not given
+    #[serde(default)]
+    pub rules: Option<String>,
+    pub position: String,
+    /// Meeple standing on the tiles of the position
+    #[serde(default)]
+    pub meeple: Vec<CaseMeeple>,
+    #[serde(rename = "move")]
+    pub play: CaseMove,
+    pub expect: CaseExpectation,
+}
+
+/// A meeple on a tile of a case's position
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaseMeeple {
+    pub x: i8,
+    pub y: i8,
+    pub region: usize,
+    pub player: PlayerIdentifier,
+}
+
+/// The move a case plays: a tile, written as a cell of a sketch, where it goes, and the region of
+/// it the player's meeple goes in, if any
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaseMove {
+    pub tile: String,
+    pub x: i8,
+    pub y: i8,
+    #[serde(default)]
+    pub meeple: Option<usize>,
+    /// Red if not given
+    #[serde(default = "first_seat")]
+    pub player: PlayerIdentifier,
+}
+
+fn first_seat() -> PlayerIdentifier {
+    SEATS[0]
+}
+
+/// What should come of a case's move. The move is expected to be legal unless `error` is given,
+/// and its points are only checked if `score` is
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CaseExpectation {
+    /// Why the move should be rejected
+    #[serde(default)]
+    pub error: Option<InvalidTilePlacement>,
+    /// The points each player should score from the move, players scoring nothing left out
+    #[serde(default)]
+    pub score: Option<HashMap<PlayerIdentifier, i32>>,
+}
+
+/// Why a case didn't come out as it expects
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConformanceFailure {
+    UnknownRules(String),
+    /// The case's rules can't be played
+    InvalidRules(RuleSetError),
+    /// The position, or the move's tile, couldn't be read
+    Sketch(SketchError),
+    /// A meeple of the position is on a square without a tile
+    NoTileUnderMeeple { x: i8, y: i8 },
+    /// A tile of the position, at `x` and `y`, couldn't be placed
+    InvalidPosition { x: i8, y: i8, reason: InvalidTilePlacement },
+    /// The move was rejected, though it should be legal
+    Rejected(InvalidTilePlacement),
+    /// The move was placed, though it should be rejected
+    Accepted { expected: InvalidTilePlacement },
+    /// The move was rejected for another reason than the one expected
+    WrongReason { expected: InvalidTilePlacement, actual: InvalidTilePlacement },
+    /// The move scored other points than expected
+    WrongScore { expected: HashMap<PlayerIdentifier, i32>, actual: HashMap<PlayerIdentifier, i32> },
+}
+
+impl Display for ConformanceFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConformanceFailure::UnknownRules(name) => write!(f, "unknown rules [{}]", name),
+            ConformanceFailure::InvalidRules(error) => write!(f, "{}", error),
+            ConformanceFailure::Sketch(error) => write!(f, "{}", error),
+            ConformanceFailure::NoTileUnderMeeple { x, y } => write!(f, "no tile at {}, {} for the meeple to stand on", x, y),
+            ConformanceFailure::InvalidPosition { x, y, reason } => write!(f, "the tile at {}, {} of the position can't be placed: {}", x, y, reason),
+            ConformanceFailure::Rejected(reason) => write!(f, "the move was rejected: {}", reason),
+            ConformanceFailure::Accepted { expected } => write!(f, "the move was placed, but should be rejected: {}", expected),
+            ConformanceFailure::WrongReason { expected, actual } => write!(f, "the move was rejected because {}, but should be because {}", actual, expected),
+            ConformanceFailure::WrongScore { expected, actual } => write!(f, "the move scored {}, but should score {}", describe(actual), describe(expected)),
+        }
+    }
+}
+
+/// Points by player in seat order, e.g. `Red 8, Green 4`
+fn describe(points: &HashMap<PlayerIdentifier, i32>) -> String {
+    let scored: Vec<_> = SEATS.iter().filter_map(|player| points.get(player).map(|points| format!("{:?} {}", player, points))).collect();
+
+    match scored.is_empty() {
+        true => "nothing".to_string(),
+        false => scored.join(", "),
+    }
+}
+
+impl ConformanceCase {
+    /// Sets up the position, plays the move and checks it comes out as expected
+    pub fn run(&self) -> Result<(), ConformanceFailure> {
+        let rules = match &self.rules {
+            Some(name) => RuleSet::preset(name).ok_or_else(|| ConformanceFailure::UnknownRules(name.clone()))?,
+            None => RuleSet::standard(),
+        };
+
+        let mut board = self.position(&rules)?;
+
+        let (tile, rotation) = sketch::read_cell(&self.play.tile, 1).map_err(ConformanceFailure::Sketch)?;
+        let mut placed = PlacedTile::new(tile, self.play.x, self.play.y, rotation);
+        placed.meeple = self.play.meeple.map(|region| (RegionIndex::new(region), Meeple::new(self.play.player)));
+
+        match (board.place_tile(placed), self.expect.error) {
+            (Ok(_), Some(expected)) => Err(ConformanceFailure::Accepted { expected }),
+            (Err(actual), Some(expected)) if actual != expected => Err(ConformanceFailure::WrongReason { expected, actual }),
+            (Err(_), Some(_)) => Ok(()),
+            (Err(reason), None) => Err(ConformanceFailure::Rejected(reason)),
+            (Ok(success), None) => {
+                let actual: HashMap<_, _> = success.score_delta.iter().filter(|(_, points)| **points != 0).map(|(player, points)| (*player, *points)).collect();
+
+                match &self.expect.score {
+                    Some(expected) if *expected != actual => Err(ConformanceFailure::WrongScore { expected: expected.clone(), actual }),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// The board of the position, under the case's rules, with its meeple stood on it
+    fn position(&self, rules: &RuleSet) -> Result<Board, ConformanceFailure> {
+        let mut tiles = sketch::parse(&self.position).map_err(ConformanceFailure::Sketch)?;
+
+        for meeple in &self.meeple {
+            let tile = tiles
+                .iter_mut()
+                .find(|tile| tile.placement.coordinate.x == meeple.x && tile.placement.coordinate.y == meeple.y)
+                .ok_or(ConformanceFailure::NoTileUnderMeeple { x: meeple.x, y: meeple.y })?;
+
+            tile.meeple = Some((RegionIndex::new(meeple.region), Meeple::new(meeple.player)));
+        }
+
+        let mut board = Board::under_rules(rules).map_err(ConformanceFailure::InvalidRules)?;
+
+        for tile in tiles {
+            let coordinate = tile.placement.coordinate;
+
+            board
+                .place_tile(tile)
+                .map_err(|reason| ConformanceFailure::InvalidPosition { x: coordinate.x, y: coordinate.y, reason })?;
+        }
+
+        Ok(board)
+    }
+}
+
+/// Why a file of the suite couldn't be read
+#[derive(Debug)]
+pub enum ConformanceError {
+    Io(PathBuf, io::Error),
+    Yaml(PathBuf, serde_yaml::Error),
+}
+
+impl Display for ConformanceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConformanceError::Io(path, error) => write!(f, "could not read {}: {}", path.display(), error),
+            ConformanceError::Yaml(path, error) => write!(f, "could not read cases from {}: {}", path.display(), error),
+        }
+    }
+}
+
+/// The outcome of one case of a suite: the file it is in, its name, and how it failed, if it did
+pub type CaseResult = (PathBuf, String, Result<(), ConformanceFailure>);
+
+/// Reads the cases in a file of the suite
+pub fn load_cases<P: AsRef<Path>>(path: P) -> Result<Vec<ConformanceCase>, ConformanceError> {
+    let path = path.as_ref();
+    let yaml = fs::read_to_string(path).map_err(|error| ConformanceError::Io(path.to_path_buf(), error))?;
+
+    serde_yaml::from_str(&yaml).map_err(|error| ConformanceError::Yaml(path.to_path_buf(), error))
+}
+
+/// Runs every case of every `.yaml` or `.yml` file in the directory, in file name order and the
+/// order the cases are written in each
+pub fn run_suite<P: AsRef<Path>>(directory: P) -> Result<Vec<CaseResult>, ConformanceError> {
+    let directory = directory.as_ref();
+    let mut paths: Vec<PathBuf> = fs::read_dir(directory)
+        .and_then(|entries| entries.map(|entry| entry.map(|entry| entry.path())).collect())
+        .map_err(|error| ConformanceError::Io(directory.to_path_buf(), error))?;
+
+    paths.retain(|path| matches!(path.extension().and_then(|extension| extension.to_str()), Some("yaml" | "yml")));
+    paths.sort();
+
+    let mut results = vec![];
+
+    for path in paths {
+        for case in load_cases(&path)? {
+            let result = case.run();
+            results.push((path.clone(), case.name, result));
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::MeepleColor;
+
+    fn case(yaml: &str) -> ConformanceCase {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn should_pass_the_suite_kept_with_the_crate() {
+        let results = run_suite(Path::new(env!("CARGO_MANIFEST_DIR")).join("conformance")).unwrap();
+        let failures: Vec<_> = results.iter().filter(|(_, _, result)| result.is_err()).collect();
+
+        assert!(!results.is_empty());
+        assert!(failures.is_empty(), "{:?}", failures);
+    }
+
+    #[test]
+    fn should_fail_a_case_the_engine_disagrees_with() {
+        let wrong_score = case(
+            "
+            name: a four tile city scoring too much
+            position: |
+              corner-city@270  corner-city
+              .                corner-city@90
+            meeple: [{ x: 1, y: 0, region: 1, player: Green }]
+            move: { tile: corner-city@180, x: 0, y: 1 }
+            expect: { score: { Green: 10 } }
+            ",
+        );
+
+        assert_eq!(
+            wrong_score.run(),
+            Err(ConformanceFailure::WrongScore { expected: HashMap::from([(MeepleColor::Green, 10)]), actual: HashMap::from([(MeepleColor::Green, 8)]) })
+        );
+
+        let wrong_reason = case(
+            "
+            name: a mismatched edge blamed on the meeple
+            position: corner-city
+            move: { tile: corner-city@90, x: -1, y: 0 }
+            expect: { error: OtherMeepleAlreadyInConnectedRegion }
+            ",
+        );
+
+        assert_eq!(
+            wrong_reason.run(),
+            Err(ConformanceFailure::WrongReason {
+                expected: InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion,
+                actual: InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles,
+            })
+        );
+    }
+
+    #[test]
+    fn should_fail_a_case_that_cant_be_set_up() {
+        let meeple_in_the_air = case(
+            "
+            name: a meeple with nothing under it
+            position: corner-city
+            meeple: [{ x: 3, y: 3, region: 0, player: Red }]
+            move: { tile: corner-city@270, x: -1, y: 0 }
+            expect: {}
+            ",
+        );
+
+        assert_eq!(meeple_in_the_air.run(), Err(ConformanceFailure::NoTileUnderMeeple { x: 3, y: 3 }));
+        assert!(serde_yaml::from_str::<ConformanceCase>("name: no move\nposition: corner-city\nexpect: {}").is_err());
+    }
+}
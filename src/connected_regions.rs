@@ -1,12 +1,11 @@
 // data class UniqueTileRegion(val tileId: UUID, val regionIndex: Int, val region: Region, val edgeless: Boolean)
 
 use crate::board::Board;
-use crate::player::{Meeple, PlayerIdentifier, RegionIndex};
+use crate::player::{Meeple, RegionIndex};
 use crate::tile::{
-    BoardCoordinate, CardinalDirection, PlacedTile, Region, RegionType,
+    BoardCoordinate, BoardTopology, CardinalDirection, PlacedTile, Region, RegionType,
 };
 use std::collections::{HashMap, HashSet};
-use uuid::Uuid;
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 pub(crate) struct PlacedTileEdge {
@@ -15,11 +14,9 @@ pub(crate) struct PlacedTileEdge {
 }
 
 impl PlacedTileEdge {
-    pub(crate) fn opposing_tile_edge(&self) -> PlacedTileEdge {
+    pub(crate) fn opposing_tile_edge(&self, topology: &BoardTopology) -> PlacedTileEdge {
         PlacedTileEdge {
-            coordinate: self
-                .coordinate
-                .adjacent_in_direction(&self.global_direction),
+            coordinate: topology.adjacent_in_direction(&self.coordinate, &self.global_direction),
             global_direction: self.global_direction.tile_opposite(),
         }
     }
@@ -42,26 +39,28 @@ impl PlacedTileRegion {
             region,
         }
     }
-
-    pub(crate) fn edgeless(&self) -> bool {
-        self.region.edges().is_empty()
-    }
 }
 
-pub(crate) type ConnectedRegionId = usize;
+/// Identifies a [ConnectedRegion] on its board
+pub type ConnectedRegionId = usize;
 
+/// A feature on the board, made of the regions of each tile it runs across
 #[derive(Debug, Clone)]
-pub(crate) struct ConnectedRegion {
+pub struct ConnectedRegion {
     pub(crate) id: ConnectedRegionId,
     pub(crate) region_type: RegionType,
     pub(crate) tile_regions: Vec<PlacedTileRegion>,
     pub(crate) adjacent_regions: HashSet<ConnectedRegionId>,
     pub(crate) connected_edges: HashMap<PlacedTileEdge, Option<PlacedTileEdge>>,
+    /// Edges not yet joined to another tile, kept so closing a region doesn't need a full count
+    pub(crate) open_edge_count: usize,
+    pub(crate) pennant_count: usize,
 }
 
 #[derive(Debug)]
 pub(crate) enum ConnectedRegionMergeFailure {
     RegionTypeMismatch,
+    #[allow(dead_code)] // only produced by ConnectedRegionCollection, which is currently test-only
     EmptyCollection,
 }
 
@@ -70,6 +69,7 @@ impl ConnectedRegion {
     pub(crate) fn merge_mut(
         &mut self,
         other: Self,
+        topology: &BoardTopology,
     ) -> Result<&mut Self, ConnectedRegionMergeFailure> {
         if self.region_type != other.region_type {
             return Err(ConnectedRegionMergeFailure::RegionTypeMismatch);
@@ -77,12 +77,16 @@ impl ConnectedRegion {
 
         self.tile_regions.extend(other.tile_regions);
         self.adjacent_regions.extend(other.adjacent_regions);
+        self.open_edge_count += other.open_edge_count;
+        self.pennant_count += other.pennant_count;
 
         for (own_edge, foreign_edge) in other.connected_edges {
-            let opposite = own_edge.opposing_tile_edge();
+            let opposite = own_edge.opposing_tile_edge(topology);
 
             if self.connected_edges.contains_key(&opposite) {
-                self.connected_edges.insert(opposite, Some(own_edge));
+                // each side of the join was open until now
+                let was_open = self.connected_edges.insert(opposite, Some(own_edge)) == Some(None);
+                self.open_edge_count -= usize::from(was_open) + usize::from(foreign_edge.is_none());
             } else {
                 self.connected_edges.insert(own_edge, foreign_edge);
             }
@@ -112,12 +116,37 @@ impl ConnectedRegion {
             .collect()
     }
 
-    pub(crate) fn is_closed(&self) -> bool {
-        !self.connected_edges.is_empty() && self.connected_edges.values().all(|e| e.is_some())
+    pub fn is_closed(&self) -> bool {
+        !self.connected_edges.is_empty() && self.open_edge_count == 0
+    }
+
+    pub fn region_type(&self) -> &RegionType {
+        &self.region_type
+    }
+
+    /// Number of tile regions in the feature. A tile the feature crosses more than once counts
+    /// each time
+    pub fn tile_region_count(&self) -> usize {
+        self.tile_regions.len()
+    }
+
+    /// Edges of the feature not yet joined to another tile. Closed features have none
+    pub fn open_edge_count(&self) -> usize {
+        self.open_edge_count
+    }
+
+    pub fn pennant_count(&self) -> usize {
+        self.pennant_count
+    }
+
+    /// Coordinates of the tiles the feature crosses, once for each of its tile regions
+    pub fn tile_coordinates(&self) -> impl Iterator<Item = BoardCoordinate> + '_ {
+        self.tile_regions.iter().map(|region| region.tile_position)
     }
 
 }
 
+#[allow(dead_code)]
 trait ConnectedRegionCollection {
     fn merge_all(self) -> Result<ConnectedRegion, ConnectedRegionMergeFailure>;
 }
@@ -133,7 +162,7 @@ impl ConnectedRegionCollection for Vec<ConnectedRegion> {
 
         // Fold the remaining items
         for region in iter {
-            acc.merge_mut(region)?;
+            acc.merge_mut(region, &BoardTopology::Plane)?;
         }
 
         Ok(acc)
@@ -149,15 +178,16 @@ mod tests {
     };
     use crate::tile::CardinalDirection::{EastSouthEast, WestSouthWest};
     use crate::tile::RegionType::{City, Cloister, Field, Road};
-    use crate::tile::{BoardCoordinate, PlacedTile, RegionType, RenderStyle};
+    use crate::tile::{BoardCoordinate, BoardTopology, PlacedTile, RegionType, Rotation};
+    #[cfg(feature = "render")]
+    use crate::tile::RenderStyle;
     use crate::tile_definitions::{
         CLOISTER_IN_FIELD, CORNER_ROAD, CROSS_INTERSECTION, STRAIGHT_ROAD, THREE_SIDED_CITY,
     };
-    use uuid::Uuid;
 
     #[test]
     fn should_derive_adjacent_regions() {
-        let regions = PlacedTile::new(&STRAIGHT_ROAD, 0, 0, 0).own_connected_regions(0);
+        let regions = PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0).own_connected_regions(0);
 
         assert_eq!(regions.len(), 3);
 
@@ -183,7 +213,7 @@ mod tests {
         };
 
         assert_eq!(
-            edge.opposing_tile_edge(),
+            edge.opposing_tile_edge(&BoardTopology::Plane),
             PlacedTileEdge {
                 coordinate: BoardCoordinate::new(1, 0),
                 global_direction: WestSouthWest
@@ -199,6 +229,8 @@ mod tests {
             id: 0,
             adjacent_regions: Default::default(),
             connected_edges: Default::default(),
+            open_edge_count: 0,
+            pennant_count: 0,
         };
 
         let merge_result = test_region.merge_mut(ConnectedRegion {
@@ -207,7 +239,9 @@ mod tests {
             id: 1,
             adjacent_regions: Default::default(),
             connected_edges: Default::default(),
-        });
+            open_edge_count: 0,
+            pennant_count: 0,
+        }, &BoardTopology::Plane);
 
         assert!(matches!(
             merge_result,
@@ -217,20 +251,20 @@ mod tests {
 
     #[test]
     fn should_merge_connected_regions() {
-        let mut test_region = PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, 0)
+        let mut test_region = PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0)
             .own_connected_regions(0)
             .into_iter()
             .find(|r| r.region_type == Field)
             .expect("should exist");
 
-        let other_region = PlacedTile::new(&THREE_SIDED_CITY, 0, 1, 0)
+        let other_region = PlacedTile::new(&THREE_SIDED_CITY, 0, 1, Rotation::R0)
             .own_connected_regions(0)
             .into_iter()
             .find(|r| r.region_type == Field)
             .expect("should exist");
 
         let merge_result = test_region
-            .merge_mut(other_region)
+            .merge_mut(other_region, &BoardTopology::Plane)
             .expect("should merge ok");
 
         assert_eq!(merge_result.tile_regions.len(), 2)
@@ -238,14 +272,14 @@ mod tests {
 
     #[test]
     fn should_merge_connected_region_collection() {
-        let mut collection: Vec<_> = PlacedTile::new(&STRAIGHT_ROAD, 0, 0, 0)
+        let mut collection: Vec<_> = PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)
             .own_connected_regions(0)
             .into_iter()
             .filter(|r| r.region_type == Field)
             .collect();
 
         collection.extend(
-            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, 0)
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0)
                 .own_connected_regions(0)
                 .into_iter()
                 .filter(|r| r.region_type == Field),
@@ -262,6 +296,7 @@ mod tests {
     ) {
         let board = Board::new_with_tiles(placed_tiles.to_vec()).unwrap();
 
+        #[cfg(feature = "render")]
         println!("{}", board.render(&RenderStyle::Ascii));
 
         let connected_regions = board.get_connected_regions();
@@ -287,9 +322,9 @@ mod tests {
     fn should_join_regions_into_a_single_region() {
         assert_connected_regions(
             &[
-                PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, 0),
-                PlacedTile::new(&CLOISTER_IN_FIELD, 1, 0, 0),
-                PlacedTile::new(&CLOISTER_IN_FIELD, 1, 1, 0),
+                PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0),
+                PlacedTile::new(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0),
+                PlacedTile::new(&CLOISTER_IN_FIELD, 1, 1, Rotation::R0),
             ],
             &[
                 (Cloister, false),
@@ -304,8 +339,8 @@ mod tests {
     fn should_keep_regions_divided_by_other_elements_distinct() {
         assert_connected_regions(
             &[
-                PlacedTile::new(&STRAIGHT_ROAD, 0, 0, 0),
-                PlacedTile::new(&STRAIGHT_ROAD, 0, 1, 0),
+                PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+                PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R0),
             ],
             &[(Field, false), (Field, false), (Road, false)],
         )
@@ -315,15 +350,15 @@ mod tests {
     fn should_wrap_around_and_connect_regions() {
         assert_connected_regions(
             &[
-                PlacedTile::new(&CORNER_ROAD, -1, -1, 0),
-                PlacedTile::new(&STRAIGHT_ROAD, -1, 0, 0),
-                PlacedTile::new(&CORNER_ROAD, -1, 1, 3),
-                PlacedTile::new(&STRAIGHT_ROAD, 0, -1, 1),
-                PlacedTile::new(&CORNER_ROAD, 1, -1, 1),
-                PlacedTile::new(&STRAIGHT_ROAD, 1, 0, 0),
-                PlacedTile::new(&CORNER_ROAD, 1, 1, 2),
-                PlacedTile::new(&STRAIGHT_ROAD, 0, 1, 1),
-                PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, 0),
+                PlacedTile::new(&CORNER_ROAD, -1, -1, Rotation::R0),
+                PlacedTile::new(&STRAIGHT_ROAD, -1, 0, Rotation::R0),
+                PlacedTile::new(&CORNER_ROAD, -1, 1, Rotation::R270),
+                PlacedTile::new(&STRAIGHT_ROAD, 0, -1, Rotation::R90),
+                PlacedTile::new(&CORNER_ROAD, 1, -1, Rotation::R90),
+                PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+                PlacedTile::new(&CORNER_ROAD, 1, 1, Rotation::R180),
+                PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R90),
+                PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0),
             ],
             &[
                 (Cloister, false),
@@ -338,14 +373,14 @@ mod tests {
     fn should_multi_merge_regions() {
         assert_connected_regions(
             &[
-                PlacedTile::new(&CORNER_ROAD, -1, -1, 0),
-                PlacedTile::new(&CROSS_INTERSECTION, -1, 0, 0),
-                PlacedTile::new(&CORNER_ROAD, -1, 1, 3),
-                PlacedTile::new(&STRAIGHT_ROAD, 0, -1, 1),
-                PlacedTile::new(&CORNER_ROAD, 1, -1, 1),
-                PlacedTile::new(&STRAIGHT_ROAD, 1, 0, 0),
-                PlacedTile::new(&CORNER_ROAD, 1, 1, 2),
-                PlacedTile::new(&STRAIGHT_ROAD, 0, 1, 1),
+                PlacedTile::new(&CORNER_ROAD, -1, -1, Rotation::R0),
+                PlacedTile::new(&CROSS_INTERSECTION, -1, 0, Rotation::R0),
+                PlacedTile::new(&CORNER_ROAD, -1, 1, Rotation::R270),
+                PlacedTile::new(&STRAIGHT_ROAD, 0, -1, Rotation::R90),
+                PlacedTile::new(&CORNER_ROAD, 1, -1, Rotation::R90),
+                PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+                PlacedTile::new(&CORNER_ROAD, 1, 1, Rotation::R180),
+                PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R90),
             ],
             &[
                 (Field, false),
@@ -0,0 +1,876 @@
+#[cfg(feature = "render")]
+use crate::analysis::{best_move, move_value, Accuracy};
+use crate::board::Board;
+use crate::bot_strategy::{Bot, BotStrategy, EnsembleBot, NoisyBot, RiverOpening};
+#[cfg(feature = "render")]
+use crate::bot_strategy::MyopicBot;
+use crate::game_logic::GamePhase;
+#[cfg(feature = "render")]
+use crate::locale::{Language, Text};
+use crate::move_hints::MoveHint;
+use crate::observer::GameEvent;
+use crate::player::{Player, PlayerIdentifier, RegionIndex};
+use crate::score::Score;
+use crate::tile::{BoardCoordinate, TileDefinition, TilePlacement};
+#[cfg(feature = "render")]
+use crate::board::{EdgeConflict, InvalidTilePlacement, RenderAnnotations};
+#[cfg(feature = "render")]
+use crate::tile::{CardinalDirection, PlacedTile, RenderStyle, Rotation};
+use indexmap::IndexMap;
+#[cfg(feature = "render")]
+use std::cell::RefCell;
+#[cfg(feature = "render")]
+use std::io::{self, BufRead, Write};
+#[cfg(feature = "render")]
+use std::rc::Rc;
+
+/// What a player can see when it is their turn
+pub struct GameView<'a> {
+    pub board: &'a Board,
+    /// The player whose turn it is
+    pub player: &'a Player,
+    /// All players, in turn order
+    pub players: &'a IndexMap<PlayerIdentifier, Player>,
+    /// The tile drawn this turn
+    pub tile: &'static TileDefinition,
+    /// Score so far, not counting the open regions on the board
+    pub score: &'a Score,
+    /// Whether the river is still being laid, as some house rules and bots play it differently
+    pub phase: GamePhase,
+}
+
+pub enum PlayerAction {
+    /// Place the drawn tile (and possibly a meeple) as described by one of the offered hints
+    Place(MoveHint),
+    /// Place the drawn tile without a meeple, and take back the player's meeple from the region
+    /// of the tile at `coordinate` without it scoring. Only under rules with
+    /// [crate::rules::RuleSet::with_meeple_recall]; otherwise, or if the player has no meeple
+    /// there, the tile is placed and nothing is taken back
+    RecallMeeple {
+        tile_placement: TilePlacement,
+        coordinate: BoardCoordinate,
+        region_index: RegionIndex,
+    },
+    /// Leave the drawn tile unplaced
+    Pass,
+    /// Leave the drawn tile unplaced and take no further turns. Meeple already on the board stay
+    /// there
+    Resign,
+    /// Hand the player's seat to another controller, which is asked for this turn's move and makes
+    /// the rest of their moves, as [crate::Game::set_controller] does before the game
+    HandOver(Box<dyn PlayerController>),
+}
+
+/// Decides a player's moves, whether that player is a bot, a person at the terminal or a client
+/// connected from elsewhere
+pub trait PlayerController {
+    /// Picks a move for the drawn tile. `hints` lists every legal move; meeple placements are only
+    /// offered when the player has a meeple to place
+    fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction;
+
+    /// Name of the bot making the moves, if this is a bot
+    fn bot_name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Called with each event of the game, as its observers are; see [Bot::observe]
+    fn observe(&mut self, _event: &GameEvent) {}
+}
+
+/// One place the drawn tile can go, with the moves that place it there
+#[derive(Debug, Clone)]
+pub struct PlacementChoice {
+    pub tile_placement: TilePlacement,
+    /// The move leaving the meeple in hand, then one for each region a meeple can go in
+    pub moves: Vec<MoveHint>,
+}
+
+impl PlacementChoice {
+    /// The hints grouped by where they put the tile, in the order the placements were first offered
+    pub fn group(hints: &[MoveHint]) -> Vec<PlacementChoice> {
+        let mut choices: Vec<PlacementChoice> = vec![];
+
+        for hint in hints {
+            match choices.iter_mut().find(|choice| choice.tile_placement == hint.tile_placement) {
+                Some(choice) => choice.moves.push(hint.clone()),
+                None => choices.push(PlacementChoice { tile_placement: hint.tile_placement.clone(), moves: vec![hint.clone()] }),
+            }
+        }
+
+        for choice in &mut choices {
+            choice.moves.sort_by_key(|hint| hint.meeple_placement.map(|region_index| *region_index));
+        }
+
+        choices
+    }
+
+    pub fn without_meeple(&self) -> Option<&MoveHint> {
+        self.moves.iter().find(|hint| hint.meeple_placement.is_none())
+    }
+
+    pub fn with_meeple(&self) -> impl Iterator<Item = &MoveHint> {
+        self.moves.iter().filter(|hint| hint.meeple_placement.is_some())
+    }
+}
+
+/// The first step of a two phase move
+pub enum PlacementAction {
+    /// Place the tile as the choice at this index does
+    Place(usize),
+    Pass,
+    Resign,
+    HandOver(Box<dyn PlayerController>),
+}
+
+/// Decides each move as it's made at the table: first where the tile goes, then whether a meeple
+/// goes on it. Every two phase controller is a [PlayerController]
+pub trait TwoPhaseController {
+    /// Picks where to place the drawn tile. An index that isn't one of `choices` passes
+    fn choose_placement(&mut self, view: &GameView, choices: &[PlacementChoice]) -> PlacementAction;
+
+    /// Picks one of the `chosen` placement's moves, or `None` to go back and choose another
+    /// placement. `choices` are all of the placements, as offered to [Self::choose_placement]
+    fn choose_meeple(&mut self, view: &GameView, choices: &[PlacementChoice], chosen: &PlacementChoice) -> Option<MoveHint>;
+
+    /// Name of the bot making the moves, if this is a bot
+    fn bot_name(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+impl<T: TwoPhaseController> PlayerController for T {
+    fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+        let choices = PlacementChoice::group(hints);
+
+        loop {
+            let chosen = match self.choose_placement(view, &choices) {
+                PlacementAction::Place(index) => match choices.get(index) {
+                    Some(chosen) => chosen,
+                    None => return PlayerAction::Pass,
+                },
+                PlacementAction::Pass => return PlayerAction::Pass,
+                PlacementAction::Resign => return PlayerAction::Resign,
+                PlacementAction::HandOver(controller) => return PlayerAction::HandOver(controller),
+            };
+
+            if let Some(hint) = self.choose_meeple(view, &choices, chosen) {
+                return PlayerAction::Place(hint);
+            }
+        }
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        TwoPhaseController::bot_name(self)
+    }
+}
+
+impl PlayerController for BotStrategy {
+    fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+        let hint = match self {
+            // rollouts play for every player, so need to know who they are
+            BotStrategy::MonteCarlo(bot) => bot.select_hint_among(view.board, view.player, view.players, hints),
+            _ => self.select_hint(view.board, view.player, hints),
+        };
+
+        match hint {
+            Some(hint) => PlayerAction::Place(hint),
+            None => PlayerAction::Pass,
+        }
+    }
+
+    fn bot_name(&self) -> Option<&'static str> {
+        Bot::bot_name(self)
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        Bot::observe(self, event);
+    }
+}
+
+/// Plays each bot as a [PlayerController] the way [Bot] describes it. Bots can't share a blanket
+/// impl with [TwoPhaseController]s, which the compiler can't tell apart from them
+macro_rules! bot_controllers {
+    ($($bot:ty),*) => {$(
+        impl PlayerController for $bot {
+            fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+                match self.select_hint(view.board, view.player, hints) {
+                    Some(hint) => PlayerAction::Place(hint),
+                    None => PlayerAction::Pass,
+                }
+            }
+
+            fn bot_name(&self) -> Option<&'static str> {
+                Bot::bot_name(self)
+            }
+
+            fn observe(&mut self, event: &GameEvent) {
+                Bot::observe(self, event);
+            }
+        }
+    )*};
+}
+
+bot_controllers!(RiverOpening<BotStrategy>, NoisyBot, EnsembleBot);
+
+/// Asks a person for each move, showing them the board and the drawn tile and reading the number
+/// of the move they want
+#[cfg(feature = "render")]
+pub struct HumanController<R, W> {
+    input: R,
+    output: W,
+    render_style: RenderStyle,
+    show_ownership: bool,
+    show_region_labels: bool,
+    annotations: RenderAnnotations,
+    preview_moves: bool,
+    language: Language,
+    teaching: Option<Rc<RefCell<Accuracy>>>,
+    /// The bot handed the game to when the player asks for it to be finished for them
+    stand_in: BotStrategy,
+}
+
+/// Standard input, read a line at a time without holding on to the lock in between, so the
+/// player's controller and an observer such as [crate::step_debugger::StepDebugger] can both read it
+#[cfg(feature = "render")]
+#[derive(Default)]
+pub struct LineStdin {
+    line: Vec<u8>,
+    position: usize,
+}
+
+#[cfg(feature = "render")]
+impl io::Read for LineStdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let count = available.len().min(buf.len());
+
+        buf[..count].copy_from_slice(&available[..count]);
+        self.consume(count);
+
+        Ok(count)
+    }
+}
+
+#[cfg(feature = "render")]
+impl BufRead for LineStdin {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.line.len() {
+            let mut line = String::new();
+            io::stdin().read_line(&mut line)?;
+
+            self.line = line.into_bytes();
+            self.position = 0;
+        }
+
+        Ok(&self.line[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.line.len());
+    }
+}
+
+#[cfg(feature = "render")]
+impl HumanController<LineStdin, io::Stdout> {
+    pub fn stdin(render_style: RenderStyle) -> Self {
+        Self::new(LineStdin::default(), io::stdout(), render_style)
+    }
+}
+
+#[cfg(feature = "render")]
+impl<R: BufRead, W: Write> HumanController<R, W> {
+    pub fn new(input: R, output: W, render_style: RenderStyle) -> Self {
+        Self {
+            input,
+            output,
+            render_style,
+            show_ownership: false,
+            show_region_labels: false,
+            annotations: RenderAnnotations::default(),
+            preview_moves: false,
+            language: Language::default(),
+            teaching: None,
+            stand_in: BotStrategy::Myopic(MyopicBot),
+        }
+    }
+
+    /// Tint the board by who controls each region
+    pub fn with_ownership_overlay(mut self) -> Self {
+        self.show_ownership = true;
+        self
+    }
+
+    /// Number the drawn tile's regions, so the meeple placement each move offers can be found on it
+    pub fn with_region_labels(mut self) -> Self {
+        self.show_region_labels = true;
+        self
+    }
+
+    /// Draw the extras `annotations` asks for around the board, such as a legend of who each
+    /// meeple belongs to
+    pub fn with_annotations(mut self, annotations: RenderAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Talk to the player in `language` rather than English
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Before playing the chosen move, draw the board as it would look with it, meeple and all, and
+    /// ask to confirm it
+    pub fn with_move_preview(mut self) -> Self {
+        self.preview_moves = true;
+        self
+    }
+
+    /// The bot to finish the game with when the player asks, the myopic bot unless set
+    pub fn with_stand_in(mut self, bot: BotStrategy) -> Self {
+        self.stand_in = bot;
+        self
+    }
+
+    /// After each move, show the move the analysis engine would have played and how many points
+    /// better it was, keeping a tally in `accuracy` for a review at the end of the game
+    pub fn with_teaching(mut self, accuracy: Rc<RefCell<Accuracy>>) -> Self {
+        self.teaching = Some(accuracy);
+        self
+    }
+
+    fn prompt_placement(&mut self, view: &GameView, choices: &[PlacementChoice]) -> io::Result<PlacementAction> {
+        let name = view.player.name.clone().unwrap_or_else(|| view.player.meeple_color.to_string());
+
+        let board = match self.show_ownership {
+            true => view.board.render_ownership(&self.render_style),
+            false => view.board.render(&self.render_style),
+        };
+
+        writeln!(self.output, "{}", self.annotations.annotate(board, view.players, &self.render_style))?;
+        write!(self.output, "{}", view.score.render_with_meeple(view.players, view.board, &self.render_style))?;
+        let tile_name = self.language.tile_name(view.tile);
+        writeln!(self.output, "{}", self.language.format(Text::YouDrew, &[&name, &tile_name, &view.player.meeple.len()]))?;
+
+        self.show_drawn(view, Rotation::R0)?;
+
+        for (index, choice) in choices.iter().enumerate() {
+            writeln!(self.output, "{:>3}: {}", index, choice.tile_placement)?;
+        }
+
+        let rotations = view.tile.distinct_rotations();
+        let mut turned = 0;
+
+        loop {
+            let Some(line) = self.read_answer(Text::ChooseMove)? else {
+                return Ok(PlacementAction::Pass);
+            };
+
+            match line.as_str() {
+                "p" => return Ok(PlacementAction::Pass),
+                "r" => return Ok(PlacementAction::Resign),
+                "f" => {
+                    writeln!(self.output, "{}", self.language.format(Text::HandedOver, &[&self.stand_in.name()]))?;
+                    return Ok(PlacementAction::HandOver(Box::new(self.stand_in.clone())));
+                }
+                "t" => {
+                    turned = (turned + 1) % rotations.len();
+                    self.show_rotation(view, choices, rotations[turned])?;
+                }
+                _ if line.starts_with('?') => match line[1..].trim().parse::<usize>().ok().and_then(|index| choices.get(index)) {
+                    Some(choice) => self.show_preview(view, choice)?,
+                    None => writeln!(self.output, "{}", self.language.format(Text::NotAMove, &[&line]))?,
+                },
+                _ => match line.parse::<usize>().ok().filter(|index| *index < choices.len()) {
+                    Some(index) => return Ok(PlacementAction::Place(index)),
+                    None => match parse_placement(&line, rotations[turned]) {
+                        Some(placement) => match choices.iter().position(|choice| choice.tile_placement == placement) {
+                            Some(index) => return Ok(PlacementAction::Place(index)),
+                            None => self.explain_rejection(view, &line, placement)?,
+                        },
+                        None => writeln!(self.output, "{}", self.language.format(Text::NotAMove, &[&line]))?,
+                    },
+                },
+            }
+        }
+    }
+
+    /// Draws the drawn tile turned by `rotation`
+    fn show_drawn(&mut self, view: &GameView, rotation: Rotation) -> io::Result<()> {
+        let drawn = PlacedTile::new(view.tile, 0, 0, rotation);
+        let drawn = match self.show_region_labels {
+            true => drawn.render_region_labels(&self.render_style),
+            false => drawn.render_to_lines(&self.render_style),
+        };
+
+        for line in drawn {
+            writeln!(self.output, "  {}", line)?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the drawn tile as the player has turned it and lists only the moves placing it that
+    /// way, still numbered as in the full list
+    fn show_rotation(&mut self, view: &GameView, choices: &[PlacementChoice], rotation: Rotation) -> io::Result<()> {
+        writeln!(self.output, "  {}", rotation)?;
+        self.show_drawn(view, rotation)?;
+
+        let mut fits = false;
+
+        for (index, choice) in choices.iter().enumerate().filter(|(_, choice)| choice.tile_placement.rotation == rotation) {
+            writeln!(self.output, "{:>3}: {}", index, choice.tile_placement)?;
+            fits = true;
+        }
+
+        if !fits {
+            writeln!(self.output, "{}", self.language.text(Text::DoesNotFitTurned))?;
+        }
+
+        Ok(())
+    }
+
+    /// Draws the board with the tile placed as the choice places it, and what that would join,
+    /// complete, score and give back, for the player to look over a move before choosing it
+    fn show_preview(&mut self, view: &GameView, choice: &PlacementChoice) -> io::Result<()> {
+        let Some(hint) = choice.without_meeple() else {
+            return Ok(());
+        };
+
+        let preview = hint.preview(view.board, view.player);
+        let points = preview.score_delta.get_player(view.player).copied().unwrap_or(0);
+        let returned = preview.returned.iter().filter(|player| **player == view.player.meeple_color).count();
+
+        writeln!(self.output, "{}", hint.render_preview(view.board, view.player.meeple_color, &self.render_style))?;
+        writeln!(self.output, "{}", self.language.format(Text::PlacementPreview, &[&preview.joined, &preview.completed.len(), &points, &returned]))
+    }
+
+    /// Says why the tile can't go where the player typed, drawing it beside the tile it doesn't
+    /// match where that's why
+    fn explain_rejection(&mut self, view: &GameView, line: &str, placement: TilePlacement) -> io::Result<()> {
+        let placed = PlacedTile { tile: view.tile, placement, meeple: None };
+
+        let Err(error) = view.board.validate_tile_placement(&placed) else {
+            return writeln!(self.output, "{}", self.language.format(Text::NotAMove, &[&line]));
+        };
+
+        writeln!(self.output, "{}", self.language.explain_placement_error(view.board, view.tile, &placed.placement, &error))?;
+
+        if let (InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles, Some(conflict)) = (error, view.board.edge_conflict(view.tile, &placed.placement)) {
+            for line in render_edge_conflict(&placed, &conflict, &self.render_style) {
+                writeln!(self.output, "  {}", line)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Lists the regions of the placed tile a meeple can go in, with what each would score as the
+    /// board stands, and reads which one the player wants
+    fn prompt_meeple(&mut self, view: &GameView, choices: &[PlacementChoice], chosen: &PlacementChoice) -> io::Result<Option<MoveHint>> {
+        let without_meeple = chosen.without_meeple().cloned();
+
+        let hint = match chosen.with_meeple().next() {
+            None => without_meeple,
+            Some(_) => {
+                let placed = PlacedTile { tile: view.tile, placement: chosen.tile_placement.clone(), meeple: None };
+
+                for line in placed.render_region_labels(&self.render_style) {
+                    writeln!(self.output, "  {}", line)?;
+                }
+
+                let points = |hint: &MoveHint| hint.score_delta(view.board, view.player, true).get_player(view.player).copied().unwrap_or(0);
+                let base = without_meeple.as_ref().map(points).unwrap_or(0);
+
+                writeln!(self.output, "  n: {}", self.language.text(Text::NoMeeple))?;
+
+                for hint in chosen.with_meeple() {
+                    let Some(region_index) = hint.meeple_placement else { continue };
+                    let region_name = self.language.region_name(&view.tile.regions[*region_index].region_type());
+                    writeln!(self.output, "{:>3}: {}", *region_index, self.language.format(Text::MeepleRegion, &[&region_name, &(points(hint) - base)]))?;
+                }
+
+                loop {
+                    let Some(line) = self.read_answer(Text::ChooseMeeple)? else {
+                        break without_meeple;
+                    };
+
+                    match line.as_str() {
+                        "n" => break without_meeple,
+                        "b" => return Ok(None),
+                        _ => match chosen.with_meeple().find(|hint| hint.meeple_placement.map(|region_index| (*region_index).to_string()).as_deref() == Some(line.as_str())) {
+                            Some(hint) => break Some(hint.clone()),
+                            None => writeln!(self.output, "{}", self.language.format(Text::NotAMove, &[&line]))?,
+                        },
+                    }
+                }
+            }
+        };
+
+        let Some(hint) = hint else {
+            return Ok(None);
+        };
+
+        if self.preview_moves && !self.confirm(view, &hint)? {
+            return Ok(None);
+        }
+
+        let hints: Vec<MoveHint> = choices.iter().flat_map(|choice| choice.moves.iter().cloned()).collect();
+        self.teach(view, &hints, &hint)?;
+
+        Ok(Some(hint))
+    }
+
+    /// Asks `question` and reads the trimmed answer, or `None` once the input has run out
+    fn read_answer(&mut self, question: Text) -> io::Result<Option<String>> {
+        write!(self.output, "{}", self.language.text(question))?;
+        self.output.flush()?;
+
+        let mut line = String::new();
+
+        if self.input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(line.trim().to_string()))
+    }
+
+    /// Shows the board with the move on it and asks whether to play it. Running out of input
+    /// plays it, as choosing it did, and anything but yes goes back to choosing
+    fn confirm(&mut self, view: &GameView, hint: &MoveHint) -> io::Result<bool> {
+        writeln!(self.output, "{}", hint.render_preview(view.board, view.player.meeple_color, &self.render_style))?;
+        write!(self.output, "{}", self.language.format(Text::ConfirmMove, &[&self.language.describe_move(hint)]))?;
+        self.output.flush()?;
+
+        let mut line = String::new();
+        self.input.read_line(&mut line)?;
+
+        Ok(self.language.is_yes(&line))
+    }
+
+    fn teach(&mut self, view: &GameView, hints: &[MoveHint], played: &MoveHint) -> io::Result<()> {
+        let Some(accuracy) = &self.teaching else {
+            return Ok(());
+        };
+
+        let opponents: Vec<PlayerIdentifier> = view.players.keys().filter(|id| **id != view.player.meeple_color).copied().collect();
+
+        let played_value = move_value(view.board, view.player, &opponents, played);
+        let Some((best_value, best)) = best_move(view.board, view.player, &opponents, hints) else {
+            return Ok(());
+        };
+
+        accuracy.borrow_mut().record(played_value, best_value);
+
+        if played_value >= best_value {
+            writeln!(self.output, "{}", self.language.text(Text::EngineAgrees))
+        } else {
+            let best = self.language.describe_move(best);
+            writeln!(self.output, "{}", self.language.format(Text::EngineBetter, &[&best, &(best_value - played_value)]))
+        }
+    }
+}
+
+/// A placement typed as `x y rotation`, or as placements are shown, e.g. `(1, -2) r90`. The rotation
+/// is in degrees, and without one the tile goes the way the player has turned it, `rotation`
+#[cfg(feature = "render")]
+fn parse_placement(line: &str, rotation: Rotation) -> Option<TilePlacement> {
+    let line = line.replace(['(', ')', ','], " ");
+    let mut parts = line.split_whitespace();
+
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    let rotation = match parts.next() {
+        Some(degrees) => Rotation::from_degrees(degrees.trim_start_matches('r').parse::<u16>().ok()? % 360)?,
+        None => rotation,
+    };
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(TilePlacement { coordinate: BoardCoordinate::new(x, y), rotation })
+}
+
+/// The placed tile drawn beside the tile it doesn't match, on the side it's on
+#[cfg(feature = "render")]
+fn render_edge_conflict(placed: &PlacedTile, conflict: &EdgeConflict, style: &RenderStyle) -> Vec<String> {
+    let (own, theirs) = (placed.render_to_lines(style), conflict.neighbour.render_to_lines(style));
+    let side_by_side = |left: &[String], right: &[String]| left.iter().zip(right).map(|(left, right)| format!("{}{}", left, right)).collect();
+
+    match conflict.direction {
+        CardinalDirection::North => theirs.into_iter().chain(own).collect(),
+        CardinalDirection::South => own.into_iter().chain(theirs).collect(),
+        CardinalDirection::East => side_by_side(&own, &theirs),
+        _ => side_by_side(&theirs, &own),
+    }
+}
+
+#[cfg(feature = "render")]
+impl<R: BufRead, W: Write> TwoPhaseController for HumanController<R, W> {
+    fn choose_placement(&mut self, view: &GameView, choices: &[PlacementChoice]) -> PlacementAction {
+        self.prompt_placement(view, choices).unwrap_or_else(|error| {
+            eprintln!("Could not read a move ({}), passing", error);
+            PlacementAction::Pass
+        })
+    }
+
+    fn choose_meeple(&mut self, view: &GameView, choices: &[PlacementChoice], chosen: &PlacementChoice) -> Option<MoveHint> {
+        self.prompt_meeple(view, choices, chosen).unwrap_or_else(|error| {
+            // going back to the placement reads from the input again, which passes once it fails
+            eprintln!("Could not read a meeple placement ({})", error);
+            None
+        })
+    }
+}
+
+#[cfg(all(test, feature = "render"))]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::LazyBot;
+    use crate::test_util::TestPlayer;
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+    use std::io::Cursor;
+
+    #[test]
+    fn should_ask_until_given_a_valid_move() {
+        let board = Board::new();
+        let player = Player::red().with_name("Alice");
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+
+        let mut output = vec![];
+        let mut controller = HumanController::new(Cursor::new("seven\n99\n1\nx\nn\n"), &mut output, RenderStyle::Ascii);
+
+        let PlayerAction::Place(hint) = controller.choose_move(&view, &hints) else {
+            panic!("should have placed the tile");
+        };
+
+        assert_eq!(hint.tile_placement, PlacementChoice::group(&hints)[1].tile_placement);
+        assert_eq!(hint.meeple_placement, None);
+        assert_eq!(String::from_utf8(output).unwrap().matches("is not one of the moves").count(), 3);
+    }
+
+    #[test]
+    fn should_compare_moves_with_the_engines_when_teaching() {
+        let mut bob = Player::green();
+        let board = Board::new_with_tiles([bob.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+
+        let alice = Player::red();
+        let players = IndexMap::from([(alice.meeple_color, alice.clone()), (bob.meeple_color, bob.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &alice, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let hints = board.get_move_hints(&SIDE_CITY, false);
+        let choices = PlacementChoice::group(&hints);
+        let closes_bobs_city = choices.iter().position(|choice| choice.tile_placement.coordinate.y == 1 && choice.tile_placement.rotation == Rotation::R180).unwrap();
+        let elsewhere = choices.iter().position(|choice| choice.tile_placement.coordinate.y == -1).unwrap();
+
+        let accuracy = Rc::new(RefCell::new(Accuracy::default()));
+        let input = format!("{}\n{}\n", elsewhere, closes_bobs_city);
+
+        let mut output = vec![];
+        let mut controller = HumanController::new(Cursor::new(input), &mut output, RenderStyle::Ascii).with_teaching(Rc::clone(&accuracy));
+
+        controller.choose_move(&view, &hints);
+        controller.choose_move(&view, &hints);
+
+        let output = String::from_utf8(output).unwrap();
+
+        // closing Bob's city scores it 4 points, rather than the 1 it is worth left open
+        assert!(output.contains("The engine would have played that too"));
+        assert!(output.contains("points better"));
+        assert_eq!(*accuracy.borrow(), Accuracy { moves: 2, best_moves: 1, points_lost: 3 });
+        assert_eq!(accuracy.borrow().percentage(), 50.0);
+    }
+
+    #[test]
+    fn should_pass_when_input_runs_out() {
+        let board = Board::new();
+        let player = Player::red();
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let mut controller = HumanController::new(Cursor::new(""), vec![], RenderStyle::Ascii);
+
+        assert!(matches!(controller.choose_move(&view, &board.get_move_hints(&SIDE_CITY, true)), PlayerAction::Pass));
+    }
+
+    #[test]
+    fn should_hand_the_game_to_a_bot_when_asked() {
+        let board = Board::new();
+        let player = Player::red();
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let mut output = vec![];
+        let mut controller = HumanController::new(Cursor::new("f\n"), &mut output, RenderStyle::Ascii).with_stand_in(BotStrategy::Lazy(LazyBot));
+
+        let PlayerAction::HandOver(stand_in) = controller.choose_move(&view, &board.get_move_hints(&SIDE_CITY, true)) else {
+            panic!("should hand over");
+        };
+
+        assert_eq!(stand_in.bot_name(), Some("lazy"));
+        assert!(String::from_utf8(output).unwrap().contains("The lazy bot will finish the game for you"));
+    }
+
+    #[test]
+    fn should_preview_a_move_until_it_is_confirmed() {
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let player = Player::red();
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+        let region_index = hints.iter().find_map(|hint| hint.meeple_placement).unwrap();
+
+        let mut output = vec![];
+        let input = format!("0\n{}\nn\n0\nn\n\n", *region_index);
+        let mut controller = HumanController::new(Cursor::new(input), &mut output, RenderStyle::Ascii).with_move_preview();
+
+        let PlayerAction::Place(hint) = controller.choose_move(&view, &hints) else {
+            panic!("should have placed the tile");
+        };
+
+        assert_eq!(hint.meeple_placement, None);
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.matches("[Y/n]").count(), 2);
+        assert_eq!(output.matches("◌").count(), 1);
+    }
+
+    #[test]
+    fn should_offer_a_meeple_only_where_one_can_go_after_placing_the_tile() {
+        let mut bob = Player::green();
+        let board = Board::new_with_tiles([bob.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+
+        let alice = Player::red();
+        let players = IndexMap::from([(alice.meeple_color, alice.clone()), (bob.meeple_color, bob.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &alice, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+        let choices = PlacementChoice::group(&hints);
+        let joins_bobs_city = choices.iter().position(|choice| choice.tile_placement.coordinate.y == 1 && choice.tile_placement.rotation == Rotation::R180).unwrap();
+        let elsewhere = choices.iter().position(|choice| choice.tile_placement.coordinate.y == -1).unwrap();
+
+        assert!(choices.iter().all(|choice| choice.without_meeple().is_some()));
+
+        let mut output = vec![];
+        let input = format!("{}\nb\n{}\n1\n", joins_bobs_city, elsewhere);
+        let mut controller = HumanController::new(Cursor::new(input), &mut output, RenderStyle::Ascii);
+
+        let PlayerAction::Place(hint) = controller.choose_move(&view, &hints) else {
+            panic!("should have placed the tile");
+        };
+
+        assert_eq!(hint.tile_placement, choices[elsewhere].tile_placement);
+        assert_eq!(hint.meeple_placement.map(|region_index| *region_index), Some(1));
+
+        // Bob's meeple already holds the city the first placement would join
+        let first_offer = String::from_utf8(output).unwrap();
+        let first_offer = first_offer.split("go back").next().unwrap().to_string();
+        assert!(first_offer.contains("  0: field"));
+        assert!(!first_offer.contains("  1: city"));
+    }
+
+    #[test]
+    fn should_explain_why_a_typed_placement_is_rejected() {
+        let board = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+        let player = Player::red();
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &STRAIGHT_ROAD, score: &score, phase: GamePhase::Main };
+
+        let mut output = vec![];
+        let mut controller = HumanController::new(Cursor::new("0 -1 90\n(0, -1) r0\nn\n"), &mut output, RenderStyle::Ascii);
+
+        let PlayerAction::Place(hint) = controller.choose_move(&view, &board.get_move_hints(&STRAIGHT_ROAD, true)) else {
+            panic!("should have placed the tile");
+        };
+
+        assert_eq!(hint.tile_placement, TilePlacement { coordinate: BoardCoordinate::new(0, -1), rotation: Rotation::R0 });
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("the south edge of your tile is a field but the tile below has a road on its north edge"));
+        // the two tiles are drawn one above the other, the road running into the field
+        assert!(output.contains("  ┃░░░░░░░░░░┃\n   ━━━━━━━━━━ \n   ━━━━━━━━━━ \n  ┃░░░░██░░░░┃"));
+    }
+
+    #[test]
+    fn should_turn_the_drawn_tile_through_its_distinct_rotations() {
+        let board = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+        let player = Player::red();
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &STRAIGHT_ROAD, score: &score, phase: GamePhase::Main };
+
+        // a straight road looks the same turned twice, so turning it twice brings it back round
+        let mut output = vec![];
+        let mut controller = HumanController::new(Cursor::new("t\n0 -1\nt\n0 -1\nn\n"), &mut output, RenderStyle::Ascii);
+
+        let PlayerAction::Place(hint) = controller.choose_move(&view, &board.get_move_hints(&STRAIGHT_ROAD, true)) else {
+            panic!("should have placed the tile");
+        };
+
+        assert_eq!(hint.tile_placement, TilePlacement { coordinate: BoardCoordinate::new(0, -1), rotation: Rotation::R0 });
+
+        let output = String::from_utf8(output).unwrap();
+        let turned = output.split("  r90\n").nth(1).unwrap().split("resign").next().unwrap();
+
+        // with the road running across, the tile fits nowhere, and no placements are listed
+        assert!(turned.contains("The tile doesn't fit anywhere turned this way"));
+        assert!(!turned.contains(" r0\n"));
+        assert_eq!(output.matches("the south edge of your tile is a field").count(), 1);
+        assert_eq!(output.matches("  r0\n").count(), 1);
+    }
+
+    #[test]
+    fn should_show_what_a_move_would_do_before_it_is_chosen() {
+        let mut bob = Player::green();
+        let board = Board::new_with_tiles([bob.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+
+        let alice = Player::red();
+        let players = IndexMap::from([(alice.meeple_color, alice.clone()), (bob.meeple_color, bob.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &alice, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let hints = board.get_move_hints(&SIDE_CITY, true);
+        let closes_bobs_city = PlacementChoice::group(&hints).iter().position(|choice| choice.tile_placement.coordinate.y == 1 && choice.tile_placement.rotation == Rotation::R180).unwrap();
+
+        let mut output = vec![];
+        let input = format!("?{}\n?99\n{}\nn\n", closes_bobs_city, closes_bobs_city);
+        let mut controller = HumanController::new(Cursor::new(input), &mut output, RenderStyle::Ascii);
+
+        assert!(matches!(controller.choose_move(&view, &hints), PlayerAction::Place(_)));
+
+        // closing the city scores Bob's meeple rather than any of Alice's
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Joins onto the board's features 1 times and completes 1, scoring you 0 points now and giving back 0 of your meeple"));
+        assert!(output.contains("[?99] is not one of the moves"));
+    }
+
+    #[test]
+    fn should_talk_to_the_player_in_their_language() {
+        let board = Board::new();
+        let player = Player::red().with_name("Alice");
+        let players = IndexMap::from([(player.meeple_color, player.clone())]);
+        let score = Score::new();
+        let view = GameView { board: &board, player: &player, players: &players, tile: &SIDE_CITY, score: &score, phase: GamePhase::Main };
+
+        let mut output = vec![];
+        let mut controller = HumanController::new(Cursor::new("x\n0\n"), &mut output, RenderStyle::Ascii).with_language(Language::German);
+
+        controller.choose_move(&view, &board.get_move_hints(&SIDE_CITY, true));
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Alice, du hast Stadtrand gezogen"));
+        assert!(output.contains("[x] ist keiner der Züge"));
+    }
+}
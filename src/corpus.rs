@@ -0,0 +1,166 @@
+use crate::observer::GameObserver;
+use crate::replay::{Replay, ReplayError, ReplayRecorder, ReplayTurn};
+use crate::score::Score;
+use crate::simulate::{self, SimulationConfig};
+use std::cell::RefCell;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// The outcome of verifying one replay of a corpus: archived games, replayed after changes to the
+/// rules engine to check old games still score the way they did
+pub type Verification = (PathBuf, Result<(), ReplayError>);
+
+/// Where two recordings of the same game first part ways, e.g. games played from the same seeds
+/// by two builds of the engine
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// The games' moves differ at `turn`, counting from 0, or one game ended there and the other
+    /// didn't. A turn is `None` past the end of its game
+    Move { turn: usize, first: Option<ReplayTurn>, second: Option<ReplayTurn> },
+    /// Every turn was the same, but the games were scored differently
+    FinalScore { first: Score, second: Score },
+}
+
+/// The first place the two games differ, or `None` if they were played and scored the same
+pub fn first_divergence(first: &Replay, second: &Replay) -> Option<Divergence> {
+    let turns = first.turns.len().max(second.turns.len());
+
+    if let Some(turn) = (0..turns).find(|turn| first.turns.get(*turn) != second.turns.get(*turn)) {
+        return Some(Divergence::Move { turn, first: first.turns.get(turn).cloned(), second: second.turns.get(turn).cloned() });
+    }
+
+    let (first, second) = (first.final_score(), second.final_score());
+
+    (first != second).then_some(Divergence::FinalScore { first, second })
+}
+
+/// The outcome of comparing a replay of one corpus with its namesake in another
+pub type CorpusDivergence = (PathBuf, Result<Option<Divergence>, ReplayError>);
+
+/// Compares every replay in the `first` directory with the replay of the same name in `second`,
+/// in file name order. Corpora recorded by [record_corpus] from the same seed have the same names
+pub fn compare_corpora<P: AsRef<Path>, Q: AsRef<Path>>(first: P, second: Q) -> io::Result<Vec<CorpusDivergence>> {
+    Ok(replay_paths(first)?
+        .into_iter()
+        .map(|path| {
+            let other = second.as_ref().join(path.file_name().expect("replays are files"));
+            let divergence = Replay::load(&path).and_then(|first| Ok(first_divergence(&first, &Replay::load(&other)?)));
+
+            (path, divergence)
+        })
+        .collect())
+}
+
+/// The replays in the directory, in file name order
+fn replay_paths<P: AsRef<Path>>(directory: P) -> io::Result<Vec<PathBuf>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<_>>()?;
+
+    paths.retain(|path| matches!(path.extension().and_then(|extension| extension.to_str()), Some("json" | "bin")));
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Loads every replay in the directory and verifies it (see [Replay::verify]), in file name order
+pub fn verify_corpus<P: AsRef<Path>>(directory: P) -> io::Result<Vec<Verification>> {
+    Ok(replay_paths(directory)?
+        .into_iter()
+        .map(|path| {
+            let verified = Replay::load(&path).and_then(|replay| replay.verify());
+            (path, verified)
+        })
+        .collect())
+}
+
+/// Plays the simulation and adds a replay of each game to the corpus, named by the game's seed so
+/// entries recorded by different runs don't overwrite each other. Returns the paths written
+pub fn record_corpus<P: AsRef<Path>>(config: SimulationConfig, directory: P) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(&directory)?;
+
+    let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+    let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(Rc::clone(&recorder))];
+    simulate::run_observed(config, &mut observers);
+
+    let recorder = recorder.borrow();
+
+    recorder
+        .replays()
+        .iter()
+        .map(|replay| {
+            let path = directory.as_ref().join(format!("{}.json", replay.seed));
+
+            replay.save(&path).map_err(|error| match error {
+                ReplayError::Io(error) => error,
+                error => io::Error::other(error.to_string()),
+            })?;
+
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+    use crate::player::Player;
+
+    #[test]
+    fn should_verify_recorded_games_and_flag_a_changed_one() {
+        let directory = std::env::temp_dir().join(format!("carcassonne-corpus-{}", uuid::Uuid::new_v4()));
+        let players = [Player::red(), Player::green()].map(|player| player.with_bot(BotStrategy::Lazy(LazyBot)));
+        let config = SimulationConfig::new(players, 2, [3; 32]).unwrap();
+
+        let written = record_corpus(config, &directory).unwrap();
+        assert_eq!(written.len(), 2);
+
+        let mut tampered = Replay::load(&written[0]).unwrap();
+        tampered.final_score[0].points += 1;
+        tampered.save(&written[0]).unwrap();
+
+        let verified = verify_corpus(&directory).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(verified.len(), 2);
+        assert_eq!(verified.iter().filter(|(_, result)| result.is_ok()).count(), 1);
+        assert!(verified.iter().any(|(path, result)| path == &written[0] && result.is_err()));
+    }
+
+    #[test]
+    fn should_find_where_two_recordings_of_the_same_games_diverge() {
+        let directory = std::env::temp_dir().join(format!("carcassonne-divergence-{}", uuid::Uuid::new_v4()));
+        let (first, second) = (directory.join("first"), directory.join("second"));
+        let config = || {
+            let players = [Player::red(), Player::green()].map(|player| player.with_bot(BotStrategy::Lazy(LazyBot)));
+            SimulationConfig::new(players, 3, [3; 32]).unwrap()
+        };
+
+        record_corpus(config(), &first).unwrap();
+        let written = record_corpus(config(), &second).unwrap();
+
+        assert!(compare_corpora(&first, &second).unwrap().iter().all(|(_, divergence)| matches!(divergence, Ok(None))));
+
+        let mut moved = Replay::load(&written[0]).unwrap();
+        moved.turns[4].placement = None;
+        moved.save(&written[0]).unwrap();
+
+        let mut rescored = Replay::load(&written[1]).unwrap();
+        rescored.final_score[0].points += 1;
+        rescored.save(&written[1]).unwrap();
+
+        fs::remove_file(&written[2]).unwrap();
+
+        let compared = compare_corpora(&first, &second).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let divergence = |written: &PathBuf| &compared.iter().find(|(path, _)| path.file_name() == written.file_name()).unwrap().1;
+
+        assert!(matches!(divergence(&written[0]), Ok(Some(Divergence::Move { turn: 4, first: Some(_), second: Some(ReplayTurn { placement: None, .. }) }))));
+        assert!(matches!(divergence(&written[1]), Ok(Some(Divergence::FinalScore { .. }))));
+        assert!(matches!(divergence(&written[2]), Err(ReplayError::Io(_))));
+    }
+}
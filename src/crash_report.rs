@@ -0,0 +1,317 @@
+//! What was going on when a game panicked: the game's seed, every move played up to the panic
+//! and the board's share code, written to a file by the panic hook [install] sets up, so a panic
+//! deep in a long simulation can be looked into without playing the run again to find it
+
+use crate::board::Board;
+use crate::player::{PlayerIdentifier, RegionIndex};
+use crate::simulate::Seed;
+use crate::tile::TilePlacement;
+use base64::{engine::general_purpose, Engine as _};
+use std::cell::RefCell;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock, TryLockError, Weak};
+
+/// Whether games keep track of their moves for a report, only once the hook is installed
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static IN_PROGRESS: RefCell<Option<GameInProgress>> = const { RefCell::new(None) };
+}
+
+/// The game being played on a thread
+struct GameInProgress {
+    game_index: usize,
+    seed: Seed,
+    board: Weak<RwLock<Board>>,
+    turns: Vec<PlayedTurn>,
+}
+
+/// A move played, in the order they were played. `placement` is `None` for a turn passed on
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayedTurn {
+    pub player: PlayerIdentifier,
+    pub tile: &'static str,
+    pub placement: Option<(TilePlacement, Option<RegionIndex>)>,
+}
+
+/// Keeps track of the game on this thread until dropped, however the game ends
+pub(crate) struct TrackedGame(());
+
+impl Drop for TrackedGame {
+    fn drop(&mut self) {
+        // the thread may be shutting down
+        let _ = IN_PROGRESS.try_with(|game| game.borrow_mut().take());
+    }
+}
+
+/// Starts keeping track of the game played on this thread, when there is a hook to report it
+pub(crate) fn track(game_index: usize, seed: Seed, board: &Arc<RwLock<Board>>) -> Option<TrackedGame> {
+    if !TRACKING.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    IN_PROGRESS.with(|game| *game.borrow_mut() = Some(GameInProgress { game_index, seed, board: Arc::downgrade(board), turns: vec![] }));
+
+    Some(TrackedGame(()))
+}
+
+/// A game tracked while others are played on the same thread, as an arena plays its games a turn
+/// at a time, set aside between the turns it has played
+pub(crate) struct SuspendedGame(Option<GameInProgress>);
+
+/// Starts keeping track of a game to be played a turn at a time, when there is a hook to report it
+pub(crate) fn suspended(game_index: usize, seed: Seed, board: &Arc<RwLock<Board>>) -> SuspendedGame {
+    SuspendedGame(TRACKING.load(Ordering::Relaxed).then(|| GameInProgress { game_index, seed, board: Arc::downgrade(board), turns: vec![] }))
+}
+
+impl SuspendedGame {
+    /// Runs `play` with this the game tracked on the thread, so a panic in it is reported as this
+    /// game's, then sets the game aside again
+    pub(crate) fn resume<T>(&mut self, play: impl FnOnce() -> T) -> T {
+        let Some(game) = self.0.take() else {
+            return play();
+        };
+
+        let previous = IN_PROGRESS.with(|tracked| tracked.borrow_mut().replace(game));
+        let _resumed = Resumed { suspended: self, previous };
+
+        play()
+    }
+}
+
+/// Puts back whatever was tracked on the thread before a suspended game was resumed, however
+/// the game's turn ends
+struct Resumed<'a> {
+    suspended: &'a mut SuspendedGame,
+    previous: Option<GameInProgress>,
+}
+
+impl Drop for Resumed<'_> {
+    fn drop(&mut self) {
+        // the thread may be shutting down
+        self.suspended.0 = IN_PROGRESS.try_with(|tracked| std::mem::replace(&mut *tracked.borrow_mut(), self.previous.take())).ok().flatten();
+    }
+}
+
+/// Notes a move played in the game tracked on this thread, if there is one
+pub(crate) fn record_turn(player: PlayerIdentifier, tile: &'static str, placement: Option<&(TilePlacement, Option<RegionIndex>)>) {
+    if !TRACKING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    IN_PROGRESS.with(|game| {
+        if let Some(game) = game.borrow_mut().as_mut() {
+            game.turns.push(PlayedTurn { player, tile, placement: placement.cloned() });
+        }
+    });
+}
+
+/// The game being played on this thread when it panicked
+#[derive(Debug, Clone)]
+pub struct CrashReport {
+    /// Which game of the run, counting from 0
+    pub game_index: usize,
+    pub seed: Seed,
+    pub turns: Vec<PlayedTurn>,
+    /// `None` when the panic came part way through changing the board
+    pub share_code: Option<String>,
+}
+
+impl CrashReport {
+    /// The report for the game being played on this thread, if one is being tracked
+    pub fn current() -> Option<Self> {
+        IN_PROGRESS
+            .try_with(|game| {
+                // a panic while the turn was being noted leaves the game borrowed
+                let game = game.try_borrow().ok()?;
+                let game = game.as_ref()?;
+
+                let share_code = game.board.upgrade().and_then(|board| match board.try_read() {
+                    Ok(board) => Some(board.to_share_code()),
+                    Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner().to_share_code()),
+                    Err(TryLockError::WouldBlock) => None,
+                });
+
+                Some(CrashReport { game_index: game.game_index, seed: game.seed, turns: game.turns.clone(), share_code })
+            })
+            .ok()
+            .flatten()
+    }
+}
+
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Game: {}", self.game_index)?;
+        writeln!(f, "Game seed: {}", general_purpose::URL_SAFE.encode(self.seed))?;
+
+        match &self.share_code {
+            Some(code) => writeln!(f, "Board: {}", code)?,
+            None => writeln!(f, "Board: unknown, it panicked part way through placing a tile")?,
+        }
+
+        writeln!(f, "Moves:")?;
+
+        for (index, turn) in self.turns.iter().enumerate() {
+            write!(f, "{:>4}. {} drew {}, ", index + 1, turn.player, turn.tile)?;
+
+            match &turn.placement {
+                Some((placement, Some(region_index))) => writeln!(f, "placed it at {} with a meeple on region {}", placement, **region_index)?,
+                Some((placement, None)) => writeln!(f, "placed it at {}", placement)?,
+                None => writeln!(f, "passed")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes a [CrashReport] to `path` when a game panics, after the panic's usual message, and says
+/// how to play the run again with `reproduce`, e.g. the command line with the run's seed. Panics
+/// outside a game are left to the hook already installed
+pub fn install(path: PathBuf, reproduce: String) {
+    TRACKING.store(true, Ordering::Relaxed);
+
+    let previous = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+
+        let Some(report) = CrashReport::current() else {
+            return;
+        };
+
+        let location = info.location().map(|location| location.to_string()).unwrap_or_default();
+        let message = info.payload_as_str().unwrap_or("(no message)");
+        let contents = format!("Panicked at {}: {}\n\nReproduce with: {}\n\n{}", location, message, reproduce, report);
+
+        match fs::write(&path, contents) {
+            Ok(()) => eprintln!("The game's seed and moves are written to {}. Reproduce with: {}", path.display(), reproduce),
+            Err(error) => eprintln!("Could not write the crash report to {}: {}\n\n{}", path.display(), error, report),
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arena::{Arena, ArenaTurn, BatchPolicy};
+    use crate::bot_strategy::{BotStrategy, LazyBot, MyopicBot};
+    use crate::controller::{GameView, PlayerAction, PlayerController};
+    use crate::game_logic::Game;
+    use crate::observer::{GameEvent, GameObserver};
+    use crate::move_hints::MoveHint;
+    use crate::player::Player;
+    use crate::tile::TileDefinition;
+    use std::rc::Rc;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Takes the report part way through the game, as a panic in a turn would
+    #[derive(Default)]
+    struct Snapshot {
+        report: Option<CrashReport>,
+    }
+
+    impl GameObserver for Snapshot {
+        fn notify(&mut self, event: &GameEvent) {
+            if let GameEvent::TurnCompleted { .. } = event {
+                self.report = self.report.take().filter(|report| report.turns.len() >= 5).or_else(CrashReport::current);
+            }
+        }
+    }
+
+    /// Has games keep track of their moves as if the hook were installed, until dropped. One test
+    /// at a time, so none turns it off under another
+    struct Tracking(#[allow(dead_code)] MutexGuard<'static, ()>);
+
+    impl Tracking {
+        fn on() -> Self {
+            static ONE_AT_A_TIME: Mutex<()> = Mutex::new(());
+
+            let guard = ONE_AT_A_TIME.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            TRACKING.store(true, Ordering::Relaxed);
+
+            Tracking(guard)
+        }
+    }
+
+    impl Drop for Tracking {
+        fn drop(&mut self) {
+            TRACKING.store(false, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn should_report_the_moves_and_board_of_the_game_being_played() {
+        let _tracking = Tracking::on();
+
+        let snapshot = Rc::new(RefCell::new(Snapshot::default()));
+
+        Game::builder()
+            .with_seed([7; 32])
+            .with_player(Player::red(), BotStrategy::Myopic(MyopicBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .build()
+            .unwrap()
+            .play_observed(2, &mut [Box::new(Rc::clone(&snapshot))]);
+
+        let report = snapshot.borrow_mut().report.take().unwrap();
+
+        assert_eq!((report.game_index, report.seed, report.turns.len()), (2, [7; 32], 5));
+        assert_eq!(report.turns[0].player, Player::red().meeple_color);
+
+        // the report is taken as the fifth turn is played, so has the board as it is after it
+        let board = Board::from_share_code(report.share_code.as_deref().unwrap()).unwrap();
+        assert_eq!(board.placed_tile_count(), 5);
+
+        let rendered = report.to_string();
+        assert!(rendered.contains("Game: 2\n"));
+        assert!(rendered.contains("   1. Red drew "), "{}", rendered);
+
+        assert!(CrashReport::current().is_none());
+    }
+
+    /// Notes the report of the game it is asked to move in
+    struct Reporter(Rc<RefCell<Vec<CrashReport>>>);
+
+    impl PlayerController for Reporter {
+        fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+            self.0.borrow_mut().extend(CrashReport::current());
+            BotStrategy::Lazy(LazyBot).choose_move(view, hints)
+        }
+    }
+
+    /// Hands every seat over to a [Reporter]
+    struct HandsOverToReporters(Rc<RefCell<Vec<CrashReport>>>);
+
+    impl BatchPolicy for HandsOverToReporters {
+        fn choose_moves(&mut self, _tile: &'static TileDefinition, turns: &[ArenaTurn]) -> Vec<PlayerAction> {
+            turns.iter().map(|_| PlayerAction::HandOver(Box::new(Reporter(Rc::clone(&self.0))))).collect()
+        }
+    }
+
+    #[test]
+    fn should_report_whichever_of_an_arenas_games_is_being_played() {
+        let _tracking = Tracking::on();
+
+        let reports = Rc::new(RefCell::new(vec![]));
+        let players = [Player::red(), Player::green()].into_iter().map(|player| (player.meeple_color, player)).collect();
+
+        Arena::new(players, [[7; 32], [8; 32]], vec![]).play(&mut HandsOverToReporters(Rc::clone(&reports)));
+
+        let reports = reports.borrow();
+
+        for game_index in 0..2 {
+            let turns: Vec<_> = reports.iter().filter(|report| report.game_index == game_index).map(|report| report.turns.len()).collect();
+
+            assert!(reports.iter().filter(|report| report.game_index == game_index).all(|report| report.seed == [7 + game_index as u8; 32]));
+            // each game's moves carry on from its own last turn, not the other game's
+            assert!(turns.len() > 10 && turns.windows(2).all(|pair| pair[1] > pair[0]), "{:?}", turns);
+        }
+
+        assert!(CrashReport::current().is_none());
+    }
+}
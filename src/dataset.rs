@@ -0,0 +1,634 @@
+//! Training data for learned bots, taken straight from simulated games: for every turn, the board
+//! the player faced as a tensor of features, the move they chose and how the game turned out for
+//! them, written as a NumPy `.npz` archive
+
+use crate::board::Board;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::{PlayerIdentifier, RegionIndex};
+use crate::replay::ReplayState;
+use crate::score::Score;
+use crate::stats::{follow, replay_turn};
+use crate::tile::{BoardCoordinate, Region, RegionType, TileId, TilePlacement};
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Squares along each side of the featurized board, centred on the first tile placed. Tiles
+/// further out than this are left off, which no game of the base tile set reaches in practice
+pub const WINDOW: usize = 33;
+
+/// Features of each square of the board, in the order the channels are laid out:
+/// * 0: 1 where there is a tile
+/// * 1 to 12: the region type at each position around the tile's edge, clockwise from the west
+///   end of its north edge: 0 for no tile, then field, city, road and water from 1
+/// * 13: 1 where the tile has a cloister
+/// * 14 to 25: who owns the region at each position around the edge, see [CONTESTED]
+/// * 26: who owns the tile's cloister
+/// * 27: whose meeple is on the tile
+///
+/// Players are numbered round the table from the one to move, who is 1, with 0 for nobody
+pub const CHANNELS: usize = 28;
+
+/// The owner of a region two or more players share the most meeple in
+pub const CONTESTED: u8 = u8::MAX;
+
+const OCCUPIED_CHANNEL: usize = 0;
+const REGION_TYPE_CHANNEL: usize = 1;
+const CLOISTER_CHANNEL: usize = 13;
+const REGION_OWNER_CHANNEL: usize = 14;
+const CLOISTER_OWNER_CHANNEL: usize = 26;
+const MEEPLE_CHANNEL: usize = 27;
+
+/// One turn of a game, as a training example
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub game: u32,
+    /// The board before the move, [CHANNELS] by [WINDOW] by [WINDOW], see [featurize]
+    pub board: Vec<u8>,
+    pub tile: TileId,
+    /// The board column and row the tile went in, its clockwise quarter turns, and the region it
+    /// took a meeple on or -1 when it took none
+    pub play: [i16; 4],
+    /// 1 for a game the player to move won, 0.5 for one they shared the top score in, 0 for a loss
+    pub outcome: f32,
+    /// The player's final score less the best of the others'
+    pub margin: i16,
+}
+
+/// Collects a [Sample] for every turn of every game it observes, keeping each game's back until it
+/// ends and the outcomes are known. Turns where the tile couldn't be placed are skipped, as no
+/// move was chosen. Finished games are deflated into temporary files, an array to a file, so a
+/// long run's samples don't have to fit in memory
+#[derive(Default)]
+pub struct DatasetRecorder {
+    state: Option<ReplayState>,
+    /// Players in turn order
+    seats: Vec<PlayerIdentifier>,
+    /// The samples of the game being played, with who was to move in each
+    game: Vec<(PlayerIdentifier, Sample)>,
+    /// The samples of finished games, created with the first of them
+    spill: Option<Spill>,
+    /// The first failure to write out a finished game, reported when the archive is written
+    error: Option<io::Error>,
+}
+
+/// The board as the player to move sees it, laid out channel by channel as described by
+/// [CHANNELS], each channel a row at a time from the north west corner of the window
+pub fn featurize(board: &Board, to_move: PlayerIdentifier, seats: &[PlayerIdentifier]) -> Vec<u8> {
+    let mut features = vec![0; CHANNELS * WINDOW * WINDOW];
+    let owners = board.region_owners();
+
+    let seat = |player: PlayerIdentifier| {
+        let offset = seats.iter().position(|seat| *seat == to_move).unwrap_or(0);
+        seats.iter().position(|seat| *seat == player).map_or(0, |index| ((index + seats.len() - offset) % seats.len() + 1) as u8)
+    };
+
+    let owner = |coordinate: BoardCoordinate, region_index: RegionIndex| match owners.get(&(coordinate, region_index)).map(Vec::as_slice) {
+        None | Some([]) => 0,
+        Some([player]) => seat(*player),
+        Some(_) => CONTESTED,
+    };
+
+    for (coordinate, tile) in board.placed_tiles() {
+        let Some(square) = window_square(coordinate) else {
+            continue;
+        };
+
+        let mut set = |channel: usize, value: u8| features[channel * WINDOW * WINDOW + square] = value;
+
+        set(OCCUPIED_CHANNEL, 1);
+
+        let region_types = tile.tile.list_oriented_region_types(tile.placement.rotation);
+        let region_indices = tile.tile.list_oriented_region_indices(tile.placement.rotation);
+
+        for position in 0..12 {
+            set(REGION_TYPE_CHANNEL + position, region_code(&region_types[position]));
+            set(REGION_OWNER_CHANNEL + position, owner(*coordinate, region_indices[position]));
+        }
+
+        if let Some(cloister) = tile.tile.regions.iter().position(|region| matches!(region, Region::Cloister { .. })) {
+            set(CLOISTER_CHANNEL, 1);
+            set(CLOISTER_OWNER_CHANNEL, owner(*coordinate, RegionIndex::new(cloister)));
+        }
+
+        if let Some((_, meeple)) = &tile.meeple {
+            set(MEEPLE_CHANNEL, seat(meeple.color));
+        }
+    }
+
+    features
+}
+
+/// Where the coordinate falls in a channel of the window, if it does
+fn window_square(coordinate: &BoardCoordinate) -> Option<usize> {
+    let half = (WINDOW / 2) as i16;
+    let (column, row) = (coordinate.x as i16 + half, coordinate.y as i16 + half);
+    let within = 0..WINDOW as i16;
+
+    (within.contains(&column) && within.contains(&row)).then(|| row as usize * WINDOW + column as usize)
+}
+
+fn region_code(region_type: &RegionType) -> u8 {
+    match region_type {
+        RegionType::Field => 1,
+        RegionType::City => 2,
+        RegionType::Road => 3,
+        RegionType::Water => 4,
+        RegionType::Cloister => 5,
+    }
+}
+
+/// How the game went for the player: 1, 0.5 or 0 as in [Sample::outcome], and their margin over
+/// the best of the others. With nobody else scored it counts as a win by their whole score
+fn outcome(score: &Score, player: PlayerIdentifier) -> (f32, i16) {
+    let own = score.iter().find(|(id, _)| **id == player).map_or(0, |(_, points)| *points);
+    let best_other = score.iter().filter(|(id, _)| **id != player).map(|(_, points)| *points).max();
+    let margin = own - best_other.unwrap_or(0);
+
+    let outcome = match best_other {
+        Some(best) if best > own => 0.0,
+        Some(best) if best == own => 0.5,
+        _ => 1.0,
+    };
+
+    (outcome, margin.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+}
+
+impl DatasetRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Samples of the finished games so far
+    pub fn sample_count(&self) -> usize {
+        self.spill.as_ref().map_or(0, |spill| spill.rows)
+    }
+
+    fn record(&mut self, sample: &Sample) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let spill = match &mut self.spill {
+            Some(spill) => Ok(spill),
+            None => Spill::create().map(|spill| self.spill.insert(spill)),
+        };
+
+        if let Err(error) = spill.and_then(|spill| spill.push(sample)) {
+            self.error = Some(error);
+        }
+    }
+
+    /// Writes the samples as an `.npz` archive of one array per field of [Sample], each with a
+    /// sample per row: `game` (u32), `board` (u8, samples by [CHANNELS] by [WINDOW] by [WINDOW]),
+    /// `tile` (u8), `play` (i16, samples by 4), `outcome` (f32) and `margin` (i16). The recorder
+    /// starts afresh afterwards
+    pub fn write_npz<W: Write>(&mut self, output: W) -> io::Result<()> {
+        if let Some(error) = self.error.take() {
+            self.spill = None;
+            return Err(error);
+        }
+
+        let mut spill = match self.spill.take() {
+            Some(spill) => spill,
+            None => Spill::create()?,
+        };
+        let mut archive = NpzWriter::new(output);
+
+        for column in std::mem::take(&mut spill.columns) {
+            let shape: Vec<usize> = std::iter::once(spill.rows).chain(column.row_shape.iter().copied()).collect();
+            let (name, descr) = (column.name, column.descr);
+
+            archive.add(name, descr, &shape, column.finish()?)?;
+        }
+
+        archive.finish()
+    }
+
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        self.write_npz(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl GameObserver for DatasetRecorder {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { players, rules, .. } => {
+                self.seats = players.iter().map(|(color, _, _)| *color).collect();
+                self.state = Some(follow(self.seats.iter().copied(), rules));
+                self.game.clear();
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                let Some(state) = &mut self.state else {
+                    return;
+                };
+
+                if let Some((TilePlacement { coordinate, rotation }, meeple)) = placement {
+                    let sample = Sample {
+                        game: *game_index as u32,
+                        board: featurize(&state.board, *player, &self.seats),
+                        tile: tile.id(),
+                        play: [coordinate.x as i16, coordinate.y as i16, rotation.quarter_turns() as i16, meeple.map_or(-1, |index| *index as i16)],
+                        outcome: 0.0,
+                        margin: 0,
+                    };
+
+                    self.game.push((*player, sample));
+                }
+
+                if state.apply(&replay_turn(*player, tile, placement)).is_err() {
+                    self.state = None;
+                }
+            }
+            GameEvent::MeepleRecalled { player, coordinate, region_index, .. } => {
+                if let Some(state) = &mut self.state {
+                    state.recall_meeple(*player, coordinate, *region_index);
+                }
+            }
+            GameEvent::GameCompleted { score, .. } => {
+                self.state = None;
+
+                for (player, mut sample) in std::mem::take(&mut self.game) {
+                    (sample.outcome, sample.margin) = outcome(score, player);
+                    self.record(&sample);
+                }
+            }
+            GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+/// The arrays of finished games, each deflated into its own file as its rows come in
+struct Spill {
+    directory: PathBuf,
+    columns: Vec<Column>,
+    rows: usize,
+}
+
+impl Spill {
+    fn create() -> io::Result<Self> {
+        let directory = std::env::temp_dir().join(format!("carcassonne-dataset-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory)?;
+
+        // the directory goes when the spill does, even if the columns can't all be created
+        let mut spill = Self { directory, columns: vec![], rows: 0 };
+
+        for (name, descr, row_shape) in [
+            ("game", "<u4", &[][..]),
+            ("board", "|u1", &[CHANNELS, WINDOW, WINDOW][..]),
+            ("tile", "|u1", &[][..]),
+            ("play", "<i2", &[4][..]),
+            ("outcome", "<f4", &[][..]),
+            ("margin", "<i2", &[][..]),
+        ] {
+            let path = spill.directory.join(name);
+            let encoder = DeflateEncoder::new(BufWriter::new(File::create(&path)?), Compression::default());
+
+            spill.columns.push(Column { name, descr, row_shape, path, encoder, crc: Crc::new(), size: 0 });
+        }
+
+        Ok(spill)
+    }
+
+    fn push(&mut self, sample: &Sample) -> io::Result<()> {
+        let [game, board, tile, play, outcome, margin] = &mut self.columns[..] else {
+            unreachable!("a spill has a column for each field of a sample");
+        };
+
+        game.write(&sample.game.to_le_bytes())?;
+        board.write(&sample.board)?;
+        tile.write(&[sample.tile])?;
+        play.write(&sample.play.iter().flat_map(|value| value.to_le_bytes()).collect::<Vec<_>>())?;
+        outcome.write(&sample.outcome.to_le_bytes())?;
+        margin.write(&sample.margin.to_le_bytes())?;
+        self.rows += 1;
+
+        Ok(())
+    }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.directory);
+    }
+}
+
+/// One array of the archive, its rows so far
+struct Column {
+    name: &'static str,
+    descr: &'static str,
+    /// The shape of each row, after the axis of samples
+    row_shape: &'static [usize],
+    path: PathBuf,
+    encoder: DeflateEncoder<BufWriter<File>>,
+    /// Of the rows before they were deflated
+    crc: Crc,
+    size: u64,
+}
+
+impl Column {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.crc.update(bytes);
+        self.size += bytes.len() as u64;
+        self.encoder.write_all(bytes)
+    }
+
+    fn finish(self) -> io::Result<Deflated<File>> {
+        self.encoder.finish()?.flush()?;
+
+        let compressed = File::open(&self.path)?;
+        let compressed_size = compressed.metadata()?.len();
+
+        Ok(Deflated { crc: self.crc, size: self.size, compressed, compressed_size })
+    }
+}
+
+/// An array's data as a complete raw deflate stream, with the checksum and size of the bytes that
+/// went into it
+struct Deflated<R> {
+    crc: Crc,
+    size: u64,
+    compressed: R,
+    compressed_size: u64,
+}
+
+/// Writes arrays in the `.npy` format into a zip64 archive, deflated, as `numpy.savez_compressed`
+/// does. Each array's `.npy` header is stored ahead of its deflated data as a block of its own, so
+/// the data needn't be compressed again once the number of rows is known
+struct NpzWriter<W> {
+    output: W,
+    written: u64,
+    /// The central directory entry of each array, written after them
+    directory: Vec<u8>,
+    entries: u64,
+}
+
+impl<W: Write> NpzWriter<W> {
+    fn new(output: W) -> Self {
+        Self { output, written: 0, directory: vec![], entries: 0 }
+    }
+
+    fn add<R: Read>(&mut self, name: &str, descr: &str, shape: &[usize], mut data: Deflated<R>) -> io::Result<()> {
+        let shape = match shape {
+            [length] => format!("({},)", length),
+            _ => format!("({})", shape.iter().map(usize::to_string).collect::<Vec<_>>().join(", ")),
+        };
+
+        let mut header = format!("{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}", descr, shape);
+        // the magic, version and header length take 10 bytes, and the data starts 64 byte aligned
+        let padding = 63 - (10 + header.len()) % 64;
+        header.extend(std::iter::repeat_n(' ', padding));
+        header.push('\n');
+
+        let mut npy = b"\x93NUMPY\x01\x00".to_vec();
+        npy.extend((header.len() as u16).to_le_bytes());
+        npy.extend(header.as_bytes());
+
+        let mut crc = Crc::new();
+        crc.update(&npy);
+        crc.combine(&data.crc);
+
+        // a stored block that isn't the last, which the data's own blocks follow
+        let mut stored = vec![0];
+        stored.extend((npy.len() as u16).to_le_bytes());
+        stored.extend((!(npy.len() as u16)).to_le_bytes());
+        stored.extend(&npy);
+
+        let size = npy.len() as u64 + data.size;
+        let compressed_size = stored.len() as u64 + data.compressed_size;
+        let file_name = format!("{}.npy", name);
+
+        // version needed, flags, deflated, time and date (1980-01-01), crc, sizes (in the zip64
+        // extra field), name length
+        let mut common = vec![];
+        common.extend(45u16.to_le_bytes());
+        common.extend(0u16.to_le_bytes());
+        common.extend(8u16.to_le_bytes());
+        common.extend(0u16.to_le_bytes());
+        common.extend(33u16.to_le_bytes());
+        common.extend(crc.sum().to_le_bytes());
+        common.extend(u32::MAX.to_le_bytes());
+        common.extend(u32::MAX.to_le_bytes());
+        common.extend((file_name.len() as u16).to_le_bytes());
+
+        let mut sizes = vec![];
+        sizes.extend(size.to_le_bytes());
+        sizes.extend(compressed_size.to_le_bytes());
+
+        let mut local = 0x04034b50u32.to_le_bytes().to_vec();
+        local.extend(&common);
+        local.extend(20u16.to_le_bytes());
+        local.extend(file_name.as_bytes());
+        local.extend(1u16.to_le_bytes());
+        local.extend(16u16.to_le_bytes());
+        local.extend(&sizes);
+
+        self.directory.extend(0x02014b50u32.to_le_bytes());
+        self.directory.extend(45u16.to_le_bytes());
+        self.directory.extend(&common);
+        self.directory.extend(28u16.to_le_bytes());
+        // comment, disk and attributes, then where the entry starts (in the zip64 extra field)
+        self.directory.extend([0; 10]);
+        self.directory.extend(u32::MAX.to_le_bytes());
+        self.directory.extend(file_name.as_bytes());
+        self.directory.extend(1u16.to_le_bytes());
+        self.directory.extend(24u16.to_le_bytes());
+        self.directory.extend(&sizes);
+        self.directory.extend(self.written.to_le_bytes());
+
+        self.output.write_all(&local)?;
+        self.output.write_all(&stored)?;
+
+        if io::copy(&mut data.compressed, &mut self.output)? != data.compressed_size {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, format!("the {} array was cut short", name)));
+        }
+
+        self.written += local.len() as u64 + compressed_size;
+        self.entries += 1;
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        // the zip64 end of central directory record, then where to find it, then the classic
+        // record with its counts and offsets left to those
+        let mut end = 0x06064b50u32.to_le_bytes().to_vec();
+        end.extend(44u64.to_le_bytes());
+        end.extend(45u16.to_le_bytes());
+        end.extend(45u16.to_le_bytes());
+        end.extend([0; 8]);
+        end.extend(self.entries.to_le_bytes());
+        end.extend(self.entries.to_le_bytes());
+        end.extend((self.directory.len() as u64).to_le_bytes());
+        end.extend(self.written.to_le_bytes());
+
+        end.extend(0x07064b50u32.to_le_bytes());
+        end.extend(0u32.to_le_bytes());
+        end.extend((self.written + self.directory.len() as u64).to_le_bytes());
+        end.extend(1u32.to_le_bytes());
+
+        end.extend(0x06054b50u32.to_le_bytes());
+        end.extend([0; 4]);
+        end.extend(u16::MAX.to_le_bytes());
+        end.extend(u16::MAX.to_le_bytes());
+        end.extend(u32::MAX.to_le_bytes());
+        end.extend(u32::MAX.to_le_bytes());
+        end.extend(0u16.to_le_bytes());
+
+        self.output.write_all(&self.directory)?;
+        self.output.write_all(&end)?;
+        self.output.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, MyopicBot};
+    use crate::player::{Meeple, MeepleColor, Player};
+    use crate::simulate::{self, SimulationConfig};
+    use crate::tile::{PlacedTile, Rotation};
+    use crate::tile_definitions::{CLOISTER_IN_FIELD, STRAIGHT_ROAD};
+    use flate2::read::DeflateDecoder;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    fn channel(features: &[u8], channel: usize, x: i8, y: i8) -> u8 {
+        features[channel * WINDOW * WINDOW + window_square(&BoardCoordinate::new(x, y)).unwrap()]
+    }
+
+    /// Each array of the archive by name, as its `.npy` file and the checksum the archive gives it
+    fn read_npz(archive: &[u8]) -> HashMap<String, (Vec<u8>, u32)> {
+        let mut arrays = HashMap::new();
+        let mut position = 0;
+
+        while archive[position..].starts_with(b"PK\x03\x04") {
+            let field = |offset: usize, length: usize| archive[position + offset..position + offset + length].iter().rev().fold(0u64, |value, byte| value << 8 | *byte as u64) as usize;
+            let (name_length, extra_length) = (field(26, 2), field(28, 2));
+            let name = std::str::from_utf8(&archive[position + 30..position + 30 + name_length]).unwrap().to_string();
+            // the sizes are in the zip64 extra field, after its id and length
+            let compressed_size = field(30 + name_length + 12, 8);
+            let start = position + 30 + name_length + extra_length;
+
+            let mut npy = vec![];
+            DeflateDecoder::new(&archive[start..start + compressed_size]).read_to_end(&mut npy).unwrap();
+
+            arrays.insert(name, (npy, field(14, 4) as u32));
+            position = start + compressed_size;
+        }
+
+        arrays
+    }
+
+    fn read_samples(archive: &[u8]) -> Vec<Sample> {
+        let arrays = read_npz(archive);
+        let data = |name: &str| {
+            let (npy, _) = &arrays[&format!("{}.npy", name)];
+            npy[10 + u16::from_le_bytes([npy[8], npy[9]]) as usize..].to_vec()
+        };
+        let (games, boards, tiles, plays, outcomes, margins) = (data("game"), data("board"), data("tile"), data("play"), data("outcome"), data("margin"));
+
+        (0..tiles.len())
+            .map(|row| Sample {
+                game: u32::from_le_bytes(games[row * 4..row * 4 + 4].try_into().unwrap()),
+                board: boards.chunks(CHANNELS * WINDOW * WINDOW).nth(row).unwrap().to_vec(),
+                tile: tiles[row],
+                play: std::array::from_fn(|index| i16::from_le_bytes(plays[row * 8 + index * 2..row * 8 + index * 2 + 2].try_into().unwrap())),
+                outcome: f32::from_le_bytes(outcomes[row * 4..row * 4 + 4].try_into().unwrap()),
+                margin: i16::from_le_bytes(margins[row * 2..row * 2 + 2].try_into().unwrap()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_number_owners_round_the_table_from_the_player_to_move() {
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Green))),
+        ])
+        .unwrap();
+        let seats = [MeepleColor::Red, MeepleColor::Green, MeepleColor::Blue];
+
+        let red = featurize(&board, MeepleColor::Red, &seats);
+        let blue = featurize(&board, MeepleColor::Blue, &seats);
+
+        assert_eq!(channel(&red, OCCUPIED_CHANNEL, 1, 0), 1);
+        assert_eq!(channel(&red, OCCUPIED_CHANNEL, 0, 1), 0);
+        assert_eq!(channel(&red, CLOISTER_CHANNEL, 1, 0), 1);
+        assert_eq!(channel(&red, CLOISTER_CHANNEL, 0, 0), 0);
+        assert_eq!((channel(&red, MEEPLE_CHANNEL, 1, 0), channel(&red, CLOISTER_OWNER_CHANNEL, 1, 0)), (2, 2));
+        assert_eq!((channel(&blue, MEEPLE_CHANNEL, 1, 0), channel(&blue, CLOISTER_OWNER_CHANNEL, 1, 0)), (3, 3));
+        // the road runs north to south through the middle of its tile
+        assert_eq!(channel(&red, REGION_TYPE_CHANNEL + 1, 0, 0), region_code(&RegionType::Road));
+        assert_eq!(channel(&red, REGION_TYPE_CHANNEL, 0, 0), region_code(&RegionType::Field));
+        assert_eq!(channel(&red, REGION_OWNER_CHANNEL + 1, 0, 0), 0);
+    }
+
+    #[test]
+    fn should_record_every_move_with_how_the_game_went_for_its_player() {
+        let players = [Player::red(), Player::green()].map(|player| player.with_bot(BotStrategy::Myopic(MyopicBot)));
+        let recorder = Rc::new(RefCell::new(DatasetRecorder::new()));
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(Rc::clone(&recorder))];
+
+        let report = simulate::run_observed(SimulationConfig::new(players, 2, [3; 32]).unwrap(), &mut observers);
+        let mut archive = vec![];
+        recorder.borrow_mut().write_npz(&mut archive).unwrap();
+        let recorded = read_samples(&archive);
+
+        assert_eq!(report.games.len(), 2);
+
+        for (game, result) in report.games.iter().enumerate() {
+            let samples: Vec<_> = recorded.iter().filter(|sample| sample.game == game as u32).collect();
+
+            // the two players' margins are opposite, and one either won or they tied
+            assert!(samples.iter().all(|sample| sample.margin.abs() == samples[0].margin.abs()));
+            assert!(samples.iter().all(|sample| (sample.outcome - 0.5) * sample.margin as f32 >= 0.0));
+            assert_eq!(samples.iter().map(|sample| sample.board.len()).max(), Some(CHANNELS * WINDOW * WINDOW));
+            assert!(!samples.is_empty() && samples.len() <= result.turn_count);
+        }
+    }
+
+    #[test]
+    fn should_write_the_samples_as_an_npz_archive() {
+        let sample = Sample { game: 0, board: vec![1; CHANNELS * WINDOW * WINDOW], tile: 4, play: [0, -1, 2, -1], outcome: 1.0, margin: 12 };
+        let mut recorder = DatasetRecorder::new();
+        recorder.record(&sample);
+        recorder.record(&Sample { game: 1, margin: -3, ..sample.clone() });
+
+        assert_eq!(recorder.sample_count(), 2);
+
+        let mut archive = vec![];
+        recorder.write_npz(&mut archive).unwrap();
+
+        assert_eq!(&archive[..4], b"PK\x03\x04");
+        assert_eq!(read_samples(&archive), [sample.clone(), Sample { game: 1, margin: -3, ..sample }]);
+
+        let arrays = read_npz(&archive);
+        let (npy, crc) = &arrays["game.npy"];
+        let header_length = u16::from_le_bytes([npy[8], npy[9]]) as usize;
+        let header = std::str::from_utf8(&npy[10..10 + header_length]).unwrap();
+
+        assert_eq!(&npy[..6], b"\x93NUMPY");
+        assert_eq!((10 + header_length) % 64, 0);
+        assert!(header.starts_with("{'descr': '<u4', 'fortran_order': False, 'shape': (2,), }"), "{}", header);
+        assert_eq!(npy.len(), 10 + header_length + 8);
+        assert!(arrays.values().all(|(npy, crc)| {
+            let mut check = Crc::new();
+            check.update(npy);
+            check.sum() == *crc
+        }));
+        assert_ne!(*crc, 0);
+
+        // the archive ends with the zip64 records, then the classic one pointing on to them
+        let end = &archive[archive.len() - 22..];
+        assert_eq!(&end[..4], b"PK\x05\x06");
+        assert_eq!(&archive[archive.len() - 42..archive.len() - 38], b"PK\x06\x07");
+        assert_eq!(u64::from_le_bytes(archive[archive.len() - 98 + 32..archive.len() - 98 + 40].try_into().unwrap()), 6);
+
+        // and the recorder starts again
+        assert_eq!(recorder.sample_count(), 0);
+    }
+}
@@ -1,7 +1,8 @@
-use crate::tile::{Expansion, TileDefinition};
-use crate::tile_definitions::{ALL_TILE_DEFINITIONS, RIVER_TERMINATOR};
+use crate::rng::GameRng;
+use crate::rules::{DiscardPolicy, RiverOptions, StartingTile};
+use crate::tile::{Expansion, ExpansionError, TileDefinition, TileId};
+use crate::tile_definitions::{find_by_id, ALL_TILE_DEFINITIONS, LAKE, RIVER_TERMINATOR};
 use rand::prelude::SliceRandom;
-use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use std::cell::RefCell;
 use std::ops::DerefMut;
@@ -10,11 +11,12 @@ use std::rc::Rc;
 struct BaseTileSequence {
     tiles: Vec<&'static TileDefinition>,
     tile_can_be_placed: Box<dyn Fn(&'static TileDefinition) -> bool>,
-    rng: Rc<RefCell<StdRng>>,
+    rng: Rc<RefCell<GameRng>>,
+    discard_policy: DiscardPolicy,
 }
 
 impl BaseTileSequence {
-    fn new<F>(rng: Rc<RefCell<StdRng>>, tile_can_be_placed: F) -> Self
+    fn new<F>(rng: Rc<RefCell<GameRng>>, tile_can_be_placed: F) -> Self
     where
         F: Fn(&'static TileDefinition) -> bool + 'static,
     {
@@ -30,8 +32,14 @@ impl BaseTileSequence {
             tiles,
             tile_can_be_placed: Box::new(tile_can_be_placed),
             rng,
+            discard_policy: DiscardPolicy::default(),
         }
     }
+
+    /// The tiles in the order they would be drawn if every one could be placed
+    fn peek(&self) -> impl Iterator<Item = &'static TileDefinition> + '_ {
+        self.tiles.iter().rev().copied()
+    }
 }
 
 impl Iterator for BaseTileSequence {
@@ -53,7 +61,7 @@ impl Iterator for BaseTileSequence {
 
         // once we find a tile, if we had discarded any tiles before we shuffle them back
         // into the stack
-        if !discarded_tiles.is_empty() {
+        if !discarded_tiles.is_empty() && self.discard_policy == DiscardPolicy::Reshuffle {
             self.tiles.append(&mut discarded_tiles);
             self.tiles.shuffle(self.rng.borrow_mut().deref_mut());
         }
@@ -64,20 +72,24 @@ impl Iterator for BaseTileSequence {
 
 struct RiverTileSequence {
     tiles: Vec<&'static TileDefinition>,
+    /// Tiles left out of a shortened river, dealt only when its end can't yet be placed
+    left_out: Vec<&'static TileDefinition>,
+    /// The tile the river ends with
+    end: &'static TileDefinition,
     current_index: usize,
     river_exhausted: bool,
     tile_can_be_placed: Box<dyn Fn(&'static TileDefinition) -> bool>,
-    rng: Rc<RefCell<StdRng>>,
+    rng: Rc<RefCell<GameRng>>,
 }
 
 impl RiverTileSequence {
-    fn new<F>(rng: Rc<RefCell<StdRng>>, tile_can_be_placed: F) -> Self
+    fn new<F>(rng: Rc<RefCell<GameRng>>, tile_can_be_placed: F) -> Self
     where
         F: Fn(&'static TileDefinition) -> bool + 'static,
     {
         let mut tiles: Vec<_> = ALL_TILE_DEFINITIONS
             .iter()
-            .filter(|t| matches!(t.expansion, Some(Expansion::River)) && t != &&RIVER_TERMINATOR)
+            .filter(|t| matches!(t.expansion, Some(Expansion::River)) && !t.is_river_end())
             .flat_map(|t| vec![t; t.count as usize])
             .collect();
 
@@ -85,12 +97,38 @@ impl RiverTileSequence {
 
         Self {
             tiles,
+            left_out: vec![],
+            end: &RIVER_TERMINATOR,
             current_index: 0,
             river_exhausted: false,
             tile_can_be_placed: Box::new(tile_can_be_placed),
             rng,
         }
     }
+
+    fn with_options(mut self, options: RiverOptions) -> Self {
+        if options.lake {
+            self.end = &LAKE;
+        }
+
+        if let Some(min_length) = options.min_length {
+            let min_length = min_length.min(self.tiles.len());
+            let length = self.rng.borrow_mut().gen_range(min_length..=self.tiles.len());
+
+            // tiles are drawn from the end
+            self.left_out = self.tiles.drain(..self.tiles.len() - length).collect();
+        }
+
+        self
+    }
+
+    /// The tiles in the order they would be drawn if every one could be placed
+    fn peek(&self) -> impl Iterator<Item = &'static TileDefinition> + '_ {
+        let first: Option<&'static TileDefinition> = (self.current_index == 0).then_some(&RIVER_TERMINATOR);
+        let last: Option<&'static TileDefinition> = (!self.river_exhausted).then_some(self.end);
+
+        first.into_iter().chain(self.tiles.iter().rev().copied()).chain(last).take_while(|_| !self.river_exhausted)
+    }
 }
 
 impl Iterator for RiverTileSequence {
@@ -106,26 +144,46 @@ impl Iterator for RiverTileSequence {
             return None;
         }
 
-        let tile = self.tiles.pop();
+        let tile = if self.tiles.is_empty() {
+            let carry_on = match (self.tile_can_be_placed)(self.end) {
+                true => None,
+                false => take_placeable(&mut self.left_out, &self.tile_can_be_placed),
+            };
 
-        if tile.is_none() {
-            self.river_exhausted = true;
-            return Some(&RIVER_TERMINATOR);
-        }
+            if carry_on.is_none() {
+                self.river_exhausted = true;
+                return Some(self.end);
+            }
+
+            carry_on
+        } else {
+            // tiles that can't be placed yet are kept for later in the river, rather than ending it
+            take_placeable(&mut self.tiles, &self.tile_can_be_placed)
+        };
 
         // The official ruling from Hans im Glück is "Try to think while playing. Players may have
         // an unfinished River...but it‘s their own fault."
-        if !(self.tile_can_be_placed)(tile?) {
-            return None;
-        }
+        let tile = tile?;
 
         self.current_index += 1;
-        tile
+        Some(tile)
     }
 }
 
+/// The last of `tiles` that can be placed, taken out of them
+fn take_placeable(tiles: &mut Vec<&'static TileDefinition>, tile_can_be_placed: &dyn Fn(&'static TileDefinition) -> bool) -> Option<&'static TileDefinition> {
+    let index = tiles.iter().rposition(|tile| tile_can_be_placed(tile))?;
+
+    Some(tiles.remove(index))
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeckError {
+    UnknownTileId(TileId),
+}
+
 pub struct Deck {
-    river_tiles: Option<Box<dyn Iterator<Item = &'static TileDefinition>>>,
+    river_tiles: Option<RiverTileSequence>,
     base_tiles: BaseTileSequence,
     river_exhausted: bool,
 }
@@ -133,7 +191,7 @@ pub struct Deck {
 impl Deck {
     pub(crate) fn new<F>(
         include_river: bool,
-        rng: Rc<RefCell<StdRng>>,
+        rng: Rc<RefCell<GameRng>>,
         tile_can_be_placed: F,
     ) -> Self
     where
@@ -141,10 +199,10 @@ impl Deck {
     {
         Self {
             river_tiles: if include_river {
-                Some(Box::new(RiverTileSequence::new(
+                Some(RiverTileSequence::new(
                     rng.clone(),
                     tile_can_be_placed.clone(),
-                )))
+                ))
             } else {
                 None
             },
@@ -152,6 +210,97 @@ impl Deck {
             river_exhausted: !include_river,
         }
     }
+
+    pub(crate) fn with_river_options(mut self, options: RiverOptions) -> Self {
+        self.river_tiles = self.river_tiles.map(|river| river.with_options(options));
+        self
+    }
+
+    pub(crate) fn with_discard_policy(mut self, discard_policy: DiscardPolicy) -> Self {
+        self.base_tiles.discard_policy = discard_policy;
+        self
+    }
+
+    /// Moves a copy of a fixed starting tile to the top of the deck. Games with the river start
+    /// from its spring instead, so are left as they are
+    pub(crate) fn with_starting_tile(mut self, starting_tile: StartingTile) -> Self {
+        let StartingTile::Fixed(starting_tile) = starting_tile else {
+            return self;
+        };
+
+        if self.river_tiles.is_some() {
+            return self;
+        }
+
+        let tiles = &mut self.base_tiles.tiles;
+
+        if let Some(index) = tiles.iter().rposition(|tile| *tile == starting_tile) {
+            // tiles are drawn from the end
+            let tile = tiles.remove(index);
+            tiles.push(tile);
+        }
+
+        self
+    }
+}
+
+impl Deck {
+    /// A deck that deals exactly these tiles in this order, whether or not they can be placed, for
+    /// scenarios, puzzles and tests that need to know what is coming
+    pub fn from_fixed_order(tile_ids: Vec<TileId>) -> Result<Self, DeckError> {
+        let mut tiles = tile_ids
+            .into_iter()
+            .map(|id| find_by_id(id).ok_or(DeckError::UnknownTileId(id)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // tiles are drawn from the end
+        tiles.reverse();
+
+        Ok(Self {
+            river_tiles: None,
+            base_tiles: BaseTileSequence {
+                tiles,
+                tile_can_be_placed: Box::new(|_| true),
+                // never used, as every tile can be placed so none are shuffled back in
+                rng: Rc::new(RefCell::new(GameRng::seed_from_u64(0))),
+                discard_policy: DiscardPolicy::default(),
+            },
+            river_exhausted: true,
+        })
+    }
+
+    /// The next `n` tiles, without drawing them. Shuffled decks set aside tiles that can't be
+    /// placed when they are drawn, so what is actually drawn may differ from this
+    pub fn peek_n(&self, n: usize) -> Vec<&'static TileDefinition> {
+        let river = self.river_tiles.iter().filter(|_| !self.river_exhausted).flat_map(RiverTileSequence::peek);
+
+        river.chain(self.base_tiles.peek()).take(n).collect()
+    }
+
+    /// Each tile still to be dealt after the river, with how many copies of it are left, in the
+    /// order they would first be drawn. Empty while the river is being dealt, as the tiles after it
+    /// can't be placed until it is laid
+    pub(crate) fn undealt(&self) -> Vec<(&'static TileDefinition, usize)> {
+        if self.river_tiles.as_ref().is_some_and(|river| !self.river_exhausted && !river.river_exhausted) {
+            return vec![];
+        }
+
+        let mut undealt: Vec<(&'static TileDefinition, usize)> = vec![];
+
+        for tile in self.base_tiles.peek() {
+            match undealt.iter_mut().find(|(undealt, _)| undealt.name == tile.name) {
+                Some((_, copies)) => *copies += 1,
+                None => undealt.push((tile, 1)),
+            }
+        }
+
+        undealt
+    }
+
+    /// Takes every copy of the tile still to be dealt after the river out of the deck
+    pub(crate) fn remove_all(&mut self, tile: &TileDefinition) {
+        self.base_tiles.tiles.retain(|undealt| undealt.name != tile.name);
+    }
 }
 
 impl Iterator for Deck {
@@ -172,14 +321,78 @@ impl Iterator for Deck {
     }
 }
 
+/// Every tile definition in a shuffled deck with these expansions, with how many of each, base
+/// game tiles first
+pub fn composition(expansions: &[Expansion]) -> Vec<(&'static TileDefinition, u8)> {
+    let included = |tile: &TileDefinition| tile.count > 0 && tile.expansion.is_none_or(|expansion| expansions.contains(&expansion));
+
+    let (base, expansion_tiles): (Vec<_>, Vec<_>) = ALL_TILE_DEFINITIONS.iter().filter(|tile| included(tile)).partition(|tile| tile.expansion.is_none());
+
+    base.into_iter().chain(expansion_tiles).map(|tile| (tile, tile.count)).collect()
+}
+
+/// Every tile definition in the tile set called `name`, with how many of each: `base` for the base
+/// game's, an expansion's name for its own, or several joined with `+`, e.g. `base+river`
+pub fn tile_set(name: &str) -> Result<Vec<(&'static TileDefinition, u8)>, ExpansionError> {
+    let parts = name
+        .split('+')
+        .map(str::trim)
+        .map(|part| match part {
+            "base" => Ok(None),
+            _ => Expansion::from_name(part).map(Some).ok_or_else(|| ExpansionError::Unknown(part.to_string())),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(parts
+        .into_iter()
+        .flat_map(|part| ALL_TILE_DEFINITIONS.iter().filter(move |tile| tile.count > 0 && tile.expansion == part))
+        .map(|tile| (tile, tile.count))
+        .collect())
+}
+
+/// How one tile set differs from another, e.g. to check the tiles of an expansion being written
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileSetDiff {
+    /// Definitions only in the second set, with how many of each
+    pub added: Vec<(&'static TileDefinition, u8)>,
+    /// Definitions only in the first set, with how many of each
+    pub removed: Vec<(&'static TileDefinition, u8)>,
+    /// Definitions in both sets a different number of times, with the first set's count then the
+    /// second's
+    pub recounted: Vec<(&'static TileDefinition, u8, u8)>,
+    /// Tiles in each set, counting every copy
+    pub totals: (usize, usize),
+}
+
+impl TileSetDiff {
+    pub fn between(first: &[(&'static TileDefinition, u8)], second: &[(&'static TileDefinition, u8)]) -> Self {
+        let count_in = |set: &[(&'static TileDefinition, u8)], tile: &TileDefinition| set.iter().find(|(other, _)| other.name == tile.name).map(|(_, count)| *count);
+        let total = |set: &[(&'static TileDefinition, u8)]| set.iter().map(|(_, count)| *count as usize).sum();
+
+        Self {
+            added: second.iter().filter(|(tile, _)| count_in(first, tile).is_none()).copied().collect(),
+            removed: first.iter().filter(|(tile, _)| count_in(second, tile).is_none()).copied().collect(),
+            recounted: first
+                .iter()
+                .filter_map(|(tile, count)| count_in(second, tile).filter(|other| other != count).map(|other| (*tile, *count, other)))
+                .collect(),
+            totals: (total(first), total(second)),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.recounted.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tile_definitions::{STRAIGHT_RIVER, THREE_SIDED_CITY_WITH_ROAD};
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_RIVER, STRAIGHT_ROAD, THREE_SIDED_CITY_WITH_ROAD};
 
     #[test]
     fn test_base_deck_yields_only_base_tiles() {
-        let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(0)));
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
 
         let base_deck = Deck::new(false, rng, |_| true);
 
@@ -190,9 +403,9 @@ mod tests {
 
     #[test]
     fn deck_yields_different_results_for_different_seeds() {
-        let rng_1 = Rc::new(RefCell::new(StdRng::seed_from_u64(1)));
-        let rng_1_copy = Rc::new(RefCell::new(StdRng::seed_from_u64(1)));
-        let rng_2 = Rc::new(RefCell::new(StdRng::seed_from_u64(2)));
+        let rng_1 = Rc::new(RefCell::new(GameRng::seed_from_u64(1)));
+        let rng_1_copy = Rc::new(RefCell::new(GameRng::seed_from_u64(1)));
+        let rng_2 = Rc::new(RefCell::new(GameRng::seed_from_u64(2)));
 
         let base_deck_1: Vec<&'static str> =
             Deck::new(false, rng_1, |_| true).map(|t| t.name).collect();
@@ -208,7 +421,7 @@ mod tests {
 
     #[test]
     fn river_starts_and_ends_with_terminator() {
-        let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(0)));
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
         let river_tile_names: Vec<&'static str> = RiverTileSequence::new(rng, |_| true)
             .map(|t| t.name)
             .collect();
@@ -218,9 +431,57 @@ mod tests {
         assert_eq!(river_tile_names.len(), 12)
     }
 
+    #[test]
+    fn river_is_shortened_to_at_least_its_minimum_length() {
+        for seed in 0..10 {
+            let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(seed)));
+            let options = RiverOptions { min_length: Some(4), lake: true, ..RiverOptions::default() };
+            let river: Vec<_> = RiverTileSequence::new(rng, |_| true).with_options(options).collect();
+
+            assert!(river.len() >= 6 && river.len() <= 12);
+            assert_eq!(river.first(), Some(&&RIVER_TERMINATOR));
+            assert_eq!(river.last(), Some(&&LAKE));
+        }
+    }
+
+    #[test]
+    fn river_carries_on_with_left_out_tiles_until_its_end_can_be_placed() {
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+        let dealt = Rc::new(std::cell::Cell::new(0));
+        let counter = Rc::clone(&dealt);
+
+        // the end can't be placed until five tiles have followed the spring
+        let options = RiverOptions { min_length: Some(0), ..RiverOptions::default() };
+        let mut river = RiverTileSequence::new(rng, move |tile| !tile.is_river_end() || counter.get() >= 5).with_options(options);
+
+        river.next();
+
+        for tile in river.by_ref() {
+            if tile.is_river_end() {
+                break;
+            }
+
+            dealt.set(dealt.get() + 1);
+        }
+
+        assert!(dealt.get() >= 5);
+        assert_eq!(river.next(), None);
+    }
+
+    #[test]
+    fn river_keeps_tiles_that_cannot_be_placed_yet_for_later() {
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+        let first_attempt = Rc::new(std::cell::Cell::new(true));
+
+        // the first river tile offered can't be placed, which used to end the river there
+        let river: Vec<_> = RiverTileSequence::new(rng, move |_| !first_attempt.replace(false)).collect();
+
+        assert_eq!(river.len(), 12);
+    }
+
     #[test]
     fn deck_with_river_starts_with_all_river_tiles() {
-        let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(0)));
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
 
         let river_deck = Deck::new(true, rng, |_| true);
 
@@ -237,7 +498,7 @@ mod tests {
 
     #[test]
     fn river_skips_tiles_that_cannot_be_placed() {
-        let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(0)));
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
         let river_tile_names: Vec<&'static str> =
             RiverTileSequence::new(rng, |tile| tile.name != STRAIGHT_RIVER.name)
                 .map(|t| t.name)
@@ -248,7 +509,7 @@ mod tests {
 
     #[test]
     fn deck_completes_with_missing_tiles_if_one_cannot_be_placed() {
-        let rng = Rc::new(RefCell::new(StdRng::seed_from_u64(0)));
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
 
         let test_tile = THREE_SIDED_CITY_WITH_ROAD;
 
@@ -263,4 +524,121 @@ mod tests {
         assert_eq!(board_tiles.len(), 83);
         assert!(!board_tiles.contains(&&test_tile))
     }
+
+    #[test]
+    fn fixed_order_deck_deals_exactly_the_tiles_given() {
+        let mut deck = Deck::from_fixed_order(vec![SIDE_CITY.id(), STRAIGHT_ROAD.id(), SIDE_CITY.id()]).unwrap();
+
+        assert_eq!(deck.peek_n(2), vec![&SIDE_CITY, &STRAIGHT_ROAD]);
+        assert_eq!(deck.next(), Some(&SIDE_CITY));
+        assert_eq!(deck.peek_n(5), vec![&STRAIGHT_ROAD, &SIDE_CITY]);
+        assert_eq!(deck.by_ref().count(), 2);
+
+        assert!(matches!(Deck::from_fixed_order(vec![u8::MAX]), Err(DeckError::UnknownTileId(u8::MAX))));
+    }
+
+    #[test]
+    fn peeking_shows_the_tiles_that_will_be_drawn() {
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+
+        let mut deck = Deck::new(true, rng, |_| true);
+        let peeked = deck.peek_n(20);
+
+        assert_eq!(peeked.len(), 20);
+        assert_eq!(deck.by_ref().take(20).collect::<Vec<_>>(), peeked);
+    }
+
+    #[test]
+    fn tile_sets_differ_by_the_tiles_only_one_has() {
+        let base = tile_set("base").unwrap();
+        let both = tile_set("base + river").unwrap();
+
+        assert_eq!(base, composition(&[]));
+        assert_eq!(both, composition(&[Expansion::River]));
+        assert_eq!(tile_set("river2"), Err(ExpansionError::Unknown("river2".to_string())));
+
+        let diff = TileSetDiff::between(&base, &both);
+
+        assert_eq!(diff.added, tile_set("river").unwrap());
+        assert!(diff.removed.is_empty() && diff.recounted.is_empty());
+        assert_eq!(diff.totals.1 - diff.totals.0, diff.added.iter().map(|(_, count)| *count as usize).sum::<usize>());
+
+        let fewer_roads: Vec<_> = base.iter().map(|(tile, count)| (*tile, if tile.name == "Straight road" { count - 1 } else { *count })).collect();
+        let diff = TileSetDiff::between(&base, &fewer_roads);
+
+        assert_eq!(diff.recounted.len(), 1);
+        assert_eq!((diff.recounted[0].1, diff.recounted[0].2), (8, 7));
+        assert!(TileSetDiff::between(&base, &base).is_empty());
+    }
+
+    #[test]
+    fn composition_counts_every_tile_a_deck_would_deal() {
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+
+        for (include_river, expansions) in [(false, vec![]), (true, vec![Expansion::River])] {
+            let composition = composition(&expansions);
+            let dealt = Deck::new(include_river, rng.clone(), |_| true).count();
+
+            assert_eq!(composition.iter().map(|(_, count)| *count as usize).sum::<usize>(), dealt);
+            assert_eq!(composition.iter().any(|(tile, _)| tile.expansion == Some(Expansion::River)), include_river);
+        }
+    }
+
+    #[test]
+    fn deck_starts_with_a_fixed_starting_tile() {
+        use crate::tile_definitions::STRAIGHT_ROAD_WITH_SIDE_CITY;
+
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+        let tiles: Vec<_> = Deck::new(false, rng, |_| true).with_starting_tile(StartingTile::Fixed(&STRAIGHT_ROAD_WITH_SIDE_CITY)).collect();
+
+        assert_eq!(tiles[0], &STRAIGHT_ROAD_WITH_SIDE_CITY);
+        assert_eq!(tiles.iter().filter(|tile| **tile == &STRAIGHT_ROAD_WITH_SIDE_CITY).count(), STRAIGHT_ROAD_WITH_SIDE_CITY.count as usize);
+
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+        let river: Vec<_> = Deck::new(true, rng, |_| true).with_starting_tile(StartingTile::Fixed(&STRAIGHT_ROAD_WITH_SIDE_CITY)).collect();
+
+        assert_eq!(river[0], &RIVER_TERMINATOR);
+    }
+
+    #[test]
+    fn deck_removes_unplaceable_tiles_only_when_told_to() {
+        let deal = |discard_policy: DiscardPolicy| {
+            let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+            let rejected = Rc::new(std::cell::Cell::new(0));
+
+            // the first few tiles drawn can't be placed, the rest can
+            Deck::new(false, rng, move |_| {
+                rejected.set(rejected.get() + 1);
+                rejected.get() > 3
+            })
+            .with_discard_policy(discard_policy)
+            .count()
+        };
+
+        let full_deck = composition(&[]).iter().map(|(_, count)| *count as usize).sum::<usize>();
+
+        assert_eq!(deal(DiscardPolicy::Reshuffle), full_deck);
+        assert_eq!(deal(DiscardPolicy::Remove), full_deck - 3);
+    }
+
+    #[test]
+    fn deck_counts_and_removes_the_tiles_left_after_the_river() {
+        let rng = Rc::new(RefCell::new(GameRng::seed_from_u64(0)));
+        let mut deck = Deck::new(true, rng, |_| true);
+
+        assert!(deck.undealt().is_empty());
+
+        // deal the river
+        deck.by_ref().take_while(|tile| tile.expansion.is_some()).for_each(drop);
+
+        let undealt = deck.undealt();
+        let copies = |undealt: &[(&TileDefinition, usize)]| undealt.iter().map(|(_, copies)| copies).sum::<usize>();
+        assert_eq!(copies(&undealt), deck.peek_n(usize::MAX).len());
+
+        let (tile, removed) = undealt[0];
+        deck.remove_all(tile);
+
+        assert_eq!(copies(&deck.undealt()), copies(&undealt) - removed);
+        assert!(deck.all(|dealt| dealt != tile));
+    }
 }
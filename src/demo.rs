@@ -0,0 +1,237 @@
+use crate::board::{Board, RenderAnnotations};
+use crate::commentary::describe_turn;
+use crate::highlight::RegionFilter;
+use crate::observer::{GameEvent, GameObserver};
+use crate::replay::{ReplayPlacement, ReplayState, ReplayTurn};
+use crate::score::Score;
+use crate::tile::{BoardCoordinate, RegionType, RenderStyle};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+/// Moves the cursor home and clears the screen, so each turn is drawn over the last
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// A feature a turn completed, sent before the turn itself
+struct Completion {
+    region_type: RegionType,
+    tiles: Vec<BoardCoordinate>,
+    points: u32,
+}
+
+/// Shows games as they are played, a turn at a time and slowly enough to follow: the board with a
+/// legend of whose meeple are whose, commentary on the move, and the score so far. A turn that
+/// completes a feature picks it out on the board, dimming the rest, and stays up for twice as long
+pub struct DemoPresenter<W> {
+    output: W,
+    style: RenderStyle,
+    /// How long each turn stays on screen
+    turn_delay: Duration,
+    annotations: RenderAnnotations,
+    state: Option<ReplayState>,
+    /// Features completed by the turn being played
+    completed: Vec<Completion>,
+}
+
+impl DemoPresenter<io::Stdout> {
+    pub fn stdout(style: RenderStyle, turn_delay: Duration) -> Self {
+        Self::new(io::stdout(), style, turn_delay)
+    }
+}
+
+impl<W: Write> DemoPresenter<W> {
+    pub fn new(output: W, style: RenderStyle, turn_delay: Duration) -> Self {
+        Self { output, style, turn_delay, annotations: RenderAnnotations::default().with_legend(), state: None, completed: vec![] }
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        // a plain ascii terminal may not understand escape codes, so turns follow on from each other
+        match self.style {
+            RenderStyle::Ascii => writeln!(self.output),
+            _ => write!(self.output, "{}", CLEAR_SCREEN),
+        }
+    }
+
+    fn introduce(&mut self, players: &[(String, Option<&'static str>)]) -> io::Result<()> {
+        let players: Vec<_> = players
+            .iter()
+            .map(|(name, bot)| match bot {
+                Some(bot) if bot != name => format!("{} ({})", name, bot),
+                _ => name.clone(),
+            })
+            .collect();
+
+        self.clear()?;
+        writeln!(self.output, "Carcassonne: {}", players.join(" v "))?;
+        self.output.flush()?;
+
+        thread::sleep(self.turn_delay);
+
+        Ok(())
+    }
+
+    fn show_turn(&mut self, turn: &ReplayTurn) -> io::Result<()> {
+        let Some(state) = &mut self.state else {
+            return Ok(());
+        };
+
+        let number = state.turn + 1;
+        let commentary = describe_turn(state, turn).unwrap_or_else(|error| format!("(no commentary: {:?})", error));
+
+        // the feature worth the most is picked out, should the turn complete more than one
+        let highlight = self.completed.iter().max_by_key(|completion| completion.points).and_then(|completion| {
+            let covering = anchor(&state.board, completion)?;
+
+            Some(RegionFilter { region_type: Some(completion.region_type.clone()), covering: Some(covering), ..Default::default() })
+        });
+
+        let board = match &highlight {
+            Some(filter) => state.board.render_highlighted(filter, &self.style),
+            None => state.board.render(&self.style),
+        };
+
+        let board = self.annotations.annotate(board, &state.players, &self.style);
+        let score = state.score.render(&state.players, &self.style);
+
+        self.clear()?;
+        writeln!(self.output, "{}\n\nTurn {}: {}\n{}", board, number, commentary, score)?;
+        self.output.flush()?;
+
+        self.completed.clear();
+
+        thread::sleep(match highlight {
+            Some(_) => self.turn_delay * 2,
+            None => self.turn_delay,
+        });
+
+        Ok(())
+    }
+
+    fn show_result(&mut self, score: &Score) -> io::Result<()> {
+        let Some(state) = self.state.take() else {
+            return Ok(());
+        };
+
+        let board = self.annotations.annotate(state.board.render(&self.style), &state.players, &self.style);
+
+        self.clear()?;
+        writeln!(self.output, "{}\n\nFinal score is\n{}", board, score.render(&state.players, &self.style))?;
+
+        let top = score.iter().map(|(_, points)| *points).max();
+        let winners: Vec<_> = score
+            .iter()
+            .filter(|(_, points)| Some(**points) == top)
+            .map(|(id, _)| state.players.get(id).and_then(|player| player.name.clone()).unwrap_or_else(|| id.to_string()))
+            .collect();
+
+        match winners.as_slice() {
+            [winner] => writeln!(self.output, "{} wins!", winner)?,
+            [] => {}
+            _ => writeln!(self.output, "{} share the win!", winners.join(" and "))?,
+        }
+
+        self.output.flush()
+    }
+}
+
+/// A tile the completed feature covers, to pick it out on the board by. A cloister is only on its
+/// own tile, the one the others surround
+fn anchor(board: &Board, completion: &Completion) -> Option<BoardCoordinate> {
+    match completion.region_type {
+        RegionType::Cloister => completion
+            .tiles
+            .iter()
+            .copied()
+            .find(|coordinate| board.topology().surrounding_coordinates(coordinate).iter().all(|around| completion.tiles.contains(around))),
+        _ => completion.tiles.first().copied(),
+    }
+}
+
+impl<W: Write> GameObserver for DemoPresenter<W> {
+    fn notify(&mut self, event: &GameEvent) {
+        // the demo is only for watching, so a closed output doesn't stop the game
+        let _ = match event {
+            GameEvent::GameStarted { players, rules, .. } => {
+                let seats = players.iter().map(|(color, name, _)| (*color, name.clone()));
+
+                self.state = Some(ReplayState::new(seats, rules).expect("a game's rules are validated before it starts"));
+                self.completed.clear();
+
+                let names: Vec<_> = players.iter().map(|(color, name, bot)| (name.clone().unwrap_or_else(|| color.to_string()), *bot)).collect();
+                self.introduce(&names)
+            }
+            GameEvent::RegionCompleted { region_type, tiles, points, .. } => {
+                self.completed.push(Completion { region_type: region_type.clone(), tiles: tiles.clone(), points: *points });
+                Ok(())
+            }
+            GameEvent::TurnCompleted { player, tile, placement, .. } => {
+                let turn = ReplayTurn {
+                    player: *player,
+                    tile: tile.name().to_string(),
+                    placement: placement.as_ref().map(|(placement, meeple)| ReplayPlacement::new(placement, *meeple)),
+                };
+
+                self.show_turn(&turn)
+            }
+            GameEvent::MeepleRecalled { player, coordinate, region_index, .. } => {
+                if let Some(state) = &mut self.state {
+                    state.recall_meeple(*player, coordinate, *region_index);
+                }
+
+                Ok(())
+            }
+            GameEvent::GameCompleted { score, .. } => self.show_result(score),
+            GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => Ok(()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, MyopicBot};
+    use crate::game_logic::Game;
+    use crate::player::Player;
+    use crate::tile::{PlacedTile, Rotation};
+    use crate::tile_definitions::CLOISTER_IN_FIELD;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn should_show_every_turn_of_the_game_and_who_won() {
+        let demo = Rc::new(RefCell::new(DemoPresenter::new(vec![], RenderStyle::Ascii, Duration::ZERO)));
+
+        let game = Game::builder()
+            .with_seed([5; 32])
+            .with_player(Player::red().with_name("Alice"), BotStrategy::Myopic(MyopicBot))
+            .with_player(Player::green().with_name("Bob"), BotStrategy::Myopic(MyopicBot))
+            .build()
+            .unwrap();
+
+        let result = game.play_observed(0, &mut [Box::new(Rc::clone(&demo))]);
+        let output = String::from_utf8(demo.borrow().output.clone()).unwrap();
+
+        assert!(output.starts_with("\nCarcassonne: Alice (myopic) v Bob (myopic)\n"), "{}", &output[..80]);
+        assert!(!output.contains(CLEAR_SCREEN));
+        assert_eq!(output.matches("ꆜR Alice").count(), result.turn_count + 1);
+        assert!(output.contains(&format!("\nTurn {}: ", result.turn_count)));
+        assert!(output.contains("Final score is\n"));
+        assert!(output.trim_end().ends_with("wins!") || output.trim_end().ends_with("share the win!"));
+    }
+
+    #[test]
+    fn should_pick_out_a_completed_cloister_by_its_own_tile() {
+        let mut tiles = vec![PlacedTile::new(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0)];
+        tiles.extend([(-1, 0), (-1, -1), (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1)].map(|(x, y)| PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0)));
+        let board = Board::new_with_tiles(tiles).unwrap();
+
+        let mut around: Vec<_> = board.placed_tiles().map(|(coordinate, _)| *coordinate).collect();
+        around.sort_by_key(|coordinate| (coordinate.y, coordinate.x));
+
+        let cloister = Completion { region_type: RegionType::Cloister, tiles: around, points: 9 };
+        let road = Completion { region_type: RegionType::Road, tiles: vec![BoardCoordinate::new(4, 0)], points: 1 };
+
+        assert_eq!(anchor(&board, &cloister), Some(BoardCoordinate::new(0, 0)));
+        assert_eq!(anchor(&board, &road), Some(BoardCoordinate::new(4, 0)));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::board::Board;
+use crate::tile::{PlacedTile, RenderCell, RenderStyle, TileCoordinate, TILE_WIDTH};
+use colored::{Color, Colorize};
+
+const HIGHLIGHT: Color = Color::BrightMagenta;
+
+/// Renders `after` with what changed since `before` picked out: tiles that weren't on the board
+/// are outlined, and meeple that have been taken off the board are marked where they stood
+pub fn render_diff(before: &Board, after: &Board, style: &RenderStyle) -> String {
+    diff(before, after, Some(style))
+}
+
+/// As [render_diff], drawn without colour like [Board::render_plain], for pasting into bug reports
+pub fn render_diff_plain(before: &Board, after: &Board) -> String {
+    diff(before, after, None)
+}
+
+/// Draws the diff in `style`, or plain when there is no style
+fn diff(before: &Board, after: &Board, style: Option<&RenderStyle>) -> String {
+    after.render_tiles(|tile| match before.placed_tiles.get(&tile.placement.coordinate) {
+        None => render_added_tile(tile, style),
+        Some(previous) => match previous.meeple_render_coordinate() {
+            Some((coordinate, _)) if tile.meeple.is_none() => render_removed_meeple(tile, coordinate, style),
+            _ => render_cell_lines(tile, style, |_, _, _| None),
+        },
+    })
+}
+
+fn render_added_tile(tile: &PlacedTile, style: Option<&RenderStyle>) -> Vec<String> {
+    render_cell_lines(tile, style, |cell, row_idx, column_idx| {
+        let outline = match (cell, style) {
+            (RenderCell::Corner, _) => return None,
+            _ if row_idx == 0 || row_idx == TILE_WIDTH - 1 => "══",
+            (_, None) if column_idx == 0 => " ║",
+            (_, None) if column_idx == TILE_WIDTH - 1 => "║ ",
+            _ if column_idx == 0 || column_idx == TILE_WIDTH - 1 => "║",
+            _ => return None,
+        };
+
+        Some(highlight(outline, style, false))
+    })
+}
+
+fn render_removed_meeple(tile: &PlacedTile, meeple_coordinate: TileCoordinate, style: Option<&RenderStyle>) -> Vec<String> {
+    render_cell_lines(tile, style, |_, row_idx, column_idx| {
+        (meeple_coordinate == TileCoordinate::new(column_idx, row_idx)).then(|| highlight("✕ ", style, true))
+    })
+}
+
+/// Draws the tile in `style`, or plain, with `marker` drawing any cell in place of the tile
+fn render_cell_lines(
+    tile: &PlacedTile,
+    style: Option<&RenderStyle>,
+    marker: impl Fn(&RenderCell, usize, usize) -> Option<String>,
+) -> Vec<String> {
+    tile.render_cells(|cell, row_idx, column_idx, meeple| {
+        marker(cell, row_idx, column_idx).unwrap_or_else(|| match style {
+            Some(style) => cell.render(style, row_idx, column_idx, meeple),
+            None => cell.render_plain(row_idx, column_idx, meeple),
+        })
+    })
+}
+
+fn highlight(marker: &str, style: Option<&RenderStyle>, inverted: bool) -> String {
+    match style {
+        None | Some(RenderStyle::Ascii) => marker.to_string(),
+        Some(_) if inverted => marker.color(HIGHLIGHT).on_color(Color::Black).to_string(),
+        Some(_) => marker.color(Color::Black).on_color(HIGHLIGHT).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use crate::test_util::TestPlayer;
+    use crate::tile::{BoardCoordinate, Rotation};
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+
+    #[test]
+    fn should_outline_added_tiles() {
+        let before = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+        let after = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, -1, 0, Rotation::R90)]).unwrap();
+
+        let rendered = render_diff(&before, &after, &RenderStyle::Ascii);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        // the new tile is on the left, the road that was already there is drawn as usual
+        assert!(lines[0].starts_with(" ══════════ "));
+        assert!(lines[3].starts_with('║'));
+        assert!(lines[3].ends_with(&after.placed_tiles[&BoardCoordinate::new(0, 0)].render_to_lines(&RenderStyle::Ascii)[3]));
+        assert_eq!(rendered.matches('║').count(), 2 * (TILE_WIDTH - 2));
+    }
+
+    #[test]
+    fn should_mark_meeple_taken_off_the_board() {
+        let mut alice = Player::red();
+
+        let before = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).unwrap();
+        // closing the city sends Alice's meeple back
+        let mut after = before.clone();
+        after.place_tile(PlacedTile::new(&SIDE_CITY, 0, 1, Rotation::R180)).unwrap();
+
+        let rendered = render_diff(&before, &after, &RenderStyle::Ascii);
+
+        assert_eq!(rendered.matches('✕').count(), 1);
+        assert!(!rendered.contains('ꆜ'));
+        assert_eq!(render_diff(&after, &after, &RenderStyle::Ascii), after.render(&RenderStyle::Ascii));
+    }
+
+    #[test]
+    fn should_diff_without_colour() {
+        let before = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+        let after = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, -1, 0, Rotation::R90)]).unwrap();
+
+        let rendered = render_diff_plain(&before, &after);
+
+        assert!(!rendered.contains('\x1b'));
+        assert!(rendered.lines().all(|line| line.chars().count() == 2 * TILE_WIDTH * 2));
+        assert!(rendered.lines().next().unwrap().starts_with("  ══════════  "));
+        assert_eq!(render_diff_plain(&after, &after), after.render_plain());
+    }
+}
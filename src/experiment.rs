@@ -0,0 +1,390 @@
+use crate::bot_strategy::{BotPlayer, BotStrategy, ExpectimaxBot};
+use crate::game_logic::GameBuildError;
+use crate::player::{MeepleColor, Player};
+use crate::replay::ReplayFormat;
+use crate::rng::GameRng;
+use crate::rules::{RiverOptions, RuleSet, RuleSetError, StartingLayout};
+use crate::simulate::{score_gap_exceeds, Seating, Seed, SimulationConfig};
+use crate::tile::{parse_expansions, Expansion, ExpansionError};
+use base64::{engine::general_purpose, Engine as _};
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A batch of simulated games described in a TOML file, so an experiment can be rerun exactly
+/// from the file rather than from shell history:
+///
+/// ```toml
+/// name = "monte carlo against the jerk"
+/// iterations = 200
+/// seed = "GWS-GnBV0dkbvQ3LkdXOBt_u_uuP_wZlCNfmI6Cy0jc="
+/// expansions = ["river"]
+/// seating = "rotate"
+///
+/// [rules]
+/// preset = "2021"
+/// meeple_per_player = 6
+/// river_min_length = 6
+/// separate_river_ends = true
+/// river_lake = true
+/// board_limit = [15, 15]
+///
+/// [output]
+/// replays = "replays/monte-carlo"
+/// replay_format = "binary"
+/// report = "reports/monte-carlo.json"
+/// csv = "reports/monte-carlo.csv"
+/// baseline = "reports/monte-carlo-before.json"
+/// contact_sheet = "reports/monte-carlo-boards.html"
+/// dataset = "datasets/monte-carlo.npz"
+///
+/// [[players]]
+/// name = "Alice"
+/// color = "Red"
+/// bot = "monte-carlo"
+/// samples = 16
+///
+/// [[players]]
+/// color = "Green"
+/// bot = "jerk"
+/// starting_score = 10
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Experiment {
+    pub name: Option<String>,
+    #[serde(default = "default_iterations")]
+    pub iterations: usize,
+    /// Url safe base64, as printed by the command line. A fresh seed is used if there isn't one
+    pub seed: Option<String>,
+    #[serde(default = "default_expansions")]
+    pub expansions: Vec<String>,
+    #[serde(default)]
+    pub seating: Seating,
+    /// Ends each game once the leader is this many points ahead; see [score_gap_exceeds]
+    pub abort_score_gap: Option<i32>,
+    #[serde(default)]
+    pub rules: ExperimentRules,
+    #[serde(default)]
+    pub output: ExperimentOutput,
+    pub players: Vec<ExperimentPlayer>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentRules {
+    /// One of [crate::rules::PRESET_NAMES], which the other rules adjust
+    pub preset: Option<String>,
+    pub meeple_per_player: Option<usize>,
+    /// Plays without farmers, see [RuleSet::with_farmers]
+    #[serde(default)]
+    pub no_farmers: bool,
+    /// See [RiverOptions::min_length]
+    pub river_min_length: Option<usize>,
+    /// See [RiverOptions::separate_ends]
+    #[serde(default)]
+    pub separate_river_ends: bool,
+    /// See [RiverOptions::lake]
+    #[serde(default)]
+    pub river_lake: bool,
+    /// Columns and rows of tiles the board may span, e.g. `[15, 15]`
+    pub board_limit: Option<(u8, u8)>,
+    /// One of [crate::rules::LAYOUT_NAMES], for games without the river
+    pub starting_layout: Option<String>,
+    /// See [RuleSet::with_obvious_moves_played]
+    #[serde(default)]
+    pub play_obvious_moves: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentOutput {
+    /// Directory to write a replay of every game to
+    pub replays: Option<PathBuf>,
+    #[serde(default)]
+    pub replay_format: ReplayFormat,
+    /// File to write the report to as JSON
+    pub report: Option<PathBuf>,
+    /// File to write a row for each player in each game to, as CSV
+    pub csv: Option<PathBuf>,
+    /// Report written by an earlier run, to show the changes since in the summary
+    pub baseline: Option<PathBuf>,
+    /// File to draw every game's final board to, as an HTML page of thumbnails
+    pub contact_sheet: Option<PathBuf>,
+    /// File to write every turn to as a training example, see [crate::dataset]
+    pub dataset: Option<PathBuf>,
+    /// Discord or Slack webhook URL to post progress and the final result to. Needs the `webhook`
+    /// feature
+    pub webhook: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExperimentPlayer {
+    pub name: Option<String>,
+    pub color: MeepleColor,
+    /// One of [crate::bot_strategy::BOT_NAMES]
+    pub bot: String,
+    /// Rollouts per move, for the monte-carlo bot
+    pub samples: Option<usize>,
+    /// Tiles per rollout, for the monte-carlo bot
+    pub horizon: Option<usize>,
+    /// Thinking time per move in milliseconds, for the expectimax bot
+    pub budget_ms: Option<u64>,
+    pub starting_score: Option<i32>,
+    pub score_multiplier: Option<f64>,
+}
+
+#[derive(Debug)]
+pub enum ExperimentError {
+    Io(std::io::Error),
+    /// Not valid TOML, e.g. a key defined twice. The error says where
+    Syntax(toml::de::Error),
+    /// Valid TOML that doesn't describe an experiment, e.g. a missing or misspelt key
+    Invalid(toml::de::Error),
+    /// Not 32 bytes of url safe base64
+    InvalidSeed,
+    UnknownBot(String),
+    /// A rules preset that isn't one of [crate::rules::PRESET_NAMES]
+    UnknownRules(String),
+    /// A starting layout that isn't one of [crate::rules::LAYOUT_NAMES]
+    UnknownLayout(String),
+    /// A bot parameter given to a bot that doesn't take it
+    UnusedParameter { bot: String, parameter: &'static str },
+    Expansions(ExpansionError),
+    Rules(RuleSetError),
+    Players(GameBuildError),
+}
+
+impl From<std::io::Error> for ExperimentError {
+    fn from(error: std::io::Error) -> Self {
+        ExperimentError::Io(error)
+    }
+}
+
+fn default_iterations() -> usize {
+    100
+}
+
+fn default_expansions() -> Vec<String> {
+    vec![Expansion::River.name().to_string()]
+}
+
+impl Experiment {
+    pub fn from_toml(source: &str) -> Result<Self, ExperimentError> {
+        let table: toml::Table = source.parse().map_err(ExperimentError::Syntax)?;
+
+        table.try_into().map_err(ExperimentError::Invalid)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ExperimentError> {
+        Self::from_toml(&std::fs::read_to_string(path)?)
+    }
+
+    /// The experiment's seed, if it has one
+    pub fn seed(&self) -> Result<Option<Seed>, ExperimentError> {
+        let Some(seed) = &self.seed else {
+            return Ok(None);
+        };
+
+        let bytes = general_purpose::URL_SAFE.decode(seed).map_err(|_| ExperimentError::InvalidSeed)?;
+
+        bytes.try_into().map(Some).map_err(|_| ExperimentError::InvalidSeed)
+    }
+
+    /// The simulation the experiment describes, seeded with `seed`. The bots are seeded from it
+    /// too, so the same seed plays the same games
+    pub fn config(&self, seed: Seed) -> Result<SimulationConfig, ExperimentError> {
+        let mut rng = GameRng::from_seed(seed);
+
+        let mut rules = match &self.rules.preset {
+            Some(name) => RuleSet::preset(name).ok_or_else(|| ExperimentError::UnknownRules(name.clone()))?,
+            None => RuleSet::standard(),
+        };
+
+        if let Some(meeple_per_player) = self.rules.meeple_per_player {
+            rules = rules.with_meeple_per_player(meeple_per_player);
+        }
+
+        if self.rules.no_farmers {
+            rules = rules.with_farmers(false);
+        }
+
+        rules = rules.with_river_options(RiverOptions {
+            min_length: self.rules.river_min_length,
+            separate_ends: self.rules.separate_river_ends,
+            lake: self.rules.river_lake,
+        });
+
+        if let Some((width, height)) = self.rules.board_limit {
+            rules = rules.with_board_limit(width, height);
+        }
+
+        if let Some(name) = &self.rules.starting_layout {
+            rules = rules.with_starting_layout(StartingLayout::preset(name).ok_or_else(|| ExperimentError::UnknownLayout(name.clone()))?);
+        }
+
+        rules = rules.with_obvious_moves_played(self.rules.play_obvious_moves);
+
+        let mut players = vec![];
+
+        for player in &self.players {
+            if let Some(points) = player.starting_score {
+                rules = rules.with_starting_score(player.color, points);
+            }
+
+            if let Some(multiplier) = player.score_multiplier {
+                rules = rules.with_score_multiplier(player.color, multiplier);
+            }
+
+            players.push(player.bot_player(&mut rng)?);
+        }
+
+        let expansions = parse_expansions(&self.expansions.join(",")).map_err(ExperimentError::Expansions)?;
+        rules.validate(&expansions).map_err(ExperimentError::Rules)?;
+
+        let mut config = SimulationConfig::new(players, self.iterations, rng.gen())
+            .map_err(ExperimentError::Players)?
+            .with_river(expansions.contains(&Expansion::River))
+            .with_seating(self.seating)
+            .with_rules(rules)
+            .map_err(ExperimentError::Players)?;
+
+        if let Some(gap) = self.abort_score_gap {
+            config = config.with_early_abort(score_gap_exceeds(gap));
+        }
+
+        Ok(config)
+    }
+}
+
+impl ExperimentPlayer {
+    fn bot_player(&self, rng: &mut GameRng) -> Result<BotPlayer, ExperimentError> {
+        let unused = |parameter| ExperimentError::UnusedParameter { bot: self.bot.clone(), parameter };
+
+        let mut bot = BotStrategy::from_name(&self.bot, rng).ok_or_else(|| ExperimentError::UnknownBot(self.bot.clone()))?;
+
+        bot = match bot {
+            BotStrategy::MonteCarlo(mut monte_carlo) => {
+                if let Some(samples) = self.samples {
+                    monte_carlo = monte_carlo.with_samples(samples);
+                }
+
+                if let Some(horizon) = self.horizon {
+                    monte_carlo = monte_carlo.with_horizon(horizon);
+                }
+
+                BotStrategy::MonteCarlo(monte_carlo)
+            }
+            _ if self.samples.is_some() => return Err(unused("samples")),
+            _ if self.horizon.is_some() => return Err(unused("horizon")),
+            bot => bot,
+        };
+
+        bot = match (bot, self.budget_ms) {
+            (BotStrategy::Expectimax(_), Some(budget)) => BotStrategy::Expectimax(ExpectimaxBot::with_budget(Duration::from_millis(budget))),
+            (_, Some(_)) => return Err(unused("budget_ms")),
+            (bot, None) => bot,
+        };
+
+        let player = match &self.name {
+            Some(name) => Player::new(self.color).with_name(name.clone()),
+            None => Player::new(self.color),
+        };
+
+        Ok(player.with_bot(bot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPERIMENT: &str = r#"
+        # a comment
+        name = "monte carlo # against the jerk"
+        iterations = 2
+        seed = "GWS-GnBV0dkbvQ3LkdXOBt_u_uuP_wZlCNfmI6Cy0jc="
+        expansions = []
+        seating = "rotate"
+
+        [rules]
+        meeple_per_player = 6
+
+        [[players]]
+        name = "Alice"
+        color = "Red"
+        bot = "monte-carlo"
+        samples = 2
+        horizon = 4
+
+        [[players]]
+        color = "Green"
+        bot = "lazy"
+        starting_score = 10 # a head start
+    "#;
+
+    #[test]
+    fn should_read_an_experiment() {
+        let experiment = Experiment::from_toml(EXPERIMENT).unwrap();
+
+        assert_eq!(experiment.name.as_deref(), Some("monte carlo # against the jerk"));
+        assert_eq!(experiment.iterations, 2);
+        assert_eq!(experiment.seating, Seating::Rotate);
+        assert_eq!(experiment.rules.meeple_per_player, Some(6));
+        assert_eq!(experiment.output, ExperimentOutput::default());
+        assert_eq!(experiment.players.len(), 2);
+        assert_eq!(experiment.players[0].samples, Some(2));
+        assert_eq!(experiment.players[1].starting_score, Some(10));
+
+        let seed = experiment.seed().unwrap().unwrap();
+        let config = experiment.config(seed).unwrap();
+
+        assert!(!config.include_river);
+        assert_eq!(config.seating, Seating::Rotate);
+        assert_eq!(config.rules, RuleSet::standard().with_meeple_per_player(6).with_starting_score(MeepleColor::Green, 10));
+        assert_eq!(config.players[&MeepleColor::Red].bot.name(), "monte-carlo");
+
+        // the same experiment describes the same games
+        assert_eq!(experiment.config(seed).unwrap().seed, config.seed);
+    }
+
+    #[test]
+    fn should_point_out_what_is_wrong_with_an_experiment() {
+        let players = "[[players]]\ncolor = \"Red\"\nbot = \"lazy\"\n";
+
+        assert!(matches!(Experiment::from_toml("iterations = 2\niterations = 3"), Err(ExperimentError::Syntax(error)) if error.to_string().contains("line 2")));
+        assert!(matches!(Experiment::from_toml("iterations"), Err(ExperimentError::Syntax(error)) if error.to_string().contains("line 1")));
+        assert!(matches!(Experiment::from_toml(&format!("iteration = 2\n{}", players)), Err(ExperimentError::Invalid(_))));
+
+        let experiment = |extra: &str| Experiment::from_toml(&format!("{}\n{}", players, extra)).unwrap();
+
+        assert!(matches!(experiment("samples = 3").config([0; 32]), Err(ExperimentError::UnusedParameter { parameter: "samples", .. })));
+        assert!(matches!(experiment("horizon = 3").config([0; 32]), Err(ExperimentError::UnusedParameter { parameter: "horizon", .. })));
+        assert!(matches!(experiment("[rules]\npreset = \"house\"").config([0; 32]), Err(ExperimentError::UnknownRules(_))));
+        assert_eq!(experiment("[rules]\npreset = \"2021\"").config([0; 32]).unwrap().rules, RuleSet::standard_2021());
+
+        let river = experiment("[rules]\nriver_min_length = 3\nriver_lake = true").config([0; 32]).unwrap().rules.river;
+        assert_eq!(river, RiverOptions { min_length: Some(3), separate_ends: false, lake: true });
+
+        let limited = experiment("[rules]\nboard_limit = [15, 12]").config([0; 32]).unwrap().rules;
+        assert_eq!(limited, RuleSet::standard().with_board_limit(15, 12));
+
+        let without_river = |extra: &str| Experiment::from_toml(&format!("expansions = []\n{}\n{}", players, extra)).unwrap();
+
+        let wheel = without_river("[rules]\nstarting_layout = \"wheel\"").config([0; 32]).unwrap().rules;
+        assert_eq!(wheel, RuleSet::standard().with_starting_layout(StartingLayout::wheel()));
+        assert!(matches!(experiment("[rules]\nstarting_layout = \"wheel\"").config([0; 32]), Err(ExperimentError::Rules(RuleSetError::StartingLayoutWithRiver))));
+        assert!(matches!(without_river("[rules]\nstarting_layout = \"maze\"").config([0; 32]), Err(ExperimentError::UnknownLayout(_))));
+        assert!(matches!(
+            Experiment::from_toml("[[players]]\ncolor = \"Red\"\nbot = \"nobody\"").unwrap().config([0; 32]),
+            Err(ExperimentError::UnknownBot(_))
+        ));
+        assert!(matches!(Experiment::from_toml(&format!("seed = \"short\"\n{}", players)).unwrap().seed(), Err(ExperimentError::InvalidSeed)));
+        assert!(matches!(
+            Experiment::from_toml(&format!("{}{}", players, players)).unwrap().config([0; 32]),
+            Err(ExperimentError::Players(GameBuildError::DuplicatePlayer(MeepleColor::Red)))
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+//! Turning many replays into images or pages at once, e.g. every game of a tournament round to
+//! publish. Each replay is written under the name of its file, and an index page links them all
+
+use crate::commentary;
+use crate::replay::{Replay, ReplayError};
+use crate::score_sheet::score_sheet;
+use crate::svg::escape;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Names of the formats replays can be exported to, as given on the command line
+pub const EXPORT_FORMATS: [&str; 3] = ["gif", "svg", "html"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The game played out a tile at a time, see [Replay::to_gif]
+    Gif { frame_delay: Duration },
+    /// The final board
+    Svg,
+    /// A page with the final board, the score broken down and commentary on every move
+    Html,
+}
+
+impl ExportFormat {
+    /// The format called `name`, one of [EXPORT_FORMATS]. A GIF's frames are `frame_delay` apart
+    pub fn from_name(name: &str, frame_delay: Duration) -> Option<Self> {
+        match name {
+            "gif" => Some(ExportFormat::Gif { frame_delay }),
+            "svg" => Some(ExportFormat::Svg),
+            "html" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Gif { .. } => "gif",
+            ExportFormat::Svg => "svg",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    fn render(&self, replay: &Replay) -> Result<Vec<u8>, ReplayError> {
+        match self {
+            ExportFormat::Gif { frame_delay } => replay.to_gif(*frame_delay),
+            ExportFormat::Svg => Ok(replay.state_at(replay.turns.len())?.board.render_svg().into_bytes()),
+            ExportFormat::Html => Ok(replay.to_html()?.into_bytes()),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Replay(PathBuf, ReplayError),
+    Write(PathBuf, io::Error),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Replay(path, error) => write!(f, "{}: {}", path.display(), error),
+            ExportError::Write(path, error) => write!(f, "could not write {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+/// A replay written out, with the file it was written to and a caption of its result
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exported {
+    pub replay: PathBuf,
+    pub output: PathBuf,
+    pub caption: String,
+}
+
+/// The outcome of exporting each replay, in the order they were given, and the index page
+/// linking those that were written
+#[derive(Debug)]
+pub struct ExportReport {
+    pub results: Vec<Result<Exported, ExportError>>,
+    pub index: PathBuf,
+}
+
+/// Writes each replay into `directory` in the format, side by side on as many threads as rayon
+/// has, then an `index.html` linking them. The files are named after the replays, see
+/// [output_names]. Replays that can't be exported are reported without stopping the rest
+pub fn export_replays(replays: &[PathBuf], format: ExportFormat, directory: &Path) -> Result<ExportReport, ExportError> {
+    fs::create_dir_all(directory).map_err(|error| ExportError::Write(directory.to_path_buf(), error))?;
+
+    let names = output_names(replays);
+
+    let results: Vec<_> = replays
+        .par_iter()
+        .zip(names.par_iter())
+        .map(|(path, name)| {
+            let replay = Replay::load(path).map_err(|error| ExportError::Replay(path.clone(), error))?;
+            let rendered = format.render(&replay).map_err(|error| ExportError::Replay(path.clone(), error))?;
+
+            let output = directory.join(format!("{}.{}", name, format.extension()));
+            fs::write(&output, rendered).map_err(|error| ExportError::Write(output.clone(), error))?;
+
+            Ok(Exported { replay: path.clone(), output, caption: caption(&replay) })
+        })
+        .collect();
+
+    let exported: Vec<&Exported> = results.iter().filter_map(|result| result.as_ref().ok()).collect();
+    let index = directory.join("index.html");
+
+    fs::write(&index, render_index(&exported, format)).map_err(|error| ExportError::Write(index.clone(), error))?;
+
+    Ok(ExportReport { results, index })
+}
+
+/// The name each replay is exported under: its file name without the extension, or where two
+/// replays share one, with the directory it's in in front, e.g. `round-2-game-1`. Any still the
+/// same are numbered in the order given
+pub fn output_names(replays: &[PathBuf]) -> Vec<String> {
+    let stem = |path: &PathBuf| path.file_stem().map_or_else(|| "replay".to_string(), |stem| stem.to_string_lossy().into_owned());
+
+    let mut stems: HashMap<String, usize> = HashMap::new();
+    for path in replays {
+        *stems.entry(stem(path)).or_default() += 1;
+    }
+
+    let mut taken: HashMap<String, usize> = HashMap::new();
+
+    replays
+        .iter()
+        .map(|path| {
+            let name = match (stems[&stem(path)], path.parent().and_then(Path::file_name)) {
+                (1, _) | (_, None) => stem(path),
+                (_, Some(parent)) => format!("{}-{}", parent.to_string_lossy(), stem(path)),
+            };
+
+            let count = taken.entry(name.clone()).or_default();
+            *count += 1;
+
+            match count {
+                1 => name,
+                _ => format!("{}-{}", name, count),
+            }
+        })
+        .collect()
+}
+
+/// The replays a pattern names: a file, every file in a directory, or the files whose names match
+/// a pattern with `*` for any run of characters and `?` for any one, e.g. `round-2/*.json`. Only
+/// the file name may have wildcards. Sorted by path
+pub fn find_replays(pattern: &str) -> io::Result<Vec<PathBuf>> {
+    let path = Path::new(pattern);
+
+    let (directory, name) = match path.file_name().map(|name| name.to_string_lossy()) {
+        Some(name) if name.contains(['*', '?']) => (path.parent().filter(|parent| !parent.as_os_str().is_empty()).unwrap_or(Path::new(".")), Some(name.into_owned())),
+        _ if path.is_dir() => (path, None),
+        _ => return Ok(vec![path.to_path_buf()]),
+    };
+
+    let mut replays: Vec<PathBuf> = fs::read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| match (&name, path.file_name()) {
+            (Some(pattern), Some(file_name)) => wildcard_match(pattern, &file_name.to_string_lossy()),
+            (None, _) => true,
+            (Some(_), None) => false,
+        })
+        .collect();
+
+    replays.sort();
+
+    Ok(replays)
+}
+
+/// Whether the whole of `text` matches the pattern, `*` standing for any run of characters and
+/// `?` for any one
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+
+    // matches[j] is whether the pattern so far matches the first j characters of the text
+    let mut matches = vec![false; text.len() + 1];
+    matches[0] = true;
+
+    for wildcard in pattern {
+        matches = match wildcard {
+            // any run of characters, so everything from the first match on
+            '*' => matches
+                .iter()
+                .scan(false, |any, matched| {
+                    *any |= *matched;
+                    Some(*any)
+                })
+                .collect(),
+            _ => std::iter::once(false)
+                .chain(text.iter().enumerate().map(|(index, character)| matches[index] && (wildcard == '?' || wildcard == *character)))
+                .collect(),
+        };
+    }
+
+    matches[text.len()]
+}
+
+/// The players and their final scores, e.g. `Alice 32, Bob 27`
+fn caption(replay: &Replay) -> String {
+    replay
+        .final_score
+        .iter()
+        .map(|score| {
+            let name = replay.players.iter().find(|player| player.color == score.player).and_then(|player| player.name.clone());
+
+            format!("{} {}", name.unwrap_or_else(|| score.player.to_string()), score.points)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_index(exported: &[&Exported], format: ExportFormat) -> String {
+    let mut html = "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Games</title><style>\
+                    body { font-family: sans-serif; display: flex; flex-wrap: wrap; gap: 12px; }\
+                    figure { margin: 0; width: 240px; }\
+                    figure img { width: 100%; height: 240px; object-fit: contain; }\
+                    figcaption { font-size: 12px; }</style></head><body>"
+        .to_string();
+
+    for exported in exported {
+        let file = exported.output.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let file = escape(&file);
+
+        match format {
+            ExportFormat::Html => write!(html, "<figure><a href=\"{}\">{}</a><figcaption>{}</figcaption></figure>", file, file, escape(&exported.caption)),
+            _ => write!(html, "<figure><a href=\"{}\"><img src=\"{}\" alt=\"{}\"></a><figcaption>{}</figcaption></figure>", file, file, file, escape(&exported.caption)),
+        }
+        .unwrap();
+    }
+
+    html.push_str("</body></html>");
+
+    html
+}
+
+impl Replay {
+    /// A page for the game: the final board, each player's score broken down by how it was
+    /// earned, and commentary on every move
+    pub fn to_html(&self) -> Result<String, ReplayError> {
+        let state = self.state_at(self.turns.len())?;
+        let sheet = score_sheet(self)?;
+        let moves = commentary::commentate(self)?;
+
+        let mut html = format!(
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{0}</title><style>\
+             body {{ font-family: sans-serif; }} td, th {{ padding: 2px 8px; text-align: right; }}</style></head>\
+             <body><h1>{0}</h1><p>Seed {1}</p>{2}<table><tr><th></th><th>Cities</th><th>Roads</th><th>Cloisters</th><th>End of game</th><th>Total</th></tr>",
+            escape(&caption(self)),
+            escape(&self.seed),
+            state.board.render_svg()
+        );
+
+        for row in &sheet {
+            let name = row.name.clone().unwrap_or_else(|| row.player.to_string());
+
+            write!(
+                html,
+                "<tr><th>{}</th><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&name),
+                row.cities,
+                row.roads,
+                row.cloisters,
+                row.end_of_game,
+                row.total()
+            )
+            .unwrap();
+        }
+
+        html.push_str("</table><h2>Moves</h2><ol>");
+
+        for description in moves {
+            write!(html, "<li>{}</li>", escape(&description)).unwrap();
+        }
+
+        html.push_str("</ol></body></html>");
+
+        Ok(html)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, MyopicBot};
+    use crate::game_logic::Game;
+    use crate::player::Player;
+    use crate::replay::ReplayRecorder;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn should_match_wildcards_against_the_whole_name() {
+        assert!(wildcard_match("*.json", "game-1.json"));
+        assert!(wildcard_match("game-?.json", "game-1.json"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("*.json", "game-1.json.bak"));
+        assert!(!wildcard_match("game-?.json", "game-10.json"));
+    }
+
+    #[test]
+    fn should_name_replays_after_their_files_and_directories_when_they_clash() {
+        let names = output_names(&["round-1/game-1.json", "round-2/game-1.json", "round-2/game-2.bin", "round-2/game-2.json"].map(PathBuf::from));
+
+        assert_eq!(names, ["round-1-game-1", "round-2-game-1", "round-2-game-2", "round-2-game-2-2"]);
+    }
+
+    #[test]
+    fn should_export_every_replay_and_link_them_from_the_index() {
+        let directory = std::env::temp_dir().join(format!("carcassonne-export-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(directory.join("replays")).unwrap();
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::to_directory(directory.join("replays"))));
+
+        for game_index in 0..2 {
+            Game::builder()
+                .with_seed([game_index as u8; 32])
+                .with_player(Player::red().with_name("Alice"), BotStrategy::Myopic(MyopicBot))
+                .with_player(Player::green().with_name("Bob & co"), BotStrategy::Myopic(MyopicBot))
+                .build()
+                .unwrap()
+                .play_observed(game_index, &mut [Box::new(Rc::clone(&recorder))]);
+        }
+
+        let mut replays = find_replays(&format!("{}/*", directory.join("replays").display())).unwrap();
+        assert_eq!(replays.len(), 2);
+
+        replays.push(directory.join("replays/missing.json"));
+
+        let report = export_replays(&replays, ExportFormat::Html, &directory.join("html")).unwrap();
+
+        assert!(matches!(report.results[2], Err(ExportError::Replay(..))));
+
+        let exported: Vec<_> = report.results.iter().filter_map(|result| result.as_ref().ok()).collect();
+        assert_eq!(exported.len(), 2);
+
+        let page = fs::read_to_string(&exported[0].output).unwrap();
+        assert!(page.contains("<svg ") && page.contains("Bob &amp; co") && page.contains("<li>"));
+
+        let index = fs::read_to_string(&report.index).unwrap();
+        for exported in exported {
+            assert!(index.contains(&format!("href=\"{}\"", exported.output.file_name().unwrap().to_string_lossy())));
+        }
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,1557 @@
+use crate::analysis::turns_remaining_estimate;
+use crate::board::{Board, LiberatedMeeple, TilePlacementSuccess};
+use crate::bot_strategy::BotPlayer;
+#[cfg(feature = "tokio")]
+use crate::async_controller::AsyncPlayerController;
+use crate::controller::{GameView, PlayerAction, PlayerController};
+use crate::crash_report;
+use crate::move_hints::{MeepleContext, MoveHint};
+use crate::deck::Deck;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::{Player, PlayerIdentifier};
+use crate::rng::GameRng;
+use crate::rules::{RuleSet, RuleSetError};
+use crate::score::Score;
+use crate::simulate::{FeatureCounts, GameResult, Seed, ThinkTime};
+use crate::team::Team;
+use crate::tile::{Expansion, PlacedTile, RegionType, TileDefinition, TileId};
+use crate::tile_definitions::find_by_id;
+use indexmap::IndexMap;
+use rand::rngs::OsRng;
+use rand::{Rng, SeedableRng};
+use std::cell::RefCell;
+#[cfg(feature = "debug-validate")]
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// A single game, ready to be played
+pub struct Game {
+    players: IndexMap<PlayerIdentifier, Player>,
+    controllers: IndexMap<PlayerIdentifier, Controller>,
+    rules: RuleSet,
+    teams: Vec<Team>,
+    abort: Option<AbortCondition>,
+    seed: Seed,
+    deck: DeckSource,
+}
+
+/// The part of the game a turn is played in. Games with the river lay it first, a tile a turn,
+/// before the main game is dealt; games without it are all main game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum GamePhase {
+    River,
+    #[default]
+    Main,
+}
+
+impl GamePhase {
+    /// The phase of the turn `tile` is drawn in
+    pub fn of(tile: &TileDefinition) -> Self {
+        match tile.expansion {
+            Some(Expansion::River) => GamePhase::River,
+            _ => GamePhase::Main,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GamePhase::River => "river",
+            GamePhase::Main => "main",
+        }
+    }
+}
+
+/// Where a game's tiles come from
+#[derive(Clone)]
+pub(crate) enum DeckSource {
+    Shuffled(Vec<Expansion>),
+    Fixed(Vec<TileId>),
+}
+
+impl DeckSource {
+    /// The deck the game is dealt from, shuffled by `rng` unless its order is fixed. A drawn tile
+    /// `can_be_placed` says no to is set aside as the rules say
+    fn deal<F>(self, rules: &RuleSet, rng: Rc<RefCell<GameRng>>, can_be_placed: F) -> Deck
+    where
+        F: Fn(&'static TileDefinition) -> bool + Clone + 'static,
+    {
+        match self {
+            DeckSource::Shuffled(expansions) => Deck::new(expansions.contains(&Expansion::River), rng, can_be_placed)
+                .with_river_options(rules.river)
+                .with_discard_policy(rules.discard_policy)
+                .with_starting_tile(rules.starting_tile),
+            DeckSource::Fixed(tile_order) => Deck::from_fixed_order(tile_order).expect("tile order is checked when the game is built"),
+        }
+    }
+}
+
+/// Ends a game early when it returns true for the score so far, e.g. once the result is no longer
+/// in doubt. It is checked before every turn
+pub type AbortCondition = Arc<dyn Fn(&Score) -> bool + Send + Sync>;
+
+/// Whatever makes a player's moves
+enum Controller {
+    Sync(Box<dyn PlayerController>),
+    #[cfg(feature = "tokio")]
+    Async(Box<dyn AsyncPlayerController>),
+}
+
+impl Controller {
+    fn bot_name(&self) -> Option<&'static str> {
+        match self {
+            Controller::Sync(controller) => controller.bot_name(),
+            #[cfg(feature = "tokio")]
+            Controller::Async(controller) => controller.bot_name(),
+        }
+    }
+
+    fn observe(&mut self, event: &GameEvent) {
+        match self {
+            Controller::Sync(controller) => controller.observe(event),
+            #[cfg(feature = "tokio")]
+            Controller::Async(controller) => controller.observe(event),
+        }
+    }
+}
+
+/// Passes the game's latest events on to every player's controller
+fn tell_players(controllers: &mut IndexMap<PlayerIdentifier, Controller>, run: &mut GameRun) {
+    for event in run.take_player_events() {
+        for controller in controllers.values_mut() {
+            controller.observe(&event);
+        }
+    }
+}
+
+/// Most times a seat can change hands in one turn. Controllers that keep handing it on, e.g. to
+/// each other, pass the turn once it has, and the last of them keeps the seat
+pub(crate) const MAX_HAND_OVERS: usize = 8;
+
+/// Gives the player's seat to `controller`, telling everyone it has changed hands
+fn hand_over(controllers: &mut IndexMap<PlayerIdentifier, Controller>, run: &mut GameRun, player_id: PlayerIdentifier, controller: Box<dyn PlayerController>) {
+    let bot = controller.bot_name();
+    controllers[&player_id] = Controller::Sync(controller);
+
+    let game_index = run.game_index;
+    notify(run.observers, &mut run.player_events, || GameEvent::ControllerChanged { game_index, player: player_id, bot });
+    tell_players(controllers, run);
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GameBuildError {
+    NoPlayers,
+    DuplicatePlayer(PlayerIdentifier),
+    /// A team member who isn't playing, or who is in more than one team
+    InvalidTeamMember(PlayerIdentifier),
+    UnknownStartingPlayer(PlayerIdentifier),
+    /// A controller set for a player who isn't playing
+    UnknownPlayer(PlayerIdentifier),
+    UnknownTile(TileId),
+    InvalidRules(RuleSetError),
+    /// The rules are for a set number of players, and the game has the number given
+    WrongPlayerCount(usize),
+}
+
+/// Builds a [Game]; see [Game::builder]
+#[derive(Default)]
+pub struct GameBuilder {
+    players: Vec<(Player, Controller)>,
+    rules: RuleSet,
+    teams: Vec<Team>,
+    abort: Option<AbortCondition>,
+    starting_player: Option<PlayerIdentifier>,
+    seed: Option<Seed>,
+    expansions: Vec<Expansion>,
+    tile_order: Option<Vec<TileId>>,
+}
+
+impl GameBuilder {
+    /// Adds a player, who will take their turn after the players already added. Their moves are
+    /// made by `controller`, e.g. a [crate::bot_strategy::BotStrategy]
+    pub fn with_player<C: PlayerController + 'static>(mut self, player: Player, controller: C) -> Self {
+        self.players.push((player, Controller::Sync(Box::new(controller))));
+        self
+    }
+
+    /// As [GameBuilder::with_player], for a player whose moves arrive asynchronously
+    #[cfg(feature = "tokio")]
+    pub fn with_async_player<C: AsyncPlayerController + 'static>(mut self, player: Player, controller: C) -> Self {
+        self.players.push((player, Controller::Async(Box::new(controller))));
+        self
+    }
+
+    pub fn with_rules(mut self, rules: RuleSet) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Puts the players in a team, whose final score is the sum of theirs. Meeple still count
+    /// towards majorities by colour, so partners can both claim a region
+    pub fn with_team<I: IntoIterator<Item = PlayerIdentifier>>(mut self, players: I) -> Self {
+        self.teams.push(Team::new(players));
+        self
+    }
+
+    /// Has `player` take the first turn instead of the first player added. Turns still go round in
+    /// the order players were added
+    pub fn with_starting_player(mut self, player: PlayerIdentifier) -> Self {
+        self.starting_player = Some(player);
+        self
+    }
+
+    /// Ends the game early, scoring the board as it stands, once `condition` holds
+    pub fn with_early_abort<F: Fn(&Score) -> bool + Send + Sync + 'static>(mut self, condition: F) -> Self {
+        self.abort = Some(Arc::new(condition));
+        self
+    }
+
+    /// Seed for the deck shuffle. A random seed is used if none is given
+    pub fn with_seed(mut self, seed: Seed) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_expansions<E: IntoIterator<Item = Expansion>>(mut self, expansions: E) -> Self {
+        self.expansions = expansions.into_iter().collect();
+        self
+    }
+
+    /// Deals exactly these tiles in this order instead of shuffling a deck, e.g. to set up a
+    /// scenario. Tiles that can't be placed are still dealt, and their player passes
+    pub fn with_tile_order<T: IntoIterator<Item = TileId>>(mut self, tiles: T) -> Self {
+        self.tile_order = Some(tiles.into_iter().collect());
+        self
+    }
+
+    pub fn build(self) -> Result<Game, GameBuildError> {
+        if self.players.is_empty() {
+            return Err(GameBuildError::NoPlayers);
+        }
+
+        self.rules.validate(&self.expansions).map_err(GameBuildError::InvalidRules)?;
+
+        if !self.rules.allows_player_count(self.players.len()) {
+            return Err(GameBuildError::WrongPlayerCount(self.players.len()));
+        }
+
+        let mut players = IndexMap::new();
+        let mut controllers = IndexMap::new();
+
+        for (player, controller) in self.players {
+            let id = player.meeple_color;
+
+            if players.insert(id, player.with_meeple_count(self.rules.meeple_per_player)).is_some() {
+                return Err(GameBuildError::DuplicatePlayer(id));
+            }
+
+            controllers.insert(id, controller);
+        }
+
+        if let Some(starting_player) = self.starting_player {
+            let Some(seat) = players.get_index_of(&starting_player) else {
+                return Err(GameBuildError::UnknownStartingPlayer(starting_player));
+            };
+
+            let (later_players, later_controllers) = (players.split_off(seat), controllers.split_off(seat));
+
+            players = later_players.into_iter().chain(players).collect();
+            controllers = later_controllers.into_iter().chain(controllers).collect();
+        }
+
+        let mut assigned = vec![];
+
+        for member in self.teams.iter().flat_map(|team| &team.players) {
+            if !players.contains_key(member) || assigned.contains(member) {
+                return Err(GameBuildError::InvalidTeamMember(*member));
+            }
+
+            assigned.push(*member);
+        }
+
+        if let Some(unknown) = self.tile_order.iter().flatten().find(|id| find_by_id(**id).is_none()) {
+            return Err(GameBuildError::UnknownTile(*unknown));
+        }
+
+        Ok(Game {
+            players,
+            controllers,
+            rules: self.rules,
+            teams: self.teams,
+            abort: self.abort,
+            seed: self.seed.unwrap_or_else(|| OsRng.gen()),
+            deck: match self.tile_order {
+                Some(tile_order) => DeckSource::Fixed(tile_order),
+                None => DeckSource::Shuffled(self.expansions),
+            },
+        })
+    }
+}
+
+impl Game {
+    pub fn builder() -> GameBuilder {
+        Default::default()
+    }
+
+    pub(crate) fn new(players: IndexMap<PlayerIdentifier, BotPlayer>, seed: Seed, expansions: Vec<Expansion>) -> Self {
+        let (players, controllers) = players
+            .into_iter()
+            .map(|(id, BotPlayer { player, bot })| ((id, player), (id, Controller::Sync(Box::new(bot)))))
+            .unzip();
+
+        Self {
+            players,
+            controllers,
+            rules: RuleSet::standard(),
+            teams: vec![],
+            abort: None,
+            seed,
+            deck: DeckSource::Shuffled(expansions),
+        }
+    }
+
+    /// As [GameBuilder::with_rules], for games set up without the builder. The rules have to be for
+    /// as many players as the game has
+    pub(crate) fn with_rules(mut self, rules: RuleSet) -> Result<Self, GameBuildError> {
+        if !rules.allows_player_count(self.players.len()) {
+            return Err(GameBuildError::WrongPlayerCount(self.players.len()));
+        }
+
+        for player in self.players.values_mut() {
+            *player = player.clone().with_meeple_count(rules.meeple_per_player);
+        }
+
+        self.rules = rules;
+        Ok(self)
+    }
+
+    /// As [GameBuilder::with_early_abort], for games set up without the builder
+    pub(crate) fn aborting_when(mut self, condition: Option<AbortCondition>) -> Self {
+        self.abort = condition;
+        self
+    }
+
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+
+    /// Has `controller` make the player's moves in place of the one they were added with. Once the
+    /// game is under way a controller hands the seat over itself, with [PlayerAction::HandOver]
+    pub fn set_controller<C: PlayerController + 'static>(&mut self, player_id: PlayerIdentifier, controller: C) -> Result<(), GameBuildError> {
+        let seat = self.controllers.get_mut(&player_id).ok_or(GameBuildError::UnknownPlayer(player_id))?;
+        *seat = Controller::Sync(Box::new(controller));
+
+        Ok(())
+    }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    pub fn teams(&self) -> &[Team] {
+        &self.teams
+    }
+
+    /// Turns likely in the game: every tile it will deal, less those whose edges fit nowhere on
+    /// the frontier of the board it starts from. See [crate::analysis::turns_remaining_estimate]
+    /// for a board part of the way through a game
+    pub fn turns_remaining_estimate(&self) -> usize {
+        let board = Board::under_rules(&self.rules).expect("a game's rules are validated before it starts");
+        let deck = self.deck.clone().deal(&self.rules, Rc::new(RefCell::new(GameRng::from_seed(self.seed))), |_| true);
+
+        turns_remaining_estimate(&board, &deck.peek_n(usize::MAX))
+    }
+
+    /// Plays the game through to the end
+    pub fn play(self) -> GameResult {
+        self.play_observed(0, &mut [])
+    }
+
+    /// As [Game::play], notifying each observer of the game's events. `game_index` identifies the
+    /// game in those events when several are played in a batch.
+    ///
+    /// If any player is asynchronous, the game is played on a single threaded runtime started for
+    /// the purpose; use [Game::play_async] instead from inside a runtime
+    pub fn play_observed(self, game_index: usize, observers: &mut [Box<dyn GameObserver>]) -> GameResult {
+        #[cfg(feature = "tokio")]
+        if self.controllers.values().any(|c| matches!(c, Controller::Async(_))) {
+            return tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("should be able to start a runtime")
+                .block_on(self.play_async(game_index, observers));
+        }
+
+        let Game { players, mut controllers, rules, teams, abort, seed, deck } = self;
+
+        let mut run = GameRun::start(players, |id| controllers[&id].bot_name(), rules, seed, deck, game_index, observers);
+        run.abort = abort;
+
+        let _tracked = crash_report::track(game_index, seed, &run.board);
+
+        loop {
+            tell_players(&mut controllers, &mut run);
+
+            let Some((player_id, tile)) = run.draw() else {
+                break;
+            };
+
+            let choose = |controllers: &mut IndexMap<PlayerIdentifier, Controller>, run: &mut GameRun| {
+                run.with_view(player_id, tile, |view, hints| match &mut controllers[&player_id] {
+                    Controller::Sync(controller) => controller.choose_move(view, hints),
+                    #[cfg(feature = "tokio")]
+                    Controller::Async(_) => unreachable!("async players are handled above"),
+                })
+            };
+
+            let mut action = choose(&mut controllers, &mut run);
+            let mut hand_overs = 0;
+
+            while let PlayerAction::HandOver(controller) = action {
+                hand_over(&mut controllers, &mut run, player_id, controller);
+                hand_overs += 1;
+                action = if hand_overs < MAX_HAND_OVERS { choose(&mut controllers, &mut run) } else { PlayerAction::Pass };
+            }
+
+            run.apply(player_id, tile, action);
+        }
+
+        let result = run.finish(teams);
+        tell_players(&mut controllers, &mut run);
+
+        result
+    }
+
+    /// Plays the game through to the end, awaiting moves from asynchronous players as they are made.
+    /// The returned future isn't `Send`, so run it with `block_on` or on a `LocalSet`
+    #[cfg(feature = "tokio")]
+    pub async fn play_async(self, game_index: usize, observers: &mut [Box<dyn GameObserver>]) -> GameResult {
+        let Game { players, mut controllers, rules, teams, abort, seed, deck } = self;
+
+        let mut run = GameRun::start(players, |id| controllers[&id].bot_name(), rules, seed, deck, game_index, observers);
+        run.abort = abort;
+
+        let _tracked = crash_report::track(game_index, seed, &run.board);
+
+        loop {
+            tell_players(&mut controllers, &mut run);
+
+            let Some((player_id, tile)) = run.draw() else {
+                break;
+            };
+
+            let hints = run.hints(player_id, tile);
+            let mut hand_overs = 0;
+
+            let action = loop {
+                let action = if hints.is_empty() {
+                    PlayerAction::Pass
+                } else if let Some(action) = run.obvious_move(player_id, tile, &hints) {
+                    action
+                } else {
+                    let started = Instant::now();
+                    let board = run.board.read().unwrap().clone();
+                    let view = GameView { board: &board, player: &run.players[&player_id], players: &run.players, tile, score: &run.score, phase: GamePhase::of(tile) };
+
+                    let action = match &mut controllers[&player_id] {
+                        Controller::Sync(controller) => controller.choose_move(&view, &hints),
+                        Controller::Async(controller) => controller.choose_move(&view, &hints).await,
+                    };
+
+                    run.record_think_time(player_id, GamePhase::of(tile), started.elapsed());
+                    action
+                };
+
+                match action {
+                    PlayerAction::HandOver(controller) => {
+                        hand_over(&mut controllers, &mut run, player_id, controller);
+                        hand_overs += 1;
+
+                        if hand_overs == MAX_HAND_OVERS {
+                            break PlayerAction::Pass;
+                        }
+                    }
+                    action => break action,
+                }
+            };
+
+            run.apply(player_id, tile, action);
+        }
+
+        let result = run.finish(teams);
+        tell_players(&mut controllers, &mut run);
+
+        result
+    }
+}
+
+/// A game being played; the turn by turn mechanics shared by the sync and async game loops
+pub(crate) struct GameRun<'o> {
+    pub(crate) players: IndexMap<PlayerIdentifier, Player>,
+    player_ids: Vec<PlayerIdentifier>,
+    turn: usize,
+    pub(crate) board: Arc<RwLock<Board>>,
+    deck: Deck,
+    pub(crate) score: Score,
+    rules: RuleSet,
+    seed: Seed,
+    game_index: usize,
+    observers: &'o mut [Box<dyn GameObserver>],
+    started: Instant,
+    /// Players who have resigned, in the order they did
+    resigned: Vec<PlayerIdentifier>,
+    abort: Option<AbortCondition>,
+    aborted: bool,
+    /// Turns taken so far, including passes
+    turn_count: usize,
+    /// How many moves the player whose turn it is was offered, for the turn's event
+    offered: usize,
+    /// Tiles reported as no longer fitting anywhere on the board, and not fitting since
+    dead_tiles: Vec<&'static str>,
+    /// How long each player has spent choosing their moves
+    think_time: IndexMap<PlayerIdentifier, ThinkTime>,
+    /// How long all the players have spent choosing their moves in each phase
+    phase_think_time: IndexMap<GamePhase, ThinkTime>,
+    features: IndexMap<PlayerIdentifier, FeatureCounts>,
+    /// How many meeple each player started with, to check none go missing
+    #[cfg(feature = "debug-validate")]
+    starting_meeple: HashMap<PlayerIdentifier, usize>,
+    /// Events the players' controllers haven't been told of yet, when they are being told
+    player_events: Option<Vec<GameEvent>>,
+}
+
+impl<'o> GameRun<'o> {
+    pub(crate) fn start(
+        players: IndexMap<PlayerIdentifier, Player>,
+        bot_name: impl Fn(PlayerIdentifier) -> Option<&'static str>,
+        rules: RuleSet,
+        seed: Seed,
+        deck: DeckSource,
+        game_index: usize,
+        observers: &'o mut [Box<dyn GameObserver>],
+    ) -> Self {
+        let rng = Rc::new(RefCell::new(GameRng::from_seed(seed)));
+
+        let board = Arc::new(RwLock::new(Board::under_rules(&rules).expect("a game's rules are validated before it starts")));
+
+        let board_clone = Arc::clone(&board);
+
+        let deck = deck.deal(&rules, rng, move |tile| {
+            let board = board_clone.read().unwrap();
+
+            // most tiles that can't be placed are ruled out by their edges alone, which saves
+            // looking for moves through the end of the deck once no more of it can be placed
+            board.fits_frontier(tile) && !board.get_move_hints(tile, false).is_empty()
+        });
+
+        let mut player_events = Some(vec![]);
+
+        notify(observers, &mut player_events, || GameEvent::GameStarted {
+            game_index,
+            seed,
+            players: players.values().map(|p| (p.meeple_color, p.name.clone(), bot_name(p.meeple_color))).collect(),
+            deck: deck.peek_n(usize::MAX),
+            rules: Box::new(rules.clone()),
+        });
+
+        Self {
+            player_events,
+            #[cfg(feature = "debug-validate")]
+            starting_meeple: players.iter().map(|(id, player)| (*id, player.meeple.len())).collect(),
+            player_ids: players.keys().copied().collect(),
+            think_time: players.keys().map(|id| (*id, ThinkTime::default())).collect(),
+            phase_think_time: IndexMap::new(),
+            features: players.keys().map(|id| (*id, FeatureCounts::default())).collect(),
+            // every player starts on the scoreboard, so a player that never scores still has a result
+            score: rules.starting_score(players.values()),
+            players,
+            rules,
+            turn: 0,
+            board,
+            deck,
+            seed,
+            game_index,
+            observers,
+            started: Instant::now(),
+            resigned: vec![],
+            abort: None,
+            aborted: false,
+            turn_count: 0,
+            offered: 0,
+            dead_tiles: vec![],
+        }
+    }
+
+    /// Stops keeping the game's events for the players' controllers, for games whose moves aren't
+    /// made by controllers
+    pub(crate) fn without_player_events(mut self) -> Self {
+        self.player_events = None;
+        self
+    }
+
+    /// The events since this was last called, for passing on to the players' controllers
+    fn take_player_events(&mut self) -> Vec<GameEvent> {
+        self.player_events.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Draws the next tile, returning it with the player whose turn it is. Resigned players' turns
+    /// are skipped, and the game ends when every player has resigned or the abort condition holds
+    pub(crate) fn draw(&mut self) -> Option<(PlayerIdentifier, &'static TileDefinition)> {
+        if self.resigned.len() == self.player_ids.len() {
+            return None;
+        }
+
+        if self.abort.as_ref().is_some_and(|abort| abort(&self.score)) {
+            self.aborted = true;
+            return None;
+        }
+
+        self.find_dead_tiles();
+
+        let tile = self.deck.next()?;
+
+        let player_id = loop {
+            let player_id = self.player_ids[self.turn % self.player_ids.len()];
+            self.turn += 1;
+
+            if !self.resigned.contains(&player_id) {
+                break player_id;
+            }
+        };
+
+        Some((player_id, tile))
+    }
+
+    /// Reports tiles left in the deck that no longer fit anywhere on the board, taking them out of
+    /// the deck if the rules prune them. Skipped when nobody would hear of them
+    fn find_dead_tiles(&mut self) {
+        let prune = self.rules.prune_dead_tiles;
+
+        if !prune && self.observers.is_empty() && self.player_events.is_none() {
+            return;
+        }
+
+        let undealt = self.deck.undealt();
+        let board = self.board.read().unwrap();
+
+        // the river's rules aren't needed, as only tiles after the river are undealt
+        let dead: Vec<_> = undealt.into_iter().filter(|(tile, _)| !board.fits_frontier(tile)).collect();
+        drop(board);
+
+        self.dead_tiles.retain(|name| dead.iter().any(|(tile, _)| tile.name() == *name));
+
+        let game_index = self.game_index;
+
+        for (tile, copies) in dead {
+            if self.dead_tiles.contains(&tile.name()) {
+                continue;
+            }
+
+            self.dead_tiles.push(tile.name());
+
+            if prune {
+                self.deck.remove_all(tile);
+            }
+
+            notify(self.observers, &mut self.player_events, || GameEvent::TileDead { game_index, tile, copies, removed: prune });
+        }
+    }
+
+    fn hints(&mut self, player_id: PlayerIdentifier, tile: &'static TileDefinition) -> Vec<MoveHint> {
+        // meeple placements are only offered to players with a meeple to place
+        let hints = self.board.read().unwrap().get_move_hints(tile, !self.players[&player_id].meeple.is_empty());
+        self.offered = hints.len();
+
+        hints
+    }
+
+    /// Notes how many moves the player was offered, for games whose hints are found elsewhere
+    pub(crate) fn set_offered(&mut self, hint_count: usize) {
+        self.offered = hint_count;
+    }
+
+    /// Asks `choose` for the player's action, passing for them if they have no legal move
+    pub(crate) fn with_view<F>(&mut self, player_id: PlayerIdentifier, tile: &'static TileDefinition, choose: F) -> PlayerAction
+    where
+        F: FnOnce(&GameView, &[MoveHint]) -> PlayerAction,
+    {
+        let hints = self.hints(player_id, tile);
+
+        if hints.is_empty() {
+            return PlayerAction::Pass;
+        }
+
+        if let Some(action) = self.obvious_move(player_id, tile, &hints) {
+            return action;
+        }
+
+        let started = Instant::now();
+
+        let action = {
+            let board = self.board.read().unwrap();
+            let view = GameView { board: &board, player: &self.players[&player_id], players: &self.players, tile, score: &self.score, phase: GamePhase::of(tile) };
+
+            choose(&view, &hints)
+        };
+
+        self.record_think_time(player_id, GamePhase::of(tile), started.elapsed());
+
+        action
+    }
+
+    /// The only placement the tile has, when no meeple is worth placing on it and the rules play
+    /// such moves for the player. Observers are told it was played for them
+    fn obvious_move(&mut self, player_id: PlayerIdentifier, tile: &'static TileDefinition, hints: &[MoveHint]) -> Option<PlayerAction> {
+        if !self.rules.play_obvious_moves || hints.iter().any(|hint| hint.tile_placement != hints[0].tile_placement) {
+            return None;
+        }
+
+        let hint = {
+            let board = self.board.read().unwrap();
+            let context = MeepleContext::new(&board, &self.players[&player_id]);
+
+            if hints.iter().any(|hint| hint.meeple_worth_placing(&context)) {
+                return None;
+            }
+
+            hints.iter().find(|hint| hint.meeple_placement.is_none())?.clone()
+        };
+
+        let (game_index, placement) = (self.game_index, hint.tile_placement.clone());
+        notify(self.observers, &mut self.player_events, || GameEvent::ObviousMovePlayed { game_index, player: player_id, tile, placement });
+
+        Some(PlayerAction::Place(hint))
+    }
+
+    fn record_think_time(&mut self, player_id: PlayerIdentifier, phase: GamePhase, elapsed: Duration) {
+        for think_time in [self.think_time.entry(player_id).or_default(), self.phase_think_time.entry(phase).or_default()] {
+            think_time.total += elapsed;
+            think_time.moves += 1;
+        }
+    }
+
+    /// Whether the board takes the tile and meeple as `hint` places them, with the meeple the
+    /// player would place if they have one left
+    fn can_place(&self, player_id: PlayerIdentifier, tile: &'static TileDefinition, hint: &MoveHint) -> bool {
+        let meeple = hint.meeple_placement.and_then(|region_index| self.players[&player_id].meeple.last().map(|meeple| (region_index, meeple.clone())));
+
+        self.board.read().unwrap().validate_tile_placement(&PlacedTile { tile, placement: hint.tile_placement.clone(), meeple }).is_ok()
+    }
+
+    pub(crate) fn apply(&mut self, player_id: PlayerIdentifier, tile: &'static TileDefinition, action: PlayerAction) {
+        let game_index = self.game_index;
+        let phase = GamePhase::of(tile);
+        let hint_count = std::mem::take(&mut self.offered);
+        self.turn_count += 1;
+
+        // a controller needn't play one of the hints it was offered, so a placement or recall the
+        // board won't take is passed, as a move that can't be played is
+        let action = match action {
+            PlayerAction::Place(hint) if !self.can_place(player_id, tile, &hint) => PlayerAction::Pass,
+            PlayerAction::RecallMeeple { tile_placement, .. }
+                if self.board.read().unwrap().validate_tile_placement(&PlacedTile { tile, placement: tile_placement.clone(), meeple: None }).is_err() =>
+            {
+                PlayerAction::Pass
+            }
+            action => action,
+        };
+
+        let (tile_placement, meeple_placement, recall) = match action {
+            PlayerAction::Place(hint) => (hint.tile_placement, hint.meeple_placement, None),
+            PlayerAction::RecallMeeple { tile_placement, coordinate, region_index } => (tile_placement, None, Some((coordinate, region_index))),
+            PlayerAction::HandOver(_) => unreachable!("hand overs are settled before the turn is played"),
+            PlayerAction::Pass | PlayerAction::Resign => {
+                crash_report::record_turn(player_id, tile.name(), None);
+
+                notify(self.observers, &mut self.player_events, || GameEvent::TurnCompleted {
+                    game_index,
+                    player: player_id,
+                    tile,
+                    phase,
+                    placement: None,
+                    score_delta: Score::new(),
+                    hint_count,
+                });
+
+                // the resigning player's meeple stay where they are, and still score at the end
+                if let PlayerAction::Resign = action {
+                    notify(self.observers, &mut self.player_events, || GameEvent::PlayerResigned { game_index, player: player_id });
+                    self.resigned.push(player_id);
+                }
+
+                return;
+            }
+        };
+
+        let player = self.players.get_mut(&player_id).expect("should exist");
+
+        let placed_tile = PlacedTile {
+            tile,
+            placement: tile_placement,
+            // only take a meeple from the player's supply when the move places one
+            meeple: meeple_placement.and_then(|region_index| player.meeple.pop().map(|meeple| (region_index, meeple))),
+        };
+
+        let placement = (placed_tile.placement.clone(), placed_tile.meeple.as_ref().map(|(region_index, _)| *region_index));
+        crash_report::record_turn(player_id, tile.name(), Some(&placement));
+
+        let TilePlacementSuccess { liberated_meeple, score_delta, completed_features, .. } = self.board.write().unwrap().place_tile(placed_tile).unwrap();
+        let score_delta = self.rules.adjust(score_delta);
+
+        for feature in completed_features {
+            for player in &feature.scored_by {
+                self.features.entry(*player).or_default().count_completed(&feature.region_type);
+            }
+
+            notify(self.observers, &mut self.player_events, || GameEvent::RegionCompleted {
+                game_index,
+                region_type: feature.region_type.clone(),
+                tiles: feature.tiles.clone(),
+                scorer: feature.scored_by.clone(),
+                points: feature.points,
+                meeple: feature.strength.clone(),
+                margin: feature.margin(),
+            });
+        }
+
+        notify(self.observers, &mut self.player_events, || GameEvent::TurnCompleted {
+            game_index,
+            player: player_id,
+            tile,
+            phase,
+            placement: Some(placement),
+            score_delta: score_delta.clone(),
+            hint_count,
+        });
+
+        self.score += score_delta;
+
+        for LiberatedMeeple { meeple, .. } in liberated_meeple {
+            self.players.get_mut(&meeple.color).expect("should exist").meeple.push(meeple);
+        }
+
+        // the meeple is taken back after the tile is placed, so one the tile just scored is gone
+        if let Some((coordinate, region_index)) = recall.filter(|_| self.rules.meeple_recall) {
+            if let Some(meeple) = self.board.write().unwrap().recall_meeple(&coordinate, region_index, player_id) {
+                self.players.get_mut(&player_id).expect("should exist").meeple.push(meeple);
+
+                notify(self.observers, &mut self.player_events, || GameEvent::MeepleRecalled { game_index, player: player_id, coordinate, region_index });
+            }
+        }
+
+        #[cfg(feature = "debug-validate")]
+        self.board.read().unwrap().assert_meeple_supply(self.players.values(), &self.starting_meeple);
+    }
+
+    pub(crate) fn finish(&mut self, teams: Vec<Team>) -> GameResult {
+        let board = self.board.read().unwrap();
+
+        self.score += self.rules.adjust(board.calculate_board_score());
+
+        for field in board.get_connected_regions().into_iter().filter(|region| region.region_type == RegionType::Field) {
+            for player in field.majority_meeple_player_ids(&board) {
+                self.features.entry(player).or_default().farms += 1;
+            }
+        }
+
+        let game_index = self.game_index;
+        let score = &self.score;
+
+        notify(self.observers, &mut self.player_events, || GameEvent::GameCompleted { game_index, score: score.clone() });
+
+        GameResult {
+            seed: self.seed,
+            score: self.score.clone(),
+            duration: self.started.elapsed(),
+            placed_tile_count: board.placed_tile_count(),
+            teams,
+            resigned: self.resigned.clone(),
+            aborted: self.aborted,
+            turn_count: self.turn_count,
+            think_time: self.think_time.clone(),
+            phase_think_time: self.phase_think_time.clone(),
+            features: self.features.clone(),
+            tie_break: self.rules.tie_break,
+        }
+    }
+}
+
+/// Builds the event only when someone is listening, so unobserved games don't pay for it
+fn notify(observers: &mut [Box<dyn GameObserver>], player_events: &mut Option<Vec<GameEvent>>, event: impl FnOnce() -> GameEvent) {
+    if observers.is_empty() && player_events.is_none() {
+        return;
+    }
+
+    let event = event();
+
+    for observer in observers.iter_mut() {
+        observer.notify(&event);
+    }
+
+    if let Some(player_events) = player_events {
+        player_events.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::bot_strategy::{Bot, BotStrategy, EnsembleBot, FillTheGridBot, LazyBot, MyopicBot};
+    use crate::player::{MeepleColor, RegionIndex, MEEPLE_COUNT};
+    use crate::replay::{Replay, ReplayHandover, ReplayRecorder};
+    use crate::rules::StartingLayout;
+    use crate::tile::{BoardCoordinate, RegionType, Rotation, TilePlacement};
+    use crate::tile_definitions::{CLOISTER_IN_FIELD, SIDE_CITY, STRAIGHT_ROAD};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn should_build_and_play_a_game() {
+        let result = Game::builder()
+            .with_player(Player::red().with_name("Alice"), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green().with_name("Bob"), BotStrategy::Lazy(LazyBot))
+            .with_rules(RuleSet::standard())
+            .with_seed([3; 32])
+            .with_expansions([Expansion::River])
+            .build()
+            .expect("game should be valid")
+            .play();
+
+        assert_eq!(result.seed, [3; 32]);
+        assert!(result.placed_tile_count > 0);
+        assert_eq!(result.score.iter().count(), 2);
+        assert_eq!(result.features.keys().copied().collect::<Vec<_>>(), vec![MeepleColor::Red, MeepleColor::Green]);
+    }
+
+    #[test]
+    fn should_estimate_the_turns_a_game_will_take() {
+        let game = |builder: GameBuilder| builder.with_player(Player::red(), BotStrategy::Lazy(LazyBot)).with_seed([3; 32]).build().unwrap();
+
+        let fixed = game(Game::builder().with_tile_order([SIDE_CITY.id(), STRAIGHT_ROAD.id(), SIDE_CITY.id()]));
+        assert_eq!(fixed.turns_remaining_estimate(), 3);
+
+        let shuffled = game(Game::builder());
+        let result = game(Game::builder()).play();
+        assert!(shuffled.turns_remaining_estimate() >= result.placed_tile_count);
+    }
+
+    #[test]
+    fn should_reject_invalid_player_lists() {
+        assert!(matches!(Game::builder().build(), Err(GameBuildError::NoPlayers)));
+
+        let duplicate = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .build();
+
+        assert!(matches!(duplicate, Err(GameBuildError::DuplicatePlayer(MeepleColor::Red))));
+
+        let crowded = Game::builder()
+            .with_rules(RuleSet::wtcoc())
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::blue(), BotStrategy::Lazy(LazyBot))
+            .build();
+
+        assert!(matches!(crowded, Err(GameBuildError::WrongPlayerCount(3))));
+    }
+
+    #[test]
+    fn should_give_players_the_meeple_the_rules_allow() {
+        let game = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_rules(RuleSet::standard().with_meeple_per_player(3))
+            .build()
+            .unwrap();
+
+        assert_eq!(game.players[&MeepleColor::Red].meeple.len(), 3);
+    }
+
+    #[test]
+    fn should_apply_handicaps_to_the_final_score() {
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_rules(RuleSet::standard().with_starting_score(MeepleColor::Red, 50).with_score_multiplier(MeepleColor::Green, 0.0))
+            .with_seed([3; 32])
+            .build()
+            .unwrap()
+            .play();
+
+        let points = |color| result.score.iter().find(|(id, _)| **id == color).map(|(_, points)| *points);
+
+        assert!(points(MeepleColor::Red).unwrap() >= 50);
+        assert_eq!(points(MeepleColor::Green), Some(0));
+    }
+
+    #[test]
+    fn should_reject_teams_with_players_not_in_the_game() {
+        let game = |builder: GameBuilder| {
+            builder
+                .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+                .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+                .build()
+        };
+
+        let stranger = game(Game::builder().with_team([MeepleColor::Red, MeepleColor::Blue]));
+        let twice = game(Game::builder().with_team([MeepleColor::Red]).with_team([MeepleColor::Red, MeepleColor::Green]));
+
+        assert!(matches!(stranger, Err(GameBuildError::InvalidTeamMember(MeepleColor::Blue))));
+        assert!(matches!(twice, Err(GameBuildError::InvalidTeamMember(MeepleColor::Red))));
+    }
+
+    #[test]
+    fn should_share_the_win_between_team_members() {
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::blue(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::yellow(), BotStrategy::Lazy(LazyBot))
+            .with_team([MeepleColor::Red, MeepleColor::Blue])
+            .with_team([MeepleColor::Green, MeepleColor::Yellow])
+            .with_seed([5; 32])
+            .build()
+            .unwrap()
+            .play();
+
+        assert_eq!(result.teams.len(), 2);
+        // winners come in whole teams
+        assert!(result.winners().len() == 2 || result.winners().len() == 4);
+    }
+
+    struct Resigner;
+
+    impl PlayerController for Resigner {
+        fn choose_move(&mut self, _view: &GameView, _hints: &[MoveHint]) -> PlayerAction {
+            PlayerAction::Resign
+        }
+    }
+
+    #[test]
+    fn should_skip_the_turns_of_a_resigned_player() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), Resigner)
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_seed([3; 32])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+
+        assert_eq!(result.resigned, vec![MeepleColor::Red]);
+        assert_eq!(replay.turns.iter().filter(|turn| turn.player == MeepleColor::Red).count(), 1);
+        assert!(result.placed_tile_count > 1);
+        assert_eq!(result.winners(), vec![MeepleColor::Green]);
+    }
+
+    /// Leaves the game to a bot as soon as it's asked for a move
+    struct LeavesItToTheBot;
+
+    impl PlayerController for LeavesItToTheBot {
+        fn choose_move(&mut self, _view: &GameView, _hints: &[MoveHint]) -> PlayerAction {
+            PlayerAction::HandOver(Box::new(BotStrategy::Lazy(LazyBot)))
+        }
+    }
+
+    #[test]
+    fn should_hand_a_seat_to_another_controller_between_turns() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), LeavesItToTheBot)
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_seed([3; 32])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+
+        // the bot played the turn it was handed, and every one after
+        assert_eq!(replay.handovers, [ReplayHandover { turn: 0, player: MeepleColor::Red, bot: Some("lazy".to_string()) }]);
+        assert!(replay.turns.iter().filter(|turn| turn.player == MeepleColor::Red).all(|turn| turn.placement.is_some()));
+        assert!(result.placed_tile_count > 2);
+        assert!(replay.verify().is_ok());
+    }
+
+    /// Never makes a move, handing the seat on to another of itself instead
+    struct HandsItOn;
+
+    impl PlayerController for HandsItOn {
+        fn choose_move(&mut self, _view: &GameView, _hints: &[MoveHint]) -> PlayerAction {
+            PlayerAction::HandOver(Box::new(HandsItOn))
+        }
+    }
+
+    #[test]
+    fn should_pass_the_turn_of_a_seat_handed_over_endlessly() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), HandsItOn)
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_seed([3; 32])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+        let red_turns: Vec<_> = replay.turns.iter().filter(|turn| turn.player == MeepleColor::Red).collect();
+
+        assert!(!red_turns.is_empty() && red_turns.iter().all(|turn| turn.placement.is_none()));
+        assert!(result.placed_tile_count > 2);
+    }
+
+    #[test]
+    fn should_set_a_players_controller_before_the_game() {
+        let mut game = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_seed([3; 32])
+            .build()
+            .unwrap();
+
+        assert_eq!(game.set_controller(MeepleColor::Blue, Resigner), Err(GameBuildError::UnknownPlayer(MeepleColor::Blue)));
+        assert_eq!(game.set_controller(MeepleColor::Red, Resigner), Ok(()));
+
+        assert_eq!(game.play().resigned, vec![MeepleColor::Red]);
+    }
+
+    #[test]
+    fn should_stop_when_the_abort_condition_holds() {
+        let checks = AtomicUsize::new(0);
+
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_early_abort(move |_| checks.fetch_add(1, Ordering::Relaxed) == 10)
+            .with_seed([3; 32])
+            .build()
+            .unwrap()
+            .play();
+
+        assert!(result.aborted);
+        assert_eq!(result.placed_tile_count, 10);
+    }
+
+    #[test]
+    fn should_start_with_the_chosen_player() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::blue(), BotStrategy::Lazy(LazyBot))
+            .with_starting_player(MeepleColor::Blue)
+            .with_seed([3; 32])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let turns = recorder.borrow().replays()[0].turns.clone();
+        let order: Vec<_> = turns.iter().take(3).map(|turn| turn.player).collect();
+
+        assert_eq!(order, vec![MeepleColor::Blue, MeepleColor::Red, MeepleColor::Green]);
+
+        let unknown = Game::builder().with_player(Player::red(), BotStrategy::Lazy(LazyBot)).with_starting_player(MeepleColor::Green).build();
+
+        assert!(matches!(unknown, Err(GameBuildError::UnknownStartingPlayer(MeepleColor::Green))));
+    }
+
+    #[test]
+    fn should_deal_tiles_in_the_given_order() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+        let order = [SIDE_CITY.id(), STRAIGHT_ROAD.id(), CLOISTER_IN_FIELD.id()];
+
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_tile_order(order)
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let tiles: Vec<_> = recorder.borrow().replays()[0].turns.iter().map(|turn| turn.tile.clone()).collect();
+
+        assert_eq!(tiles, vec![SIDE_CITY.name(), STRAIGHT_ROAD.name(), CLOISTER_IN_FIELD.name()]);
+        assert_eq!(result.placed_tile_count, 3);
+
+        let unknown = Game::builder().with_player(Player::red(), BotStrategy::Lazy(LazyBot)).with_tile_order([u8::MAX]).build();
+
+        assert!(matches!(unknown, Err(GameBuildError::UnknownTile(u8::MAX))));
+    }
+
+    #[test]
+    fn should_play_the_same_game_from_the_same_seed() {
+        let play = || {
+            let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+            Game::builder()
+                .with_player(Player::red(), BotStrategy::Myopic(MyopicBot))
+                .with_player(Player::green(), BotStrategy::FillTheGrid(FillTheGridBot::new(GameRng::from_seed([3; 32]))))
+                .with_seed([5; 32])
+                .build()
+                .unwrap()
+                .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+            let turns = recorder.borrow().replays()[0].turns.clone();
+            turns
+        };
+
+        assert_eq!(play(), play());
+    }
+
+    /// Plays the first move matching its test
+    struct Picks(fn(&MoveHint) -> bool);
+
+    impl PlayerController for Picks {
+        fn choose_move(&mut self, _view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+            hints.iter().find(|hint| self.0(hint)).cloned().map_or(PlayerAction::Pass, PlayerAction::Place)
+        }
+    }
+
+    #[derive(Default)]
+    struct Events(Vec<GameEvent>);
+
+    impl GameObserver for Events {
+        fn notify(&mut self, event: &GameEvent) {
+            self.0.push(event.clone());
+        }
+    }
+
+    /// Places its tiles without meeple, counting the farmers its opponents place
+    struct FarmWatcher {
+        player: PlayerIdentifier,
+        events: Rc<RefCell<Vec<GameEvent>>>,
+        opponent_farmers: Rc<Cell<usize>>,
+    }
+
+    impl Bot for FarmWatcher {
+        fn select_hint(&mut self, _board: &Board, _player: &Player, hints: &[MoveHint]) -> Option<MoveHint> {
+            hints.iter().find(|hint| hint.meeple_placement.is_none()).cloned()
+        }
+
+        fn observe(&mut self, event: &GameEvent) {
+            self.events.borrow_mut().push(event.clone());
+
+            if let GameEvent::TurnCompleted { player, tile, placement: Some((_, Some(region_index))), .. } = event {
+                if *player != self.player && tile.regions[**region_index].region_type() == RegionType::Field {
+                    self.opponent_farmers.set(self.opponent_farmers.get() + 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn should_let_bots_observe_the_game_as_it_is_played() {
+        let events = Rc::new(RefCell::new(vec![]));
+        let opponent_farmers = Rc::new(Cell::new(0));
+
+        let watcher = FarmWatcher { player: MeepleColor::Red, events: Rc::clone(&events), opponent_farmers: Rc::clone(&opponent_farmers) };
+
+        Game::builder()
+            .with_player(Player::red(), EnsembleBot::new().with_member(Box::new(watcher), 1.0))
+            .with_player(Player::green(), Picks(|hint| hint.meeple_placement == Some(RegionIndex::new(0))))
+            .with_tile_order([SIDE_CITY.id(), SIDE_CITY.id(), SIDE_CITY.id()])
+            .build()
+            .unwrap()
+            .play();
+
+        let events = events.borrow();
+
+        assert!(matches!(events.first(), Some(GameEvent::GameStarted { .. })));
+        assert!(matches!(events.last(), Some(GameEvent::GameCompleted { .. })));
+        assert_eq!(events.iter().filter(|event| matches!(event, GameEvent::TurnCompleted { .. })).count(), 3);
+        assert_eq!(opponent_farmers.get(), 1);
+    }
+
+    /// Takes back its meeple whenever it has one on the board, and places one otherwise
+    struct Recaller;
+
+    impl PlayerController for Recaller {
+        fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+            let deployed = view.board.placed_tiles.values().find_map(|tile| tile.meeple.as_ref().map(|(region_index, _)| (tile.placement.coordinate, *region_index)));
+
+            match deployed {
+                Some((coordinate, region_index)) => PlayerAction::RecallMeeple { tile_placement: hints[0].tile_placement.clone(), coordinate, region_index },
+                None => PlayerAction::Place(hints.iter().find(|hint| hint.meeple_placement.is_some()).cloned().unwrap()),
+            }
+        }
+    }
+
+    #[test]
+    fn should_take_back_meeple_only_when_the_rules_allow() {
+        let play = |rules: RuleSet| {
+            let events = Rc::new(RefCell::new(Events::default()));
+            let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+            Game::builder()
+                .with_player(Player::red(), Recaller)
+                .with_rules(rules)
+                .with_tile_order([STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id()])
+                .build()
+                .unwrap()
+                .play_observed(0, &mut [Box::new(Rc::clone(&events)), Box::new(Rc::clone(&recorder))]);
+
+            let recalls = events.borrow().0.iter().filter(|event| matches!(event, GameEvent::MeepleRecalled { .. })).count();
+            let replay = recorder.borrow().replays()[0].clone();
+
+            (recalls, replay)
+        };
+
+        let (recalls, replay) = play(RuleSet::standard().with_meeple_recall(true));
+
+        assert_eq!(recalls, 1);
+        assert!(replay.turns[1].placement.as_ref().unwrap().recalled.is_some());
+        assert!(replay.verify().is_ok());
+        assert_eq!(Replay::from_binary(&replay.to_binary().unwrap()).unwrap(), replay);
+        // the meeple taken back on the second turn is placed again on the third
+        assert_eq!(replay.state_at(3).unwrap().players[&MeepleColor::Red].meeple.len(), MEEPLE_COUNT - 1);
+
+        let (recalls, replay) = play(RuleSet::standard());
+
+        assert_eq!(recalls, 0);
+        assert!(replay.turns.iter().all(|turn| turn.placement.as_ref().unwrap().recalled.is_none()));
+        assert_eq!(replay.state_at(3).unwrap().players[&MeepleColor::Red].meeple.len(), MEEPLE_COUNT - 1);
+    }
+
+    /// Takes back a meeple that isn't there, placing the tile where it can't go
+    struct OffTheBoard;
+
+    impl PlayerController for OffTheBoard {
+        fn choose_move(&mut self, _view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+            let mut tile_placement = hints[0].tile_placement.clone();
+            tile_placement.coordinate = BoardCoordinate::new(40, 40);
+
+            PlayerAction::RecallMeeple { tile_placement, coordinate: BoardCoordinate::new(0, 0), region_index: RegionIndex::new(0) }
+        }
+    }
+
+    #[test]
+    fn should_pass_a_recall_whose_tile_cant_be_placed() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), OffTheBoard)
+            .with_rules(RuleSet::standard().with_meeple_recall(true))
+            .with_tile_order([STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id()])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+
+        assert_eq!(result.placed_tile_count, 2);
+        assert!(replay.turns[1].placement.is_none());
+        assert!(replay.verify().is_ok());
+    }
+
+    /// Places the tile where it can't go, as though it were one of the hints
+    struct MadeUpPlacement;
+
+    impl PlayerController for MadeUpPlacement {
+        fn choose_move(&mut self, _view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+            let mut hint = hints[0].clone();
+            hint.tile_placement.coordinate = BoardCoordinate::new(40, 40);
+
+            PlayerAction::Place(hint)
+        }
+    }
+
+    #[test]
+    fn should_pass_a_placement_the_board_wont_take() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), MadeUpPlacement)
+            .with_tile_order([STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id()])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+
+        assert_eq!(result.placed_tile_count, 2);
+        assert!(replay.turns[1].placement.is_none());
+        assert!(replay.verify().is_ok());
+    }
+
+    #[test]
+    fn should_play_on_from_the_starting_layout() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_rules(RuleSet::standard().with_starting_layout(StartingLayout::wheel()))
+            .with_tile_order([STRAIGHT_ROAD.id()])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        assert_eq!(result.placed_tile_count, StartingLayout::wheel().tiles().len() + 1);
+
+        // the drawn tile goes beside the wheel rather than where a first tile would
+        let replay = recorder.borrow().replays()[0].clone();
+        let placement = replay.turns[0].placement.as_ref().unwrap();
+        assert!(StartingLayout::wheel().tiles().iter().all(|tile| tile.placement.coordinate != BoardCoordinate::new(placement.x, placement.y)));
+    }
+
+    #[test]
+    fn should_report_tiles_that_no_longer_fit_and_prune_them_when_the_rules_say_to() {
+        let play = |rules: RuleSet| {
+            let events = Rc::new(RefCell::new(Events::default()));
+
+            // the first tile fills the board, leaving nowhere for the rest to go
+            let result = Game::builder()
+                .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+                .with_rules(rules.with_board_limit(1, 1))
+                .with_tile_order([SIDE_CITY.id(), STRAIGHT_ROAD.id(), STRAIGHT_ROAD.id(), CLOISTER_IN_FIELD.id()])
+                .build()
+                .unwrap()
+                .play_observed(0, &mut [Box::new(Rc::clone(&events))]);
+
+            let dead: Vec<_> = events
+                .borrow()
+                .0
+                .iter()
+                .filter_map(|event| match event {
+                    GameEvent::TileDead { tile, copies, removed, .. } => Some((tile.name(), *copies, *removed)),
+                    _ => None,
+                })
+                .collect();
+
+            (dead, result.turn_count)
+        };
+
+        let (dead, turn_count) = play(RuleSet::standard());
+
+        // each tile is reported once, though it stays dead for the rest of the game
+        assert_eq!(dead, vec![(STRAIGHT_ROAD.name(), 2, false), (CLOISTER_IN_FIELD.name(), 1, false)]);
+        assert_eq!(turn_count, 4);
+
+        let (dead, turn_count) = play(RuleSet::standard().with_dead_tile_pruning(true));
+
+        assert_eq!(dead, vec![(STRAIGHT_ROAD.name(), 2, true), (CLOISTER_IN_FIELD.name(), 1, true)]);
+        assert_eq!(turn_count, 1);
+    }
+
+    #[test]
+    fn should_play_moves_with_nothing_to_decide_when_the_rules_say_to() {
+        let play = |rules: RuleSet| {
+            let events = Rc::new(RefCell::new(Events::default()));
+
+            Game::builder()
+                .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+                .with_rules(rules)
+                .with_tile_order([CLOISTER_IN_FIELD.id(), STRAIGHT_ROAD.id()])
+                .build()
+                .unwrap()
+                .play_observed(0, &mut [Box::new(Rc::clone(&events))]);
+
+            let played: Vec<_> = events
+                .borrow()
+                .0
+                .iter()
+                .filter_map(|event| match event {
+                    GameEvent::ObviousMovePlayed { tile, placement, .. } => Some((tile.name(), placement.clone())),
+                    _ => None,
+                })
+                .collect();
+
+            played
+        };
+
+        assert_eq!(play(RuleSet::standard()), vec![]);
+
+        // a cloister looks the same turned any way, so goes only in the middle of the empty board,
+        // where a meeple on it would score less than it's worth kept. The road can go either side
+        let played = play(RuleSet::standard().with_obvious_moves_played(true));
+        assert_eq!(played, vec![(CLOISTER_IN_FIELD.name(), TilePlacement { coordinate: BoardCoordinate::new(0, 0), rotation: Rotation::R0 })]);
+    }
+
+    #[test]
+    fn should_announce_completed_regions_before_the_turn() {
+        let events = Rc::new(RefCell::new(Events::default()));
+
+        // Alice claims a city that Bob closes
+        Game::builder()
+            .with_player(Player::red(), Picks(|hint| hint.tile_placement.rotation == Rotation::R0 && hint.meeple_placement == Some(RegionIndex::new(1))))
+            .with_player(Player::green(), Picks(|hint| hint.tile_placement.coordinate == BoardCoordinate::new(0, 1) && hint.tile_placement.rotation == Rotation::R180))
+            .with_tile_order([SIDE_CITY.id(), SIDE_CITY.id()])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&events))]);
+
+        let events = &events.borrow().0;
+        let completed = events.iter().position(|event| matches!(event, GameEvent::RegionCompleted { .. })).unwrap();
+
+        let GameEvent::RegionCompleted { region_type, tiles, scorer, points, .. } = &events[completed] else {
+            unreachable!();
+        };
+
+        assert_eq!(region_type, &RegionType::City);
+        assert_eq!(tiles, &vec![BoardCoordinate::new(0, 0), BoardCoordinate::new(0, 1)]);
+        assert_eq!(scorer, &vec![MeepleColor::Red]);
+        assert_eq!(*points, 4);
+        assert!(matches!(events[completed + 1], GameEvent::TurnCompleted { player: MeepleColor::Green, .. }));
+        assert_eq!(events.iter().filter(|event| matches!(event, GameEvent::RegionCompleted { .. })).count(), 1);
+    }
+
+    /// Plays the first move it is offered, noting the phase of each turn it is asked to play
+    struct PhaseWatcher(Rc<RefCell<Vec<GamePhase>>>);
+
+    impl PlayerController for PhaseWatcher {
+        fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+            self.0.borrow_mut().push(view.phase);
+            PlayerAction::Place(hints[0].clone())
+        }
+    }
+
+    #[test]
+    fn should_tell_controllers_and_observers_whether_the_river_is_being_laid() {
+        let phases = Rc::new(RefCell::new(vec![]));
+        let events = Rc::new(RefCell::new(Events::default()));
+
+        let result = Game::builder()
+            .with_player(Player::red(), PhaseWatcher(Rc::clone(&phases)))
+            .with_player(Player::green(), PhaseWatcher(Rc::clone(&phases)))
+            .with_seed([3; 32])
+            .with_expansions([Expansion::River])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&events))]);
+
+        let events = &events.borrow().0;
+
+        let Some(GameEvent::GameStarted { deck, .. }) = events.first() else {
+            unreachable!();
+        };
+
+        let river_tiles = deck.iter().filter(|tile| GamePhase::of(tile) == GamePhase::River).count();
+        let turns: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                GameEvent::TurnCompleted { phase, .. } => Some(*phase),
+                _ => None,
+            })
+            .collect();
+
+        assert!(river_tiles > 0);
+        assert!(turns[..river_tiles].iter().all(|phase| *phase == GamePhase::River));
+        assert!(turns[river_tiles..].iter().all(|phase| *phase == GamePhase::Main));
+
+        let phases = phases.borrow();
+
+        assert_eq!(phases.iter().filter(|phase| **phase == GamePhase::River).count(), river_tiles);
+        assert!(phases.windows(2).all(|pair| pair != [GamePhase::Main, GamePhase::River]));
+        assert_eq!(result.phase_think_time[&GamePhase::River].moves, river_tiles);
+        assert_eq!(result.phase_think_time[&GamePhase::Main].moves, phases.len() - river_tiles);
+    }
+}
@@ -0,0 +1,189 @@
+use crate::board::Board;
+use crate::connected_regions::ConnectedRegionId;
+use crate::locale::Language;
+use crate::ownership::{region_cells, unrotated};
+use crate::player::PlayerIdentifier;
+use crate::tile::{PlacedTile, TILE_WIDTH};
+use serde::Serialize;
+
+/// A point on the board, one unit to a cell of the tile drawing. `y` points north, so tiles
+/// further down the rendered board have lower `y`
+type Position = [i32; 2];
+
+type Ring = Vec<Position>;
+
+#[derive(Debug, Serialize)]
+struct FeatureCollection {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    features: Vec<Feature>,
+}
+
+#[derive(Debug, Serialize)]
+struct Feature {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    geometry: Geometry,
+    properties: Properties,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "coordinates")]
+enum Geometry {
+    Polygon(Vec<Ring>),
+    MultiPolygon(Vec<Vec<Ring>>),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Properties {
+    Tile {
+        tile: &'static str,
+        x: i8,
+        y: i8,
+        rotations: u8,
+    },
+    Region {
+        id: ConnectedRegionId,
+        #[serde(rename = "type")]
+        region_type: &'static str,
+        owners: Vec<PlayerIdentifier>,
+        closed: bool,
+        score: u32,
+    },
+}
+
+impl Board {
+    /// The board as a GeoJSON feature collection, for drawing in mapping and plotting tools. Each
+    /// tile is a square polygon, and each connected region the cells of the tile drawings it
+    /// covers, with its type, owners and score
+    pub fn render_geojson(&self) -> String {
+        let mut tiles: Vec<_> = self.placed_tiles().map(|(_, tile)| tile).collect();
+        tiles.sort_by_key(|tile| (tile.placement.coordinate.y, tile.placement.coordinate.x));
+
+        let mut features: Vec<_> = tiles
+            .iter()
+            .map(|tile| Feature {
+                kind: "Feature",
+                geometry: Geometry::Polygon(vec![rectangle(tile, 0, 0, TILE_WIDTH, TILE_WIDTH)]),
+                properties: Properties::Tile {
+                    tile: tile.tile.name,
+                    x: tile.placement.coordinate.x,
+                    y: tile.placement.coordinate.y,
+                    rotations: tile.placement.rotation.quarter_turns(),
+                },
+            })
+            .collect();
+
+        let mut regions = self.get_connected_regions();
+        regions.sort_by_key(|region| region.id);
+
+        for region in regions {
+            let polygons = region
+                .tile_regions
+                .iter()
+                .filter_map(|tile_region| self.get_tile_at_coordinate(&tile_region.tile_position).map(|tile| (tile, tile_region.region_index)))
+                .flat_map(|(tile, region_index)| {
+                    let cells = region_cells(tile.tile);
+
+                    cell_runs(|row, column| {
+                        let (row, column) = unrotated(row, column, tile.placement.rotation);
+                        cells[row][column] == Some(region_index)
+                    })
+                    .into_iter()
+                    .map(move |(row, start, end)| vec![rectangle(tile, start, row, end - start, 1)])
+                })
+                .collect();
+
+            features.push(Feature {
+                kind: "Feature",
+                geometry: Geometry::MultiPolygon(polygons),
+                properties: Properties::Region {
+                    id: region.id,
+                    region_type: Language::English.region_name(region.region_type()),
+                    owners: region.majority_meeple_player_ids(self),
+                    closed: region.is_closed(),
+                    score: region.score(self),
+                },
+            });
+        }
+
+        serde_json::to_string_pretty(&FeatureCollection { kind: "FeatureCollection", features }).expect("board should serialize")
+    }
+}
+
+/// Runs of neighbouring cells along each row of a tile that are in the region, as (row, first
+/// column, column after the last), so a region isn't made of a square for every cell
+fn cell_runs(in_region: impl Fn(usize, usize) -> bool) -> Vec<(usize, usize, usize)> {
+    let mut runs = vec![];
+
+    for row in 0..TILE_WIDTH {
+        let mut start = None;
+
+        for column in 0..=TILE_WIDTH {
+            match (start, column < TILE_WIDTH && in_region(row, column)) {
+                (None, true) => start = Some(column),
+                (Some(first), false) => {
+                    runs.push((row, first, column));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    runs
+}
+
+/// A closed ring around `width` by `height` cells of the tile, from the cell at `column`, `row`
+fn rectangle(tile: &PlacedTile, column: usize, row: usize, width: usize, height: usize) -> Ring {
+    let left = tile.placement.coordinate.x as i32 * TILE_WIDTH as i32 + column as i32;
+    let top = -(tile.placement.coordinate.y as i32 * TILE_WIDTH as i32 + row as i32);
+    let (right, bottom) = (left + width as i32, top - height as i32);
+
+    // anticlockwise, as GeoJSON wants outer rings
+    vec![[left, top], [left, bottom], [right, bottom], [right, top], [left, top]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Rotation;
+    use crate::player::{Meeple, MeepleColor, RegionIndex};
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+    use serde_json::Value;
+
+    #[test]
+    fn should_export_tiles_and_region_footprints() {
+        let meeple = (RegionIndex::new(1), Meeple::new(MeepleColor::Red));
+        let board = Board::new_with_tiles([
+            PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, meeple),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+        ])
+        .unwrap();
+
+        let geojson: Value = serde_json::from_str(&board.render_geojson()).unwrap();
+        assert_eq!(geojson["type"], "FeatureCollection");
+
+        let features = geojson["features"].as_array().unwrap();
+        let tiles: Vec<_> = features.iter().filter(|feature| feature["properties"]["kind"] == "tile").collect();
+
+        assert_eq!(tiles.len(), 2);
+        assert_eq!(tiles[1]["geometry"]["type"], "Polygon");
+        assert_eq!(tiles[1]["geometry"]["coordinates"][0][0], serde_json::json!([7, 0]));
+        assert_eq!(tiles[1]["geometry"]["coordinates"][0][2], serde_json::json!([14, -7]));
+
+        let city = features.iter().find(|feature| feature["properties"]["type"] == "city").unwrap();
+        assert_eq!(city["geometry"]["type"], "MultiPolygon");
+        assert_eq!(city["properties"]["owners"], serde_json::json!(["Red"]));
+
+        // the city is drawn along the south of the tile, so below its middle
+        assert!(city["geometry"]["coordinates"].as_array().unwrap().iter().all(|polygon| polygon[0][0][1].as_i64().unwrap() < -3));
+    }
+
+    #[test]
+    fn should_join_neighbouring_cells_into_runs() {
+        assert_eq!(cell_runs(|row, column| row == 2 && (1..4).contains(&column)), vec![(2, 1, 4)]);
+        assert_eq!(cell_runs(|row, column| row == 0 && column != 3).len(), 2);
+    }
+}
@@ -0,0 +1,336 @@
+//! Animated GIFs of replays, a frame for each tile placed. The format is small enough to write
+//! by hand: a palette, then each frame's palette indices compressed with LZW
+
+use crate::replay::{Replay, ReplayError};
+use crate::terminal_graphics::Bitmap;
+use crate::tile::BoardCoordinate;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Most codes an LZW code table holds, those that fit in 12 bits
+const MAX_CODES: u16 = 4096;
+/// How many times longer than the others the final board is shown before the animation loops
+const FINAL_FRAME_HOLD: u16 = 4;
+
+impl Replay {
+    /// The game as a looping animated GIF with a frame for the board after each tile is placed,
+    /// `frame_delay` apart. Every frame is the size of the final board, so the board doesn't jump
+    /// about as it grows
+    pub fn to_gif(&self, frame_delay: Duration) -> Result<Vec<u8>, ReplayError> {
+        let final_board = self.state_at(self.turns.len())?.board;
+        let bounds = final_board.bounding_box().unwrap_or((BoardCoordinate::new(0, 0), BoardCoordinate::new(0, 0)));
+
+        let mut state = self.initial_state()?;
+        let mut frames = vec![];
+
+        for turn in &self.turns {
+            // a turn passed on leaves the board as it was
+            if state.apply(turn)?.is_some() {
+                frames.push(state.board.render_bitmap_within(bounds));
+            }
+        }
+
+        if frames.is_empty() {
+            frames.push(state.board.render_bitmap_within(bounds));
+        }
+
+        Ok(encode_animation(&frames, (frame_delay.as_millis() / 10).min(u16::MAX as u128) as u16))
+    }
+}
+
+/// The frames as a GIF that loops forever, each shown for `delay` hundredths of a second and the
+/// last for longer. The frames must all be the same size
+fn encode_animation(frames: &[Bitmap], delay: u16) -> Vec<u8> {
+    let (width, height) = (frames[0].width, frames[0].height);
+    let palette = Palette::of(frames);
+
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend((width as u16).to_le_bytes());
+    gif.extend((height as u16).to_le_bytes());
+    // a global colour table of 2^(size + 1) colours, with 8 bits a channel
+    gif.extend([0b1111_0000 | palette.size, 0, 0]);
+
+    for index in 0..palette.capacity() {
+        let (r, g, b) = palette.colours.get(index).copied().unwrap_or((0, 0, 0));
+        gif.extend([r, g, b]);
+    }
+
+    // the application extension that makes the animation loop, 0 times meaning forever
+    gif.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+    for (index, frame) in frames.iter().enumerate() {
+        let delay = if index + 1 == frames.len() { delay.saturating_mul(FINAL_FRAME_HOLD) } else { delay };
+
+        gif.extend([0x21, 0xf9, 0x04, 0x00]);
+        gif.extend(delay.to_le_bytes());
+        gif.extend([0x00, 0x00]);
+
+        gif.push(0x2c);
+        gif.extend([0, 0, 0, 0]);
+        gif.extend((width as u16).to_le_bytes());
+        gif.extend((height as u16).to_le_bytes());
+        gif.push(0);
+
+        // neighbouring pixels are mostly the same colour, so each is only looked up once in a run
+        let mut last: Option<((u8, u8, u8), u8)> = None;
+        let indices: Vec<u8> = frame
+            .pixels
+            .iter()
+            .map(|pixel| match last {
+                Some((colour, index)) if colour == *pixel => index,
+                _ => {
+                    let index = palette.index(pixel);
+                    last = Some((*pixel, index));
+                    index
+                }
+            })
+            .collect();
+        let min_code_size = (palette.size + 1).max(2);
+
+        gif.push(min_code_size);
+
+        for block in lzw_encode(&indices, min_code_size).chunks(255) {
+            gif.push(block.len() as u8);
+            gif.extend(block);
+        }
+
+        gif.push(0);
+    }
+
+    gif.push(0x3b);
+
+    gif
+}
+
+/// The colours the frames are drawn in, at most 256 as GIF allows. Any beyond those are drawn in
+/// the nearest of them, though boards are drawn in far fewer
+struct Palette {
+    colours: Vec<(u8, u8, u8)>,
+    indices: HashMap<(u8, u8, u8), u8>,
+    /// The colour table holds 2^(size + 1) colours
+    size: u8,
+}
+
+impl Palette {
+    fn of(frames: &[Bitmap]) -> Self {
+        let mut colours = vec![];
+        let mut indices = HashMap::new();
+
+        let mut last = None;
+
+        for pixel in frames.iter().flat_map(|frame| &frame.pixels) {
+            if last == Some(pixel) {
+                continue;
+            }
+
+            last = Some(pixel);
+
+            if colours.len() < 256 && !indices.contains_key(pixel) {
+                indices.insert(*pixel, colours.len() as u8);
+                colours.push(*pixel);
+            }
+        }
+
+        let size = (0..8).find(|size| 2usize << size >= colours.len()).unwrap_or(7);
+
+        Self { colours, indices, size }
+    }
+
+    fn capacity(&self) -> usize {
+        2 << self.size
+    }
+
+    fn index(&self, pixel: &(u8, u8, u8)) -> u8 {
+        self.indices.get(pixel).copied().unwrap_or_else(|| {
+            let distance = |(r, g, b): &(u8, u8, u8)| (*r as i32 - pixel.0 as i32).pow(2) + (*g as i32 - pixel.1 as i32).pow(2) + (*b as i32 - pixel.2 as i32).pow(2);
+
+            self.colours.iter().enumerate().min_by_key(|(_, colour)| distance(colour)).map_or(0, |(index, _)| index as u8)
+        })
+    }
+}
+
+/// Compresses palette indices as GIF's variable width LZW, the codes packed least significant
+/// bit first. The code table starts again once it is full
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    // the code for each code followed by each index, 0 for none as no string's code is 0
+    let alphabet = clear as usize;
+    let mut table = vec![0u16; MAX_CODES as usize * alphabet];
+
+    let mut output = BitWriter::default();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+
+    output.write(clear, width);
+
+    let mut indices = indices.iter();
+    let Some(&first) = indices.next() else {
+        output.write(end, width);
+        return output.finish();
+    };
+
+    let mut prefix = first as u16;
+
+    for &index in indices {
+        let entry = prefix as usize * alphabet + index as usize;
+
+        if table[entry] != 0 {
+            prefix = table[entry];
+            continue;
+        }
+
+        output.write(prefix, width);
+
+        if next == MAX_CODES {
+            output.write(clear, width);
+            table.fill(0);
+            next = end + 1;
+            width = min_code_size + 1;
+        } else {
+            table[entry] = next;
+
+            // the decoder widens its codes as soon as the next one it adds won't fit
+            if next == 1 << width && width < 12 {
+                width += 1;
+            }
+
+            next += 1;
+        }
+
+        prefix = index as u16;
+    }
+
+    output.write(prefix, width);
+    output.write(end, width);
+
+    output.finish()
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += width;
+
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, MyopicBot};
+    use crate::game_logic::Game;
+    use crate::player::Player;
+    use crate::replay::ReplayRecorder;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Reads LZW codes back as a GIF decoder does
+    fn lzw_decode(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1u16 << min_code_size;
+        let end = clear + 1;
+        let reset = || ((0..clear).map(|code| vec![code as u8]).chain([vec![], vec![]]).collect::<Vec<_>>(), min_code_size + 1);
+
+        let (mut table, mut width) = reset();
+        let (mut position, mut previous): (usize, Option<u16>) = (0, None);
+        let mut output = vec![];
+
+        loop {
+            let code = (0..width as usize).fold(0u16, |code, bit| code | (((bytes[(position + bit) / 8] >> ((position + bit) % 8)) & 1) as u16) << bit);
+            position += width as usize;
+
+            if code == clear {
+                (table, width) = reset();
+                previous = None;
+                continue;
+            }
+
+            if code == end {
+                return output;
+            }
+
+            let entry = match (table.get(code as usize), previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = table[previous as usize].clone();
+                    entry.push(entry[0]);
+                    entry
+                }
+                (None, None) => panic!("code {} before any other", code),
+            };
+
+            if let Some(previous) = previous {
+                if table.len() < MAX_CODES as usize {
+                    let mut added = table[previous as usize].clone();
+                    added.push(entry[0]);
+                    table.push(added);
+
+                    if table.len() == 1 << width && width < 12 {
+                        width += 1;
+                    }
+                }
+            }
+
+            output.extend(&entry);
+            previous = Some(code);
+        }
+    }
+
+    #[test]
+    fn should_compress_indices_the_way_a_decoder_reads_them() {
+        let repetitive: Vec<u8> = (0..20_000).map(|index| (index / 7 % 4) as u8).collect();
+        let noisy: Vec<u8> = (0..20_000u32).map(|index| (index.wrapping_mul(2_654_435_761) >> 13) as u8 % 16).collect();
+
+        assert_eq!(lzw_decode(&lzw_encode(&repetitive, 2), 2), repetitive);
+        assert_eq!(lzw_decode(&lzw_encode(&noisy, 4), 4), noisy);
+        assert_eq!(lzw_decode(&lzw_encode(&[], 2), 2), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn should_animate_a_replay_a_frame_for_each_tile_placed() {
+        let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+
+        Game::builder()
+            .with_seed([4; 32])
+            .with_player(Player::red(), BotStrategy::Myopic(MyopicBot))
+            .with_player(Player::green(), BotStrategy::Myopic(MyopicBot))
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(Rc::clone(&recorder))]);
+
+        // the opening is enough to animate, and quicker to encode unoptimised
+        let mut replay = recorder.borrow().replays()[0].clone();
+        replay.turns.truncate(12);
+
+        let gif = replay.to_gif(Duration::from_millis(250)).unwrap();
+
+        let placed = replay.turns.iter().filter(|turn| turn.placement.is_some()).count();
+        let frames = gif.windows(4).filter(|window| window == &[0x21, 0xf9, 0x04, 0x00]).count();
+
+        assert!(gif.starts_with(b"GIF89a") && gif.ends_with(&[0x3b]));
+        assert!(frames >= placed, "{} frames for {} tiles", frames, placed);
+        // the delay, in hundredths of a second, follows the graphic control extension's flags
+        assert!(gif.windows(6).any(|window| window == [0x21, 0xf9, 0x04, 0x00, 25, 0]));
+    }
+}
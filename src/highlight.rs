@@ -0,0 +1,126 @@
+use crate::board::Board;
+use crate::connected_regions::ConnectedRegion;
+use crate::ownership::{region_cells, unrotated};
+use crate::player::{PlayerIdentifier, RegionIndex};
+use crate::tile::{BoardCoordinate, PlacedTile, RegionType, RenderCell, RenderStyle, TileCoordinate, TILE_WIDTH};
+use colored::{Color, Colorize};
+use std::collections::HashSet;
+
+/// The features [Board::render_highlighted] picks out. Without any of these, every feature is picked
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RegionFilter {
+    /// Only features of this type
+    pub region_type: Option<RegionType>,
+    /// Only features the player has a meeple in
+    pub player: Option<PlayerIdentifier>,
+    /// Only features with part of themselves on the tile at this coordinate
+    pub covering: Option<BoardCoordinate>,
+}
+
+impl RegionFilter {
+    fn matches(&self, region: &ConnectedRegion, board: &Board) -> bool {
+        let type_matches = self.region_type.as_ref().is_none_or(|region_type| region.region_type() == region_type);
+        let player_matches = self.player.is_none_or(|player| region.residents(board).iter().any(|(_, _, meeple)| meeple.color == player));
+        let covering_matches = self.covering.is_none_or(|coordinate| region.tile_regions.iter().any(|tile_region| tile_region.tile_position == coordinate));
+
+        type_matches && player_matches && covering_matches
+    }
+}
+
+impl Board {
+    /// Renders the board with the features the filter picks out drawn as usual and everything else
+    /// dimmed, e.g. to find one player's farms on a big board
+    pub fn render_highlighted(&self, filter: &RegionFilter, style: &RenderStyle) -> String {
+        let highlighted: HashSet<(BoardCoordinate, RegionIndex)> = self
+            .get_connected_regions()
+            .into_iter()
+            .filter(|region| filter.matches(region, self))
+            .flat_map(|region| region.tile_regions.iter().map(|tile_region| (tile_region.tile_position, tile_region.region_index)))
+            .collect();
+
+        self.render_tiles(|tile| render_tile_highlighted(tile, &highlighted, style))
+    }
+}
+
+fn render_tile_highlighted(tile: &PlacedTile, highlighted: &HashSet<(BoardCoordinate, RegionIndex)>, style: &RenderStyle) -> Vec<String> {
+    let cells = region_cells(tile.tile);
+    let meeple_coordinate = tile.meeple_render_coordinate();
+
+    tile.tile
+        .render
+        .rotated(tile.placement.rotation)
+        .enumerate()
+        .map(|(row_idx, row)| {
+            row.enumerate()
+                .map(|(column_idx, cell)| {
+                    let meeple = match &meeple_coordinate {
+                        Some((coordinate, meeple)) if coordinate == &TileCoordinate::new(column_idx, row_idx) => Some(*meeple),
+                        _ => None,
+                    };
+
+                    // the tile's border is left as it is, so tiles can still be told apart
+                    let border = row_idx == 0 || column_idx == 0 || row_idx == TILE_WIDTH - 1 || column_idx == TILE_WIDTH - 1;
+
+                    let (row, column) = unrotated(row_idx, column_idx, tile.placement.rotation);
+                    let is_highlighted = cells[row][column].is_some_and(|region_index| highlighted.contains(&(tile.placement.coordinate, region_index)));
+
+                    match border || is_highlighted {
+                        true => cell.render(style, row_idx, column_idx, meeple),
+                        false => render_dimmed_cell(cell, style),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The cell faded towards black, or left blank where there are too few colours to fade it
+fn render_dimmed_cell(cell: &RenderCell, style: &RenderStyle) -> String {
+    match style {
+        RenderStyle::Ascii => "  ".to_string(),
+        RenderStyle::Ansi => "░░".color(Color::BrightBlack).to_string(),
+        RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel => {
+            let (r, g, b) = cell.rgb();
+
+            "  ".on_color(Color::TrueColor { r: r / 4, g: g / 4, b: b / 4 }).to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Rotation;
+    use crate::player::{MeepleColor, Player};
+    use crate::test_util::TestPlayer;
+    use crate::tile_definitions::SIDE_CITY;
+
+    #[test]
+    fn should_dim_everything_but_the_players_features_of_the_type() {
+        let mut alice = Player::red();
+        let mut bob = Player::blue();
+
+        // Alice farming the field of the first tile, and Bob in its city
+        let board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0), bob.move_with_meeple(&SIDE_CITY, 1, 0, Rotation::R0, 1)]).unwrap();
+
+        let everything = board.render_highlighted(&RegionFilter::default(), &RenderStyle::Ascii);
+        assert_eq!(everything, board.render(&RenderStyle::Ascii));
+
+        let alices_farms = RegionFilter { region_type: Some(RegionType::Field), player: Some(MeepleColor::Red), ..Default::default() };
+        let rendered = board.render_highlighted(&alices_farms, &RenderStyle::Ascii);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        // only Alice's field is drawn, and Bob's meeple in the city beside it is dimmed with it
+        assert_eq!(lines[2], "┃░░░░ꆜ ░░░░┃┃░░░░░░░░░░┃");
+        assert_eq!(lines[5], "┃░░      ░░┃┃░░      ░░┃");
+
+        let bobs_farms = RegionFilter { region_type: Some(RegionType::Field), player: Some(MeepleColor::Blue), ..Default::default() };
+        assert!(!board.render_highlighted(&bobs_farms, &RenderStyle::Ascii).contains("░░"));
+
+        // the first tile's field runs on into the second's, so both fields are picked out along
+        // with the first tile's city, leaving only the second tile's city dimmed
+        let on_the_first_tile = RegionFilter { covering: Some(BoardCoordinate::new(0, 0)), ..Default::default() };
+        let lines: Vec<String> = board.render_highlighted(&on_the_first_tile, &RenderStyle::Ascii).lines().map(str::to_string).collect();
+        assert_eq!(lines[5], "┃░░▓▓▓▓▓▓░░┃┃░░      ░░┃");
+    }
+}
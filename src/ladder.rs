@@ -0,0 +1,227 @@
+use crate::bot_strategy::BotStrategy;
+use crate::tournament::PairedReport;
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Where the ladder is kept unless given somewhere else
+pub const DEFAULT_PATH: &str = "ladder.json";
+
+/// The rating a bot starts the ladder on
+pub const INITIAL_RATING: f64 = 1500.0;
+
+/// How far a single game can move a rating
+const K_FACTOR: f64 = 16.0;
+
+/// Elo ratings of bots, kept between tournament runs so their strength can be followed as they
+/// change. Bots are rated by name and [BotStrategy::parameters], so a bot playing with different
+/// settings is rated separately
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ladder {
+    entries: Vec<LadderEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LadderEntry {
+    pub bot: String,
+    /// Fingerprint of the bot's parameters, which with its name identifies it on the ladder
+    pub parameter_hash: String,
+    /// The parameters themselves, to show alongside the name
+    pub parameters: String,
+    pub rating: f64,
+    pub games: usize,
+}
+
+#[derive(Debug)]
+pub enum LadderError {
+    Io(std::io::Error),
+    Malformed(serde_json::Error),
+}
+
+impl std::fmt::Display for LadderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LadderError::Io(error) => write!(f, "{}", error),
+            LadderError::Malformed(error) => write!(f, "malformed ladder: {}", error),
+        }
+    }
+}
+
+impl From<std::io::Error> for LadderError {
+    fn from(error: std::io::Error) -> Self {
+        LadderError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for LadderError {
+    fn from(error: serde_json::Error) -> Self {
+        LadderError::Malformed(error)
+    }
+}
+
+impl Ladder {
+    /// Reads the ladder saved at `path`, or an empty ladder if nothing has been saved there yet
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LadderError> {
+        match fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), LadderError> {
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    /// The bot's entry, if it has played on the ladder
+    pub fn entry(&self, bot: &BotStrategy) -> Option<&LadderEntry> {
+        let parameter_hash = parameter_hash(&bot.parameters());
+
+        self.entries.iter().find(|entry| entry.bot == bot.name() && entry.parameter_hash == parameter_hash)
+    }
+
+    fn entry_index(&mut self, bot: &BotStrategy) -> usize {
+        let parameters = bot.parameters();
+        let parameter_hash = parameter_hash(&parameters);
+
+        match self.entries.iter().position(|entry| entry.bot == bot.name() && entry.parameter_hash == parameter_hash) {
+            Some(index) => index,
+            None => {
+                self.entries.push(LadderEntry { bot: bot.name().to_string(), parameter_hash, parameters, rating: INITIAL_RATING, games: 0 });
+                self.entries.len() - 1
+            }
+        }
+    }
+
+    /// Updates both bots' ratings for a game between them, where `first_score` is 1 for a win by
+    /// the first bot, 0 for a loss and 0.5 for a draw
+    pub fn record_game(&mut self, first: &BotStrategy, second: &BotStrategy, first_score: f64) {
+        let (first, second) = (self.entry_index(first), self.entry_index(second));
+
+        // a bot playing itself has nothing to learn from the game
+        if first == second {
+            return;
+        }
+
+        let expected = 1.0 / (1.0 + 10f64.powf((self.entries[second].rating - self.entries[first].rating) / 400.0));
+        let change = K_FACTOR * (first_score - expected);
+
+        self.entries[first].rating += change;
+        self.entries[first].games += 1;
+        self.entries[second].rating -= change;
+        self.entries[second].games += 1;
+    }
+
+    /// Records both games of every pair in the report, in the order they were played, with the
+    /// points each bot scored deciding who won
+    pub fn record_paired(&mut self, report: &PairedReport, first: &BotStrategy, second: &BotStrategy) {
+        for pair in &report.games {
+            for game in [&pair.original, &pair.mirrored] {
+                let points = |player| game.score.iter().find(|(id, _)| **id == player).map_or(0, |(_, points)| *points);
+
+                let first_score = match points(report.first).cmp(&points(report.second)) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                };
+
+                self.record_game(first, second, first_score);
+            }
+        }
+    }
+
+    /// Every bot on the ladder, highest rated first
+    pub fn standings(&self) -> Vec<&LadderEntry> {
+        let mut standings: Vec<_> = self.entries.iter().collect();
+        standings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+        standings
+    }
+
+    pub fn render_standings(&self) -> String {
+        let mut rendered = String::new();
+
+        for (rank, entry) in self.standings().into_iter().enumerate() {
+            let parameters = if entry.parameters.is_empty() { String::new() } else { format!(" ({})", entry.parameters) };
+
+            writeln!(rendered, "{:>3}. {:<40} {:>6.0} over {} games", rank + 1, format!("{}{}", entry.bot, parameters), entry.rating, entry.games)
+                .expect("writing to a string can't fail");
+        }
+
+        rendered
+    }
+}
+
+/// FNV-1a of the parameters, as std's hasher isn't guaranteed to hash the same way between
+/// releases and the ladder outlives them
+fn parameter_hash(parameters: &str) -> String {
+    let hash = parameters.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{ExpectimaxBot, LazyBot, MonteCarloBot, MyopicBot};
+    use crate::player::Player;
+    use crate::rng::GameRng;
+    use crate::tournament::PairedMatchup;
+    use rand::SeedableRng;
+
+    #[test]
+    fn should_move_ratings_towards_the_result() {
+        let mut ladder = Ladder::default();
+        let (myopic, lazy) = (BotStrategy::Myopic(MyopicBot), BotStrategy::Lazy(LazyBot));
+
+        ladder.record_game(&myopic, &lazy, 1.0);
+
+        assert_eq!(ladder.entry(&myopic).unwrap().rating, INITIAL_RATING + K_FACTOR / 2.0);
+        assert_eq!(ladder.entry(&lazy).unwrap().rating, INITIAL_RATING - K_FACTOR / 2.0);
+
+        // an expected win moves the ratings less than the upset did
+        ladder.record_game(&myopic, &lazy, 1.0);
+        assert!(ladder.entry(&myopic).unwrap().rating < INITIAL_RATING + K_FACTOR);
+
+        assert_eq!(ladder.standings().iter().map(|entry| entry.bot.as_str()).collect::<Vec<_>>(), ["myopic", "lazy"]);
+        assert_eq!(ladder.entry(&myopic).unwrap().games, 2);
+    }
+
+    #[test]
+    fn should_rate_bots_with_different_parameters_separately() {
+        let mut ladder = Ladder::default();
+        let rng = || GameRng::from_seed([1; 32]);
+
+        let quick = BotStrategy::MonteCarlo(MonteCarloBot::new(rng()).with_samples(2));
+        let thorough = BotStrategy::MonteCarlo(MonteCarloBot::new(rng()).with_samples(20));
+
+        ladder.record_game(&thorough, &quick, 1.0);
+
+        assert_eq!(ladder.standings().len(), 2);
+        assert!(ladder.entry(&thorough).unwrap().rating > ladder.entry(&quick).unwrap().rating);
+        assert!(ladder.entry(&BotStrategy::Expectimax(ExpectimaxBot::default())).is_none());
+    }
+
+    #[test]
+    fn should_keep_ratings_between_runs() {
+        let directory = std::env::temp_dir().join(format!("carcassonne-ladder-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join(DEFAULT_PATH);
+
+        assert_eq!(Ladder::load(&path).unwrap(), Ladder::default());
+
+        let (myopic, lazy) = (BotStrategy::Myopic(MyopicBot), BotStrategy::Lazy(LazyBot));
+        let report = PairedMatchup::new(Player::red().with_bot(myopic.clone()), Player::green().with_bot(lazy.clone())).unwrap().with_river(false).run(2, [3; 32]);
+
+        let mut ladder = Ladder::load(&path).unwrap();
+        ladder.record_paired(&report, &myopic, &lazy);
+        ladder.save(&path).unwrap();
+
+        let reloaded = Ladder::load(&path).unwrap();
+        assert_eq!(reloaded, ladder);
+        assert_eq!(reloaded.entry(&lazy).unwrap().games, 4);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+//! A Carcassonne rules engine, with bots to play it and tools to study their games.
+//!
+//! ```no_run
+//! use carcassonne::prelude::*;
+//! use carcassonne::bot_strategy::{LazyBot, MyopicBot};
+//!
+//! let result = Game::builder()
+//!     .with_player(Player::red().with_name("Alice"), BotStrategy::Myopic(MyopicBot))
+//!     .with_player(Player::green().with_name("Bob"), BotStrategy::Lazy(LazyBot))
+//!     .with_rules(RuleSet::standard())
+//!     .with_expansions([Expansion::River])
+//!     .build()
+//!     .expect("players should be distinct")
+//!     .play();
+//!
+//! println!("{:?}", result.score);
+//! ```
+
+#[cfg(feature = "alloc-stats")]
+pub mod alloc_stats;
+mod allocator;
+pub mod board;
+pub mod board_growth;
+pub mod region_graph;
+pub mod score_sheet;
+pub mod share_code;
+pub mod sketch;
+mod symmetry;
+#[cfg(feature = "render")]
+mod diff;
+#[cfg(feature = "render")]
+pub mod export;
+#[cfg(feature = "render")]
+mod geojson;
+#[cfg(feature = "render")]
+mod gif;
+#[cfg(feature = "render")]
+mod highlight;
+#[cfg(feature = "render")]
+pub mod meeple_spots;
+#[cfg(feature = "render")]
+mod ownership;
+#[cfg(feature = "render")]
+mod pdf;
+#[cfg(feature = "render")]
+mod svg;
+#[cfg(feature = "render")]
+mod terminal_graphics;
+mod connected_regions;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+pub mod corpus;
+pub mod crash_report;
+pub mod dataset;
+pub mod deck;
+#[cfg(feature = "render")]
+pub mod demo;
+mod game_logic;
+pub mod player;
+pub mod rules;
+pub mod tile;
+pub mod tile_definitions;
+pub mod score;
+#[cfg(feature = "score-check")]
+mod score_check;
+pub mod team;
+mod move_hints;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_util;
+pub mod bot_strategy;
+pub mod selftest;
+pub mod tie_break;
+pub mod observer;
+pub mod controller;
+#[cfg(feature = "tokio")]
+pub mod async_controller;
+#[cfg(feature = "progress")]
+pub mod progress;
+pub mod simulate;
+pub mod stats;
+#[cfg(feature = "render")]
+pub mod step_debugger;
+pub mod tournament;
+pub mod ladder;
+pub mod replay;
+pub mod rng;
+mod binary_replay;
+pub mod analysis;
+pub mod arena;
+pub mod commentary;
+pub mod experiment;
+pub mod locale;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "webhook")]
+pub mod webhook;
+pub mod prelude;
+
+pub use board::Board;
+#[cfg(feature = "render")]
+pub use board::RenderAnnotations;
+pub use connected_regions::{ConnectedRegion, ConnectedRegionId};
+pub use deck::Deck;
+pub use game_logic::{Game, GameBuildError, GameBuilder, GamePhase};
+pub use move_hints::{MeepleContext, MoveHint, MovePotential, PlacementPreview};
+pub use player::{MeepleColor, MeepleKind, Player, PlayerIdentifier};
+pub use allocator::ALLOCATOR_NAME;
+#[cfg(any(feature = "jemalloc", feature = "mimalloc", feature = "alloc-stats"))]
+pub use allocator::Allocator;
+pub use rng::GameRng;
+pub use rules::RuleSet;
+pub use score::{NamedScore, Score};
+pub use simulate::Seed;
+pub use team::Team;
+pub use tile::BoardTopology;
+pub use tile::Expansion;
+pub use tile::Rotation;
+#[cfg(feature = "render")]
+pub use diff::{render_diff, render_diff_plain};
+#[cfg(feature = "render")]
+pub use highlight::RegionFilter;
+#[cfg(feature = "render")]
+pub use svg::{ContactSheet, TileArtwork};
+#[cfg(feature = "render")]
+pub use terminal_graphics::RenderConfig;
+#[cfg(feature = "render")]
+pub use tile::RenderStyle;
@@ -0,0 +1,362 @@
+use crate::board::{Board, InvalidTilePlacement};
+use crate::move_hints::MoveHint;
+use crate::tile::{CardinalDirection, RegionType, TileDefinition, TilePlacement};
+use std::fmt::Display;
+
+/// A language the player-facing text can be shown in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+/// The sentences shown to players, each with `{}` where its values go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    /// Player name, tile name, meeple in hand
+    YouDrew,
+    ChooseMove,
+    ChooseMeeple,
+    NoMeeple,
+    /// The region type, the points it would be worth to the player now
+    MeepleRegion,
+    /// What was typed
+    NotAMove,
+    /// The move
+    ConfirmMove,
+    /// The region type
+    MeepleOn,
+    EngineAgrees,
+    /// The engine's move, how many points better it was
+    EngineBetter,
+    /// The score table
+    FinalScore,
+    /// Player name
+    Resigned,
+    /// Percentage, best moves, moves, points given away
+    Accuracy,
+    /// Number of tiles
+    Deck,
+    /// The side of the tile, the feature on it, where the tile beside it is, the feature on that
+    /// tile, and that tile's side
+    EdgeConflict,
+    DoesNotFitTurned,
+    /// The placement
+    PlayedForYou,
+    /// Times the tile joins onto features, features completed, points scored, meeple given back
+    PlacementPreview,
+    /// The bot's name
+    HandedOver,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// ISO 639-1 code of the language, as given on the command line
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+
+    pub fn text(&self, text: Text) -> &'static str {
+        match self {
+            Language::English => match text {
+                Text::YouDrew => "{}, you drew {} ({} meeple in hand):",
+                Text::ChooseMove => "Choose where to place the tile, by number or as x y rotation, ? and its number to look a move over, t to turn it, p to pass, f to have a bot finish the game for you or r to resign: ",
+                Text::ChooseMeeple => "Choose a region for a meeple, n for none or b to go back: ",
+                Text::NoMeeple => "no meeple",
+                Text::MeepleRegion => "{}, {} points as it stands",
+                Text::NotAMove => "[{}] is not one of the moves",
+                Text::ConfirmMove => "Play {}? [Y/n] ",
+                Text::MeepleOn => ", meeple on the {}",
+                Text::EngineAgrees => "The engine would have played that too",
+                Text::EngineBetter => "The engine would have played {}, {} points better",
+                Text::FinalScore => "Final score is\n{}",
+                Text::Resigned => "{} resigned",
+                Text::Accuracy => "Accuracy {}%: you played the engine's move {} of {} times, and gave away {} points",
+                Text::Deck => "Deck of {} tiles:",
+                Text::EdgeConflict => "the {} edge of your tile is a {} but the tile {} has a {} on its {} edge",
+                Text::DoesNotFitTurned => "The tile doesn't fit anywhere turned this way",
+                Text::PlayedForYou => "Your tile fits only at {} and a meeple isn't worth placing on it, so it was placed for you",
+                Text::PlacementPreview => "Joins onto the board's features {} times and completes {}, scoring you {} points now and giving back {} of your meeple",
+                Text::HandedOver => "The {} bot will finish the game for you",
+            },
+            Language::German => match text {
+                Text::YouDrew => "{}, du hast {} gezogen ({} Gefolgsleute übrig):",
+                Text::ChooseMove => "Wähle, wohin die Karte kommt, als Nummer oder x y Drehung, ? und die Nummer zum Ansehen eines Zugs, t zum Drehen, p zum Passen, f, damit ein Bot die Partie für dich beendet, oder r zum Aufgeben: ",
+                Text::ChooseMeeple => "Wähle ein Gebiet für einen Gefolgsmann, n für keinen oder b für zurück: ",
+                Text::NoMeeple => "kein Gefolgsmann",
+                Text::MeepleRegion => "{}, derzeit {} Punkte",
+                Text::NotAMove => "[{}] ist keiner der Züge",
+                Text::ConfirmMove => "{} spielen? [J/n] ",
+                Text::MeepleOn => ", Gefolgsmann auf: {}",
+                Text::EngineAgrees => "Die Engine hätte genauso gespielt",
+                Text::EngineBetter => "Die Engine hätte {} gespielt, {} Punkte besser",
+                Text::FinalScore => "Endstand\n{}",
+                Text::Resigned => "{} hat aufgegeben",
+                Text::Accuracy => "Genauigkeit {}%: {} von {} Zügen wie die Engine gespielt, {} Punkte verschenkt",
+                Text::Deck => "Stapel mit {} Karten:",
+                Text::EdgeConflict => "an der {}kante deiner Karte ist {}, aber die Karte {} hat {} an ihrer {}kante",
+                Text::DoesNotFitTurned => "So gedreht passt die Karte nirgends hin",
+                Text::PlayedForYou => "Deine Karte passt nur bei {} und ein Gefolgsmann lohnt sich darauf nicht, also wurde sie für dich gelegt",
+                Text::PlacementPreview => "Schließt {}-mal an Gebiete an und vollendet {}, bringt dir jetzt {} Punkte und {} deiner Gefolgsleute zurück",
+                Text::HandedOver => "Der Bot {} beendet die Partie für dich",
+            },
+        }
+    }
+
+    /// The text with each `{}` filled by the next of `values`
+    pub fn format(&self, text: Text, values: &[&dyn Display]) -> String {
+        let mut values = values.iter();
+
+        self.text(text)
+            .split("{}")
+            .enumerate()
+            .map(|(index, part)| match index {
+                0 => part.to_string(),
+                _ => values.next().map(|value| value.to_string()).unwrap_or_default() + part,
+            })
+            .collect()
+    }
+
+    pub fn tile_name(&self, tile: &TileDefinition) -> &'static str {
+        let english = tile.name();
+
+        let translated = match self {
+            Language::English => None,
+            Language::German => match english {
+                "Cross intersection" => Some("Kreuzung"),
+                "Three-way junction with city" => Some("Dreierkreuzung mit Stadt"),
+                "Straight city with side fields" => Some("Stadtdurchgang mit Wiesen"),
+                "Straight city with side fields and pennant" => Some("Stadtdurchgang mit Wiesen und Wappen"),
+                "Straight road with side city" => Some("Gerade Straße mit Stadtrand"),
+                "Field with two corner cities" => Some("Wiese mit zwei Stadträndern"),
+                "Corner road with pennanted corner city" => Some("Straßenkurve mit Eckstadt und Wappen"),
+                "Three-way junction" => Some("Dreierkreuzung"),
+                "River terminator" => Some("Flussende"),
+                "Lake" => Some("See"),
+                "Corner road" => Some("Straßenkurve"),
+                "River corner with road corner" => Some("Flusskurve mit Straßenkurve"),
+                "Corner city" => Some("Eckstadt"),
+                "Side city with side road and pennant" => Some("Stadtrand mit Straße und Wappen"),
+                "Corner road with corner city" => Some("Straßenkurve mit Eckstadt"),
+                "Straight river with two side cities" => Some("Gerader Fluss mit zwei Stadträndern"),
+                "Corner road with side city" => Some("Straßenkurve mit Stadtrand"),
+                "Cloister with road and river" => Some("Kloster mit Straße und Fluss"),
+                "Cloister in field" => Some("Kloster"),
+                "Three sided city" => Some("Dreiseitige Stadt"),
+                "Centre city with pennant" => Some("Stadtmitte mit Wappen"),
+                "Side city with straight river and bridge" => Some("Stadtrand mit Fluss und Brücke"),
+                "Straight road" => Some("Gerade Straße"),
+                "Straight river with straight road and bridge" => Some("Gerader Fluss mit Straße und Brücke"),
+                "Corner city with pennant" => Some("Eckstadt mit Wappen"),
+                "Three sided city with pennant" => Some("Dreiseitige Stadt mit Wappen"),
+                "Corner city with corner river" => Some("Eckstadt mit Flusskurve"),
+                "Straight river" => Some("Gerader Fluss"),
+                "Corner river" => Some("Flusskurve"),
+                "Side city" => Some("Stadtrand"),
+                "Opposing side cities" => Some("Gegenüberliegende Stadtränder"),
+                "Three sided city with road" => Some("Dreiseitige Stadt mit Straße"),
+                "Straight road with right corner road" => Some("Gerade Straße mit Abzweig nach rechts"),
+                "Cloister with road" => Some("Kloster mit Straße"),
+                _ => None,
+            },
+        };
+
+        // a tile added without a translation is better shown in English than not at all
+        translated.unwrap_or(english)
+    }
+
+    pub fn region_name(&self, region_type: &RegionType) -> &'static str {
+        match (self, region_type) {
+            (Language::English, RegionType::City) => "city",
+            (Language::English, RegionType::Field) => "field",
+            (Language::English, RegionType::Cloister) => "cloister",
+            (Language::English, RegionType::Road) => "road",
+            (Language::English, RegionType::Water) => "water",
+            (Language::German, RegionType::City) => "Stadt",
+            (Language::German, RegionType::Field) => "Wiese",
+            (Language::German, RegionType::Cloister) => "Kloster",
+            (Language::German, RegionType::Road) => "Straße",
+            (Language::German, RegionType::Water) => "Fluss",
+        }
+    }
+
+    /// Whether the answer to a yes or no question, such as [Text::ConfirmMove], is yes. No answer
+    /// at all is, as the question's capitalised choice shows
+    pub fn is_yes(&self, answer: &str) -> bool {
+        let yes: &[&str] = match self {
+            Language::English => &["", "y", "yes"],
+            Language::German => &["", "j", "ja"],
+        };
+
+        yes.iter().any(|yes| answer.trim().eq_ignore_ascii_case(yes))
+    }
+
+    /// Where the move puts the tile and its meeple, e.g. `(1, -2) r90, meeple on the city`
+    pub fn describe_move(&self, hint: &MoveHint) -> String {
+        match hint.meeple_placement.and_then(|region_index| hint.tile.regions.get(*region_index)) {
+            Some(region) => hint.tile_placement.to_string() + &self.format(Text::MeepleOn, &[&self.region_name(&region.region_type())]),
+            None => hint.tile_placement.to_string(),
+        }
+    }
+
+    /// Why the tile can't be placed there, spelling out which edges don't match where that's the
+    /// reason, e.g. `the north edge of your tile is a road but the tile above has a field on its
+    /// south edge`
+    pub fn explain_placement_error(&self, board: &Board, tile: &'static TileDefinition, placement: &TilePlacement, error: &InvalidTilePlacement) -> String {
+        match (error, board.edge_conflict(tile, placement)) {
+            (InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles, Some(conflict)) => self.format(
+                Text::EdgeConflict,
+                &[
+                    &self.direction_name(&conflict.direction),
+                    &self.region_name(&conflict.own),
+                    &self.position_name(&conflict.direction),
+                    &self.region_name(&conflict.neighbour_region),
+                    &self.direction_name(&conflict.direction.compass_opposite()),
+                ],
+            ),
+            _ => self.placement_error(error).to_string(),
+        }
+    }
+
+    fn direction_name(&self, direction: &CardinalDirection) -> &'static str {
+        match (self, direction) {
+            (Language::English, CardinalDirection::North) => "north",
+            (Language::English, CardinalDirection::East) => "east",
+            (Language::English, CardinalDirection::South) => "south",
+            (Language::English, _) => "west",
+            (Language::German, CardinalDirection::North) => "Nord",
+            (Language::German, CardinalDirection::East) => "Ost",
+            (Language::German, CardinalDirection::South) => "Süd",
+            (Language::German, _) => "West",
+        }
+    }
+
+    /// Where the tile on the `direction` side of another is, as seen on the board
+    fn position_name(&self, direction: &CardinalDirection) -> &'static str {
+        match (self, direction) {
+            (Language::English, CardinalDirection::North) => "above",
+            (Language::English, CardinalDirection::East) => "to its right",
+            (Language::English, CardinalDirection::South) => "below",
+            (Language::English, _) => "to its left",
+            (Language::German, CardinalDirection::North) => "darüber",
+            (Language::German, CardinalDirection::East) => "rechts daneben",
+            (Language::German, CardinalDirection::South) => "darunter",
+            (Language::German, _) => "links daneben",
+        }
+    }
+
+    pub fn placement_error(&self, error: &InvalidTilePlacement) -> &'static str {
+        match self {
+            Language::English => match error {
+                InvalidTilePlacement::TileAlreadyAtCoordinate => "there is already a tile there",
+                InvalidTilePlacement::TileDoesNotContactPlacedTiles => "the tile has to be placed next to another tile",
+                InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles => "the tile's edges don't match the tiles next to it",
+                InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion => "another meeple is already on that feature",
+                InvalidTilePlacement::RiverMustBeConnected => "the river has to carry on from the last river tile",
+                InvalidTilePlacement::RiverMustNotImmediatelyTurnOnItself => "the river can't turn back on itself",
+                InvalidTilePlacement::RiverEndsMustNotTouch => "the river can't end next to its spring",
+                InvalidTilePlacement::OutsideBoardLimit => "the board would no longer fit within its limit",
+                InvalidTilePlacement::InvalidMeeplePlacementIndex => "the tile has no region with that index",
+                InvalidTilePlacement::MeepleCannotBePlacedInRiver => "meeple can't be placed in the river",
+                InvalidTilePlacement::MeepleKindCannotOccupyRegion => "that kind of meeple can't go on that feature",
+                InvalidTilePlacement::MeepleKindNeedsOwnFollower => "that kind of meeple has to join one of its player's followers",
+                InvalidTilePlacement::RegionsOutOfSync => "the board's regions don't match its tiles",
+                InvalidTilePlacement::NoFarmers => "meeple can't be placed in fields without farmers",
+            },
+            Language::German => match error {
+                InvalidTilePlacement::TileAlreadyAtCoordinate => "dort liegt schon eine Karte",
+                InvalidTilePlacement::TileDoesNotContactPlacedTiles => "die Karte muss an eine andere Karte angelegt werden",
+                InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles => "die Kanten der Karte passen nicht zu den Nachbarkarten",
+                InvalidTilePlacement::OtherMeepleAlreadyInConnectedRegion => "auf diesem Gebiet steht schon ein Gefolgsmann",
+                InvalidTilePlacement::RiverMustBeConnected => "der Fluss muss an die letzte Flusskarte anschließen",
+                InvalidTilePlacement::RiverMustNotImmediatelyTurnOnItself => "der Fluss darf nicht auf sich selbst zurückbiegen",
+                InvalidTilePlacement::RiverEndsMustNotTouch => "der Fluss darf nicht neben seiner Quelle enden",
+                InvalidTilePlacement::OutsideBoardLimit => "das Spielfeld würde nicht mehr in seine Grenzen passen",
+                InvalidTilePlacement::InvalidMeeplePlacementIndex => "die Karte hat kein Gebiet mit dieser Nummer",
+                InvalidTilePlacement::MeepleCannotBePlacedInRiver => "in den Fluss dürfen keine Gefolgsleute",
+                InvalidTilePlacement::MeepleKindCannotOccupyRegion => "diese Figur darf nicht auf dieses Gebiet",
+                InvalidTilePlacement::MeepleKindNeedsOwnFollower => "diese Figur muss zu einem eigenen Gefolgsmann gestellt werden",
+                InvalidTilePlacement::RegionsOutOfSync => "die Gebiete des Spielfelds passen nicht zu seinen Karten",
+                InvalidTilePlacement::NoFarmers => "ohne Bauern dürfen keine Gefolgsleute auf Wiesen",
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::RegionIndex;
+    use crate::tile::{BoardCoordinate, PlacedTile, Rotation};
+    use crate::tile_definitions::{ALL_TILE_DEFINITIONS, SIDE_CITY, STRAIGHT_ROAD};
+
+    #[test]
+    fn should_translate_every_tile() {
+        for tile in ALL_TILE_DEFINITIONS.iter() {
+            assert_eq!(Language::English.tile_name(tile), tile.name());
+            assert_ne!(Language::German.tile_name(tile), tile.name(), "{} has no German name", tile.name());
+        }
+    }
+
+    #[test]
+    fn should_fill_placeholders_in_order() {
+        assert_eq!(Language::English.format(Text::EngineBetter, &[&"(0, 1) r90", &3]), "The engine would have played (0, 1) r90, 3 points better");
+        assert_eq!(Language::German.format(Text::NotAMove, &[&"x"]), "[x] ist keiner der Züge");
+        assert_eq!(Language::English.format(Text::EngineAgrees, &[]), "The engine would have played that too");
+    }
+
+    #[test]
+    fn should_describe_moves_in_the_language() {
+        let tile_placement = TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 };
+        let hint = MoveHint { tile: &SIDE_CITY, tile_placement, meeple_placement: Some(RegionIndex::new(1)) };
+
+        assert_eq!(Language::English.describe_move(&hint), hint.to_string());
+        assert_eq!(Language::German.describe_move(&hint), "(0, 1) r180, Gefolgsmann auf: Stadt");
+    }
+
+    #[test]
+    fn should_explain_which_edges_dont_match() {
+        let board = Board::new_with_tiles(vec![PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+
+        let across = TilePlacement { coordinate: BoardCoordinate::new(0, -1), rotation: Rotation::R90 };
+        let error = InvalidTilePlacement::TileEdgesDoNotMatchPlacedTiles;
+
+        assert_eq!(
+            Language::English.explain_placement_error(&board, &STRAIGHT_ROAD, &across, &error),
+            "the south edge of your tile is a field but the tile below has a road on its north edge"
+        );
+        assert_eq!(
+            Language::German.explain_placement_error(&board, &STRAIGHT_ROAD, &across, &error),
+            "an der Südkante deiner Karte ist Wiese, aber die Karte darunter hat Straße an ihrer Nordkante"
+        );
+
+        let apart = TilePlacement { coordinate: BoardCoordinate::new(4, 4), rotation: Rotation::R0 };
+        let error = InvalidTilePlacement::TileDoesNotContactPlacedTiles;
+
+        assert_eq!(Language::English.explain_placement_error(&board, &STRAIGHT_ROAD, &apart, &error), "the tile has to be placed next to another tile");
+    }
+
+    #[test]
+    fn should_read_yes_in_the_language() {
+        assert!(Language::German.format(Text::ConfirmMove, &[&"(0, 1) r0"]).ends_with("[J/n] "));
+        assert!(Language::German.is_yes("j") && Language::German.is_yes("Ja") && Language::German.is_yes(""));
+        assert!(!Language::German.is_yes("n") && !Language::German.is_yes("nein"));
+        assert!(Language::English.is_yes("Y") && !Language::English.is_yes("j"));
+    }
+
+    #[test]
+    fn should_look_up_languages_by_code() {
+        assert_eq!(Language::from_code("DE"), Some(Language::German));
+        assert_eq!(Language::from_code("fr"), None);
+        assert!(Language::ALL.iter().all(|language| Language::from_code(language.code()) == Some(*language)));
+    }
+}
@@ -1,155 +1,1754 @@
-use crate::board::{Board, TilePlacementSuccess};
-use crate::deck::Deck;
-use crate::player::Player;
-use crate::tile::{PlacedTile, RenderStyle};
-use rand::prelude::StdRng;
-use rand::{Rng, RngCore, SeedableRng};
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::ops::DerefMut;
-use std::rc::Rc;
-use std::sync::{Arc, RwLock};
-use std::time::Instant;
-use indexmap::IndexMap;
+use carcassonne::analysis::{self, Accuracy};
+use carcassonne::board_growth::BoardGrowth;
+use carcassonne::commentary;
+use carcassonne::crash_report;
+use carcassonne::dataset::DatasetRecorder;
+use carcassonne::demo::DemoPresenter;
+use carcassonne::experiment::{Experiment, ExperimentOutput};
+use carcassonne::export;
+use carcassonne::deck;
+use carcassonne::ladder::{self, Ladder};
+use carcassonne::meeple_spots;
+use carcassonne::controller::HumanController;
+use carcassonne::bot_strategy::{BotStrategy, Difficulty, FillTheGridBot, JerkBot, LazyBot, MyopicBot, NoisyBot, RandoBot, BOT_NAMES};
+use carcassonne::observer::{GameEvent, GameObserver};
+use carcassonne::{render_diff, render_diff_plain, ContactSheet, RegionFilter, RenderAnnotations, RenderConfig};
+use carcassonne::rules;
+use carcassonne::selftest;
+use carcassonne::replay::{Replay, ReplayFormat, ReplayPlacement, ReplayRecorder};
+use carcassonne::GameRng;
+use carcassonne::locale::{Language, Text};
+use carcassonne::prelude::*;
+use carcassonne::simulate::{self, SavedReport, Seating, SimulationConfig};
+use carcassonne::stats::{compare_rules_observed, render_region_table, render_rules_comparison, render_score_chart, render_tile_table, ContestStats, DuplicateGames, MeepleTracker, Records, ScoreTimeline, TileStats};
+use carcassonne::step_debugger::StepDebugger;
+use carcassonne::tile::{parse_expansions, RegionType, Rotation};
+use carcassonne::tile_definitions;
+use carcassonne::tournament::{PairedGame, PairedMatchup, PairedReport, ParameterRange, Sprt, SprtDecision, Sweep};
+use clap::{Parser, Subcommand};
+use rand::{Rng, SeedableRng};
 use rand::rngs::OsRng;
-use uuid::Uuid;
-use crate::bot_strategy::{Bot, BotPlayer, BotStrategy, FillTheGridBot, JerkBot, LazyBot, MyopicBot, RandoBot};
-use crate::score::Score;
 use base64::{engine::general_purpose, Engine as _};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::exit;
+use std::rc::Rc;
+use std::time::Duration;
+
+// the allocator the build's features choose, see carcassonne::ALLOCATOR_NAME, with alloc-stats
+// counting over whichever it is
+#[cfg(all(any(feature = "jemalloc", feature = "mimalloc"), not(feature = "alloc-stats")))]
+#[global_allocator]
+static GLOBAL: carcassonne::Allocator = carcassonne::Allocator;
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: carcassonne::alloc_stats::CountingAllocator = carcassonne::alloc_stats::CountingAllocator;
+
+#[derive(Parser)]
+#[command(about = "Carcassonne engine and bot playground")]
+struct Cli {
+    /// Base64 (url safe) seed to reproduce a previous run
+    #[arg(long, global = true)]
+    seed: Option<String>,
 
+    /// Draw boards and tiles in this style where the terminal supports it. Defaults to the richest
+    /// style the terminal supports
+    #[arg(long, global = true, value_parser = ["ascii", "ansi", "truecolor", "kitty", "sixel"])]
+    style: Option<String>,
 
-mod board;
-mod connected_regions;
-mod deck;
-mod game_logic;
-mod player;
-mod tile;
-mod tile_definitions;
-mod score;
-mod move_hints;
-mod test_util;
-mod bot_strategy;
+    /// Draw in the --style given even when the terminal doesn't look like it supports it
+    #[arg(long, global = true, requires = "style")]
+    force_style: bool,
 
-// #[global_allocator]
-// static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
+    /// Where to write the seed, moves and board of a game that panics
+    #[arg(long, global = true, default_value = "carcassonne-crash.txt")]
+    crash_report: PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Play a batch of bot games and print the combined score (the default)
+    Simulate {
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+        /// Write a replay of every game to this directory
+        #[arg(long)]
+        replays: Option<PathBuf>,
+        /// Write the replays in the compact binary format rather than JSON
+        #[arg(long)]
+        binary_replays: bool,
+        /// Write the report, with every game's result, to this file as JSON
+        #[arg(long)]
+        report: Option<PathBuf>,
+        /// Write each player's score and features finished in every game to this file as CSV
+        #[arg(long)]
+        csv: Option<PathBuf>,
+        /// A report written by an earlier run with --report, to compare this run's mean scores and
+        /// win rates with
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Draw the final board of every game to this file, as an HTML page of thumbnails
+        #[arg(long)]
+        contact_sheet: Option<PathBuf>,
+        /// Write every turn of every game to this file as a training example for learned bots: the
+        /// board as the player saw it, the move they chose and how the game went, as NumPy .npz
+        #[arg(long)]
+        dataset: Option<PathBuf>,
+        /// Post progress every 15 minutes, and the final result, to this Discord or Slack webhook
+        /// URL. Needs the webhook feature
+        #[arg(long)]
+        webhook: Option<String>,
+        /// Pause after every turn to show the board and answer questions about it, until Enter is
+        /// pressed
+        #[arg(long)]
+        step: bool,
+        /// Write how many entries the board's containers hold after every turn to stderr at the end
+        /// of each game, flagging any that outgrew the tiles placed
+        #[arg(long)]
+        board_sizes: bool,
+        /// Follow every game on a second board to report how each player's meeple were used and
+        /// the largest features completed, which slows the simulation
+        #[arg(long)]
+        board_stats: bool,
+        /// The rules to play under
+        #[arg(long, default_value = "standard", value_parser = rules::PRESET_NAMES)]
+        rules: String,
+        /// Play the rules without farmers, keeping meeple out of fields
+        #[arg(long)]
+        no_farmers: bool,
+        /// Who takes the first turn in each game: always the first bot, each bot in turn, at random,
+        /// or every seat order in turn for each deck, playing each deck that many times
+        #[arg(long, default_value = "fixed", value_parser = ["fixed", "rotate", "shuffle", "permute"])]
+        seating: String,
+        /// Run the experiment described in this TOML file, in place of the other options. Its seed,
+        /// if it has one, takes the place of --seed
+        #[arg(long, conflicts_with_all = ["iterations", "replays", "binary_replays", "report", "csv", "baseline", "contact_sheet", "dataset", "webhook", "rules", "no_farmers", "seating"])]
+        config: Option<PathBuf>,
+    },
+    /// Play two bots against each other in seat-swapped pairs until one is shown to be stronger
+    Compare {
+        bot_a: String,
+        bot_b: String,
+        /// Give up without a decision after this many pairs of games
+        #[arg(long, default_value_t = 1000)]
+        max_pairs: usize,
+        /// Chance of wrongly concluding bot A is stronger
+        #[arg(long, default_value_t = 0.05)]
+        alpha: f64,
+        /// Chance of wrongly concluding bot B is stronger
+        #[arg(long, default_value_t = 0.05)]
+        beta: f64,
+        /// How far from an even pair win rate counts as a real difference
+        #[arg(long, default_value_t = 0.05)]
+        margin: f64,
+        #[arg(long)]
+        no_river: bool,
+        /// Play on this many threads rather than one for each core. The games played are the same
+        #[arg(long)]
+        threads: Option<usize>,
+        /// The ladder to update both bots' ratings on with the games played
+        #[arg(long, default_value = ladder::DEFAULT_PATH)]
+        ladder: PathBuf,
+        /// Post progress every 15 minutes, and the final result, to this Discord or Slack webhook
+        /// URL. Needs the webhook feature
+        #[arg(long)]
+        webhook: Option<String>,
+    },
+    /// Try a bot with each value of one of its parameters against a fixed pool of opponents, in
+    /// seat-swapped pairs of games, and report the values best first
+    Sweep {
+        #[arg(long)]
+        bot: String,
+        /// The parameter and its values, e.g. samples=1..8, budget=100..500step100 or horizon=5,10,20
+        #[arg(long)]
+        param: String,
+        /// Games played with each value, shared between the opponents
+        #[arg(long, default_value_t = 100)]
+        games: usize,
+        /// The bots each value plays against
+        #[arg(long, value_delimiter = ',', default_value = "myopic")]
+        opponents: Vec<String>,
+        #[arg(long)]
+        no_river: bool,
+        /// Play on this many threads rather than one for each core. The games played are the same
+        #[arg(long)]
+        threads: Option<usize>,
+    },
+    /// Print the bots' ratings from the games played by compare, highest first
+    Ladder {
+        #[arg(long, default_value = ladder::DEFAULT_PATH)]
+        ladder: PathBuf,
+    },
+    /// Play the same games under two rule sets and report how the scores and the use of tiles and
+    /// meeple change
+    CompareRules {
+        /// The rules to compare against
+        #[arg(value_parser = rules::PRESET_NAMES)]
+        baseline: String,
+        /// The rules to compare
+        #[arg(value_parser = rules::PRESET_NAMES)]
+        alternative: String,
+        #[arg(long, default_value_t = 100)]
+        iterations: usize,
+        #[arg(long)]
+        no_river: bool,
+        /// Post progress every 15 minutes, and the final result, to this Discord or Slack webhook
+        /// URL. Needs the webhook feature
+        #[arg(long)]
+        webhook: Option<String>,
+    },
+    /// Compare every move in a replay against the best move available and report the blunders
+    Analyze {
+        replay: PathBuf,
+        /// Flag moves worth more than this many points less than the best move
+        #[arg(long, default_value_t = 3)]
+        threshold: i32,
+        /// Draw the board after each blunder with the move that was played highlighted
+        #[arg(long)]
+        boards: bool,
+    },
+    /// Watch bots play a game in the terminal, slowed down, with commentary on each turn and every
+    /// completed feature picked out on the board
+    Demo {
+        /// The bots to watch, in turn order
+        #[arg(default_values_t = ["monte-carlo".to_string(), "myopic".to_string()])]
+        bots: Vec<String>,
+        /// How long each turn stays on screen, in milliseconds. Turns completing a feature stay
+        /// twice as long
+        #[arg(long, default_value_t = 1500)]
+        delay: u64,
+        #[arg(long)]
+        no_river: bool,
+    },
+    /// Play a game at the terminal against bots
+    Play {
+        /// Bots to play against, in turn order after you
+        #[arg(default_values_t = ["myopic".to_string()])]
+        opponents: Vec<String>,
+        #[arg(long)]
+        no_river: bool,
+        /// Comma separated expansions to play with, e.g. `river`. Defaults to the river
+        #[arg(long, conflicts_with = "no_river")]
+        expansions: Option<String>,
+        /// Write a score sheet for the game to this PDF file when it ends
+        #[arg(long)]
+        pdf: Option<PathBuf>,
+        /// Tint the board by who controls each region
+        #[arg(long)]
+        ownership: bool,
+        /// Number the regions of the drawn tile, to match the meeple placements offered
+        #[arg(long)]
+        region_labels: bool,
+        /// Draw a key under the board of whose meeple are whose, with the meeple each has left
+        #[arg(long)]
+        legend: bool,
+        /// Draw the board with your chosen move on it and ask you to confirm it before it is played
+        #[arg(long)]
+        preview: bool,
+        /// Play in two teams, partnered with the players sitting opposite (every other seat)
+        #[arg(long)]
+        teams: bool,
+        /// How strong the bots play. Easier bots make more random moves
+        #[arg(long, value_enum, default_value_t = Difficulty::Hard)]
+        difficulty: Difficulty,
+        /// After each of your moves, show what the analysis engine would have played, and how
+        /// accurately you played at the end
+        #[arg(long)]
+        teach: bool,
+        /// Language to play in, by its two letter code: en or de
+        #[arg(long, default_value = "en")]
+        language: String,
+        /// The rules to play under
+        #[arg(long, default_value = "standard", value_parser = rules::PRESET_NAMES)]
+        rules: String,
+        /// Play the rules without farmers, keeping meeple out of fields
+        #[arg(long)]
+        no_farmers: bool,
+        /// Pause after every turn, the bots' included, to show the board and answer questions about
+        /// it, until Enter is pressed
+        #[arg(long)]
+        step: bool,
+        /// Keep the board within this many columns and rows of tiles, e.g. 15x15
+        #[arg(long)]
+        board_limit: Option<String>,
+        /// Place the tile for you when it fits in only one place and a meeple isn't worth placing
+        /// on it
+        #[arg(long)]
+        obvious_moves: bool,
+    },
+    /// Print commentary on every turn of a replay
+    Commentary {
+        replay: PathBuf,
+    },
+    /// List every feature on the board of a replay, most valuable first, with its size, open edges,
+    /// meeple and what it would score now
+    Regions {
+        replay: PathBuf,
+        /// List the features before this turn is played, counting from 1, rather than at the end
+        #[arg(long)]
+        turn: Option<usize>,
+        /// Print how the features border each other as a graph instead, in `dot` or `json`
+        #[arg(long)]
+        graph: Option<String>,
+        /// Print the tiles and the outline of each feature as GeoJSON instead, for mapping tools
+        #[arg(long, conflicts_with = "graph")]
+        geojson: bool,
+    },
+    /// Draw the board of a replay with only some features picked out and the rest dimmed
+    Render {
+        replay: PathBuf,
+        /// Draw the board before this turn is played, counting from 1, rather than at the end
+        #[arg(long)]
+        turn: Option<usize>,
+        /// Pick out only features of this type: city, road, field or cloister
+        #[arg(long)]
+        only_region_type: Option<String>,
+        /// Pick out only features this player, by name or colour, has a meeple in
+        #[arg(long)]
+        player: Option<String>,
+    },
+    /// Play every replay in a directory again and check each still adds up to its recorded score,
+    /// e.g. after changing the rules engine
+    Verify {
+        corpus: PathBuf,
+        /// First add this many bot games, played from --seed, to the corpus
+        #[arg(long)]
+        record: Option<usize>,
+    },
+    /// Run every case of a conformance suite, a directory of YAML files each listing positions, a
+    /// move to play in each and what the rules say should come of it
+    #[cfg(feature = "conformance")]
+    Conformance {
+        suite: PathBuf,
+    },
+    /// Find where games recorded by two builds first differ, e.g. to check a change to the engine
+    /// plays the same games. Takes two replays, or two corpora recorded by `verify --record` with
+    /// the same --seed
+    Diverge {
+        first: PathBuf,
+        second: PathBuf,
+    },
+    /// Check each bot chooses a legal move in a set of fixed positions, in time and without panicking
+    Selftest {
+        /// Check only this bot
+        #[arg(long)]
+        bot: Option<String>,
+        /// Milliseconds each bot may take to choose a move
+        #[arg(long, default_value_t = selftest::DEFAULT_BUDGET.as_millis() as u64)]
+        budget: u64,
+    },
+    /// Write a score sheet for a replay as a PDF, with the final board, score breakdown and moves
+    Pdf {
+        replay: PathBuf,
+        output: PathBuf,
+    },
+    /// Turn many replays into GIFs, SVGs of their final boards or HTML pages at once, side by side,
+    /// each named after its replay, with an index.html linking them all
+    Export {
+        /// Replay files, directories of them, or patterns of their names such as `round-2/*.json`
+        #[arg(required = true)]
+        replays: Vec<String>,
+        #[arg(long, value_parser = export::EXPORT_FORMATS)]
+        format: String,
+        /// Directory to write the exports to, made if it doesn't exist
+        #[arg(long)]
+        out: PathBuf,
+        /// Milliseconds between the frames of a GIF
+        #[arg(long, default_value_t = 500)]
+        delay: u64,
+    },
+    /// Replay a game up to a turn, play a different move there, and let the bots finish the game
+    Branch {
+        replay: PathBuf,
+        /// Turn to play differently, counting from 1 as in the analyze output
+        #[arg(long)]
+        turn: usize,
+        #[arg(long, allow_hyphen_values = true)]
+        x: i8,
+        #[arg(long, allow_hyphen_values = true)]
+        y: i8,
+        /// Clockwise quarter turns, 0 to 3
+        #[arg(long, alias = "rotations", default_value = "0", value_parser = parse_rotation)]
+        rotation: Rotation,
+        /// Region index of the tile to place a meeple in
+        #[arg(long)]
+        meeple: Option<usize>,
+        /// Write the branched game to this file as a replay
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Draw a tile in all four rotations, with each region's index where its meeple would stand
+    ShowTile {
+        /// Tile name, the start of one, or its id
+        name: String,
+        /// Draw only this rotation, counted in clockwise quarter turns
+        #[arg(long, value_parser = parse_rotation)]
+        rotation: Option<Rotation>,
+    },
+    /// Check every tile definition's meeple spots are on their own region's cells, suggesting a
+    /// spot for any that aren't. With a tile, print the spot worked out for each of its regions
+    CheckMeepleSpots {
+        /// Tile name, the start of one, or its id
+        tile: Option<String>,
+    },
+    /// Lay out the board drawn in a sketch file, checking each tile fits, and draw it. The file has
+    /// a row of tiles to a line, each a name or id turned with `@`, e.g. `corner-city@90`, and `.`
+    /// for an empty square
+    Sketch {
+        path: PathBuf,
+    },
+    /// Work with sets of tile definitions
+    Tiles {
+        #[command(subcommand)]
+        command: TilesCommand,
+    },
+    /// Host games for clients to play over HTTP
+    #[cfg(feature = "server")]
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:3000")]
+        address: std::net::SocketAddr,
+        /// Seconds to keep a seat for a client that stops responding, before its turns are passed
+        #[arg(long, default_value_t = 60)]
+        reconnect_grace: u64,
+        /// Directory of `<tile id>.png` images to draw boards with
+        #[arg(long)]
+        artwork: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum TilesCommand {
+    /// Show which tile definitions the second set adds or removes from the first, and how their
+    /// counts differ, drawing the added tiles. A set is `base`, an expansion's name, or several
+    /// joined with `+`, e.g. `base+river`
+    Diff {
+        first: String,
+        second: String,
+    },
+}
 
 fn main() {
+    let cli = Cli::parse();
+
+    let seed: Seed = match &cli.seed {
+        Some(seed_string) => parse_seed(seed_string).unwrap_or_else(|| {
+            eprintln!("Seed must be 32 bytes of url safe base64");
+            exit(1)
+        }),
+        None => OsRng.gen(),
+    };
 
-    let seed: [u8; 32] = OsRng.gen();
+    let style = RenderConfig::new().with_style(cli.style.as_deref().and_then(RenderStyle::from_name)).forced(cli.force_style).resolve();
     // let seed = [210, 233, 120, 7, 69, 3, 119, 55, 175, 78, 62, 244, 9, 228, 209, 19, 30, 87, 10, 94, 40, 240, 237, 33, 213, 63, 135, 34, 17, 176, 193, 162];
 
-    let seed_string = general_purpose::URL_SAFE.encode(&seed);
+    let seed_string = general_purpose::URL_SAFE.encode(seed);
 
     println!("{}", seed_string);
 
-    let rng = Rc::new(RefCell::new(StdRng::from_seed(seed)));
+    crash_report::install(cli.crash_report.clone(), reproduction_command(&seed_string));
+
+    let default_command = Command::Simulate { iterations: 100, replays: None, binary_replays: false, report: None, csv: None, baseline: None, contact_sheet: None, dataset: None, webhook: None, step: false, board_sizes: false, board_stats: false, rules: "standard".to_string(), no_farmers: false, seating: "fixed".to_string(), config: None };
+
+    match cli.command.unwrap_or(default_command) {
+        Command::Simulate { iterations, replays, binary_replays, report, csv, baseline, contact_sheet, dataset, webhook, step, board_sizes, board_stats, rules, no_farmers, seating, config } => {
+            let replay_format = if binary_replays { ReplayFormat::Binary } else { ReplayFormat::Json };
+            let seating = match seating.as_str() {
+                "rotate" => Seating::Rotate,
+                "shuffle" => Seating::Shuffle,
+                "permute" => Seating::Permute,
+                _ => Seating::Fixed,
+            };
+
+            match config {
+                Some(path) => {
+                    let (config, output) = experiment_simulation(&path, seed);
+                    simulate(config, output, step, board_sizes, board_stats, style)
+                }
+                None => {
+                    let config = simulation_under(default_simulation(seed, iterations, seating), rule_preset(&rules).with_farmers(!no_farmers));
+                    simulate(config, ExperimentOutput { replays, replay_format, report, csv, baseline, contact_sheet, dataset, webhook }, step, board_sizes, board_stats, style)
+                }
+            }
+        }
+        Command::Compare { bot_a, bot_b, max_pairs, alpha, beta, margin, no_river, threads, ladder, webhook } => {
+            let (first, second, report) = compare(seed, &bot_a, &bot_b, max_pairs, Sprt { alpha, beta, margin }, !no_river, threads, webhook.as_deref());
+            update_ladder(&ladder, &first, &second, &report)
+        }
+        Command::Sweep { bot, param, games, opponents, no_river, threads } => sweep(seed, &bot, &param, games, &opponents, !no_river, threads),
+        Command::Ladder { ladder } => print!("{}", load_ladder(&ladder).render_standings()),
+        Command::CompareRules { baseline, alternative, iterations, no_river, webhook } => {
+            let config = simulation_under(default_simulation(seed, iterations, Seating::Fixed), rule_preset(&baseline)).with_river(!no_river);
+            let mut observers: Vec<Box<dyn GameObserver>> = vec![];
+
+            #[cfg(feature = "webhook")]
+            let webhook = webhook.map(|url| Rc::new(RefCell::new(carcassonne::webhook::WebhookNotifier::new(&url, config.game_count() * 2))));
+            #[cfg(feature = "webhook")]
+            if let Some(notifier) = &webhook {
+                observers.push(Box::new(Rc::clone(notifier)));
+            }
+
+            #[cfg(not(feature = "webhook"))]
+            if webhook.is_some() {
+                eprintln!("Posting to a webhook needs the webhook feature");
+                exit(1)
+            }
+
+            let comparison = compare_rules_observed(config, rule_preset(&alternative), &mut observers).unwrap_or_else(|error| {
+                eprintln!("Could not compare the rules: {:?}", error);
+                exit(1)
+            });
+
+            #[cfg(feature = "webhook")]
+            if let Some(notifier) = &webhook {
+                for failure in notifier.borrow_mut().finish() {
+                    eprintln!("Could not post to webhook: {}", failure);
+                }
+            }
+
+            println!("{} against {} over {} games", alternative, baseline, comparison.baseline.report.games.len());
+            print!("{}", render_rules_comparison(&comparison));
+        }
+        Command::Analyze { replay, threshold, boards } => analyze(&replay, threshold, boards, &style),
+        Command::Demo { bots, delay, no_river } => demo(seed, &bots, Duration::from_millis(delay), !no_river, style),
+        Command::Play { opponents, no_river, expansions, pdf, ownership, region_labels, legend, preview, teams, difficulty, teach, language, rules, no_farmers, step, board_limit, obvious_moves } => {
+            let expansions = match expansions {
+                Some(list) => parse_expansions(&list).unwrap_or_else(|error| {
+                    let names: Vec<_> = Expansion::ALL.iter().map(Expansion::name).collect();
+                    eprintln!("Invalid expansions: {}, expected some of: {}", error, names.join(", "));
+                    exit(1)
+                }),
+                None if no_river => vec![],
+                None => vec![Expansion::River],
+            };
+
+            let language = Language::from_code(&language).unwrap_or_else(|| {
+                let codes: Vec<_> = Language::ALL.iter().map(Language::code).collect();
+                eprintln!("Unknown language [{}], expected one of: {}", language, codes.join(", "));
+                exit(1)
+            });
+
+            let mut rules = rule_preset(&rules).with_farmers(!no_farmers);
+
+            if let Some(limit) = board_limit {
+                let Some((Ok(width), Ok(height))) = limit.split_once('x').map(|(width, height)| (width.parse(), height.parse())) else {
+                    eprintln!("Invalid board limit [{}], expected columns x rows, e.g. 15x15", limit);
+                    exit(1)
+                };
+
+                rules = rules.with_board_limit(width, height);
+            }
+
+            let rules = rules.with_obvious_moves_played(obvious_moves);
+
+            play(seed, &opponents, PlayOptions { expansions, pdf, ownership, region_labels, legend, preview, teams, difficulty, teach, language, rules, step }, style)
+        }
+        Command::Commentary { replay } => commentate(&replay),
+        Command::Regions { replay, turn, graph, geojson } => list_regions(&replay, turn, graph.as_deref(), geojson),
+        Command::Verify { corpus, record } => verify(&corpus, record, seed),
+        #[cfg(feature = "conformance")]
+        Command::Conformance { suite } => conformance(&suite),
+        Command::Diverge { first, second } => diverge(&first, &second, &style),
+        Command::Selftest { bot, budget } => run_selftest(bot.as_deref(), Duration::from_millis(budget), seed),
+        Command::Pdf { replay, output } => write_pdf(&load_replay(&replay), &output),
+        Command::Export { replays, format, out, delay } => export_replays(&replays, &format, &out, Duration::from_millis(delay)),
+        Command::Branch { replay, turn, x, y, rotation, meeple, output } => {
+            branch(&replay, turn, ReplayPlacement { x, y, rotation, meeple, recalled: None }, output)
+        }
+        Command::Render { replay, turn, only_region_type, player } => {
+            render_highlighted(&replay, turn, only_region_type.as_deref(), player.as_deref(), &style)
+        }
+        Command::ShowTile { name, rotation } => show_tile(&name, rotation, &style),
+        Command::CheckMeepleSpots { tile } => check_meeple_spots(tile.as_deref()),
+        Command::Sketch { path } => show_sketch(&path, &style),
+        Command::Tiles { command: TilesCommand::Diff { first, second } } => diff_tile_sets(&first, &second, &style),
+        #[cfg(feature = "server")]
+        Command::Serve { address, reconnect_grace, artwork } => serve(address, reconnect_grace, artwork),
+    }
+}
+
+fn show_tile(name: &str, rotation: Option<Rotation>, style: &RenderStyle) {
+    let tile = tile_definitions::find(name).unwrap_or_else(|| {
+        eprintln!("No tile matches {:?}", name);
+        exit(1)
+    });
+
+    let rotations = match rotation {
+        Some(rotation) => vec![rotation],
+        None => Rotation::ALL.to_vec(),
+    };
+
+    println!("{} (id {}, {} distinct rotations)", tile.name(), tile.id(), tile.symmetry_class());
+    println!("{}", tile.render_preview(&rotations, style));
+}
+
+fn check_meeple_spots(name: Option<&str>) {
+    let Some(name) = name else {
+        let misplaced = meeple_spots::check_all_meeple_spots();
+
+        if misplaced.is_empty() {
+            println!("Every meeple spot is on its own region");
+            return;
+        }
+
+        for spot in &misplaced {
+            println!("{}", spot);
+        }
+
+        exit(1)
+    };
+
+    let tile = tile_definitions::find(name).unwrap_or_else(|| {
+        eprintln!("No tile matches {:?}", name);
+        exit(1)
+    });
+
+    for (index, (region_type, spot)) in meeple_spots::derived_meeple_spots(tile).into_iter().enumerate() {
+        match spot {
+            Some((x, y)) => println!("region {} ({:?}): TileCoordinate {{ x: {}, y: {} }}", index, region_type, x, y),
+            None => println!("region {} ({:?}): no meeple spot", index, region_type),
+        }
+    }
+
+    for spot in meeple_spots::check_meeple_spots(tile) {
+        println!("{}", spot);
+    }
+}
+
+fn show_sketch(path: &Path, style: &RenderStyle) {
+    let sketch = fs::read_to_string(path).unwrap_or_else(|error| {
+        eprintln!("Could not read sketch {}: {}", path.display(), error);
+        exit(1)
+    });
+
+    let board = Board::from_sketch(&sketch).unwrap_or_else(|error| {
+        eprintln!("Could not lay out sketch {}: {}", path.display(), error);
+        exit(1)
+    });
+
+    println!("{}", board.render(style));
+}
+
+fn diff_tile_sets(first: &str, second: &str, style: &RenderStyle) {
+    let load = |name: &str| {
+        deck::tile_set(name).unwrap_or_else(|error| {
+            eprintln!("Unknown tile set [{}]: {}", name, error);
+            exit(1)
+        })
+    };
+
+    let (first_set, second_set) = (load(first), load(second));
+    let diff = deck::TileSetDiff::between(&first_set, &second_set);
+
+    println!("{}: {} tiles of {} definitions", first, diff.totals.0, first_set.len());
+    println!("{}: {} tiles of {} definitions", second, diff.totals.1, second_set.len());
+
+    if diff.is_empty() {
+        println!("\nThe sets have the same tiles");
+        return;
+    }
+
+    if !diff.added.is_empty() {
+        println!("\nAdded in {}:", second);
+
+        for (tile, count) in &diff.added {
+            println!("  +{} {} (id {})", count, tile.name(), tile.id());
+            println!("{}", tile.render_preview(&[Rotation::R0], style));
+        }
+    }
+
+    if !diff.removed.is_empty() {
+        println!("\nRemoved from {}:", first);
+
+        for (tile, count) in &diff.removed {
+            println!("  -{} {} (id {})", count, tile.name(), tile.id());
+        }
+    }
+
+    if !diff.recounted.is_empty() {
+        println!("\nRecounted:");
+
+        for (tile, first_count, second_count) in &diff.recounted {
+            println!("  {} (id {}): {} -> {}", tile.name(), tile.id(), first_count, second_count);
+        }
+    }
+}
+
+fn parse_rotation(quarter_turns: &str) -> Result<Rotation, String> {
+    let quarter_turns: u8 = quarter_turns.parse().map_err(|error: std::num::ParseIntError| error.to_string())?;
+
+    Rotation::try_from(quarter_turns).map_err(|error| error.to_string())
+}
+
+fn parse_seed(seed_string: &str) -> Option<Seed> {
+    general_purpose::URL_SAFE.decode(seed_string).ok()?.try_into().ok()
+}
+
+/// The command line this was run with, seeded with `seed_string` so it plays the same games again
+fn reproduction_command(seed_string: &str) -> String {
+    let mut args = std::env::args();
+    let mut command = vec![args.next().unwrap_or_else(|| "carcassonne".to_string()), "--seed".to_string(), seed_string.to_string()];
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                args.next();
+            }
+            _ if arg.starts_with("--seed=") => {}
+            _ => command.push(arg),
+        }
+    }
+
+    command.join(" ")
+}
+
+/// The bots played when no experiment is given
+/// The simulation under `rules`, exiting if they aren't for as many players as it has
+fn simulation_under(config: SimulationConfig, rules: RuleSet) -> SimulationConfig {
+    config.with_rules(rules).unwrap_or_else(|error| {
+        eprintln!("Could not set up the simulation: {:?}", error);
+        exit(1)
+    })
+}
+
+fn default_simulation(seed: Seed, iteration_count: usize, seating: Seating) -> SimulationConfig {
+    let mut rng = GameRng::from_seed(seed);
 
     let jerk_bot = BotStrategy::Jerk(JerkBot);
-    let fill_the_grid_bot = BotStrategy::FillTheGrid(FillTheGridBot::new(StdRng::from_rng(rng.borrow_mut().deref_mut()).unwrap()));
-    let rando_bot = BotStrategy::Rando(RandoBot::new(StdRng::from_rng(rng.borrow_mut().deref_mut()).unwrap()));
-    let myopic_bot = BotStrategy::Myopic(MyopicBot);
+    let _fill_the_grid_bot = BotStrategy::FillTheGrid(FillTheGridBot::new(GameRng::from_rng(&mut rng).unwrap()));
+    let rando_bot = BotStrategy::Rando(RandoBot::new(GameRng::from_rng(&mut rng).unwrap()));
+    let _myopic_bot = BotStrategy::Myopic(MyopicBot);
     let lazy_bot = BotStrategy::Lazy(LazyBot);
 
     let alice = Player::red().with_name("Alice").with_bot(lazy_bot.clone());
     let bob = Player::green().with_name("Bob").with_bot(lazy_bot);
-    let carol = Player::blue().with_name("Carol").with_bot(rando_bot);
-    let dave = Player::yellow().with_name("Dave").with_bot(jerk_bot);
-
-    let mut overall_score = Score::new();
-
-    let iteration_count = 100;
-    let render_style = RenderStyle::TrueColor;
+    let _carol = Player::blue().with_name("Carol").with_bot(rando_bot);
+    let _dave = Player::yellow().with_name("Dave").with_bot(jerk_bot);
 
     // let alice_rando = Player::red().with_name("Alice").with_bot(rando_bot.clone());
     // let bob_rando = Player::green().with_name("Bob").with_bot(rando_bot.clone());
-    let players: IndexMap<_, BotPlayer> = vec![
+    let players = vec![
         // alice_rando,
         // bob_rando,
         alice,
         bob,
         // carol,
         // dave
-    ]
-        .into_iter()
-        .map(|p| (p.player.meeple_color, p))
-        .collect();
+    ];
+
+    let config = SimulationConfig::new(players, iteration_count, rng.gen()).unwrap_or_else(|error| {
+        eprintln!("Could not set up the simulation: {:?}", error);
+        exit(1)
+    });
+
+    config.with_seating(seating)
+}
+
+/// The simulation an experiment file describes, with its seed if it names one, and where to write its output
+fn experiment_simulation(path: &PathBuf, seed: Seed) -> (SimulationConfig, ExperimentOutput) {
+    let experiment = Experiment::load(path).unwrap_or_else(|error| {
+        eprintln!("Could not load experiment {}: {:?}", path.display(), error);
+        exit(1)
+    });
+
+    let config = experiment
+        .seed()
+        .and_then(|experiment_seed| experiment.config(experiment_seed.unwrap_or(seed)))
+        .unwrap_or_else(|error| {
+            eprintln!("Invalid experiment {}: {:?}", path.display(), error);
+            exit(1)
+        });
+
+    if let Some(name) = &experiment.name {
+        println!("Experiment: {}", name);
+    }
+
+    (config, experiment.output)
+}
+
+fn simulate(config: SimulationConfig, output: ExperimentOutput, step: bool, board_sizes: bool, board_stats: bool, render_style: RenderStyle) {
+    let game_count = config.game_count();
+
+    #[allow(unused_mut)]
+    let mut observers: Vec<Box<dyn GameObserver>> = vec![
+        #[cfg(feature = "progress")]
+        Box::new(carcassonne::progress::ProgressObserver::new(game_count as u64)),
+    ];
+
+    if let Some(directory) = output.replays {
+        fs::create_dir_all(&directory).unwrap_or_else(|error| {
+            eprintln!("Could not create replay directory {}: {}", directory.display(), error);
+            exit(1)
+        });
+
+        observers.push(Box::new(ReplayRecorder::to_directory(directory).with_format(output.replay_format)));
+    }
+
+    let meeple = Rc::new(RefCell::new(MeepleTracker::default()));
+    let records = Rc::new(RefCell::new(Records::default()));
+
+    if board_stats {
+        observers.push(Box::new(Rc::clone(&meeple)));
+        observers.push(Box::new(Rc::clone(&records)));
+    }
+
+    let contests = Rc::new(RefCell::new(ContestStats::default()));
+    observers.push(Box::new(Rc::clone(&contests)));
+
+    let tiles = Rc::new(RefCell::new(TileStats::default()));
+    observers.push(Box::new(Rc::clone(&tiles)));
+
+    let duplicates = Rc::new(RefCell::new(DuplicateGames::default()));
+    observers.push(Box::new(Rc::clone(&duplicates)));
+
+    #[cfg(feature = "alloc-stats")]
+    let memory = Rc::new(RefCell::new(carcassonne::alloc_stats::MemoryObserver::default()));
+    #[cfg(feature = "alloc-stats")]
+    observers.push(Box::new(Rc::clone(&memory)));
+
+    let contact_sheet = Rc::new(RefCell::new(ContactSheet::new()));
+
+    if output.contact_sheet.is_some() {
+        observers.push(Box::new(Rc::clone(&contact_sheet)));
+    }
+
+    let dataset = Rc::new(RefCell::new(DatasetRecorder::new()));
+
+    if output.dataset.is_some() {
+        observers.push(Box::new(Rc::clone(&dataset)));
+    }
+
+    if step {
+        observers.push(Box::new(StepDebugger::stdin(render_style)));
+    }
+
+    if board_sizes {
+        observers.push(Box::new(BoardGrowth::stderr()));
+    }
+
+    #[cfg(feature = "webhook")]
+    let webhook = output.webhook.as_deref().map(|url| Rc::new(RefCell::new(carcassonne::webhook::WebhookNotifier::new(url, game_count))));
+    #[cfg(feature = "webhook")]
+    if let Some(notifier) = &webhook {
+        observers.push(Box::new(Rc::clone(notifier)));
+    }
+
+    #[cfg(not(feature = "webhook"))]
+    if output.webhook.is_some() {
+        eprintln!("Posting to a webhook needs the webhook feature");
+        exit(1)
+    }
+
+    let report = simulate::run_observed(config.clone(), &mut observers);
+
+    #[cfg(feature = "webhook")]
+    if let Some(notifier) = &webhook {
+        for failure in notifier.borrow_mut().finish() {
+            eprintln!("Could not post to webhook: {}", failure);
+        }
+    }
+
+    if let Some(path) = &output.report {
+        fs::write(path, report.to_json(&config)).unwrap_or_else(|error| {
+            eprintln!("Could not write report {}: {}", path.display(), error);
+            exit(1)
+        });
+    }
+
+    if let Some(path) = &output.contact_sheet {
+        fs::write(path, contact_sheet.borrow().render_html()).unwrap_or_else(|error| {
+            eprintln!("Could not write contact sheet {}: {}", path.display(), error);
+            exit(1)
+        });
+    }
+
+    if let Some(path) = &output.dataset {
+        dataset.borrow_mut().save(path).unwrap_or_else(|error| {
+            eprintln!("Could not write dataset {}: {}", path.display(), error);
+            exit(1)
+        });
+    }
+
+    if let Some(path) = &output.csv {
+        fs::write(path, report.to_csv()).unwrap_or_else(|error| {
+            eprintln!("Could not write CSV {}: {}", path.display(), error);
+            exit(1)
+        });
+    }
+
+    println!("Elapsed: {:.2?}", report.duration);
+    println!("Completed {} iterations. Final score is\n{}", report.games.len(), report.total_score().render(&report.players, &render_style));
+
+    for (id, usage) in &meeple.borrow().usage {
+        let name = report.players.get(id).and_then(|player| player.name()).map(str::to_string).unwrap_or_else(|| id.to_string());
+
+        println!(
+            "{}: meeple on the board {:.0}% of the time, {:.1} points per deployment over {} deployments (gini {:.2})",
+            name,
+            usage.utilisation() * 100.0,
+            usage.points_per_deployment(),
+            usage.deployments,
+            usage.gini()
+        );
+    }
+
+    for (id, contest) in &contests.borrow().records {
+        let name = report.players.get(id).and_then(|player| player.name()).map(str::to_string).unwrap_or_else(|| id.to_string());
+
+        println!(
+            "{}: in {} contested features with {:.1} meeple on average, won {} by {:.1} meeple on average, shared {} and lost {}",
+            name,
+            contest.contested,
+            contest.mean_meeple(),
+            contest.won,
+            contest.mean_margin(),
+            contest.shared,
+            contest.lost()
+        );
+    }
+
+    println!("Mean game length: {:.1} turns", report.mean_turn_count());
+
+    for phase in [GamePhase::River, GamePhase::Main] {
+        let think_time = report.phase_think_time(phase);
+
+        if think_time.moves > 0 {
+            println!("Think time in the {} phase: {:.2?} per move over {} moves", phase.name(), think_time.mean(), think_time.moves);
+        }
+    }
+
+    for (id, player) in &report.players {
+        let name = player.name().map(str::to_string).unwrap_or_else(|| id.to_string());
+        let think_time = report.think_time(*id);
+
+        println!("{}: {:.2?} per move over {} moves", name, think_time.mean(), think_time.moves);
+
+        let features = report.features(*id);
+        let per_game = |count: usize| count as f64 / report.games.len().max(1) as f64;
+
+        println!(
+            "{}: per game, completed {:.1} cities, {:.1} roads and {:.1} cloisters, and held {:.1} farms",
+            name,
+            per_game(features.cities),
+            per_game(features.roads),
+            per_game(features.cloisters),
+            per_game(features.farms)
+        );
+    }
+
+    if board_stats {
+        println!("{}", records.borrow());
+    }
+
+    println!("{}", render_tile_table(&tiles.borrow()));
+
+    let duplicates = duplicates.borrow();
+    println!("Distinct final positions: {} of {} games", duplicates.distinct_positions(), duplicates.game_count());
+
+    for games in duplicates.duplicate_games() {
+        println!("Games {:?} played the same moves, so their random number generators may not be independent", games);
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    {
+        use carcassonne::alloc_stats::Subsystem;
+
+        let memory = memory.borrow();
+        println!("Peak memory of a game: {} KiB", memory.peak_bytes() / 1024);
+
+        for subsystem in Subsystem::ALL {
+            let usage = memory.mean_usage(subsystem);
+            println!("{}: {} allocations, {} KiB per game", subsystem.name(), usage.allocations, usage.bytes / 1024);
+        }
+    }
+
+    let baseline = output.baseline.as_ref().and_then(|path| {
+        SavedReport::load(path)
+            .map_err(|error| eprintln!("Could not read baseline report {}: {:?}", path.display(), error))
+            .ok()
+    });
+
+    print!("{}", report.render_summary(baseline.as_ref()));
+    println!("{}", report.render_timing(baseline.as_ref()));
+}
+
+/// Plays the bots against each other until the test decides which is stronger, returning them and
+/// the games they played
+#[allow(clippy::too_many_arguments)]
+fn compare(seed: Seed, bot_a: &str, bot_b: &str, max_pairs: usize, sprt: Sprt, include_river: bool, threads: Option<usize>, webhook: Option<&str>) -> (BotStrategy, BotStrategy, PairedReport) {
+    let mut rng = GameRng::from_seed(seed);
+
+    let mut named_bot = |name: &str| -> BotStrategy {
+        BotStrategy::from_name(name, &mut rng).unwrap_or_else(|| {
+            eprintln!("Unknown bot [{}], expected one of: {}", name, BOT_NAMES.join(", "));
+            exit(1)
+        })
+    };
+
+    let first = named_bot(bot_a);
+    let second = named_bot(bot_b);
+
+    let mut matchup = PairedMatchup::new(Player::red().with_bot(first.clone()), Player::green().with_bot(second.clone()))
+        .unwrap_or_else(|error| {
+            eprintln!("Could not set up the matchup: {:?}", error);
+            exit(1)
+        })
+        .with_river(include_river);
+
+    if let Some(threads) = threads {
+        matchup = matchup.with_threads(threads);
+    }
+
+    #[cfg(feature = "webhook")]
+    let mut notifier = webhook.map(|url| {
+        carcassonne::webhook::WebhookNotifier::new(url, max_pairs * 2)
+            .with_player_name(MeepleColor::Red, bot_a)
+            .with_player_name(MeepleColor::Green, bot_b)
+    });
+    #[cfg(feature = "webhook")]
+    let mut on_pair = |pair: &PairedGame| {
+        if let Some(notifier) = &mut notifier {
+            notifier.record_result(&pair.original);
+            notifier.record_result(&pair.mirrored);
+        }
+    };
+
+    #[cfg(not(feature = "webhook"))]
+    if webhook.is_some() {
+        eprintln!("Posting to a webhook needs the webhook feature");
+        exit(1)
+    }
+    #[cfg(not(feature = "webhook"))]
+    let mut on_pair = |_: &PairedGame| {};
+
+    let report = matchup.run_sprt_observed(&sprt, max_pairs, rng.gen(), &mut on_pair);
+
+    #[cfg(feature = "webhook")]
+    for failure in notifier.as_mut().map(|notifier| notifier.finish()).unwrap_or_default() {
+        eprintln!("Could not post to webhook: {}", failure);
+    }
+
+    let (wins, losses, draws) = report.paired.win_loss_draw();
+    let (lower, upper) = sprt.bounds();
+    let (ci_low, ci_high) = report.paired.confidence_interval();
+
+    println!("Pairs played: {} (W-L-D {}-{}-{})", report.paired.games.len(), wins, losses, draws);
+    println!("LLR: {:.3} (bounds {:.3}, {:.3})", report.log_likelihood_ratio, lower, upper);
+    println!(
+        "Mean paired differential for {}: {:.2} (95% CI {:.2} to {:.2})",
+        bot_a, report.paired.mean_differential(), ci_low, ci_high
+    );
+
+    match report.decision {
+        SprtDecision::FirstIsStronger => println!("{} is stronger than {}", bot_a, bot_b),
+        SprtDecision::SecondIsStronger => println!("{} is stronger than {}", bot_b, bot_a),
+        SprtDecision::Inconclusive => println!("No decision after {} pairs", max_pairs),
+    }
+
+    (first, second, report.paired)
+}
+
+fn sweep(seed: Seed, bot: &str, param: &str, games: usize, opponents: &[String], include_river: bool, threads: Option<usize>) {
+    let mut rng = GameRng::from_seed(seed);
+
+    let mut named_bot = |name: &str| -> BotStrategy {
+        BotStrategy::from_name(name, &mut rng).unwrap_or_else(|| {
+            eprintln!("Unknown bot [{}], expected one of: {}", name, BOT_NAMES.join(", "));
+            exit(1)
+        })
+    };
+
+    let swept = named_bot(bot);
+    let pool: Vec<BotStrategy> = opponents.iter().map(|name| named_bot(name)).collect();
+
+    let mut sweep = ParameterRange::parse(param)
+        .and_then(|range| Sweep::new(swept, range, pool))
+        .unwrap_or_else(|error| {
+            eprintln!("Could not set up the sweep: {}", error);
+            exit(1)
+        })
+        .with_river(include_river);
+
+    if let Some(threads) = threads {
+        sweep = sweep.with_threads(threads);
+    }
 
-    let now = Instant::now();
+    // each pair is two games, played against every opponent
+    let pairs = (games / (2 * opponents.len())).max(1);
+    let points = sweep.run(pairs, rng.gen());
 
-    for _ in 0..iteration_count {
+    println!("{} pairs of games with each value against {}", pairs * opponents.len(), opponents.join(", "));
+    println!("{:>10} {:>12} {:>20} {:>12}", "value", "mean diff", "95% CI", "W-L-D");
 
-        let mut players = players.clone();
+    for point in &points {
+        let (low, high) = point.report.confidence_interval();
+        let (wins, losses, draws) = point.report.win_loss_draw();
+        // values not clearly worse than the best could be as good, given more games
+        let contender = if point.clearly_worse_than(&points[0]) { "" } else { " *" };
 
+        println!(
+            "{:>10} {:>12.2} {:>20} {:>12}{}",
+            point.value,
+            point.report.mean_differential(),
+            format!("{:.2} to {:.2}", low, high),
+            format!("{}-{}-{}", wins, losses, draws),
+            contender
+        );
+    }
+
+    println!("* not clearly worse than the best value, {}", points[0].value);
+}
+
+fn load_ladder(path: &PathBuf) -> Ladder {
+    Ladder::load(path).unwrap_or_else(|error| {
+        eprintln!("Could not load ladder {}: {}", path.display(), error);
+        exit(1)
+    })
+}
+
+/// Rates the bots on the ladder by the games they played, and prints their new ratings
+fn update_ladder(path: &PathBuf, first: &BotStrategy, second: &BotStrategy, report: &PairedReport) {
+    let mut ladder = load_ladder(path);
+    let rating = |ladder: &Ladder, bot| ladder.entry(bot).map_or(ladder::INITIAL_RATING, |entry| entry.rating);
+    let (first_before, second_before) = (rating(&ladder, first), rating(&ladder, second));
+
+    ladder.record_paired(report, first, second);
+
+    ladder.save(path).unwrap_or_else(|error| {
+        eprintln!("Could not write ladder {}: {}", path.display(), error);
+        exit(1)
+    });
+
+    for (bot, before) in [(first, first_before), (second, second_before)] {
+        let after = rating(&ladder, bot);
+        println!("{} now rated {:.0} ({:+.0})", bot.name(), after, after - before);
+    }
+}
+
+fn load_replay(path: &PathBuf) -> Replay {
+    Replay::load(path).unwrap_or_else(|error| {
+        eprintln!("Could not load replay {}: {}", path.display(), error);
+        exit(1)
+    })
+}
+
+fn analyze(path: &PathBuf, threshold: i32, boards: bool, style: &RenderStyle) {
+    let replay = load_replay(path);
+
+    let report = analysis::find_blunders(&replay, threshold).unwrap_or_else(|error| {
+        eprintln!("Replay is invalid: {}", error);
+        exit(1)
+    });
+
+    let luck = analysis::luck(&replay).expect("replay was validated by the analysis");
+
+    // farms are scored only at the end, so how well they were set up shows best halfway through
+    let halfway = replay.state_at(replay.turns.len() / 2).expect("replay was validated by the analysis");
+    let end = replay.state_at(replay.turns.len()).expect("replay was validated by the analysis");
+    let unseen = analysis::unseen_tiles(&halfway.board);
+    let risk = analysis::open_feature_risk(&halfway.board, &unseen);
+
+    for player in &replay.players {
+        let name = player.name.clone().unwrap_or_else(|| player.color.to_string());
+        let blunders: Vec<_> = report.blunders_by(player.color).collect();
+
+        println!(
+            "{}: {} blunders in {} moves, {} points lost, draws {:+.1} points luckier than average",
+            name,
+            blunders.len(),
+            report.moves.get(&player.color).copied().unwrap_or(0),
+            report.points_lost(player.color),
+            luck.get(&player.color).copied().unwrap_or(0.0)
+        );
+
+        println!(
+            "  farms expected to score {:.1} points halfway through, scored {}",
+            analysis::farm_equity(&halfway.board, player.color, &unseen),
+            analysis::farm_equity(&end.board, player.color, &[])
+        );
+
+        if let Some(features) = risk.get(&player.color) {
+            println!(
+                "  {:.1} of {} meeple in open features halfway through expected to be stranded",
+                features.iter().map(|feature| feature.stranded_meeple()).sum::<f64>(),
+                features.iter().map(|feature| feature.meeple).sum::<usize>()
+            );
+        }
+
+        for blunder in blunders {
+            println!(
+                "  turn {:>3} {:<40} played {:>3}, best {:>3} at ({}, {}) {}{}",
+                blunder.turn + 1,
+                blunder.tile.name(),
+                blunder.played_value,
+                blunder.best_value,
+                blunder.best_move.x,
+                blunder.best_move.y,
+                blunder.best_move.rotation,
+                blunder.best_move.meeple.map(|region| format!(" with meeple in region {}", region)).unwrap_or_default(),
+            );
+
+            if boards {
+                let before = replay.state_at(blunder.turn).expect("replay was validated by the analysis");
+                let after = replay.state_at(blunder.turn + 1).expect("replay was validated by the analysis");
+
+                // piped into a file or a bug report, the diff is more use without colour codes
+                match io::stdout().is_terminal() {
+                    true => println!("{}", render_diff(&before.board, &after.board, style)),
+                    false => println!("{}", render_diff_plain(&before.board, &after.board)),
+                }
+            }
+        }
+    }
+
+    println!("{}", Records::from_replay(&replay).expect("replay was validated by the analysis"));
+}
+
+fn branch(path: &PathBuf, turn: usize, alternative_move: ReplayPlacement, output: Option<PathBuf>) {
+    let replay = load_replay(path);
+
+    let branched = analysis::branch(&replay, turn.saturating_sub(1), alternative_move).unwrap_or_else(|error| {
+        eprintln!("Could not branch at turn {}: {}", turn, error);
+        exit(1)
+    });
+
+    let original_score = replay.final_score();
+    let branched_score = branched.final_score();
+
+    for player in &replay.players {
+        let name = player.name.clone().unwrap_or_else(|| player.color.to_string());
+        let points = |score: &carcassonne::score::Score| score.iter().find(|(id, _)| **id == player.color).map(|(_, s)| *s).unwrap_or(0);
+
+        println!("{}: {} -> {}", name, points(&original_score), points(&branched_score));
+    }
+
+    if let Some(output) = output {
+        branched.save(&output).unwrap_or_else(|error| {
+            eprintln!("Could not write replay {}: {}", output.display(), error);
+            exit(1)
+        });
+    }
+}
+
+fn commentate(path: &PathBuf) {
+    let replay = load_replay(path);
+
+    let commentary = commentary::commentate(&replay).unwrap_or_else(|error| {
+        eprintln!("Replay is invalid: {}", error);
+        exit(1)
+    });
+
+    for (turn, text) in commentary.iter().enumerate() {
+        println!("Turn {}: {}", turn + 1, text);
+    }
+}
+
+fn list_regions(path: &PathBuf, turn: Option<usize>, graph: Option<&str>, geojson: bool) {
+    let replay = load_replay(path);
+
+    let state = replay.state_at(turn.map_or(replay.turns.len(), |turn| turn.saturating_sub(1))).unwrap_or_else(|error| {
+        eprintln!("Replay is invalid: {}", error);
+        exit(1)
+    });
+
+    if geojson {
+        println!("{}", state.board.render_geojson());
+        return;
+    }
+
+    match graph {
+        None => print!("{}", render_region_table(&state.board)),
+        Some("dot") => print!("{}", state.board.region_graph_dot()),
+        Some("json") => println!("{}", state.board.region_graph_json()),
+        Some(format) => {
+            eprintln!("Unknown graph format {:?}, expected dot or json", format);
+            exit(1)
+        }
+    }
+}
+
+fn render_highlighted(path: &PathBuf, turn: Option<usize>, region_type: Option<&str>, player: Option<&str>, style: &RenderStyle) {
+    let replay = load_replay(path);
 
-        let player_ids: Vec<_> = players.keys().copied().collect();
-        let mut player_id_iter = player_ids.iter().cycle();
+    let state = replay.state_at(turn.map_or(replay.turns.len(), |turn| turn.saturating_sub(1))).unwrap_or_else(|error| {
+        eprintln!("Replay is invalid: {}", error);
+        exit(1)
+    });
+
+    let region_type = region_type.map(|name| match name.to_lowercase().as_str() {
+        "city" => RegionType::City,
+        "road" => RegionType::Road,
+        "field" | "farm" => RegionType::Field,
+        "cloister" => RegionType::Cloister,
+        _ => {
+            eprintln!("Unknown region type [{}], expected one of: city, road, field, cloister", name);
+            exit(1)
+        }
+    });
 
-        let mut score = Score::new();
-        let board = Arc::new(RwLock::new(Board::new()));
+    let player = player.map(|name| {
+        let found = replay.players.iter().find(|player| {
+            player.name.as_ref().is_some_and(|player_name| player_name.eq_ignore_ascii_case(name)) || player.color.to_string().eq_ignore_ascii_case(name)
+        });
+
+        match found {
+            Some(player) => player.color,
+            None => {
+                let names: Vec<_> = replay.players.iter().map(|player| player.name.clone().unwrap_or_else(|| player.color.to_string())).collect();
+                eprintln!("Unknown player [{}], expected one of: {}", name, names.join(", "));
+                exit(1)
+            }
+        }
+    });
 
-        let board_clone = Arc::clone(&board);
+    println!("{}", state.board.render_highlighted(&RegionFilter { region_type, player, ..Default::default() }, style));
+}
 
-        let deck = Deck::new(true, rng.clone(), move |tile| {
-            !board_clone
-                .read()
-                .unwrap()
-                .get_move_hints(tile, false)
-                .is_empty()
+fn verify(corpus: &PathBuf, record: Option<usize>, seed: Seed) {
+    if let Some(games) = record {
+        let written = carcassonne::corpus::record_corpus(default_simulation(seed, games, Seating::Fixed), corpus).unwrap_or_else(|error| {
+            eprintln!("Could not record games to {}: {}", corpus.display(), error);
+            exit(1)
         });
 
-        for tile in deck {
-            let player_id = player_id_iter
-                .next()
-                .expect("should always have a next player while tiles remain");
-
-            let BotPlayer { player,bot } = players.get_mut(player_id).expect("should exist");
-
-            let selected_move_hint = bot.select_hint(&board.read().unwrap(), &player, tile);
-
-            if let Some(random_move) = selected_move_hint {
-                let tile = PlacedTile {
-                    tile,
-                    placement: random_move.tile_placement.clone(),
-                    meeple: if let (Some(region_index), Some(meeple)) =
-                        (random_move.meeple_placement, player.meeple.pop())
-                    {
-                        Some((region_index, meeple))
-                    } else {
-                        None
-                    },
+        println!("Recorded {} games", written.len());
+    }
+
+    let verified = carcassonne::corpus::verify_corpus(corpus).unwrap_or_else(|error| {
+        eprintln!("Could not read corpus {}: {}", corpus.display(), error);
+        exit(1)
+    });
+
+    let mut failures = 0;
+
+    for (path, result) in &verified {
+        if let Err(error) = result {
+            failures += 1;
+            println!("{}: {}", path.display(), error);
+        }
+    }
+
+    println!("{} of {} replays verified", verified.len() - failures, verified.len());
+
+    if failures > 0 {
+        exit(1)
+    }
+}
+
+#[cfg(feature = "conformance")]
+fn conformance(suite: &Path) {
+    let results = carcassonne::conformance::run_suite(suite).unwrap_or_else(|error| {
+        eprintln!("Could not read conformance suite: {}", error);
+        exit(1)
+    });
+
+    let mut failures = 0;
+
+    for (path, name, result) in &results {
+        if let Err(failure) = result {
+            failures += 1;
+            println!("{} [{}]: {}", path.display(), name, failure);
+        }
+    }
+
+    println!("{} of {} cases passed", results.len() - failures, results.len());
+
+    if failures > 0 {
+        exit(1)
+    }
+}
+
+fn diverge(first: &PathBuf, second: &PathBuf, style: &RenderStyle) {
+    let compared = match first.is_dir() {
+        true => carcassonne::corpus::compare_corpora(first, second).unwrap_or_else(|error| {
+            eprintln!("Could not read corpus {}: {}", first.display(), error);
+            exit(1)
+        }),
+        false => vec![(first.clone(), Ok(carcassonne::corpus::first_divergence(&load_replay(first), &load_replay(second))))],
+    };
+
+    let mut diverged = 0;
+
+    for (path, result) in &compared {
+        match result {
+            Ok(None) => {}
+            Ok(Some(divergence)) => {
+                diverged += 1;
+
+                let other = match second.is_dir() {
+                    true => second.join(path.file_name().expect("replays are files")),
+                    false => second.clone(),
                 };
 
-                // println!("{}", tile.render_to_lines(RenderStyle::TrueColor).join("\n"));
+                print_divergence(path, &load_replay(path), &load_replay(&other), divergence, style);
+            }
+            Err(error) => {
+                diverged += 1;
+                println!("{}: {}", path.display(), error);
+            }
+        }
+    }
+
+    println!("{} of {} games played the same", compared.len() - diverged, compared.len());
+
+    if diverged > 0 {
+        exit(1)
+    }
+}
+
+/// Prints where the games part ways, with each game's board there
+fn print_divergence(path: &Path, first: &Replay, second: &Replay, divergence: &carcassonne::corpus::Divergence, style: &RenderStyle) {
+    // piped into a file or a bug report, the boards are more use without colour codes
+    let render = |before: &Board, after: &Board| match io::stdout().is_terminal() {
+        true => render_diff(before, after, style),
+        false => render_diff_plain(before, after),
+    };
+
+    match divergence {
+        carcassonne::corpus::Divergence::Move { turn, .. } => {
+            println!("{}: the games part ways on turn {}", path.display(), turn + 1);
 
-                let TilePlacementSuccess { liberated_meeple, score_delta } = board.write().unwrap().place_tile(tile).unwrap();
+            for (label, replay) in [("first", first), ("second", second)] {
+                let played = replay.state_at(*turn).and_then(|mut state| {
+                    let before = state.board.clone();
 
-                score += score_delta;
+                    match replay.turns.get(*turn) {
+                        Some(played) => commentary::describe_turn(&mut state, played).map(|text| (text, before, state.board)),
+                        None => Ok(("The game is over.".to_string(), before.clone(), before)),
+                    }
+                });
 
-                for meeple in liberated_meeple {
-                    players.get_mut(&meeple.color).expect("should exist").player.meeple.push(meeple);
+                match played {
+                    Ok((text, before, after)) => println!("  {}: {}\n{}", label, text, render(&before, &after)),
+                    Err(error) => println!("  {}: can't be played here: {}", label, error),
                 }
-            } else {
-                // panic!("no move hints?")
             }
         }
-        //
-        // println!(
-        //     "Done, board has {} tiles placed",
-        //     board.read().unwrap().placed_tile_count()
-        // );
+        carcassonne::corpus::Divergence::FinalScore { first: first_score, second: second_score } => {
+            let scores = |score: &carcassonne::score::Score| score.iter().map(|(player, points)| format!("{} {}", player, points)).collect::<Vec<_>>().join(", ");
 
-        let board_score = board.read().unwrap().calculate_board_score();
+            println!("{}: the same moves were scored {} and {}", path.display(), scores(first_score), scores(second_score));
 
-        score += board_score;
+            if let Ok(state) = first.state_at(first.turns.len()) {
+                println!("{}", render(&state.board, &state.board));
+            }
+        }
+    }
+}
 
-        // println!("{}", board.read().unwrap().render(&render_style));
-        // println!("Final score is:\n{}", score.render(&players.into_iter().map(|(id, BotPlayer { player, ..})|(id, player)).collect(), &render_style));
+fn run_selftest(only: Option<&str>, budget: Duration, seed: Seed) {
+    let mut rng = GameRng::from_seed(seed);
+    let names: Vec<_> = BOT_NAMES.iter().copied().filter(|name| only.is_none_or(|only| only == *name)).collect();
 
-        overall_score += score;
+    if names.is_empty() {
+        eprintln!("Unknown bot [{}], expected one of: {}", only.unwrap_or_default(), BOT_NAMES.join(", "));
+        exit(1)
     }
 
-    let elapsed = now.elapsed();
-    println!("Elapsed: {:.2?}", elapsed);
-    println!("Completed {} iterations. Final score is\n{}", iteration_count, overall_score.render(&players.into_iter().map(|(id, BotPlayer { player, ..})|(id, player)).collect(), &render_style))
+    let mut failures = 0;
+
+    for name in &names {
+        let bot = BotStrategy::from_name(name, &mut rng).expect("should be a known bot");
 
+        match selftest::selftest(bot, budget) {
+            Ok(()) => println!("{}: ok", name),
+            Err(error) => {
+                failures += 1;
+                println!("{}: {}", name, error);
+            }
+        }
+    }
+
+    println!("{} of {} bots passed", names.len() - failures, names.len());
+
+    if failures > 0 {
+        exit(1)
+    }
+}
+
+fn export_replays(patterns: &[String], format: &str, out: &Path, frame_delay: Duration) {
+    let format = export::ExportFormat::from_name(format, frame_delay).expect("clap checks the format");
+
+    let replays: Vec<PathBuf> = patterns
+        .iter()
+        .flat_map(|pattern| {
+            export::find_replays(pattern).unwrap_or_else(|error| {
+                eprintln!("Could not list replays matching {}: {}", pattern, error);
+                exit(1)
+            })
+        })
+        .collect();
+
+    if replays.is_empty() {
+        eprintln!("No replays match {}", patterns.join(" "));
+        exit(1)
+    }
+
+    let report = export::export_replays(&replays, format, out).unwrap_or_else(|error| {
+        eprintln!("Could not export replays: {}", error);
+        exit(1)
+    });
+
+    let mut failed = 0;
+
+    for result in &report.results {
+        if let Err(error) = result {
+            eprintln!("Could not export {}", error);
+            failed += 1;
+        }
+    }
+
+    println!("Exported {} of {} replays to {}, see {}", replays.len() - failed, replays.len(), out.display(), report.index.display());
+
+    if failed > 0 {
+        exit(1)
+    }
+}
+
+fn write_pdf(replay: &Replay, output: &PathBuf) {
+    let pdf = replay.to_pdf().unwrap_or_else(|error| {
+        eprintln!("Replay is invalid: {}", error);
+        exit(1)
+    });
+
+    std::fs::write(output, pdf).unwrap_or_else(|error| {
+        eprintln!("Could not write {}: {}", output.display(), error);
+        exit(1)
+    });
+}
+
+/// Lists the tiles the game will be dealt, a line per expansion
+fn print_deck(expansions: &[Expansion], language: Language) {
+    let composition = deck::composition(expansions);
+
+    let tile_count = composition.iter().map(|(_, count)| *count as usize).sum::<usize>();
+    println!("{}", language.format(Text::Deck, &[&tile_count]));
+
+    for expansion in [None].into_iter().chain(expansions.iter().map(Some)) {
+        let tiles: Vec<_> = composition.iter().filter(|(tile, _)| tile.expansion() == expansion.copied()).map(|(tile, count)| format!("{} {}", count, language.tile_name(tile))).collect();
+
+        println!("  {}: {}", expansion.map(Expansion::name).unwrap_or("base"), tiles.join(", "));
+    }
+}
+
+struct PlayOptions {
+    expansions: Vec<Expansion>,
+    pdf: Option<PathBuf>,
+    ownership: bool,
+    region_labels: bool,
+    legend: bool,
+    preview: bool,
+    teams: bool,
+    difficulty: Difficulty,
+    teach: bool,
+    language: Language,
+    rules: RuleSet,
+    step: bool,
+}
+
+fn rule_preset(name: &str) -> RuleSet {
+    RuleSet::preset(name).expect("clap checks the rules")
+}
+
+fn demo(seed: Seed, bots: &[String], turn_delay: Duration, river: bool, style: RenderStyle) {
+    let mut rng = GameRng::from_seed(seed);
+    let seats = [Player::red(), Player::green(), Player::blue(), Player::yellow(), Player::black()];
+
+    if bots.len() > seats.len() {
+        eprintln!("At most {} bots can play", seats.len());
+        exit(1)
+    }
+
+    let mut builder = Game::builder().with_seed(rng.gen()).with_expansions(if river { vec![Expansion::River] } else { vec![] });
+
+    for (name, player) in bots.iter().zip(seats) {
+        let bot = BotStrategy::from_name(name, &mut rng).unwrap_or_else(|| {
+            eprintln!("Unknown bot [{}], expected one of: {}", name, BOT_NAMES.join(", "));
+            exit(1)
+        });
+
+        builder = builder.with_player(player.with_name(bot.name()), bot);
+    }
+
+    let game = builder.build().unwrap_or_else(|error| {
+        eprintln!("Could not set up the game: {:?}", error);
+        exit(1)
+    });
+
+    game.play_observed(0, &mut [Box::new(DemoPresenter::stdout(style, turn_delay))]);
+}
+
+fn play(seed: Seed, opponents: &[String], options: PlayOptions, render_style: RenderStyle) {
+    let PlayOptions { expansions, pdf, ownership, region_labels, legend, preview, teams, difficulty, teach, language, rules, step } = options;
+
+    let mut rng = GameRng::from_seed(seed);
+
+    let seats = [Player::green(), Player::blue(), Player::yellow(), Player::black()];
+
+    if opponents.len() > seats.len() {
+        eprintln!("At most {} opponents can play", seats.len());
+        exit(1)
+    }
+
+    if teams && opponents.len().is_multiple_of(2) {
+        eprintln!("Teams need an even number of players");
+        exit(1)
+    }
+
+    let annotations = RenderAnnotations { legend };
+    let mut human = HumanController::stdin(render_style).with_language(language).with_annotations(annotations);
+
+    if ownership {
+        human = human.with_ownership_overlay();
+    }
+
+    if region_labels {
+        human = human.with_region_labels();
+    }
+
+    if preview {
+        human = human.with_move_preview();
+    }
+
+    let accuracy = Rc::new(RefCell::new(Accuracy::default()));
+
+    if teach {
+        human = human.with_teaching(Rc::clone(&accuracy));
+    }
+
+    let you = Player::red().with_name("You");
+
+    let mut builder = Game::builder()
+        .with_seed(rng.gen())
+        .with_expansions(expansions.clone())
+        .with_rules(rules)
+        .with_player(you.clone(), human);
+
+    let mut players = vec![you.clone()];
+
+    let seat_colors: Vec<MeepleColor> =
+        [you.meeple_color()].into_iter().chain(seats.iter().map(|p| p.meeple_color())).take(opponents.len() + 1).collect();
+    let partners_of = |seat: usize| seat_colors.iter().enumerate().filter(move |(other, _)| *other != seat && other % 2 == seat % 2).map(|(_, c)| *c);
+
+    for (seat, (name, player)) in opponents.iter().zip(seats).enumerate().map(|(index, opponent)| (index + 1, opponent)) {
+        let mut bot = BotStrategy::from_name(name, &mut rng).unwrap_or_else(|| {
+            eprintln!("Unknown bot [{}], expected one of: {}", name, BOT_NAMES.join(", "));
+            exit(1)
+        });
+
+        if let (true, BotStrategy::Team(team_bot)) = (teams, &mut bot) {
+            *team_bot = team_bot.clone().with_partners(partners_of(seat));
+        }
+
+        let player = player.with_name(bot.name());
+
+        players.push(player.clone());
+
+        builder = match difficulty {
+            Difficulty::Hard => builder.with_player(player, bot),
+            _ => builder.with_player(player, NoisyBot::with_difficulty(Box::new(bot), difficulty, GameRng::from_rng(&mut rng).unwrap())),
+        };
+    }
+
+    if teams {
+        for parity in [0, 1] {
+            builder = builder.with_team(seat_colors.iter().enumerate().filter(|(seat, _)| seat % 2 == parity).map(|(_, c)| *c));
+        }
+    }
+
+    let game = builder.build().unwrap_or_else(|error| {
+        eprintln!("Could not set up the game: {:?}", error);
+        exit(1)
+    });
+
+    print_deck(&expansions, language);
+
+    let recorder = Rc::new(RefCell::new(ReplayRecorder::new()));
+    let timeline = Rc::new(RefCell::new(ScoreTimeline::default()));
+    let records = Rc::new(RefCell::new(Records::default()));
+    let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(Rc::clone(&recorder)), Box::new(Rc::clone(&timeline)), Box::new(Rc::clone(&records))];
+
+    observers.push(Box::new(ObviousMoveNotice { player: you.meeple_color(), language }));
+
+    if step {
+        observers.push(Box::new(StepDebugger::stdin(render_style).with_annotations(annotations)));
+    }
+
+    let result = game.play_observed(0, &mut observers);
+
+    let players = players.into_iter().map(|p| (p.meeple_color(), p)).collect();
+
+    let score_table = match result.teams.is_empty() {
+        true => result.score.render(&players, &render_style),
+        false => result.score.render_teams(&players, &result.teams, &render_style),
+    };
+
+    println!("{}", language.format(Text::FinalScore, &[&score_table]));
+    println!("{}", render_score_chart(&timeline.borrow()));
+    println!("{}", records.borrow());
+
+    for resigned in &result.resigned {
+        let name = players[resigned].name().map(str::to_string).unwrap_or_else(|| resigned.to_string());
+        println!("{}", language.format(Text::Resigned, &[&name]));
+    }
+
+    if teach {
+        let accuracy = accuracy.borrow();
+
+        let percentage = format!("{:.0}", accuracy.percentage());
+        println!("{}", language.format(Text::Accuracy, &[&percentage, &accuracy.best_moves, &accuracy.moves, &accuracy.points_lost]));
+    }
+
+    if let Some(output) = pdf {
+        write_pdf(&recorder.borrow().replays()[0], &output);
+    }
+}
+
+/// Tells the player when their tile was placed for them, as it could only go one way
+struct ObviousMoveNotice {
+    player: MeepleColor,
+    language: Language,
+}
+
+impl GameObserver for ObviousMoveNotice {
+    fn notify(&mut self, event: &GameEvent) {
+        if let GameEvent::ObviousMovePlayed { player, placement, .. } = event {
+            if *player == self.player {
+                println!("{}", self.language.format(Text::PlayedForYou, &[placement]));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "server")]
+fn serve(address: std::net::SocketAddr, reconnect_grace: u64, artwork: Option<PathBuf>) {
+    let mut server = carcassonne::server::GameServer::new().with_reconnect_grace(std::time::Duration::from_secs(reconnect_grace));
+
+    if let Some(directory) = artwork {
+        match carcassonne::TileArtwork::from_directory(&directory) {
+            Ok(artwork) => server = server.with_artwork(artwork),
+            Err(error) => {
+                eprintln!("Failed to load artwork from {}: {}", directory.display(), error);
+                exit(1)
+            }
+        }
+    }
+
+    println!("Listening on {}", address);
+
+    let result = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("should be able to start a runtime")
+        .block_on(carcassonne::server::serve(address, server));
+
+    if let Err(error) = result {
+        eprintln!("Server stopped: {}", error);
+        exit(1)
+    }
 }
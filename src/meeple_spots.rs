@@ -0,0 +1,213 @@
+//! Where a meeple stands on each region of a tile, worked out from the tile's drawing. Tile
+//! definitions give every region's meeple spot by hand, which is easy to get wrong when adding
+//! tiles, so [check_meeple_spots] flags spots that aren't on their own region's cells, and
+//! [derived_meeple_spot] suggests one that is
+
+use crate::ownership::{cell_in_region, edge_region_cells};
+use crate::player::RegionIndex;
+use crate::tile::{Region, RegionType, TileCoordinate, TileDefinition, TILE_WIDTH};
+use crate::tile_definitions::ALL_TILE_DEFINITIONS;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+/// A region of a tile definition whose meeple spot is somewhere it shouldn't be
+#[derive(Debug, Clone, PartialEq)]
+pub struct MisplacedMeepleSpot {
+    pub tile: &'static str,
+    pub region_index: RegionIndex,
+    /// The spot as declared, column then row
+    pub declared: (usize, usize),
+    pub problem: MeepleSpotProblem,
+    /// A spot on the region's own cells, if it has any
+    pub suggested: Option<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeepleSpotProblem {
+    /// The spot is on cells drawn as another region of the same type
+    OnAnotherRegion(RegionIndex),
+    /// The spot is on a cell drawn as a different type of region
+    WrongCellType,
+}
+
+impl Display for MisplacedMeepleSpot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, region {}: meeple spot ({}, {}) ", self.tile, *self.region_index, self.declared.0, self.declared.1)?;
+
+        match &self.problem {
+            MeepleSpotProblem::OnAnotherRegion(other) => write!(f, "is on region {}", **other)?,
+            MeepleSpotProblem::WrongCellType => write!(f, "isn't drawn as the region's type")?,
+        }
+
+        match self.suggested {
+            Some((x, y)) => write!(f, ", try TileCoordinate {{ x: {}, y: {} }}", x, y),
+            None => write!(f, ", and the region has no cells of its own"),
+        }
+    }
+}
+
+/// Every misplaced meeple spot in every tile definition
+pub fn check_all_meeple_spots() -> Vec<MisplacedMeepleSpot> {
+    ALL_TILE_DEFINITIONS.iter().flat_map(check_meeple_spots).collect()
+}
+
+/// The tile's regions whose meeple spot isn't on cells drawn as that region
+pub fn check_meeple_spots(tile: &'static TileDefinition) -> Vec<MisplacedMeepleSpot> {
+    let cells = edge_region_cells(tile);
+
+    tile.regions
+        .iter()
+        .enumerate()
+        .filter_map(|(index, region)| {
+            let TileCoordinate { x, y } = declared_spot(region)?;
+            let region_index = RegionIndex::new(index);
+
+            let problem = if !cell_in_region(&tile.render.0[y][x], region) {
+                MeepleSpotProblem::WrongCellType
+            } else {
+                // a spot on cells no edge floods into is the region's own, as with a city drawn in two parts
+                match cells[y][x] {
+                    Some(other) if other != region_index => MeepleSpotProblem::OnAnotherRegion(other),
+                    _ => return None,
+                }
+            };
+
+            Some(MisplacedMeepleSpot { tile: tile.name, region_index, declared: (x, y), problem, suggested: derived_meeple_spot(tile, region_index) })
+        })
+        .collect()
+}
+
+/// The most central of the region's cells: the one furthest inside the region, then closest to
+/// the middle of the region. The tile's border is avoided where the region reaches further in, as
+/// it's drawn over by the tile's frame. `None` for a region with no meeple spot, or no cells
+pub fn derived_meeple_spot(tile: &TileDefinition, region_index: RegionIndex) -> Option<(usize, usize)> {
+    let region = &tile.regions[*region_index];
+    declared_spot(region)?;
+
+    let cells = edge_region_cells(tile);
+    let own: Vec<(usize, usize)> = (0..TILE_WIDTH)
+        .flat_map(|y| (0..TILE_WIDTH).map(move |x| (x, y)))
+        .filter(|&(x, y)| cells[y][x] == Some(region_index))
+        .collect();
+
+    let border = |&(x, y): &(usize, usize)| x == 0 || y == 0 || x == TILE_WIDTH - 1 || y == TILE_WIDTH - 1;
+    let inner: Vec<_> = own.iter().copied().filter(|cell| !border(cell)).collect();
+    let candidates = if inner.is_empty() { own } else { inner };
+
+    let depth = depths(&candidates);
+    let count = candidates.len() as i64;
+    let (sum_x, sum_y) = candidates.iter().fold((0, 0), |(sum_x, sum_y), &(x, y)| (sum_x + x as i64, sum_y + y as i64));
+
+    // distance from the middle of the region, scaled by the number of cells to stay in whole numbers
+    let off_centre = |&(x, y): &(usize, usize)| (x as i64 * count - sum_x).pow(2) + (y as i64 * count - sum_y).pow(2);
+
+    // ties go to the cell further out from the middle of the tile, so turning a tile turns its spots with it
+    let from_tile_centre = |&(x, y): &(usize, usize)| x.abs_diff(TILE_WIDTH / 2).pow(2) + y.abs_diff(TILE_WIDTH / 2).pow(2);
+
+    candidates
+        .iter()
+        .copied()
+        .enumerate()
+        .min_by_key(|(index, cell)| (Reverse(depth[*index]), off_centre(cell), Reverse(from_tile_centre(cell)), cell.1, cell.0))
+        .map(|(_, cell)| cell)
+}
+
+/// The spot worked out for each of the tile's regions in turn, with the region's type, for
+/// writing a new tile's definition
+pub fn derived_meeple_spots(tile: &TileDefinition) -> Vec<(RegionType, Option<(usize, usize)>)> {
+    tile.regions.iter().enumerate().map(|(index, region)| (region.region_type(), derived_meeple_spot(tile, RegionIndex::new(index)))).collect()
+}
+
+fn declared_spot(region: &Region) -> Option<TileCoordinate> {
+    match region {
+        Region::City { meeple_coordinate, .. } | Region::Field { meeple_coordinate, .. } | Region::Road { meeple_coordinate, .. } | Region::Cloister { meeple_coordinate } => {
+            Some(meeple_coordinate.clone())
+        }
+        Region::Water { .. } => None,
+    }
+}
+
+/// How many steps each of the cells is from the nearest cell that isn't one of them
+fn depths(cells: &[(usize, usize)]) -> Vec<usize> {
+    let mut depth = [[None; TILE_WIDTH]; TILE_WIDTH];
+    let mut queue: VecDeque<_> = (0..TILE_WIDTH).flat_map(|y| (0..TILE_WIDTH).map(move |x| (x, y))).filter(|cell| !cells.contains(cell)).collect();
+
+    for &(x, y) in &queue {
+        depth[y][x] = Some(0);
+    }
+
+    while let Some((x, y)) = queue.pop_front() {
+        let next = depth[y][x].unwrap_or(0) + 1;
+        let neighbours = [(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)];
+
+        for (x, y) in neighbours {
+            if x < TILE_WIDTH && y < TILE_WIDTH && depth[y][x].is_none() {
+                depth[y][x] = Some(next);
+                queue.push_back((x, y));
+            }
+        }
+    }
+
+    // cells touching the edge of the drawing are a step from whatever is beyond it
+    cells.iter().map(|&(x, y)| depth[y][x].unwrap_or(0).min(x + 1).min(y + 1).min(TILE_WIDTH - x).min(TILE_WIDTH - y)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::CardinalDirection::{East, EastNorthEast, EastSouthEast, North, NorthNorthEast, NorthNorthWest, South, SouthSouthEast, SouthSouthWest, West, WestNorthWest, WestSouthWest};
+    use crate::tile_definitions::{ascii_to_tile, CLOISTER_IN_FIELD, SIDE_CITY};
+
+    #[test]
+    fn should_find_every_tile_definitions_meeple_spots_on_their_own_regions() {
+        let misplaced: Vec<_> = check_all_meeple_spots().iter().map(ToString::to_string).collect();
+
+        assert!(misplaced.is_empty(), "{:#?}", misplaced);
+    }
+
+    #[test]
+    fn should_derive_a_spot_in_the_middle_of_the_region() {
+        let cloister = CLOISTER_IN_FIELD.regions.iter().position(|region| region.region_type() == RegionType::Cloister).unwrap();
+
+        assert_eq!(derived_meeple_spot(&CLOISTER_IN_FIELD, RegionIndex::new(cloister)), Some((3, 3)));
+
+        let (x, y) = derived_meeple_spot(&SIDE_CITY, RegionIndex::new(1)).unwrap();
+        assert!(SIDE_CITY.regions[1].region_type() == RegionType::City && y >= 4, "({}, {})", x, y);
+    }
+
+    #[test]
+    fn should_flag_a_spot_on_another_region() {
+        static MISPLACED: TileDefinition = TileDefinition {
+            expansion: None,
+            count: 1,
+            name: "Misplaced",
+            render: ascii_to_tile(
+                "
++FFRFF+
+FFFRFFF
+FFFRFFF
+FFFRFFF
+FFFRFFF
+FFFRFFF
++FFRFF+
+",
+            ),
+            regions: &[
+                Region::Road { edges: &[North, South], meeple_coordinate: TileCoordinate { x: 3, y: 3 } },
+                Region::Field { edges: &[WestSouthWest, West, WestNorthWest, NorthNorthWest, SouthSouthWest], meeple_coordinate: TileCoordinate { x: 5, y: 3 } },
+                Region::Field { edges: &[NorthNorthEast, EastNorthEast, East, EastSouthEast, SouthSouthEast], meeple_coordinate: TileCoordinate { x: 3, y: 1 } },
+            ],
+            // a straight road, the same upside down
+            symmetry_class: 2,
+        };
+
+        let misplaced = check_meeple_spots(&MISPLACED);
+
+        assert_eq!(misplaced.len(), 2);
+        assert_eq!((misplaced[0].region_index, &misplaced[0].problem), (RegionIndex::new(1), &MeepleSpotProblem::OnAnotherRegion(RegionIndex::new(2))));
+        assert_eq!(misplaced[0].suggested, Some((1, 3)));
+        assert_eq!((misplaced[1].region_index, &misplaced[1].problem), (RegionIndex::new(2), &MeepleSpotProblem::WrongCellType));
+        assert_eq!(misplaced[1].to_string(), "Misplaced, region 2: meeple spot (3, 1) isn't drawn as the region's type, try TileCoordinate { x: 5, y: 3 }");
+    }
+}
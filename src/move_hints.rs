@@ -1,18 +1,125 @@
-use crate::board::{Board, TilePlacementSuccess};
-use crate::player::{Meeple, MeepleColor, Player, RegionIndex};
+use crate::analysis::{meeple_in_hand_value, open_feature_potential, turns_remaining_estimate, unseen_tiles};
+use crate::board::{validate_meeple_region, Board, BoardVersion, CandidatePlacement, TilePlacementSuccess};
+use crate::locale::Language;
+use crate::player::{Meeple, MeepleKind, Player, PlayerIdentifier, RegionIndex};
 use crate::score::Score;
-use crate::tile::{BoardCoordinate, PlacedTile, RegionType, TileDefinition, TilePlacement};
-use indexmap::IndexSet;
-use std::collections::HashSet;
-use std::ops::{Add, Sub};
+use crate::tile::{BoardCoordinate, PlacedTile, RegionType, Rotation, TileDefinition, TileId, TilePlacement};
+#[cfg(feature = "render")]
+use crate::tile::RenderStyle;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use rayon::prelude::*;
 
-pub(crate) struct MoveHint {
+#[derive(Debug, Clone)]
+pub struct MoveHint {
     pub(crate) tile: &'static TileDefinition,
     pub(crate) tile_placement: TilePlacement,
     pub(crate) meeple_placement: Option<RegionIndex>,
 }
 
+impl MoveHint {
+    /// What makes two hints the same move: the tile, where it goes and how it's turned, and the
+    /// meeple's region. Hints compare, hash and sort by it, ordered by tile, then placement, then
+    /// meeple, a move without a meeple coming before those with one
+    fn identity(&self) -> (&'static str, &TilePlacement, Option<RegionIndex>) {
+        (self.tile.name, &self.tile_placement, self.meeple_placement)
+    }
+}
+
+impl PartialEq for MoveHint {
+    fn eq(&self, other: &Self) -> bool {
+        self.identity() == other.identity()
+    }
+}
+
+impl Eq for MoveHint {}
+
+impl Hash for MoveHint {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.identity().hash(state)
+    }
+}
+
+impl PartialOrd for MoveHint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for MoveHint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.identity().cmp(&other.identity())
+    }
+}
+
+/// What a bot needs to weigh a move that places a meeple against the same placement keeping it in
+/// hand
+pub struct MeepleContext<'a> {
+    board: &'a Board,
+    player: &'a Player,
+    /// What the player's meeple are worth kept in hand, with the tiles there are left to play them on
+    in_hand_value: f64,
+}
+
+/// What a move is worth to the player making it, see [MoveHint::potential]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MovePotential {
+    /// Points the move scores the player straight away, from the features it completes
+    pub points: i32,
+    /// How much the move changes what the player's open cities, roads and cloisters would score
+    /// them once completed. Features the move completes leave this, as their points are scored
+    pub potential: i32,
+}
+
+/// What a move would do to the board as it stands, worked out without playing it, see
+/// [MoveHint::preview]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacementPreview {
+    /// How many times the tile's regions join onto features already on the board. A region
+    /// bridging two features joins twice, merging them
+    pub joined: usize,
+    /// Features the move completes, which score straight away
+    pub completed: Vec<RegionType>,
+    /// Points each player scores from the completed features, before any handicap multiplier
+    pub score_delta: Score,
+    /// The player of each meeple the completed features give back
+    pub returned: Vec<PlayerIdentifier>,
+}
+
+impl MovePotential {
+    pub fn total(&self) -> i32 {
+        self.points + self.potential
+    }
+}
+
+/// A move as far as its score goes: the tile, where it goes and how it's turned, the meeple's
+/// region and whose it is, and whether the score is worked out as if the game then ended
+type ScoreKey = (TileId, BoardCoordinate, Rotation, Option<RegionIndex>, PlayerIdentifier, bool);
+
+/// The score deltas worked out on one board version, so bots and analysis weighing the same move
+/// in the same turn, e.g. the members of an [crate::bot_strategy::EnsembleBot], only work each
+/// out once. Forgotten as soon as a move is weighed on any other board
+#[derive(Default)]
+struct ScoreMemo {
+    board: Option<BoardVersion>,
+    deltas: HashMap<ScoreKey, Score>,
+}
+
+thread_local! {
+    static SCORE_MEMO: RefCell<ScoreMemo> = RefCell::new(ScoreMemo::default());
+}
+
+impl<'a> MeepleContext<'a> {
+    pub fn new(board: &'a Board, player: &'a Player) -> Self {
+        let turns_remaining = turns_remaining_estimate(board, &unseen_tiles(board));
+
+        Self { board, player, in_hand_value: meeple_in_hand_value(turns_remaining) }
+    }
+}
+
 impl Board {
 
     fn possible_next_tile_coordinates(&self) -> HashSet<BoardCoordinate> {
@@ -20,13 +127,7 @@ impl Board {
             return HashSet::from([BoardCoordinate::new(0, 0)]);
         }
 
-        let board_coordinates: HashSet<BoardCoordinate> = self.placed_tiles.keys().cloned().collect();
-
-        let expanded_coordinates: HashSet<_> = self.placed_tiles.keys()
-            .flat_map(|coordinate|coordinate.adjacent_coordinates().into_values())
-            .collect();
-
-        expanded_coordinates.sub(&board_coordinates)
+        self.frontier_coordinates().copied().filter(|coordinate| self.within_limit(*coordinate)).collect()
     }
 
     pub(crate) fn get_move_hints(
@@ -34,59 +135,179 @@ impl Board {
         tile: &'static TileDefinition,
         include_meeple_placement_hints: bool,
     ) -> Vec<MoveHint> {
-        let possible_coordinates = self.possible_next_tile_coordinates();
+        #[cfg(feature = "alloc-stats")]
+        let _scope = crate::alloc_stats::enter(crate::alloc_stats::Subsystem::Hints);
 
-        let candidate_tile_placements = possible_coordinates.into_iter().flat_map(|coordinate| {
-            // here we discard duplicate rotated sequences as these represent tiles with rotational
-            // symmetry so it doesn't make sense to offer it as a placement variant
-
-            let mut region_sequences: HashSet<[RegionType;12]> = HashSet::new();
+        let possible_coordinates = self.possible_next_tile_coordinates();
 
-            (0..4).filter(move |&rotations| {
-                let region_sequence = tile.list_oriented_region_types(rotations);
+        // rotations repeating another by the tile's rotational symmetry aren't offered as separate
+        // placements
+        let rotations = tile.distinct_rotations();
 
-                region_sequences.insert(region_sequence)
-            }).map(move |rotations| TilePlacement {
+        let candidate_tile_placements = possible_coordinates.into_iter().flat_map(|coordinate| {
+            rotations.iter().map(move |&rotation| TilePlacement {
                 coordinate,
-                rotations,
+                rotation,
             })
         });
 
         candidate_tile_placements.par_bridge().flat_map(|placement| {
-            let unplaced_meeple_candidate = [(placement.clone(), None)];
+            // rayon's threads don't share this one's subsystem
+            #[cfg(feature = "alloc-stats")]
+            let _scope = crate::alloc_stats::enter(crate::alloc_stats::Subsystem::Hints);
+
+            // the tile has to fit whether or not a meeple goes with it, so its edges are only
+            // checked once, and its regions only joined up once for all the meeple it could take
+            if self.validate_candidate(&CandidatePlacement { tile, placement: &placement, meeple_region: None }).is_err() {
+                return vec![];
+            }
+
+            let mut hints = vec![];
 
             if include_meeple_placement_hints {
-                (0..tile.regions.len()).map(|idx| (placement.clone(), Some(RegionIndex::new(idx)))).chain(unplaced_meeple_candidate).collect::<Vec<_>>()
-            } else {
-                unplaced_meeple_candidate.into_iter().collect::<Vec<_>>()
+                let occupied = self.occupied_tile_regions(tile, &placement);
+
+                hints.extend(
+                    (0..tile.regions.len())
+                        .map(RegionIndex::new)
+                        .filter(|idx| !occupied[**idx] && validate_meeple_region(tile, idx, &MeepleKind::Normal).is_ok())
+                        .filter(|idx| self.has_farmers() || tile.regions[**idx].region_type() != RegionType::Field)
+                        .map(|idx| MoveHint { tile, tile_placement: placement.clone(), meeple_placement: Some(idx) }),
+                );
             }
-        }).filter(|(placement, meeple_region_index)| {
-            self.validate_tile_placement(
-                &PlacedTile {
-                    tile,
-                    placement: placement.clone(),
-                    meeple: meeple_region_index.map(|idx| (idx, Meeple::dummy())),
-                },
-                None,
-            ).is_ok()
-        }).map(|(tile_placement, meeple_placement)|MoveHint {
-            tile,
-            tile_placement,
-            meeple_placement,
+
+            hints.push(MoveHint { tile, tile_placement: placement, meeple_placement: None });
+            hints
         }).collect()
     }
 
 }
 
+/// Where the move puts the tile and its meeple, e.g. `(1, -2) r90, meeple on the city`
+impl Display for MoveHint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&Language::English.describe_move(self))
+    }
+}
+
+#[cfg(feature = "render")]
+impl MoveHint {
+    /// The board as it would look after the move, without playing it: the tile drawn in its place,
+    /// and the meeple, if the move places one, as a dimmed ghost in the player's colour
+    pub fn render_preview(&self, board: &Board, player: PlayerIdentifier, style: &RenderStyle) -> String {
+        let placed = PlacedTile { tile: self.tile, placement: self.tile_placement.clone(), meeple: None };
+
+        board.render_tiles_with(Some(&placed), |tile| match self.meeple_placement {
+            Some(region_index) if std::ptr::eq(tile, &placed) => tile.render_meeple_ghost(region_index, player, style),
+            _ => tile.render_to_lines(style),
+        })
+    }
+}
+
 impl MoveHint {
 
+    /// The points the move scores the player if the game then ended, plus what the meeple is worth
+    /// kept in hand if the move doesn't place it
+    pub fn with_meeple_value(&self, context: &MeepleContext) -> f64 {
+        let points = self.score_delta(context.board, context.player, true).get_player(context.player).copied().unwrap_or(0) as f64;
+
+        match self.meeple_placement {
+            None if !context.player.meeple.is_empty() => points + context.in_hand_value,
+            _ => points,
+        }
+    }
+
+    /// Whether the meeple the move places is worth more on the board than in hand. Moves without a
+    /// meeple never are
+    pub fn meeple_worth_placing(&self, context: &MeepleContext) -> bool {
+        if self.meeple_placement.is_none() {
+            return false;
+        }
+
+        let without_meeple = MoveHint { meeple_placement: None, ..self.clone() };
+
+        self.with_meeple_value(context) > without_meeple.with_meeple_value(context)
+    }
+
+    /// What the move would join, complete, score and give back as the board stands
+    pub fn preview(&self, board: &Board, player: &Player) -> PlacementPreview {
+        let mut test_board = board.clone();
+        let before = board.get_connected_regions().len();
+
+        let placed_tile = PlacedTile {
+            tile: self.tile,
+            placement: self.tile_placement.clone(),
+            meeple: self.meeple_placement.map(|region_index| (region_index, Meeple::new(player.meeple_color))),
+        };
+
+        let TilePlacementSuccess { score_delta, liberated_meeple, completed_features, .. } = test_board.place_tile(placed_tile).expect("should be a valid move");
+        let after = test_board.get_connected_regions().len();
+
+        PlacementPreview {
+            joined: (before + self.tile.regions.len()).saturating_sub(after),
+            completed: completed_features.into_iter().map(|feature| feature.region_type).collect(),
+            score_delta,
+            returned: liberated_meeple.into_iter().map(|liberated| liberated.meeple.color).collect(),
+        }
+    }
+
+    /// The points the move scores the player, with the change in what their open features would
+    /// score once completed, so adding a tile to their city is seen to be worth its two points
+    /// before the city is closed
+    pub fn potential(&self, board: &Board, player: &Player) -> MovePotential {
+        let mut test_board = board.clone();
+
+        let placed_tile = PlacedTile {
+            tile: self.tile,
+            placement: self.tile_placement.clone(),
+            meeple: self.meeple_placement.map(|region_index| (region_index, Meeple::new(player.meeple_color))),
+        };
+
+        let TilePlacementSuccess { score_delta, .. } = test_board.place_tile(placed_tile).expect("should be a valid move");
+
+        let before = open_feature_potential(board, player.meeple_color) as i32;
+        let after = open_feature_potential(&test_board, player.meeple_color) as i32;
+
+        MovePotential { points: score_delta.get_player(player).copied().unwrap_or(0), potential: after - before }
+    }
+
+    /// The change in everyone's score the move makes, remembered for the rest of the turn (see
+    /// [ScoreMemo])
     pub(crate) fn score_delta(&self, board: &Board, player: &Player, calculate_as_if_last_tile: bool) -> Score {
+        let key = (self.tile.id(), self.tile_placement.coordinate, self.tile_placement.rotation, self.meeple_placement, player.meeple_color, calculate_as_if_last_tile);
+
+        let remembered = SCORE_MEMO.with_borrow_mut(|memo| {
+            if memo.board != Some(board.version()) {
+                memo.board = Some(board.version());
+                memo.deltas.clear();
+            }
+
+            memo.deltas.get(&key).cloned()
+        });
+
+        if let Some(score_delta) = remembered {
+            return score_delta;
+        }
+
+        let score_delta = self.evaluate_score_delta(board, player, calculate_as_if_last_tile);
+
+        SCORE_MEMO.with_borrow_mut(|memo| {
+            // scoring rules are free to weigh moves on boards of their own in the meantime
+            if memo.board == Some(board.version()) {
+                memo.deltas.insert(key, score_delta.clone());
+            }
+        });
+
+        score_delta
+    }
+
+    fn evaluate_score_delta(&self, board: &Board, player: &Player, calculate_as_if_last_tile: bool) -> Score {
         let mut test_board = board.clone();
 
         let dummy_tile = PlacedTile {
             tile: self.tile,
             placement: self.tile_placement.clone(),
-            meeple: self.meeple_placement.map(|region_index|(region_index, Meeple { color: player.meeple_color })),
+            meeple: self.meeple_placement.map(|region_index|(region_index, Meeple::new(player.meeple_color))),
         };
 
         let TilePlacementSuccess {score_delta, ..} = test_board.place_tile(dummy_tile).expect("should be a valid move");
@@ -107,10 +328,11 @@ impl MoveHint {
 
 #[cfg(test)]
 mod tests {
-    use crate::test_util::tests::{TestMoveHint, TestPlayer};
-    use crate::tile::RenderStyle;
+    use crate::test_util::{TestMoveHint, TestPlayer};
+    #[cfg(feature = "render")]
+    use crate::tile::{RenderStyle, Rotation, TILE_WIDTH};
     use super::*;
-    use crate::tile_definitions::{CENTRE_CITY_WITH_PENNANT, CLOISTER_IN_FIELD, CLOISTER_WITH_ROAD, CORNER_ROAD, CORNER_ROAD_WITH_SIDE_CITY, SIDE_CITY, STRAIGHT_CITY_WITH_SIDE_FIELDS, STRAIGHT_ROAD};
+    use crate::tile_definitions::{CENTRE_CITY_WITH_PENNANT, CLOISTER_IN_FIELD, CLOISTER_WITH_ROAD, CORNER_ROAD, CORNER_ROAD_WITH_SIDE_CITY, SIDE_CITY, STRAIGHT_ROAD};
 
 
     #[test]
@@ -150,8 +372,8 @@ mod tests {
     fn should_return_a_list_of_valid_tile_placements_for_a_given_tile() {
 
         Board::new_with_tiles([
-            PlacedTile::new(&CLOISTER_WITH_ROAD, 1, 0, 2),
-            PlacedTile::new(&CORNER_ROAD, 2, 0, 0),
+            PlacedTile::new(&CLOISTER_WITH_ROAD, 1, 0, Rotation::R180),
+            PlacedTile::new(&CORNER_ROAD, 2, 0, Rotation::R0),
         ])
             .expect("should be valid")
             .get_move_hints(&CORNER_ROAD_WITH_SIDE_CITY, false)
@@ -175,7 +397,7 @@ mod tests {
         let mut alice = Player::blue();
 
         Board::new_with_tiles([
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 3, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R270, 1),
         ])
             .expect("should be valid")
             .get_move_hints(&CENTRE_CITY_WITH_PENNANT, true)
@@ -193,17 +415,18 @@ mod tests {
         let mut carol = Player::green();
 
         let board = Board::new_with_tiles([
-            alice.move_with_meeple(&SIDE_CITY, 1, 0, 1, 1),
-            bob.move_no_meeple(&CORNER_ROAD, 1, -1, 3),
-            carol.move_no_meeple(&CORNER_ROAD, 1, 1, 0),
-            alice.move_with_meeple(&SIDE_CITY, 0, -1, 0, 1),
-            bob.move_no_meeple(&SIDE_CITY, 0, 1, 2),
-            carol.move_with_meeple(&CORNER_ROAD, -1, -1, 2, 1),
-            alice.move_with_meeple(&CLOISTER_IN_FIELD, -1, 1, 0, 1),
-            bob.move_with_meeple(&SIDE_CITY, -1, 0, 3, 1),
+            alice.move_with_meeple(&SIDE_CITY, 1, 0, Rotation::R90, 1),
+            bob.move_no_meeple(&CORNER_ROAD, 1, -1, Rotation::R270),
+            carol.move_no_meeple(&CORNER_ROAD, 1, 1, Rotation::R0),
+            alice.move_with_meeple(&SIDE_CITY, 0, -1, Rotation::R0, 1),
+            bob.move_no_meeple(&SIDE_CITY, 0, 1, Rotation::R180),
+            carol.move_with_meeple(&CORNER_ROAD, -1, -1, Rotation::R180, 1),
+            alice.move_with_meeple(&CLOISTER_IN_FIELD, -1, 1, Rotation::R0, 1),
+            bob.move_with_meeple(&SIDE_CITY, -1, 0, Rotation::R270, 1),
         ])
             .expect("should be valid");
 
+        #[cfg(feature = "render")]
         println!("{}", board.render(&RenderStyle::Ascii));
 
         let move_hints = board
@@ -229,4 +452,176 @@ mod tests {
 
     }
 
+
+    #[test]
+    fn should_work_the_score_delta_out_again_once_the_board_changes() {
+        let mut alice = Player::red();
+        let mut board = Board::new_with_tiles([alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1)]).expect("should be valid");
+
+        // Bob closing Alice's city
+        let bob = Player::blue();
+        let hint = MoveHint { tile: &SIDE_CITY, tile_placement: TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 }, meeple_placement: None };
+
+        let closed = Score::from_iter([(&alice, 4)]);
+
+        assert_eq!(hint.score_delta(&board, &bob, false), closed);
+        // a clone is the same board, until either changes
+        assert_eq!(hint.score_delta(&board.clone(), &bob, false), closed);
+
+        board.recall_meeple(&BoardCoordinate::new(0, 0), RegionIndex::new(1), alice.meeple_color).expect("should be Alice's");
+
+        assert_eq!(hint.score_delta(&board, &bob, false), Score::new());
+    }
+
+    #[test]
+    fn should_describe_the_placement_and_meeple() {
+        let tile_placement = TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 };
+
+        let hint = MoveHint { tile: &SIDE_CITY, tile_placement: tile_placement.clone(), meeple_placement: None };
+        assert_eq!(hint.to_string(), "(0, 1) r180");
+
+        let hint = MoveHint { tile: &SIDE_CITY, tile_placement, meeple_placement: Some(RegionIndex::new(1)) };
+        assert_eq!(hint.to_string(), "(0, 1) r180, meeple on the city");
+    }
+
+    #[test]
+    fn should_count_the_growth_of_open_features_towards_a_moves_potential() {
+        use crate::tile_definitions::STRAIGHT_CITY_WITH_SIDE_FIELDS;
+
+        let player = Player::red();
+        let board = Board::new_with_tiles([PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(player.meeple_color)))]).unwrap();
+
+        let closes_the_city = MoveHint { tile: &SIDE_CITY, tile_placement: TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 }, meeple_placement: None };
+
+        // the one tile city would have scored two closed, so closing it at four gains two
+        assert_eq!(closes_the_city.potential(&board, &player), MovePotential { points: 4, potential: -2 });
+
+        let extends_the_city = board
+            .get_move_hints(&STRAIGHT_CITY_WITH_SIDE_FIELDS, false)
+            .into_iter()
+            .find(|hint| hint.tile_placement.coordinate == BoardCoordinate::new(0, 1))
+            .expect("should extend the city");
+
+        assert_eq!(extends_the_city.potential(&board, &player), MovePotential { points: 0, potential: 2 });
+        assert_eq!(extends_the_city.potential(&board, &Player::green()).total(), 0);
+    }
+
+    #[test]
+    fn should_only_place_a_meeple_worth_more_than_keeping_it() {
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let player = Player::red();
+        let context = MeepleContext::new(&board, &player);
+
+        let closing_city = TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 };
+        let closes_the_city = MoveHint { tile: &SIDE_CITY, tile_placement: closing_city.clone(), meeple_placement: Some(RegionIndex::new(1)) };
+        let keeps_the_meeple = MoveHint { tile: &SIDE_CITY, tile_placement: closing_city, meeple_placement: None };
+
+        assert_eq!(closes_the_city.with_meeple_value(&context), 4.0);
+        assert_eq!(keeps_the_meeple.with_meeple_value(&context), 1.5);
+        assert!(closes_the_city.meeple_worth_placing(&context));
+        assert!(!keeps_the_meeple.meeple_worth_placing(&context));
+
+        // an empty field scores nothing, and there are plenty of tiles left to play the meeple on
+        let empty_field = MoveHint { tile: &SIDE_CITY, tile_placement: TilePlacement { coordinate: BoardCoordinate::new(0, -1), rotation: Rotation::R180 }, meeple_placement: Some(RegionIndex::new(0)) };
+
+        assert!(!empty_field.meeple_worth_placing(&context));
+    }
+
+    #[test]
+    fn should_say_what_a_move_would_join_complete_and_give_back() {
+        use crate::player::MeepleColor;
+
+        let board = Board::new_with_tiles([PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Green)))]).unwrap();
+        let closes_the_city = MoveHint { tile: &SIDE_CITY, tile_placement: TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 }, meeple_placement: None };
+
+        let preview = closes_the_city.preview(&board, &Player::red());
+
+        // the tile's field doesn't reach the field below, so only the city is joined
+        assert_eq!(preview.joined, 1);
+        assert_eq!(preview.completed, vec![RegionType::City]);
+        assert_eq!(preview.score_delta.iter().collect::<Vec<_>>(), vec![(&MeepleColor::Green, &4)]);
+        assert_eq!(preview.returned, vec![MeepleColor::Green]);
+        assert_eq!(board.placed_tile_count(), 1);
+
+        let beside_the_road = MoveHint { tile: &STRAIGHT_ROAD, tile_placement: TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R0 }, meeple_placement: None };
+        let board = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)]).unwrap();
+
+        // the road and the fields either side of it
+        assert_eq!(beside_the_road.preview(&board, &Player::red()).joined, 3);
+    }
+
+    #[test]
+    fn should_offer_the_same_moves_as_validating_each_one() {
+        use crate::player::MeepleColor;
+        use crate::tile_definitions::ALL_TILE_DEFINITIONS;
+
+        let board = Board::new_with_tiles([
+            PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Red))),
+            PlacedTile::new_with_meeple(&STRAIGHT_ROAD, 1, 0, Rotation::R0, (RegionIndex::new(0), Meeple::new(MeepleColor::Green))),
+            PlacedTile::new(&CORNER_ROAD, 1, -1, Rotation::R0),
+        ])
+        .unwrap();
+
+        for tile in ALL_TILE_DEFINITIONS.iter().filter(|tile| tile.count > 0) {
+            let mut hints: Vec<_> = board.get_move_hints(tile, true).iter().map(|hint| (hint.tile_placement.clone(), hint.meeple_placement)).collect();
+            hints.sort();
+
+            // every placement offered is offered without a meeple too, so these are all of them
+            let placements: Vec<_> = hints.iter().filter(|(_, meeple)| meeple.is_none()).map(|(placement, _)| placement.clone()).collect();
+            let mut validated = vec![];
+
+            for placement in placements {
+                for meeple in (0..tile.regions.len()).map(|idx| Some(RegionIndex::new(idx))).chain([None]) {
+                    let candidate = PlacedTile { tile, placement: placement.clone(), meeple: meeple.map(|idx| (idx, Meeple::dummy())) };
+
+                    if board.validate_tile_placement(&candidate).is_ok() {
+                        validated.push((placement.clone(), meeple));
+                    }
+                }
+            }
+
+            validated.sort();
+
+            assert_eq!(hints, validated, "{}", tile.name);
+        }
+    }
+
+    #[test]
+    fn should_tell_hints_for_the_same_move_apart_from_the_rest() {
+        use std::collections::BTreeSet;
+
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let hints = board.get_move_hints(&STRAIGHT_ROAD, true);
+        let twice: Vec<_> = hints.iter().chain(hints.iter()).cloned().collect();
+
+        assert_eq!(twice.iter().collect::<HashSet<_>>().len(), hints.len());
+
+        let sorted: Vec<_> = twice.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+        let mut expected = hints.clone();
+        expected.sort_by_key(|hint| (hint.tile_placement.coordinate.x, hint.tile_placement.coordinate.y, hint.tile_placement.rotation, hint.meeple_placement.map(|idx| *idx)));
+
+        assert_eq!(sorted, expected);
+
+        let placement = TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R0 };
+        let without_meeple = MoveHint { tile: &STRAIGHT_ROAD, tile_placement: placement.clone(), meeple_placement: None };
+        let with_meeple = MoveHint { meeple_placement: Some(RegionIndex::new(0)), ..without_meeple.clone() };
+
+        assert!(without_meeple < with_meeple);
+        assert_ne!(without_meeple, MoveHint { tile: &CORNER_ROAD, ..without_meeple.clone() });
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_preview_a_move_without_playing_it() {
+        let board = Board::new_with_tiles([PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]).unwrap();
+        let tile_placement = TilePlacement { coordinate: BoardCoordinate::new(0, 1), rotation: Rotation::R180 };
+        let hint = MoveHint { tile: &SIDE_CITY, tile_placement, meeple_placement: Some(RegionIndex::new(1)) };
+
+        let preview = hint.render_preview(&board, PlayerIdentifier::Red, &RenderStyle::Ascii);
+
+        assert_eq!(preview.lines().count(), TILE_WIDTH * 2);
+        assert_eq!(preview.matches("◌").count(), 1);
+        assert_eq!(board.placed_tile_count(), 1);
+        assert_eq!(board.render(&RenderStyle::Ascii).lines().count(), TILE_WIDTH);
+    }
 }
@@ -0,0 +1,143 @@
+use crate::game_logic::GamePhase;
+use crate::player::{PlayerIdentifier, RegionIndex};
+use crate::rules::RuleSet;
+use crate::score::Score;
+use crate::simulate::Seed;
+use crate::tile::{BoardCoordinate, RegionType, TileDefinition, TilePlacement};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Events emitted by the game loop. Observers receive these as they happen, which keeps concerns
+/// like progress reporting and replay recording out of the loop itself
+#[derive(Debug, Clone)]
+pub enum GameEvent {
+    GameStarted {
+        game_index: usize,
+        seed: Seed,
+        /// Players in turn order, with their names and the name of the bot playing them (if any)
+        players: Vec<(PlayerIdentifier, Option<String>, Option<&'static str>)>,
+        /// The tiles in the order the deck was shuffled into. Tiles that can't be placed when drawn
+        /// are set aside and shuffled back in, so this is the order they are dealt only if none are
+        deck: Vec<&'static TileDefinition>,
+        /// The rules the game is played under
+        rules: Box<RuleSet>,
+    },
+    TurnCompleted {
+        game_index: usize,
+        player: PlayerIdentifier,
+        tile: &'static TileDefinition,
+        phase: GamePhase,
+        /// Where the tile was placed, and the region a meeple was placed in (if any). `None` when
+        /// the player did not place the tile
+        placement: Option<(TilePlacement, Option<RegionIndex>)>,
+        score_delta: Score,
+        /// How many legal moves the player had to choose from, 0 when the tile couldn't be placed
+        hint_count: usize,
+    },
+    /// A city, road or cloister finished by a turn's tile, sent before that turn's `TurnCompleted`
+    RegionCompleted {
+        game_index: usize,
+        region_type: RegionType,
+        /// The tiles the feature covers, or for a cloister, the cloister and the tiles around it
+        tiles: Vec<BoardCoordinate>,
+        /// Players with the most meeple in the feature, who each score its points
+        scorer: Vec<PlayerIdentifier>,
+        /// Points each scorer got, before any handicap multiplier
+        points: u32,
+        /// The weighted meeple each player had in the feature, strongest first
+        meeple: Vec<(PlayerIdentifier, u32)>,
+        /// How many more weighted meeple the scorers had than the next strongest player; see
+        /// [crate::board::CompletedFeature::margin]
+        margin: u32,
+    },
+    /// A meeple taken back off the board without scoring, sent after the `TurnCompleted` of the
+    /// turn it was taken back in
+    MeepleRecalled {
+        game_index: usize,
+        player: PlayerIdentifier,
+        coordinate: BoardCoordinate,
+        region_index: RegionIndex,
+    },
+    /// A tile with copies left in the deck that no longer fits anywhere on the board, sent before
+    /// the next tile is drawn. It is sent again if the tile fits again as the board grows, then
+    /// stops fitting once more
+    TileDead {
+        game_index: usize,
+        tile: &'static TileDefinition,
+        /// Copies of the tile left in the deck
+        copies: usize,
+        /// Whether the copies were taken out of the deck, see
+        /// [crate::rules::RuleSet::with_dead_tile_pruning]
+        removed: bool,
+    },
+    /// A move played for the player without asking them, as the tile fitted in only one place and
+    /// no meeple was worth placing on it; see [crate::rules::RuleSet::with_obvious_moves_played].
+    /// Sent before the rest of the turn's events
+    ObviousMovePlayed {
+        game_index: usize,
+        player: PlayerIdentifier,
+        tile: &'static TileDefinition,
+        placement: TilePlacement,
+    },
+    /// A player's moves handed to another controller between turns, e.g. a person leaving a bot to
+    /// finish the game for them. Sent before the turn the new controller first plays
+    ControllerChanged {
+        game_index: usize,
+        player: PlayerIdentifier,
+        /// Name of the bot now playing them, `None` when it isn't a bot
+        bot: Option<&'static str>,
+    },
+    /// Sent after the resigning player's last turn
+    PlayerResigned {
+        game_index: usize,
+        player: PlayerIdentifier,
+    },
+    GameCompleted {
+        game_index: usize,
+        score: Score,
+    },
+}
+
+pub trait GameObserver {
+    fn notify(&mut self, event: &GameEvent);
+}
+
+/// A shared observer, so whoever set it up can still read what it collected after the game
+impl<T: GameObserver> GameObserver for Rc<RefCell<T>> {
+    fn notify(&mut self, event: &GameEvent) {
+        self.borrow_mut().notify(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingObserver(usize);
+
+    impl GameObserver for CountingObserver {
+        fn notify(&mut self, event: &GameEvent) {
+            if let GameEvent::GameCompleted { .. } = event {
+                self.0 += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn observers_are_notified_through_trait_objects() {
+        let mut counter = CountingObserver::default();
+
+        {
+            let mut observers: Vec<&mut dyn GameObserver> = vec![&mut counter];
+
+            for game_index in 0..3 {
+                for observer in observers.iter_mut() {
+                    observer.notify(&GameEvent::GameCompleted { game_index, score: Score::new() });
+                }
+            }
+        }
+
+        assert_eq!(counter.0, 3);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::board::Board;
+use crate::player::{PlayerIdentifier, RegionIndex};
+use crate::tile::{BoardCoordinate, CardinalDirection, PlacedTile, Region, RenderCell, RenderStyle, Rotation, TILE_WIDTH, TileCoordinate, TileDefinition};
+use colored::{Color, Colorize};
+use std::collections::{HashMap, VecDeque};
+
+type RegionCells = [[Option<RegionIndex>; TILE_WIDTH]; TILE_WIDTH];
+
+impl Board {
+    /// Renders the board with every cell tinted by the colour of the player controlling the
+    /// region it belongs to, and hatched in both colours where players are tied for control
+    pub fn render_ownership(&self, style: &RenderStyle) -> String {
+        let owners = self.region_owners();
+
+        self.render_tiles(|tile| render_tile_ownership(tile, &owners, style))
+    }
+}
+
+fn render_tile_ownership(
+    tile: &PlacedTile,
+    owners: &HashMap<(BoardCoordinate, RegionIndex), Vec<PlayerIdentifier>>,
+    style: &RenderStyle,
+) -> Vec<String> {
+    let cells = region_cells(tile.tile);
+    let meeple_coordinate = tile.meeple_render_coordinate();
+
+    tile.tile
+        .render
+        .rotated(tile.placement.rotation)
+        .enumerate()
+        .map(|(row_idx, row)| {
+            row.enumerate()
+                .map(|(column_idx, cell)| {
+                    let meeple = match &meeple_coordinate {
+                        Some((coordinate, meeple)) if coordinate == &TileCoordinate::new(column_idx, row_idx) => Some(*meeple),
+                        _ => None,
+                    };
+
+                    // the tile's border is left as it is, so tiles can still be told apart
+                    let border = row_idx == 0 || column_idx == 0 || row_idx == TILE_WIDTH - 1 || column_idx == TILE_WIDTH - 1;
+
+                    let (row, column) = unrotated(row_idx, column_idx, tile.placement.rotation);
+                    let region_owners = cells[row][column]
+                        .filter(|_| !border)
+                        .and_then(|region_index| owners.get(&(tile.placement.coordinate, region_index)));
+
+                    match region_owners {
+                        Some(region_owners) => render_owned_cell(cell, region_owners, meeple.is_some(), style),
+                        None => cell.render(style, row_idx, column_idx, meeple),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn render_owned_cell(cell: &RenderCell, owners: &[PlayerIdentifier], meeple: bool, style: &RenderStyle) -> String {
+    let glyph = if meeple { "ꆜ " } else { "  " };
+
+    match (style, owners) {
+        (RenderStyle::Ascii, [owner]) if !meeple => owner.initial().repeat(2),
+        (RenderStyle::Ascii, _) if !meeple => "╳╳".to_string(),
+        (RenderStyle::Ascii, _) => glyph.to_string(),
+        (_, [owner]) => glyph.color(Color::Black).on_color(tint(cell, owner, style)).to_string(),
+        (_, [first, second, ..]) if !meeple => "╱╱".color(tint(cell, second, style)).on_color(tint(cell, first, style)).to_string(),
+        (_, owners) => glyph.color(Color::Black).on_color(tint(cell, &owners[0], style)).to_string(),
+    }
+}
+
+/// The cell's colour mixed half and half with the player's, or just the player's colour where
+/// there are too few colours to mix
+fn tint(cell: &RenderCell, player: &PlayerIdentifier, style: &RenderStyle) -> Color {
+    match style {
+        RenderStyle::Ascii | RenderStyle::Ansi => player.render_color(style),
+        RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel => {
+            let ((cell_r, cell_g, cell_b), (r, g, b)) = (cell.rgb(), player.rgb());
+            let mix = |a: u8, b: u8| ((a as u16 + b as u16) / 2) as u8;
+
+            Color::TrueColor { r: mix(cell_r, r), g: mix(cell_g, g), b: mix(cell_b, b) }
+        }
+    }
+}
+
+/// Where a cell of the rotated tile is in the unrotated tile
+pub(crate) fn unrotated(row: usize, column: usize, rotation: Rotation) -> (usize, usize) {
+    match rotation {
+        Rotation::R0 => (row, column),
+        Rotation::R90 => (TILE_WIDTH - column - 1, row),
+        Rotation::R180 => (TILE_WIDTH - row - 1, TILE_WIDTH - column - 1),
+        Rotation::R270 => (column, TILE_WIDTH - row - 1),
+    }
+}
+
+/// Which region each cell of the unrotated tile drawing belongs to. Regions only describe their
+/// edges, so each is flooded out from its cells on the edge of the tile (and its meeple spot)
+/// through cells drawn as its type
+pub(crate) fn region_cells(tile: &TileDefinition) -> RegionCells {
+    flood_regions(tile, true)
+}
+
+/// Which region each cell belongs to going by the regions' edges alone, so a meeple spot can be
+/// checked against the cells it is meant to be on. A cloister has no edges, so is flooded from
+/// every cell drawn as one
+pub(crate) fn edge_region_cells(tile: &TileDefinition) -> RegionCells {
+    flood_regions(tile, false)
+}
+
+fn flood_regions(tile: &TileDefinition, from_meeple_spots: bool) -> RegionCells {
+    let mut cells: RegionCells = [[None; TILE_WIDTH]; TILE_WIDTH];
+
+    for (index, region) in tile.regions.iter().enumerate() {
+        let matches = |row: usize, column: usize| cell_in_region(&tile.render.0[row][column], region);
+
+        let mut queue: VecDeque<(usize, usize)> = region.edges().iter().flat_map(edge_cells).collect();
+
+        match region.meeple_coordinate_rotated(Rotation::R0) {
+            Some(TileCoordinate { x, y }) if from_meeple_spots => queue.push_back((y, x)),
+            _ if matches!(region, Region::Cloister { .. }) => {
+                queue.extend((0..TILE_WIDTH).flat_map(|row| (0..TILE_WIDTH).map(move |column| (row, column))).filter(|&(row, column)| matches(row, column)))
+            }
+            _ => {}
+        }
+
+        while let Some((row, column)) = queue.pop_front() {
+            if cells[row][column].is_some() || !matches(row, column) {
+                continue;
+            }
+
+            cells[row][column] = Some(RegionIndex::new(index));
+
+            if row > 0 {
+                queue.push_back((row - 1, column));
+            }
+            if row < TILE_WIDTH - 1 {
+                queue.push_back((row + 1, column));
+            }
+            if column > 0 {
+                queue.push_back((row, column - 1));
+            }
+            if column < TILE_WIDTH - 1 {
+                queue.push_back((row, column + 1));
+            }
+        }
+    }
+
+    cells
+}
+
+pub(crate) fn cell_in_region(cell: &RenderCell, region: &Region) -> bool {
+    matches!(
+        (cell, region),
+        (RenderCell::City | RenderCell::Pennant, Region::City { .. })
+            | (RenderCell::Field, Region::Field { .. })
+            | (RenderCell::Road, Region::Road { .. })
+            | (RenderCell::Cloister, Region::Cloister { .. })
+            | (RenderCell::Water, Region::Water { .. })
+    )
+}
+
+/// The cells along the tile's edge drawn for an edge direction, as (row, column)
+fn edge_cells(direction: &CardinalDirection) -> Vec<(usize, usize)> {
+    let last = TILE_WIDTH - 1;
+
+    match direction {
+        CardinalDirection::NorthNorthWest => vec![(0, 1), (0, 2)],
+        CardinalDirection::North => vec![(0, 3)],
+        CardinalDirection::NorthNorthEast => vec![(0, 4), (0, 5)],
+        CardinalDirection::EastNorthEast => vec![(1, last), (2, last)],
+        CardinalDirection::East => vec![(3, last)],
+        CardinalDirection::EastSouthEast => vec![(4, last), (5, last)],
+        CardinalDirection::SouthSouthEast => vec![(last, 4), (last, 5)],
+        CardinalDirection::South => vec![(last, 3)],
+        CardinalDirection::SouthSouthWest => vec![(last, 1), (last, 2)],
+        CardinalDirection::WestSouthWest => vec![(4, 0), (5, 0)],
+        CardinalDirection::West => vec![(3, 0)],
+        CardinalDirection::WestNorthWest => vec![(1, 0), (2, 0)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use crate::test_util::TestPlayer;
+    use crate::tile_definitions::{CLOISTER_IN_FIELD, SIDE_CITY};
+
+    #[test]
+    fn should_find_the_region_of_every_cell() {
+        let cells = region_cells(&SIDE_CITY);
+
+        // the city is along the south edge and the rest is field
+        assert_eq!(cells[5][3], Some(RegionIndex::new(1)));
+        assert_eq!(cells[1][3], Some(RegionIndex::new(0)));
+        assert_eq!(cells[0][0], None);
+
+        let cells = region_cells(&CLOISTER_IN_FIELD);
+        let cloister = CLOISTER_IN_FIELD.regions.iter().position(|r| matches!(r, Region::Cloister { .. })).unwrap();
+
+        assert_eq!(cells[3][3], Some(RegionIndex::new(cloister)));
+    }
+
+    #[test]
+    fn should_tint_owned_regions_and_hatch_contested_ones() {
+        let mut alice = Player::red();
+        let mut bob = Player::blue();
+
+        // a farmer each in fields that the last cloister joins, and Alice alone in the first cloister
+        let board = Board::new_with_tiles([
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0),
+            bob.move_with_meeple(&SIDE_CITY, 0, 1, Rotation::R180, 0),
+            alice.move_with_meeple(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0, 1),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 1, Rotation::R0),
+        ])
+        .unwrap();
+
+        let rendered = board.render_ownership(&RenderStyle::Ascii);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines[3], "┃╳╳╳╳╳╳╳╳╳╳┃┃╳╳RRꆜ ╳╳╳╳┃");
+        // the completed city has nobody in it
+        assert!(lines[5].contains("▓▓"));
+        assert!(!rendered.contains("BB"));
+
+        assert!(board.render_ownership(&RenderStyle::TrueColor).contains('╱'));
+    }
+
+    #[test]
+    fn should_map_rotated_cells_back_to_the_drawing() {
+        assert_eq!(unrotated(0, 6, Rotation::R90), (0, 0));
+        assert_eq!(unrotated(0, 6, Rotation::R180), (6, 0));
+        assert_eq!(unrotated(0, 6, Rotation::R270), (6, 6));
+        assert_eq!(unrotated(0, 6, Rotation::R90 + Rotation::R270), (0, 6));
+    }
+}
@@ -0,0 +1,295 @@
+use crate::board::Board;
+use crate::commentary;
+use crate::replay::{Replay, ReplayError};
+use crate::score_sheet::{score_sheet, ScoreBreakdown};
+use crate::tile::{BoardCoordinate, TileCoordinate, TILE_WIDTH};
+use std::fmt::Write;
+
+/// A4, in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 40.0;
+
+/// Space on the first page for the board, below the title
+const BOARD_HEIGHT: f32 = 430.0;
+/// Largest a tile cell is drawn, so small boards aren't blown up to fill the page
+const MAX_CELL_SIZE: f32 = 6.0;
+
+const LINE_HEIGHT: f32 = 13.0;
+/// Characters of the move list that fit across the page
+const LINE_LENGTH: usize = 100;
+
+impl Replay {
+    /// A score sheet for the game as a PDF: the final board, each player's score broken down by
+    /// how it was earned, and commentary on every move
+    pub fn to_pdf(&self) -> Result<Vec<u8>, ReplayError> {
+        let state = self.state_at(self.turns.len())?;
+        let sheet = score_sheet(self)?;
+        let moves = commentary::commentate(self)?;
+
+        let mut first_page = String::new();
+        let mut y = PAGE_HEIGHT - MARGIN - 16.0;
+
+        text(&mut first_page, MARGIN, y, 16.0, "Carcassonne score sheet");
+        y -= LINE_HEIGHT;
+        text(&mut first_page, MARGIN, y, 9.0, &format!("Seed {}", self.seed));
+        y -= LINE_HEIGHT;
+
+        y = draw_board(&mut first_page, &state.board, y);
+        draw_score_table(&mut first_page, &sheet, y - 2.0 * LINE_HEIGHT);
+
+        let mut pages = vec![first_page];
+
+        let lines: Vec<String> = moves
+            .iter()
+            .enumerate()
+            .flat_map(|(turn, description)| wrap(&format!("{}. {}", turn + 1, description)))
+            .collect();
+
+        let lines_per_page = ((PAGE_HEIGHT - 2.0 * MARGIN) / LINE_HEIGHT) as usize - 2;
+
+        for (page_index, page_lines) in lines.chunks(lines_per_page).enumerate() {
+            let mut page = String::new();
+            let mut y = PAGE_HEIGHT - MARGIN - 12.0;
+
+            let heading = if page_index == 0 { "Moves" } else { "Moves (continued)" };
+            text(&mut page, MARGIN, y, 12.0, heading);
+            y -= 2.0 * LINE_HEIGHT;
+
+            for line in page_lines {
+                text(&mut page, MARGIN, y, 9.0, line);
+                y -= LINE_HEIGHT;
+            }
+
+            pages.push(page);
+        }
+
+        Ok(write_document(&pages))
+    }
+}
+
+/// Draws the board below `top`, returning where the drawing ends
+fn draw_board(page: &mut String, board: &Board, top: f32) -> f32 {
+    let Some((BoardCoordinate { x: min_x, y: min_y }, BoardCoordinate { x: max_x, y: max_y })) = board.bounding_box() else {
+        return top;
+    };
+
+    let columns = (max_x - min_x + 1) as f32 * TILE_WIDTH as f32;
+    let rows = (max_y - min_y + 1) as f32 * TILE_WIDTH as f32;
+
+    let cell = ((PAGE_WIDTH - 2.0 * MARGIN) / columns).min(BOARD_HEIGHT / rows).min(MAX_CELL_SIZE);
+
+    for tile in board.placed_tiles.values() {
+        let left = MARGIN + (tile.placement.coordinate.x - min_x) as f32 * TILE_WIDTH as f32 * cell;
+        let tile_top = top - (tile.placement.coordinate.y - min_y) as f32 * TILE_WIDTH as f32 * cell;
+
+        for (row_idx, row) in tile.tile.render.rotated(tile.placement.rotation).enumerate() {
+            for (column_idx, render_cell) in row.enumerate() {
+                rectangle(page, left + column_idx as f32 * cell, tile_top - (row_idx + 1) as f32 * cell, cell, render_cell.rgb());
+            }
+        }
+
+        if let Some((TileCoordinate { x, y }, meeple)) = tile.meeple_render_coordinate() {
+            let (meeple_left, meeple_bottom) = (left + x as f32 * cell, tile_top - (y + 1) as f32 * cell);
+
+            rectangle(page, meeple_left, meeple_bottom, cell, (0, 0, 0));
+            rectangle(page, meeple_left + cell / 6.0, meeple_bottom + cell / 6.0, cell * 2.0 / 3.0, meeple.color.rgb());
+        }
+    }
+
+    top - rows * cell
+}
+
+fn draw_score_table(page: &mut String, sheet: &[ScoreBreakdown], top: f32) {
+    const COLUMNS: [f32; 6] = [0.0, 150.0, 210.0, 270.0, 340.0, 420.0];
+
+    let mut y = top;
+
+    for (column, heading) in COLUMNS.iter().zip(["Player", "Cities", "Roads", "Cloisters", "End of game", "Total"]) {
+        text(page, MARGIN + column, y, 10.0, heading);
+    }
+
+    for breakdown in sheet {
+        y -= LINE_HEIGHT;
+
+        let name = breakdown.name.clone().unwrap_or_else(|| breakdown.player.to_string());
+        let values = [breakdown.cities, breakdown.roads, breakdown.cloisters, breakdown.end_of_game, breakdown.total()];
+
+        text(page, MARGIN, y, 10.0, &name);
+
+        for (column, value) in COLUMNS[1..].iter().zip(values) {
+            text(page, MARGIN + column, y, 10.0, &value.to_string());
+        }
+    }
+}
+
+fn rectangle(page: &mut String, x: f32, y: f32, size: f32, (r, g, b): (u8, u8, u8)) {
+    writeln!(
+        page,
+        "{:.3} {:.3} {:.3} rg {:.2} {:.2} {:.2} {:.2} re f",
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        x,
+        y,
+        size,
+        size
+    )
+    .unwrap();
+}
+
+fn text(page: &mut String, x: f32, y: f32, size: f32, value: &str) {
+    // the standard fonts only cover ascii reliably, and brackets and backslashes need escaping
+    let escaped: String = value
+        .chars()
+        .map(|c| if c.is_ascii() && !c.is_ascii_control() { c } else { '?' })
+        .fold(String::new(), |mut escaped, c| {
+            if matches!(c, '(' | ')' | '\\') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            escaped
+        });
+
+    writeln!(page, "0 0 0 rg BT /F1 {} Tf {:.2} {:.2} Td ({}) Tj ET", size, x, y, escaped).unwrap();
+}
+
+/// Splits a line at spaces so it fits across the page
+fn wrap(line: &str) -> Vec<String> {
+    let mut lines = vec![String::new()];
+
+    for word in line.split(' ') {
+        let current = lines.last_mut().expect("there is always a line");
+
+        if !current.is_empty() && current.len() + 1 + word.len() > LINE_LENGTH {
+            lines.push(format!("    {}", word));
+        } else {
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+    }
+
+    lines
+}
+
+/// Assembles the pages' content streams into a PDF file, with Helvetica as the only font
+fn write_document(pages: &[String]) -> Vec<u8> {
+    // catalog, page tree and font come first, then each page and its content
+    let page_ids: Vec<usize> = (0..pages.len()).map(|index| 4 + index * 2).collect();
+
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+
+    for (page, id) in pages.iter().zip(&page_ids) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            id + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", page.len(), page));
+    }
+
+    let mut document = String::from("%PDF-1.4\n");
+    let mut offsets = vec![];
+
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(document.len());
+        writeln!(document, "{} 0 obj\n{}\nendobj", index + 1, object).unwrap();
+    }
+
+    let xref_offset = document.len();
+
+    writeln!(document, "xref\n0 {}\n0000000000 65535 f ", objects.len() + 1).unwrap();
+
+    for offset in offsets {
+        writeln!(document, "{:010} 00000 n ", offset).unwrap();
+    }
+
+    writeln!(document, "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF", objects.len() + 1, xref_offset).unwrap();
+
+    document.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Rotation;
+    use crate::player::MeepleColor;
+    use crate::replay::{ReplayPlacement, ReplayPlayer, ReplayRules, ReplayScore, ReplayTurn};
+
+    fn replay(turns: usize) -> Replay {
+        // side cities in a row along the top, all open to the north
+        Replay {
+            seed: "seed".to_string(),
+            deck: vec![],
+            players: vec![
+                ReplayPlayer { color: MeepleColor::Red, name: Some("Alice (host)".to_string()), bot: None },
+                ReplayPlayer { color: MeepleColor::Green, name: None, bot: Some("lazy".to_string()) },
+            ],
+            turns: (0..turns)
+                .map(|turn| ReplayTurn {
+                    player: if turn % 2 == 0 { MeepleColor::Red } else { MeepleColor::Green },
+                    tile: "Side city".to_string(),
+                    placement: Some(ReplayPlacement { x: turn as i8, y: 0, rotation: Rotation::R0, meeple: (turn == 0).then_some(1), recalled: None }),
+                })
+                .collect(),
+            final_score: vec![ReplayScore { player: MeepleColor::Red, points: 1 }, ReplayScore { player: MeepleColor::Green, points: 0 }],
+            handovers: vec![],
+            rules: ReplayRules::default(),
+        }
+    }
+
+    #[test]
+    fn should_write_a_pdf_with_the_board_scores_and_moves() {
+        let pdf = String::from_utf8(replay(3).to_pdf().unwrap()).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("(Alice \\(host\\)) Tj"));
+        assert!(pdf.contains("(Green) Tj"));
+        assert!(pdf.contains("(1. Alice \\(host\\) places Side city at \\(0, 0\\) and claims the city.) Tj"));
+        assert_eq!(pdf.matches(" re f").count(), 3 * TILE_WIDTH * TILE_WIDTH + 2);
+    }
+
+    #[test]
+    fn should_point_the_cross_reference_table_at_each_object() {
+        let pdf = String::from_utf8(replay(3).to_pdf().unwrap()).unwrap();
+
+        let xref = &pdf[pdf.find("xref\n").unwrap()..];
+        let offsets: Vec<usize> = xref.lines().skip(3).take_while(|line| line.ends_with(" n ")).map(|line| line[..10].parse().unwrap()).collect();
+
+        assert_eq!(offsets.len(), 7);
+
+        for (index, offset) in offsets.iter().enumerate() {
+            assert!(pdf[*offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+
+    #[test]
+    fn should_continue_long_move_lists_onto_more_pages() {
+        let pdf = String::from_utf8(replay(70).to_pdf().unwrap()).unwrap();
+
+        assert!(pdf.contains("/Count 3"));
+        assert!(pdf.contains("(Moves \\(continued\\)) Tj"));
+    }
+
+    #[test]
+    fn should_wrap_long_lines_at_spaces() {
+        let lines = wrap(&"word ".repeat(30));
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.len() <= LINE_LENGTH));
+        assert!(lines[1].starts_with("    word"));
+    }
+}
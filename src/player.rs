@@ -1,10 +1,14 @@
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
+#[cfg(feature = "render")]
 use colored::Color;
-use uuid::Uuid;
+#[cfg(feature = "render")]
 use crate::tile::RenderStyle;
+use crate::tile::RegionType;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
-pub(crate) enum MeepleColor {
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, Serialize, Deserialize)]
+pub enum MeepleColor {
     Red,
     Green,
     Blue,
@@ -12,7 +16,19 @@ pub(crate) enum MeepleColor {
     Yellow,
 }
 
+#[cfg(feature = "render")]
 impl MeepleColor {
+    /// A letter for the colour where colours can't be shown, `K` for black so it differs from blue
+    pub(crate) fn initial(&self) -> &'static str {
+        match self {
+            MeepleColor::Red => "R",
+            MeepleColor::Green => "G",
+            MeepleColor::Blue => "B",
+            MeepleColor::Black => "K",
+            MeepleColor::Yellow => "Y",
+        }
+    }
+
     pub(crate) fn render_color(&self, style: &RenderStyle) -> Color {
         match (self, style) {
             (MeepleColor::Red, RenderStyle::Ascii | RenderStyle::Ansi) => Color::Red,
@@ -21,27 +37,27 @@ impl MeepleColor {
             (MeepleColor::Black, RenderStyle::Ascii | RenderStyle::Ansi) => Color::Black,
             (MeepleColor::Yellow, RenderStyle::Ascii | RenderStyle::Ansi) => Color::BrightYellow,
 
-            (MeepleColor::Red, RenderStyle::TrueColor) => Color::TrueColor {
+            (MeepleColor::Red, RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel) => Color::TrueColor {
                 r: 194,
                 g: 0,
                 b: 25,
             },
-            (MeepleColor::Green, RenderStyle::TrueColor) => Color::TrueColor {
+            (MeepleColor::Green, RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel) => Color::TrueColor {
                 r: 16,
                 g: 126,
                 b: 50,
             },
-            (MeepleColor::Blue, RenderStyle::TrueColor) => Color::TrueColor {
+            (MeepleColor::Blue, RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel) => Color::TrueColor {
                 r: 10,
                 g: 79,
                 b: 147,
             },
-            (MeepleColor::Black, RenderStyle::TrueColor) => Color::TrueColor {
+            (MeepleColor::Black, RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel) => Color::TrueColor {
                 r: 43,
                 g: 42,
                 b: 44,
             },
-            (MeepleColor::Yellow, RenderStyle::TrueColor) => Color::TrueColor {
+            (MeepleColor::Yellow, RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel) => Color::TrueColor {
                 r: 215,
                 g: 184,
                 b: 18,
@@ -50,9 +66,53 @@ impl MeepleColor {
     }
 }
 
+impl MeepleColor {
+    /// Single byte identifier, for compact encodings
+    pub(crate) fn to_byte(self) -> u8 {
+        match self {
+            MeepleColor::Red => 0,
+            MeepleColor::Green => 1,
+            MeepleColor::Blue => 2,
+            MeepleColor::Black => 3,
+            MeepleColor::Yellow => 4,
+        }
+    }
+
+    /// Where the colour is in [SEATS]
+    pub(crate) fn seat(self) -> usize {
+        self.to_byte() as usize
+    }
+
+    pub(crate) fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(MeepleColor::Red),
+            1 => Some(MeepleColor::Green),
+            2 => Some(MeepleColor::Blue),
+            3 => Some(MeepleColor::Black),
+            4 => Some(MeepleColor::Yellow),
+            _ => None,
+        }
+    }
+}
+
+impl Display for MeepleColor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            MeepleColor::Red => "Red",
+            MeepleColor::Green => "Green",
+            MeepleColor::Blue => "Blue",
+            MeepleColor::Black => "Black",
+            MeepleColor::Yellow => "Yellow",
+        })
+    }
+}
+
 pub type PlayerIdentifier = MeepleColor;
 
-const MEEPLE_COUNT: usize = 7;
+/// Every colour in seat order, so per-player data can be kept in a small array indexed by seat
+pub(crate) static SEATS: [MeepleColor; 5] = [MeepleColor::Red, MeepleColor::Green, MeepleColor::Blue, MeepleColor::Black, MeepleColor::Yellow];
+
+pub(crate) const MEEPLE_COUNT: usize = 7;
 
 #[derive(Debug, Clone)]
 pub struct Player {
@@ -62,53 +122,66 @@ pub struct Player {
 }
 
 impl Player {
-    fn new(color: MeepleColor) -> Self {
+    pub(crate) fn new(color: MeepleColor) -> Self {
         let meeple = Vec::with_capacity(MEEPLE_COUNT);
 
         let mut player = Self {
             name: None,
             meeple,
-            meeple_color: color.clone(),
+            meeple_color: color,
         };
 
         for _ in 0..MEEPLE_COUNT {
-            player.meeple.push(Meeple::new(color.clone()));
+            player.meeple.push(Meeple::new(color));
         }
 
         player
     }
 
-    pub(crate) fn black() -> Self {
+    pub fn black() -> Self {
         Self::new(MeepleColor::Black)
     }
 
-    pub(crate) fn green() -> Self {
+    pub fn green() -> Self {
         Self::new(MeepleColor::Green)
     }
 
-    pub(crate) fn red() -> Self {
+    pub fn red() -> Self {
         Self::new(MeepleColor::Red)
     }
 
-    pub(crate) fn blue() -> Self {
+    pub fn blue() -> Self {
         Self::new(MeepleColor::Blue)
     }
 
-    pub(crate) fn yellow() -> Self {
+    pub fn yellow() -> Self {
         Self::new(MeepleColor::Yellow)
     }
 
-    pub(crate) fn with_name(mut self, name: &'static str) -> Self {
-        self.name = Some(name.to_string());
+    pub fn with_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn meeple_color(&self) -> MeepleColor {
+        self.meeple_color
+    }
+
+    pub(crate) fn with_meeple_count(mut self, count: usize) -> Self {
+        self.meeple = (0..count).map(|_| Meeple::new(self.meeple_color)).collect();
         self
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
-pub(crate) struct RegionIndex(usize);
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, PartialOrd, Ord)]
+pub struct RegionIndex(usize);
 
 impl RegionIndex {
-    pub(crate) fn new(v: usize) -> Self {
+    pub fn new(v: usize) -> Self {
         Self(v)
     }
 }
@@ -121,22 +194,79 @@ impl Deref for RegionIndex {
     }
 }
 
-#[derive(Debug, Clone)]
+/// The kinds of follower a player can place. The base game only has normal meeple; the others
+/// come with expansions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MeepleKind {
+    #[default]
+    Normal,
+    /// Counts as two meeple towards a majority
+    Big,
+    /// Extends its player's city or road; doesn't count towards a majority
+    Builder,
+    /// Enriches its player's field; doesn't count towards a majority
+    Pig,
+    /// Only placed in cloisters
+    Abbot,
+}
+
+impl MeepleKind {
+    /// How many meeple this counts as when working out who has the most in a region
+    pub fn majority_weight(&self) -> u32 {
+        match self {
+            MeepleKind::Normal | MeepleKind::Abbot => 1,
+            MeepleKind::Big => 2,
+            MeepleKind::Builder | MeepleKind::Pig => 0,
+        }
+    }
+
+    /// Whether this kind can be placed in a region of the given type
+    pub(crate) fn can_occupy(&self, region_type: &RegionType) -> bool {
+        match self {
+            MeepleKind::Normal | MeepleKind::Big => !matches!(region_type, RegionType::Water),
+            MeepleKind::Builder => matches!(region_type, RegionType::City | RegionType::Road),
+            MeepleKind::Pig => matches!(region_type, RegionType::Field),
+            MeepleKind::Abbot => matches!(region_type, RegionType::Cloister),
+        }
+    }
+
+    /// Whether this kind joins one of its player's own followers, rather than claiming a region
+    /// nobody is in
+    pub(crate) fn joins_own_follower(&self) -> bool {
+        matches!(self, MeepleKind::Builder | MeepleKind::Pig)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Meeple {
     pub(crate) color: MeepleColor,
+    pub(crate) kind: MeepleKind,
 }
 
 impl Meeple {
-    fn new(color: MeepleColor) -> Self {
+    /// A normal meeple in the player's colour, e.g. to check a move with
+    /// [crate::Board::validate_tile_placement]
+    pub fn new(color: MeepleColor) -> Self {
+        Self::of_kind(color, MeepleKind::Normal)
+    }
+
+    pub fn of_kind(color: MeepleColor, kind: MeepleKind) -> Self {
         Self {
             color,
+            kind,
         }
     }
 
     pub(crate) fn dummy() -> Self {
-        Self {
-            color: MeepleColor::Black,
-        }
+        Self::new(MeepleColor::Black)
+    }
+
+    pub fn color(&self) -> MeepleColor {
+        self.color
+    }
+
+    pub fn kind(&self) -> MeepleKind {
+        self.kind
     }
 }
 
@@ -150,4 +280,10 @@ mod tests {
 
         assert_eq!(player.meeple.len(), MEEPLE_COUNT);
     }
+
+    #[test]
+    fn test_display_meeple_color() {
+        assert_eq!(MeepleColor::Red.to_string(), "Red");
+        assert_eq!(format!("{:>6}", MeepleColor::Blue), "  Blue");
+    }
 }
@@ -0,0 +1,24 @@
+//! The types most uses of the crate need, for glob importing:
+//!
+//! ```
+//! use carcassonne::prelude::*;
+//! ```
+
+pub use crate::board::Board;
+#[cfg(feature = "render")]
+pub use crate::board::RenderAnnotations;
+pub use crate::bot_strategy::BotStrategy;
+pub use crate::controller::{GameView, PlayerAction, PlayerController};
+pub use crate::deck::Deck;
+pub use crate::game_logic::{Game, GameBuilder, GamePhase};
+pub use crate::move_hints::MoveHint;
+pub use crate::player::{MeepleColor, Player};
+pub use crate::rules::RuleSet;
+pub use crate::score::Score;
+pub use crate::simulate::Seed;
+pub use crate::team::Team;
+pub use crate::tile::{Expansion, Rotation};
+#[cfg(feature = "render")]
+pub use crate::terminal_graphics::RenderConfig;
+#[cfg(feature = "render")]
+pub use crate::tile::RenderStyle;
@@ -0,0 +1,32 @@
+use crate::observer::{GameEvent, GameObserver};
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Draws a progress bar for a batch of games, advancing each time a game completes. Shows the
+/// throughput and an ETA so long simulations aren't silent until they finish
+pub struct ProgressObserver(ProgressBar);
+
+impl ProgressObserver {
+    pub fn new(game_count: u64) -> Self {
+        let bar = ProgressBar::new(game_count).with_style(
+            ProgressStyle::with_template(
+                "{elapsed_precise} [{wide_bar}] {pos}/{len} games ({per_sec}, ETA {eta})",
+            )
+            .expect("template should be valid")
+            .progress_chars("=> "),
+        );
+
+        Self(bar)
+    }
+}
+
+impl GameObserver for ProgressObserver {
+    fn notify(&mut self, event: &GameEvent) {
+        if let GameEvent::GameCompleted { .. } = event {
+            self.0.inc(1);
+
+            if Some(self.0.position()) == self.0.length() {
+                self.0.finish();
+            }
+        }
+    }
+}
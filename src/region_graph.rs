@@ -0,0 +1,143 @@
+use crate::board::Board;
+use crate::connected_regions::ConnectedRegionId;
+use crate::locale::Language;
+use crate::player::PlayerIdentifier;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// The board's features and which of them border each other, for looking at outside the game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegionGraph {
+    pub nodes: Vec<RegionNode>,
+    /// Pairs of bordering features, lower id first
+    pub edges: Vec<(ConnectedRegionId, ConnectedRegionId)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RegionNode {
+    pub id: ConnectedRegionId,
+    #[serde(rename = "type")]
+    pub region_type: &'static str,
+    /// Tile regions in the feature, as in [crate::connected_regions::ConnectedRegion::tile_region_count]
+    pub size: usize,
+    /// Players with the most meeple in the feature, more than one when they're tied
+    pub owners: Vec<PlayerIdentifier>,
+    pub closed: bool,
+    pub score: u32,
+}
+
+impl Board {
+    /// Every connected region on the board as a node, joined to the regions it borders
+    pub fn region_graph(&self) -> RegionGraph {
+        let mut regions = self.get_connected_regions();
+        regions.sort_by_key(|region| region.id);
+
+        let mut edges = BTreeSet::new();
+
+        for region in &regions {
+            for adjacent in self.adjacent_regions(region) {
+                edges.insert((region.id.min(adjacent.id), region.id.max(adjacent.id)));
+            }
+        }
+
+        RegionGraph {
+            nodes: regions
+                .iter()
+                .map(|region| RegionNode {
+                    id: region.id,
+                    region_type: Language::English.region_name(region.region_type()),
+                    size: region.tile_region_count(),
+                    owners: region.majority_meeple_player_ids(self),
+                    closed: region.is_closed(),
+                    score: region.score(self),
+                })
+                .collect(),
+            edges: edges.into_iter().collect(),
+        }
+    }
+
+    /// The region graph in Graphviz DOT, e.g. for `dot -Tsvg`
+    pub fn region_graph_dot(&self) -> String {
+        self.region_graph().to_dot()
+    }
+
+    pub fn region_graph_json(&self) -> String {
+        serde_json::to_string_pretty(&self.region_graph()).expect("region graph should serialize")
+    }
+}
+
+impl RegionGraph {
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("graph regions {\n");
+
+        for node in &self.nodes {
+            let owners = match node.owners.is_empty() {
+                true => String::new(),
+                false => format!("\\n{}", node.owners.iter().map(|owner| owner.to_string()).collect::<Vec<_>>().join(", ")),
+            };
+
+            dot.push_str(&format!(
+                "  r{} [label=\"{} {}\\n{} tiles{}\", shape={}, style={}];\n",
+                node.id,
+                node.region_type,
+                node.id,
+                node.size,
+                owners,
+                node_shape(node.region_type),
+                if node.closed { "bold" } else { "dashed" },
+            ));
+        }
+
+        for (from, to) in &self.edges {
+            dot.push_str(&format!("  r{} -- r{};\n", from, to));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn node_shape(region_type: &str) -> &'static str {
+    match region_type {
+        "city" => "box",
+        "field" => "ellipse",
+        "road" => "diamond",
+        _ => "octagon",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::{Meeple, MeepleColor, RegionIndex};
+    use crate::tile::{PlacedTile, Rotation};
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+
+    #[test]
+    fn should_join_features_that_border_each_other() {
+        let meeple = (RegionIndex::new(1), Meeple::new(MeepleColor::Red));
+        let board = Board::new_with_tiles([
+            PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, meeple),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+        ])
+        .unwrap();
+
+        let graph = board.region_graph();
+        let city = graph.nodes.iter().find(|node| node.region_type == "city").unwrap();
+
+        assert_eq!(city.owners, vec![MeepleColor::Red]);
+        assert!(!city.closed);
+        assert_eq!(graph.nodes.iter().filter(|node| node.region_type == "road").count(), 1);
+        // the city borders only the field it opens onto
+        assert_eq!(graph.edges.iter().filter(|(from, to)| *from == city.id || *to == city.id).count(), 1);
+        assert!(graph.edges.iter().all(|(from, to)| from < to));
+
+        let dot = board.region_graph_dot();
+        assert!(dot.starts_with("graph regions {"));
+        assert_eq!(dot.matches(" -- ").count(), graph.edges.len());
+
+        let json: serde_json::Value = serde_json::from_str(&board.region_graph_json()).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), graph.nodes.len());
+        assert_eq!(json["nodes"][0]["type"], graph.nodes[0].region_type);
+    }
+}
@@ -0,0 +1,1006 @@
+use crate::binary_replay;
+pub use crate::binary_replay::BINARY_FORMAT_VERSION;
+use crate::board::{Board, InvalidTilePlacement, TilePlacementSuccess};
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::{Meeple, Player, PlayerIdentifier, RegionIndex, SEATS};
+use crate::rules::{RiverOptions, RuleSet, RuleSetError, StartingLayout, PRESET_NAMES};
+use crate::score::{Score, SmallCityScoring};
+use crate::simulate::Seed;
+use crate::tile::{BoardCoordinate, BoardTopology, PlacedTile, Rotation, TileDefinition, TileId, TilePlacement};
+use crate::tile_definitions;
+use base64::{engine::general_purpose, Engine as _};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A record of every turn of a game, sufficient to rebuild the board at any point
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    /// The url safe base64 seed the game was played with
+    pub seed: String,
+    /// Names of the tiles in the order the deck was shuffled into, so the game's tiles are known
+    /// without relying on the shuffle staying the same between versions. Empty for replays
+    /// recorded before the deck was kept
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deck: Vec<String>,
+    /// Players in turn order
+    pub players: Vec<ReplayPlayer>,
+    pub turns: Vec<ReplayTurn>,
+    pub final_score: Vec<ReplayScore>,
+    /// Players whose moves were handed to another controller part way through the game, in the
+    /// order they were
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub handovers: Vec<ReplayHandover>,
+    /// The rules the game was played under, left out of replays of games under the standard rules
+    #[serde(default, skip_serializing_if = "ReplayRules::is_standard")]
+    pub rules: ReplayRules,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayPlayer {
+    pub color: PlayerIdentifier,
+    pub name: Option<String>,
+    /// Name of the bot that played (see [crate::bot_strategy::BOT_NAMES])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplayTurn {
+    pub player: PlayerIdentifier,
+    /// The name of the drawn tile's definition
+    pub tile: String,
+    /// `None` when the player did not place the tile
+    pub placement: Option<ReplayPlacement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplayPlacement {
+    pub x: i8,
+    pub y: i8,
+    #[serde(rename = "rotations")]
+    pub rotation: Rotation,
+    /// Region index of the tile the player placed a meeple in
+    pub meeple: Option<usize>,
+    /// The meeple the player took back in place of placing one, see
+    /// [crate::controller::PlayerAction::RecallMeeple]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recalled: Option<ReplayRecall>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ReplayRecall {
+    pub x: i8,
+    pub y: i8,
+    /// Region index of the tile the meeple was taken back from
+    pub meeple: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayHandover {
+    /// How many turns had been played when the player's moves changed hands
+    pub turn: usize,
+    pub player: PlayerIdentifier,
+    /// Name of the bot that took over, `None` when it wasn't a bot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bot: Option<String>,
+}
+
+/// The rules a game was played under, as far as they change how its moves play out and score, so
+/// it is played back under them
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplayRules {
+    /// Name of the scoring (see [crate::score::ScoringRules::name]). Only the scorings of the
+    /// presets, see [crate::rules::PRESET_NAMES], can be played back
+    pub scoring: String,
+    /// Whether two tile cities are scored without being doubled, see [SmallCityScoring::Single]
+    pub single_small_cities: bool,
+    pub meeple_per_player: usize,
+    /// Whether meeple could be placed in fields, see [RuleSet::with_farmers]
+    pub farmers: bool,
+    /// See [crate::rules::RiverOptions::separate_ends]
+    pub separate_river_ends: bool,
+    /// Most columns and rows the board may span, see [RuleSet::with_board_limit]
+    pub board_limit: Option<(u8, u8)>,
+    /// Columns and rows of a board whose edges wrap around, see [BoardTopology::Torus]
+    pub torus: Option<(u8, u8)>,
+    /// Tiles laid before the first turn, in the order they were laid
+    pub starting_layout: Vec<ReplayLayoutTile>,
+    /// Points players started with and what their points were multiplied by, in seat order
+    pub handicaps: Vec<ReplayHandicap>,
+}
+
+/// A player's handicap, see [RuleSet::with_starting_score] and [RuleSet::with_score_multiplier]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayHandicap {
+    pub player: PlayerIdentifier,
+    #[serde(default)]
+    pub starting_score: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_multiplier: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayLayoutTile {
+    /// The name of the tile's definition
+    pub tile: String,
+    pub x: i8,
+    pub y: i8,
+    #[serde(rename = "rotations")]
+    pub rotation: Rotation,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayScore {
+    pub player: PlayerIdentifier,
+    pub points: i32,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    Malformed(serde_json::Error),
+    /// A binary replay that couldn't be decoded
+    InvalidBinary(&'static str),
+    /// A replay written in a format version newer than this build understands
+    UnsupportedVersion(u8),
+    /// A replay saved under different tile definitions that can't be carried over to these
+    TileSetMismatch { saved: String, current: String },
+    UnknownTile(String),
+    UnknownPlayer(PlayerIdentifier),
+    NoMeepleAvailable { turn: usize },
+    /// A turn that takes back a meeple the player doesn't have where it says
+    NoMeepleToRecall { turn: usize },
+    InvalidPlacement { turn: usize, reason: InvalidTilePlacement },
+    TurnOutOfRange(usize),
+    /// A turn whose recorded score doesn't match the score its placement earns when played again
+    ScoreDivergence { turn: usize, recorded: Score, recomputed: Score },
+    /// A replay whose recorded final score doesn't match the score its turns add up to
+    FinalScoreDivergence { recorded: Score, recomputed: Score },
+    /// The rules the game was played under can't be played, e.g. a starting layout that can't be laid
+    InvalidRules(RuleSetError),
+    /// A scoring that isn't one of the presets', so can't be played back
+    UnknownScoring(String),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "{}", error),
+            ReplayError::Malformed(error) => write!(f, "malformed replay: {}", error),
+            ReplayError::InvalidBinary(reason) => write!(f, "invalid binary replay: {}", reason),
+            ReplayError::UnsupportedVersion(version) => write!(f, "replay format version {} is newer than this build supports", version),
+            ReplayError::TileSetMismatch { saved, current } => write!(f, "saved with tile set {}, current is {}", saved, current),
+            ReplayError::UnknownTile(name) => write!(f, "there is no tile called [{}]", name),
+            ReplayError::UnknownPlayer(player) => write!(f, "{} is not one of the replay's players", player),
+            ReplayError::NoMeepleAvailable { turn } => write!(f, "turn {} places a meeple the player doesn't have", turn),
+            ReplayError::NoMeepleToRecall { turn } => write!(f, "turn {} takes back a meeple the player doesn't have there", turn),
+            ReplayError::InvalidPlacement { turn, reason } => write!(f, "turn {} can't be played: {}", turn, reason),
+            ReplayError::TurnOutOfRange(turn) => write!(f, "the replay has no turn {}", turn),
+            ReplayError::ScoreDivergence { turn, .. } => write!(f, "turn {} scores differently to the recorded score", turn),
+            ReplayError::InvalidRules(error) => write!(f, "the game's rules can't be played: {}", error),
+            ReplayError::UnknownScoring(name) => write!(f, "the game was scored under [{}], which can't be played back", name),
+            ReplayError::FinalScoreDivergence { recorded, recomputed } => {
+                write!(f, "the turns score {:?}, but the recorded final score is {:?}", recomputed.iter().collect::<Vec<_>>(), recorded.iter().collect::<Vec<_>>())
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(error: std::io::Error) -> Self {
+        ReplayError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for ReplayError {
+    fn from(error: serde_json::Error) -> Self {
+        ReplayError::Malformed(error)
+    }
+}
+
+/// Version of the JSON replay format written by [Replay::to_json]. Replays without one are version 1
+pub const JSON_FORMAT_VERSION: u8 = 2;
+
+/// A replay with the version and tile set hash (see [tile_definitions::tile_set_hash]) it was saved
+/// with, so replays written by older builds can be brought up to date when read
+#[derive(Serialize, Deserialize)]
+struct VersionedReplay<R> {
+    #[serde(default = "unversioned")]
+    format_version: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tile_set: Option<String>,
+    #[serde(flatten)]
+    replay: R,
+}
+
+fn unversioned() -> u8 {
+    1
+}
+
+impl VersionedReplay<Replay> {
+    fn migrate(self) -> Result<Replay, ReplayError> {
+        if self.format_version > JSON_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(self.format_version));
+        }
+
+        // version 1 only lacked the stamp, so has nothing to upgrade
+
+        let current = tile_definitions::tile_set_hash();
+
+        match self.tile_set {
+            // tiles are saved by name, so a replay from another tile set still reads as long as
+            // every tile it uses is still around
+            Some(saved) if saved != current => {
+                let mut tiles = self.replay.deck.iter().chain(self.replay.turns.iter().map(|turn| &turn.tile));
+
+                if tiles.all(|name| tile_definitions::find_by_name(name).is_some()) {
+                    Ok(self.replay)
+                } else {
+                    Err(ReplayError::TileSetMismatch { saved, current })
+                }
+            }
+            _ => Ok(self.replay),
+        }
+    }
+}
+
+/// How replays are written to disk
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayFormat {
+    #[default]
+    Json,
+    /// The compact format of [Replay::to_binary], for large batches of games
+    Binary,
+}
+
+impl ReplayFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ReplayFormat::Json => "json",
+            ReplayFormat::Binary => "bin",
+        }
+    }
+}
+
+/// The state of a game part way through a replay
+#[derive(Debug, Clone)]
+pub struct ReplayState {
+    pub board: Board,
+    pub players: IndexMap<PlayerIdentifier, Player>,
+    pub score: Score,
+    /// Index of the next turn to be applied
+    pub turn: usize,
+    /// The rules the game is played under, for scoring it as it was scored
+    rules: RuleSet,
+}
+
+impl Replay {
+    pub fn from_json(json: &str) -> Result<Self, ReplayError> {
+        serde_json::from_str::<VersionedReplay<Replay>>(json)?.migrate()
+    }
+
+    /// The replay as JSON, stamped with [JSON_FORMAT_VERSION] and the tile set it was played with
+    pub fn to_json(&self) -> String {
+        let versioned = VersionedReplay {
+            format_version: JSON_FORMAT_VERSION,
+            tile_set: Some(tile_definitions::tile_set_hash()),
+            replay: self,
+        };
+
+        serde_json::to_string_pretty(&versioned).expect("replay should always be serializable")
+    }
+
+    /// Reads a replay saved in either format
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ReplayError> {
+        let bytes = fs::read(path)?;
+
+        if binary_replay::is_binary(&bytes) {
+            Self::from_binary(&bytes)
+        } else {
+            serde_json::from_slice::<VersionedReplay<Replay>>(&bytes)?.migrate()
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ReplayError> {
+        self.save_as(path, ReplayFormat::Json)
+    }
+
+    pub fn save_as<P: AsRef<Path>>(&self, path: P, format: ReplayFormat) -> Result<(), ReplayError> {
+        match format {
+            ReplayFormat::Json => Ok(fs::write(path, self.to_json())?),
+            ReplayFormat::Binary => Ok(fs::write(path, self.to_binary()?)?),
+        }
+    }
+
+    pub fn seed(&self) -> Option<Seed> {
+        general_purpose::URL_SAFE.decode(&self.seed).ok()?.try_into().ok()
+    }
+
+    /// The recorded deck order, e.g. for [crate::GameBuilder::with_tile_order] to deal the same
+    /// tiles again
+    pub fn deck_order(&self) -> Result<Vec<TileId>, ReplayError> {
+        self.deck
+            .iter()
+            .map(|name| tile_definitions::find_by_name(name).map(TileDefinition::id).ok_or_else(|| ReplayError::UnknownTile(name.clone())))
+            .collect()
+    }
+
+    /// The state before the first turn, under the rules the game was played under
+    pub fn initial_state(&self) -> Result<ReplayState, ReplayError> {
+        let players = self.players.iter().map(|p| (p.color, p.name.clone()));
+
+        ReplayState::new(players, &self.rules.rules()?).map_err(ReplayError::InvalidRules)
+    }
+
+    /// The state of the game before `turn` is played
+    pub fn state_at(&self, turn: usize) -> Result<ReplayState, ReplayError> {
+        if turn > self.turns.len() {
+            return Err(ReplayError::TurnOutOfRange(turn));
+        }
+
+        let mut state = self.initial_state()?;
+
+        for replay_turn in &self.turns[..turn] {
+            state.apply(replay_turn)?;
+        }
+
+        Ok(state)
+    }
+
+    pub(crate) fn record_final_score(&mut self, score: &Score) {
+        self.final_score = self
+            .players
+            .iter()
+            .map(|p| ReplayScore {
+                player: p.color,
+                points: score.iter().find(|(id, _)| **id == p.color).map(|(_, s)| *s).unwrap_or(0),
+            })
+            .collect();
+    }
+
+    /// Plays every turn again and checks the final score they add up to is the one recorded, e.g.
+    /// to catch a rules change that scores old games differently
+    pub fn verify(&self) -> Result<(), ReplayError> {
+        let recomputed = self.state_at(self.turns.len())?.final_score();
+        let recorded = self.final_score();
+
+        match recomputed == recorded {
+            true => Ok(()),
+            false => Err(ReplayError::FinalScoreDivergence { recorded, recomputed }),
+        }
+    }
+
+    pub fn final_score(&self) -> Score {
+        let mut score = Score::new();
+
+        for s in &self.final_score {
+            score.add_score(s.player, s.points);
+        }
+
+        score
+    }
+}
+
+impl ReplayTurn {
+    pub fn tile(&self) -> Result<&'static TileDefinition, ReplayError> {
+        tile_definitions::find_by_name(&self.tile).ok_or_else(|| ReplayError::UnknownTile(self.tile.clone()))
+    }
+}
+
+impl ReplayPlacement {
+    pub fn new(placement: &TilePlacement, meeple: Option<RegionIndex>) -> Self {
+        Self {
+            x: placement.coordinate.x,
+            y: placement.coordinate.y,
+            rotation: placement.rotation,
+            meeple: meeple.map(|region_index| *region_index),
+            recalled: None,
+        }
+    }
+
+    pub fn tile_placement(&self) -> TilePlacement {
+        TilePlacement {
+            coordinate: BoardCoordinate::new(self.x, self.y),
+            rotation: self.rotation,
+        }
+    }
+
+    pub fn meeple_region(&self) -> Option<RegionIndex> {
+        self.meeple.map(RegionIndex::new)
+    }
+}
+
+impl ReplayRecall {
+    pub fn new(coordinate: &BoardCoordinate, region_index: RegionIndex) -> Self {
+        Self { x: coordinate.x, y: coordinate.y, meeple: *region_index }
+    }
+
+    pub fn coordinate(&self) -> BoardCoordinate {
+        BoardCoordinate::new(self.x, self.y)
+    }
+}
+
+impl ReplayRules {
+    /// The parts of `rules` that change how a game's moves play out and score
+    pub fn of(rules: &RuleSet) -> Self {
+        Self {
+            scoring: rules.scoring.name().to_string(),
+            single_small_cities: rules.small_city_scoring == SmallCityScoring::Single,
+            meeple_per_player: rules.meeple_per_player,
+            farmers: rules.farmers,
+            separate_river_ends: rules.river.separate_ends,
+            board_limit: rules.board_limit.map(|limit| (limit.width, limit.height)),
+            torus: match rules.topology {
+                BoardTopology::Plane => None,
+                BoardTopology::Torus { width, height } => Some((width, height)),
+            },
+            starting_layout: rules
+                .starting_layout
+                .tiles()
+                .iter()
+                .map(|tile| ReplayLayoutTile {
+                    tile: tile.tile.name().to_string(),
+                    x: tile.placement.coordinate.x,
+                    y: tile.placement.coordinate.y,
+                    rotation: tile.placement.rotation,
+                })
+                .collect(),
+            handicaps: SEATS
+                .iter()
+                .filter(|player| rules.starting_scores.contains_key(player) || rules.score_multipliers.contains_key(player))
+                .map(|player| ReplayHandicap {
+                    player: *player,
+                    starting_score: rules.starting_scores.get(player).copied().unwrap_or(0),
+                    score_multiplier: rules.score_multipliers.get(player).copied(),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn is_standard(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Rules that play the game back as it was played
+    pub fn rules(&self) -> Result<RuleSet, ReplayError> {
+        let scoring = PRESET_NAMES
+            .iter()
+            .filter_map(|name| RuleSet::preset(name))
+            .map(|rules| rules.scoring)
+            .find(|scoring| scoring.name() == self.scoring)
+            .ok_or_else(|| ReplayError::UnknownScoring(self.scoring.clone()))?;
+
+        let layout = self
+            .starting_layout
+            .iter()
+            .map(|tile| {
+                let definition = tile_definitions::find_by_name(&tile.tile).ok_or_else(|| ReplayError::UnknownTile(tile.tile.clone()))?;
+                Ok(PlacedTile::new(definition, tile.x, tile.y, tile.rotation))
+            })
+            .collect::<Result<Vec<_>, ReplayError>>()?;
+
+        let mut rules = RuleSet::standard()
+            .with_meeple_per_player(self.meeple_per_player)
+            .with_farmers(self.farmers)
+            .with_small_city_scoring(if self.single_small_cities { SmallCityScoring::Single } else { SmallCityScoring::Doubled })
+            .with_river_options(RiverOptions { separate_ends: self.separate_river_ends, ..RiverOptions::default() })
+            .with_starting_layout(StartingLayout::new(layout));
+
+        rules.scoring = scoring;
+
+        if let Some((width, height)) = self.board_limit {
+            rules = rules.with_board_limit(width, height);
+        }
+
+        if let Some((width, height)) = self.torus {
+            rules = rules.with_topology(BoardTopology::Torus { width, height });
+        }
+
+        for handicap in &self.handicaps {
+            if handicap.starting_score != 0 {
+                rules = rules.with_starting_score(handicap.player, handicap.starting_score);
+            }
+
+            if let Some(multiplier) = handicap.score_multiplier {
+                rules = rules.with_score_multiplier(handicap.player, multiplier);
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+impl Default for ReplayRules {
+    fn default() -> Self {
+        Self::of(&RuleSet::standard())
+    }
+}
+
+impl ReplayState {
+    /// The state before the first turn of a game between the players, given by colour and name,
+    /// under `rules`
+    pub(crate) fn new<P: IntoIterator<Item = (PlayerIdentifier, Option<String>)>>(players: P, rules: &RuleSet) -> Result<Self, RuleSetError> {
+        let players: IndexMap<PlayerIdentifier, Player> = players
+            .into_iter()
+            .map(|(color, name)| {
+                let mut player = Player::new(color).with_meeple_count(rules.meeple_per_player);
+                player.name = name;
+                (color, player)
+            })
+            .collect();
+
+        Ok(ReplayState {
+            board: Board::under_rules(rules)?,
+            score: rules.starting_score(players.values()),
+            players,
+            turn: 0,
+            rules: rules.clone(),
+        })
+    }
+
+    /// Plays the next turn, returning the result of the tile placement (if the tile was placed)
+    pub fn apply(&mut self, turn: &ReplayTurn) -> Result<Option<TilePlacementSuccess>, ReplayError> {
+        let turn_index = self.turn;
+        self.turn += 1;
+
+        let Some(placement) = &turn.placement else {
+            return Ok(None);
+        };
+
+        let tile = turn.tile()?;
+
+        let player = self
+            .players
+            .get_mut(&turn.player)
+            .ok_or(ReplayError::UnknownPlayer(turn.player))?;
+
+        let meeple = match placement.meeple_region() {
+            Some(region_index) => {
+                let meeple = player
+                    .meeple
+                    .pop()
+                    .ok_or(ReplayError::NoMeepleAvailable { turn: turn_index })?;
+                Some((region_index, meeple))
+            }
+            None => None,
+        };
+
+        let success = self
+            .board
+            .place_tile(PlacedTile {
+                tile,
+                placement: placement.tile_placement(),
+                meeple,
+            })
+            .map_err(|reason| ReplayError::InvalidPlacement { turn: turn_index, reason })?;
+
+        self.score += &self.rules.adjust(success.score_delta.clone());
+
+        for liberated in &success.liberated_meeple {
+            self.players
+                .get_mut(&liberated.meeple.color)
+                .ok_or(ReplayError::UnknownPlayer(liberated.meeple.color))?
+                .meeple
+                .push(liberated.meeple.clone());
+        }
+
+        if let Some(recall) = &placement.recalled {
+            if !self.recall_meeple(turn.player, &recall.coordinate(), RegionIndex::new(recall.meeple)) {
+                return Err(ReplayError::NoMeepleToRecall { turn: turn_index });
+            }
+        }
+
+        Ok(Some(success))
+    }
+
+    /// Takes the player's meeple back off the board into their supply, for following a
+    /// [GameEvent::MeepleRecalled]. False if the player has no meeple there
+    pub fn recall_meeple(&mut self, player: PlayerIdentifier, coordinate: &BoardCoordinate, region_index: RegionIndex) -> bool {
+        let Some(supply) = self.players.get_mut(&player) else {
+            return false;
+        };
+
+        match self.board.recall_meeple(coordinate, region_index, player) {
+            Some(meeple) => {
+                supply.meeple.push(meeple);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The score if the game ended now, including the open regions on the board
+    pub fn final_score(&self) -> Score {
+        self.score.clone() + self.rules.adjust(self.board.calculate_board_score())
+    }
+}
+
+impl Board {
+    /// Rebuilds the board from the placements in an event log, checking each turn's recorded score
+    /// against the score the placement earns when played again. Only the first game in the log is
+    /// used, under the rules its start records, or the standard rules if the log starts later
+    pub fn from_events(events: &[GameEvent]) -> Result<Board, ReplayError> {
+        let started = events.iter().find_map(|event| match event {
+            GameEvent::GameStarted { rules, .. } => Some(rules.as_ref()),
+            _ => None,
+        });
+
+        Self::from_events_under(events, started.unwrap_or(&RuleSet::standard()))
+    }
+
+    /// As [Board::from_events], for a game played under `rules`
+    pub fn from_events_under(events: &[GameEvent], rules: &RuleSet) -> Result<Board, ReplayError> {
+        let mut board = Board::under_rules(rules).map_err(ReplayError::InvalidRules)?;
+        let mut game = None;
+        let mut turn = 0;
+
+        for event in events {
+            let (game_index, player, tile, placement, recorded) = match event {
+                GameEvent::TurnCompleted { game_index, player, tile, placement, score_delta, .. } => (game_index, player, tile, placement, score_delta),
+                GameEvent::MeepleRecalled { game_index, player, coordinate, region_index } if game == Some(*game_index) => {
+                    board.recall_meeple(coordinate, *region_index, *player).ok_or(ReplayError::NoMeepleToRecall { turn: turn - 1 })?;
+                    continue;
+                }
+                _ => continue,
+            };
+
+            if *game.get_or_insert(*game_index) != *game_index {
+                continue;
+            }
+
+            let turn_index = turn;
+            turn += 1;
+
+            let Some((tile_placement, meeple_region)) = placement else {
+                continue;
+            };
+
+            let success = board
+                .place_tile(PlacedTile {
+                    tile,
+                    placement: tile_placement.clone(),
+                    meeple: meeple_region.map(|region_index| (region_index, Meeple::new(*player))),
+                })
+                .map_err(|reason| ReplayError::InvalidPlacement { turn: turn_index, reason })?;
+
+            let recomputed = rules.adjust(success.score_delta);
+
+            if &recomputed != recorded {
+                return Err(ReplayError::ScoreDivergence { turn: turn_index, recorded: recorded.clone(), recomputed });
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+/// Observer that builds a [Replay] of every game it sees. Replays are either kept in memory or,
+/// if a directory is given, written out as each game completes
+#[derive(Default)]
+pub struct ReplayRecorder {
+    in_progress: HashMap<usize, Replay>,
+    directory: Option<PathBuf>,
+    format: ReplayFormat,
+    replays: Vec<Replay>,
+}
+
+impl ReplayRecorder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn to_directory<P: Into<PathBuf>>(directory: P) -> Self {
+        Self {
+            directory: Some(directory.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Format to write replays to the directory in
+    pub fn with_format(mut self, format: ReplayFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Completed replays, when not writing to a directory
+    pub fn replays(&self) -> &[Replay] {
+        &self.replays
+    }
+}
+
+impl GameObserver for ReplayRecorder {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, seed, players, deck, rules } => {
+                self.in_progress.insert(
+                    *game_index,
+                    Replay {
+                        seed: general_purpose::URL_SAFE.encode(seed),
+                        deck: deck.iter().map(|tile| tile.name().to_string()).collect(),
+                        players: players
+                            .iter()
+                            .map(|(color, name, bot)| ReplayPlayer {
+                                color: *color,
+                                name: name.clone(),
+                                bot: bot.map(str::to_string),
+                            })
+                            .collect(),
+                        turns: vec![],
+                        final_score: vec![],
+                        handovers: vec![],
+                        rules: ReplayRules::of(rules),
+                    },
+                );
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                if let Some(replay) = self.in_progress.get_mut(game_index) {
+                    replay.turns.push(ReplayTurn {
+                        player: *player,
+                        tile: tile.name.to_string(),
+                        placement: placement
+                            .as_ref()
+                            .map(|(placement, meeple)| ReplayPlacement::new(placement, *meeple)),
+                    });
+                }
+            }
+            GameEvent::MeepleRecalled { game_index, coordinate, region_index, .. } => {
+                let placement = self.in_progress.get_mut(game_index).and_then(|replay| replay.turns.last_mut()).and_then(|turn| turn.placement.as_mut());
+
+                if let Some(placement) = placement {
+                    placement.recalled = Some(ReplayRecall::new(coordinate, *region_index));
+                }
+            }
+            // completions are worked out again when the replay is played back
+            GameEvent::RegionCompleted { .. } => {}
+            // the resigning turn is recorded as a pass, and the turns skipped after it aren't played
+            GameEvent::PlayerResigned { .. } => {}
+            // the tiles drawn are recorded, so those taken out of the deck are too by their absence
+            GameEvent::TileDead { .. } => {}
+            // the move is recorded with its turn, as if the player had chosen it
+            GameEvent::ObviousMovePlayed { .. } => {}
+            GameEvent::ControllerChanged { game_index, player, bot } => {
+                if let Some(replay) = self.in_progress.get_mut(game_index) {
+                    replay.handovers.push(ReplayHandover { turn: replay.turns.len(), player: *player, bot: bot.map(str::to_string) });
+                }
+            }
+            GameEvent::GameCompleted { game_index, score } => {
+                let Some(mut replay) = self.in_progress.remove(game_index) else {
+                    return;
+                };
+
+                replay.record_final_score(score);
+
+                match &self.directory {
+                    Some(directory) => {
+                        let path = directory.join(format!("game-{:05}.{}", game_index, self.format.extension()));
+
+                        if let Err(error) = replay.save_as(&path, self.format) {
+                            eprintln!("Failed to write replay {}: {:?}", path.display(), error);
+                        }
+                    }
+                    None => self.replays.push(replay),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+    use crate::player::MeepleColor;
+    use crate::simulate::play_game_observed;
+
+    fn recorded_lazy_game() -> Replay {
+        recorded_lazy_game_in(ReplayFormat::Json)
+    }
+
+    fn recorded_lazy_game_in(format: ReplayFormat) -> Replay {
+        let players = [Player::red().with_name("Alice"), Player::green().with_name("Bob")]
+            .into_iter()
+            .map(|p| (p.meeple_color, p.with_bot(BotStrategy::Lazy(LazyBot))))
+            .collect();
+
+        let directory = std::env::temp_dir().join(format!("carcassonne-replay-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(ReplayRecorder::to_directory(&directory).with_format(format))];
+        play_game_observed(players, [1; 32], false, 0, &mut observers);
+
+        let replay = Replay::load(directory.join(format!("game-00000.{}", format.extension()))).expect("replay should be written");
+        fs::remove_dir_all(&directory).unwrap();
+
+        replay
+    }
+
+    #[derive(Default)]
+    struct EventCollector(Vec<GameEvent>);
+
+    impl GameObserver for EventCollector {
+        fn notify(&mut self, event: &GameEvent) {
+            self.0.push(event.clone());
+        }
+    }
+
+    fn lazy_game_events() -> Vec<GameEvent> {
+        let players = [Player::red(), Player::green()]
+            .into_iter()
+            .map(|p| (p.meeple_color, p.with_bot(BotStrategy::Lazy(LazyBot))))
+            .collect();
+
+        let collector = std::rc::Rc::new(std::cell::RefCell::new(EventCollector::default()));
+        let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(std::rc::Rc::clone(&collector))];
+        play_game_observed(players, [1; 32], false, 0, &mut observers);
+
+        collector.take().0
+    }
+
+    #[test]
+    fn should_rebuild_the_board_from_the_event_log() {
+        let events = lazy_game_events();
+        let placements = events.iter().filter(|event| matches!(event, GameEvent::TurnCompleted { placement: Some(_), .. })).count();
+
+        let board = Board::from_events(&events).expect("the log should be consistent");
+
+        assert_eq!(board.placed_tile_count(), placements);
+    }
+
+    #[test]
+    fn should_flag_a_recorded_score_that_diverges() {
+        let mut events = lazy_game_events();
+
+        let (turn, tampered) = events
+            .iter_mut()
+            .filter(|event| matches!(event, GameEvent::TurnCompleted { .. }))
+            .enumerate()
+            .find(|(_, event)| matches!(event, GameEvent::TurnCompleted { placement: Some(_), .. }))
+            .unwrap();
+
+        if let GameEvent::TurnCompleted { score_delta, .. } = tampered {
+            *score_delta = Score::from_iter([(&Player::red(), 99)]);
+        }
+
+        assert!(matches!(Board::from_events(&events), Err(ReplayError::ScoreDivergence { turn: diverged, .. }) if diverged == turn));
+    }
+
+    #[test]
+    fn should_round_trip_through_json() {
+        let replay = recorded_lazy_game();
+
+        assert_eq!(Replay::from_json(&replay.to_json()).unwrap(), replay);
+        assert_eq!(replay.seed(), Some([1; 32]));
+    }
+
+    #[test]
+    fn should_load_a_replay_recorded_in_binary() {
+        let replay = recorded_lazy_game_in(ReplayFormat::Binary);
+
+        assert_eq!(Replay::from_binary(&replay.to_binary().unwrap()).unwrap(), replay);
+        assert_eq!(replay.state_at(replay.turns.len()).unwrap().final_score(), replay.final_score());
+    }
+
+    #[test]
+    fn should_rebuild_the_final_score_from_the_recorded_turns() {
+        let replay = recorded_lazy_game();
+
+        let state = replay.state_at(replay.turns.len()).expect("replay should be valid");
+
+        assert_eq!(state.final_score(), replay.final_score());
+        assert_eq!(state.board.placed_tile_count(), replay.turns.iter().filter(|t| t.placement.is_some()).count());
+    }
+
+    #[test]
+    fn should_play_back_a_game_under_the_rules_it_was_played_under() {
+        let rules = RuleSet::preset("first-edition").unwrap().with_meeple_per_player(5).with_starting_layout(StartingLayout::count_city());
+        let recorder = std::rc::Rc::new(std::cell::RefCell::new(ReplayRecorder::new()));
+
+        crate::game_logic::Game::builder()
+            .with_seed([3; 32])
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_rules(rules)
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(std::rc::Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+
+        assert!(!replay.rules.is_standard());
+        assert_eq!(replay.rules.rules().unwrap().scoring.name(), "first edition");
+        assert!(replay.verify().is_ok());
+        assert_eq!(Replay::from_json(&replay.to_json()).unwrap(), replay);
+        assert_eq!(Replay::from_binary(&replay.to_binary().unwrap()).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_play_back_a_handicapped_game_from_its_starting_scores() {
+        let rules = RuleSet::standard().with_starting_score(MeepleColor::Red, 50).with_score_multiplier(MeepleColor::Green, 0.5);
+        let recorder = std::rc::Rc::new(std::cell::RefCell::new(ReplayRecorder::new()));
+
+        crate::game_logic::Game::builder()
+            .with_seed([3; 32])
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_rules(rules.clone())
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(std::rc::Rc::clone(&recorder))]);
+
+        let replay = recorder.borrow().replays()[0].clone();
+
+        assert_eq!(replay.rules.rules().unwrap(), rules);
+        assert_eq!(replay.initial_state().unwrap().score, Score::from_iter([(&Player::red(), 50), (&Player::green(), 0)]));
+        assert!(replay.verify().is_ok());
+        assert_eq!(Replay::from_binary(&replay.to_binary().unwrap()).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_verify_the_recorded_final_score() {
+        let mut replay = recorded_lazy_game();
+
+        assert!(replay.verify().is_ok());
+
+        replay.final_score[0].points += 1;
+
+        assert!(matches!(replay.verify(), Err(ReplayError::FinalScoreDivergence { .. })));
+    }
+
+    #[test]
+    fn should_record_the_shuffled_deck() {
+        let replay = recorded_lazy_game();
+        let deck_order = replay.deck_order().unwrap();
+
+        assert!(deck_order.len() >= replay.turns.len());
+        assert_eq!(replay.deck[0], replay.turns[0].tile);
+
+        let mut json: serde_json::Value = serde_json::from_str(&replay.to_json()).unwrap();
+        json.as_object_mut().unwrap().remove("deck");
+
+        assert!(Replay::from_json(&json.to_string()).unwrap().deck.is_empty());
+    }
+
+    #[test]
+    fn should_stamp_json_with_the_format_and_tile_set() {
+        let replay = recorded_lazy_game();
+        let json: serde_json::Value = serde_json::from_str(&replay.to_json()).unwrap();
+
+        assert_eq!(json["format_version"], JSON_FORMAT_VERSION);
+        assert_eq!(json["tile_set"], tile_definitions::tile_set_hash());
+        assert_eq!(Replay::from_json(&replay.to_json()).unwrap(), replay);
+    }
+
+    #[test]
+    fn should_migrate_json_from_older_versions_and_tile_sets() {
+        let replay = recorded_lazy_game();
+        let mut json: serde_json::Value = serde_json::from_str(&replay.to_json()).unwrap();
+
+        let unversioned = json.as_object_mut().unwrap();
+        unversioned.remove("format_version");
+        unversioned.remove("tile_set");
+        assert_eq!(Replay::from_json(&json.to_string()).unwrap(), replay);
+
+        json["tile_set"] = "0123456789abcdef".into();
+        assert_eq!(Replay::from_json(&json.to_string()).unwrap(), replay);
+
+        json["turns"][0]["tile"] = "Dragon's lair".into();
+        let error = Replay::from_json(&json.to_string()).unwrap_err();
+        assert_eq!(error.to_string(), format!("saved with tile set 0123456789abcdef, current is {}", tile_definitions::tile_set_hash()));
+
+        json["format_version"] = (JSON_FORMAT_VERSION + 1).into();
+        assert!(matches!(Replay::from_json(&json.to_string()), Err(ReplayError::UnsupportedVersion(_))));
+    }
+
+    #[test]
+    fn should_reject_a_replay_with_an_unknown_tile() {
+        let mut replay = recorded_lazy_game();
+        replay.turns[3].tile = "Dragon's lair".to_string();
+
+        assert!(matches!(replay.state_at(4), Err(ReplayError::UnknownTile(_))));
+    }
+}
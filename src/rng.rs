@@ -0,0 +1,68 @@
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+
+/// The random number generator behind everything a seed has to reproduce: deck shuffles, bots and
+/// seating. It is ChaCha20, pinned rather than rand's `StdRng`, whose algorithm may change between
+/// rand versions and so wouldn't replay old seeds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRng(ChaCha20Rng);
+
+impl GameRng {
+    /// One of many independent generators from the same seed, e.g. one for each game of a batch,
+    /// so each game's randomness doesn't depend on which games were played before it
+    pub fn stream(seed: <Self as SeedableRng>::Seed, index: u64) -> Self {
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        rng.set_stream(index);
+
+        Self(rng)
+    }
+}
+
+impl SeedableRng for GameRng {
+    type Seed = <ChaCha20Rng as SeedableRng>::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self(ChaCha20Rng::from_seed(seed))
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_produce_the_chacha20_keystream() {
+        // the first words of the RFC 7539 keystream for an all zero key and nonce
+        let mut rng = GameRng::from_seed([0; 32]);
+
+        assert_eq!(rng.next_u32(), 0xade0b876);
+        assert_eq!(rng.next_u32(), 0x903df1a0);
+    }
+
+    #[test]
+    fn should_give_each_stream_its_own_numbers() {
+        let first = |mut rng: GameRng| rng.next_u64();
+
+        assert_eq!(first(GameRng::stream([0; 32], 0)), first(GameRng::from_seed([0; 32])));
+        assert_eq!(first(GameRng::stream([0; 32], 1)), first(GameRng::stream([0; 32], 1)));
+        assert_ne!(first(GameRng::stream([0; 32], 1)), first(GameRng::stream([0; 32], 2)));
+    }
+}
@@ -0,0 +1,603 @@
+use crate::player::{Player, PlayerIdentifier, MEEPLE_COUNT};
+use crate::board::{Board, InvalidTilePlacement};
+use crate::connected_regions::ConnectedRegion;
+use crate::sketch::{self, SketchError};
+use crate::score::{Score, ScoringRules, Scoring, SmallCityScoring};
+use crate::tile::{BoardTopology, Expansion, ExpansionError, PlacedTile, RegionType, Rotation, TileDefinition, check_expansions};
+use crate::tile_definitions::{CORNER_CITY, CROSS_INTERSECTION, STRAIGHT_ROAD, STRAIGHT_ROAD_WITH_SIDE_CITY};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// The rules a game is played under
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleSet {
+    /// Number of meeple each player starts with
+    pub(crate) meeple_per_player: usize,
+    /// Points players start the game with, to give weaker players a head start
+    pub(crate) starting_scores: HashMap<PlayerIdentifier, i32>,
+    /// What each player's points are multiplied by as they are scored
+    pub(crate) score_multipliers: HashMap<PlayerIdentifier, f64>,
+    /// How features are scored
+    pub(crate) scoring: Scoring,
+    pub(crate) small_city_scoring: SmallCityScoring,
+    pub(crate) discard_policy: DiscardPolicy,
+    pub(crate) starting_tile: StartingTile,
+    pub(crate) river: RiverOptions,
+    /// The window every tile has to fit within, if the board is limited
+    pub(crate) board_limit: Option<BoardLimit>,
+    pub(crate) topology: BoardTopology,
+    /// How many players the rules are for, if they are only played with that many
+    pub(crate) player_count: Option<usize>,
+    pub(crate) tie_break: TieBreak,
+    /// Whether meeple may be placed in fields, see [RuleSet::with_farmers]
+    pub(crate) farmers: bool,
+    /// Whether players may take back one of their meeple in place of placing one, see
+    /// [crate::controller::PlayerAction::RecallMeeple]
+    pub(crate) meeple_recall: bool,
+    /// Whether tiles left in the deck that no longer fit anywhere on the board are taken out of it,
+    /// see [RuleSet::with_dead_tile_pruning]
+    pub(crate) prune_dead_tiles: bool,
+    /// Whether moves with nothing to choose between are played without asking, see
+    /// [RuleSet::with_obvious_moves_played]
+    pub(crate) play_obvious_moves: bool,
+    pub(crate) starting_layout: StartingLayout,
+}
+
+/// The most columns and rows of tiles a board may span, wherever they are, as in adaptations played
+/// on a fixed size table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardLimit {
+    pub width: u8,
+    pub height: u8,
+}
+
+/// What happens to a drawn tile that can't be placed anywhere
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiscardPolicy {
+    /// It's shuffled back into the deck once a tile that can be placed is drawn
+    #[default]
+    Reshuffle,
+    /// It's out of the game
+    Remove,
+}
+
+/// The tile a game without the river starts from
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum StartingTile {
+    /// Whichever tile is on top of the shuffled deck
+    #[default]
+    Drawn,
+    /// A copy of the tile, taken out of the deck
+    Fixed(&'static TileDefinition),
+}
+
+/// Names of the layouts [StartingLayout::preset] knows
+pub const LAYOUT_NAMES: [&str; 2] = ["count-city", "wheel"];
+
+/// Tiles laid on the board before the first tile is drawn, in the order they are laid. They are
+/// laid as well as the deck rather than taken out of it, as the Count's city is
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StartingLayout {
+    tiles: Vec<PlacedTile>,
+}
+
+impl StartingLayout {
+    /// Each tile has to be placeable where the tiles before it leave room, as if played in turn
+    pub fn new<T: IntoIterator<Item = PlacedTile>>(tiles: T) -> Self {
+        Self { tiles: tiles.into_iter().collect() }
+    }
+
+    /// A walled city of four corner cities, standing in for the city of Carcassonne the Count
+    /// starts from, whose own tiles aren't in the tile set
+    pub fn count_city() -> Self {
+        Self::new([
+            PlacedTile::new(&CORNER_CITY, 0, 0, Rotation::R270),
+            PlacedTile::new(&CORNER_CITY, 1, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_CITY, 1, 1, Rotation::R90),
+            PlacedTile::new(&CORNER_CITY, 0, 1, Rotation::R180),
+        ])
+    }
+
+    /// A crossroads with a road leading out of each side, in the shape of the Wheel of Fortune's
+    /// starting tile
+    pub fn wheel() -> Self {
+        Self::new([
+            PlacedTile::new(&CROSS_INTERSECTION, 0, 0, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, 0, -1, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R90),
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R0),
+            PlacedTile::new(&STRAIGHT_ROAD, -1, 0, Rotation::R90),
+        ])
+    }
+
+    /// The layout drawn in `sketch`, as [sketch::parse] reads it
+    pub fn from_sketch(sketch: &str) -> Result<Self, SketchError> {
+        sketch::parse(sketch).map(Self::new)
+    }
+
+    /// The layout called `name`, one of [LAYOUT_NAMES]
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "count-city" => Some(Self::count_city()),
+            "wheel" => Some(Self::wheel()),
+            _ => None,
+        }
+    }
+
+    pub fn tiles(&self) -> &[PlacedTile] {
+        &self.tiles
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+}
+
+/// How players level on points at the end of a game are told apart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TieBreak {
+    /// They share the win
+    #[default]
+    Shared,
+    /// The player who completed the most cities, roads and cloisters wins, sharing it only if
+    /// they completed as many
+    MostCompletedFeatures,
+}
+
+/// How the river is dealt and laid, in games with the river
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RiverOptions {
+    /// Shortens the river to a random length of at least this many tiles between its ends, leaving
+    /// the rest out of the game. The whole river is dealt without it
+    pub min_length: Option<usize>,
+    /// Stops the river ending on a tile next to its spring. The river is dealt more tiles, from
+    /// those left out, until its end can go elsewhere
+    pub separate_ends: bool,
+    /// Ends the river in a lake in place of a second terminator
+    pub lake: bool,
+}
+
+/// Fewest columns or rows of a [BoardTopology::Torus]
+pub const MIN_TORUS_SIZE: u8 = 3;
+
+/// Names of the presets [RuleSet::preset] knows
+pub const PRESET_NAMES: [&str; 5] = ["standard", "first-edition", "2021", "tournament", "wtcoc"];
+
+/// Scoring as the first edition printed it, with farmers supplying cities with four points each
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FirstEditionScoring;
+
+impl ScoringRules for FirstEditionScoring {
+    fn name(&self) -> &'static str {
+        "first edition"
+    }
+
+    fn score_field(&self, region: &ConnectedRegion, board: &Board) -> u32 {
+        let adjacent_closed_city_count = board.adjacent_regions(region).into_iter().filter(|region| {
+            *region.region_type() == RegionType::City && region.is_closed()
+        }).count();
+
+        (adjacent_closed_city_count * 4) as u32
+    }
+}
+
+impl RuleSet {
+    /// The base game rules
+    pub fn standard() -> Self {
+        Self {
+            meeple_per_player: MEEPLE_COUNT,
+            starting_scores: HashMap::new(),
+            score_multipliers: HashMap::new(),
+            scoring: Scoring::default(),
+            small_city_scoring: SmallCityScoring::default(),
+            discard_policy: DiscardPolicy::default(),
+            starting_tile: StartingTile::default(),
+            river: RiverOptions::default(),
+            board_limit: None,
+            topology: BoardTopology::default(),
+            player_count: None,
+            tie_break: TieBreak::default(),
+            farmers: true,
+            meeple_recall: false,
+            prune_dead_tiles: false,
+            play_obvious_moves: false,
+            starting_layout: StartingLayout::default(),
+        }
+    }
+
+    /// The rules of the first edition: a set starting tile, unplayable tiles out of the game, two
+    /// tile cities scoring two points, and [FirstEditionScoring]
+    pub fn first_edition() -> Self {
+        Self::standard_2021().with_scoring(FirstEditionScoring).with_small_city_scoring(SmallCityScoring::Single)
+    }
+
+    /// The rules of the 2021 edition: a set starting tile and unplayable tiles out of the game
+    pub fn standard_2021() -> Self {
+        Self::standard()
+            .with_starting_tile(StartingTile::Fixed(&STRAIGHT_ROAD_WITH_SIDE_CITY))
+            .with_discard_policy(DiscardPolicy::Remove)
+    }
+
+    /// The 2021 edition's rules with unplayable tiles shuffled back, so every game plays out the
+    /// whole deck and players are compared on the same tiles
+    pub fn tournament() -> Self {
+        Self::standard_2021().with_discard_policy(DiscardPolicy::Reshuffle)
+    }
+
+    /// The rules of the world championship's two player games: the 2021 edition's set starting
+    /// tile and unplayable tiles out of the game, farmers scored as usual, and ties going to the
+    /// player who completed the most features. Events played without farmers add
+    /// [RuleSet::with_farmers] to it
+    pub fn wtcoc() -> Self {
+        Self::standard_2021().with_player_count(2).with_tie_break(TieBreak::MostCompletedFeatures)
+    }
+
+    /// The rule set called `name`, one of [PRESET_NAMES]
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "standard" => Some(Self::standard()),
+            "first-edition" => Some(Self::first_edition()),
+            "2021" => Some(Self::standard_2021()),
+            "tournament" => Some(Self::tournament()),
+            "wtcoc" => Some(Self::wtcoc()),
+            _ => None,
+        }
+    }
+
+    pub fn with_meeple_per_player(mut self, meeple_per_player: usize) -> Self {
+        self.meeple_per_player = meeple_per_player;
+        self
+    }
+
+    /// Starts the player on `points` rather than zero. Negative points handicap them
+    pub fn with_starting_score(mut self, player: PlayerIdentifier, points: i32) -> Self {
+        self.starting_scores.insert(player, points);
+        self
+    }
+
+    /// Multiplies every score the player earns by `multiplier`, rounded to the nearest point
+    pub fn with_score_multiplier(mut self, player: PlayerIdentifier, multiplier: f64) -> Self {
+        self.score_multipliers.insert(player, multiplier);
+        self
+    }
+
+    /// Scores features under `scoring` in place of the base game's [crate::score::StandardScoring]
+    pub fn with_scoring<S: ScoringRules + 'static>(mut self, scoring: S) -> Self {
+        self.scoring = Scoring::new(scoring);
+        self
+    }
+
+    pub fn with_small_city_scoring(mut self, small_city_scoring: SmallCityScoring) -> Self {
+        self.small_city_scoring = small_city_scoring;
+        self
+    }
+
+    pub fn with_discard_policy(mut self, discard_policy: DiscardPolicy) -> Self {
+        self.discard_policy = discard_policy;
+        self
+    }
+
+    pub fn with_starting_tile(mut self, starting_tile: StartingTile) -> Self {
+        self.starting_tile = starting_tile;
+        self
+    }
+
+    pub fn with_river_options(mut self, river: RiverOptions) -> Self {
+        self.river = river;
+        self
+    }
+
+    /// Keeps every tile within a `width` by `height` window
+    pub fn with_board_limit(mut self, width: u8, height: u8) -> Self {
+        self.board_limit = Some(BoardLimit { width, height });
+        self
+    }
+
+    /// Plays on a board whose edges join up as `topology` says, in place of the endless table
+    pub fn with_topology(mut self, topology: BoardTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Only lets games under the rules be built with exactly `player_count` players
+    pub fn with_player_count(mut self, player_count: usize) -> Self {
+        self.player_count = Some(player_count);
+        self
+    }
+
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Lets meeple be placed in fields as farmers, as they are by default. Without them fields are
+    /// never occupied, so never scored
+    pub fn with_farmers(mut self, farmers: bool) -> Self {
+        self.farmers = farmers;
+        self
+    }
+
+    /// Whether games under the rules can be played by `player_count` players, see
+    /// [RuleSet::with_player_count]
+    pub fn allows_player_count(&self, player_count: usize) -> bool {
+        self.player_count.is_none_or(|count| count == player_count)
+    }
+
+    /// Lets players take a meeple back off the board unscored in place of placing one, a house
+    /// rule for rescuing meeple stranded in features that will never be finished
+    pub fn with_meeple_recall(mut self, meeple_recall: bool) -> Self {
+        self.meeple_recall = meeple_recall;
+        self
+    }
+
+    /// Takes tiles out of the deck as soon as none of their copies fit anywhere on the board, so
+    /// simulations don't spend their last turns drawing tiles only to set them aside. Tiles that
+    /// might have fitted again as the board grew are lost with them
+    pub fn with_dead_tile_pruning(mut self, prune_dead_tiles: bool) -> Self {
+        self.prune_dead_tiles = prune_dead_tiles;
+        self
+    }
+
+    /// Plays the drawn tile for the player, without asking them, when it fits in only one place and
+    /// no meeple is worth placing on it, telling observers with
+    /// [crate::observer::GameEvent::ObviousMovePlayed]. Saves waiting on a person, or a slow bot,
+    /// for a move with nothing to decide
+    pub fn with_obvious_moves_played(mut self, play_obvious_moves: bool) -> Self {
+        self.play_obvious_moves = play_obvious_moves;
+        self
+    }
+
+    /// Lays the tiles on the board before the first draw. Replays record only the turns, so a game
+    /// played from a layout is played back by [crate::Board::from_events_under] its rules
+    pub fn with_starting_layout(mut self, starting_layout: StartingLayout) -> Self {
+        self.starting_layout = starting_layout;
+        self
+    }
+
+    /// The score before anyone has played, with each player on their starting score
+    pub(crate) fn starting_score<'a, I: IntoIterator<Item = &'a Player>>(&self, players: I) -> Score {
+        Score::from_iter(players.into_iter().map(|p| (p, self.starting_scores.get(&p.meeple_color).copied().unwrap_or(0))))
+    }
+
+    /// Points as they count towards each player's score once the multipliers are applied
+    pub(crate) fn adjust(&self, score: Score) -> Score {
+        score.scaled(&self.score_multipliers)
+    }
+
+    /// Checks the rules can be played with `expansions`, which have to be supported together (see
+    /// [Expansion::compatibility_matrix])
+    pub fn validate(&self, expansions: &[Expansion]) -> Result<(), RuleSetError> {
+        check_expansions(expansions).map_err(RuleSetError::Expansions)?;
+
+        if self.meeple_per_player == 0 {
+            return Err(RuleSetError::NoMeeple);
+        }
+
+        if self.player_count == Some(0) {
+            return Err(RuleSetError::NoPlayers);
+        }
+
+        if self.board_limit.is_some_and(|limit| limit.width == 0 || limit.height == 0) {
+            return Err(RuleSetError::EmptyBoardLimit);
+        }
+
+        if let BoardTopology::Torus { width, height } = self.topology {
+            // smaller tori would make a tile its own neighbour, or its neighbours' neighbour from
+            // both sides
+            if !(MIN_TORUS_SIZE..=i8::MAX as u8).contains(&width) || !(MIN_TORUS_SIZE..=i8::MAX as u8).contains(&height) {
+                return Err(RuleSetError::InvalidTorusSize(width, height));
+            }
+        }
+
+        if !self.starting_layout.is_empty() {
+            // the river has to start from its spring
+            if expansions.contains(&Expansion::River) {
+                return Err(RuleSetError::StartingLayoutWithRiver);
+            }
+
+            Board::new()
+                .with_limit(self.board_limit)
+                .with_topology(self.topology)
+                .lay(&self.starting_layout)
+                .map_err(|(index, reason)| RuleSetError::InvalidStartingLayout(index, reason))?;
+        }
+
+        if let StartingTile::Fixed(tile) = self.starting_tile {
+            if tile.expansion.is_some_and(|expansion| !expansions.contains(&expansion)) {
+                return Err(RuleSetError::StartingTileNotInDeck(tile.name()));
+            }
+        }
+
+        // sorted so the same rules always report the same player
+        let mut multipliers: Vec<_> = self.score_multipliers.iter().collect();
+        multipliers.sort_by_key(|(player, _)| player.seat());
+
+        match multipliers.into_iter().find(|(_, multiplier)| !multiplier.is_finite() || **multiplier < 0.0) {
+            Some((player, multiplier)) => Err(RuleSetError::InvalidScoreMultiplier(*player, *multiplier)),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether the rules treat any player differently from the others
+    pub fn has_handicaps(&self) -> bool {
+        !self.starting_scores.is_empty() || !self.score_multipliers.is_empty()
+    }
+}
+
+/// Why a [RuleSet] can't be played
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleSetError {
+    Expansions(ExpansionError),
+    NoMeeple,
+    /// Rules for games of no players can't be played
+    NoPlayers,
+    /// A board limited to no rows or columns has nowhere to put a tile
+    EmptyBoardLimit,
+    /// Tori have to be at least [MIN_TORUS_SIZE] tiles across each way, and no more than fit a
+    /// coordinate
+    InvalidTorusSize(u8, u8),
+    /// The starting tile belongs to an expansion the game isn't played with
+    StartingTileNotInDeck(&'static str),
+    /// Multipliers have to be a finite number of points, and can't take points away
+    InvalidScoreMultiplier(PlayerIdentifier, f64),
+    /// The tile at the index in the starting layout can't be laid where it is
+    InvalidStartingLayout(usize, InvalidTilePlacement),
+    StartingLayoutWithRiver,
+}
+
+impl Display for RuleSetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleSetError::Expansions(error) => write!(f, "{}", error),
+            RuleSetError::NoMeeple => write!(f, "players need at least one meeple"),
+            RuleSetError::NoPlayers => write!(f, "the rules have to be for at least one player"),
+            RuleSetError::EmptyBoardLimit => write!(f, "the board has to be limited to at least one row and column"),
+            RuleSetError::InvalidTorusSize(width, height) => {
+                write!(f, "a {}x{} torus has to be between {} and {} tiles each way", width, height, MIN_TORUS_SIZE, i8::MAX)
+            }
+            RuleSetError::StartingTileNotInDeck(name) => write!(f, "the starting tile [{}] isn't in the deck", name),
+            RuleSetError::InvalidScoreMultiplier(player, multiplier) => {
+                write!(f, "{}'s score multiplier of {} has to be a number no less than zero", player, multiplier)
+            }
+            RuleSetError::InvalidStartingLayout(index, reason) => write!(f, "tile {} of the starting layout can't be laid: {}", index + 1, reason),
+            RuleSetError::StartingLayoutWithRiver => write!(f, "games with the river start from its spring, so can't have a starting layout"),
+        }
+    }
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::MeepleColor;
+
+    #[test]
+    fn should_start_players_on_their_handicap() {
+        let alice = Player::red();
+        let bob = Player::green();
+
+        let rules = RuleSet::standard().with_starting_score(bob.meeple_color, 10);
+
+        assert_eq!(rules.starting_score([&alice, &bob]), Score::from_iter([(&alice, 0), (&bob, 10)]));
+        assert!(rules.has_handicaps());
+        assert!(!RuleSet::standard().has_handicaps());
+    }
+
+    #[test]
+    fn should_reject_rules_that_cant_be_played() {
+        assert_eq!(RuleSet::standard().validate(&[Expansion::River]), Ok(()));
+        assert_eq!(
+            RuleSet::standard().validate(&[Expansion::River, Expansion::River]),
+            Err(RuleSetError::Expansions(ExpansionError::Repeated(Expansion::River)))
+        );
+        assert_eq!(RuleSet::standard().with_meeple_per_player(0).validate(&[]), Err(RuleSetError::NoMeeple));
+
+        let negative = RuleSet::standard().with_score_multiplier(MeepleColor::Green, -1.0);
+        assert_eq!(negative.validate(&[]), Err(RuleSetError::InvalidScoreMultiplier(MeepleColor::Green, -1.0)));
+        assert_eq!(
+            negative.validate(&[]).unwrap_err().to_string(),
+            "Green's score multiplier of -1 has to be a number no less than zero"
+        );
+    }
+
+    #[test]
+    fn should_multiply_only_the_players_given_a_multiplier() {
+        let alice = Player::red();
+        let bob = Player::green();
+
+        let rules = RuleSet::standard().with_score_multiplier(alice.meeple_color, 1.5);
+
+        assert_eq!(
+            rules.adjust(Score::from_iter([(&alice, 3), (&bob, 3)])),
+            Score::from_iter([(&alice, 5), (&bob, 3)])
+        );
+    }
+
+    #[test]
+    fn should_find_every_preset_by_name() {
+        for name in PRESET_NAMES {
+            assert!(RuleSet::preset(name).is_some(), "no preset called {}", name);
+        }
+
+        assert_eq!(RuleSet::preset("standard"), Some(RuleSet::standard()));
+        assert_eq!(RuleSet::preset("2021").unwrap().discard_policy, DiscardPolicy::Remove);
+        assert_eq!(RuleSet::preset("tournament").unwrap().discard_policy, DiscardPolicy::Reshuffle);
+        assert_eq!(RuleSet::preset("wtcoc").unwrap().player_count, Some(2));
+        assert!(RuleSet::wtcoc().allows_player_count(2) && !RuleSet::wtcoc().allows_player_count(3));
+        assert!(RuleSet::standard().allows_player_count(5));
+        assert_ne!(RuleSet::first_edition(), RuleSet::standard_2021());
+        assert_eq!(RuleSet::preset("house"), None);
+    }
+
+    #[test]
+    fn should_score_small_cities_and_farms_as_the_first_edition() {
+        use crate::player::{Meeple, RegionIndex};
+        use crate::tile::PlacedTile;
+        use crate::tile_definitions::SIDE_CITY;
+
+        let mut board = Board::under_rules(&RuleSet::first_edition()).unwrap();
+
+        board.place_tile(PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(0), Meeple::new(MeepleColor::Green)))).unwrap();
+        let closed = board.place_tile(PlacedTile::new_with_meeple(&SIDE_CITY, 0, 1, Rotation::R180, (RegionIndex::new(1), Meeple::new(MeepleColor::Red)))).unwrap();
+
+        // the two tile city scores a point a tile, without doubling
+        assert_eq!(closed.score_delta, [(MeepleColor::Red, 2)].into_iter().collect());
+        // and the farm beside it four points
+        assert_eq!(board.calculate_board_score(), [(MeepleColor::Green, 4)].into_iter().collect());
+    }
+
+    #[test]
+    fn should_limit_the_board_to_at_least_one_tile() {
+        assert_eq!(RuleSet::standard().with_board_limit(15, 15).validate(&[]), Ok(()));
+        assert_eq!(RuleSet::standard().with_board_limit(15, 0).validate(&[]), Err(RuleSetError::EmptyBoardLimit));
+    }
+
+    #[test]
+    fn should_only_wrap_a_torus_big_enough_to_turn_a_corner() {
+        let torus = |width, height| RuleSet::standard().with_topology(BoardTopology::Torus { width, height }).validate(&[]);
+
+        assert_eq!(torus(8, 8), Ok(()));
+        assert_eq!(torus(2, 8), Err(RuleSetError::InvalidTorusSize(2, 8)));
+    }
+
+    #[test]
+    fn should_only_start_from_a_tile_in_the_deck() {
+        use crate::tile_definitions::RIVER_TERMINATOR;
+
+        let river_start = RuleSet::standard().with_starting_tile(StartingTile::Fixed(&RIVER_TERMINATOR));
+
+        assert_eq!(river_start.validate(&[]), Err(RuleSetError::StartingTileNotInDeck(RIVER_TERMINATOR.name())));
+        assert_eq!(river_start.validate(&[Expansion::River]), Ok(()));
+    }
+
+    #[test]
+    fn should_lay_the_starting_layout_before_the_first_draw() {
+        for name in LAYOUT_NAMES {
+            let rules = RuleSet::standard().with_starting_layout(StartingLayout::preset(name).unwrap());
+
+            assert_eq!(rules.validate(&[]), Ok(()), "{}", name);
+            assert_eq!(rules.validate(&[Expansion::River]), Err(RuleSetError::StartingLayoutWithRiver));
+        }
+
+        let board = Board::under_rules(&RuleSet::standard().with_starting_layout(StartingLayout::count_city())).unwrap();
+        let city = board.get_connected_regions().into_iter().find(|region| region.region_type == RegionType::City).unwrap();
+
+        assert_eq!(board.placed_tile_count(), 4);
+        assert!(city.is_closed());
+
+        // the second tile is nowhere near the first
+        let scattered = StartingLayout::new([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&STRAIGHT_ROAD, 5, 5, Rotation::R0)]);
+
+        let scattered = RuleSet::standard().with_starting_layout(scattered);
+        let expected = RuleSetError::InvalidStartingLayout(1, InvalidTilePlacement::TileDoesNotContactPlacedTiles);
+
+        assert_eq!(scattered.validate(&[]), Err(expected.clone()));
+        // nor is a board laid out under the rules without validating them
+        assert_eq!(Board::under_rules(&scattered).err(), Some(expected));
+    }
+}
@@ -1,32 +1,70 @@
-use std::collections::hash_map::Iter;
 use crate::board::Board;
 use crate::connected_regions::ConnectedRegion;
-use crate::player::{Meeple, Player, PlayerIdentifier};
-use crate::tile::{Region, RegionType, RenderStyle};
+use crate::player::{MeepleColor, Player, PlayerIdentifier, SEATS};
+#[cfg(feature = "render")]
+use crate::player::Meeple;
+use crate::tile::RegionType;
+#[cfg(feature = "render")]
+use crate::tile::RenderStyle;
+#[cfg(feature = "render")]
 use colored::Colorize;
 use std::collections::HashMap;
-use std::ops::{Add, AddAssign, Sub};
+use std::fmt::{Debug, Formatter};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Deref, Sub};
+use std::sync::Arc;
 use indexmap::IndexMap;
+use serde::Serialize;
 
+/// Points by player, in a slot for each seat so working scores out doesn't allocate. A player
+/// without their slot filled hasn't been scored, which isn't the same as scoring nothing
 #[derive(Debug, Default, PartialEq, Clone)]
-pub struct Score(HashMap<PlayerIdentifier, i32>);
+pub struct Score([Option<i32>; SEATS.len()]);
+
+/// A player's points with their name and colour, for exports read outside the process where a
+/// [PlayerIdentifier] on its own says little about who scored them
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NamedScore {
+    /// The player's name, or their colour for a player without one
+    pub name: String,
+    pub color: MeepleColor,
+    pub points: i32,
+}
 
 impl Score {
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self(Default::default())
     }
 
+    /// Each scored player's points with their name and colour, in seat order
+    pub fn to_named(&self, players: &IndexMap<PlayerIdentifier, Player>) -> Vec<NamedScore> {
+        self.iter()
+            .map(|(player_id, points)| NamedScore {
+                name: players.get(player_id).and_then(Player::name).map_or_else(|| player_id.to_string(), str::to_string),
+                color: *player_id,
+                points: *points,
+            })
+            .collect()
+    }
+
 
     pub(crate) fn from_iter<'a, I: IntoIterator<Item=(&'a Player, i32)>>(player_score: I) -> Self {
-        Self(HashMap::from_iter(player_score.into_iter().map(|(player, score)|(player.meeple_color, score))))
+        let mut score = Score::new();
+
+        for (player, points) in player_score {
+            score.0[player.meeple_color.seat()] = Some(points);
+        }
+
+        score
     }
 
     // @todo make a proper pretty table
-    pub(crate) fn render(&self, players: &IndexMap<PlayerIdentifier, Player>, render_style: &RenderStyle) -> String {
+    #[cfg(feature = "render")]
+    pub fn render(&self, players: &IndexMap<PlayerIdentifier, Player>, render_style: &RenderStyle) -> String {
 
         let mut out = String::new();
 
-        for (player_id, score) in &self.0 {
+        for (player_id, score) in self.iter() {
 
             let player = players.get(player_id).expect("should exist");
 
@@ -37,16 +75,79 @@ impl Score {
 
     }
 
+    /// The score table with each player's meeple alongside: those still in hand as glyphs in
+    /// their colour, and those on the board counted by the feature they are on
+    #[cfg(feature = "render")]
+    pub fn render_with_meeple(&self, players: &IndexMap<PlayerIdentifier, Player>, board: &Board, render_style: &RenderStyle) -> String {
+        let mut out = String::new();
+
+        for (player_id, player) in players {
+            let name = player.name.clone().unwrap_or_else(|| player_id.to_string());
+            let color = player.meeple_color.render_color(render_style);
+
+            let mut deployed: Vec<(RegionType, usize)> = vec![];
+
+            for (region_type, meeple) in board.deployed_meeple() {
+                if meeple.color != *player_id {
+                    continue;
+                }
+
+                match deployed.iter_mut().find(|(deployed_type, _)| *deployed_type == region_type) {
+                    Some((_, count)) => *count += 1,
+                    None => deployed.push((region_type, 1)),
+                }
+            }
+
+            deployed.sort();
+
+            let deployed = deployed
+                .iter()
+                .map(|(region_type, count)| format!("{} on {:?}", count, region_type).to_lowercase())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            out += format!(
+                "{} = {} {}{}\n",
+                name.color(color),
+                self.0[player_id.seat()].unwrap_or(0),
+                "ꆜ".repeat(player.meeple.len()).color(color),
+                if deployed.is_empty() { String::new() } else { format!(" ({} deployed)", deployed) },
+            )
+            .as_str();
+        }
+
+        out
+    }
+
     pub(crate) fn add_score(&mut self, player_id: PlayerIdentifier, score: i32) {
-        *self.0.entry(player_id).or_insert(0) += score;
+        *self.0[player_id.seat()].get_or_insert(0) += score;
     }
 
-    pub(crate) fn get_player(&self, player: &Player) -> Option<&i32> {
-        self.0.get(&player.meeple_color)
+    pub fn get_player(&self, player: &Player) -> Option<&i32> {
+        self.0[player.meeple_color.seat()].as_ref()
     }
 
-    pub(crate) fn iter(&self) -> Iter<'_, PlayerIdentifier, i32> {
-        self.0.iter()
+    /// Each scored player and their points, in seat order
+    pub fn iter(&self) -> impl Iterator<Item = (&PlayerIdentifier, &i32)> {
+        SEATS.iter().zip(&self.0).filter_map(|(player_id, points)| Some((player_id, points.as_ref()?)))
+    }
+
+    /// The player with the most points and their points. When players are tied, which of them is
+    /// returned is unspecified
+    pub fn max_player(&self) -> Option<(PlayerIdentifier, i32)> {
+        self.iter().max_by_key(|(_, score)| **score).map(|(player_id, score)| (*player_id, *score))
+    }
+
+    /// Each player's points multiplied by their multiplier, rounded to the nearest point. Players
+    /// without a multiplier keep their points
+    pub(crate) fn scaled(mut self, multipliers: &HashMap<PlayerIdentifier, f64>) -> Self {
+        for (player_id, score) in SEATS.iter().zip(self.0.iter_mut()) {
+            if let (Some(score), Some(multiplier)) = (score, multipliers.get(player_id)) {
+                *score = (*score as f64 * multiplier).round() as i32;
+            }
+        }
+
+        self
     }
 
 }
@@ -56,97 +157,227 @@ impl Add for Score {
     type Output = Score;
 
     fn add(mut self, rhs: Self) -> Self::Output {
-        for (player_id, score) in rhs.0.iter() {
-            *self.0.entry(*player_id).or_insert(0) += score;
-        }
-
+        self += &rhs;
         self
     }
 }
 
 impl AddAssign for Score {
     fn add_assign(&mut self, rhs: Self) {
-        for (player_id, score) in rhs.0.iter() {
-            self.add_score(*player_id, *score);
+        *self += &rhs;
+    }
+}
+
+impl AddAssign<&Score> for Score {
+    fn add_assign(&mut self, rhs: &Score) {
+        for (own, other) in self.0.iter_mut().zip(rhs.0) {
+            if let Some(other) = other {
+                *own.get_or_insert(0) += other;
+            }
         }
     }
 }
 
+impl Sum for Score {
+    fn sum<I: Iterator<Item = Score>>(iter: I) -> Self {
+        iter.fold(Score::new(), |mut total, score| {
+            total += score;
+            total
+        })
+    }
+}
+
+impl<'a> Sum<&'a Score> for Score {
+    fn sum<I: Iterator<Item = &'a Score>>(iter: I) -> Self {
+        iter.fold(Score::new(), |mut total, score| {
+            total += score;
+            total
+        })
+    }
+}
+
+/// Collects points by player, adding up any player that appears more than once
+impl FromIterator<(PlayerIdentifier, i32)> for Score {
+    fn from_iter<I: IntoIterator<Item = (PlayerIdentifier, i32)>>(iter: I) -> Self {
+        let mut score = Score::new();
+
+        for (player_id, points) in iter {
+            score.add_score(player_id, points);
+        }
+
+        score
+    }
+}
+
 
 impl Sub for Score {
     type Output = Score;
 
     fn sub(mut self, rhs: Self) -> Self::Output {
-        for (player_id, score) in rhs.0.iter() {
-            *self.0.entry(*player_id).or_insert(0) -= *score;
+        for (own, other) in self.0.iter_mut().zip(rhs.0) {
+            if let Some(other) = other {
+                *own.get_or_insert(0) -= other;
+            }
         }
 
         self
     }
 }
 
-impl ConnectedRegion {
+#[cfg(feature = "render")]
+impl Board {
+    /// Every meeple on the board, with the type of feature it is on
+    pub(crate) fn deployed_meeple(&self) -> impl Iterator<Item = (RegionType, &Meeple)> {
+        self.placed_tiles.values().filter_map(|tile| {
+            let (region_index, meeple) = tile.meeple.as_ref()?;
 
-    pub(crate) fn score(&self, board: &Board) -> u32 {
-        match self.region_type {
-            RegionType::City => {
-
-                let base_score = self.tile_regions.iter().map(|region| match region.region {
-                    Region:: City { pennant: true, .. } => 2,
-                    Region:: City { pennant: false, .. } => 1,
-                    _ => unreachable!()
-                }).sum();
-
-                if self.is_closed() {
-                    base_score * 2
-                } else {
-                    base_score
-                }
+            Some((tile.tile.regions[**region_index].region_type(), meeple))
+        })
+    }
+}
 
-            },
-            RegionType::Field => {
+/// How completed and unfinished features are scored. Each method defaults to the base game's
+/// scoring, so a variant only has to override the features it scores differently
+pub trait ScoringRules: Send + Sync {
+    /// Identifies the scoring in rule set comparisons and reports
+    fn name(&self) -> &'static str;
+
+    /// A point for each tile and pennant, doubled once the city is closed, unless it is a two tile
+    /// city under rules that don't double those (see [SmallCityScoring])
+    fn score_city(&self, region: &ConnectedRegion, board: &Board) -> u32 {
+        let base_score = (region.tile_region_count() + region.pennant_count()) as u32;
+        let doubled = match board.small_city_scoring() {
+            SmallCityScoring::Doubled => true,
+            SmallCityScoring::Single => region.tile_region_count() > 2,
+        };
+
+        if region.is_closed() && doubled {
+            base_score * 2
+        } else {
+            base_score
+        }
+    }
 
-                let adjacent_closed_city_count = self.adjacent_regions.iter().filter(|&connected_region_id|{
-                    if let Some(region) = board.get_connected_region(connected_region_id) {
-                        region.region_type == RegionType::City && region.is_closed()
-                    } else {
-                        false
-                    }
-                }).count();
+    /// Three points for each closed city the field touches
+    fn score_field(&self, region: &ConnectedRegion, board: &Board) -> u32 {
+        let adjacent_closed_city_count = board.adjacent_regions(region).into_iter().filter(|region| {
+            *region.region_type() == RegionType::City && region.is_closed()
+        }).count();
+
+        (adjacent_closed_city_count * 3) as u32
+    }
 
-                (adjacent_closed_city_count * 3) as u32
+    /// A point for the cloister's tile and each tile around it
+    fn score_cloister(&self, region: &ConnectedRegion, board: &Board) -> u32 {
+        assert_eq!(region.tile_region_count(), 1);
+        let cloister_coordinate = region.tile_coordinates().next().expect("there should be one");
 
-            },
-            RegionType::Cloister => {
+        let adjacent_count = board.list_surrounding_tiles(&cloister_coordinate).len();
 
-                assert_eq!(self.tile_regions.len(), 1);
-                let cloister_coordinate = self.tile_regions.iter().map(|r|r.tile_position).next().expect("there should be one");
+        adjacent_count as u32 + 1
+    }
 
-                let adjacent_count = board.list_surrounding_tiles(&cloister_coordinate).len();
+    /// A point for each tile
+    fn score_road(&self, region: &ConnectedRegion, _board: &Board) -> u32 {
+        region.tile_region_count() as u32
+    }
 
-                adjacent_count as u32 + 1
-            },
-            RegionType::Road => self.tile_regions.len() as u32,
+    fn score(&self, region: &ConnectedRegion, board: &Board) -> u32 {
+        match region.region_type() {
+            RegionType::City => self.score_city(region, board),
+            RegionType::Field => self.score_field(region, board),
+            RegionType::Cloister => self.score_cloister(region, board),
+            RegionType::Road => self.score_road(region, board),
             RegionType::Water => 0
         }
     }
+}
+
+/// What a closed city of two tiles scores. Editions differ on whether it is doubled to four points
+/// like any other closed city, or only scores its two
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SmallCityScoring {
+    #[default]
+    Doubled,
+    Single,
+}
+
+/// The base game's scoring
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct StandardScoring;
+
+impl ScoringRules for StandardScoring {
+    fn name(&self) -> &'static str {
+        "standard"
+    }
+}
+
+/// The [ScoringRules] a board scores its features with, shared between the board's clones
+#[derive(Clone)]
+pub struct Scoring(Arc<dyn ScoringRules>);
+
+impl Scoring {
+    pub fn new<S: ScoringRules + 'static>(rules: S) -> Self {
+        Self(Arc::new(rules))
+    }
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Self::new(StandardScoring)
+    }
+}
+
+impl Deref for Scoring {
+    type Target = dyn ScoringRules;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl Debug for Scoring {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Scoring").field(&self.name()).finish()
+    }
+}
+
+impl PartialEq for Scoring {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl ConnectedRegion {
+
+    /// The points the region is worth under the board's [ScoringRules]
+    pub(crate) fn score(&self, board: &Board) -> u32 {
+        board.scoring().score(self, board)
+    }
 
     pub(crate) fn majority_meeple_player_ids(&self, board: &Board) -> Vec<PlayerIdentifier> {
-        let mut counts = HashMap::new();
+        let strength = self.meeple_strength(board);
 
-        for player_id in self.residents(board).iter().map(|(_, _, &ref meeple)| meeple.color) {
-            *counts.entry(player_id).or_insert(0) += 1;
+        // a region with only builders and pigs in it has nobody in charge
+        match strength.first() {
+            Some(&(_, max_count)) => strength.into_iter().filter(|(_, count)| *count == max_count).map(|(player_id, _)| player_id).collect(),
+            None => Vec::new(),
         }
+    }
 
-        if let Some(&max_count) = counts.values().max() {
-            // Collect all PlayerIds with the maximum count
-            counts
-                .into_iter()
-                .filter_map(|(player_id, count)| if count == max_count { Some(player_id) } else { None })
-                .collect()
-        } else {
-            Vec::new()
+    /// The meeple each player has in the region, weighted by [crate::player::MeepleKind::majority_weight],
+    /// strongest first. Players whose meeple carry no weight are left out
+    pub(crate) fn meeple_strength(&self, board: &Board) -> Vec<(PlayerIdentifier, u32)> {
+        let mut counts: HashMap<PlayerIdentifier, u32> = HashMap::new();
+
+        for (_, _, meeple) in self.residents(board) {
+            *counts.entry(meeple.color).or_insert(0) += meeple.kind.majority_weight();
         }
+
+        let mut strength: Vec<_> = counts.into_iter().filter(|(_, count)| *count > 0).collect();
+        strength.sort_by_key(|(player_id, count)| (std::cmp::Reverse(*count), player_id.seat()));
+
+        strength
     }
 }
 
@@ -179,7 +410,9 @@ impl Board {
 mod tests {
     use super::*;
     use crate::tile_definitions::{CLOISTER_IN_FIELD, CLOISTER_WITH_ROAD, CORNER_CITY_WITH_PENNANT, CORNER_ROAD, CORNER_ROAD_WITH_CORNER_CITY, OPPOSING_SIDE_CITIES, SIDE_CITY, STRAIGHT_ROAD, THREE_SIDED_CITY};
-    use crate::test_util::tests::{TestConnectedRegion, TestPlayer};
+    use crate::test_util::{TestConnectedRegion, TestPlayer};
+    use crate::player::{Meeple, MeepleColor, MeepleKind, RegionIndex};
+    use crate::tile::{PlacedTile, Rotation};
 
     #[test]
     fn should_add_score() {
@@ -195,6 +428,59 @@ mod tests {
         assert_eq!(a, Score::from_iter([(&alice, 3), (&bob, 3)]))
     }
 
+    #[test]
+    fn should_list_scored_players_in_seat_order() {
+        let score = Score::from_iter([(&Player::yellow(), 4), (&Player::red(), 0)]);
+
+        assert_eq!(score.iter().collect::<Vec<_>>(), vec![(&MeepleColor::Red, &0), (&MeepleColor::Yellow, &4)]);
+        assert_eq!(score.get_player(&Player::red()), Some(&0));
+        assert_eq!(score.get_player(&Player::green()), None);
+        assert!(SEATS.iter().enumerate().all(|(seat, color)| color.seat() == seat));
+    }
+
+    #[test]
+    fn should_name_each_scored_player() {
+        let alice = Player::green().with_name("Alice");
+        let bob = Player::blue();
+        let players: IndexMap<_, _> = [(alice.meeple_color, alice.clone()), (bob.meeple_color, bob.clone())].into_iter().collect();
+
+        let named = Score::from_iter([(&bob, 3), (&alice, 7)]).to_named(&players);
+
+        assert_eq!(named, [
+            NamedScore { name: "Alice".to_string(), color: MeepleColor::Green, points: 7 },
+            NamedScore { name: "Blue".to_string(), color: MeepleColor::Blue, points: 3 },
+        ]);
+        assert_eq!(serde_json::to_string(&named[0]).unwrap(), r#"{"name":"Alice","color":"Green","points":7}"#);
+    }
+
+    #[test]
+    fn should_sum_scores_by_reference() {
+        let alice = Player::green();
+        let bob = Player::blue();
+
+        let scores = [Score::from_iter([(&alice, 1)]), Score::from_iter([(&alice, 2), (&bob, 3)])];
+
+        let mut total = Score::new();
+        total += &scores[0];
+        total += &scores[1];
+
+        assert_eq!(total, Score::from_iter([(&alice, 3), (&bob, 3)]));
+        assert_eq!(scores.iter().sum::<Score>(), total);
+        assert_eq!(scores.into_iter().sum::<Score>(), total);
+    }
+
+    #[test]
+    fn should_collect_points_by_player() {
+        let alice = Player::green();
+        let bob = Player::blue();
+
+        let score: Score = [(alice.meeple_color, 2), (bob.meeple_color, 5), (alice.meeple_color, 1)].into_iter().collect();
+
+        assert_eq!(score, Score::from_iter([(&alice, 3), (&bob, 5)]));
+        assert_eq!(score.max_player(), Some((bob.meeple_color, 5)));
+        assert_eq!(Score::new().max_player(), None);
+    }
+
     #[test]
     fn should_subtract_scores() {
         let alice = Player::red();
@@ -214,11 +500,11 @@ mod tests {
         let mut bob = Player::green();
 
         [
-            bob.move_no_meeple(&CORNER_ROAD, -1, -1, 0),
-            alice.move_with_meeple(&STRAIGHT_ROAD, -1, 0, 0, 0),
-            bob.move_no_meeple(&CORNER_ROAD, -1, 1, 3),
-            alice.move_no_meeple(&STRAIGHT_ROAD, 0, -1, 1),
-            bob.move_with_meeple(&CORNER_ROAD, 0, 0, 0, 2),
+            bob.move_no_meeple(&CORNER_ROAD, -1, -1, Rotation::R0),
+            alice.move_with_meeple(&STRAIGHT_ROAD, -1, 0, Rotation::R0, 0),
+            bob.move_no_meeple(&CORNER_ROAD, -1, 1, Rotation::R270),
+            alice.move_no_meeple(&STRAIGHT_ROAD, 0, -1, Rotation::R90),
+            bob.move_with_meeple(&CORNER_ROAD, 0, 0, Rotation::R0, 2),
         ].should_have_score(Score::from_iter([
             (&alice, 4),
             (&bob, 1),
@@ -233,13 +519,13 @@ mod tests {
 
         let mut alice = Player::red();
         let mut bob = Player::green();
-        let mut carol = Player::blue();
+        let carol = Player::blue();
 
         [
-            alice.move_with_meeple(&CLOISTER_IN_FIELD, -1, -1, 0, 1),
-            bob.move_with_meeple(&CLOISTER_WITH_ROAD, -1, 0, 2, 2),
-            carol.move_no_meeple(&CORNER_ROAD, -1, 1, 3),
-            alice.move_no_meeple(&STRAIGHT_ROAD, 0, 1, 3),
+            alice.move_with_meeple(&CLOISTER_IN_FIELD, -1, -1, Rotation::R0, 1),
+            bob.move_with_meeple(&CLOISTER_WITH_ROAD, -1, 0, Rotation::R180, 2),
+            carol.move_no_meeple(&CORNER_ROAD, -1, 1, Rotation::R270),
+            alice.move_no_meeple(&STRAIGHT_ROAD, 0, 1, Rotation::R270),
         ].should_have_score(Score::from_iter([
             (&alice, 2),
             (&bob, 4),
@@ -254,10 +540,10 @@ mod tests {
         let mut bob = Player::green();
 
         [
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 0, 1),
-            bob.move_with_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, 2, 1),
-            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, 3),
-            bob.move_no_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1),
+            bob.move_with_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, Rotation::R180, 1),
+            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, Rotation::R270),
+            bob.move_no_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, Rotation::R90),
         ].should_have_score(Score::from_iter([
             (&alice, 2),
             (&bob, 3),
@@ -272,12 +558,12 @@ mod tests {
         let mut bob = Player::green();
 
         [
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 0, 1),
-            bob.move_with_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, 2, 1),
-            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, 3),
-            bob.move_no_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, 1),
-            alice.move_no_meeple(&SIDE_CITY, 0, 2, 2),
-            bob.move_no_meeple(&SIDE_CITY, 1, 1, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 1),
+            bob.move_with_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, Rotation::R180, 1),
+            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, Rotation::R270),
+            bob.move_no_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, Rotation::R90),
+            alice.move_no_meeple(&SIDE_CITY, 0, 2, Rotation::R180),
+            bob.move_no_meeple(&SIDE_CITY, 1, 1, Rotation::R90),
         ].should_have_score(Score::from_iter([
             (&alice, 8),
             (&bob, 3),
@@ -293,12 +579,12 @@ mod tests {
         let mut bob = Player::green();
 
         [
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 0, 0),
-            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, 2),
-            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, 3),
-            bob.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, 1, 1),
-            alice.move_no_meeple(&SIDE_CITY, 0, 2, 2),
-            bob.move_no_meeple(&SIDE_CITY, 1, 1, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0),
+            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, Rotation::R180),
+            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, Rotation::R270),
+            bob.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, Rotation::R90, 1),
+            alice.move_no_meeple(&SIDE_CITY, 0, 2, Rotation::R180),
+            bob.move_no_meeple(&SIDE_CITY, 1, 1, Rotation::R90),
         ].should_have_score(Score::from_iter([
             (&alice, 3),
             (&bob, 0),
@@ -313,15 +599,15 @@ mod tests {
         let mut bob = Player::green();
 
         [
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 0, 0),
-            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, 2),
-            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, 3),
-            bob.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, 1, 1),
-            alice.move_no_meeple(&SIDE_CITY, 0, 2, 2),
-            bob.move_no_meeple(&SIDE_CITY, 1, 1, 1),
-            alice.move_no_meeple(&OPPOSING_SIDE_CITIES, 2, 1, 0),
-            bob.move_no_meeple(&STRAIGHT_ROAD, 3, 1, 0),
-            bob.move_no_meeple(&CORNER_ROAD, 3, 0, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0),
+            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, Rotation::R180),
+            alice.move_no_meeple(&THREE_SIDED_CITY, 0, 1, Rotation::R270),
+            bob.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, Rotation::R90, 1),
+            alice.move_no_meeple(&SIDE_CITY, 0, 2, Rotation::R180),
+            bob.move_no_meeple(&SIDE_CITY, 1, 1, Rotation::R90),
+            alice.move_no_meeple(&OPPOSING_SIDE_CITIES, 2, 1, Rotation::R0),
+            bob.move_no_meeple(&STRAIGHT_ROAD, 3, 1, Rotation::R0),
+            bob.move_no_meeple(&CORNER_ROAD, 3, 0, Rotation::R90),
         ].should_have_score(Score::from_iter([
             (&alice, 3),
             (&bob, 3),
@@ -333,18 +619,18 @@ mod tests {
     fn should_only_give_the_score_for_one_meeple_when_a_player_has_more_than_one_meeple_in_a_region() {
 
         let mut alice = Player::red();
-        let mut bob = Player::green();
+        let bob = Player::green();
 
         [
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 0, 0),
-            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, 2),
-            alice.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, 1, 1),
-            bob.move_no_meeple(&THREE_SIDED_CITY, 0, 1, 3),
-            alice.move_no_meeple(&SIDE_CITY, 0, 2, 2),
-            bob.move_no_meeple(&SIDE_CITY, 1, 1, 1),
-            alice.move_no_meeple(&OPPOSING_SIDE_CITIES, 2, 1, 0),
-            bob.move_no_meeple(&STRAIGHT_ROAD, 3, 1, 0),
-            bob.move_no_meeple(&CORNER_ROAD, 3, 0, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0),
+            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, Rotation::R180),
+            alice.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, Rotation::R90, 1),
+            bob.move_no_meeple(&THREE_SIDED_CITY, 0, 1, Rotation::R270),
+            alice.move_no_meeple(&SIDE_CITY, 0, 2, Rotation::R180),
+            bob.move_no_meeple(&SIDE_CITY, 1, 1, Rotation::R90),
+            alice.move_no_meeple(&OPPOSING_SIDE_CITIES, 2, 1, Rotation::R0),
+            bob.move_no_meeple(&STRAIGHT_ROAD, 3, 1, Rotation::R0),
+            bob.move_no_meeple(&CORNER_ROAD, 3, 0, Rotation::R90),
         ].should_have_score(Score::from_iter([
             (&alice, 3),
         ]))
@@ -359,20 +645,120 @@ mod tests {
         let mut bob = Player::green();
 
         [
-            alice.move_with_meeple(&SIDE_CITY, 0, 0, 0, 0),
-            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, 2),
-            alice.move_with_meeple(&THREE_SIDED_CITY, 0, 1, 3, 0),
-            bob.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, 2, 1),
-            alice.move_no_meeple(&SIDE_CITY, 0, 2, 2),
-            bob.move_no_meeple(&CORNER_ROAD, 2, 1, 3),
-            alice.move_no_meeple(&STRAIGHT_ROAD, -1, 1, 0),
-            bob.move_no_meeple(&SIDE_CITY, 1, 1, 1),
-            alice.move_no_meeple(&CORNER_ROAD, -1, 0, 1),
+            alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0),
+            bob.move_no_meeple(&CORNER_CITY_WITH_PENNANT, 1, 0, Rotation::R180),
+            alice.move_with_meeple(&THREE_SIDED_CITY, 0, 1, Rotation::R270, 0),
+            bob.move_with_meeple(&CORNER_ROAD_WITH_CORNER_CITY, 2, 0, Rotation::R180, 1),
+            alice.move_no_meeple(&SIDE_CITY, 0, 2, Rotation::R180),
+            bob.move_no_meeple(&CORNER_ROAD, 2, 1, Rotation::R270),
+            alice.move_no_meeple(&STRAIGHT_ROAD, -1, 1, Rotation::R0),
+            bob.move_no_meeple(&SIDE_CITY, 1, 1, Rotation::R90),
+            alice.move_no_meeple(&CORNER_ROAD, -1, 0, Rotation::R90),
         ].should_have_score(Score::from_iter([
             (&alice, 3),
         ]))
 
     }
 
+    #[cfg(feature = "render")]
+    #[test]
+    fn should_show_meeple_in_hand_and_on_the_board() {
+        let mut alice = Player::red().with_name("Alice");
+        let bob = Player::green().with_name("Bob");
+
+        let board = Board::new_with_tiles([
+            alice.move_with_meeple(&STRAIGHT_ROAD, 0, 0, Rotation::R0, 0),
+            alice.move_with_meeple(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0, 1),
+        ])
+        .unwrap();
+
+        let players = IndexMap::from([(alice.meeple_color, alice.clone()), (bob.meeple_color, bob.clone())]);
+        let rendered = Score::from_iter([(&alice, 4)]).render_with_meeple(&players, &board, &RenderStyle::Ascii);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert!(lines[0].contains("Alice"));
+        assert!(lines[0].contains(" = 4 "));
+        assert_eq!(lines[0].matches('ꆜ').count(), 5);
+        assert!(lines[0].ends_with(" (1 on cloister, 1 on road deployed)"));
+        assert_eq!(lines[1].matches('ꆜ').count(), 7);
+        assert!(!lines[1].contains("deployed"));
+    }
+
+    #[test]
+    fn should_weigh_big_meeple_as_two() {
+        let mut alice = Player::red();
+        let mut bob = Player::blue();
+
+        let mut big_farmer = alice.move_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, 0);
+        big_farmer.meeple.as_mut().unwrap().1.kind = MeepleKind::Big;
+
+        // the cloisters join Alice's field to Bob's
+        let board = Board::new_with_tiles([
+            big_farmer,
+            bob.move_with_meeple(&SIDE_CITY, 0, 1, Rotation::R180, 0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 0, Rotation::R0),
+            PlacedTile::new(&CLOISTER_IN_FIELD, 1, 1, Rotation::R0),
+        ])
+        .unwrap();
+
+        let field = board.get_connected_regions().into_iter().find(|region| region.residents(&board).len() == 2).unwrap();
+
+        assert_eq!(field.majority_meeple_player_ids(&board), vec![alice.meeple_color]);
+    }
+
+    /// Cities score the same closed or not
+    struct FlatCities;
+
+    impl ScoringRules for FlatCities {
+        fn name(&self) -> &'static str {
+            "flat cities"
+        }
+
+        fn score_city(&self, region: &ConnectedRegion, _board: &Board) -> u32 {
+            region.tile_region_count() as u32
+        }
+    }
+
+    #[test]
+    fn should_score_features_under_the_boards_scoring_rules() {
+        let score_closed_city = |board: Board| {
+            let mut board = board;
+            board.place_tile(PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Red)))).unwrap();
+            board.place_tile(PlacedTile::new(&SIDE_CITY, 0, 1, Rotation::R180)).unwrap().score_delta
+        };
+
+        assert_eq!(score_closed_city(Board::new()), [(MeepleColor::Red, 4)].into_iter().collect::<Score>());
+        assert_eq!(score_closed_city(Board::new().with_scoring(Scoring::new(FlatCities))), [(MeepleColor::Red, 2)].into_iter().collect::<Score>());
+    }
+
+    #[test]
+    fn should_tell_rule_sets_apart_by_their_scoring() {
+        use crate::rules::RuleSet;
 
+        assert_eq!(RuleSet::standard(), RuleSet::standard().with_scoring(StandardScoring));
+        assert_ne!(RuleSet::standard(), RuleSet::standard().with_scoring(FlatCities));
+    }
+
+    #[test]
+    fn should_only_double_two_tile_cities_when_the_rules_say_so() {
+        use crate::tile_definitions::STRAIGHT_CITY_WITH_SIDE_FIELDS;
+
+        let close_city = |small_city_scoring: SmallCityScoring, middle: &[&'static crate::tile::TileDefinition]| {
+            let mut board = Board::new().with_small_city_scoring(small_city_scoring);
+            board.place_tile(PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Red)))).unwrap();
+
+            for (y, tile) in middle.iter().enumerate() {
+                board.place_tile(PlacedTile::new(tile, 0, y as i8 + 1, Rotation::R0)).unwrap();
+            }
+
+            board.place_tile(PlacedTile::new(&SIDE_CITY, 0, middle.len() as i8 + 1, Rotation::R180)).unwrap().score_delta
+        };
+
+        let red = |points| [(MeepleColor::Red, points)].into_iter().collect::<Score>();
+
+        assert_eq!(close_city(SmallCityScoring::Doubled, &[]), red(4));
+        assert_eq!(close_city(SmallCityScoring::Single, &[]), red(2));
+        assert_eq!(close_city(SmallCityScoring::Doubled, &[&STRAIGHT_CITY_WITH_SIDE_FIELDS]), red(6));
+        assert_eq!(close_city(SmallCityScoring::Single, &[&STRAIGHT_CITY_WITH_SIDE_FIELDS]), red(6));
+    }
 }
@@ -0,0 +1,313 @@
+//! Cross-checks the score each placement hands out against the features worked out again from the
+//! tiles alone, for catching the incremental region bookkeeping scoring a feature wrongly. Every
+//! placement pays for a flood fill, so it is only built with the `score-check` feature
+
+use crate::board::{Board, CompletedFeature};
+use crate::connected_regions::{ConnectedRegion, PlacedTileEdge, PlacedTileRegion};
+use crate::player::{Meeple, PlayerIdentifier, RegionIndex};
+use crate::score::Score;
+use crate::tile::{BoardCoordinate, CardinalDirection, Region, RegionType, TileDefinition};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// The meeple on the board before a placement, with the one the placement adds, as features the
+/// placement completes are scored before their meeple are taken off
+pub(crate) type MeepleBefore = HashMap<(BoardCoordinate, RegionIndex), Meeple>;
+
+/// A feature the placement completed, found by following the tiles' regions edge to edge
+struct RecomputedFeature {
+    region_type: RegionType,
+    tiles: Vec<BoardCoordinate>,
+    points: u32,
+    scored_by: Vec<PlayerIdentifier>,
+}
+
+impl Board {
+    pub(crate) fn meeple_before(&self) -> MeepleBefore {
+        self.placed_tiles
+            .values()
+            .filter_map(|tile| tile.meeple.as_ref().map(|(region_index, meeple)| ((tile.placement.coordinate, *region_index), meeple.clone())))
+            .collect()
+    }
+
+    /// Panics with both sides of the difference unless the score the tile just placed at
+    /// `coordinate` handed out is what its completed features are worth when found again from
+    /// scratch
+    pub(crate) fn assert_score_delta(&self, coordinate: BoardCoordinate, meeple: &MeepleBefore, score_delta: &Score, completed_features: &[CompletedFeature]) {
+        let recomputed = self.recompute_completed_features(coordinate, meeple);
+
+        let mut expected = Score::new();
+
+        for feature in &recomputed {
+            for player in &feature.scored_by {
+                expected.add_score(*player, feature.points as i32);
+            }
+        }
+
+        if &expected == score_delta {
+            return;
+        }
+
+        let tile = &self.placed_tiles[&coordinate];
+        let mut diff = format!("{} placed at {} scored {:?}, but its completed features are worth {:?}\n", tile.tile.name, tile.placement, score_delta, expected);
+
+        diff += "scored as it was placed:\n";
+        for feature in completed_features {
+            let _ = writeln!(diff, "  {:?} over {:?} for {} to {:?}", feature.region_type, feature.tiles, feature.points, feature.scored_by);
+        }
+
+        diff += "found again from the tiles:\n";
+        for feature in &recomputed {
+            let _ = writeln!(diff, "  {:?} over {:?} for {} to {:?}", feature.region_type, feature.tiles, feature.points, feature.scored_by);
+        }
+
+        panic!("{}board: {}", diff, self.to_share_code());
+    }
+
+    /// The features the tile at `coordinate` closed, and the cloisters it completed, without
+    /// reference to the board's connected regions
+    fn recompute_completed_features(&self, coordinate: BoardCoordinate, meeple: &MeepleBefore) -> Vec<RecomputedFeature> {
+        let tile = &self.placed_tiles[&coordinate];
+        let mut seen = HashSet::new();
+        let mut features = vec![];
+
+        for (index, region) in tile.tile.regions.iter().enumerate() {
+            // a cloister has no edges, so is only ever completed by the tiles around it
+            if region.edges().is_empty() || seen.contains(&(coordinate, RegionIndex::new(index))) {
+                continue;
+            }
+
+            let feature = self.flood_fill(coordinate, RegionIndex::new(index), &mut seen);
+
+            if feature.is_closed() {
+                features.push(self.recomputed(feature, meeple));
+            }
+        }
+
+        let cloisters = self.topology().surrounding_coordinates(&coordinate).into_iter().chain([coordinate]);
+
+        for cloister in cloisters {
+            let Some(tile) = self.placed_tiles.get(&cloister) else {
+                continue;
+            };
+
+            let Some(index) = tile.tile.regions.iter().position(|region| matches!(region, Region::Cloister { .. })) else {
+                continue;
+            };
+
+            if self.list_surrounding_tiles(&cloister).len() < 8 || !meeple.contains_key(&(cloister, RegionIndex::new(index))) {
+                continue;
+            }
+
+            let region = ConnectedRegion {
+                id: 0,
+                region_type: RegionType::Cloister,
+                tile_regions: vec![PlacedTileRegion::new(tile, RegionIndex::new(index))],
+                adjacent_regions: HashSet::new(),
+                connected_edges: HashMap::new(),
+                open_edge_count: 0,
+                pennant_count: 0,
+            };
+
+            features.push(self.recomputed(region, meeple));
+        }
+
+        features
+    }
+
+    /// The feature the tile region is part of, open or closed. Every tile region reached is added
+    /// to `seen`
+    fn flood_fill(&self, coordinate: BoardCoordinate, region_index: RegionIndex, seen: &mut HashSet<(BoardCoordinate, RegionIndex)>) -> ConnectedRegion {
+        let mut tile_regions = vec![];
+        let mut connected_edges = HashMap::new();
+        let mut open_edge_count = 0;
+        let mut queue = vec![(coordinate, region_index)];
+
+        seen.insert((coordinate, region_index));
+
+        while let Some((coordinate, region_index)) = queue.pop() {
+            let tile = &self.placed_tiles[&coordinate];
+            tile_regions.push(PlacedTileRegion::new(tile, region_index));
+
+            for edge in tile.tile.regions[*region_index].edges() {
+                let edge = PlacedTileEdge { coordinate, global_direction: edge.rotate(tile.placement.rotation) };
+                let opposing = edge.opposing_tile_edge(self.topology());
+
+                let Some(neighbour) = self.placed_tiles.get(&opposing.coordinate) else {
+                    open_edge_count += 1;
+                    continue;
+                };
+
+                let index = region_at(neighbour.tile, opposing.global_direction.rotate(neighbour.placement.rotation.inverse()));
+
+                if seen.insert((opposing.coordinate, index)) {
+                    queue.push((opposing.coordinate, index));
+                }
+
+                connected_edges.insert(edge, Some(opposing));
+            }
+        }
+
+        ConnectedRegion {
+            id: 0,
+            region_type: tile_regions[0].region.region_type(),
+            pennant_count: tile_regions.iter().filter(|region| matches!(region.region, Region::City { pennant: true, .. })).count(),
+            tile_regions,
+            adjacent_regions: HashSet::new(),
+            connected_edges,
+            open_edge_count,
+        }
+    }
+
+    /// What the feature is worth under the board's scoring, and who scores it going by the
+    /// meeple in it before they were taken off
+    fn recomputed(&self, region: ConnectedRegion, meeple: &MeepleBefore) -> RecomputedFeature {
+        let mut strength: HashMap<PlayerIdentifier, u32> = HashMap::new();
+
+        for tile_region in &region.tile_regions {
+            if let Some(meeple) = meeple.get(&(tile_region.tile_position, tile_region.region_index)) {
+                *strength.entry(meeple.color).or_default() += meeple.kind.majority_weight();
+            }
+        }
+
+        let strongest = strength.values().copied().max().unwrap_or(0);
+        let mut scored_by: Vec<_> = strength.into_iter().filter(|(_, count)| *count > 0 && *count == strongest).map(|(player, _)| player).collect();
+        scored_by.sort_by_key(|player| player.seat());
+
+        let mut tiles: Vec<_> = match region.region_type {
+            RegionType::Cloister => region.tile_coordinates().flat_map(|cloister| self.topology().surrounding_coordinates(&cloister).into_iter().chain([cloister])).collect(),
+            _ => region.tile_coordinates().collect(),
+        };
+        tiles.sort_by_key(|coordinate| (coordinate.y, coordinate.x));
+        tiles.dedup();
+
+        let region_type = region.region_type.clone();
+
+        // the board's scoring finds the cities a field borders through the regions beside it, so
+        // the field is scored on a board holding only those found again from the tiles
+        let points = match region_type {
+            RegionType::Field => {
+                let mut regions = self.bordering_cities(&region);
+                let field = regions.len();
+
+                regions.push(ConnectedRegion { adjacent_regions: (0..field).collect(), ..region });
+
+                let board = self.with_only_regions(regions);
+                board.scoring().score(board.connected_region(field).expect("the field was just added"), &board)
+            }
+            _ => region.score(self),
+        };
+
+        RecomputedFeature { region_type, tiles, points, scored_by }
+    }
+
+    /// The cities the field borders, open or closed, going by the regions beside it on each tile
+    fn bordering_cities(&self, field: &ConnectedRegion) -> Vec<ConnectedRegion> {
+        let mut seen = HashSet::new();
+        let mut cities = vec![];
+
+        for tile_region in &field.tile_regions {
+            let tile = &self.placed_tiles[&tile_region.tile_position];
+
+            for edge in tile_region.region.edges() {
+                let (left, right) = edge.adjacent();
+
+                for index in [region_at(tile.tile, left), region_at(tile.tile, right)] {
+                    if matches!(tile.tile.regions[*index], Region::City { .. }) && !seen.contains(&(tile_region.tile_position, index)) {
+                        cities.push(self.flood_fill(tile_region.tile_position, index, &mut seen));
+                    }
+                }
+            }
+        }
+
+        cities
+    }
+}
+
+/// The region of the unturned tile that runs to the edge `direction`
+fn region_at(tile: &TileDefinition, direction: CardinalDirection) -> RegionIndex {
+    RegionIndex::new(tile.regions.iter().position(|region| region.edges().contains(&direction)).expect("every edge is in a region"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board::Board;
+    use crate::player::{Meeple, MeepleColor, RegionIndex};
+    use crate::rules::FirstEditionScoring;
+    use crate::score::{Score, Scoring};
+    use crate::tile::{BoardCoordinate, PlacedTile, Rotation};
+    use crate::tile_definitions::{CLOISTER_IN_FIELD, CORNER_ROAD, SIDE_CITY, STRAIGHT_ROAD, STRAIGHT_ROAD_WITH_SIDE_CITY};
+
+    #[test]
+    fn should_agree_with_the_score_of_a_closed_city() {
+        let mut board = Board::new();
+        board.place_tile(PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Red)))).unwrap();
+
+        let meeple = board.meeple_before();
+        let placed = board.place_tile(PlacedTile::new(&SIDE_CITY, 0, 1, Rotation::R180)).unwrap();
+
+        assert_eq!(placed.score_delta, [(MeepleColor::Red, 4)].into_iter().collect::<Score>());
+        board.assert_score_delta(BoardCoordinate::new(0, 1), &meeple, &placed.score_delta, &placed.completed_features);
+    }
+
+    #[test]
+    fn should_find_a_completed_cloister_again() {
+        let mut board = Board::new_with_tiles([(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1)].map(|(x, y)| PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0))).unwrap();
+        board.place_tile(PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Green)))).unwrap();
+
+        let meeple = board.meeple_before();
+        let placed = board.place_tile(PlacedTile::new(&CLOISTER_IN_FIELD, 1, 1, Rotation::R0)).unwrap();
+
+        assert_eq!(placed.score_delta, [(MeepleColor::Green, 9)].into_iter().collect::<Score>());
+        board.assert_score_delta(BoardCoordinate::new(1, 1), &meeple, &placed.score_delta, &placed.completed_features);
+    }
+
+    #[test]
+    fn should_agree_with_a_cloister_completed_as_it_is_laid() {
+        let mut board = Board::new_with_tiles([(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)].map(|(x, y)| PlacedTile::new(&CLOISTER_IN_FIELD, x, y, Rotation::R0))).unwrap();
+
+        let mut meeple = board.meeple_before();
+        meeple.insert((BoardCoordinate::new(0, 0), RegionIndex::new(1)), Meeple::new(MeepleColor::Green));
+        let placed = board.place_tile(PlacedTile::new_with_meeple(&CLOISTER_IN_FIELD, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Green)))).unwrap();
+
+        assert_eq!(placed.score_delta, [(MeepleColor::Green, 9)].into_iter().collect::<Score>());
+        board.assert_score_delta(BoardCoordinate::new(0, 0), &meeple, &placed.score_delta, &placed.completed_features);
+    }
+
+    #[test]
+    fn should_score_an_enclosed_field_under_the_boards_own_scoring() {
+        let mut board = Board::new().with_scoring(Scoring::new(FirstEditionScoring));
+
+        for tile in [
+            PlacedTile::new(&CORNER_ROAD, -1, -1, Rotation::R0),
+            PlacedTile::new_with_meeple(&STRAIGHT_ROAD, -1, 0, Rotation::R0, (RegionIndex::new(1) /* the inner field */, Meeple::new(MeepleColor::Red))),
+            PlacedTile::new(&CORNER_ROAD, -1, 1, Rotation::R270),
+            PlacedTile::new(&STRAIGHT_ROAD_WITH_SIDE_CITY, 0, -1, Rotation::R180),
+            PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R180),
+            PlacedTile::new(&CORNER_ROAD, 1, -1, Rotation::R90),
+            PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, 1, 1, Rotation::R180),
+        ] {
+            board.place_tile(tile).unwrap();
+        }
+
+        let meeple = board.meeple_before();
+        let placed = board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R90)).unwrap();
+
+        // the first edition's farmers supply the closed city with four points
+        assert_eq!(placed.score_delta, [(MeepleColor::Red, 4)].into_iter().collect::<Score>());
+        board.assert_score_delta(BoardCoordinate::new(0, 1), &meeple, &placed.score_delta, &placed.completed_features);
+    }
+
+    #[test]
+    #[should_panic(expected = "but its completed features are worth")]
+    fn should_halt_on_a_score_that_doesnt_match_the_board() {
+        let mut board = Board::new();
+        board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0)).unwrap();
+
+        let meeple = board.meeple_before();
+        let placed = board.place_tile(PlacedTile::new(&STRAIGHT_ROAD, 0, 1, Rotation::R0)).unwrap();
+
+        board.assert_score_delta(BoardCoordinate::new(0, 1), &meeple, &[(MeepleColor::Blue, 2)].into_iter().collect(), &placed.completed_features);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::player::PlayerIdentifier;
+use crate::replay::{Replay, ReplayError};
+use crate::tile::RegionType;
+
+/// Where a player's points came from over a game
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    pub player: PlayerIdentifier,
+    pub name: Option<String>,
+    /// Points for cities, roads and cloisters completed during the game
+    pub cities: i32,
+    pub roads: i32,
+    pub cloisters: i32,
+    /// Points for the features still open when the game ended
+    pub end_of_game: i32,
+}
+
+impl ScoreBreakdown {
+    pub fn total(&self) -> i32 {
+        self.cities + self.roads + self.cloisters + self.end_of_game
+    }
+}
+
+/// Each player's score broken down by how it was earned, in seat order
+pub fn score_sheet(replay: &Replay) -> Result<Vec<ScoreBreakdown>, ReplayError> {
+    let mut sheet: Vec<ScoreBreakdown> = replay
+        .players
+        .iter()
+        .map(|p| ScoreBreakdown { player: p.color, name: p.name.clone(), cities: 0, roads: 0, cloisters: 0, end_of_game: 0 })
+        .collect();
+
+    let mut state = replay.initial_state()?;
+
+    for turn in &replay.turns {
+        let Some(success) = state.apply(turn)? else {
+            continue;
+        };
+
+        for feature in &success.completed_features {
+            for breakdown in sheet.iter_mut().filter(|b| feature.scored_by.contains(&b.player)) {
+                let points = feature.points as i32;
+
+                match feature.region_type {
+                    RegionType::City => breakdown.cities += points,
+                    RegionType::Road => breakdown.roads += points,
+                    RegionType::Cloister => breakdown.cloisters += points,
+                    RegionType::Field | RegionType::Water => {}
+                }
+            }
+        }
+    }
+
+    let end_of_game = state.board.calculate_board_score();
+
+    for breakdown in &mut sheet {
+        breakdown.end_of_game = end_of_game.iter().find(|(id, _)| **id == breakdown.player).map(|(_, s)| *s).unwrap_or(0);
+    }
+
+    Ok(sheet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::MeepleColor;
+    use crate::replay::{ReplayPlacement, ReplayPlayer, ReplayRules, ReplayTurn};
+    use crate::tile::Rotation;
+
+    fn side_city_turn(player: PlayerIdentifier, x: i8, y: i8, rotation: Rotation, meeple: Option<usize>) -> ReplayTurn {
+        ReplayTurn {
+            player,
+            tile: "Side city".to_string(),
+            placement: Some(ReplayPlacement { x, y, rotation, meeple, recalled: None }),
+        }
+    }
+
+    #[test]
+    fn should_split_points_scored_in_play_from_those_scored_at_the_end() {
+        let replay = Replay {
+            seed: String::new(),
+            deck: vec![],
+            players: vec![
+                ReplayPlayer { color: MeepleColor::Red, name: Some("Alice".to_string()), bot: None },
+                ReplayPlayer { color: MeepleColor::Green, name: Some("Bob".to_string()), bot: None },
+            ],
+            turns: vec![
+                // Bob closes Alice's city, then claims a city that is never finished
+                side_city_turn(MeepleColor::Red, 0, 0, Rotation::R0, Some(1)),
+                side_city_turn(MeepleColor::Green, 0, 1, Rotation::R180, None),
+                side_city_turn(MeepleColor::Green, 1, 0, Rotation::R0, Some(1)),
+            ],
+            final_score: vec![],
+            handovers: vec![],
+            rules: ReplayRules::default(),
+        };
+
+        let sheet = score_sheet(&replay).unwrap();
+
+        assert_eq!(sheet[0], ScoreBreakdown { player: MeepleColor::Red, name: Some("Alice".to_string()), cities: 4, roads: 0, cloisters: 0, end_of_game: 0 });
+        assert_eq!(sheet[1], ScoreBreakdown { player: MeepleColor::Green, name: Some("Bob".to_string()), cities: 0, roads: 0, cloisters: 0, end_of_game: 1 });
+        assert_eq!(sheet[0].total(), 4);
+    }
+}
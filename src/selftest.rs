@@ -0,0 +1,197 @@
+use crate::board::Board;
+use crate::bot_strategy::Bot;
+use crate::move_hints::MoveHint;
+use crate::player::{Meeple, MeepleColor, Player, RegionIndex};
+use crate::tile::{PlacedTile, Rotation, TileDefinition};
+use crate::tile_definitions::{CLOISTER_IN_FIELD, CORNER_ROAD, RIVER_TERMINATOR, SIDE_CITY, STRAIGHT_RIVER, STRAIGHT_ROAD, STRAIGHT_ROAD_WITH_SIDE_CITY};
+use std::any::Any;
+use std::fmt::{Display, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long a bot gets to choose its move in each scenario, unless given longer
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(5);
+
+/// A position a bot is asked to move in: the board, the tile drawn and the player to play it
+pub struct Scenario {
+    pub name: &'static str,
+    board: Board,
+    tile: &'static TileDefinition,
+    player: Player,
+}
+
+/// What went wrong with a bot's move in a [Scenario]
+#[derive(Debug)]
+pub enum SelfTestFailure {
+    /// The bot panicked choosing its move, with the panic's message
+    Panicked(String),
+    /// The bot left the tile unplaced, though it had legal moves
+    NoMove,
+    /// The bot chose a move that wasn't one of those it was offered
+    IllegalMove(MoveHint),
+    /// The bot took longer than its budget to choose
+    TooSlow(Duration),
+    /// The bot was still choosing long after its budget, so was given up on
+    TimedOut(Duration),
+}
+
+/// The first scenario a bot failed, and how
+#[derive(Debug)]
+pub struct SelfTestError {
+    pub scenario: &'static str,
+    pub failure: SelfTestFailure,
+}
+
+impl Display for SelfTestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.scenario)?;
+
+        match &self.failure {
+            SelfTestFailure::Panicked(message) => write!(f, "panicked: {}", message),
+            SelfTestFailure::NoMove => write!(f, "chose no move"),
+            SelfTestFailure::IllegalMove(hint) => write!(f, "chose a move it wasn't offered, {}", hint),
+            SelfTestFailure::TooSlow(took) => write!(f, "took {:.2?} to choose", took),
+            SelfTestFailure::TimedOut(waited) => write!(f, "still hadn't chosen after {:.2?}", waited),
+        }
+    }
+}
+
+/// The positions every bot is checked in. The same each time, so a failure can be reproduced
+pub fn scenarios() -> Vec<Scenario> {
+    let board = |tiles: Vec<PlacedTile>| Board::new_with_tiles(tiles).expect("scenario boards should be valid");
+
+    vec![
+        Scenario { name: "opening", board: Board::new(), tile: &STRAIGHT_ROAD_WITH_SIDE_CITY, player: Player::red() },
+        Scenario {
+            name: "contested city",
+            board: board(vec![
+                PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Green))),
+                PlacedTile::new(&STRAIGHT_ROAD, 1, 0, Rotation::R0),
+                PlacedTile::new(&CORNER_ROAD, 1, -1, Rotation::R0),
+            ]),
+            tile: &SIDE_CITY,
+            player: Player::red(),
+        },
+        Scenario {
+            name: "cloister",
+            board: board(vec![
+                PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+                PlacedTile::new_with_meeple(&STRAIGHT_ROAD, 0, 1, Rotation::R0, (RegionIndex::new(0), Meeple::new(MeepleColor::Green))),
+            ]),
+            tile: &CLOISTER_IN_FIELD,
+            player: Player::red(),
+        },
+        Scenario { name: "river", board: board(vec![PlacedTile::new(&RIVER_TERMINATOR, 0, 0, Rotation::R0)]), tile: &STRAIGHT_RIVER, player: Player::red() },
+        Scenario {
+            name: "no meeple in hand",
+            board: board(vec![PlacedTile::new(&SIDE_CITY, 0, 0, Rotation::R0)]),
+            tile: &STRAIGHT_ROAD_WITH_SIDE_CITY,
+            player: Player::red().with_meeple_count(0),
+        },
+    ]
+}
+
+/// Asks the bot for its move in each of [scenarios], checking it picks one of the legal moves it
+/// is offered within `budget`, and doesn't panic. Stops at the first scenario it fails. The bot
+/// plays on a thread of its own, so one that never chooses is given up on rather than hanging the
+/// test; it is left to finish, or not, on its own
+pub fn selftest<B: Bot + Send + 'static>(mut bot: B, budget: Duration) -> Result<(), SelfTestError> {
+    let (moves, chosen_moves) = mpsc::channel();
+
+    thread::spawn(move || {
+        for scenario in scenarios() {
+            let started = Instant::now();
+
+            let chosen = panic::catch_unwind(AssertUnwindSafe(|| {
+                let hints = scenario.board.get_move_hints(scenario.tile, !scenario.player.meeple.is_empty());
+                let chosen = bot.select_hint(&scenario.board, &scenario.player, &hints);
+
+                (hints, chosen)
+            }));
+
+            // nobody is waiting for the rest once a scenario has failed
+            if moves.send((chosen.map_err(panic_message), started.elapsed())).is_err() {
+                return;
+            }
+        }
+    });
+
+    for scenario in scenarios() {
+        let fail = |failure| SelfTestError { scenario: scenario.name, failure };
+
+        // a little longer than the budget, so a bot just over it is told how long it took
+        let (chosen, took) = match chosen_moves.recv_timeout(budget * 2) {
+            Ok(chosen) => chosen,
+            Err(RecvTimeoutError::Timeout) => return Err(fail(SelfTestFailure::TimedOut(budget * 2))),
+            Err(RecvTimeoutError::Disconnected) => unreachable!("the bot's thread reports every scenario, panics included"),
+        };
+
+        let (hints, chosen) = chosen.map_err(|message| fail(SelfTestFailure::Panicked(message)))?;
+        let chosen = chosen.ok_or_else(|| fail(SelfTestFailure::NoMove))?;
+
+        if !hints.iter().any(|hint| hint.tile_placement == chosen.tile_placement && hint.meeple_placement == chosen.meeple_placement) {
+            return Err(fail(SelfTestFailure::IllegalMove(chosen)));
+        }
+
+        if took > budget {
+            return Err(fail(SelfTestFailure::TooSlow(took)));
+        }
+    }
+
+    Ok(())
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or_else(|| "unknown panic".to_string(), |message| message.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot, MyopicBot, ScoreRankingBot};
+
+    #[test]
+    fn should_offer_a_move_in_every_scenario() {
+        for scenario in scenarios() {
+            assert!(!scenario.board.get_move_hints(scenario.tile, true).is_empty(), "{}", scenario.name);
+        }
+    }
+
+    #[test]
+    fn should_pass_bots_that_play_legal_moves() {
+        assert!(selftest(BotStrategy::Lazy(LazyBot), DEFAULT_BUDGET).is_ok());
+        assert!(selftest(BotStrategy::Myopic(MyopicBot), DEFAULT_BUDGET).is_ok());
+    }
+
+    #[test]
+    fn should_catch_a_bot_that_panics() {
+        let error = selftest(ScoreRankingBot, DEFAULT_BUDGET).unwrap_err();
+
+        assert_eq!(error.scenario, "opening");
+        assert!(matches!(error.failure, SelfTestFailure::Panicked(ref message) if message == "not yet implemented"));
+    }
+
+    /// Never chooses a move
+    struct Stalls;
+
+    impl Bot for Stalls {
+        fn select_hint(&mut self, _board: &Board, _player: &Player, _hints: &[MoveHint]) -> Option<MoveHint> {
+            loop {
+                thread::park();
+            }
+        }
+    }
+
+    #[test]
+    fn should_give_up_on_a_bot_that_never_chooses() {
+        let error = selftest(Stalls, Duration::from_millis(50)).unwrap_err();
+
+        assert_eq!(error.scenario, "opening");
+        assert!(matches!(error.failure, SelfTestFailure::TimedOut(waited) if waited == Duration::from_millis(100)));
+    }
+}
@@ -0,0 +1,1137 @@
+use crate::board::Board;
+use crate::bot_strategy::BotStrategy;
+use crate::controller::{GameView, PlayerAction, PlayerController};
+use crate::game_logic::{Game, GamePhase};
+use crate::move_hints::MoveHint;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::{Player, PlayerIdentifier, RegionIndex};
+use crate::replay::{Replay, ReplayPlacement, ReplayPlayer, ReplayRecall, ReplayRules, ReplayScore, ReplayState, ReplayTurn};
+use crate::rng::GameRng;
+use crate::rules::RuleSet;
+use crate::simulate::Seed;
+use crate::svg::TileArtwork;
+use crate::tile::{BoardCoordinate, Expansion, TileDefinition};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use base64::{engine::general_purpose, Engine as _};
+use rand::rngs::OsRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How long a move submission waits for the game to apply it before responding
+const MOVE_APPLIED_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a client can go without contacting the server while it is their turn before their
+/// seat passes its turns, unless configured with [GameServer::with_reconnect_grace]
+pub const DEFAULT_RECONNECT_GRACE: Duration = Duration::from_secs(60);
+
+/// How long a finished game is kept for clients to fetch how it ended, unless configured with
+/// [GameServer::with_finished_game_retention]
+pub const DEFAULT_FINISHED_GAME_RETENTION: Duration = Duration::from_secs(600);
+
+/// Version of the HTTP protocol, bumped whenever a change would break existing clients
+pub const PROTOCOL_VERSION: u32 = 1;
+/// Oldest client protocol version the server still understands
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+/// Header clients send their protocol version in, so requests from incompatible clients are
+/// refused up front rather than misread
+pub const PROTOCOL_HEADER: &str = "carcassonne-protocol";
+
+/// Optional parts of the protocol a client can rely on once agreed in the handshake
+pub const PROTOCOL_FEATURES: &[&str] = &["board_svg", "legal_moves", "pass", "event_replay", "board_hash"];
+/// Rule options that can be set when creating a game
+pub const RULE_OPTIONS: &[&str] = &["meeple_per_player", "meeple_recall"];
+
+/// What a client sends to open a session: the protocol version it speaks and the expansions and
+/// features it would like to use
+#[derive(Debug, Clone, Deserialize)]
+pub struct Hello {
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub expansions: Vec<String>,
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// The server's reply to a [Hello]: the protocol version to speak (the older of the two sides),
+/// and the requested expansions and features the server supports. Anything the client asked for
+/// that is missing here is unavailable, and the client should do without it or give up
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Welcome {
+    pub protocol_version: u32,
+    pub expansions: Vec<&'static str>,
+    pub rules: Vec<&'static str>,
+    pub features: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateGame {
+    /// Players in turn order
+    pub players: Vec<SeatRequest>,
+    /// Url safe base64 seed; a random seed is used if none is given
+    #[serde(default)]
+    pub seed: Option<String>,
+    #[serde(default)]
+    pub river: bool,
+    /// Overrides the number of meeple each player starts with
+    #[serde(default)]
+    pub meeple_per_player: Option<usize>,
+    /// Lets players take back a meeple instead of placing one, see [RuleSet::with_meeple_recall]
+    #[serde(default)]
+    pub meeple_recall: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SeatRequest {
+    pub color: PlayerIdentifier,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Name of a bot to play the seat (see [crate::bot_strategy::BOT_NAMES]). Seats without a
+    /// bot are played by clients, using the token issued for the seat
+    #[serde(default)]
+    pub bot: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedGame {
+    pub id: Uuid,
+    /// Token for each client played seat, to be sent as a bearer token with its moves
+    pub tokens: HashMap<PlayerIdentifier, String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSummary {
+    pub id: Uuid,
+    pub players: Vec<ReplayPlayer>,
+    /// Number of turns played
+    pub turn: usize,
+    /// The player whose move is awaited, and the tile they drew
+    pub to_play: Option<PlayerIdentifier>,
+    pub drawn_tile: Option<String>,
+    /// Whether the awaited move lays the river or is in the main game, by [GamePhase::name]
+    pub phase: Option<&'static str>,
+    /// Score so far, or the final score once finished
+    pub score: Vec<ReplayScore>,
+    pub finished: bool,
+    pub board: Vec<BoardTile>,
+    /// The board's [Board::zobrist_hash]
+    pub board_hash: u64,
+    /// Why the game was halted, if a client's board was found to differ from the server's
+    pub desync: Option<DesyncReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BoardTile {
+    pub tile: String,
+    #[serde(flatten)]
+    pub placement: ReplayPlacement,
+    /// Owner of the meeple on the tile, if any
+    pub meeple_player: Option<PlayerIdentifier>,
+}
+
+/// The turns played in a game from a given turn onwards, for a client (re)joining part way through
+/// to rebuild the board by applying them in order from the start
+#[derive(Debug, Clone, Serialize)]
+pub struct EventLog {
+    pub turns: Vec<ReplayTurn>,
+    /// The board's [Board::zobrist_hash] after each of the turns, for the client to check its own
+    /// board against as it applies them
+    pub hashes: Vec<u64>,
+    /// The turn to ask for events from next time
+    pub next: usize,
+    pub finished: bool,
+    pub final_score: Vec<ReplayScore>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventsQuery {
+    /// First turn to include
+    #[serde(default)]
+    pub since: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SubmitMove {
+    /// One of the moves listed for the turn; `None` to pass. A move without a meeple can take one
+    /// back with `recalled` in games with `meeple_recall`
+    pub placement: Option<ReplayPlacement>,
+    /// The [Board::zobrist_hash] of the client's board before the move. The game is halted if it
+    /// isn't the server's, rather than played on with the two boards apart
+    #[serde(default)]
+    pub board_hash: Option<u64>,
+}
+
+/// What the server had when a client's board was found to differ from its own, for working out
+/// where the two went apart
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DesyncReport {
+    /// Turns played before the move the client sent its hash with
+    pub turn: usize,
+    pub player: PlayerIdentifier,
+    pub server_hash: u64,
+    pub client_hash: u64,
+    /// The game as the server played it, to compare the client's turns with
+    pub replay: Replay,
+    pub board: Vec<BoardTile>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ServerError {
+    GameNotFound,
+    InvalidGame(String),
+    Unauthorized,
+    NotYourTurn,
+    IllegalMove,
+    UnsupportedProtocol(u32),
+    /// The [PROTOCOL_HEADER] isn't a version number
+    MalformedProtocolHeader(String),
+    /// The client's board isn't the server's, so the game has been halted
+    Desync(Box<DesyncReport>),
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ServerError::Desync(report) => return (StatusCode::CONFLICT, Json(report)).into_response(),
+            ServerError::GameNotFound => (StatusCode::NOT_FOUND, "no such game".to_string()),
+            ServerError::InvalidGame(reason) => (StatusCode::BAD_REQUEST, reason),
+            ServerError::Unauthorized => (StatusCode::UNAUTHORIZED, "missing or unknown player token".to_string()),
+            ServerError::NotYourTurn => (StatusCode::CONFLICT, "it is not your turn".to_string()),
+            ServerError::IllegalMove => (StatusCode::UNPROCESSABLE_ENTITY, "not one of the legal moves".to_string()),
+            ServerError::UnsupportedProtocol(version) => (
+                StatusCode::UPGRADE_REQUIRED,
+                format!(
+                    "protocol version {} is not supported, this server speaks versions {} to {}",
+                    version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION
+                ),
+            ),
+            ServerError::MalformedProtocolHeader(value) => (StatusCode::BAD_REQUEST, format!("the {} header should be a protocol version, not [{}]", PROTOCOL_HEADER, value)),
+        };
+
+        (status, message).into_response()
+    }
+}
+
+/// The move a client is being asked for
+struct PendingTurn {
+    player: PlayerIdentifier,
+    tile: &'static TileDefinition,
+    hints: Vec<MoveHint>,
+}
+
+/// What clients can see of a game, kept up to date by the thread playing it
+struct HostedState {
+    replay: Replay,
+    state: ReplayState,
+    /// The board's hash after each turn
+    hashes: Vec<u64>,
+    desync: Option<DesyncReport>,
+    pending: Option<PendingTurn>,
+    /// When the game finished, if it has
+    finished_at: Option<Instant>,
+    /// When each client last made an authenticated request
+    last_seen: HashMap<PlayerIdentifier, Instant>,
+}
+
+/// A game being played on its own thread, with moves for client seats arriving over HTTP
+struct HostedGame {
+    tokens: HashMap<String, PlayerIdentifier>,
+    reconnect_grace: Duration,
+    moves: HashMap<PlayerIdentifier, Sender<Option<SubmittedMove>>>,
+    meeple_recall: bool,
+    state: Mutex<HostedState>,
+    changed: Condvar,
+}
+
+/// Hosts games for clients to play over HTTP; see [router]
+pub struct GameServer {
+    games: Mutex<HashMap<Uuid, Arc<HostedGame>>>,
+    reconnect_grace: Duration,
+    finished_game_retention: Duration,
+    artwork: TileArtwork,
+}
+
+impl Default for GameServer {
+    fn default() -> Self {
+        Self {
+            games: Default::default(),
+            reconnect_grace: DEFAULT_RECONNECT_GRACE,
+            finished_game_retention: DEFAULT_FINISHED_GAME_RETENTION,
+            artwork: Default::default(),
+        }
+    }
+}
+
+impl GameServer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// How long a seat is kept for a client that has stopped responding. Once it runs out the
+    /// seat passes its turns, without holding up the rest of the game, until the client is back
+    pub fn with_reconnect_grace(mut self, reconnect_grace: Duration) -> Self {
+        self.reconnect_grace = reconnect_grace;
+        self
+    }
+
+    /// How long a finished game is kept for clients to fetch how it ended. Once it runs out the
+    /// game is dropped when the next game is created
+    pub fn with_finished_game_retention(mut self, finished_game_retention: Duration) -> Self {
+        self.finished_game_retention = finished_game_retention;
+        self
+    }
+
+    /// Artwork to draw boards with in [GameServer::board_svg]
+    pub fn with_artwork(mut self, artwork: TileArtwork) -> Self {
+        self.artwork = artwork;
+        self
+    }
+
+    /// Agrees the protocol version and capabilities for a client, refusing clients too old to be
+    /// understood. Newer clients are offered the server's version to downgrade to
+    pub fn handshake(&self, hello: Hello) -> Result<Welcome, ServerError> {
+        if hello.protocol_version < MIN_PROTOCOL_VERSION {
+            return Err(ServerError::UnsupportedProtocol(hello.protocol_version));
+        }
+
+        Ok(Welcome {
+            protocol_version: hello.protocol_version.min(PROTOCOL_VERSION),
+            expansions: Expansion::ALL
+                .iter()
+                .map(Expansion::name)
+                .filter(|name| hello.expansions.iter().any(|requested| requested == name))
+                .collect(),
+            rules: RULE_OPTIONS.to_vec(),
+            features: PROTOCOL_FEATURES
+                .iter()
+                .copied()
+                .filter(|feature| hello.features.iter().any(|requested| requested == feature))
+                .collect(),
+        })
+    }
+
+    /// Starts a game, returning its id and a token for each seat played by a client
+    pub fn create_game(&self, request: CreateGame) -> Result<CreatedGame, ServerError> {
+        let seed: Seed = match &request.seed {
+            Some(seed) => general_purpose::URL_SAFE
+                .decode(seed)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| ServerError::InvalidGame("seed must be 32 bytes of url safe base64".to_string()))?,
+            None => OsRng.gen(),
+        };
+
+        if request.players.is_empty() {
+            return Err(ServerError::InvalidGame("a game needs players".to_string()));
+        }
+
+        for (index, seat) in request.players.iter().enumerate() {
+            if request.players[..index].iter().any(|other| other.color == seat.color) {
+                return Err(ServerError::InvalidGame(format!("{:?} is seated twice", seat.color)));
+            }
+        }
+
+        let expansions = if request.river { vec![Expansion::River] } else { vec![] };
+        let rules = match request.meeple_per_player {
+            Some(meeple_per_player) => RuleSet::standard().with_meeple_per_player(meeple_per_player),
+            None => RuleSet::standard(),
+        }
+        .with_meeple_recall(request.meeple_recall);
+
+        rules.validate(&expansions).map_err(|error| ServerError::InvalidGame(error.to_string()))?;
+
+        let mut rng = GameRng::from_seed(seed);
+        let mut bots = HashMap::new();
+
+        for seat in &request.players {
+            if let Some(name) = &seat.bot {
+                let bot = BotStrategy::from_name(name, &mut rng).ok_or_else(|| ServerError::InvalidGame(format!("unknown bot [{}]", name)))?;
+                bots.insert(seat.color, bot);
+            }
+        }
+
+        let mut tokens = HashMap::new();
+        let mut moves = HashMap::new();
+        let mut receivers = HashMap::new();
+
+        for seat in request.players.iter().filter(|seat| seat.bot.is_none()) {
+            let (sender, receiver) = mpsc::channel();
+
+            tokens.insert(Uuid::new_v4().to_string(), seat.color);
+            moves.insert(seat.color, sender);
+            receivers.insert(seat.color, receiver);
+        }
+
+        let replay = Replay {
+            seed: general_purpose::URL_SAFE.encode(seed),
+            deck: vec![],
+            players: request
+                .players
+                .iter()
+                .map(|seat| ReplayPlayer { color: seat.color, name: seat.name.clone(), bot: seat.bot.clone() })
+                .collect(),
+            turns: vec![],
+            final_score: vec![],
+            handovers: vec![],
+            rules: ReplayRules::of(&rules),
+        };
+        let state = replay.initial_state().map_err(|error| ServerError::InvalidGame(error.to_string()))?;
+
+        let now = Instant::now();
+        let last_seen = moves.keys().map(|color| (*color, now)).collect();
+
+        let game = Arc::new(HostedGame {
+            tokens,
+            reconnect_grace: self.reconnect_grace,
+            moves,
+            meeple_recall: request.meeple_recall,
+            state: Mutex::new(HostedState { state, replay, hashes: vec![], desync: None, pending: None, finished_at: None, last_seen }),
+            changed: Condvar::new(),
+        });
+
+        let hosted = Arc::clone(&game);
+        let (built, build_result) = mpsc::channel();
+
+        // games aren't Send, as their controllers needn't be, so each is built on the thread that
+        // plays it, which reports back before the game is handed out
+        thread::spawn(move || {
+            let desynced = Arc::clone(&hosted);
+            let mut builder = Game::builder()
+                .with_seed(seed)
+                .with_expansions(expansions)
+                .with_rules(rules)
+                .with_early_abort(move |_| desynced.state.lock().unwrap().desync.is_some());
+
+            for seat in request.players {
+                let mut player = Player::new(seat.color);
+                player.name = seat.name;
+
+                builder = match bots.remove(&seat.color) {
+                    Some(bot) => builder.with_player(player, bot),
+                    None => {
+                        let moves = receivers.remove(&seat.color).expect("client seats should have a move channel");
+                        builder.with_player(player, RemoteController { game: Arc::clone(&hosted), moves })
+                    }
+                };
+            }
+
+            let game = builder.build();
+            let _ = built.send(game.as_ref().map(|_| ()).map_err(|error| format!("{:?}", error)));
+
+            let Ok(game) = game else {
+                return;
+            };
+
+            let mut observers: Vec<Box<dyn GameObserver>> = vec![Box::new(HostedObserver(hosted))];
+            game.play_observed(0, &mut observers);
+        });
+
+        match build_result.recv() {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => return Err(ServerError::InvalidGame(format!("the game can't be set up: {}", error))),
+            Err(_) => return Err(ServerError::InvalidGame("the game can't be set up".to_string())),
+        }
+
+        let id = Uuid::new_v4();
+        let tokens = game.tokens.iter().map(|(token, color)| (*color, token.clone())).collect();
+
+        let mut games = self.games.lock().unwrap();
+        games.retain(|_, game| game.state.lock().unwrap().finished_at.is_none_or(|finished_at| finished_at.elapsed() < self.finished_game_retention));
+        games.insert(id, game);
+
+        Ok(CreatedGame { id, tokens })
+    }
+
+    fn game(&self, id: Uuid) -> Result<Arc<HostedGame>, ServerError> {
+        self.games.lock().unwrap().get(&id).cloned().ok_or(ServerError::GameNotFound)
+    }
+
+    pub fn summary(&self, id: Uuid) -> Result<GameSummary, ServerError> {
+        let game = self.game(id)?;
+        let hosted = game.state.lock().unwrap();
+
+        let score = if hosted.finished_at.is_some() { hosted.replay.final_score.clone() } else { scores(&hosted.replay, &hosted.state) };
+
+        Ok(GameSummary {
+            id,
+            players: hosted.replay.players.clone(),
+            turn: hosted.state.turn,
+            to_play: hosted.pending.as_ref().map(|pending| pending.player),
+            drawn_tile: hosted.pending.as_ref().map(|pending| pending.tile.name().to_string()),
+            phase: hosted.pending.as_ref().map(|pending| GamePhase::of(pending.tile).name()),
+            score,
+            finished: hosted.finished_at.is_some(),
+            board: board_tiles(&hosted.state.board),
+            board_hash: hosted.state.board.zobrist_hash(),
+            desync: hosted.desync.clone(),
+        })
+    }
+
+    /// The turns played since `since`, so a client can catch up on what it missed while
+    /// disconnected. A token, if given, marks its seat as connected again
+    pub fn events(&self, id: Uuid, since: usize, token: Option<&str>) -> Result<EventLog, ServerError> {
+        let game = self.game(id)?;
+
+        if let Some(token) = token {
+            game.player(token)?;
+        }
+
+        let hosted = game.state.lock().unwrap();
+
+        Ok(EventLog {
+            turns: hosted.replay.turns.iter().skip(since).cloned().collect(),
+            hashes: hosted.hashes.iter().skip(since).copied().collect(),
+            next: hosted.replay.turns.len(),
+            finished: hosted.finished_at.is_some(),
+            final_score: hosted.replay.final_score.clone(),
+        })
+    }
+
+    pub fn board_svg(&self, id: Uuid) -> Result<String, ServerError> {
+        let game = self.game(id)?;
+        let svg = game.state.lock().unwrap().state.board.render_svg_with_artwork(&self.artwork);
+
+        Ok(svg)
+    }
+
+    /// The legal moves for the tile the token's player drew, if it is their turn
+    pub fn legal_moves(&self, id: Uuid, token: &str) -> Result<Vec<ReplayPlacement>, ServerError> {
+        let game = self.game(id)?;
+        let player = game.player(token)?;
+        let hosted = game.state.lock().unwrap();
+
+        match &hosted.pending {
+            Some(pending) if pending.player == player => Ok(pending
+                .hints
+                .iter()
+                .map(|hint| ReplayPlacement::new(&hint.tile_placement, hint.meeple_placement))
+                .collect()),
+            _ => Err(ServerError::NotYourTurn),
+        }
+    }
+
+    /// Plays the token's player's move, waiting (briefly) for the game to apply it so the next
+    /// request sees the board after the move. A move sent with a board hash other than the
+    /// server's halts the game instead, answering with what the server had
+    pub fn submit_move(&self, id: Uuid, token: &str, submitted: SubmitMove) -> Result<(), ServerError> {
+        let game = self.game(id)?;
+        let player = game.player(token)?;
+        let mut hosted = game.state.lock().unwrap();
+
+        let server_hash = hosted.state.board.zobrist_hash();
+
+        if let Some(client_hash) = submitted.board_hash.filter(|client_hash| *client_hash != server_hash) {
+            if !hosted.pending.as_ref().is_some_and(|pending| pending.player == player) {
+                return Err(ServerError::NotYourTurn);
+            }
+
+            let report = DesyncReport {
+                turn: hosted.state.turn,
+                player,
+                server_hash,
+                client_hash,
+                replay: hosted.replay.clone(),
+                board: board_tiles(&hosted.state.board),
+            };
+
+            hosted.desync = Some(report.clone());
+            hosted.pending = None;
+
+            // the turn is passed to let the game get to its next draw, where it stops
+            game.moves[&player].send(None).map_err(|_| ServerError::GameNotFound)?;
+
+            return Err(ServerError::Desync(Box::new(report)));
+        }
+
+        let submitted_move = match &hosted.pending {
+            Some(pending) if pending.player == player => match &submitted.placement {
+                None => None,
+                // a meeple is only taken back by a move that doesn't place one, in games that allow it
+                Some(ReplayPlacement { recalled: Some(_), meeple: Some(_), .. }) => return Err(ServerError::IllegalMove),
+                Some(ReplayPlacement { recalled: Some(_), .. }) if !game.meeple_recall => return Err(ServerError::IllegalMove),
+                Some(placement) => {
+                    let placed = ReplayPlacement { recalled: None, ..placement.clone() };
+                    let hint = pending
+                        .hints
+                        .iter()
+                        .find(|hint| ReplayPlacement::new(&hint.tile_placement, hint.meeple_placement) == placed)
+                        .cloned()
+                        .ok_or(ServerError::IllegalMove)?;
+
+                    Some(SubmittedMove { hint, recall: placement.recalled.clone() })
+                }
+            },
+            _ => return Err(ServerError::NotYourTurn),
+        };
+
+        hosted.pending = None;
+        let turn = hosted.state.turn;
+
+        game.moves[&player].send(submitted_move).map_err(|_| ServerError::GameNotFound)?;
+
+        let _ = game
+            .changed
+            .wait_timeout_while(hosted, MOVE_APPLIED_TIMEOUT, |hosted| hosted.state.turn == turn && hosted.finished_at.is_none())
+            .unwrap();
+
+        Ok(())
+    }
+}
+
+impl HostedGame {
+    /// The player the token belongs to, who is seen to be connected
+    fn player(&self, token: &str) -> Result<PlayerIdentifier, ServerError> {
+        let player = self.tokens.get(token).copied().ok_or(ServerError::Unauthorized)?;
+
+        self.state.lock().unwrap().last_seen.insert(player, Instant::now());
+
+        Ok(player)
+    }
+}
+
+fn scores(replay: &Replay, state: &ReplayState) -> Vec<ReplayScore> {
+    replay
+        .players
+        .iter()
+        .map(|p| ReplayScore {
+            player: p.color,
+            points: state.score.iter().find(|(id, _)| **id == p.color).map(|(_, s)| *s).unwrap_or(0),
+        })
+        .collect()
+}
+
+fn board_tiles(board: &Board) -> Vec<BoardTile> {
+    board
+        .placed_tiles
+        .values()
+        .map(|tile| BoardTile {
+            tile: tile.tile.name().to_string(),
+            placement: ReplayPlacement::new(&tile.placement, tile.meeple.as_ref().map(|(region_index, _)| *region_index)),
+            meeple_player: tile.meeple.as_ref().map(|(_, meeple)| meeple.color),
+        })
+        .collect()
+}
+
+/// One of the moves offered to a client, with the meeple it takes back if any
+struct SubmittedMove {
+    hint: MoveHint,
+    recall: Option<ReplayRecall>,
+}
+
+/// Plays a seat by publishing the turn for its client and waiting for them to submit a move
+struct RemoteController {
+    game: Arc<HostedGame>,
+    moves: Receiver<Option<SubmittedMove>>,
+}
+
+impl PlayerController for RemoteController {
+    fn choose_move(&mut self, view: &GameView, hints: &[MoveHint]) -> PlayerAction {
+        let player = view.player.meeple_color;
+        let asked_at = Instant::now();
+
+        let mut hosted = self.game.state.lock().unwrap();
+
+        // a client that was already gone by its turn isn't waited for again
+        if asked_at.duration_since(hosted.last_seen[&player]) > self.game.reconnect_grace {
+            return PlayerAction::Pass;
+        }
+
+        hosted.pending = Some(PendingTurn { player, tile: view.tile, hints: hints.to_vec() });
+        drop(hosted);
+        self.game.changed.notify_all();
+
+        loop {
+            let deadline = self.game.state.lock().unwrap().last_seen[&player].max(asked_at) + self.game.reconnect_grace;
+
+            match self.moves.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(Some(SubmittedMove { hint, recall: None })) => return PlayerAction::Place(hint),
+                Ok(Some(SubmittedMove { hint, recall: Some(recall) })) => {
+                    return PlayerAction::RecallMeeple {
+                        tile_placement: hint.tile_placement,
+                        coordinate: BoardCoordinate::new(recall.x, recall.y),
+                        region_index: RegionIndex::new(recall.meeple),
+                    }
+                }
+                Ok(None) | Err(RecvTimeoutError::Disconnected) => return PlayerAction::Pass,
+                Err(RecvTimeoutError::Timeout) => {
+                    let mut hosted = self.game.state.lock().unwrap();
+
+                    // unless the client was heard from since, or its move is already on the way
+                    if hosted.last_seen[&player].max(asked_at) + self.game.reconnect_grace <= Instant::now() && hosted.pending.is_some() {
+                        hosted.pending = None;
+                        return PlayerAction::Pass;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Mirrors the game's turns into the state clients see
+struct HostedObserver(Arc<HostedGame>);
+
+impl GameObserver for HostedObserver {
+    fn notify(&mut self, event: &GameEvent) {
+        let mut hosted = self.0.state.lock().unwrap();
+
+        match event {
+            GameEvent::GameStarted { .. } | GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+            GameEvent::TurnCompleted { player, tile, placement, .. } => {
+                let turn = ReplayTurn {
+                    player: *player,
+                    tile: tile.name().to_string(),
+                    placement: placement.as_ref().map(|(placement, meeple)| ReplayPlacement::new(placement, *meeple)),
+                };
+
+                hosted.state.apply(&turn).expect("the game should only make legal moves");
+                hosted.replay.turns.push(turn);
+
+                let hash = hosted.state.board.zobrist_hash();
+                hosted.hashes.push(hash);
+            }
+            GameEvent::MeepleRecalled { player, coordinate, region_index, .. } => {
+                hosted.state.recall_meeple(*player, coordinate, *region_index);
+
+                if let Some(placement) = hosted.replay.turns.last_mut().and_then(|turn| turn.placement.as_mut()) {
+                    placement.recalled = Some(ReplayRecall::new(coordinate, *region_index));
+                }
+
+                // the turn's hash is of the board once the meeple is back
+                let hash = hosted.state.board.zobrist_hash();
+                if let Some(last) = hosted.hashes.last_mut() {
+                    *last = hash;
+                }
+            }
+            GameEvent::GameCompleted { score, .. } => {
+                hosted.replay.record_final_score(score);
+                hosted.finished_at = Some(Instant::now());
+            }
+        }
+
+        drop(hosted);
+        self.0.changed.notify_all();
+    }
+}
+
+fn check_protocol_version(version: u32) -> Result<(), ServerError> {
+    if (MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&version) {
+        Ok(())
+    } else {
+        Err(ServerError::UnsupportedProtocol(version))
+    }
+}
+
+/// Refuses requests whose [PROTOCOL_HEADER] names a version the server can't speak; clients
+/// should send the version agreed in the handshake. Requests
+/// without the header are let through, so the API can still be explored by hand
+async fn require_protocol(request: Request, next: Next) -> Result<Response, ServerError> {
+    if let Some(value) = request.headers().get(PROTOCOL_HEADER) {
+        check_protocol_version(protocol_version(value)?)?;
+    }
+
+    Ok(next.run(request).await)
+}
+
+fn protocol_version(value: &HeaderValue) -> Result<u32, ServerError> {
+    value
+        .to_str()
+        .ok()
+        .and_then(|version| version.trim().parse().ok())
+        .ok_or_else(|| ServerError::MalformedProtocolHeader(String::from_utf8_lossy(value.as_bytes()).into_owned()))
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<&str, ServerError> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or(ServerError::Unauthorized)
+}
+
+/// HTTP routes for a [GameServer]:
+/// * `POST /handshake` agrees the protocol from a [Hello], replying with a [Welcome]
+/// * `POST /games` creates a game from a [CreateGame]
+/// * `GET /games/{id}` describes the game as a [GameSummary]
+/// * `GET /games/{id}/events?since={turn}` lists the turns played since a turn as an [EventLog]
+/// * `GET /games/{id}/board.svg` draws the board
+/// * `GET /games/{id}/moves` lists the legal moves for the drawn tile
+/// * `POST /games/{id}/moves` plays a [SubmitMove]
+///
+/// The moves routes take the seat's token as a bearer token, as can the events route to mark the
+/// seat as reconnected. A move sent with a board hash that isn't the server's halts the game, and
+/// is answered with a [DesyncReport]. Any route refuses clients sending an
+/// unsupported version in the [PROTOCOL_HEADER]
+pub fn router(server: Arc<GameServer>) -> Router {
+    Router::new()
+        .route("/handshake", axum::routing::post(handshake))
+        .route("/games", axum::routing::post(create_game))
+        .route("/games/{id}", get(summary))
+        .route("/games/{id}/events", get(events))
+        .route("/games/{id}/board.svg", get(board_svg))
+        .route("/games/{id}/moves", get(legal_moves).post(submit_move))
+        .layer(middleware::from_fn(require_protocol))
+        .with_state(server)
+}
+
+/// Serves the [router] for `server` on `address` until the process ends
+pub async fn serve(address: SocketAddr, server: GameServer) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(address).await?;
+
+    axum::serve(listener, router(Arc::new(server))).await
+}
+
+async fn handshake(State(server): State<Arc<GameServer>>, Json(hello): Json<Hello>) -> Result<Json<Welcome>, ServerError> {
+    server.handshake(hello).map(Json)
+}
+
+async fn create_game(State(server): State<Arc<GameServer>>, Json(request): Json<CreateGame>) -> Result<Json<CreatedGame>, ServerError> {
+    server.create_game(request).map(Json)
+}
+
+async fn summary(State(server): State<Arc<GameServer>>, Path(id): Path<Uuid>) -> Result<Json<GameSummary>, ServerError> {
+    server.summary(id).map(Json)
+}
+
+async fn events(
+    State(server): State<Arc<GameServer>>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Result<Json<EventLog>, ServerError> {
+    let token = match headers.contains_key(header::AUTHORIZATION) {
+        true => Some(bearer_token(&headers)?),
+        false => None,
+    };
+
+    server.events(id, query.since, token).map(Json)
+}
+
+async fn board_svg(State(server): State<Arc<GameServer>>, Path(id): Path<Uuid>) -> Result<impl IntoResponse, ServerError> {
+    Ok(([(header::CONTENT_TYPE, "image/svg+xml")], server.board_svg(id)?))
+}
+
+async fn legal_moves(State(server): State<Arc<GameServer>>, Path(id): Path<Uuid>, headers: HeaderMap) -> Result<Json<Vec<ReplayPlacement>>, ServerError> {
+    server.legal_moves(id, bearer_token(&headers)?).map(Json)
+}
+
+async fn submit_move(
+    State(server): State<Arc<GameServer>>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+    Json(submitted): Json<SubmitMove>,
+) -> Result<StatusCode, ServerError> {
+    let token = bearer_token(&headers)?.to_string();
+
+    // submitting waits on the game thread, so is kept off the async workers
+    tokio::task::spawn_blocking(move || server.submit_move(id, &token, submitted))
+        .await
+        .map_err(|_| ServerError::GameNotFound)??;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::Rotation;
+    use crate::player::MeepleColor;
+
+    fn seat(color: MeepleColor, bot: Option<&str>) -> SeatRequest {
+        SeatRequest { color, name: None, bot: bot.map(str::to_string) }
+    }
+
+    fn wait_for_turn(server: &GameServer, id: Uuid) -> GameSummary {
+        for _ in 0..500 {
+            let summary = server.summary(id).unwrap();
+
+            if summary.to_play.is_some() || summary.finished {
+                return summary;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        panic!("game never asked for a move");
+    }
+
+    #[test]
+    fn should_play_a_client_move_and_continue_the_game() {
+        let server = GameServer::new();
+
+        let created = server
+            .create_game(CreateGame {
+                players: vec![seat(MeepleColor::Red, None), seat(MeepleColor::Green, Some("lazy"))],
+                seed: Some(general_purpose::URL_SAFE.encode([2; 32])),
+                river: false,
+                meeple_per_player: None,
+                meeple_recall: false,
+            })
+            .unwrap();
+
+        assert_eq!(created.tokens.len(), 1);
+        let token = &created.tokens[&MeepleColor::Red];
+
+        let summary = wait_for_turn(&server, created.id);
+        assert_eq!(summary.to_play, Some(MeepleColor::Red));
+        assert!(summary.board.is_empty());
+
+        let moves = server.legal_moves(created.id, token).unwrap();
+        // without a meeple, as the bot's reply could complete the region and return it
+        let chosen = moves.iter().find(|placement| placement.meeple.is_none()).unwrap().clone();
+
+        server.submit_move(created.id, token, SubmitMove { placement: Some(chosen.clone()), board_hash: None }).unwrap();
+
+        let summary = wait_for_turn(&server, created.id);
+
+        // our move and the bot's reply
+        assert_eq!(summary.turn, 2);
+        assert_eq!(summary.board[0].placement, chosen);
+        assert!(server.board_svg(created.id).unwrap().contains("<rect"));
+    }
+
+    #[test]
+    fn should_reject_moves_from_strangers_and_illegal_moves() {
+        let server = GameServer::new();
+
+        let created = server
+            .create_game(CreateGame {
+                players: vec![seat(MeepleColor::Red, None), seat(MeepleColor::Green, None)],
+                seed: None,
+                river: false,
+                meeple_per_player: None,
+                meeple_recall: false,
+            })
+            .unwrap();
+
+        wait_for_turn(&server, created.id);
+
+        let green = &created.tokens[&MeepleColor::Green];
+        let red = &created.tokens[&MeepleColor::Red];
+
+        assert_eq!(server.legal_moves(created.id, "guess"), Err(ServerError::Unauthorized));
+        assert_eq!(server.legal_moves(created.id, green), Err(ServerError::NotYourTurn));
+
+        let illegal = SubmitMove { placement: Some(ReplayPlacement { x: 4, y: 4, rotation: Rotation::R0, meeple: None, recalled: None }), board_hash: None };
+        assert_eq!(server.submit_move(created.id, red, illegal), Err(ServerError::IllegalMove));
+        assert_eq!(server.summary(Uuid::new_v4()).err(), Some(ServerError::GameNotFound));
+    }
+
+    #[test]
+    fn should_take_back_a_meeple_only_in_games_that_allow_it() {
+        let server = GameServer::new();
+
+        let play_two = |meeple_recall: bool| {
+            let created = server
+                .create_game(CreateGame {
+                    players: vec![seat(MeepleColor::Red, None)],
+                    seed: Some(general_purpose::URL_SAFE.encode([3; 32])),
+                    river: false,
+                    meeple_per_player: None,
+                    meeple_recall,
+                })
+                .unwrap();
+            let token = &created.tokens[&MeepleColor::Red];
+
+            wait_for_turn(&server, created.id);
+            let placed = server.legal_moves(created.id, token).unwrap().into_iter().find(|placement| placement.meeple.is_some()).unwrap();
+            server.submit_move(created.id, token, SubmitMove { placement: Some(placed.clone()), board_hash: None }).unwrap();
+
+            wait_for_turn(&server, created.id);
+            let moves = server.legal_moves(created.id, token).unwrap();
+            let recall = Some(ReplayRecall { x: placed.x, y: placed.y, meeple: placed.meeple.unwrap() });
+
+            let with_meeple = moves.iter().find(|placement| placement.meeple.is_some()).unwrap();
+            let both = SubmitMove { placement: Some(ReplayPlacement { recalled: recall.clone(), ..with_meeple.clone() }), board_hash: None };
+            assert_eq!(server.submit_move(created.id, token, both), Err(ServerError::IllegalMove));
+
+            let without_meeple = moves.iter().find(|placement| placement.meeple.is_none()).unwrap();
+            let recalled = SubmitMove { placement: Some(ReplayPlacement { recalled: recall, ..without_meeple.clone() }), board_hash: None };
+
+            (server.submit_move(created.id, token, recalled), created.id)
+        };
+
+        assert_eq!(play_two(false).0, Err(ServerError::IllegalMove));
+
+        let (submitted, id) = play_two(true);
+        assert_eq!(submitted, Ok(()));
+
+        let summary = wait_for_turn(&server, id);
+        assert_eq!(summary.turn, 2);
+        assert!(summary.board.iter().all(|tile| tile.placement.meeple.is_none()));
+    }
+
+    #[test]
+    fn should_reject_games_with_unknown_bots_or_repeated_seats() {
+        let server = GameServer::new();
+
+        let unknown_bot = CreateGame { players: vec![seat(MeepleColor::Red, Some("clippy"))], seed: None, river: false, meeple_per_player: None, meeple_recall: false };
+        let repeated = CreateGame { players: vec![seat(MeepleColor::Red, None), seat(MeepleColor::Red, None)], seed: None, river: false, meeple_per_player: None, meeple_recall: false };
+
+        assert!(matches!(server.create_game(unknown_bot), Err(ServerError::InvalidGame(_))));
+        assert!(matches!(server.create_game(repeated), Err(ServerError::InvalidGame(_))));
+
+        let no_meeple = CreateGame { players: vec![seat(MeepleColor::Red, None)], seed: None, river: false, meeple_per_player: Some(0), meeple_recall: false };
+
+        assert_eq!(server.create_game(no_meeple).err(), Some(ServerError::InvalidGame("players need at least one meeple".to_string())));
+    }
+
+    #[test]
+    fn should_agree_the_older_protocol_and_shared_capabilities() {
+        let server = GameServer::new();
+
+        let welcome = server
+            .handshake(Hello {
+                protocol_version: PROTOCOL_VERSION + 1,
+                expansions: vec!["river".to_string(), "inns_and_cathedrals".to_string()],
+                features: vec!["legal_moves".to_string(), "teleport".to_string()],
+            })
+            .unwrap();
+
+        assert_eq!(
+            welcome,
+            Welcome { protocol_version: PROTOCOL_VERSION, expansions: vec!["river"], rules: vec!["meeple_per_player", "meeple_recall"], features: vec!["legal_moves"] }
+        );
+    }
+
+    #[test]
+    fn should_refuse_clients_older_than_the_protocol() {
+        let server = GameServer::new();
+
+        let hello = Hello { protocol_version: MIN_PROTOCOL_VERSION - 1, expansions: vec![], features: vec![] };
+
+        assert_eq!(server.handshake(hello), Err(ServerError::UnsupportedProtocol(MIN_PROTOCOL_VERSION - 1)));
+        assert_eq!(check_protocol_version(PROTOCOL_VERSION), Ok(()));
+    }
+
+    #[test]
+    fn should_tell_a_malformed_protocol_header_from_an_unsupported_version() {
+        assert_eq!(protocol_version(&HeaderValue::from_static(" 2 ")), Ok(2));
+        assert_eq!(protocol_version(&HeaderValue::from_static("two")), Err(ServerError::MalformedProtocolHeader("two".to_string())));
+        assert_eq!(protocol_version(&HeaderValue::from_static("")), Err(ServerError::MalformedProtocolHeader(String::new())));
+    }
+
+    #[test]
+    fn should_pass_for_an_absent_client_and_let_it_catch_up_on_return() {
+        let server = GameServer::new().with_reconnect_grace(Duration::from_millis(20));
+
+        let created = server
+            .create_game(CreateGame {
+                players: vec![seat(MeepleColor::Red, None), seat(MeepleColor::Green, Some("lazy"))],
+                seed: None,
+                river: false,
+                meeple_per_player: None,
+                meeple_recall: false,
+            })
+            .unwrap();
+
+        // red never shows up, so the game carries on without them
+        for _ in 0..500 {
+            if server.summary(created.id).unwrap().turn >= 4 {
+                break;
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+
+        let token = &created.tokens[&MeepleColor::Red];
+        let events = server.events(created.id, 0, Some(token)).unwrap();
+
+        assert!(events.next >= 4);
+        assert_eq!(events.turns[0].player, MeepleColor::Red);
+        assert_eq!(events.turns[0].placement, None);
+
+        let later = server.events(created.id, 2, None).unwrap();
+        assert_eq!(later.turns[..], server.events(created.id, 0, None).unwrap().turns[2..later.turns.len() + 2]);
+
+        // the missed turns rebuild the board the server has
+        let summary = server.summary(created.id).unwrap();
+        let mut state = Replay { seed: String::new(), deck: vec![], players: summary.players, turns: vec![], final_score: vec![], handovers: vec![], rules: ReplayRules::default() }.initial_state().unwrap();
+
+        for turn in &server.events(created.id, 0, None).unwrap().turns[..summary.turn] {
+            state.apply(turn).unwrap();
+        }
+
+        assert_eq!(board_tiles(&state.board).len(), summary.board.len());
+    }
+
+    #[test]
+    fn should_halt_the_game_when_a_clients_board_hash_differs() {
+        let server = GameServer::new();
+
+        let created = server
+            .create_game(CreateGame {
+                players: vec![seat(MeepleColor::Red, None), seat(MeepleColor::Green, Some("lazy"))],
+                seed: Some(general_purpose::URL_SAFE.encode([2; 32])),
+                river: false,
+                meeple_per_player: None,
+                meeple_recall: false,
+            })
+            .unwrap();
+
+        let token = &created.tokens[&MeepleColor::Red];
+        let summary = wait_for_turn(&server, created.id);
+
+        // the client's empty board agrees with the server's
+        let mut state = Replay { seed: String::new(), deck: vec![], players: summary.players, turns: vec![], final_score: vec![], handovers: vec![], rules: ReplayRules::default() }.initial_state().unwrap();
+        let chosen = server.legal_moves(created.id, token).unwrap().into_iter().find(|placement| placement.meeple.is_none()).unwrap();
+
+        server.submit_move(created.id, token, SubmitMove { placement: Some(chosen), board_hash: Some(state.board.zobrist_hash()) }).unwrap();
+
+        let summary = wait_for_turn(&server, created.id);
+        let events = server.events(created.id, 0, None).unwrap();
+
+        for (turn, hash) in events.turns.iter().zip(&events.hashes) {
+            state.apply(turn).unwrap();
+            assert_eq!(state.board.zobrist_hash(), *hash);
+        }
+
+        assert_eq!(summary.board_hash, state.board.zobrist_hash());
+
+        // a client that has lost track of the board stops the game
+        let moves = server.legal_moves(created.id, token).unwrap();
+        let lost = SubmitMove { placement: moves.first().cloned(), board_hash: Some(summary.board_hash ^ 1) };
+
+        let Err(ServerError::Desync(report)) = server.submit_move(created.id, token, lost) else {
+            panic!("the mismatched hash should have been reported");
+        };
+
+        assert_eq!((report.turn, report.player, report.server_hash, report.client_hash), (2, MeepleColor::Red, summary.board_hash, summary.board_hash ^ 1));
+        assert_eq!(report.replay.turns, events.turns);
+
+        let summary = wait_for_turn(&server, created.id);
+        assert!(summary.finished);
+        assert_eq!(summary.turn, 3);
+        assert_eq!(summary.desync, Some(*report));
+    }
+
+    #[test]
+    fn should_drop_finished_games_once_their_retention_runs_out() {
+        let server = GameServer::new().with_finished_game_retention(Duration::ZERO);
+        let bots_only = || CreateGame {
+            players: vec![seat(MeepleColor::Red, Some("lazy")), seat(MeepleColor::Green, Some("lazy"))],
+            seed: Some(general_purpose::URL_SAFE.encode([5; 32])),
+            river: false,
+            meeple_per_player: None,
+            meeple_recall: false,
+        };
+
+        let finished = server.create_game(bots_only()).unwrap();
+        assert!(wait_for_turn(&server, finished.id).finished);
+
+        let next = server.create_game(bots_only()).unwrap();
+
+        assert_eq!(server.summary(finished.id).err(), Some(ServerError::GameNotFound));
+        assert!(server.summary(next.id).is_ok());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::board::{Board, InvalidTilePlacement};
+use crate::player::{Meeple, MeepleColor, RegionIndex};
+use crate::tile::{PlacedTile, Rotation};
+use crate::tile_definitions;
+use base64::{engine::general_purpose, Engine as _};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+const SHARE_CODE_VERSION: u8 = 1;
+
+const WITH_MEEPLE: u8 = 0b100;
+const COLOR_SHIFT: u8 = 3;
+
+#[derive(Debug)]
+pub enum ShareCodeError {
+    /// Not url safe base64
+    Encoding,
+    /// Didn't decompress, or ended part way through a tile
+    Malformed,
+    UnsupportedVersion(u8),
+    UnknownTile(u8),
+    UnknownColor(u8),
+    InvalidPlacement(InvalidTilePlacement),
+}
+
+impl Board {
+    /// A short string describing every tile on the board and any meeple on them, for pasting
+    /// positions into issues and chat. [Board::from_share_code] rebuilds the board exactly
+    pub fn to_share_code(&self) -> String {
+        // tiles are listed in the order they were placed, so rebuilding the board places each one
+        // next to a tile that is already there
+        let mut bytes = vec![SHARE_CODE_VERSION];
+
+        for tile in self.placed_tiles.values() {
+            let (meeple_flag, meeple) = match &tile.meeple {
+                Some((region_index, meeple)) => (WITH_MEEPLE | (meeple.color.to_byte() << COLOR_SHIFT), Some(**region_index as u8)),
+                None => (0, None),
+            };
+
+            bytes.extend([
+                tile.tile.id(),
+                tile.placement.coordinate.x as u8,
+                tile.placement.coordinate.y as u8,
+                tile.placement.rotation.quarter_turns() | meeple_flag,
+            ]);
+            bytes.extend(meeple);
+        }
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(&bytes).expect("writing to memory should not fail");
+
+        general_purpose::URL_SAFE_NO_PAD.encode(encoder.finish().expect("writing to memory should not fail"))
+    }
+
+    pub fn from_share_code(code: &str) -> Result<Self, ShareCodeError> {
+        let compressed = general_purpose::URL_SAFE_NO_PAD
+            .decode(code.trim())
+            .map_err(|_| ShareCodeError::Encoding)?;
+
+        let mut bytes = vec![];
+        DeflateDecoder::new(compressed.as_slice())
+            .read_to_end(&mut bytes)
+            .map_err(|_| ShareCodeError::Malformed)?;
+
+        let (&version, mut rest) = bytes.split_first().ok_or(ShareCodeError::Malformed)?;
+
+        if version != SHARE_CODE_VERSION {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+
+        let mut tiles = vec![];
+
+        while let [id, x, y, flags, remaining @ ..] = rest {
+            let definition = tile_definitions::find_by_id(*id).ok_or(ShareCodeError::UnknownTile(*id))?;
+
+            let mut tile = PlacedTile::new(definition, *x as i8, *y as i8, Rotation::ALL[(flags & 0b11) as usize]);
+
+            rest = remaining;
+
+            if flags & WITH_MEEPLE != 0 {
+                let color_byte = flags >> COLOR_SHIFT;
+                let color = MeepleColor::from_byte(color_byte).ok_or(ShareCodeError::UnknownColor(color_byte))?;
+
+                let (region_index, remaining) = rest.split_first().ok_or(ShareCodeError::Malformed)?;
+                rest = remaining;
+
+                tile.meeple = Some((RegionIndex::new(*region_index as usize), Meeple::new(color)));
+            }
+
+            tiles.push(tile);
+        }
+
+        if !rest.is_empty() {
+            return Err(ShareCodeError::Malformed);
+        }
+
+        Board::new_with_tiles(tiles).map_err(ShareCodeError::InvalidPlacement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use crate::test_util::TestPlayer;
+    use crate::tile_definitions::{CORNER_ROAD, STRAIGHT_ROAD};
+
+    #[test]
+    fn should_rebuild_the_board_from_its_share_code() {
+        let mut alice = Player::red();
+        let mut bob = Player::yellow();
+
+        let board = Board::new_with_tiles([
+            alice.move_with_meeple(&CORNER_ROAD, -1, -1, Rotation::R0, 1),
+            PlacedTile::new(&STRAIGHT_ROAD, -1, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, -1, 1, Rotation::R270),
+            PlacedTile::new(&STRAIGHT_ROAD, 0, -1, Rotation::R90),
+            bob.move_with_meeple(&CORNER_ROAD, 1, -1, Rotation::R90, 2),
+        ])
+        .unwrap();
+
+        let code = board.to_share_code();
+
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+
+        let rebuilt = Board::from_share_code(&code).unwrap();
+
+        assert_eq!(rebuilt.to_share_code(), code);
+        assert_eq!(rebuilt.placed_tile_count(), 5);
+        assert_eq!(rebuilt.calculate_board_score(), board.calculate_board_score());
+    }
+
+    #[test]
+    fn should_share_an_empty_board() {
+        let rebuilt = Board::from_share_code(&Board::new().to_share_code()).unwrap();
+
+        assert_eq!(rebuilt.placed_tile_count(), 0);
+    }
+
+    #[test]
+    fn should_reject_codes_that_are_not_boards() {
+        assert!(matches!(Board::from_share_code("not a code!"), Err(ShareCodeError::Encoding)));
+        assert!(matches!(Board::from_share_code("AAAA"), Err(ShareCodeError::Malformed)));
+    }
+}
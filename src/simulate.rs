@@ -0,0 +1,1158 @@
+use crate::bot_strategy::BotPlayer;
+use crate::game_logic::{AbortCondition, Game, GameBuildError, GamePhase};
+use crate::observer::GameObserver;
+use crate::player::{Player, PlayerIdentifier};
+use crate::rng::GameRng;
+use crate::rules::{RuleSet, TieBreak};
+use crate::score::{NamedScore, Score};
+use crate::team::{team_ranking, Team};
+use crate::tile::{Expansion, RegionType};
+use indexmap::IndexMap;
+use base64::{engine::general_purpose, Engine as _};
+use rand::prelude::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::ops::Add;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+pub type Seed = [u8; 32];
+
+/// How players are seated for each game of a batch. Whoever sits first takes the first turn, which
+/// is worth something, so batches where it is always the same player are biased
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Seating {
+    /// In the order the players were given, every game
+    #[default]
+    Fixed,
+    /// Each game starts one seat further round than the last
+    Rotate,
+    /// A random order each game
+    Shuffle,
+    /// Each deck played once in every seat order, so neither the first turn nor the draw of the deck
+    /// favours anyone. With more than three players, so the games don't multiply out of hand, only
+    /// in the orders starting from each seat in turn, which still seats everyone in every seat once
+    Permute,
+}
+
+impl Seating {
+    /// The players in turn order for the game
+    fn seat<T: Clone>(&self, players: &IndexMap<PlayerIdentifier, T>, game_index: usize, rng: &mut GameRng) -> IndexMap<PlayerIdentifier, T> {
+        let mut seated = players.clone();
+
+        match self {
+            Seating::Fixed => {}
+            Seating::Rotate if !seated.is_empty() => {
+                let later = seated.split_off(game_index % players.len());
+                seated = later.into_iter().chain(seated).collect();
+            }
+            Seating::Rotate => {}
+            Seating::Shuffle => {
+                let mut order: Vec<usize> = (0..seated.len()).collect();
+                order.shuffle(rng);
+
+                seated = order.into_iter().filter_map(|index| players.get_index(index)).map(|(id, p)| (*id, p.clone())).collect();
+            }
+            Seating::Permute if players.len() > 3 => return Seating::Rotate.seat(players, game_index, rng),
+            Seating::Permute => {
+                let order = permutation(players.len(), game_index % self.orders_per_deck(players.len()));
+
+                seated = order.into_iter().filter_map(|index| players.get_index(index)).map(|(id, p)| (*id, p.clone())).collect();
+            }
+        }
+
+        seated
+    }
+
+    /// How many games each deck is played in, one for each seat order
+    pub fn orders_per_deck(&self, player_count: usize) -> usize {
+        match self {
+            Seating::Permute if player_count > 3 => player_count,
+            Seating::Permute => (1..=player_count).product(),
+            _ => 1,
+        }
+    }
+}
+
+/// The `index`th ordering of `0..count`, in lexicographic order
+fn permutation(count: usize, mut index: usize) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..count).collect();
+    let mut order = Vec::with_capacity(count);
+
+    for position in (0..count).rev() {
+        let orderings_each: usize = (1..=position).product();
+        order.push(remaining.remove(index / orderings_each));
+        index %= orderings_each;
+    }
+
+    order
+}
+
+#[derive(Clone)]
+pub struct SimulationConfig {
+    pub players: IndexMap<PlayerIdentifier, BotPlayer>,
+    /// Decks to play, each once, or once in every seat order with [Seating::Permute]
+    pub iterations: usize,
+    /// Seed for the whole batch; each game derives its own seed from this
+    pub seed: Seed,
+    pub include_river: bool,
+    /// Ends each game early once this holds; see [score_gap_exceeds]
+    pub abort: Option<AbortCondition>,
+    pub seating: Seating,
+    pub rules: RuleSet,
+}
+
+impl SimulationConfig {
+    /// Players are told apart by their meeple colour, so no two can share one
+    pub fn new<P: IntoIterator<Item = BotPlayer>>(players: P, iterations: usize, seed: Seed) -> Result<Self, GameBuildError> {
+        let mut seated = IndexMap::new();
+
+        for player in players {
+            let id = player.player.meeple_color;
+
+            if seated.insert(id, player).is_some() {
+                return Err(GameBuildError::DuplicatePlayer(id));
+            }
+        }
+
+        if seated.is_empty() {
+            return Err(GameBuildError::NoPlayers);
+        }
+
+        Ok(Self {
+            players: seated,
+            iterations,
+            seed,
+            include_river: true,
+            abort: None,
+            seating: Seating::Fixed,
+            rules: RuleSet::standard(),
+        })
+    }
+
+    pub fn with_river(mut self, include_river: bool) -> Self {
+        self.include_river = include_river;
+        self
+    }
+
+    pub fn with_seating(mut self, seating: Seating) -> Self {
+        self.seating = seating;
+        self
+    }
+
+    pub fn with_early_abort(mut self, condition: AbortCondition) -> Self {
+        self.abort = Some(condition);
+        self
+    }
+
+    /// The rules have to be for as many players as the simulation has
+    pub fn with_rules(mut self, rules: RuleSet) -> Result<Self, GameBuildError> {
+        if !rules.allows_player_count(self.players.len()) {
+            return Err(GameBuildError::WrongPlayerCount(self.players.len()));
+        }
+
+        self.rules = rules;
+        Ok(self)
+    }
+
+    /// Games played in all, counting each seat order a deck is played in
+    pub fn game_count(&self) -> usize {
+        self.iterations * self.seating.orders_per_deck(self.players.len())
+    }
+}
+
+/// An abort condition that ends a game once the leader is more than `threshold` points ahead of
+/// the last placed player
+pub fn score_gap_exceeds(threshold: i32) -> AbortCondition {
+    Arc::new(move |score: &Score| {
+        let points = || score.iter().map(|(_, points)| *points);
+
+        match (points().max(), points().min()) {
+            (Some(best), Some(worst)) => best - worst > threshold,
+            _ => false,
+        }
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct GameResult {
+    /// The seed the game's deck was shuffled with
+    pub seed: Seed,
+    pub score: Score,
+    pub duration: Duration,
+    pub placed_tile_count: usize,
+    /// The teams the players were in, if they played in teams
+    pub teams: Vec<Team>,
+    /// Players who resigned, in the order they did
+    pub resigned: Vec<PlayerIdentifier>,
+    /// Whether the game was ended early by its abort condition
+    pub aborted: bool,
+    /// Turns taken, including passes
+    pub turn_count: usize,
+    /// How long each player spent choosing their moves
+    pub think_time: IndexMap<PlayerIdentifier, ThinkTime>,
+    /// How long all the players spent choosing their moves in each phase of the game
+    pub phase_think_time: IndexMap<GamePhase, ThinkTime>,
+    /// The features each player finished
+    pub features: IndexMap<PlayerIdentifier, FeatureCounts>,
+    /// How the game's rules separate players tied on points
+    pub tie_break: TieBreak,
+}
+
+/// Time a player spent choosing their moves. Turns with no legal move aren't counted, as the
+/// player isn't asked
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThinkTime {
+    pub total: Duration,
+    pub moves: usize,
+}
+
+/// The features a player completed and scored during a game, and the fields they held when it ended.
+/// Shared features count for every player who scored them
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct FeatureCounts {
+    pub cities: usize,
+    pub roads: usize,
+    pub cloisters: usize,
+    pub farms: usize,
+}
+
+impl FeatureCounts {
+    /// The cities, roads and cloisters completed, leaving out farms
+    pub fn completed(&self) -> usize {
+        self.cities + self.roads + self.cloisters
+    }
+
+    pub(crate) fn count_completed(&mut self, region_type: &RegionType) {
+        match region_type {
+            RegionType::City => self.cities += 1,
+            RegionType::Road => self.roads += 1,
+            RegionType::Cloister => self.cloisters += 1,
+            RegionType::Field | RegionType::Water => {}
+        }
+    }
+}
+
+impl Add for FeatureCounts {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            cities: self.cities + rhs.cities,
+            roads: self.roads + rhs.roads,
+            cloisters: self.cloisters + rhs.cloisters,
+            farms: self.farms + rhs.farms,
+        }
+    }
+}
+
+impl ThinkTime {
+    pub fn mean(&self) -> Duration {
+        if self.moves == 0 {
+            return Duration::ZERO;
+        }
+
+        self.total / self.moves as u32
+    }
+}
+
+impl GameResult {
+    /// The players with the highest final score, or every member of the team with the highest
+    /// combined score in a team game. There is more than one winner when the game is tied and
+    /// its [TieBreak] can't separate them. Players who resigned can't win
+    pub fn winners(&self) -> Vec<PlayerIdentifier> {
+        if !self.teams.is_empty() {
+            let ranking = team_ranking(&self.teams, &self.score);
+
+            let Some(&(_, best)) = ranking.first() else {
+                return vec![];
+            };
+
+            return ranking
+                .iter()
+                .filter(|(_, points)| *points == best)
+                .flat_map(|(team, _)| team.players.iter().copied())
+                .collect();
+        }
+
+        let standing = || self.score.iter().filter(|(player_id, _)| !self.resigned.contains(player_id));
+
+        let Some(best) = standing().map(|(_, score)| *score).max() else {
+            return vec![];
+        };
+
+        let tied: Vec<_> = standing().filter(|(_, score)| **score == best).map(|(player_id, _)| *player_id).collect();
+
+        match self.tie_break {
+            TieBreak::Shared => tied,
+            TieBreak::MostCompletedFeatures => {
+                let completed = |player_id: &PlayerIdentifier| self.features.get(player_id).map_or(0, |features| features.completed());
+                let most = tied.iter().map(completed).max().unwrap_or(0);
+
+                tied.into_iter().filter(|player_id| completed(player_id) == most).collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SimulationReport {
+    pub players: IndexMap<PlayerIdentifier, Player>,
+    /// Name of the bot playing each player
+    pub bots: IndexMap<PlayerIdentifier, &'static str>,
+    pub games: Vec<GameResult>,
+    pub duration: Duration,
+}
+
+impl SimulationReport {
+    pub fn total_score(&self) -> Score {
+        self.games.iter().map(|game| &game.score).sum()
+    }
+
+    pub fn mean_score(&self, player_id: PlayerIdentifier) -> f64 {
+        if self.games.is_empty() {
+            return 0.0;
+        }
+
+        let total: i32 = self
+            .games
+            .iter()
+            .filter_map(|game| game.score.iter().find(|(id, _)| **id == player_id))
+            .map(|(_, score)| *score)
+            .sum();
+
+        total as f64 / self.games.len() as f64
+    }
+
+    /// The report as JSON for other tools to read: the configuration it was run with, every
+    /// game's result, and each player's totals
+    pub fn to_json(&self, config: &SimulationConfig) -> String {
+        let seed = |seed: &Seed| general_purpose::URL_SAFE.encode(seed);
+        let wins = self.win_counts();
+
+        let report = JsonReport {
+            config: JsonConfig {
+                seed: seed(&config.seed),
+                iterations: config.iterations,
+                include_river: config.include_river,
+                seating: config.seating,
+                early_abort: config.abort.is_some(),
+                meeple_per_player: config.rules.meeple_per_player,
+                players: config
+                    .players
+                    .values()
+                    .map(|BotPlayer { player, bot }| JsonPlayer {
+                        color: player.meeple_color,
+                        name: player.name.clone(),
+                        bot: bot.name(),
+                        starting_score: config.rules.starting_scores.get(&player.meeple_color).copied().unwrap_or(0),
+                        score_multiplier: config.rules.score_multipliers.get(&player.meeple_color).copied().unwrap_or(1.0),
+                    })
+                    .collect(),
+            },
+            games: self
+                .games
+                .iter()
+                .map(|game| JsonGame {
+                    seed: seed(&game.seed),
+                    score: game.score.to_named(&self.players),
+                    winners: game.winners(),
+                    duration_ms: millis(game.duration),
+                    placed_tile_count: game.placed_tile_count,
+                    turn_count: game.turn_count,
+                    mean_think_time_ms: game.think_time.iter().map(|(id, think_time)| JsonThinkTime { player: *id, ms: millis(think_time.mean()) }).collect(),
+                    resigned: game.resigned.clone(),
+                    aborted: game.aborted,
+                    features: game.features.iter().map(|(id, features)| JsonFeatures { player: *id, features: *features }).collect(),
+                })
+                .collect(),
+            totals: self
+                .players
+                .keys()
+                .map(|id| JsonTotals {
+                    player: *id,
+                    total_score: self.total_score().iter().find(|(player, _)| *player == id).map_or(0, |(_, points)| *points),
+                    mean_score: self.mean_score(*id),
+                    wins: wins.get(id).copied().unwrap_or(0),
+                    mean_think_time_ms: millis(self.think_time(*id).mean()),
+                    features: self.features(*id),
+                })
+                .collect(),
+            duration_ms: millis(self.duration),
+            mean_game_duration_ms: millis(self.mean_game_duration()),
+            allocator: crate::ALLOCATOR_NAME,
+        };
+
+        serde_json::to_string_pretty(&report).expect("report should serialize")
+    }
+
+    /// A row for each player in each game, with their score and the features they finished, for
+    /// spreadsheets
+    pub fn to_csv(&self) -> String {
+        let mut csv = "game,seed,player,name,score,won,cities,roads,cloisters,farms\n".to_string();
+
+        for (index, game) in self.games.iter().enumerate() {
+            let winners = game.winners();
+            let seed = general_purpose::URL_SAFE.encode(game.seed);
+
+            for NamedScore { name, color, points } in game.score.to_named(&self.players) {
+                let features = game.features.get(&color).copied().unwrap_or_default();
+
+                writeln!(
+                    csv,
+                    "{},{},{},{},{},{},{},{},{},{}",
+                    index, seed, color, csv_field(&name), points, winners.contains(&color), features.cities, features.roads, features.cloisters, features.farms
+                ).expect("writing to a string can't fail");
+            }
+        }
+
+        csv
+    }
+
+    /// The features a player finished, summed over every game
+    pub fn features(&self, player: PlayerIdentifier) -> FeatureCounts {
+        self.games.iter().filter_map(|game| game.features.get(&player)).fold(FeatureCounts::default(), |sum, features| sum + *features)
+    }
+
+    /// A player's think time summed over every game
+    pub fn think_time(&self, player: PlayerIdentifier) -> ThinkTime {
+        self.games
+            .iter()
+            .filter_map(|game| game.think_time.get(&player))
+            .fold(ThinkTime::default(), |sum, think_time| ThinkTime { total: sum.total + think_time.total, moves: sum.moves + think_time.moves })
+    }
+
+    /// All the players' think time in a phase of the game, summed over every game
+    pub fn phase_think_time(&self, phase: GamePhase) -> ThinkTime {
+        self.games
+            .iter()
+            .filter_map(|game| game.phase_think_time.get(&phase))
+            .fold(ThinkTime::default(), |sum, think_time| ThinkTime { total: sum.total + think_time.total, moves: sum.moves + think_time.moves })
+    }
+
+    pub fn mean_turn_count(&self) -> f64 {
+        if self.games.is_empty() {
+            return 0.0;
+        }
+
+        self.games.iter().map(|game| game.turn_count).sum::<usize>() as f64 / self.games.len() as f64
+    }
+
+    /// Count of games won by each player. Tied games count as a win for every tied player
+    pub fn win_counts(&self) -> IndexMap<PlayerIdentifier, usize> {
+        let mut wins: IndexMap<_, _> = self.players.keys().map(|id| (*id, 0)).collect();
+
+        for winner in self.games.iter().flat_map(|game| game.winners()) {
+            *wins.entry(winner).or_insert(0) += 1;
+        }
+
+        wins
+    }
+
+    pub fn mean_game_duration(&self) -> Duration {
+        if self.games.is_empty() {
+            return Duration::ZERO;
+        }
+
+        self.games.iter().map(|game| game.duration).sum::<Duration>() / self.games.len() as u32
+    }
+
+    /// The players ranked by mean score, each with their bot, mean score and win rate with its 95%
+    /// Wilson interval. Given an earlier report, each figure is followed by its change since then,
+    /// so a change to a bot that made it worse stands out
+    pub fn render_summary(&self, previous: Option<&SavedReport>) -> String {
+        let wins = self.win_counts();
+        let games = self.games.len();
+
+        let mut ranking: Vec<PlayerIdentifier> = self.players.keys().copied().collect();
+        ranking.sort_by(|a, b| self.mean_score(*b).total_cmp(&self.mean_score(*a)));
+
+        let mut summary = String::new();
+
+        for (rank, id) in ranking.into_iter().enumerate() {
+            let name = self.players[&id].name().map(str::to_string).unwrap_or_else(|| id.to_string());
+            let bot = self.bots.get(&id).copied().unwrap_or("unknown");
+
+            let mean_score = self.mean_score(id);
+            let player_wins = wins.get(&id).copied().unwrap_or(0);
+            let win_rate = player_wins as f64 / games.max(1) as f64;
+            let (low, high) = wilson_interval(player_wins, games);
+
+            let before = previous.and_then(|previous| previous.totals.get(&id).map(|totals| (totals, previous.games)));
+
+            let score_change = before.map(|(totals, _)| format!(" ({:+.1})", mean_score - totals.mean_score)).unwrap_or_default();
+            let win_rate_change = before
+                .map(|(totals, games)| format!(" ({:+.1}%)", (win_rate - totals.wins as f64 / games.max(1) as f64) * 100.0))
+                .unwrap_or_default();
+
+            writeln!(
+                summary,
+                "{}. {} ({}): mean score {:.1}{}, won {:.1}% [{:.1}%, {:.1}%]{}",
+                rank + 1,
+                name,
+                bot,
+                mean_score,
+                score_change,
+                win_rate * 100.0,
+                low * 100.0,
+                high * 100.0,
+                win_rate_change
+            )
+            .expect("writing to a string can't fail");
+        }
+
+        summary
+    }
+
+    /// How long a game took on average and the allocator it was played with. Given an earlier
+    /// report, it's compared with that run's, e.g. to see whether another allocator is faster
+    pub fn render_timing(&self, previous: Option<&SavedReport>) -> String {
+        let mean_ms = millis(self.mean_game_duration());
+        let mut timing = format!("Mean game: {:.2} ms with the {} allocator", mean_ms, crate::ALLOCATOR_NAME);
+
+        if let Some((previous_ms, previous)) = previous.and_then(|previous| previous.mean_game_duration_ms.map(|ms| (ms, previous))) {
+            let allocator = previous.allocator.as_deref().unwrap_or("unknown");
+            let change = (mean_ms - previous_ms) / previous_ms.max(f64::EPSILON) * 100.0;
+
+            write!(timing, " ({:+.1}% on {:.2} ms with the {} allocator)", change, previous_ms, allocator).expect("writing to a string can't fail");
+        }
+
+        timing
+    }
+}
+
+/// The 95% Wilson score interval for the rate of `successes` in `trials`, which unlike the normal
+/// approximation stays between 0 and 1 and holds up for small batches and lopsided rates
+pub fn wilson_interval(successes: usize, trials: usize) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+
+    const Z: f64 = 1.96;
+
+    let n = trials as f64;
+    let p = successes as f64 / n;
+
+    let centre = p + Z * Z / (2.0 * n);
+    let spread = Z * (p * (1.0 - p) / n + Z * Z / (4.0 * n * n)).sqrt();
+    let denominator = 1.0 + Z * Z / n;
+
+    (((centre - spread) / denominator).max(0.0), ((centre + spread) / denominator).min(1.0))
+}
+
+/// The totals from a report written by [SimulationReport::to_json], to compare a later run with
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedReport {
+    /// Games played in the run
+    pub games: usize,
+    pub totals: IndexMap<PlayerIdentifier, SavedTotals>,
+    /// `None` for reports written before they were timed
+    pub mean_game_duration_ms: Option<f64>,
+    /// `None` for reports written before the allocator was recorded
+    pub allocator: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct SavedTotals {
+    pub mean_score: f64,
+    pub wins: usize,
+}
+
+#[derive(Debug)]
+pub enum SavedReportError {
+    Io(std::io::Error),
+    /// Not a report written by [SimulationReport::to_json]
+    Invalid(serde_json::Error),
+}
+
+impl From<std::io::Error> for SavedReportError {
+    fn from(error: std::io::Error) -> Self {
+        SavedReportError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for SavedReportError {
+    fn from(error: serde_json::Error) -> Self {
+        SavedReportError::Invalid(error)
+    }
+}
+
+impl SavedReport {
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, SavedReportError> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SavedReportError> {
+        let saved: SavedJson = serde_json::from_str(json)?;
+
+        Ok(Self {
+            games: saved.games.len(),
+            totals: saved.totals.into_iter().map(|totals| (totals.player, totals.totals)).collect(),
+            mean_game_duration_ms: saved.mean_game_duration_ms,
+            allocator: saved.allocator,
+        })
+    }
+}
+
+/// The parts of [JsonReport] a [SavedReport] reads back
+#[derive(Deserialize)]
+struct SavedJson {
+    games: Vec<serde::de::IgnoredAny>,
+    totals: Vec<SavedPlayerTotals>,
+    #[serde(default)]
+    mean_game_duration_ms: Option<f64>,
+    #[serde(default)]
+    allocator: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SavedPlayerTotals {
+    player: PlayerIdentifier,
+    #[serde(flatten)]
+    totals: SavedTotals,
+}
+
+#[derive(Serialize)]
+struct JsonReport {
+    config: JsonConfig,
+    games: Vec<JsonGame>,
+    totals: Vec<JsonTotals>,
+    duration_ms: f64,
+    mean_game_duration_ms: f64,
+    /// See [crate::ALLOCATOR_NAME], so runs built with different allocators can be compared
+    allocator: &'static str,
+}
+
+#[derive(Serialize)]
+struct JsonConfig {
+    seed: String,
+    iterations: usize,
+    include_river: bool,
+    seating: Seating,
+    /// Whether games were ended early by an abort condition, which can't itself be written out
+    early_abort: bool,
+    meeple_per_player: usize,
+    players: Vec<JsonPlayer>,
+}
+
+#[derive(Serialize)]
+struct JsonPlayer {
+    color: PlayerIdentifier,
+    name: Option<String>,
+    bot: &'static str,
+    starting_score: i32,
+    score_multiplier: f64,
+}
+
+#[derive(Serialize)]
+struct JsonGame {
+    seed: String,
+    score: Vec<NamedScore>,
+    winners: Vec<PlayerIdentifier>,
+    duration_ms: f64,
+    placed_tile_count: usize,
+    turn_count: usize,
+    mean_think_time_ms: Vec<JsonThinkTime>,
+    resigned: Vec<PlayerIdentifier>,
+    aborted: bool,
+    features: Vec<JsonFeatures>,
+}
+
+#[derive(Serialize)]
+struct JsonTotals {
+    player: PlayerIdentifier,
+    total_score: i32,
+    mean_score: f64,
+    wins: usize,
+    mean_think_time_ms: f64,
+    features: FeatureCounts,
+}
+
+#[derive(Serialize)]
+struct JsonFeatures {
+    player: PlayerIdentifier,
+    #[serde(flatten)]
+    features: FeatureCounts,
+}
+
+#[derive(Serialize)]
+struct JsonThinkTime {
+    player: PlayerIdentifier,
+    ms: f64,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// The value quoted if it would otherwise be read as more than one field, or a quote
+fn csv_field(value: &str) -> String {
+    match value.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+pub fn run(config: SimulationConfig) -> SimulationReport {
+    run_observed(config, &mut [])
+}
+
+/// As [run], notifying each observer as games complete
+pub fn run_observed(config: SimulationConfig, observers: &mut [Box<dyn GameObserver>]) -> SimulationReport {
+    SimulationHandle::new(config).run_observed(observers)
+}
+
+/// Stops a running simulation. Clones share the same flag, so one can be kept, or handed to
+/// another thread, while the simulation runs
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// How far through a simulation is, given to the progress callback after each game
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimulationProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub elapsed: Duration,
+}
+
+impl SimulationProgress {
+    /// Time left if the remaining games take as long as the ones so far, once any have completed
+    pub fn eta(&self) -> Option<Duration> {
+        (self.completed > 0).then(|| self.elapsed.mul_f64((self.total - self.completed) as f64 / self.completed as f64))
+    }
+}
+
+pub type ProgressCallback = Box<dyn FnMut(&SimulationProgress) + Send>;
+
+/// A simulation for embedding in another application, which can be told how it is getting on and
+/// stop it early without killing the process
+pub struct SimulationHandle {
+    config: SimulationConfig,
+    cancellation: CancellationToken,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl SimulationHandle {
+    pub fn new(config: SimulationConfig) -> Self {
+        Self { config, cancellation: CancellationToken::default(), on_progress: None }
+    }
+
+    /// Called after each game completes
+    pub fn on_progress<F: FnMut(&SimulationProgress) + Send + 'static>(mut self, callback: F) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Cancelling the token ends the game being played at its next turn, marked as aborted, and
+    /// no more are started
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancellation.clone()
+    }
+
+    pub fn run(self) -> SimulationReport {
+        self.run_observed(&mut [])
+    }
+
+    /// Plays the games until they are all done or the simulation is cancelled, reporting those
+    /// that were played
+    pub fn run_observed(mut self, observers: &mut [Box<dyn GameObserver>]) -> SimulationReport {
+        let config = self.config;
+        let game_count = config.game_count();
+        let orders_per_deck = config.seating.orders_per_deck(config.players.len());
+        let players = config.players;
+
+        let cancellation = self.cancellation;
+        let abort: AbortCondition = {
+            let cancellation = cancellation.clone();
+            let condition = config.abort;
+            Arc::new(move |score| cancellation.is_cancelled() || condition.as_ref().is_some_and(|condition| condition(score)))
+        };
+
+        let mut seed_rng = GameRng::from_seed(config.seed);
+        // seats are shuffled with their own generator so the games' seeds don't depend on the seating
+        let mut seating_rng = GameRng::from_rng(&mut seed_rng.clone()).expect("should seed from another generator");
+
+        let now = Instant::now();
+        let mut games = vec![];
+        let mut seed = config.seed;
+
+        for game_index in 0..game_count {
+            if cancellation.is_cancelled() {
+                break;
+            }
+
+            // every seat order of a deck is played before moving on to the next
+            if game_index % orders_per_deck == 0 {
+                seed = seed_rng.gen();
+            }
+
+            let expansions = if config.include_river { vec![Expansion::River] } else { vec![] };
+
+            let result = Game::new(config.seating.seat(&players, game_index, &mut seating_rng), seed, expansions)
+                .with_rules(config.rules.clone())
+                .expect("the simulation's rules should be for as many players as it has")
+                .aborting_when(Some(abort.clone()))
+                .play_observed(game_index, observers);
+
+            games.push(result);
+
+            if let Some(on_progress) = &mut self.on_progress {
+                on_progress(&SimulationProgress { completed: games.len(), total: game_count, elapsed: now.elapsed() });
+            }
+        }
+
+        SimulationReport {
+            bots: players.iter().map(|(id, BotPlayer { bot, .. })| (*id, bot.name())).collect(),
+            players: players.into_iter().map(|(id, BotPlayer { player, .. })| (id, player)).collect(),
+            games,
+            duration: now.elapsed(),
+        }
+    }
+}
+
+pub fn play_game(players: IndexMap<PlayerIdentifier, BotPlayer>, seed: Seed, include_river: bool) -> GameResult {
+    play_game_observed(players, seed, include_river, 0, &mut [])
+}
+
+pub fn play_game_observed(
+    players: IndexMap<PlayerIdentifier, BotPlayer>,
+    seed: Seed,
+    include_river: bool,
+    game_index: usize,
+    observers: &mut [Box<dyn GameObserver>],
+) -> GameResult {
+    let expansions = if include_river { vec![Expansion::River] } else { vec![] };
+
+    Game::new(players, seed, expansions).play_observed(game_index, observers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+    use crate::player::MeepleColor;
+
+    fn lazy_bots() -> Vec<BotPlayer> {
+        vec![
+            Player::red().with_bot(BotStrategy::Lazy(LazyBot)),
+            Player::green().with_bot(BotStrategy::Lazy(LazyBot)),
+        ]
+    }
+
+    #[test]
+    fn should_report_a_result_for_every_game() {
+        let report = run(SimulationConfig::new(lazy_bots(), 2, [0; 32]).unwrap().with_river(false));
+
+        assert_eq!(report.games.len(), 2);
+        assert_ne!(report.games[0].seed, report.games[1].seed);
+
+        for game in &report.games {
+            assert!(game.placed_tile_count > 0);
+            assert!(!game.winners().is_empty());
+        }
+
+        assert_eq!(report.win_counts().len(), 2);
+
+        for game in &report.games {
+            assert!(game.turn_count >= game.placed_tile_count);
+            assert_eq!(game.think_time.len(), 2);
+
+            // every tile placed was a move someone was asked for
+            let moves: usize = game.think_time.values().map(|think_time| think_time.moves).sum();
+            assert_eq!(moves, game.placed_tile_count);
+        }
+
+        assert!(report.think_time(MeepleColor::Red).moves > 0);
+        assert!(report.mean_turn_count() > 0.0);
+    }
+
+    #[test]
+    fn should_write_the_config_and_every_result_as_json() {
+        let config = SimulationConfig::new(lazy_bots(), 2, [0; 32]).unwrap().with_river(false).with_seating(Seating::Rotate);
+        let report = run(config.clone());
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json(&config)).unwrap();
+
+        assert_eq!(json["config"]["iterations"], 2);
+        assert_eq!(json["config"]["seating"], "rotate");
+        assert_eq!(json["config"]["players"][0]["bot"], "lazy");
+        assert_eq!(json["games"].as_array().unwrap().len(), 2);
+        assert_eq!(json["games"][0]["seed"], general_purpose::URL_SAFE.encode(report.games[0].seed));
+        assert_eq!(json["games"][0]["placed_tile_count"], report.games[0].placed_tile_count);
+        assert_eq!(json["games"][0]["score"][0]["name"], "Red");
+        assert_eq!(json["games"][0]["score"][0]["color"], "Red");
+        assert_eq!(json["totals"][0]["player"], "Red");
+        assert_eq!(json["totals"][0]["mean_score"], report.mean_score(MeepleColor::Red));
+    }
+
+    #[test]
+    fn should_report_progress_after_each_game() {
+        let progress = Arc::new(std::sync::Mutex::new(vec![]));
+        let seen = Arc::clone(&progress);
+
+        let report = SimulationHandle::new(SimulationConfig::new(lazy_bots(), 3, [0; 32]).unwrap().with_river(false))
+            .on_progress(move |progress| seen.lock().unwrap().push(*progress))
+            .run();
+
+        let progress = progress.lock().unwrap();
+
+        assert_eq!(report.games.len(), 3);
+        assert_eq!(progress.iter().map(|progress| progress.completed).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(progress.iter().all(|progress| progress.total == 3));
+        assert_eq!(progress[2].eta(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn should_stop_once_cancelled() {
+        let handle = SimulationHandle::new(SimulationConfig::new(lazy_bots(), 5, [0; 32]).unwrap().with_river(false));
+        let cancellation = handle.cancellation_token();
+
+        let report = handle.on_progress(move |progress| if progress.completed == 2 { cancellation.cancel() }).run();
+
+        assert_eq!(report.games.len(), 2);
+        assert!(report.games.iter().all(|game| !game.aborted));
+    }
+
+    #[test]
+    fn should_abort_the_game_in_progress_when_cancelled() {
+        let handle = SimulationHandle::new(SimulationConfig::new(lazy_bots(), 5, [0; 32]).unwrap().with_river(false));
+        handle.cancellation_token().cancel();
+
+        assert!(handle.run().games.is_empty());
+
+        let cancellation = CancellationToken::default();
+        let token = cancellation.clone();
+        let config = SimulationConfig::new(lazy_bots(), 5, [0; 32])
+            .unwrap()
+            .with_river(false)
+            .with_early_abort(Arc::new(move |_| {
+                token.cancel();
+                false
+            }));
+
+        let handle = SimulationHandle { cancellation, ..SimulationHandle::new(config) };
+        let report = handle.run();
+
+        assert_eq!(report.games.len(), 1);
+        assert!(report.games[0].aborted);
+    }
+
+    #[test]
+    fn should_reject_players_sharing_a_meeple_colour() {
+        let players = [Player::red().with_name("Alice"), Player::red().with_name("Bob")].map(|player| player.with_bot(BotStrategy::Lazy(LazyBot)));
+
+        assert!(matches!(SimulationConfig::new(players, 1, [0; 32]), Err(GameBuildError::DuplicatePlayer(MeepleColor::Red))));
+        assert!(matches!(SimulationConfig::new([], 1, [0; 32]), Err(GameBuildError::NoPlayers)));
+    }
+
+    #[test]
+    fn should_reject_rules_for_another_number_of_players() {
+        let three = [Player::red(), Player::green(), Player::blue()].map(|player| player.with_bot(BotStrategy::Lazy(LazyBot)));
+        let config = SimulationConfig::new(three, 1, [0; 32]).unwrap();
+
+        assert!(matches!(config.with_rules(RuleSet::wtcoc()), Err(GameBuildError::WrongPlayerCount(3))));
+        assert!(SimulationConfig::new(lazy_bots(), 1, [0; 32]).unwrap().with_rules(RuleSet::wtcoc()).is_ok());
+    }
+
+    #[test]
+    fn should_aggregate_scores_and_wins_across_games() {
+        let alice = Player::red().with_name("Smith, Alice");
+        let bob = Player::green();
+
+        let game = |alice_score, bob_score| GameResult {
+            seed: [0; 32],
+            score: Score::from_iter([(&alice, alice_score), (&bob, bob_score)]),
+            duration: Duration::from_millis(10),
+            placed_tile_count: 72,
+            teams: vec![],
+            resigned: vec![],
+            aborted: false,
+            turn_count: 72,
+            think_time: IndexMap::new(),
+            phase_think_time: IndexMap::new(),
+            features: [(alice.meeple_color, FeatureCounts { cities: 2, roads: 1, cloisters: 0, farms: 1 })].into_iter().collect(),
+            tie_break: TieBreak::Shared,
+        };
+
+        let report = SimulationReport {
+            players: [(alice.meeple_color, alice.clone()), (bob.meeple_color, bob.clone())].into_iter().collect(),
+            bots: [(alice.meeple_color, "lazy"), (bob.meeple_color, "myopic")].into_iter().collect(),
+            games: vec![game(10, 4), game(6, 6), game(2, 8)],
+            duration: Duration::from_millis(30),
+        };
+
+        assert_eq!(report.total_score(), Score::from_iter([(&alice, 18), (&bob, 18)]));
+        assert_eq!(report.mean_score(alice.meeple_color), 6.0);
+        assert_eq!(report.win_counts().get(&alice.meeple_color), Some(&2));
+        assert_eq!(report.win_counts().get(&bob.meeple_color), Some(&2));
+        assert_eq!(report.mean_game_duration(), Duration::from_millis(10));
+        assert_eq!(report.features(alice.meeple_color), FeatureCounts { cities: 6, roads: 3, cloisters: 0, farms: 3 });
+        assert_eq!(report.features(bob.meeple_color), FeatureCounts::default());
+
+        let csv = report.to_csv();
+        let rows: Vec<_> = csv.lines().collect();
+
+        assert_eq!(rows.len(), 1 + 3 * 2);
+        assert_eq!(rows[0], "game,seed,player,name,score,won,cities,roads,cloisters,farms");
+        assert!(rows[1].starts_with("0,") && rows[1].ends_with(",Red,\"Smith, Alice\",10,true,2,1,0,1"), "{}", rows[1]);
+        assert!(rows[6].ends_with(",Green,Green,8,true,0,0,0,0"));
+    }
+
+    #[test]
+    fn should_break_ties_by_completed_features_when_the_rules_say() {
+        let alice = Player::red();
+        let bob = Player::green();
+
+        let mut game = GameResult {
+            seed: [0; 32],
+            score: Score::from_iter([(&alice, 30), (&bob, 30)]),
+            duration: Duration::ZERO,
+            placed_tile_count: 72,
+            teams: vec![],
+            resigned: vec![],
+            aborted: false,
+            turn_count: 72,
+            think_time: IndexMap::new(),
+            phase_think_time: IndexMap::new(),
+            features: [
+                (alice.meeple_color, FeatureCounts { cities: 1, roads: 1, cloisters: 0, farms: 3 }),
+                (bob.meeple_color, FeatureCounts { cities: 2, roads: 0, cloisters: 1, farms: 0 }),
+            ]
+            .into_iter()
+            .collect(),
+            tie_break: TieBreak::Shared,
+        };
+
+        assert_eq!(game.winners(), vec![alice.meeple_color, bob.meeple_color]);
+
+        game.tie_break = TieBreak::MostCompletedFeatures;
+        assert_eq!(game.winners(), vec![bob.meeple_color]);
+
+        // farms don't count towards the tie break
+        game.features[&bob.meeple_color].cloisters = 0;
+        assert_eq!(game.winners(), vec![alice.meeple_color, bob.meeple_color]);
+    }
+
+    #[test]
+    fn should_bracket_the_win_rate_between_zero_and_one() {
+        let (low, high) = wilson_interval(5, 10);
+
+        assert!((low - 0.2366).abs() < 0.001 && (high - 0.7634).abs() < 0.001);
+        assert_eq!(wilson_interval(0, 10).0, 0.0);
+        assert_eq!(wilson_interval(10, 10).1, 1.0);
+        assert_eq!(wilson_interval(0, 0), (0.0, 1.0));
+    }
+
+    #[test]
+    fn should_rank_players_and_show_changes_since_a_saved_report() {
+        let config = SimulationConfig::new(lazy_bots(), 4, [0; 32]).unwrap();
+        let report = run(config.clone());
+
+        let summary = report.render_summary(None);
+        let lines: Vec<_> = summary.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("1. ") && lines[0].contains("(lazy): mean score "));
+        assert!(!summary.contains("(+"));
+
+        let previous = SavedReport::from_json(&report.to_json(&config)).unwrap();
+
+        assert_eq!(previous.games, 4);
+        assert_eq!(report.render_summary(Some(&previous)).matches("(+0.0").count(), 4);
+        assert!(matches!(SavedReport::from_json("{}"), Err(SavedReportError::Invalid(_))));
+    }
+
+    #[test]
+    fn should_compare_game_times_with_a_saved_report() {
+        let config = SimulationConfig::new(lazy_bots(), 2, [0; 32]).unwrap();
+        let report = run(config.clone());
+
+        let mut previous = SavedReport::from_json(&report.to_json(&config)).unwrap();
+        assert_eq!(previous.allocator.as_deref(), Some(crate::ALLOCATOR_NAME));
+
+        assert!(!report.render_timing(None).contains('%'));
+
+        previous.mean_game_duration_ms = Some(millis(report.mean_game_duration()) * 2.0);
+        previous.allocator = Some("mimalloc".to_string());
+
+        let timing = report.render_timing(Some(&previous));
+        assert!(timing.contains("(-50.0% on "));
+        assert!(timing.ends_with("with the mimalloc allocator)"));
+    }
+
+    #[test]
+    fn should_average_think_time_over_moves() {
+        let think_time = ThinkTime { total: Duration::from_millis(30), moves: 3 };
+
+        assert_eq!(think_time.mean(), Duration::from_millis(10));
+        assert_eq!(ThinkTime::default().mean(), Duration::ZERO);
+    }
+
+    #[test]
+    fn should_abort_once_the_score_gap_is_too_wide() {
+        let alice = Player::red();
+        let bob = Player::green();
+
+        let abort = score_gap_exceeds(10);
+
+        assert!(!abort(&Score::from_iter([(&alice, 20), (&bob, 10)])));
+        assert!(abort(&Score::from_iter([(&alice, 21), (&bob, 10)])));
+        assert!(!abort(&Score::new()));
+    }
+
+    #[test]
+    fn should_seat_players_for_each_game() {
+        let players: IndexMap<_, _> = [MeepleColor::Red, MeepleColor::Green, MeepleColor::Blue].into_iter().map(|id| (id, ())).collect();
+        let mut rng = GameRng::seed_from_u64(0);
+
+        let order = |seating: Seating, game_index, rng: &mut GameRng| seating.seat(&players, game_index, rng).keys().copied().collect::<Vec<_>>();
+
+        assert_eq!(order(Seating::Fixed, 4, &mut rng), vec![MeepleColor::Red, MeepleColor::Green, MeepleColor::Blue]);
+        assert_eq!(order(Seating::Rotate, 4, &mut rng), vec![MeepleColor::Green, MeepleColor::Blue, MeepleColor::Red]);
+
+        let mut shuffled = order(Seating::Shuffle, 0, &mut rng);
+        shuffled.sort_by_key(|id| players.get_index_of(id));
+
+        assert_eq!(shuffled, vec![MeepleColor::Red, MeepleColor::Green, MeepleColor::Blue]);
+    }
+
+    #[test]
+    fn should_play_each_deck_in_every_seat_order_when_permuting() {
+        let players: IndexMap<_, _> = [MeepleColor::Red, MeepleColor::Green, MeepleColor::Blue].into_iter().map(|id| (id, ())).collect();
+        let mut rng = GameRng::seed_from_u64(0);
+
+        let orders: Vec<_> = (0..6).map(|game_index| Seating::Permute.seat(&players, game_index, &mut rng).keys().copied().collect::<Vec<_>>()).collect();
+        assert_eq!(orders[1], vec![MeepleColor::Red, MeepleColor::Blue, MeepleColor::Green]);
+
+        for (index, order) in orders.iter().enumerate() {
+            assert!(!orders[..index].contains(order), "{:?} played twice", order);
+        }
+
+        // past three players, each only starts once
+        assert_eq!(Seating::Permute.orders_per_deck(4), 4);
+
+        let config = SimulationConfig::new(lazy_bots(), 2, [0; 32]).unwrap().with_river(false).with_seating(Seating::Permute);
+        assert_eq!(config.game_count(), 4);
+
+        let report = run(config);
+        let seeds: Vec<_> = report.games.iter().map(|game| game.seed).collect();
+
+        assert_eq!(seeds.len(), 4);
+        assert_eq!(seeds[0], seeds[1]);
+        assert_eq!(seeds[2], seeds[3]);
+        assert_ne!(seeds[0], seeds[2]);
+    }
+}
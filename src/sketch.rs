@@ -0,0 +1,201 @@
+use crate::board::{Board, InvalidTilePlacement};
+use crate::tile::{PlacedTile, Rotation, TileDefinition};
+use crate::tile_definitions;
+use std::fmt::{Display, Formatter};
+
+/// Why a sketch couldn't be laid out as a board. Lines count from 1, comments included
+#[derive(Debug, Clone, PartialEq)]
+pub enum SketchError {
+    /// The sketch has no tiles in it
+    Empty,
+    /// No tile definition matches the cell's tile
+    UnknownTile { line: usize, cell: String },
+    /// The cell's rotation isn't one of 0, 90, 180 or 270
+    InvalidRotation { line: usize, cell: String },
+    /// The sketch is wider or taller than a board can hold
+    TooLarge { line: usize },
+    /// No edge of the cell's tile touches the tiles laid out before it
+    Disconnected { line: usize, cell: String },
+    /// The cell's tile doesn't fit the tiles around it
+    InvalidPlacement { line: usize, cell: String, reason: InvalidTilePlacement },
+}
+
+impl Display for SketchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SketchError::Empty => write!(f, "the sketch has no tiles"),
+            SketchError::UnknownTile { line, cell } => write!(f, "line {}: no tile matches [{}]", line, cell),
+            SketchError::InvalidRotation { line, cell } => write!(f, "line {}: [{}] should be turned 0, 90, 180 or 270", line, cell),
+            SketchError::TooLarge { line } => write!(f, "line {}: the sketch is too large for a board", line),
+            SketchError::Disconnected { line, cell } => write!(f, "line {}: [{}] doesn't touch the rest of the board", line, cell),
+            SketchError::InvalidPlacement { line, cell, reason } => write!(f, "line {}: [{}] {}", line, cell, reason),
+        }
+    }
+}
+
+/// A tile drawn in a sketch, with where it was drawn for errors to point at
+struct SketchedTile {
+    tile: PlacedTile,
+    line: usize,
+    cell: String,
+}
+
+/// Reads a board drawn as a grid of tiles, one row of the board to a line, with each tile given
+/// by its id or name (words joined with `-`) and how far it is turned clockwise, e.g.
+/// `side-city@90`. A `.` leaves the square empty, and lines starting with `#` are comments:
+///
+/// ```text
+/// # the Count's city
+/// corner-city@270  corner-city
+/// corner-city@180  corner-city@90
+/// ```
+///
+/// The first row is `y = 0` and its first square `x = 0`, with `y` growing down the board. Tiles
+/// are returned in an order they can be placed in, each touching one before it
+pub fn parse(sketch: &str) -> Result<Vec<PlacedTile>, SketchError> {
+    Ok(read(sketch)?.into_iter().map(|sketched| sketched.tile).collect())
+}
+
+/// The tiles drawn in the sketch with where they were drawn, in placing order
+fn read(sketch: &str) -> Result<Vec<SketchedTile>, SketchError> {
+    let mut drawn = vec![];
+    let mut row = 0;
+
+    for (index, text) in sketch.lines().enumerate() {
+        let line = index + 1;
+        let text = text.trim();
+
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        for (column, cell) in text.split_whitespace().enumerate() {
+            if cell == "." {
+                continue;
+            }
+
+            let (tile, rotation) = read_cell(cell, line)?;
+            let (x, y) = (i8::try_from(column).map_err(|_| SketchError::TooLarge { line })?, i8::try_from(row).map_err(|_| SketchError::TooLarge { line })?);
+
+            drawn.push(SketchedTile { tile: PlacedTile::new(tile, x, y, rotation), line, cell: cell.to_string() });
+        }
+
+        row += 1;
+    }
+
+    in_placing_order(drawn)
+}
+
+/// The tile and clockwise quarter turns a cell, such as `side-city@90`, stands for
+pub(crate) fn read_cell(cell: &str, line: usize) -> Result<(&'static TileDefinition, Rotation), SketchError> {
+    let (name, degrees) = cell.split_once('@').unwrap_or((cell, "0"));
+    let tile = tile_definitions::find(name).ok_or_else(|| SketchError::UnknownTile { line, cell: cell.to_string() })?;
+
+    let rotation = degrees
+        .parse()
+        .ok()
+        .and_then(Rotation::from_degrees)
+        .ok_or_else(|| SketchError::InvalidRotation { line, cell: cell.to_string() })?;
+
+    Ok((tile, rotation))
+}
+
+/// The tiles reordered so each touches one before it, starting from the first drawn and taking
+/// the earliest drawn of those that can go next
+fn in_placing_order(mut unplaced: Vec<SketchedTile>) -> Result<Vec<SketchedTile>, SketchError> {
+    if unplaced.is_empty() {
+        return Err(SketchError::Empty);
+    }
+
+    let mut placed = vec![unplaced.remove(0)];
+
+    while !unplaced.is_empty() {
+        let touching = unplaced.iter().position(|candidate| {
+            let at = candidate.tile.placement.coordinate;
+            placed.iter().any(|placed| (placed.tile.placement.coordinate.x - at.x).abs() + (placed.tile.placement.coordinate.y - at.y).abs() == 1)
+        });
+
+        match touching {
+            Some(index) => placed.push(unplaced.remove(index)),
+            None => {
+                let SketchedTile { line, cell, .. } = unplaced.remove(0);
+                return Err(SketchError::Disconnected { line, cell });
+            }
+        }
+    }
+
+    Ok(placed)
+}
+
+impl Board {
+    /// The board drawn in `sketch` (see [parse]), each tile checked to fit those around it as it is
+    /// placed, so a sketch of a position that couldn't come up in a game is caught with the tile
+    /// at fault
+    pub fn from_sketch(sketch: &str) -> Result<Board, SketchError> {
+        let mut board = Board::new();
+
+        for SketchedTile { tile, line, cell } in read(sketch)? {
+            board.place_tile(tile).map_err(|reason| SketchError::InvalidPlacement { line, cell, reason })?;
+        }
+
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::StartingLayout;
+
+    const COUNT_CITY: &str = "
+        # the Count's city
+        corner-city@270  corner-city
+
+        corner-city@180  corner-city@90
+    ";
+
+    #[test]
+    fn should_read_the_tiles_drawn_in_a_sketch() {
+        let mut layout = StartingLayout::count_city().tiles().to_vec();
+        // the layout goes round the city, where a sketch is read a row at a time
+        layout.swap(2, 3);
+
+        assert_eq!(parse(COUNT_CITY), Ok(layout));
+    }
+
+    #[test]
+    fn should_place_tiles_drawn_out_of_order_once_they_touch_the_board() {
+        let tiles = parse("straight-road@90  .  cross-intersection\nstraight-road@90  straight-road@90  straight-road@90").unwrap();
+        let order: Vec<_> = tiles.iter().map(|tile| (tile.placement.coordinate.x, tile.placement.coordinate.y)).collect();
+
+        assert_eq!(order, [(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn should_build_the_board_drawn_in_a_sketch() {
+        let board = Board::from_sketch(COUNT_CITY).unwrap();
+        let layout = Board::new_with_tiles(StartingLayout::count_city().tiles().to_vec()).unwrap();
+
+        assert_eq!(board.placed_tile_count(), 4);
+        assert_eq!(board.zobrist_hash(), layout.zobrist_hash());
+    }
+
+    #[test]
+    fn should_point_at_the_cell_a_sketch_goes_wrong_in() {
+        assert_eq!(parse("# nothing here\n. ."), Err(SketchError::Empty));
+        assert_eq!(parse("corner-city\n\ncorner-castle"), Err(SketchError::UnknownTile { line: 3, cell: "corner-castle".to_string() }));
+        assert_eq!(parse("corner-city@45"), Err(SketchError::InvalidRotation { line: 1, cell: "corner-city@45".to_string() }));
+        assert_eq!(parse("corner-city  .  corner-city@90"), Err(SketchError::Disconnected { line: 1, cell: "corner-city@90".to_string() }));
+    }
+
+    #[test]
+    fn should_reject_a_tile_that_doesnt_fit_those_around_it() {
+        let error = Board::from_sketch("corner-city@270  corner-city\ncorner-city@180  corner-city@270").unwrap_err();
+
+        assert!(
+            matches!(&error, SketchError::InvalidPlacement { line: 2, cell, .. } if cell == "corner-city@270"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,1130 @@
+use crate::board::Board;
+use crate::game_logic::GameBuildError;
+use crate::locale::Language;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::{MeepleColor, PlayerIdentifier, RegionIndex};
+use crate::replay::{Replay, ReplayError, ReplayPlacement, ReplayRecall, ReplayState, ReplayTurn};
+use crate::score::Score;
+use crate::tile::{BoardCoordinate, RegionType, TileDefinition, TilePlacement};
+use crate::rules::RuleSet;
+use crate::simulate::{self, SimulationConfig, SimulationReport};
+use indexmap::{IndexMap, IndexSet};
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// Rows of the score chart, from no points up to the highest score
+const CHART_HEIGHT: usize = 12;
+/// Most columns of the score chart; longer games have turns left out to fit
+const CHART_WIDTH: usize = 72;
+
+/// Each player's running score over a game, recorded as it is played
+#[derive(Debug, Clone, Default)]
+pub struct ScoreTimeline {
+    /// Players in turn order
+    pub players: Vec<PlayerIdentifier>,
+    /// Everyone's score after each turn, then the final score once the open features are counted
+    pub scores: Vec<Score>,
+    /// Everyone's score before the first turn, which handicaps can start above 0
+    starting_score: Score,
+}
+
+impl GameObserver for ScoreTimeline {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { players, rules, .. } => {
+                self.players = players.iter().map(|(color, _, _)| *color).collect();
+                self.starting_score = self.players.iter().map(|color| (*color, rules.starting_scores.get(color).copied().unwrap_or(0))).collect();
+                self.scores.clear();
+            }
+            GameEvent::TurnCompleted { score_delta, .. } => {
+                let mut score = self.scores.last().cloned().unwrap_or_else(|| self.starting_score.clone());
+                score += score_delta;
+                self.scores.push(score);
+            }
+            GameEvent::GameCompleted { score, .. } => self.scores.push(score.clone()),
+            GameEvent::RegionCompleted { .. } | GameEvent::MeepleRecalled { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+/// A line chart of each player's score by turn, drawn with the first letter of their colour, and
+/// `*` where players' lines cross
+pub fn render_score_chart(timeline: &ScoreTimeline) -> String {
+    let points = |score: &Score, player: &PlayerIdentifier| score.iter().find(|(id, _)| *id == player).map(|(_, points)| *points).unwrap_or(0);
+
+    let turns = timeline.scores.len();
+    let width = turns.clamp(1, CHART_WIDTH);
+    let highest = timeline.scores.iter().flat_map(|score| score.iter().map(|(_, points)| *points)).max().unwrap_or(0).max(1);
+
+    let mut grid = vec![vec![' '; width]; CHART_HEIGHT];
+
+    for player in &timeline.players {
+        for (column, cells) in (0..width).map(|column| (column, column * turns.saturating_sub(1) / (width - 1).max(1))) {
+            let Some(score) = timeline.scores.get(cells) else {
+                continue;
+            };
+
+            let height = (points(score, player).max(0) as usize * (CHART_HEIGHT - 1) + highest as usize / 2) / highest as usize;
+            let cell = &mut grid[CHART_HEIGHT - 1 - height][column];
+
+            *cell = if *cell == ' ' { marker(player) } else { '*' };
+        }
+    }
+
+    let label_width = highest.to_string().len();
+    let mut chart = String::new();
+
+    for (row, cells) in grid.iter().enumerate() {
+        let label = match row {
+            0 => highest.to_string(),
+            _ if row == CHART_HEIGHT - 1 => "0".to_string(),
+            _ => String::new(),
+        };
+
+        chart.push_str(&format!("{:>label_width$} │{}\n", label, cells.iter().collect::<String>()));
+    }
+
+    chart.push_str(&format!("{:>label_width$} └{}\n", "", "─".repeat(width)));
+    chart.push_str(&format!("{:>label_width$}  turn 1{:>pad$}\n", "", turns, pad = width.saturating_sub(6).max(2)));
+
+    let legend: Vec<String> = timeline.players.iter().map(|player| format!("{} {:?}", marker(player), player)).collect();
+    chart.push_str(&format!("{:>label_width$}  {}\n", "", legend.join("  ")));
+
+    chart
+}
+
+/// How a player used their meeple, over one or more games
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeepleUsage {
+    /// Meeple placed on the board
+    pub deployments: usize,
+    /// Turns each meeple spent on the board, summed over the meeple
+    pub deployed_turns: usize,
+    /// Turns each meeple spent in hand, summed over the meeple
+    pub idle_turns: usize,
+    /// Points each deployment earned its player, once it came off the board or the game ended
+    pub deployment_points: Vec<u32>,
+}
+
+impl MeepleUsage {
+    /// Share of meeple turns spent on the board
+    pub fn utilisation(&self) -> f64 {
+        match self.deployed_turns + self.idle_turns {
+            0 => 0.0,
+            turns => self.deployed_turns as f64 / turns as f64,
+        }
+    }
+
+    pub fn points_per_deployment(&self) -> f64 {
+        match self.deployment_points.len() {
+            0 => 0.0,
+            deployments => self.deployment_points.iter().sum::<u32>() as f64 / deployments as f64,
+        }
+    }
+
+    /// Gini coefficient of the points earned by each deployment: 0 when every deployment earned
+    /// the same, towards 1 when a few deployments earned all the points
+    pub fn gini(&self) -> f64 {
+        let mut points = self.deployment_points.clone();
+        points.sort();
+
+        let (count, total) = (points.len() as f64, points.iter().sum::<u32>() as f64);
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let weighted: f64 = points.iter().enumerate().map(|(index, points)| (2.0 * (index + 1) as f64 - count - 1.0) * *points as f64).sum();
+
+        weighted / (count * total)
+    }
+}
+
+/// Collects each player's [MeepleUsage] over every game it observes, following the games on a
+/// board of its own
+#[derive(Debug, Default)]
+pub struct MeepleTracker {
+    games: HashMap<usize, ReplayState>,
+    /// Usage by each player, in the turn order of the first game they played
+    pub usage: IndexMap<PlayerIdentifier, MeepleUsage>,
+}
+
+impl GameObserver for MeepleTracker {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, players, rules, .. } => {
+                for (color, _, _) in players {
+                    self.usage.entry(*color).or_default();
+                }
+
+                self.games.insert(*game_index, follow(players.iter().map(|(color, _, _)| *color), rules));
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                let Some(state) = self.games.get_mut(game_index) else {
+                    return;
+                };
+
+                let success = state.apply(&replay_turn(*player, tile, placement)).expect("the game should only make legal moves");
+
+                if let Some((_, Some(_))) = placement {
+                    self.usage.entry(*player).or_default().deployments += 1;
+                }
+
+                for liberated in success.iter().flat_map(|success| &success.liberated_meeple) {
+                    let color = liberated.meeple.color;
+
+                    // the liberated meeple only says what its feature scored, not who scored it
+                    let scored = success.iter().flat_map(|success| &success.completed_features).any(|feature| {
+                        feature.region_type == liberated.region_type && feature.points == liberated.points && feature.scored_by.contains(&color)
+                    });
+
+                    self.usage.entry(color).or_default().deployment_points.push(if scored { liberated.points } else { 0 });
+                }
+
+                for (color, player) in &state.players {
+                    let usage = self.usage.entry(*color).or_default();
+
+                    usage.deployed_turns += deployed_meeple(&state.board, *color).count();
+                    usage.idle_turns += player.meeple.len();
+                }
+            }
+            GameEvent::GameCompleted { game_index, .. } => {
+                let Some(state) = self.games.remove(game_index) else {
+                    return;
+                };
+
+                // meeple still on the board earn what their feature scores at the end of the game
+                for color in state.players.keys() {
+                    let points: Vec<u32> = deployed_meeple(&state.board, *color)
+                        .map(|(coordinate, region_index)| {
+                            state
+                                .board
+                                .get_connected_regions()
+                                .into_iter()
+                                .find(|region| region.tile_regions.iter().any(|r| r.tile_position == coordinate && r.region_index == region_index))
+                                .filter(|region| region.majority_meeple_player_ids(&state.board).contains(color))
+                                .map_or(0, |region| region.score(&state.board))
+                        })
+                        .collect();
+
+                    self.usage.entry(*color).or_default().deployment_points.extend(points);
+                }
+            }
+            GameEvent::MeepleRecalled { game_index, player, coordinate, region_index } => {
+                let Some(state) = self.games.get_mut(game_index) else {
+                    return;
+                };
+
+                // a meeple taken back scores nothing for its deployment
+                if state.recall_meeple(*player, coordinate, *region_index) {
+                    self.usage.entry(*player).or_default().deployment_points.push(0);
+                }
+            }
+            GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+/// How a player fared in completed features that another player also had meeple in, over one or
+/// more games
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContestRecord {
+    pub contested: usize,
+    /// Contests the player scored alone
+    pub won: usize,
+    /// Contests the player scored along with someone else
+    pub shared: usize,
+    /// Weighted meeple the player won each contest by
+    pub margins: Vec<u32>,
+    /// Weighted meeple the player had in each contest, won or not
+    pub meeple: Vec<u32>,
+}
+
+impl ContestRecord {
+    pub fn lost(&self) -> usize {
+        self.contested - self.won - self.shared
+    }
+
+    pub fn mean_margin(&self) -> f64 {
+        mean(&self.margins)
+    }
+
+    pub fn mean_meeple(&self) -> f64 {
+        mean(&self.meeple)
+    }
+}
+
+fn mean(values: &[u32]) -> f64 {
+    match values.len() {
+        0 => 0.0,
+        count => values.iter().sum::<u32>() as f64 / count as f64,
+    }
+}
+
+/// Each player's [ContestRecord], from the cities, roads and cloisters completed in the games it
+/// observes, for measuring whether stacking meeple in a feature pays off. Farms are only scored at
+/// the end of a game, so aren't counted
+#[derive(Debug, Default)]
+pub struct ContestStats {
+    /// Records of the players that have been in a contest, in the order they first were
+    pub records: IndexMap<PlayerIdentifier, ContestRecord>,
+}
+
+impl GameObserver for ContestStats {
+    fn notify(&mut self, event: &GameEvent) {
+        let GameEvent::RegionCompleted { scorer, meeple, margin, .. } = event else {
+            return;
+        };
+
+        if meeple.len() < 2 {
+            return;
+        }
+
+        for (player, count) in meeple {
+            let record = self.records.entry(*player).or_default();
+
+            record.contested += 1;
+            record.meeple.push(*count);
+
+            match (scorer.contains(player), scorer.len()) {
+                (false, _) => {}
+                (true, 1) => {
+                    record.won += 1;
+                    record.margins.push(*margin);
+                }
+                (true, _) => record.shared += 1,
+            }
+        }
+    }
+}
+
+/// How many different games a batch really played. Final positions are compared by
+/// [Board::canonical_hash], so games ending in a rotation or reflection of each other count as one
+/// position. Games with the very same moves suggest the games' random number generators aren't
+/// independent of each other
+#[derive(Debug, Default)]
+pub struct DuplicateGames {
+    games: HashMap<usize, (ReplayState, Vec<ReplayTurn>)>,
+    /// Games ending in each final position, by its canonical hash
+    positions: HashMap<u64, Vec<usize>>,
+    /// Games playing each sequence of moves, by a hash of the moves
+    move_sequences: HashMap<u64, Vec<usize>>,
+}
+
+impl DuplicateGames {
+    pub fn game_count(&self) -> usize {
+        self.positions.values().map(Vec::len).sum()
+    }
+
+    pub fn distinct_positions(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Groups of games that played exactly the same moves as each other, by game index
+    pub fn duplicate_games(&self) -> Vec<Vec<usize>> {
+        let mut duplicates: Vec<Vec<usize>> = self
+            .move_sequences
+            .values()
+            .filter(|games| games.len() > 1)
+            .map(|games| {
+                let mut games = games.clone();
+                games.sort();
+                games
+            })
+            .collect();
+
+        duplicates.sort();
+        duplicates
+    }
+}
+
+impl GameObserver for DuplicateGames {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, players, rules, .. } => {
+                self.games.insert(*game_index, (follow(players.iter().map(|(color, _, _)| *color), rules), vec![]));
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                if let Some((state, turns)) = self.games.get_mut(game_index) {
+                    let turn = replay_turn(*player, tile, placement);
+                    state.apply(&turn).expect("the game should only make legal moves");
+                    turns.push(turn);
+                }
+            }
+            GameEvent::MeepleRecalled { game_index, player, coordinate, region_index } => {
+                if let Some((state, turns)) = self.games.get_mut(game_index) {
+                    state.recall_meeple(*player, coordinate, *region_index);
+
+                    // the recall is part of the turn it was made in, so games differing only in it differ
+                    if let Some(placement) = turns.last_mut().and_then(|turn| turn.placement.as_mut()) {
+                        placement.recalled = Some(ReplayRecall::new(coordinate, *region_index));
+                    }
+                }
+            }
+            GameEvent::GameCompleted { game_index, .. } => {
+                let Some((state, turns)) = self.games.remove(game_index) else {
+                    return;
+                };
+
+                let mut hasher = DefaultHasher::new();
+                turns.hash(&mut hasher);
+
+                self.positions.entry(state.board.canonical_hash()).or_default().push(*game_index);
+                self.move_sequences.entry(hasher.finish()).or_default().push(*game_index);
+            }
+            GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+/// The biggest feature of its kind seen so far
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub game_index: usize,
+    /// Tiles the feature covers
+    pub tiles: usize,
+    /// Players with the most meeple in the feature
+    pub players: Vec<PlayerIdentifier>,
+}
+
+/// The largest city and longest road completed, and the biggest farm at the end of a game, over
+/// every game it observes. Cities and roads are recorded as they are completed, so the records
+/// are current during play
+#[derive(Debug, Default)]
+pub struct Records {
+    games: HashMap<usize, ReplayState>,
+    pub largest_city: Option<Record>,
+    pub longest_road: Option<Record>,
+    pub biggest_farm: Option<Record>,
+}
+
+impl Records {
+    /// The records set in a replayed game
+    pub fn from_replay(replay: &Replay) -> Result<Self, ReplayError> {
+        let mut records = Self::default();
+        let mut state = replay.initial_state()?;
+
+        for turn in &replay.turns {
+            for feature in state.apply(turn)?.iter().flat_map(|success| &success.completed_features) {
+                records.record(&feature.region_type, Record { game_index: 0, tiles: feature.tiles.len(), players: feature.scored_by.clone() });
+            }
+        }
+
+        records.record_farms(0, &state.board);
+
+        Ok(records)
+    }
+
+    /// Keeps the feature if it is bigger than the record for its kind. Ties stay with whoever set
+    /// the record first
+    fn record(&mut self, region_type: &RegionType, record: Record) {
+        let current = match region_type {
+            RegionType::City => &mut self.largest_city,
+            RegionType::Road => &mut self.longest_road,
+            RegionType::Field => &mut self.biggest_farm,
+            RegionType::Cloister | RegionType::Water => return,
+        };
+
+        if current.as_ref().is_none_or(|current| record.tiles > current.tiles) {
+            *current = Some(record);
+        }
+    }
+
+    /// Farms are only scored at the end of a game, so are measured on the final board
+    fn record_farms(&mut self, game_index: usize, board: &Board) {
+        for region in board.get_connected_regions().into_iter().filter(|region| region.region_type == RegionType::Field) {
+            let tiles = region.tile_regions.iter().map(|r| r.tile_position).collect::<HashSet<_>>().len();
+
+            self.record(&RegionType::Field, Record { game_index, tiles, players: region.majority_meeple_player_ids(board) });
+        }
+    }
+}
+
+impl GameObserver for Records {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, players, rules, .. } => {
+                self.games.insert(*game_index, follow(players.iter().map(|(color, _, _)| *color), rules));
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                if let Some(state) = self.games.get_mut(game_index) {
+                    state.apply(&replay_turn(*player, tile, placement)).expect("the game should only make legal moves");
+                }
+            }
+            GameEvent::MeepleRecalled { game_index, player, coordinate, region_index } => {
+                if let Some(state) = self.games.get_mut(game_index) {
+                    state.recall_meeple(*player, coordinate, *region_index);
+                }
+            }
+            GameEvent::RegionCompleted { game_index, region_type, tiles, scorer, .. } => {
+                self.record(region_type, Record { game_index: *game_index, tiles: tiles.len(), players: scorer.clone() });
+            }
+            GameEvent::GameCompleted { game_index, .. } => {
+                if let Some(state) = self.games.remove(game_index) {
+                    self.record_farms(*game_index, &state.board);
+                }
+            }
+            GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+impl Display for Records {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let records = [("Largest city", &self.largest_city), ("Longest road", &self.longest_road), ("Biggest farm", &self.biggest_farm)];
+
+        for (index, (name, record)) in records.into_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            match record {
+                None => write!(f, "{}: none", name)?,
+                Some(record) => {
+                    let players: Vec<_> = record.players.iter().map(|player| player.to_string()).collect();
+                    let held_by = if players.is_empty() { "unclaimed".to_string() } else { players.join(", ") };
+
+                    write!(f, "{}: {} tiles, {}", name, record.tiles, held_by)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How a tile definition was drawn and played, over one or more games
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TileUsage {
+    pub drawn: usize,
+    pub placed: usize,
+    /// Tiles from the starting tile each placement was, summed over the placements
+    distance: usize,
+    /// The points each placement scored its player straight away, and whether that player went on
+    /// to win
+    pub outcomes: Vec<(i32, bool)>,
+    /// Legal moves offered each time the tile was drawn, summed over the draws
+    hints: usize,
+    /// The most legal moves offered for a single draw of the tile
+    pub most_hints: usize,
+    /// Copies left in the deck when the tile stopped fitting anywhere on the board, over every
+    /// time it did
+    pub dead: usize,
+}
+
+impl TileUsage {
+    /// Mean distance of a placement from the starting tile, counted in steps along rows and columns
+    pub fn mean_distance(&self) -> f64 {
+        match self.placed {
+            0 => 0.0,
+            placed => self.distance as f64 / placed as f64,
+        }
+    }
+
+    /// Mean legal moves offered each time the tile was drawn, how wide a search of its turns is
+    pub fn mean_hints(&self) -> f64 {
+        match self.drawn {
+            0 => 0.0,
+            drawn => self.hints as f64 / drawn as f64,
+        }
+    }
+
+    /// How strongly the points a placement scored went with its player winning, from -1 to 1.
+    /// `None` until there's been both a winning and a losing placement with different scores
+    pub fn win_correlation(&self) -> Option<f64> {
+        let count = self.outcomes.len() as f64;
+        let (points, wins): (Vec<f64>, Vec<f64>) = self.outcomes.iter().map(|(points, won)| (*points as f64, if *won { 1.0 } else { 0.0 })).unzip();
+
+        let mean = |values: &[f64]| values.iter().sum::<f64>() / count;
+        let (mean_points, mean_wins) = (mean(&points), mean(&wins));
+
+        let covariance: f64 = points.iter().zip(&wins).map(|(p, w)| (p - mean_points) * (w - mean_wins)).sum();
+        let spread = |values: &[f64], mean: f64| values.iter().map(|v| (v - mean).powi(2)).sum::<f64>().sqrt();
+
+        let denominator = spread(&points, mean_points) * spread(&wins, mean_wins);
+
+        (denominator > 0.0).then(|| covariance / denominator)
+    }
+}
+
+/// Collects [TileUsage] for each tile definition over every game it observes
+#[derive(Debug, Default)]
+pub struct TileStats {
+    /// Each game's placements so far, held until the game ends and its winners are known
+    games: HashMap<usize, Vec<(&'static str, PlayerIdentifier, i32)>>,
+    /// Usage of each tile, in the order they were first drawn
+    pub usage: IndexMap<&'static str, TileUsage>,
+    /// Each game's turns so far and the legal moves offered over them
+    offered: HashMap<usize, (usize, usize)>,
+    /// Mean legal moves offered a turn in each completed game, its branching factor
+    pub branching_factors: Vec<f64>,
+}
+
+impl TileStats {
+    /// Mean branching factor over the completed games, if any have completed
+    pub fn mean_branching_factor(&self) -> Option<f64> {
+        (!self.branching_factors.is_empty()).then(|| self.branching_factors.iter().sum::<f64>() / self.branching_factors.len() as f64)
+    }
+}
+
+impl GameObserver for TileStats {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, .. } => {
+                self.games.insert(*game_index, vec![]);
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, score_delta, hint_count, .. } => {
+                let usage = self.usage.entry(tile.name()).or_default();
+                usage.drawn += 1;
+                usage.hints += hint_count;
+                usage.most_hints = usage.most_hints.max(*hint_count);
+
+                let (turns, offered) = self.offered.entry(*game_index).or_default();
+                *turns += 1;
+                *offered += hint_count;
+
+                let Some((placement, _)) = placement else {
+                    return;
+                };
+
+                let BoardCoordinate { x, y } = placement.coordinate;
+                usage.placed += 1;
+                usage.distance += x.unsigned_abs() as usize + y.unsigned_abs() as usize;
+
+                let points = score_delta.iter().find(|(id, _)| *id == player).map_or(0, |(_, points)| *points);
+                self.games.entry(*game_index).or_default().push((tile.name(), *player, points));
+            }
+            GameEvent::GameCompleted { game_index, score } => {
+                let best = score.iter().map(|(_, points)| *points).max();
+                let winners: Vec<_> = score.iter().filter(|(_, points)| Some(**points) == best).map(|(id, _)| *id).collect();
+
+                for (tile, player, points) in self.games.remove(game_index).unwrap_or_default() {
+                    self.usage.entry(tile).or_default().outcomes.push((points, winners.contains(&player)));
+                }
+
+                if let Some((turns @ 1.., offered)) = self.offered.remove(game_index) {
+                    self.branching_factors.push(offered as f64 / turns as f64);
+                }
+            }
+            GameEvent::TileDead { tile, copies, .. } => self.usage.entry(tile.name()).or_default().dead += copies,
+            GameEvent::RegionCompleted { .. } | GameEvent::MeepleRecalled { .. } | GameEvent::PlayerResigned { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+/// A table of how each tile was used, most drawn first, with the games' mean branching factor
+/// below it
+pub fn render_tile_table(stats: &TileStats) -> String {
+    let mut usage: Vec<_> = stats.usage.iter().collect();
+    usage.sort_by_key(|(name, usage)| (std::cmp::Reverse(usage.drawn), **name));
+
+    let name_width = usage.iter().map(|(name, _)| name.len()).max().unwrap_or(0).max(4);
+
+    let mut table = format!("{:<name_width$}  {:>6}  {:>6}  {:>8}  {:>8}  {:>6}  {:>5}  {:>5}\n", "Tile", "Drawn", "Placed", "Distance", "Win corr", "Hints", "Most", "Dead");
+
+    for (name, usage) in usage {
+        let correlation = usage.win_correlation().map_or("-".to_string(), |correlation| format!("{:+.2}", correlation));
+
+        table.push_str(&format!(
+            "{:<name_width$}  {:>6}  {:>6}  {:>8.1}  {:>8}  {:>6.1}  {:>5}  {:>5}\n",
+            name,
+            usage.drawn,
+            usage.placed,
+            usage.mean_distance(),
+            correlation,
+            usage.mean_hints(),
+            usage.most_hints,
+            usage.dead
+        ));
+    }
+
+    if let Some(branching_factor) = stats.mean_branching_factor() {
+        table.push_str(&format!("\nMean branching factor {:.1} over {} games\n", branching_factor, stats.branching_factors.len()));
+    }
+
+    table
+}
+
+/// A table of the features on the board, most valuable first, with the edges each has left open,
+/// the meeple standing in it and what it would score now
+pub fn render_region_table(board: &Board) -> String {
+    let mut regions = board.get_connected_regions();
+    regions.sort_by_key(|region| (std::cmp::Reverse(region.score(board)), region.id));
+
+    let mut table = format!("{:<8}  {:>5}  {:>10}  {:<16}  {:>5}\n", "Feature", "Tiles", "Open edges", "Residents", "Score");
+
+    for region in regions {
+        let region_type = Language::English.region_name(region.region_type());
+
+        let mut residents: IndexMap<MeepleColor, usize> = IndexMap::new();
+
+        for (_, _, meeple) in region.residents(board) {
+            *residents.entry(meeple.color).or_default() += 1;
+        }
+
+        let residents = match residents.is_empty() {
+            true => "-".to_string(),
+            false => residents.iter().map(|(color, count)| format!("{} {}", color, count)).collect::<Vec<_>>().join(", "),
+        };
+
+        table.push_str(&format!(
+            "{:<8}  {:>5}  {:>10}  {:<16}  {:>5}\n",
+            region_type,
+            region.tile_region_count(),
+            region.open_edge_count(),
+            residents,
+            region.score(board)
+        ));
+    }
+
+    table
+}
+
+/// How the games of a simulation went under one rule set
+#[derive(Debug)]
+pub struct RulesRun {
+    pub report: SimulationReport,
+    pub tiles: TileStats,
+    pub meeple: MeepleTracker,
+}
+
+/// The same games played under two rule sets, to see what changing the rules does to the scores and
+/// to how tiles and meeple are used
+#[derive(Debug)]
+pub struct RulesComparison {
+    pub baseline: RulesRun,
+    pub alternative: RulesRun,
+}
+
+/// Plays the simulation under its own rules and then again under `alternative`, from the same seed,
+/// so both are dealt the same decks as far as the rules allow. The alternative has to be for as
+/// many players as the simulation has
+pub fn compare_rules(config: SimulationConfig, alternative: RuleSet) -> Result<RulesComparison, GameBuildError> {
+    compare_rules_observed(config, alternative, &mut vec![])
+}
+
+/// As [compare_rules], notifying each observer as the games of both runs complete
+pub fn compare_rules_observed(config: SimulationConfig, alternative: RuleSet, observers: &mut Vec<Box<dyn GameObserver>>) -> Result<RulesComparison, GameBuildError> {
+    let mut run = |config: SimulationConfig| {
+        let tiles = Rc::new(RefCell::new(TileStats::default()));
+        let meeple = Rc::new(RefCell::new(MeepleTracker::default()));
+
+        let mut all: Vec<Box<dyn GameObserver>> = vec![Box::new(Rc::clone(&tiles)), Box::new(Rc::clone(&meeple))];
+        all.append(observers);
+
+        let report = simulate::run_observed(config, &mut all);
+        // the caller's observers go back to them for the next run
+        observers.extend(all.drain(2..));
+
+        RulesRun { report, tiles: tiles.take(), meeple: meeple.take() }
+    };
+
+    let alternative_config = config.clone().with_rules(alternative)?;
+
+    Ok(RulesComparison { baseline: run(config), alternative: run(alternative_config) })
+}
+
+/// Each player's mean score and meeple usage under both rule sets, then the tiles whose share of
+/// draws that were placed changed, most changed first
+pub fn render_rules_comparison(comparison: &RulesComparison) -> String {
+    let RulesComparison { baseline, alternative } = comparison;
+    let mut rendered = format!("{:<12}  {:>15}  {:>15}  {:>15}\n", "Player", "Mean score", "Meeple/game", "Points/meeple");
+
+    for (id, player) in &baseline.report.players {
+        let name = player.name().map(str::to_string).unwrap_or_else(|| id.to_string());
+        let usage = |run: &RulesRun| run.meeple.usage.get(id).cloned().unwrap_or_default();
+        let per_game = |run: &RulesRun| usage(run).deployments as f64 / run.report.games.len().max(1) as f64;
+
+        rendered.push_str(&format!(
+            "{:<12}  {:>15}  {:>15}  {:>15}\n",
+            name,
+            change(baseline.report.mean_score(*id), alternative.report.mean_score(*id)),
+            change(per_game(baseline), per_game(alternative)),
+            change(usage(baseline).points_per_deployment(), usage(alternative).points_per_deployment())
+        ));
+    }
+
+    let placed_share = |run: &RulesRun, tile: &str| {
+        run.tiles.usage.get(tile).filter(|usage| usage.drawn > 0).map_or(0.0, |usage| usage.placed as f64 / usage.drawn as f64 * 100.0)
+    };
+
+    let mut tiles: Vec<&str> = baseline.tiles.usage.keys().chain(alternative.tiles.usage.keys()).copied().collect::<IndexSet<_>>().into_iter().collect();
+    tiles.retain(|tile| placed_share(baseline, tile) != placed_share(alternative, tile));
+    tiles.sort_by(|a, b| {
+        let shift = |tile| (placed_share(alternative, tile) - placed_share(baseline, tile)).abs();
+        shift(b).total_cmp(&shift(a)).then(a.cmp(b))
+    });
+
+    let name_width = tiles.iter().map(|name| name.len()).max().unwrap_or(0).max(4);
+    rendered.push_str(&format!("\n{:<name_width$}  {:>19}\n", "Tile", "Placed % of draws"));
+
+    for tile in tiles {
+        rendered.push_str(&format!("{:<name_width$}  {:>19}\n", tile, change(placed_share(baseline, tile), placed_share(alternative, tile))));
+    }
+
+    rendered
+}
+
+/// `before -> after`, or just the value where it didn't change
+fn change(before: f64, after: f64) -> String {
+    match (after - before).abs() < 0.05 {
+        true => format!("{:.1}", before),
+        false => format!("{:.1} -> {:.1}", before, after),
+    }
+}
+
+/// A board for following a game from its events, for observers that need more than the events say
+pub(crate) fn follow<P: IntoIterator<Item = PlayerIdentifier>>(players: P, rules: &RuleSet) -> ReplayState {
+    ReplayState::new(players.into_iter().map(|color| (color, None)), rules).expect("a game's rules are validated before it starts")
+}
+
+pub(crate) fn replay_turn(player: PlayerIdentifier, tile: &TileDefinition, placement: &Option<(TilePlacement, Option<RegionIndex>)>) -> ReplayTurn {
+    ReplayTurn {
+        player,
+        tile: tile.name().to_string(),
+        placement: placement.as_ref().map(|(placement, meeple)| ReplayPlacement::new(placement, *meeple)),
+    }
+}
+
+/// Where each of the player's meeple on the board is
+fn deployed_meeple(board: &Board, player: PlayerIdentifier) -> impl Iterator<Item = (BoardCoordinate, RegionIndex)> + '_ {
+    board.placed_tiles.values().filter_map(move |tile| match &tile.meeple {
+        Some((region_index, meeple)) if meeple.color == player => Some((tile.placement.coordinate, *region_index)),
+        _ => None,
+    })
+}
+
+fn marker(player: &PlayerIdentifier) -> char {
+    match player {
+        MeepleColor::Red => 'R',
+        MeepleColor::Green => 'G',
+        MeepleColor::Blue => 'B',
+        MeepleColor::Black => 'K',
+        MeepleColor::Yellow => 'Y',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_logic::GamePhase;
+    use crate::player::Meeple;
+    use crate::replay::{ReplayPlayer, ReplayRules};
+    use crate::tile::{PlacedTile, Rotation, TilePlacement};
+    use crate::tile_definitions::SIDE_CITY;
+
+    fn turn(player: PlayerIdentifier, points: i32) -> GameEvent {
+        GameEvent::TurnCompleted {
+            game_index: 0,
+            player,
+            tile: &SIDE_CITY,
+            phase: GamePhase::Main,
+            placement: None,
+            score_delta: [(player, points)].into_iter().collect(),
+            hint_count: 0,
+        }
+    }
+
+    #[test]
+    fn should_chart_each_players_running_score() {
+        let mut timeline = ScoreTimeline::default();
+
+        let players = vec![(MeepleColor::Red, None, None), (MeepleColor::Green, None, None)];
+        timeline.notify(&GameEvent::GameStarted { game_index: 0, seed: [0; 32], players, deck: vec![], rules: Box::new(RuleSet::standard()) });
+
+        for event in [turn(MeepleColor::Red, 4), turn(MeepleColor::Green, 0), turn(MeepleColor::Red, 0), turn(MeepleColor::Green, 2)] {
+            timeline.notify(&event);
+        }
+
+        timeline.notify(&GameEvent::GameCompleted { game_index: 0, score: [(MeepleColor::Red, 11), (MeepleColor::Green, 3)].into_iter().collect() });
+
+        assert_eq!(timeline.scores.len(), 5);
+        assert_eq!(timeline.scores[3], [(MeepleColor::Red, 4), (MeepleColor::Green, 2)].into_iter().collect());
+
+        let chart = render_score_chart(&timeline);
+        let lines: Vec<&str> = chart.lines().collect();
+
+        assert_eq!(lines.len(), CHART_HEIGHT + 3);
+        // Red finishes on the highest score, and Green scores nothing for three turns
+        assert_eq!(lines[0], "11 │    R");
+        assert_eq!(lines[CHART_HEIGHT - 1], " 0 │GGG  ");
+        assert_eq!(lines[CHART_HEIGHT + 2], "    R Red  G Green");
+    }
+
+    #[test]
+    fn should_start_each_players_running_score_from_their_handicap() {
+        let mut timeline = ScoreTimeline::default();
+
+        let players = vec![(MeepleColor::Red, None, None), (MeepleColor::Green, None, None)];
+        let rules = RuleSet::standard().with_starting_score(MeepleColor::Green, 10);
+        timeline.notify(&GameEvent::GameStarted { game_index: 0, seed: [0; 32], players, deck: vec![], rules: Box::new(rules) });
+
+        timeline.notify(&turn(MeepleColor::Red, 4));
+
+        assert_eq!(timeline.scores[0], [(MeepleColor::Red, 4), (MeepleColor::Green, 10)].into_iter().collect());
+    }
+
+    #[test]
+    fn should_track_how_each_players_meeple_are_used() {
+        let mut tracker = MeepleTracker::default();
+
+        let players = vec![(MeepleColor::Red, None, None), (MeepleColor::Green, None, None)];
+        tracker.notify(&GameEvent::GameStarted { game_index: 0, seed: [0; 32], players, deck: vec![], rules: Box::new(RuleSet::standard()) });
+
+        let side_city = |player, x, y, rotation, meeple: Option<usize>| GameEvent::TurnCompleted {
+            game_index: 0,
+            player,
+            tile: &SIDE_CITY,
+            phase: GamePhase::Main,
+            placement: Some((TilePlacement { coordinate: BoardCoordinate::new(x, y), rotation }, meeple.map(RegionIndex::new))),
+            score_delta: Score::new(),
+            hint_count: 0,
+        };
+
+        // Bob closes Alice's city, then claims a city that is never finished
+        tracker.notify(&side_city(MeepleColor::Red, 0, 0, Rotation::R0, Some(1)));
+        tracker.notify(&side_city(MeepleColor::Green, 0, 1, Rotation::R180, None));
+        tracker.notify(&side_city(MeepleColor::Green, 1, 0, Rotation::R0, Some(1)));
+        tracker.notify(&GameEvent::GameCompleted { game_index: 0, score: Score::new() });
+
+        let alice = &tracker.usage[&MeepleColor::Red];
+        let bob = &tracker.usage[&MeepleColor::Green];
+
+        assert_eq!(alice.deployments, 1);
+        assert_eq!(alice.deployment_points, vec![4]);
+        assert_eq!((alice.deployed_turns, alice.idle_turns), (1, 6 + 7 + 7));
+        assert_eq!(bob.deployment_points, vec![1]);
+        assert_eq!((bob.deployed_turns, bob.idle_turns), (1, 7 + 7 + 6));
+        assert!(tracker.games.is_empty());
+    }
+
+    #[test]
+    fn should_measure_how_unevenly_deployments_score() {
+        let usage = |deployment_points: Vec<u32>| MeepleUsage { deployment_points, ..MeepleUsage::default() };
+
+        assert_eq!(usage(vec![3, 3, 3]).gini(), 0.0);
+        assert_eq!(usage(vec![0, 0, 0, 12]).gini(), 0.75);
+        assert_eq!(usage(vec![]).gini(), 0.0);
+        assert_eq!(usage(vec![0, 4]).points_per_deployment(), 2.0);
+    }
+
+    #[test]
+    fn should_keep_the_biggest_features_of_each_kind() {
+        let mut records = Records::default();
+
+        let completed = |game_index, region_type, tiles: usize, scorer| GameEvent::RegionCompleted {
+            game_index,
+            region_type,
+            tiles: (0..tiles as i8).map(|x| BoardCoordinate::new(x, 0)).collect(),
+            scorer,
+            points: 0,
+            meeple: vec![],
+            margin: 0,
+        };
+
+        records.notify(&completed(0, RegionType::City, 3, vec![MeepleColor::Red]));
+        records.notify(&completed(0, RegionType::Road, 5, vec![]));
+        records.notify(&completed(1, RegionType::City, 4, vec![MeepleColor::Green, MeepleColor::Red]));
+        records.notify(&completed(1, RegionType::City, 4, vec![MeepleColor::Blue]));
+        records.notify(&completed(1, RegionType::Cloister, 9, vec![MeepleColor::Blue]));
+
+        assert_eq!(records.largest_city, Some(Record { game_index: 1, tiles: 4, players: vec![MeepleColor::Green, MeepleColor::Red] }));
+        assert_eq!(records.longest_road, Some(Record { game_index: 0, tiles: 5, players: vec![] }));
+        assert_eq!(records.biggest_farm, None);
+        assert_eq!(records.to_string(), "Largest city: 4 tiles, Green, Red\nLongest road: 5 tiles, unclaimed\nBiggest farm: none");
+    }
+
+    #[test]
+    fn should_count_distinct_positions_and_flag_repeated_games() {
+        let mut duplicates = DuplicateGames::default();
+
+        let side_city = |game_index, x, y, rotation| GameEvent::TurnCompleted {
+            game_index,
+            player: MeepleColor::Red,
+            tile: &SIDE_CITY,
+            phase: GamePhase::Main,
+            placement: Some((TilePlacement { coordinate: BoardCoordinate::new(x, y), rotation }, None)),
+            score_delta: Score::new(),
+            hint_count: 0,
+        };
+
+        // games 0 and 1 are the same game, game 2 ends in the same position turned half way round,
+        // and game 3 ends somewhere else
+        let games = [
+            [(0, 0, Rotation::R0), (0, 1, Rotation::R180)],
+            [(0, 0, Rotation::R0), (0, 1, Rotation::R180)],
+            [(0, 0, Rotation::R180), (0, -1, Rotation::R0)],
+            [(0, 0, Rotation::R0), (1, 0, Rotation::R0)],
+        ];
+
+        for (game_index, moves) in games.into_iter().enumerate() {
+            duplicates.notify(&GameEvent::GameStarted { game_index, seed: [0; 32], players: vec![(MeepleColor::Red, None, None)], deck: vec![], rules: Box::new(RuleSet::standard()) });
+
+            for (x, y, rotation) in moves {
+                duplicates.notify(&side_city(game_index, x, y, rotation));
+            }
+
+            duplicates.notify(&GameEvent::GameCompleted { game_index, score: Score::new() });
+        }
+
+        assert_eq!(duplicates.game_count(), 4);
+        assert_eq!(duplicates.distinct_positions(), 2);
+        assert_eq!(duplicates.duplicate_games(), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn should_count_contests_won_shared_and_lost() {
+        let mut contests = ContestStats::default();
+
+        let completed = |scorer: Vec<MeepleColor>, meeple: Vec<(MeepleColor, u32)>, margin| GameEvent::RegionCompleted {
+            game_index: 0,
+            region_type: RegionType::City,
+            tiles: vec![],
+            scorer,
+            points: 4,
+            meeple,
+            margin,
+        };
+
+        contests.notify(&completed(vec![MeepleColor::Red], vec![(MeepleColor::Red, 2), (MeepleColor::Green, 1)], 1));
+        contests.notify(&completed(vec![MeepleColor::Red, MeepleColor::Green], vec![(MeepleColor::Red, 1), (MeepleColor::Green, 1)], 0));
+        // uncontested, so not counted
+        contests.notify(&completed(vec![MeepleColor::Green], vec![(MeepleColor::Green, 1)], 1));
+
+        let red = &contests.records[&MeepleColor::Red];
+        let green = &contests.records[&MeepleColor::Green];
+
+        assert_eq!((red.contested, red.won, red.shared, red.lost()), (2, 1, 1, 0));
+        assert_eq!((red.mean_margin(), red.mean_meeple()), (1.0, 1.5));
+        assert_eq!((green.contested, green.won, green.shared, green.lost()), (2, 0, 1, 1));
+    }
+
+    #[test]
+    fn should_measure_farms_at_the_end_of_a_replay() {
+        let side_city = |player, x, y, rotation, meeple: Option<usize>| ReplayTurn {
+            player,
+            tile: "Side city".to_string(),
+            placement: Some(ReplayPlacement { x, y, rotation, meeple, recalled: None }),
+        };
+
+        let replay = Replay {
+            seed: String::new(),
+            deck: vec![],
+            players: vec![ReplayPlayer { color: MeepleColor::Red, name: None, bot: None }, ReplayPlayer { color: MeepleColor::Green, name: None, bot: None }],
+            turns: vec![
+                // Green farms the field alongside Red's city, which Red then closes
+                side_city(MeepleColor::Red, 0, 0, Rotation::R0, Some(1)),
+                side_city(MeepleColor::Green, 1, 0, Rotation::R0, Some(0)),
+                side_city(MeepleColor::Red, 0, 1, Rotation::R180, None),
+            ],
+            final_score: vec![],
+            handovers: vec![],
+            rules: ReplayRules::default(),
+        };
+
+        let records = Records::from_replay(&replay).unwrap();
+
+        assert_eq!(records.largest_city, Some(Record { game_index: 0, tiles: 2, players: vec![MeepleColor::Red] }));
+        assert_eq!(records.longest_road, None);
+        assert_eq!(records.biggest_farm, Some(Record { game_index: 0, tiles: 2, players: vec![MeepleColor::Green] }));
+    }
+
+    #[test]
+    fn should_tabulate_how_each_tile_was_used() {
+        let mut stats = TileStats::default();
+
+        let players = vec![(MeepleColor::Red, None, None), (MeepleColor::Green, None, None)];
+        stats.notify(&GameEvent::GameStarted { game_index: 0, seed: [0; 32], players, deck: vec![], rules: Box::new(RuleSet::standard()) });
+
+        let side_city = |player, x, y, points| GameEvent::TurnCompleted {
+            game_index: 0,
+            player,
+            tile: &SIDE_CITY,
+            phase: GamePhase::Main,
+            placement: Some((TilePlacement { coordinate: BoardCoordinate::new(x, y), rotation: Rotation::R0 }, None)),
+            score_delta: [(player, points)].into_iter().collect(),
+            hint_count: 12,
+        };
+
+        stats.notify(&side_city(MeepleColor::Red, 0, 0, 4));
+        stats.notify(&side_city(MeepleColor::Green, 1, -2, 0));
+        stats.notify(&turn(MeepleColor::Red, 0));
+        stats.notify(&GameEvent::GameCompleted { game_index: 0, score: [(MeepleColor::Red, 4), (MeepleColor::Green, 0)].into_iter().collect() });
+
+        let usage = &stats.usage[SIDE_CITY.name()];
+
+        assert_eq!((usage.drawn, usage.placed), (3, 2));
+        assert_eq!(usage.mean_distance(), 1.5);
+        assert_eq!(usage.outcomes, vec![(4, true), (0, false)]);
+        assert!((usage.win_correlation().unwrap() - 1.0).abs() < 1e-9);
+        // the passed turn had no moves to choose from
+        assert_eq!((usage.mean_hints(), usage.most_hints), (8.0, 12));
+        assert_eq!(stats.mean_branching_factor(), Some(8.0));
+
+        let table = render_tile_table(&stats);
+
+        assert_eq!(table.lines().nth(1), Some("Side city       3       2       1.5     +1.00     8.0     12      0"));
+        assert_eq!(table.lines().last(), Some("Mean branching factor 8.0 over 1 games"));
+    }
+
+    #[test]
+    fn should_list_each_feature_with_its_open_edges_and_residents() {
+        let board = Board::new_with_tiles([PlacedTile::new_with_meeple(&SIDE_CITY, 0, 0, Rotation::R0, (RegionIndex::new(1), Meeple::new(MeepleColor::Red)))]).unwrap();
+
+        let table = render_region_table(&board);
+
+        assert_eq!(table.lines().nth(1), Some("city          1           3  Red 1                 1"));
+        assert_eq!(table.lines().nth(2), Some("field         1           9  -                     0"));
+    }
+
+    #[test]
+    fn should_leave_the_correlation_out_until_it_means_something() {
+        let usage = TileUsage { outcomes: vec![(3, true), (3, false)], ..TileUsage::default() };
+
+        assert_eq!(usage.win_correlation(), None);
+        assert_eq!(TileUsage::default().win_correlation(), None);
+    }
+
+    #[test]
+    fn should_play_the_same_games_under_both_rule_sets() {
+        use crate::bot_strategy::{BotStrategy, LazyBot};
+        use crate::player::Player;
+
+        let players = [Player::red().with_name("Alice"), Player::green()].map(|player| player.with_bot(BotStrategy::Lazy(LazyBot)));
+        let config = SimulationConfig::new(players, 2, [4; 32]).unwrap().with_river(false);
+
+        let comparison = compare_rules(config, RuleSet::first_edition()).unwrap();
+        let seeds = |run: &RulesRun| run.report.games.iter().map(|game| game.seed).collect::<Vec<_>>();
+
+        assert_eq!(seeds(&comparison.baseline), seeds(&comparison.alternative));
+        assert!(comparison.alternative.tiles.usage.values().any(|usage| usage.placed > 0));
+
+        let rendered = render_rules_comparison(&comparison);
+
+        assert!(rendered.lines().nth(1).is_some_and(|line| line.starts_with("Alice")));
+        assert!(rendered.lines().nth(2).is_some_and(|line| line.starts_with("Green")));
+    }
+
+    #[test]
+    fn should_follow_each_game_under_its_own_rules_when_comparing() {
+        use crate::bot_strategy::{BotStrategy, MyopicBot};
+        use crate::player::{Player, MEEPLE_COUNT};
+
+        let players = [Player::red(), Player::green()].map(|player| player.with_bot(BotStrategy::Myopic(MyopicBot)));
+        let config = SimulationConfig::new(players, 2, [4; 32]).unwrap().with_river(false);
+
+        let comparison = compare_rules(config, RuleSet::standard().with_meeple_per_player(2)).unwrap();
+
+        // each turn, every meeple of both players is either on the board or in their supply
+        for (run, meeple_per_player) in [(&comparison.baseline, MEEPLE_COUNT), (&comparison.alternative, 2)] {
+            let turns: usize = run.tiles.usage.values().map(|usage| usage.drawn).sum();
+            let meeple_turns: usize = run.meeple.usage.values().map(|usage| usage.deployed_turns + usage.idle_turns).sum();
+
+            assert_eq!(meeple_turns, 2 * meeple_per_player * turns);
+        }
+    }
+}
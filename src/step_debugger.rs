@@ -0,0 +1,197 @@
+use crate::board::{Board, RenderAnnotations};
+use crate::controller::LineStdin;
+use crate::locale::Language;
+use crate::move_hints::MoveHint;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::RegionIndex;
+use crate::replay::ReplayState;
+use crate::stats::{follow, render_region_table, replay_turn};
+use crate::tile::{RenderStyle, TileDefinition, TilePlacement};
+use std::io::{self, BufRead, Write};
+
+const COMMANDS: &str = "Enter for the next turn, or one of: regions, score, hints, run";
+
+/// Pauses the game after every turn, drawing the board and waiting for Enter before the next, so a
+/// game can be stepped through to settle rules questions. While paused it answers `regions` with
+/// the features on the board, `score` with the score so far and as if the game ended now, and
+/// `hints` with every move the last tile could have made. `run` plays the rest without pausing
+pub struct StepDebugger<R, W> {
+    input: R,
+    output: W,
+    render_style: RenderStyle,
+    annotations: RenderAnnotations,
+    state: Option<ReplayState>,
+    /// The board as it was before the last turn, for the moves the tile could have made
+    before: Option<Board>,
+    last_tile: Option<&'static TileDefinition>,
+    running: bool,
+}
+
+impl StepDebugger<LineStdin, io::Stdout> {
+    pub fn stdin(render_style: RenderStyle) -> Self {
+        Self::new(LineStdin::default(), io::stdout(), render_style)
+    }
+}
+
+impl<R: BufRead, W: Write> StepDebugger<R, W> {
+    pub fn new(input: R, output: W, render_style: RenderStyle) -> Self {
+        Self { input, output, render_style, annotations: RenderAnnotations::default(), state: None, before: None, last_tile: None, running: false }
+    }
+
+    /// Draw the extras `annotations` asks for around the board each pause
+    pub fn with_annotations(mut self, annotations: RenderAnnotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Shows the turn just played and answers commands until told to carry on. Running out of
+    /// input runs the rest of the game, as `run` does
+    fn pause(&mut self, headline: &str) -> io::Result<()> {
+        let Some(state) = &self.state else {
+            return Ok(());
+        };
+
+        writeln!(self.output, "{}", state.board.render_annotated(&self.render_style, &state.players, &self.annotations))?;
+        writeln!(self.output, "{}", headline)?;
+
+        loop {
+            write!(self.output, "> ")?;
+            self.output.flush()?;
+
+            let mut line = String::new();
+
+            if self.input.read_line(&mut line)? == 0 {
+                self.running = true;
+                return Ok(());
+            }
+
+            let Some(state) = &self.state else {
+                return Ok(());
+            };
+
+            match line.trim() {
+                "" => return Ok(()),
+                "run" => {
+                    self.running = true;
+                    return Ok(());
+                }
+                "regions" => write!(self.output, "{}", render_region_table(&state.board))?,
+                "score" => {
+                    writeln!(self.output, "Banked:")?;
+                    write!(self.output, "{}", state.score.render(&state.players, &self.render_style))?;
+                    writeln!(self.output, "If the game ended now:")?;
+                    write!(self.output, "{}", state.final_score().render(&state.players, &self.render_style))?;
+                }
+                "hints" => match (&self.before, self.last_tile) {
+                    (Some(before), Some(tile)) => {
+                        for hint in before.get_move_hints(tile, true) {
+                            writeln!(self.output, "  {}", Language::English.describe_move(&hint))?;
+                        }
+                    }
+                    _ => writeln!(self.output, "No tile has been played yet")?,
+                },
+                _ => writeln!(self.output, "{}", COMMANDS)?,
+            }
+        }
+    }
+}
+
+impl<R: BufRead, W: Write> GameObserver for StepDebugger<R, W> {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { players, rules, .. } => {
+                self.state = Some(follow(players.iter().map(|(color, _, _)| *color), rules));
+                self.before = None;
+                self.last_tile = None;
+            }
+            GameEvent::TurnCompleted { player, tile, placement, .. } => {
+                let Some(state) = &mut self.state else {
+                    return;
+                };
+
+                let before = state.board.clone();
+                let turn = replay_turn(*player, tile, placement);
+
+                if state.apply(&turn).is_err() {
+                    return;
+                }
+
+                let headline = match placement {
+                    Some(placement) => format!("Turn {}: {} drew {} and played {}", state.turn, player, tile.name(), describe_turn(tile, placement)),
+                    None => format!("Turn {}: {} drew {} and passed", state.turn, player, tile.name()),
+                };
+
+                self.before = Some(before);
+                self.last_tile = Some(tile);
+
+                if !self.running {
+                    // the game carries on without pausing if the terminal goes away
+                    if self.pause(&headline).is_err() {
+                        self.running = true;
+                    }
+                }
+            }
+            GameEvent::MeepleRecalled { player, coordinate, region_index, .. } => {
+                if let Some(state) = &mut self.state {
+                    state.recall_meeple(*player, coordinate, *region_index);
+                }
+            }
+            GameEvent::GameCompleted { .. } => self.state = None,
+            GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+fn describe_turn(tile: &'static TileDefinition, (tile_placement, meeple_placement): &(TilePlacement, Option<RegionIndex>)) -> String {
+    let hint = MoveHint { tile, tile_placement: tile_placement.clone(), meeple_placement: *meeple_placement };
+
+    Language::English.describe_move(&hint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{BotStrategy, LazyBot};
+    use crate::game_logic::Game;
+    use crate::player::Player;
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+    use std::cell::RefCell;
+    use std::io::Cursor;
+    use std::rc::Rc;
+
+    #[test]
+    fn should_pause_after_each_turn_and_answer_commands() {
+        let output = Rc::new(RefCell::new(vec![]));
+        let debugger = StepDebugger::new(Cursor::new("regions\nscore\nhints\nwhat\n\nrun\n"), SharedOutput(Rc::clone(&output)), RenderStyle::Ascii);
+
+        Game::builder()
+            .with_player(Player::red(), BotStrategy::Lazy(LazyBot))
+            .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+            .with_tile_order([SIDE_CITY.id(), STRAIGHT_ROAD.id(), SIDE_CITY.id()])
+            .build()
+            .unwrap()
+            .play_observed(0, &mut [Box::new(debugger)]);
+
+        let output = String::from_utf8(output.borrow().clone()).unwrap();
+
+        assert!(output.contains("Turn 1: Red drew Side city and played"));
+        assert!(output.contains("Feature"));
+        assert!(output.contains("If the game ended now"));
+        assert!(output.contains(COMMANDS));
+        assert!(output.contains("Turn 2: Green drew"));
+        // `run` is given on the second turn, so the third goes by without a pause
+        assert!(!output.contains("Turn 3:"));
+    }
+
+    struct SharedOutput(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use crate::board::Board;
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::{MeepleColor, Player, PlayerIdentifier};
+use crate::replay::ReplayState;
+use crate::stats::{follow, replay_turn};
+use crate::tile::{BoardCoordinate, PlacedTile, RenderCell, RenderStyle, TileCoordinate, TileId, TILE_WIDTH};
+use crate::tile_definitions::ALL_TILE_DEFINITIONS;
+use base64::{engine::general_purpose, Engine as _};
+use colored::Color;
+use indexmap::IndexMap;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::io;
+use std::path::Path;
+
+/// Size of a tile cell in the SVG, in pixels
+const CELL_SIZE: usize = 8;
+
+/// Images to draw tiles with in place of the coloured cells, keyed by [TileId]. Tiles without
+/// artwork are drawn as cells. Images should show the tile unrotated
+#[derive(Debug, Clone, Default)]
+pub struct TileArtwork {
+    /// Data uri for each tile with an image of its own
+    tiles: HashMap<TileId, String>,
+    sprite_sheet: Option<SpriteSheet>,
+}
+
+/// One image holding every tile, in rows of `columns` tiles in [TileId] order
+#[derive(Debug, Clone)]
+struct SpriteSheet {
+    href: String,
+    columns: usize,
+}
+
+impl TileArtwork {
+    /// Loads `<tile id>.png` for each tile from the directory, skipping tiles without a file
+    pub fn from_directory(directory: impl AsRef<Path>) -> io::Result<Self> {
+        let mut artwork = Self::default();
+
+        for tile in ALL_TILE_DEFINITIONS {
+            let path = directory.as_ref().join(format!("{}.png", tile.id()));
+
+            if path.exists() {
+                artwork = artwork.with_tile(tile.id(), &std::fs::read(path)?);
+            }
+        }
+
+        Ok(artwork)
+    }
+
+    /// Uses a PNG sprite sheet for tiles without an image of their own
+    pub fn with_sprite_sheet(mut self, png: &[u8], columns: usize) -> Self {
+        self.sprite_sheet = Some(SpriteSheet { href: png_data_uri(png), columns: columns.max(1) });
+        self
+    }
+
+    pub fn with_tile(mut self, id: TileId, png: &[u8]) -> Self {
+        self.tiles.insert(id, png_data_uri(png));
+        self
+    }
+
+    /// Draws the tile's artwork, turned to its placement, filling a `size` square, if there is any
+    fn draw(&self, svg: &mut String, tile: &PlacedTile, size: usize) -> bool {
+        let id = tile.tile.id();
+
+        if !self.tiles.contains_key(&id) && self.sprite_sheet.is_none() {
+            return false;
+        }
+
+        write!(svg, r#"<g transform="rotate({} {centre} {centre})">"#, tile.placement.rotation.degrees(), centre = size / 2).unwrap();
+
+        match (self.tiles.get(&id), &self.sprite_sheet) {
+            (Some(href), _) => write!(svg, r#"<image href="{href}" width="{size}" height="{size}" preserveAspectRatio="none"/>"#).unwrap(),
+            (None, Some(sheet)) => sheet.draw(svg, id, size),
+            (None, None) => unreachable!("tiles without artwork should have been drawn as cells"),
+        }
+
+        svg.push_str("</g>");
+
+        true
+    }
+}
+
+impl SpriteSheet {
+    fn draw(&self, svg: &mut String, id: TileId, size: usize) {
+        let rows = ALL_TILE_DEFINITIONS.len().div_ceil(self.columns);
+        let (row, column) = (id as usize / self.columns, id as usize % self.columns);
+
+        // a nested svg clips the sheet to the one tile
+        write!(
+            svg,
+            r#"<svg width="{size}" height="{size}" viewBox="{} {} {size} {size}"><image href="{}" width="{}" height="{}" preserveAspectRatio="none"/></svg>"#,
+            column * size,
+            row * size,
+            self.href,
+            self.columns * size,
+            rows * size
+        )
+        .unwrap();
+    }
+}
+
+fn png_data_uri(png: &[u8]) -> String {
+    format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(png))
+}
+
+impl RenderCell {
+    pub(crate) fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            RenderCell::Field => (0x8f, 0xb9, 0x2d),
+            RenderCell::Road => (0xbe, 0xbe, 0xbe),
+            RenderCell::City => (0xc7, 0x93, 0x58),
+            RenderCell::Cloister => (0xf5, 0xf5, 0xf5),
+            RenderCell::Pennant => (0x00, 0x64, 0xae),
+            RenderCell::Water => (0x8f, 0xa3, 0xd7),
+            RenderCell::Corner => (0x8f, 0xb9, 0x2d),
+        }
+    }
+}
+
+impl MeepleColor {
+    pub(crate) fn rgb(&self) -> (u8, u8, u8) {
+        let Color::TrueColor { r, g, b } = self.render_color(&RenderStyle::TrueColor) else {
+            unreachable!("true color meeple should have an rgb colour")
+        };
+
+        (r, g, b)
+    }
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+impl Board {
+    /// Draws the board as an SVG image, each tile cell as a coloured square and each meeple as a
+    /// circle in its player's colour
+    pub fn render_svg(&self) -> String {
+        self.render_svg_with_artwork(&TileArtwork::default())
+    }
+
+    /// Draws the board as an SVG image using the artwork for tiles that have it
+    pub fn render_svg_with_artwork(&self, artwork: &TileArtwork) -> String {
+        let tile_size = TILE_WIDTH * CELL_SIZE;
+
+        let (min_x, min_y, max_x, max_y) = self.placed_tiles.keys().fold(
+            (0, 0, 0, 0),
+            |(min_x, min_y, max_x, max_y), &BoardCoordinate { x, y }| (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)),
+        );
+
+        let width = (max_x - min_x + 1) as usize * tile_size;
+        let height = (max_y - min_y + 1) as usize * tile_size;
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+
+        for tile in self.placed_tiles.values() {
+            let left = (tile.placement.coordinate.x - min_x) as usize * tile_size;
+            let top = (tile.placement.coordinate.y - min_y) as usize * tile_size;
+
+            write!(svg, r#"<g transform="translate({left} {top})"><title>{}</title>"#, tile.tile.name()).unwrap();
+
+            if !artwork.draw(&mut svg, tile, tile_size) {
+                draw_cells(&mut svg, tile);
+            }
+
+            if let Some((TileCoordinate { x, y }, meeple)) = tile.meeple_render_coordinate() {
+                write!(
+                    svg,
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}" stroke="black"/>"#,
+                    x * CELL_SIZE + CELL_SIZE / 2,
+                    y * CELL_SIZE + CELL_SIZE / 2,
+                    CELL_SIZE * 3 / 4,
+                    hex(meeple.color.rgb())
+                )
+                .unwrap();
+            }
+
+            write!(svg, r#"<rect width="{tile_size}" height="{tile_size}" fill="none" stroke="black" stroke-opacity="0.3"/></g>"#).unwrap();
+        }
+
+        svg.push_str("</svg>");
+
+        svg
+    }
+}
+
+/// Width of each board on a [ContactSheet], in pixels
+const THUMBNAIL_SIZE: usize = 240;
+
+/// The final board of every game it observes, drawn small and side by side on one HTML page, to
+/// see at a glance whether a bot builds boards of a strange shape
+#[derive(Default)]
+pub struct ContactSheet {
+    /// The board of each game being played and its players, who the captions name
+    games: HashMap<usize, (ReplayState, IndexMap<PlayerIdentifier, Player>)>,
+    /// Each game's final board as an SVG, with a caption of its result, in the order they ended
+    boards: Vec<(usize, String, String)>,
+}
+
+impl ContactSheet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The page of boards, in the order the games were played
+    pub fn render_html(&self) -> String {
+        let mut boards = self.boards.clone();
+        boards.sort_by_key(|(game_index, ..)| *game_index);
+
+        let mut html = format!(
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Final boards</title><style>\
+             body {{ font-family: sans-serif; display: flex; flex-wrap: wrap; gap: 12px; }}\
+             figure {{ margin: 0; width: {THUMBNAIL_SIZE}px; }}\
+             figure svg {{ width: 100%; height: {THUMBNAIL_SIZE}px; }}\
+             figcaption {{ font-size: 12px; }}</style></head><body>"
+        );
+
+        for (_, svg, caption) in boards {
+            write!(html, "<figure>{}<figcaption>{}</figcaption></figure>", svg.replacen("<svg ", "<svg preserveAspectRatio=\"xMidYMid meet\" ", 1), caption).unwrap();
+        }
+
+        html.push_str("</body></html>");
+
+        html
+    }
+}
+
+impl GameObserver for ContactSheet {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { game_index, players, rules, .. } => {
+                let named = players
+                    .iter()
+                    .map(|(color, name, bot)| {
+                        let player = Player::new(*color);
+
+                        let player = match name.clone().or_else(|| bot.map(str::to_string)) {
+                            Some(name) => player.with_name(name),
+                            None => player,
+                        };
+
+                        (*color, player)
+                    })
+                    .collect();
+
+                self.games.insert(*game_index, (follow(players.iter().map(|(color, _, _)| *color), rules), named));
+            }
+            GameEvent::TurnCompleted { game_index, player, tile, placement, .. } => {
+                if let Some((state, _)) = self.games.get_mut(game_index) {
+                    // a turn that can't be followed only leaves the drawing out of date
+                    let _ = state.apply(&replay_turn(*player, tile, placement));
+                }
+            }
+            GameEvent::MeepleRecalled { game_index, player, coordinate, region_index } => {
+                if let Some((state, _)) = self.games.get_mut(game_index) {
+                    state.recall_meeple(*player, coordinate, *region_index);
+                }
+            }
+            GameEvent::GameCompleted { game_index, score } => {
+                let Some((state, players)) = self.games.remove(game_index) else {
+                    return;
+                };
+
+                let result: Vec<_> = score.to_named(&players).iter().map(|named| format!("{} {}", escape(&named.name), named.points)).collect();
+                let caption = format!("Game {}: {}", game_index + 1, result.join(", "));
+
+                self.boards.push((*game_index, state.board.render_svg(), caption));
+            }
+            GameEvent::RegionCompleted { .. } | GameEvent::PlayerResigned { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } => {}
+        }
+    }
+}
+
+fn draw_cells(svg: &mut String, tile: &PlacedTile) {
+    for (row_idx, row) in tile.tile.render.rotated(tile.placement.rotation).enumerate() {
+        for (column_idx, cell) in row.enumerate() {
+            write!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{CELL_SIZE}" height="{CELL_SIZE}" fill="{}"/>"#,
+                column_idx * CELL_SIZE,
+                row_idx * CELL_SIZE,
+                hex(cell.rgb())
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// The text with the characters HTML reads as markup written as entities
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use crate::test_util::TestPlayer;
+    use crate::tile::{PlacedTile, Rotation};
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+
+    #[test]
+    fn should_draw_every_tile_and_meeple() {
+        let mut alice = Player::red();
+
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            alice.move_with_meeple(&SIDE_CITY, -1, 0, Rotation::R90, 1),
+        ])
+        .unwrap();
+
+        let svg = board.render_svg();
+
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+        assert!(svg.contains(r#"width="112" height="56""#));
+        assert_eq!(svg.matches("<g ").count(), 2);
+        assert_eq!(svg.matches("<circle").count(), 1);
+        assert!(svg.contains(r##"fill="#c20019""##));
+    }
+
+    #[test]
+    fn should_draw_tiles_with_artwork_and_fall_back_to_cells() {
+        let board = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, -1, 0, Rotation::R90)]).unwrap();
+
+        let artwork = TileArtwork::default().with_tile(SIDE_CITY.id(), b"png");
+        let svg = board.render_svg_with_artwork(&artwork);
+
+        assert_eq!(svg.matches("<image").count(), 1);
+        assert!(svg.contains(r#"<g transform="rotate(90 28 28)"><image href="data:image/png;base64,cG5n""#));
+        // the road has no artwork so is drawn as its 7x7 cells, plus the tile borders
+        assert_eq!(svg.matches("<rect").count(), TILE_WIDTH * TILE_WIDTH + 2);
+
+        let sheet = artwork.with_sprite_sheet(b"sheet", 4);
+        let svg = board.render_svg_with_artwork(&sheet);
+
+        let id = STRAIGHT_ROAD.id() as usize;
+        assert!(svg.contains(&format!(r#"viewBox="{} {} 56 56""#, id % 4 * 56, id / 4 * 56)));
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn should_draw_the_final_board_of_every_game_on_one_page() {
+        use crate::bot_strategy::{BotStrategy, LazyBot};
+        use crate::game_logic::Game;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let sheet = Rc::new(RefCell::new(ContactSheet::new()));
+
+        for game_index in [1, 0] {
+            Game::builder()
+                .with_player(Player::red().with_name("Alice & Bob"), BotStrategy::Lazy(LazyBot))
+                .with_player(Player::green(), BotStrategy::Lazy(LazyBot))
+                .with_tile_order([SIDE_CITY.id(), STRAIGHT_ROAD.id()])
+                .build()
+                .unwrap()
+                .play_observed(game_index, &mut [Box::new(Rc::clone(&sheet))]);
+        }
+
+        let html = sheet.borrow().render_html();
+
+        assert!(html.starts_with("<!DOCTYPE html>") && html.ends_with("</html>"));
+        assert_eq!(html.matches("<figure><svg preserveAspectRatio").count(), 2);
+        assert!(html.find("Game 1: ").unwrap() < html.find("Game 2: ").unwrap());
+        // players are named, or called after the bot playing them
+        assert!(html.contains("Game 1: Alice &amp; Bob 0, lazy 0"), "{}", html);
+    }
+
+    #[test]
+    fn should_load_artwork_named_by_tile_id() {
+        let directory = std::env::temp_dir().join(format!("carcassonne-artwork-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(directory.join(format!("{}.png", SIDE_CITY.id())), b"png").unwrap();
+
+        let artwork = TileArtwork::from_directory(&directory).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(artwork.tiles.keys().collect::<Vec<_>>(), vec![&SIDE_CITY.id()]);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::board::Board;
+use crate::tile::{BoardCoordinate, PlacedTile, Region, Rotation, PERIMETER_REGION_DIRECTIONS};
+
+/// One of the 8 ways to turn or flip the whole board
+#[derive(Debug, Clone, Copy)]
+struct Symmetry {
+    /// Turned clockwise
+    rotation: Rotation,
+    /// Mirrored left to right before rotating
+    reflected: bool,
+}
+
+impl Symmetry {
+    fn all() -> impl Iterator<Item = Symmetry> {
+        [false, true].into_iter().flat_map(|reflected| Rotation::ALL.into_iter().map(move |rotation| Symmetry { rotation, reflected }))
+    }
+
+    fn coordinate(&self, coordinate: &BoardCoordinate) -> (i8, i8) {
+        let (mut x, mut y) = (coordinate.x, coordinate.y);
+
+        if self.reflected {
+            x = -x;
+        }
+
+        for _ in 0..self.rotation.quarter_turns() {
+            // north (negative y) turns to east (positive x)
+            (x, y) = (-y, x);
+        }
+
+        (x, y)
+    }
+
+    /// Where a position around the edge of a tile ends up, as an index into
+    /// [PERIMETER_REGION_DIRECTIONS]
+    fn perimeter_index(&self, index: usize) -> u8 {
+        let index = if self.reflected { (12 + 2 - index) % 12 } else { index };
+
+        ((index + self.rotation.quarter_turns() as usize * 3) % 12) as u8
+    }
+}
+
+impl Board {
+    /// Hash of the position that is the same for every rotation and reflection of the whole board,
+    /// so positions that are transpositions of each other can be recognised as one. Stable across
+    /// builds, so hashes can be stored
+    pub fn canonical_hash(&self) -> u64 {
+        let canonical = Symmetry::all()
+            .map(|symmetry| self.describe(symmetry))
+            .min()
+            .expect("there should be a symmetry");
+
+        fnv1a(&canonical)
+    }
+
+    /// Byte description of the board as seen after applying `symmetry`. Tiles are described by
+    /// their regions rather than their definitions, as a reflected tile needn't have a definition
+    /// of its own
+    fn describe(&self, symmetry: Symmetry) -> Vec<u8> {
+        let mut tiles: Vec<((i8, i8), Vec<u8>)> = self
+            .placed_tiles
+            .values()
+            .map(|tile| (symmetry.coordinate(&tile.placement.coordinate), describe_tile(tile, symmetry)))
+            .collect();
+
+        tiles.sort();
+
+        tiles
+            .into_iter()
+            .flat_map(|((x, y), description)| [x as u8, y as u8, description.len() as u8].into_iter().chain(description))
+            .collect()
+    }
+}
+
+fn describe_tile(tile: &PlacedTile, symmetry: Symmetry) -> Vec<u8> {
+    let meeple_region = tile.meeple.as_ref().map(|(region_index, meeple)| (**region_index, meeple.color.to_byte()));
+
+    let mut regions: Vec<Vec<u8>> = tile
+        .tile
+        .regions
+        .iter()
+        .enumerate()
+        .map(|(index, region)| {
+            let mut edges: Vec<u8> = region
+                .edges()
+                .iter()
+                .map(|edge| {
+                    let position = PERIMETER_REGION_DIRECTIONS.iter().position(|d| d == &edge.rotate(tile.placement.rotation));
+                    symmetry.perimeter_index(position.expect("edges should be on the perimeter"))
+                })
+                .collect();
+
+            edges.sort();
+
+            let pennant = matches!(region, Region::City { pennant: true, .. });
+
+            let meeple = match meeple_region {
+                Some((region_index, color)) if region_index == index => color,
+                _ => u8::MAX,
+            };
+
+            [region.region_type() as u8, pennant as u8, meeple].into_iter().chain(edges).collect()
+        })
+        .collect();
+
+    regions.sort();
+
+    regions.into_iter().flat_map(|region| [region.len() as u8].into_iter().chain(region)).collect()
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Player;
+    use crate::test_util::TestPlayer;
+    use crate::tile_definitions::{CORNER_ROAD, SIDE_CITY, STRAIGHT_ROAD, STRAIGHT_ROAD_WITH_SIDE_CITY};
+
+    #[test]
+    fn should_hash_rotated_and_reflected_boards_the_same() {
+        let mut alice = Player::red();
+
+        let board = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, 0, -1, Rotation::R0),
+            alice.move_with_meeple(&SIDE_CITY, 1, 0, Rotation::R270, 1),
+        ])
+        .unwrap();
+
+        // the same position turned a quarter clockwise
+        let rotated = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R90),
+            PlacedTile::new(&CORNER_ROAD, 1, 0, Rotation::R90),
+            alice.move_with_meeple(&SIDE_CITY, 0, 1, Rotation::R0, 1),
+        ])
+        .unwrap();
+
+        // and mirrored left to right
+        let reflected = Board::new_with_tiles([
+            PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0),
+            PlacedTile::new(&CORNER_ROAD, 0, -1, Rotation::R90),
+            alice.move_with_meeple(&SIDE_CITY, -1, 0, Rotation::R90, 1),
+        ])
+        .unwrap();
+
+        assert_eq!(board.canonical_hash(), rotated.canonical_hash());
+        assert_eq!(board.canonical_hash(), reflected.canonical_hash());
+    }
+
+    #[test]
+    fn should_hash_different_positions_differently() {
+        let board = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, 1, 0, Rotation::R270)]).unwrap();
+        let other = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&STRAIGHT_ROAD_WITH_SIDE_CITY, 1, 0, Rotation::R90)]).unwrap();
+
+        let mut alice = Player::red();
+        let with_meeple = Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), alice.move_with_meeple(&SIDE_CITY, 1, 0, Rotation::R270, 1)]).unwrap();
+
+        assert_ne!(board.canonical_hash(), other.canonical_hash());
+        assert_ne!(board.canonical_hash(), with_meeple.canonical_hash());
+    }
+}
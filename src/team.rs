@@ -0,0 +1,98 @@
+use crate::player::{Player, PlayerIdentifier};
+use crate::score::Score;
+#[cfg(feature = "render")]
+use crate::tile::RenderStyle;
+#[cfg(feature = "render")]
+use colored::Colorize;
+use indexmap::IndexMap;
+
+/// Players who pool their points. Each still places and counts their own meeple; only the final
+/// score is shared
+#[derive(Debug, Clone, PartialEq)]
+pub struct Team {
+    pub players: Vec<PlayerIdentifier>,
+}
+
+impl Team {
+    pub fn new<I: IntoIterator<Item = PlayerIdentifier>>(players: I) -> Self {
+        Self { players: players.into_iter().collect() }
+    }
+
+    pub fn contains(&self, player: PlayerIdentifier) -> bool {
+        self.players.contains(&player)
+    }
+
+    /// The team's members' names joined together, e.g. "Alice & Carol"
+    pub fn name(&self, players: &IndexMap<PlayerIdentifier, Player>) -> String {
+        self.players
+            .iter()
+            .map(|id| players.get(id).and_then(|p| p.name.clone()).unwrap_or_else(|| id.to_string()))
+            .collect::<Vec<_>>()
+            .join(" & ")
+    }
+
+    /// The sum of the members' scores
+    pub fn score(&self, score: &Score) -> i32 {
+        score.iter().filter(|(id, _)| self.contains(**id)).map(|(_, points)| *points).sum()
+    }
+}
+
+/// Teams with their summed score, best first
+pub fn team_ranking<'t>(teams: &'t [Team], score: &Score) -> Vec<(&'t Team, i32)> {
+    let mut ranking: Vec<_> = teams.iter().map(|team| (team, team.score(score))).collect();
+
+    ranking.sort_by_key(|(_, points)| -points);
+
+    ranking
+}
+
+impl Score {
+    /// The score table by team, best team first, with each member's own points beneath their
+    /// team's total
+    #[cfg(feature = "render")]
+    pub fn render_teams(&self, players: &IndexMap<PlayerIdentifier, Player>, teams: &[Team], render_style: &RenderStyle) -> String {
+        let mut out = String::new();
+
+        for (team, points) in team_ranking(teams, self) {
+            out += format!("{} = {}\n", team.name(players), points).as_str();
+
+            for player_id in &team.players {
+                let Some(player) = players.get(player_id) else {
+                    continue;
+                };
+
+                let name = player.name.clone().unwrap_or_else(|| player_id.to_string());
+                let points = self.iter().find(|(id, _)| *id == player_id).map(|(_, points)| *points).unwrap_or(0);
+
+                out += format!("  {} = {}\n", name.color(player_id.render_color(render_style)), points).as_str();
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_rank_teams_by_their_summed_score() {
+        let alice = Player::red().with_name("Alice");
+        let bob = Player::green().with_name("Bob");
+        let carol = Player::blue().with_name("Carol");
+        let dave = Player::yellow().with_name("Dave");
+
+        let players: IndexMap<_, _> = [&alice, &bob, &carol, &dave].into_iter().map(|p| (p.meeple_color, p.clone())).collect();
+        let teams = [Team::new([alice.meeple_color, carol.meeple_color]), Team::new([bob.meeple_color, dave.meeple_color])];
+
+        // Bob has the best score of anyone, but Alice and Carol have more together
+        let score = Score::from_iter([(&alice, 10), (&bob, 15), (&carol, 8), (&dave, 0)]);
+
+        let ranking = team_ranking(&teams, &score);
+
+        assert_eq!(ranking[0], (&teams[0], 18));
+        assert_eq!(ranking[1], (&teams[1], 15));
+        assert_eq!(teams[0].name(&players), "Alice & Carol");
+    }
+}
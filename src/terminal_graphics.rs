@@ -0,0 +1,343 @@
+use crate::board::Board;
+use crate::tile::{BoardCoordinate, RenderStyle, TileCoordinate, TILE_WIDTH};
+use base64::{engine::general_purpose, Engine as _};
+use std::env;
+use std::fmt::Write;
+
+/// Size of a tile cell in the bitmap, in pixels
+const CELL_PIXELS: usize = 6;
+
+/// Most base64 the kitty protocol accepts in one escape sequence
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+const EMPTY: (u8, u8, u8) = (0, 0, 0);
+const MEEPLE_OUTLINE: (u8, u8, u8) = (0, 0, 0);
+
+/// An rgb image, row by row
+pub(crate) struct Bitmap {
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) pixels: Vec<(u8, u8, u8)>,
+}
+
+impl Bitmap {
+    fn fill(&mut self, left: usize, top: usize, size: usize, colour: (u8, u8, u8)) {
+        for y in top..top + size {
+            self.pixels[y * self.width + left..y * self.width + left + size].fill(colour);
+        }
+    }
+
+    /// The image as a kitty graphics protocol escape sequence, split into chunks as the protocol
+    /// requires
+    pub(crate) fn to_kitty(&self) -> String {
+        let rgb: Vec<u8> = self.pixels.iter().flat_map(|&(r, g, b)| [r, g, b]).collect();
+        let encoded = general_purpose::STANDARD.encode(rgb);
+
+        let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+        let mut output = String::new();
+
+        for (index, chunk) in chunks.iter().enumerate() {
+            let more = (index + 1 < chunks.len()) as u8;
+            let chunk = std::str::from_utf8(chunk).expect("base64 should be ascii");
+
+            if index == 0 {
+                write!(output, "\x1b_Ga=T,f=24,s={},v={},m={};{}\x1b\\", self.width, self.height, more, chunk).unwrap();
+            } else {
+                write!(output, "\x1b_Gm={};{}\x1b\\", more, chunk).unwrap();
+            }
+        }
+
+        output
+    }
+
+    /// The image as a sixel escape sequence. Sixels are six pixel tall bands drawn once per colour,
+    /// each column a character whose bits say which of the six pixels take the colour
+    pub(crate) fn to_sixel(&self) -> String {
+        let mut palette: Vec<(u8, u8, u8)> = vec![];
+
+        for pixel in &self.pixels {
+            if !palette.contains(pixel) {
+                palette.push(*pixel);
+            }
+        }
+
+        let mut output = format!("\x1bPq\"1;1;{};{}", self.width, self.height);
+
+        for (index, (r, g, b)) in palette.iter().enumerate() {
+            // sixel colours are percentages
+            let percent = |channel: &u8| *channel as usize * 100 / 255;
+            write!(output, "#{};2;{};{};{}", index, percent(r), percent(g), percent(b)).unwrap();
+        }
+
+        for band in (0..self.height).step_by(6) {
+            for (index, colour) in palette.iter().enumerate() {
+                let columns: Vec<u8> = (0..self.width)
+                    .map(|x| {
+                        (0..6)
+                            .filter(|row| band + row < self.height && &self.pixels[(band + row) * self.width + x] == colour)
+                            .fold(0, |bits, row| bits | 1 << row)
+                    })
+                    .collect();
+
+                if columns.iter().all(|bits| *bits == 0) {
+                    continue;
+                }
+
+                write!(output, "#{}", index).unwrap();
+                write_run_length_encoded(&mut output, &columns);
+                output.push('$');
+            }
+
+            output.push('-');
+        }
+
+        output.push_str("\x1b\\");
+
+        output
+    }
+}
+
+fn write_run_length_encoded(output: &mut String, columns: &[u8]) {
+    let mut columns = columns.iter().peekable();
+
+    while let Some(bits) = columns.next() {
+        let mut count = 1;
+
+        while columns.next_if_eq(&bits).is_some() {
+            count += 1;
+        }
+
+        let sixel = (63 + bits) as char;
+
+        if count > 3 {
+            write!(output, "!{}{}", count, sixel).unwrap();
+        } else {
+            output.extend(std::iter::repeat_n(sixel, count));
+        }
+    }
+}
+
+impl Board {
+    /// Draws the board as a bitmap, each tile cell as a square of colour and each meeple as a
+    /// square of its player's colour
+    pub(crate) fn render_bitmap(&self) -> Bitmap {
+        self.render_bitmap_within(self.bounding_box().unwrap_or((BoardCoordinate::new(0, 0), BoardCoordinate::new(0, 0))))
+    }
+
+    /// Draws the part of the board between the corners, inclusive, e.g. every frame of an animated
+    /// game the size of its final board
+    pub(crate) fn render_bitmap_within(&self, (min, max): (BoardCoordinate, BoardCoordinate)) -> Bitmap {
+        let tile_pixels = TILE_WIDTH * CELL_PIXELS;
+        let (BoardCoordinate { x: min_x, y: min_y }, BoardCoordinate { x: max_x, y: max_y }) = (min, max);
+
+        let width = (max_x - min_x + 1) as usize * tile_pixels;
+        let height = (max_y - min_y + 1) as usize * tile_pixels;
+
+        let mut bitmap = Bitmap { width, height, pixels: vec![EMPTY; width * height] };
+
+        let within = |coordinate: &BoardCoordinate| (min_x..=max_x).contains(&coordinate.x) && (min_y..=max_y).contains(&coordinate.y);
+
+        for tile in self.placed_tiles.values().filter(|tile| within(&tile.placement.coordinate)) {
+            let left = (tile.placement.coordinate.x - min_x) as usize * tile_pixels;
+            let top = (tile.placement.coordinate.y - min_y) as usize * tile_pixels;
+
+            for (row_idx, row) in tile.tile.render.rotated(tile.placement.rotation).enumerate() {
+                for (column_idx, cell) in row.enumerate() {
+                    bitmap.fill(left + column_idx * CELL_PIXELS, top + row_idx * CELL_PIXELS, CELL_PIXELS, cell.rgb());
+                }
+            }
+
+            if let Some((TileCoordinate { x, y }, meeple)) = tile.meeple_render_coordinate() {
+                let (meeple_left, meeple_top) = (left + x * CELL_PIXELS, top + y * CELL_PIXELS);
+
+                bitmap.fill(meeple_left, meeple_top, CELL_PIXELS, MEEPLE_OUTLINE);
+                bitmap.fill(meeple_left + 1, meeple_top + 1, CELL_PIXELS - 2, meeple.color.rgb());
+            }
+        }
+
+        bitmap
+    }
+}
+
+impl RenderStyle {
+    /// The richest style the terminal the process is running in supports, judged from its
+    /// environment variables
+    pub fn detect() -> Self {
+        Self::detect_from(|name| env::var(name).ok())
+    }
+
+    fn detect_from(var: impl Fn(&str) -> Option<String>) -> Self {
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+
+        // https://no-color.org asks for no colour whenever NO_COLOR is set and not empty
+        if term == "dumb" || var("NO_COLOR").is_some_and(|no_color| !no_color.is_empty()) {
+            return RenderStyle::Ascii;
+        }
+
+        if var("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" || ["WezTerm", "ghostty"].contains(&program.as_str()) {
+            return RenderStyle::Kitty;
+        }
+
+        if term.contains("sixel") || ["mlterm", "foot"].iter().any(|sixel_term| term.starts_with(sixel_term)) {
+            return RenderStyle::Sixel;
+        }
+
+        match var("COLORTERM").as_deref() {
+            Some("truecolor" | "24bit") => RenderStyle::TrueColor,
+            _ => RenderStyle::Ansi,
+        }
+    }
+
+    /// The style by the name it's asked for by on the command line
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ascii" => Some(RenderStyle::Ascii),
+            "ansi" => Some(RenderStyle::Ansi),
+            "truecolor" => Some(RenderStyle::TrueColor),
+            "kitty" => Some(RenderStyle::Kitty),
+            "sixel" => Some(RenderStyle::Sixel),
+            _ => None,
+        }
+    }
+
+    /// How much a terminal has to support to draw the style, from plain text up to inline images
+    fn richness(&self) -> u8 {
+        match self {
+            RenderStyle::Ascii => 0,
+            RenderStyle::Ansi => 1,
+            RenderStyle::TrueColor => 2,
+            RenderStyle::Kitty | RenderStyle::Sixel => 3,
+        }
+    }
+
+    /// The style, or the richest one short of it that a terminal supporting `supported` can draw.
+    /// One image protocol is no use to a terminal speaking the other, which gets true colour
+    fn within(self, supported: RenderStyle) -> Self {
+        if self == supported || self.richness() < supported.richness() {
+            self
+        } else if self.richness() == supported.richness() {
+            RenderStyle::TrueColor.within(supported)
+        } else {
+            supported
+        }
+    }
+}
+
+/// Decides the style everything is drawn in, so a style asked for on the command line is held to
+/// what the terminal can show rather than filling a basic one with 24-bit escape sequences
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RenderConfig {
+    requested: Option<RenderStyle>,
+    forced: bool,
+}
+
+impl RenderConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draws in `style` where the terminal supports it, or the richest style it does otherwise.
+    /// Without one, the richest style the terminal supports is used
+    pub fn with_style(mut self, style: Option<RenderStyle>) -> Self {
+        self.requested = style;
+        self
+    }
+
+    /// Draws in the requested style even when the terminal doesn't look like it supports it, for
+    /// terminals that don't say what they can do, or output going somewhere that isn't a terminal
+    pub fn forced(mut self, forced: bool) -> Self {
+        self.forced = forced;
+        self
+    }
+
+    /// The style to draw in, judged against the terminal the process is running in
+    pub fn resolve(&self) -> RenderStyle {
+        self.resolve_from(|name| env::var(name).ok())
+    }
+
+    fn resolve_from(&self, var: impl Fn(&str) -> Option<String>) -> RenderStyle {
+        match self.requested {
+            Some(style) if self.forced => style,
+            Some(style) => style.within(RenderStyle::detect_from(var)),
+            None => RenderStyle::detect_from(var),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::{PlacedTile, Rotation};
+    use crate::tile_definitions::{SIDE_CITY, STRAIGHT_ROAD};
+    use std::collections::HashMap;
+
+    fn board() -> Board {
+        Board::new_with_tiles([PlacedTile::new(&STRAIGHT_ROAD, 0, 0, Rotation::R0), PlacedTile::new(&SIDE_CITY, -1, 0, Rotation::R90)]).unwrap()
+    }
+
+    #[test]
+    fn should_send_the_board_to_kitty_in_chunks() {
+        let kitty = board().render(&RenderStyle::Kitty);
+
+        // 84x42 pixels of rgb is 14 kilobytes of base64
+        assert!(kitty.starts_with("\x1b_Ga=T,f=24,s=84,v=42,m=1;"));
+        assert_eq!(kitty.matches("\x1b_G").count(), 4);
+        assert!(kitty.contains("\x1b_Gm=0;"));
+        assert!(kitty.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn should_draw_the_board_in_sixels() {
+        let sixel = board().render(&RenderStyle::Sixel);
+
+        assert!(sixel.starts_with("\x1bPq\"1;1;84;42"));
+        // field, road, city and corner colours; corners share the field colour
+        assert_eq!(sixel.matches(";2;").count(), 3);
+        // 42 pixels is seven bands
+        assert_eq!(sixel.matches('-').count(), 7);
+        assert!(sixel.ends_with("\x1b\\"));
+    }
+
+    #[test]
+    fn should_run_length_encode_repeated_columns() {
+        let mut output = String::new();
+        write_run_length_encoded(&mut output, &[0, 0, 0, 0, 0, 63, 1, 1]);
+
+        assert_eq!(output, "!5?~@@");
+    }
+
+    #[test]
+    fn should_detect_graphics_support_from_the_environment() {
+        let detect = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            RenderStyle::detect_from(|name| vars.get(name).cloned())
+        };
+
+        assert_eq!(detect(&[("TERM", "xterm-kitty")]), RenderStyle::Kitty);
+        assert_eq!(detect(&[("TERM_PROGRAM", "WezTerm")]), RenderStyle::Kitty);
+        assert_eq!(detect(&[("TERM", "foot")]), RenderStyle::Sixel);
+        assert_eq!(detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]), RenderStyle::TrueColor);
+        assert_eq!(detect(&[]), RenderStyle::Ansi);
+        assert_eq!(detect(&[("TERM", "dumb"), ("COLORTERM", "truecolor")]), RenderStyle::Ascii);
+        assert_eq!(detect(&[("TERM", "xterm-kitty"), ("NO_COLOR", "1")]), RenderStyle::Ascii);
+        assert_eq!(detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor"), ("NO_COLOR", "")]), RenderStyle::TrueColor);
+    }
+
+    #[test]
+    fn should_hold_the_requested_style_to_what_the_terminal_supports() {
+        let resolve = |config: RenderConfig, vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+            config.resolve_from(|name| vars.get(name).cloned())
+        };
+        let asking = |style| RenderConfig::new().with_style(Some(style));
+
+        assert_eq!(resolve(RenderConfig::new(), &[("COLORTERM", "truecolor")]), RenderStyle::TrueColor);
+        assert_eq!(resolve(asking(RenderStyle::TrueColor), &[("TERM", "xterm")]), RenderStyle::Ansi);
+        assert_eq!(resolve(asking(RenderStyle::TrueColor), &[("TERM", "dumb")]), RenderStyle::Ascii);
+        assert_eq!(resolve(asking(RenderStyle::Ascii), &[("TERM", "xterm-kitty")]), RenderStyle::Ascii);
+        assert_eq!(resolve(asking(RenderStyle::Kitty), &[("TERM", "foot")]), RenderStyle::TrueColor);
+        assert_eq!(resolve(asking(RenderStyle::Sixel), &[("TERM", "foot")]), RenderStyle::Sixel);
+        assert_eq!(resolve(asking(RenderStyle::TrueColor).forced(true), &[("TERM", "dumb")]), RenderStyle::TrueColor);
+    }
+}
@@ -1,73 +1,93 @@
-#[cfg(test)]
-pub(crate) mod tests {
-    use crate::board::{Board, TilePlacementSuccess};
-    use crate::move_hints::MoveHint;
-    use crate::player::{Player, RegionIndex};
-    use crate::score::Score;
-    use crate::tile::{PlacedTile, RenderStyle, TileDefinition};
-
-    pub(crate) trait TestMoveHint {
-        fn should_have_hint_placements<T : IntoIterator<Item = &'static str>>(&self, placements: T);
-    }
+//! Helpers for testing bots and rule variants against the engine: building up boards move by
+//! move and checking the scores and move hints they give. Public with the `test-support` feature
+
+use crate::board::{Board, TilePlacementSuccess};
+use crate::move_hints::MoveHint;
+use crate::player::{Player, RegionIndex};
+use crate::score::Score;
+use crate::tile::{BoardCoordinate, PlacedTile, Rotation, TileDefinition, TilePlacement};
+
+/// Assertions on the moves offered for a tile
+pub trait TestMoveHint {
+    /// Checks the hints are exactly `placements`, in any order, each written `x,y @rotations` with
+    /// ` [region index]` after it when a meeple is placed, e.g. `0,1 @2 [1]`
+    fn should_have_hint_placements<T : IntoIterator<Item = &'static str>>(&self, placements: T);
+}
 
-    impl TestMoveHint for Vec<MoveHint> {
-        fn should_have_hint_placements<T : IntoIterator<Item = &'static str>>(&self, placements: T) {
-            let mut expectation: Vec<_> = placements.into_iter().collect();
-            expectation.sort();
+impl TestMoveHint for Vec<MoveHint> {
+    fn should_have_hint_placements<T : IntoIterator<Item = &'static str>>(&self, placements: T) {
+        let mut expectation: Vec<_> = placements.into_iter().map(read_hint_placement).collect();
+        expectation.sort();
 
-            let mut test: Vec<_> = self.iter().map(|hint|
-                format!("{},{} @{}{}", hint.tile_placement.coordinate.x, hint.tile_placement.coordinate.y, hint.tile_placement.rotations, hint.meeple_placement.map_or("".to_string(), |r|format!(" [{}]", *r)))
-            ).collect();
-            test.sort();
+        let mut test: Vec<_> = self.iter().map(|hint| (hint.tile_placement.clone(), hint.meeple_placement)).collect();
+        test.sort();
 
-            assert_eq!(test, expectation)
-        }
+        assert_eq!(test, expectation)
     }
+}
 
+/// The placement and meeple region of a hint written `x,y @rotations [region index]`
+fn read_hint_placement(written: &str) -> (TilePlacement, Option<RegionIndex>) {
+    let read = || {
+        let (coordinate, rest) = written.split_once(" @")?;
+        let (x, y) = coordinate.split_once(',')?;
+        let (rotations, meeple) = match rest.split_once(' ') {
+            Some((rotations, meeple)) => (rotations, Some(meeple.strip_prefix('[')?.strip_suffix(']')?.parse().ok()?)),
+            None => (rest, None),
+        };
 
+        let placement = TilePlacement { coordinate: BoardCoordinate::new(x.parse().ok()?, y.parse().ok()?), rotation: Rotation::try_from(rotations.parse::<u8>().ok()?).ok()? };
 
-    pub(crate) trait TestConnectedRegion {
-        fn should_have_score(&self, expectation: Score);
-    }
-
-    impl TestConnectedRegion for [PlacedTile] {
-        fn should_have_score(&self, expectation: Score) {
-            let mut board = Board::default();
+        Some((placement, meeple.map(RegionIndex::new)))
+    };
 
-            let mut score = Score::new();
+    read().unwrap_or_else(|| panic!("hint placement `{}` should be written `x,y @rotations [region index]`", written))
+}
 
-            for tile in self.iter().cloned() {
-                let TilePlacementSuccess { score_delta, .. } = board.place_tile(tile).expect("tile placement should be valid");
-                score += score_delta
-            }
+/// Assertions on what a sequence of placements scores
+pub trait TestConnectedRegion {
+    /// Places the tiles on an empty board in order, panicking if any can't be placed, and checks
+    /// the points scored along the way plus the board's end of game score come to `expectation`
+    fn should_have_score(&self, expectation: Score);
+}
 
-            score += board.calculate_board_score();
+impl TestConnectedRegion for [PlacedTile] {
+    fn should_have_score(&self, expectation: Score) {
+        let mut board = Board::default();
 
-            println!("{}", board.render(&RenderStyle::Ascii));
+        let mut score = Score::new();
 
-            assert_eq!(score, expectation)
+        for tile in self.iter().cloned() {
+            let TilePlacementSuccess { score_delta, .. } = board.place_tile(tile).expect("tile placement should be valid");
+            score += score_delta
         }
-    }
-
-    pub(crate) trait TestPlayer {
 
-        fn move_with_meeple(&mut self, tile: &'static TileDefinition, x: i8, y: i8, rotations: u8, meeple_region_index: usize) -> PlacedTile;
+        score += board.calculate_board_score();
 
-        fn move_no_meeple(&self, tile: &'static TileDefinition, x: i8, y: i8, rotations: u8) -> PlacedTile;
+        assert_eq!(score, expectation)
     }
+}
+
+/// Tile placements on behalf of a player, for building up boards to test against
+pub trait TestPlayer {
+    /// The tile placed with one of the player's meeple on the region at `meeple_region_index`,
+    /// taken from their hand so they run out as they would in a game
+    fn move_with_meeple(&mut self, tile: &'static TileDefinition, x: i8, y: i8, rotation: Rotation, meeple_region_index: usize) -> PlacedTile;
 
-    impl TestPlayer for Player {
-        fn move_with_meeple(&mut self, tile: &'static TileDefinition, x: i8, y: i8, rotations: u8, meeple_region_index: usize) -> PlacedTile {
-            let mut tile = PlacedTile::new(tile, x, y, rotations);
+    /// The tile placed without a meeple
+    fn move_no_meeple(&self, tile: &'static TileDefinition, x: i8, y: i8, rotation: Rotation) -> PlacedTile;
+}
 
-            tile.meeple = Some((RegionIndex::new(meeple_region_index), self.meeple.pop().expect("player should have enough meeple")));
+impl TestPlayer for Player {
+    fn move_with_meeple(&mut self, tile: &'static TileDefinition, x: i8, y: i8, rotation: Rotation, meeple_region_index: usize) -> PlacedTile {
+        let mut tile = PlacedTile::new(tile, x, y, rotation);
 
-            tile
-        }
+        tile.meeple = Some((RegionIndex::new(meeple_region_index), self.meeple.pop().expect("player should have enough meeple")));
 
-        fn move_no_meeple(&self, tile: &'static TileDefinition, x: i8, y: i8, rotations: u8) -> PlacedTile {
-            PlacedTile::new(tile, x, y, rotations)
-        }
+        tile
     }
 
+    fn move_no_meeple(&self, tile: &'static TileDefinition, x: i8, y: i8, rotation: Rotation) -> PlacedTile {
+        PlacedTile::new(tile, x, y, rotation)
+    }
 }
@@ -0,0 +1,98 @@
+//! Picking between equally good moves the same way every time. Move hints are found in parallel,
+//! so they come in a different order from one run to the next, and a bot taking the first or last
+//! of its best moves would play differently from the same seed. These functions put the hints in a
+//! fixed order before looking for the best, then break any tie with the bot's own generator, or
+//! failing that by taking the first of them.
+
+use crate::move_hints::MoveHint;
+use crate::rng::GameRng;
+use crate::tile::Rotation;
+use rand::prelude::SliceRandom;
+use std::cmp::Ordering;
+
+/// Where the hint puts the tile, then its rotation, then its meeple, with no meeple first
+fn canonical_key(hint: &MoveHint) -> (i8, i8, Rotation, Option<usize>) {
+    let coordinate = hint.tile_placement.coordinate;
+
+    (coordinate.x, coordinate.y, hint.tile_placement.rotation, hint.meeple_placement.map(|region_index| *region_index))
+}
+
+/// Sorts `hints` into the same order however they were found: by the coordinate they place the
+/// tile at, then rotation, then the region they place a meeple in
+pub fn canonical_order(hints: &mut [MoveHint]) {
+    hints.sort_by_key(canonical_key);
+}
+
+/// Index into `hints` of the best of `ratings`, one for each hint. Equally rated hints are picked
+/// between at random with `rng`, or without one the first in [canonical_order] is
+pub fn best_index<R: PartialOrd>(hints: &[MoveHint], ratings: &[R], rng: Option<&mut GameRng>) -> Option<usize> {
+    let mut order: Vec<usize> = (0..hints.len().min(ratings.len())).collect();
+    order.sort_by_key(|index| canonical_key(&hints[*index]));
+
+    let compare = |a: &usize, b: &usize| ratings[*a].partial_cmp(&ratings[*b]).unwrap_or(Ordering::Equal);
+
+    let best = *order.iter().max_by(|a, b| compare(a, b))?;
+    let ties: Vec<usize> = order.into_iter().filter(|index| compare(index, &best) == Ordering::Equal).collect();
+
+    match rng {
+        Some(rng) => ties.choose(rng).copied(),
+        None => ties.first().copied(),
+    }
+}
+
+/// The hint with the highest `key`, with ties broken as by [best_index]
+pub fn best_by_key<'a, K: PartialOrd>(hints: &'a [MoveHint], rng: Option<&mut GameRng>, key: impl FnMut(&MoveHint) -> K) -> Option<&'a MoveHint> {
+    let ratings: Vec<K> = hints.iter().map(key).collect();
+
+    best_index(hints, &ratings, rng).map(|index| &hints[index])
+}
+
+/// Any one of `hints`, picked the same way from the same generator whatever order they are in
+pub fn random<'a>(hints: &'a [MoveHint], rng: &mut GameRng) -> Option<&'a MoveHint> {
+    best_by_key(hints, Some(rng), |_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::tile_definitions::SIDE_CITY;
+    use rand::SeedableRng;
+
+    fn placement(hint: &MoveHint) -> (i8, i8, Rotation, Option<usize>) {
+        canonical_key(hint)
+    }
+
+    #[test]
+    fn should_pick_the_same_move_whatever_order_the_hints_are_in() {
+        let hints = Board::new().get_move_hints(&SIDE_CITY, true);
+        let mut reversed = hints.clone();
+        reversed.reverse();
+
+        let first = best_by_key(&hints, None, |hint| hint.meeple_placement.is_some()).map(placement);
+
+        assert_eq!(first, best_by_key(&reversed, None, |hint| hint.meeple_placement.is_some()).map(placement));
+        assert_eq!(first.and_then(|(.., meeple)| meeple), Some(0));
+
+        let random_pick = random(&hints, &mut GameRng::from_seed([7; 32])).map(placement);
+
+        assert_eq!(random_pick, random(&reversed, &mut GameRng::from_seed([7; 32])).map(placement));
+    }
+
+    #[test]
+    fn should_only_pick_between_the_best_moves() {
+        let hints = Board::new().get_move_hints(&SIDE_CITY, true);
+        let mut rng = GameRng::from_seed([1; 32]);
+
+        for _ in 0..20 {
+            let pick = best_by_key(&hints, Some(&mut rng), |hint| hint.meeple_placement.is_none()).unwrap();
+
+            assert_eq!(pick.meeple_placement, None);
+        }
+
+        let mut sorted = hints.clone();
+        canonical_order(&mut sorted);
+
+        assert!(sorted.windows(2).all(|pair| placement(&pair[0]) < placement(&pair[1])));
+    }
+}
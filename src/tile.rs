@@ -1,13 +1,16 @@
 use crate::connected_regions::{
     ConnectedRegion, ConnectedRegionId, PlacedTileEdge, PlacedTileRegion,
 };
-use crate::player::{Meeple, MeepleColor, RegionIndex};
-use crate::tile_definitions::RIVER_TERMINATOR;
+use crate::player::{Meeple, RegionIndex};
+#[cfg(feature = "render")]
+use crate::player::MeepleColor;
+use crate::tile_definitions::ALL_TILE_DEFINITIONS;
+#[cfg(feature = "render")]
 use colored::{Color, Colorize};
+use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
 use std::collections::{BTreeMap, HashMap};
-use std::fmt::Debug;
-use uuid::Uuid;
+use std::fmt::{Debug, Display, Formatter};
 
 pub const TILE_WIDTH: usize = 7;
 
@@ -17,24 +20,24 @@ pub(crate) struct TileCoordinate {
     pub(crate) y: usize,
 }
 
+#[cfg(feature = "render")]
 impl TileCoordinate {
     pub(crate) fn new(x: usize, y: usize) -> Self {
         Self { x, y }
     }
 
-    pub(crate) fn rotate_around_center(&self, rotations: u8) -> Self {
-        let rotations = rotations % 4;
-        match rotations {
-            0 => self.clone(),                                                // No rotation
-            1 => Self::new(TILE_WIDTH - 1 - self.y, self.x), // 90 degrees clockwise
-            2 => Self::new(TILE_WIDTH - 1 - self.x, TILE_WIDTH - 1 - self.y), // 180 degrees
-            3 => Self::new(self.y, TILE_WIDTH - 1 - self.x), // 270 degrees clockwise
-            _ => unreachable!(),                             // This branch is logically unreachable
+    pub(crate) fn rotate_around_center(&self, rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::R0 => self.clone(),
+            Rotation::R90 => Self::new(TILE_WIDTH - 1 - self.y, self.x),
+            Rotation::R180 => Self::new(TILE_WIDTH - 1 - self.x, TILE_WIDTH - 1 - self.y),
+            Rotation::R270 => Self::new(self.y, TILE_WIDTH - 1 - self.x),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+/// Ordered by column then row, so sorted coordinates read down the board a column at a time
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct BoardCoordinate {
     pub(crate) x: i8,
     pub(crate) y: i8,
@@ -45,16 +48,27 @@ impl BoardCoordinate {
         Self { x, y }
     }
 
+    /// Columns east of the first tile placed
+    pub fn x(&self) -> i8 {
+        self.x
+    }
+
+    /// Rows south of the first tile placed
+    pub fn y(&self) -> i8 {
+        self.y
+    }
+
+    /// The side of this coordinate `other` is on, or `None` if they aren't side by side
     pub(crate) fn direction_to_adjacent_coordinate(
         &self,
         other: BoardCoordinate,
-    ) -> CardinalDirection {
-        match (self.x - other.x, self.y - other.y) {
-            (0, 1) => CardinalDirection::North,
-            (-1, 0) => CardinalDirection::East,
-            (0, -1) => CardinalDirection::South,
-            (1, 0) => CardinalDirection::West,
-            _ => panic!("Coordinates are not adjacent!"),
+    ) -> Option<CardinalDirection> {
+        match (self.x as i16 - other.x as i16, self.y as i16 - other.y as i16) {
+            (0, 1) => Some(CardinalDirection::North),
+            (-1, 0) => Some(CardinalDirection::East),
+            (0, -1) => Some(CardinalDirection::South),
+            (1, 0) => Some(CardinalDirection::West),
+            _ => None,
         }
     }
 
@@ -96,115 +110,262 @@ impl BoardCoordinate {
     }
 }
 
-#[derive(Debug, Clone)]
+/// How the edges of the board join up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoardTopology {
+    /// An endless table, as in the real game
+    #[default]
+    Plane,
+    /// Experimental: a board `width` columns by `height` rows whose edges wrap around, so a tile
+    /// leaving one side joins the tiles on the other. Coordinates run from zero, where the first
+    /// tile is placed, up to one less than the width and height
+    Torus { width: u8, height: u8 },
+}
+
+impl BoardTopology {
+    /// The coordinate as it is found on the board, wrapped around a torus
+    pub(crate) fn wrap(&self, coordinate: BoardCoordinate) -> BoardCoordinate {
+        match self {
+            BoardTopology::Plane => coordinate,
+            BoardTopology::Torus { width, height } => BoardCoordinate::new(
+                (coordinate.x as i16).rem_euclid(*width as i16) as i8,
+                (coordinate.y as i16).rem_euclid(*height as i16) as i8,
+            ),
+        }
+    }
+
+    pub(crate) fn adjacent_in_direction(&self, coordinate: &BoardCoordinate, direction: &CardinalDirection) -> BoardCoordinate {
+        self.wrap(coordinate.adjacent_in_direction(direction))
+    }
+
+    pub(crate) fn adjacent_coordinates(&self, coordinate: &BoardCoordinate) -> BTreeMap<CardinalDirection, BoardCoordinate> {
+        coordinate.adjacent_coordinates().into_iter().map(|(direction, adjacent)| (direction, self.wrap(adjacent))).collect()
+    }
+
+    pub(crate) fn surrounding_coordinates(&self, coordinate: &BoardCoordinate) -> Vec<BoardCoordinate> {
+        coordinate.surrounding_coordinates().into_iter().map(|surrounding| self.wrap(surrounding)).collect()
+    }
+
+    /// The side of `coordinate` that `other` is on, or `None` if they aren't side by side
+    pub(crate) fn direction_to_adjacent_coordinate(&self, coordinate: &BoardCoordinate, other: BoardCoordinate) -> Option<CardinalDirection> {
+        match self {
+            BoardTopology::Plane => coordinate.direction_to_adjacent_coordinate(other),
+            BoardTopology::Torus { .. } => PRIMARY_CARDINAL_DIRECTIONS.iter().find(|direction| self.adjacent_in_direction(coordinate, direction) == other).copied(),
+        }
+    }
+}
+
+/// Ordered by coordinate then rotation
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct TilePlacement {
     pub(crate) coordinate: BoardCoordinate,
-    pub(crate) rotations: u8, // count of 90° rotations from the definition (i.e. range is 0-3 inclusive)
+    pub(crate) rotation: Rotation,
+}
+
+impl Display for BoardCoordinate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// The coordinate and the tile's clockwise rotation in degrees, e.g. `(1, -2) r90`
+impl Display for TilePlacement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.coordinate, self.rotation)
+    }
+}
+
+impl TilePlacement {
+    pub fn coordinate(&self) -> BoardCoordinate {
+        self.coordinate
+    }
+
+    /// How far the tile is turned clockwise from its definition
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+}
+
+/// How far a tile is turned clockwise from the way it is defined, a quarter turn at a time.
+/// Serialized as its number of quarter turns
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(into = "u8", try_from = "u8")]
+#[repr(u8)]
+pub enum Rotation {
+    #[default]
+    R0 = 0,
+    R90 = 1,
+    R180 = 2,
+    R270 = 3,
+}
+
+/// A number of quarter turns that isn't one of 0 to 3
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidRotation(pub u8);
+
+impl Display for InvalidRotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} quarter turns isn't a rotation, expected 0 to 3", self.0)
+    }
+}
+
+impl std::error::Error for InvalidRotation {}
+
+impl Rotation {
+    /// Every rotation, a quarter turn apart
+    pub const ALL: [Rotation; 4] = [Rotation::R0, Rotation::R90, Rotation::R180, Rotation::R270];
+
+    /// Quarter turns clockwise, 0 to 3
+    pub const fn quarter_turns(self) -> u8 {
+        self as u8
+    }
+
+    pub const fn degrees(self) -> u16 {
+        self as u16 * 90
+    }
+
+    /// The rotation turned `degrees` clockwise, if it is a whole number of quarter turns under a full turn
+    pub fn from_degrees(degrees: u16) -> Option<Self> {
+        match degrees {
+            0 => Some(Rotation::R0),
+            90 => Some(Rotation::R90),
+            180 => Some(Rotation::R180),
+            270 => Some(Rotation::R270),
+            _ => None,
+        }
+    }
+
+    /// The rotation a further `quarter_turns` clockwise, coming back round after a full turn
+    pub const fn turned(self, quarter_turns: u8) -> Self {
+        Self::ALL[((self as u8 + quarter_turns % 4) % 4) as usize]
+    }
+
+    /// The rotation that turns the tile back to how it's defined
+    pub const fn inverse(self) -> Self {
+        Rotation::R0.turned(4 - self as u8)
+    }
+}
+
+impl TryFrom<u8> for Rotation {
+    type Error = InvalidRotation;
+
+    /// The rotation of `quarter_turns` clockwise, which must be 0 to 3
+    fn try_from(quarter_turns: u8) -> Result<Self, Self::Error> {
+        Self::ALL.get(quarter_turns as usize).copied().ok_or(InvalidRotation(quarter_turns))
+    }
+}
+
+impl From<Rotation> for u8 {
+    fn from(rotation: Rotation) -> Self {
+        rotation.quarter_turns()
+    }
+}
+
+impl std::ops::Add for Rotation {
+    type Output = Rotation;
+
+    fn add(self, other: Rotation) -> Rotation {
+        self.turned(other.quarter_turns())
+    }
+}
+
+impl std::ops::Sub for Rotation {
+    type Output = Rotation;
+
+    fn sub(self, other: Rotation) -> Rotation {
+        self.turned(other.inverse().quarter_turns())
+    }
+}
+
+/// In degrees, e.g. `r90`
+impl Display for Rotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "r{}", self.degrees())
+    }
 }
 
 // note that the diagonal corners are intentionally omitted because carcassonne tiles do not form
 // connected regions from touching corners
+// Each direction's discriminant is its position clockwise from north, so turning and reflecting
+// directions is arithmetic modulo 12
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[repr(u8)]
 pub(crate) enum CardinalDirection {
-    North,
-    NorthNorthEast,
+    North = 0,
+    NorthNorthEast = 1,
     //  NorthEast,
-    EastNorthEast,
-    East,
-    EastSouthEast,
+    EastNorthEast = 2,
+    East = 3,
+    EastSouthEast = 4,
     //  SouthEast,
-    SouthSouthEast,
-    South,
-    SouthSouthWest,
+    SouthSouthEast = 5,
+    South = 6,
+    SouthSouthWest = 7,
     //  SouthWest,
-    WestSouthWest,
-    West,
-    WestNorthWest,
+    WestSouthWest = 8,
+    West = 9,
+    WestNorthWest = 10,
 
     //  NorthWest,
-    NorthNorthWest,
+    NorthNorthWest = 11,
 }
 
-// @todo the methods here are jank or verbose, and there are almost certainly better approaches.
-impl CardinalDirection {
+/// Every direction, indexed by its discriminant
+const CLOCKWISE_DIRECTIONS: [CardinalDirection; 12] = [
+    CardinalDirection::North,
+    CardinalDirection::NorthNorthEast,
+    CardinalDirection::EastNorthEast,
+    CardinalDirection::East,
+    CardinalDirection::EastSouthEast,
+    CardinalDirection::SouthSouthEast,
+    CardinalDirection::South,
+    CardinalDirection::SouthSouthWest,
+    CardinalDirection::WestSouthWest,
+    CardinalDirection::West,
+    CardinalDirection::WestNorthWest,
+    CardinalDirection::NorthNorthWest,
+];
 
-    pub(crate) fn rotate(&self, n: usize) -> Self {
+/// Steps clockwise between one edge of a tile and the next
+const STEPS_PER_EDGE: u8 = 3;
 
-        let current_index = PERIMETER_REGION_DIRECTIONS
-            .iter()
-            .position(|&d| d == *self)
-            .expect("should exist");
+impl CardinalDirection {
 
-        let new_index = (current_index + n * 3) % PERIMETER_REGION_DIRECTIONS.len();
+    fn index(&self) -> u8 {
+        *self as u8
+    }
+
+    /// The direction `steps` clockwise of north, wrapping around
+    fn from_index(steps: u8) -> Self {
+        CLOCKWISE_DIRECTIONS[(steps % 12) as usize]
+    }
 
-        PERIMETER_REGION_DIRECTIONS[new_index]
+    /// The direction after turning by `rotation`
+    pub(crate) fn rotate(&self, rotation: Rotation) -> Self {
+        Self::from_index(self.index() + rotation.quarter_turns() * STEPS_PER_EDGE)
     }
 
+    /// The directions either side of this one, anticlockwise first
     pub(crate) fn adjacent(&self) -> (Self, Self) {
-        match self {
-            Self::North => (Self::NorthNorthWest, Self::NorthNorthEast),
-            Self::NorthNorthEast => (Self::North, Self::EastNorthEast),
-            Self::EastNorthEast => (Self::NorthNorthEast, Self::East),
-            Self::East => (Self::EastNorthEast, Self::EastSouthEast),
-            Self::EastSouthEast => (Self::East, Self::SouthSouthEast),
-            Self::SouthSouthEast => (Self::EastSouthEast, Self::South),
-            Self::South => (Self::SouthSouthEast, Self::SouthSouthWest),
-            Self::SouthSouthWest => (Self::South, Self::WestSouthWest),
-            Self::WestSouthWest => (Self::SouthSouthWest, Self::West),
-            Self::West => (Self::WestSouthWest, Self::WestNorthWest),
-            Self::WestNorthWest => (Self::West, Self::NorthNorthWest),
-            Self::NorthNorthWest => (Self::WestNorthWest, Self::North),
-        }
+        (Self::from_index(self.index() + 11), Self::from_index(self.index() + 1))
     }
 
+    /// The direction pointing the other way, e.g. south-south-west for north-north-east
     pub(crate) fn compass_opposite(&self) -> Self {
-        match self {
-            CardinalDirection::North => CardinalDirection::South,
-            CardinalDirection::NorthNorthEast => CardinalDirection::SouthSouthWest,
-            CardinalDirection::EastNorthEast => CardinalDirection::WestSouthWest,
-            CardinalDirection::East => CardinalDirection::West,
-            CardinalDirection::EastSouthEast => CardinalDirection::WestNorthWest,
-            CardinalDirection::SouthSouthEast => CardinalDirection::NorthNorthWest,
-            CardinalDirection::South => CardinalDirection::North,
-            CardinalDirection::SouthSouthWest => CardinalDirection::NorthNorthEast,
-            CardinalDirection::WestSouthWest => CardinalDirection::EastNorthEast,
-            CardinalDirection::West => CardinalDirection::East,
-            CardinalDirection::WestNorthWest => CardinalDirection::EastSouthEast,
-            CardinalDirection::NorthNorthWest => CardinalDirection::SouthSouthEast,
-        }
+        Self::from_index(self.index() + 6)
     }
 
+    /// The direction facing this one across the edge with the neighbouring tile, e.g.
+    /// south-south-east for north-north-east: mirrored along the edge rather than pointed back
     pub(crate) fn tile_opposite(&self) -> Self {
-        match self {
-            CardinalDirection::North => CardinalDirection::South,
-            CardinalDirection::NorthNorthEast => CardinalDirection::SouthSouthEast,
-            CardinalDirection::EastNorthEast => CardinalDirection::WestNorthWest,
-            CardinalDirection::East => CardinalDirection::West,
-            CardinalDirection::EastSouthEast => CardinalDirection::WestSouthWest,
-            CardinalDirection::SouthSouthEast => CardinalDirection::NorthNorthEast,
-            CardinalDirection::South => CardinalDirection::North,
-            CardinalDirection::SouthSouthWest => CardinalDirection::NorthNorthWest,
-            CardinalDirection::WestSouthWest => CardinalDirection::EastSouthEast,
-            CardinalDirection::West => CardinalDirection::East,
-            CardinalDirection::WestNorthWest => CardinalDirection::EastNorthEast,
-            CardinalDirection::NorthNorthWest => CardinalDirection::SouthSouthWest,
-        }
-    }
-
-    pub(crate) fn primary_direction(&self) -> Self {
-        match self {
-            CardinalDirection::North => CardinalDirection::North,
-            CardinalDirection::NorthNorthEast => CardinalDirection::North,
-            CardinalDirection::EastNorthEast => CardinalDirection::East,
-            CardinalDirection::East => CardinalDirection::East,
-            CardinalDirection::EastSouthEast => CardinalDirection::East,
-            CardinalDirection::SouthSouthEast => CardinalDirection::South,
-            CardinalDirection::South => CardinalDirection::South,
-            CardinalDirection::SouthSouthWest => CardinalDirection::South,
-            CardinalDirection::WestSouthWest => CardinalDirection::West,
-            CardinalDirection::West => CardinalDirection::West,
-            CardinalDirection::WestNorthWest => CardinalDirection::West,
-            CardinalDirection::NorthNorthWest => CardinalDirection::North,
+        // the edge the direction is on, counting north from the north-north-west corner
+        let edge = (self.index() + 1) % 12 / STEPS_PER_EDGE;
+
+        match edge % 2 {
+            // north and south edges mirror top to bottom
+            0 => Self::from_index(18 - self.index()),
+            // east and west edges mirror left to right
+            _ => Self::from_index(12 - self.index()),
         }
     }
 }
@@ -231,8 +392,160 @@ pub(crate) const PERIMETER_REGION_DIRECTIONS: &[CardinalDirection; 12] = &[
     CardinalDirection::WestNorthWest,
 ];
 
+/// The region types around a perimeter, in the order of [PERIMETER_REGION_DIRECTIONS], packed two
+/// bits to a position so edges can be matched with a few integer operations. Positions without a
+/// neighbouring tile are unknown, and match any region type
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub(crate) struct EdgeSignature {
+    regions: u32,
+    /// Both bits set for each position whose region type is known
+    known: u32,
+}
+
+const EDGE_POSITION_BITS: usize = 2;
+/// The three positions along one edge
+const EDGE_MASK: u32 = (1 << (3 * EDGE_POSITION_BITS)) - 1;
+/// The low bit of every position
+const EDGE_POSITION_LOW_BITS: u32 = 0x55_5555;
+
+impl EdgeSignature {
+    pub(crate) fn from_regions<'a>(regions: impl IntoIterator<Item = Option<&'a RegionType>>) -> Self {
+        regions.into_iter().enumerate().fold(Self::default(), |signature, (position, region_type)| match region_type {
+            Some(region_type) => Self {
+                regions: signature.regions | Self::code(region_type) << (position * EDGE_POSITION_BITS),
+                known: signature.known | 0b11 << (position * EDGE_POSITION_BITS),
+            },
+            None => signature,
+        })
+    }
+
+    fn code(region_type: &RegionType) -> u32 {
+        match region_type {
+            RegionType::Field => 0,
+            RegionType::City => 1,
+            RegionType::Road => 2,
+            RegionType::Water => 3,
+            RegionType::Cloister => unreachable!("cloisters don't reach the edge of a tile"),
+        }
+    }
+
+    /// Where the edge facing `direction` starts, in bits
+    fn edge_offset(direction: &CardinalDirection) -> usize {
+        match direction {
+            CardinalDirection::North => 0,
+            CardinalDirection::East => 3 * EDGE_POSITION_BITS,
+            CardinalDirection::South => 6 * EDGE_POSITION_BITS,
+            CardinalDirection::West => 9 * EDGE_POSITION_BITS,
+            _ => panic!("only primary cardinal directions are supported"),
+        }
+    }
+
+    /// The signature with the edge facing `direction` known to be `edge`
+    pub(crate) fn with_edge(self, direction: &CardinalDirection, edge: &[RegionType; 3]) -> Self {
+        let offset = Self::edge_offset(direction);
+        let edge = Self::from_regions(edge.iter().map(Some));
+
+        Self {
+            regions: self.regions & !(EDGE_MASK << offset) | edge.regions << offset,
+            known: self.known | EDGE_MASK << offset,
+        }
+    }
+
+    /// Whether no position is known
+    pub(crate) fn is_unknown(&self) -> bool {
+        self.known == 0
+    }
+
+    /// Whether every known position of `other` has the same region type here
+    pub(crate) fn fits(&self, other: &EdgeSignature) -> bool {
+        (self.regions ^ other.regions) & other.known == 0
+    }
+
+    /// The low bit of every known position that is water
+    fn water(&self) -> u32 {
+        self.regions & self.regions >> 1 & self.known & EDGE_POSITION_LOW_BITS
+    }
+
+    pub(crate) fn has_water(&self) -> bool {
+        self.water() != 0
+    }
+
+    /// Whether water here meets water in `other` at any position
+    pub(crate) fn joins_water(&self, other: &EdgeSignature) -> bool {
+        self.water() & other.water() != 0
+    }
+
+    /// The region type at the position, if it's known
+    fn region_at(&self, position: usize) -> Option<RegionType> {
+        let shift = position * EDGE_POSITION_BITS;
+
+        if self.known >> shift & 0b11 == 0 {
+            return None;
+        }
+
+        Some(match self.regions >> shift & 0b11 {
+            0 => RegionType::Field,
+            1 => RegionType::City,
+            2 => RegionType::Road,
+            _ => RegionType::Water,
+        })
+    }
+}
+
+/// The region types a tile placed at an empty coordinate has to match around its perimeter, set by
+/// the placed tiles next to it. Slots go clockwise from the western end of the north edge, three
+/// to an edge, as in [TileDefinition::list_oriented_region_types]; a slot with no tile beside it
+/// is `None` and takes any region type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeConstraints {
+    signature: EdgeSignature,
+}
+
+impl EdgeConstraints {
+    pub(crate) fn new(signature: EdgeSignature) -> Self {
+        Self { signature }
+    }
+
+    pub fn slots(&self) -> [Option<RegionType>; 12] {
+        std::array::from_fn(|position| self.signature.region_at(position))
+    }
+
+    /// The north edge's slots, west to east
+    pub fn north(&self) -> [Option<RegionType>; 3] {
+        self.edge(0)
+    }
+
+    /// The east edge's slots, north to south
+    pub fn east(&self) -> [Option<RegionType>; 3] {
+        self.edge(1)
+    }
+
+    /// The south edge's slots, east to west
+    pub fn south(&self) -> [Option<RegionType>; 3] {
+        self.edge(2)
+    }
+
+    /// The west edge's slots, south to north
+    pub fn west(&self) -> [Option<RegionType>; 3] {
+        self.edge(3)
+    }
+
+    fn edge(&self, edge: usize) -> [Option<RegionType>; 3] {
+        std::array::from_fn(|position| self.signature.region_at(edge * 3 + position))
+    }
+
+    /// Whether the tile's edges match every constrained slot when it's turned by `rotation`. Only
+    /// the edges are checked, not the river's rules or the board's limit
+    pub fn fits(&self, tile: &TileDefinition, rotation: Rotation) -> bool {
+        tile.edge_signature(rotation).fits(&self.signature)
+    }
+}
+
+/// The kind of feature a region is part of. Expansions bring more, so matches on it need a catch
+/// all
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
-pub(crate) enum RegionType {
+#[non_exhaustive]
+pub enum RegionType {
     City,
     Field,
     Cloister,
@@ -274,7 +587,8 @@ impl Region {
         }
     }
 
-    pub(crate) fn meeple_coordinate_rotated(&self, rotations: u8) -> Option<TileCoordinate> {
+    #[cfg(feature = "render")]
+    pub(crate) fn meeple_coordinate_rotated(&self, rotation: Rotation) -> Option<TileCoordinate> {
         let coordinate = match self {
             Region::City {
                 meeple_coordinate, ..
@@ -292,7 +606,7 @@ impl Region {
         }
         .cloned();
 
-        coordinate.map(|coord| coord.rotate_around_center(rotations))
+        coordinate.map(|coord| coord.rotate_around_center(rotation))
     }
 
     // @todo the existence of the two enums is a code smell. Some refactoring is needed!
@@ -307,11 +621,101 @@ impl Region {
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub(crate) enum Expansion {
+/// An expansion whose tiles can be dealt
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub enum Expansion {
     River,
 }
 
+impl Expansion {
+    pub const ALL: [Expansion; 1] = [Expansion::River];
+
+    /// Name of the expansion in network protocols
+    pub fn name(&self) -> &'static str {
+        match self {
+            Expansion::River => "river",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|expansion| expansion.name() == name)
+    }
+
+    /// Expansions that can't be played alongside this one
+    fn incompatible_with(&self) -> &'static [Expansion] {
+        match self {
+            Expansion::River => &[],
+        }
+    }
+
+    /// Whether the two expansions can be played in the same game
+    pub fn compatible_with(&self, other: &Expansion) -> bool {
+        self != other && !self.incompatible_with().contains(other) && !other.incompatible_with().contains(self)
+    }
+
+    /// Every expansion with the others it can be played alongside, for setup screens to offer only
+    /// combinations the engine supports
+    pub fn compatibility_matrix() -> Vec<(Expansion, Vec<Expansion>)> {
+        Self::ALL
+            .into_iter()
+            .map(|expansion| (expansion, Self::ALL.into_iter().filter(|other| expansion.compatible_with(other)).collect()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpansionError {
+    Unknown(String),
+    Repeated(Expansion),
+    Incompatible(Expansion, Expansion),
+}
+
+impl Display for ExpansionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpansionError::Unknown(name) => write!(f, "there is no expansion called [{}]", name),
+            ExpansionError::Repeated(expansion) => write!(f, "the {} expansion is given more than once", expansion.name()),
+            ExpansionError::Incompatible(first, second) => {
+                write!(f, "the {} and {} expansions can't be played together", first.name(), second.name())
+            }
+        }
+    }
+}
+
+/// Parses a comma separated list of expansion names, e.g. `river,inns`, checking they can all be
+/// played together. An empty list is the base game alone
+pub fn parse_expansions(list: &str) -> Result<Vec<Expansion>, ExpansionError> {
+    let expansions = list
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| Expansion::from_name(name).ok_or_else(|| ExpansionError::Unknown(name.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    check_expansions(&expansions)?;
+
+    Ok(expansions)
+}
+
+/// Checks no expansion is given twice and each can be played alongside the others
+pub(crate) fn check_expansions(expansions: &[Expansion]) -> Result<(), ExpansionError> {
+    for (index, expansion) in expansions.iter().enumerate() {
+        if let Some(other) = expansions[..index].iter().find(|other| !expansion.compatible_with(other)) {
+            return Err(match other == expansion {
+                true => ExpansionError::Repeated(*expansion),
+                false => ExpansionError::Incompatible(*other, *expansion),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Position of a tile definition in [ALL_TILE_DEFINITIONS]; a compact way to refer to a tile, e.g.
+/// in saved games
+pub type TileId = u8;
+
 #[derive(Debug, PartialEq)]
 pub struct TileDefinition {
     pub(crate) count: u8,
@@ -319,9 +723,54 @@ pub struct TileDefinition {
     pub(crate) render: TileRenderRepresentation,
     pub(crate) regions: &'static [Region],
     pub(crate) expansion: Option<Expansion>,
+    /// Worked out from the regions by [TileDefinition::symmetry_class_of] as the tile is defined
+    pub(crate) symmetry_class: u8,
 }
 
 impl TileDefinition {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The expansion the tile comes from, or `None` for the base game
+    pub fn expansion(&self) -> Option<Expansion> {
+        self.expansion
+    }
+
+    pub fn id(&self) -> TileId {
+        ALL_TILE_DEFINITIONS
+            .iter()
+            .position(|definition| definition.name == self.name)
+            .expect("every tile should be in the definitions") as TileId
+    }
+
+    /// Where the river leaves the tile turned by `rotation`, given it comes in from `direction`.
+    /// `None` on a tile the river starts or stops on
+    pub(crate) fn opposite_river_end_direction(&self, rotation: Rotation, direction: CardinalDirection) -> Option<CardinalDirection> {
+        if self.is_river_end() {
+            return None;
+        }
+
+        let region = self
+            .regions
+            .iter()
+            .find(|r| matches!(r, Region::Water { .. }))?;
+
+        let rotated_edges: Vec<_> = region
+            .edges()
+            .iter()
+            .map(|d| d.rotate(rotation))
+            .collect();
+        assert_eq!(rotated_edges.len(), 2);
+
+        rotated_edges.into_iter().find(|&e| e != direction)
+    }
+
+    /// Whether the river starts or stops on the tile, as on the terminator and the lake
+    pub(crate) fn is_river_end(&self) -> bool {
+        self.regions.iter().any(|region| matches!(region, Region::Water { edges } if edges.len() == 1))
+    }
+
     /// The list of region types around the perimeter of the definition
     /// * no rotation applied
     /// * starting from NorthNorthWest, going clockwise
@@ -329,15 +778,92 @@ impl TileDefinition {
         PERIMETER_REGION_DIRECTIONS.map(|dir|self.regions.iter().find(|r|r.edges().contains(&dir)).expect("should exist").region_type())
     }
 
-    pub(crate) fn list_oriented_region_types(&self, rotations: u8) -> [RegionType;12] {
+    pub(crate) fn list_oriented_region_types(&self, rotation: Rotation) -> [RegionType;12] {
         let mut perimeter = self.perimeter_regions();
 
-        let index_offset = ((4 - rotations) * 3) % 12;
+        // turning the tile clockwise moves each position three along, so reading from the same
+        // place starts that many further back
+        perimeter.rotate_right(rotation.quarter_turns() as usize * 3);
+
+        perimeter
+    }
+
+    /// The index of the region at each position around the perimeter of the tile turned by
+    /// `rotation`, in the same order as [TileDefinition::list_oriented_region_types]
+    pub(crate) fn list_oriented_region_indices(&self, rotation: Rotation) -> [RegionIndex; 12] {
+        let mut perimeter = PERIMETER_REGION_DIRECTIONS.map(|dir| RegionIndex::new(self.regions.iter().position(|r| r.edges().contains(&dir)).expect("should exist")));
 
-        perimeter.rotate_left(index_offset as usize);
+        perimeter.rotate_right(rotation.quarter_turns() as usize * 3);
 
         perimeter
     }
+
+    pub(crate) fn edge_signature(&self, rotation: Rotation) -> EdgeSignature {
+        EdgeSignature::from_regions(self.list_oriented_region_types(rotation).iter().map(Some))
+    }
+
+    /// How many rotations of the tile turn its edges a different way: 1 for a tile that looks the
+    /// same whichever way it's turned, 2 for one that looks the same upside down, otherwise 4
+    pub const fn symmetry_class(&self) -> u8 {
+        self.symmetry_class
+    }
+
+    /// The symmetry class of a tile with these regions, for its definition to store
+    pub(crate) const fn symmetry_class_of(regions: &[Region]) -> u8 {
+        // the region type along each perimeter position, indexed by direction
+        let mut perimeter = [0u8; 12];
+        let mut region = 0;
+
+        while region < regions.len() {
+            let (region_type, edges): (u8, &[CardinalDirection]) = match &regions[region] {
+                Region::Field { edges, .. } => (0, edges),
+                Region::Road { edges, .. } => (1, edges),
+                Region::City { edges, .. } => (2, edges),
+                Region::Water { edges, .. } => (3, edges),
+                Region::Cloister { .. } => (0, &[]),
+            };
+
+            let mut edge = 0;
+
+            while edge < edges.len() {
+                perimeter[edges[edge] as usize] = region_type;
+                edge += 1;
+            }
+
+            region += 1;
+        }
+
+        // turning the tile a quarter moves each position three along
+        const fn turns_onto_itself(perimeter: &[u8; 12], rotations: usize) -> bool {
+            let mut position = 0;
+
+            while position < 12 {
+                if perimeter[position] != perimeter[(position + rotations * 3) % 12] {
+                    return false;
+                }
+
+                position += 1;
+            }
+
+            true
+        }
+
+        match (turns_onto_itself(&perimeter, 1), turns_onto_itself(&perimeter, 2)) {
+            (true, _) => 1,
+            (false, true) => 2,
+            (false, false) => 4,
+        }
+    }
+
+    /// The rotations that turn the tile's edges a different way, fewest first. A tile with
+    /// rotational symmetry looks the same at the others, so they aren't worth telling apart
+    pub fn distinct_rotations(&self) -> &'static [Rotation] {
+        match self.symmetry_class() {
+            1 => &[Rotation::R0],
+            2 => &[Rotation::R0, Rotation::R90],
+            _ => &Rotation::ALL,
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -351,7 +877,7 @@ pub(crate) enum RenderCell {
     Corner, // @todo remove?
 }
 
-
+#[cfg(feature = "render")]
 impl RenderCell {
     fn ascii_code(&self) -> &'static str {
         match self {
@@ -365,6 +891,33 @@ impl RenderCell {
         }
     }
 
+    /// The cell as exactly two characters without colour, the same whatever the terminal, with a
+    /// meeple drawn with its player's initial
+    pub(crate) fn render_plain(&self, row_idx: usize, column_idx: usize, meeple: Option<&Meeple>) -> String {
+        if let RenderCell::Corner = self {
+            "  ".to_string()
+        } else if row_idx == 0 || row_idx == TILE_WIDTH - 1 {
+            "━━".to_string()
+        } else if column_idx == 0 {
+            " ┃".to_string()
+        } else if column_idx == TILE_WIDTH - 1 {
+            "┃ ".to_string()
+        } else {
+            match meeple {
+                Some(meeple) => format!("ꆜ{}", meeple.color.initial()),
+                None => self.ascii_code().to_string(),
+            }
+        }
+    }
+
+    pub(crate) fn render(&self, style: &RenderStyle, row_idx: usize, column_idx: usize, meeple: Option<&Meeple>) -> String {
+        match style {
+            RenderStyle::Ascii => self.render_ascii(row_idx, column_idx, meeple),
+            RenderStyle::Ansi => self.render_ansi(row_idx, column_idx, meeple),
+            RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel => self.render_true_color(row_idx, column_idx, meeple),
+        }
+    }
+
     fn render_ascii(&self, row_idx: usize, column_idx: usize, meeple: Option<&Meeple>) -> String {
         if let RenderCell::Corner = self {
             " "
@@ -386,7 +939,7 @@ impl RenderCell {
         .to_string()
     }
 
-    fn render_ansi(&self, row_idx: usize, column_idx: usize, meeple: Option<&Meeple>) -> String {
+    fn render_ansi(&self, row_idx: usize, column_idx: usize, _meeple: Option<&Meeple>) -> String {
         let color = match self {
             Self::Field => Color::Green,
             Self::Road => Color::BrightBlack,
@@ -534,14 +1087,21 @@ impl RenderCell {
     }
 }
 
+/// How boards and tiles are drawn in a terminal
+#[cfg(feature = "render")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum RenderStyle {
     Ansi,
     TrueColor,
     Ascii,
-    // image??
+    /// Boards as an inline image using the kitty graphics protocol; anything else as [RenderStyle::TrueColor]
+    Kitty,
+    /// Boards as an inline sixel image; anything else as [RenderStyle::TrueColor]
+    Sixel,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PlacedTile {
     pub(crate) tile: &'static TileDefinition,
     pub(crate) placement: TilePlacement,
@@ -549,25 +1109,42 @@ pub struct PlacedTile {
 }
 
 impl PlacedTile {
-    pub(crate) fn new(tile: &'static TileDefinition, x: i8, y: i8, rotations: u8) -> Self {
+    pub fn new(tile: &'static TileDefinition, x: i8, y: i8, rotation: Rotation) -> Self {
         PlacedTile {
             tile,
             placement: TilePlacement {
                 coordinate: BoardCoordinate { x, y },
-                rotations,
+                rotation,
             },
             meeple: Default::default(),
         }
     }
 
-    pub(crate) fn new_with_meeple(tile: &'static TileDefinition, x: i8, y: i8, rotations: u8, meeple_placement: (RegionIndex, Meeple)) -> Self {
-        let mut tile = Self::new(tile, x, y, rotations);
+    pub fn new_with_meeple(tile: &'static TileDefinition, x: i8, y: i8, rotation: Rotation, meeple_placement: (RegionIndex, Meeple)) -> Self {
+        let mut tile = Self::new(tile, x, y, rotation);
 
         tile.meeple = Some(meeple_placement);
 
         tile
     }
 
+    pub fn tile(&self) -> &'static TileDefinition {
+        self.tile
+    }
+
+    pub fn placement(&self) -> &TilePlacement {
+        &self.placement
+    }
+
+    /// The meeple on the tile, if there is one, with the index of the region it is on
+    pub fn meeple(&self) -> Option<(RegionIndex, &Meeple)> {
+        self.meeple.as_ref().map(|(region_index, meeple)| (*region_index, meeple))
+    }
+
+    pub(crate) fn has_cloister(&self) -> bool {
+        self.tile.regions.iter().any(|region| matches!(region, Region::Cloister { .. }))
+    }
+
     pub(crate) fn has_occupied_cloister(&self) -> bool {
 
         if let Some((meeple_index, _)) = self.meeple {
@@ -596,7 +1173,7 @@ impl PlacedTile {
                     .map(|edge| {
                         (
                             PlacedTileEdge {
-                                global_direction: edge.rotate(self.placement.rotations as usize),
+                                global_direction: edge.rotate(self.placement.rotation),
                                 coordinate: self.placement.coordinate,
                             },
                             None,
@@ -606,9 +1183,11 @@ impl PlacedTile {
 
                 ConnectedRegion {
                     region_type: region.region.region_type(),
+                    pennant_count: usize::from(matches!(region.region, Region::City { pennant: true, .. })),
                     tile_regions: Vec::from([region]),
                     id: max_connected_region_id + index + 1,
                     adjacent_regions: Default::default(),
+                    open_edge_count: connected_edges.len(),
                     connected_edges,
                 }
             })
@@ -656,25 +1235,7 @@ impl PlacedTile {
         &self,
         direction: CardinalDirection,
     ) -> Option<CardinalDirection> {
-        if self.tile == &RIVER_TERMINATOR {
-            return None;
-        }
-
-        let region = self
-            .tile
-            .regions
-            .iter()
-            .find(|r| matches!(r, Region::Water { .. }))
-            .expect("should be river tile");
-
-        let rotated_edges: Vec<_> = region
-            .edges()
-            .iter()
-            .map(|d| d.rotate(self.placement.rotations as usize))
-            .collect();
-        assert_eq!(rotated_edges.len(), 2);
-
-        rotated_edges.into_iter().find(|&e| e != direction)
+        self.tile.opposite_river_end_direction(self.placement.rotation, direction)
     }
 
     pub(crate) fn list_regions_on_edge(
@@ -683,7 +1244,7 @@ impl PlacedTile {
     ) -> [RegionType;3] {
         let edges = self
             .tile
-            .list_oriented_region_types(self.placement.rotations);
+            .list_oriented_region_types(self.placement.rotation);
 
         let skip = match cardinal_direction {
             CardinalDirection::North => 0,
@@ -704,75 +1265,160 @@ impl PlacedTile {
             .collect()
     }
 
+    /// The meeple on the tile (if any), with the cell of the rotated tile it is drawn in
+    #[cfg(feature = "render")]
+    pub(crate) fn meeple_render_coordinate(&self) -> Option<(TileCoordinate, &Meeple)> {
+        let (meeple_region_index, meeple) = self.meeple.as_ref()?;
+
+        self.tile
+            .regions
+            .get(**meeple_region_index)?
+            .meeple_coordinate_rotated(self.placement.rotation)
+            .map(|coordinate| (coordinate, meeple))
+    }
+
+    #[cfg(feature = "render")]
     pub fn render_to_lines(&self, render_style: &RenderStyle) -> Vec<String> {
-        let meeple_render_coordinate = if let Some((meeple_region_index, meeple)) = &self.meeple {
-            self.tile
-                .regions
-                .iter()
-                .enumerate()
-                .find_map(|(tile_region_index, region)| {
-                    if **meeple_region_index == tile_region_index {
-                        region.meeple_coordinate_rotated(self.placement.rotations)
-                    } else {
-                        None
-                    }
-                    .map(|coordinate| (coordinate, meeple))
-                })
-        } else {
-            None
-        };
+        self.render_cells(|cell, row_idx, column_idx, meeple| cell.render(render_style, row_idx, column_idx, meeple))
+    }
+
+    /// The tile without colour, for snapshot tests and pasting into bug reports
+    #[cfg(feature = "render")]
+    pub fn render_plain(&self) -> Vec<String> {
+        self.render_cells(|cell, row_idx, column_idx, meeple| cell.render_plain(row_idx, column_idx, meeple))
+    }
+
+    /// As [PlacedTile::render_to_lines], with each region's index drawn where a meeple on it would
+    /// stand, so the index to choose for a meeple can be read off the drawing
+    #[cfg(feature = "render")]
+    pub fn render_region_labels(&self, render_style: &RenderStyle) -> Vec<String> {
+        let labels: HashMap<TileCoordinate, usize> = self
+            .tile
+            .regions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, region)| region.meeple_coordinate_rotated(self.placement.rotation).map(|coordinate| (coordinate, index)))
+            .collect();
+
+        self.render_overlaid(render_style, |coordinate, _| {
+            labels.get(&coordinate).map(|index| match render_style {
+                RenderStyle::Ascii => format!("{:<2}", index),
+                _ => format!("{:<2}", index).bold().white().on_black().to_string(),
+            })
+        })
+    }
+
+    /// As [PlacedTile::render_to_lines], with a dimmed meeple in the player's colour on the region, to
+    /// show where a meeple would go before it is placed
+    #[cfg(feature = "render")]
+    pub(crate) fn render_meeple_ghost(&self, region_index: RegionIndex, color: MeepleColor, render_style: &RenderStyle) -> Vec<String> {
+        let ghost = self
+            .tile
+            .regions
+            .get(*region_index)
+            .and_then(|region| region.meeple_coordinate_rotated(self.placement.rotation));
+
+        self.render_overlaid(render_style, |coordinate, cell| {
+            (Some(coordinate) == ghost).then(|| match render_style {
+                RenderStyle::Ascii => "◌ ".to_string(),
+                RenderStyle::Ansi => "ꆜ ".color(color.render_color(render_style)).dimmed().to_string(),
+                RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel => {
+                    let (r, g, b) = cell.rgb();
+
+                    "ꆜ ".color(color.render_color(render_style)).dimmed().on_color(Color::TrueColor { r, g, b }).to_string()
+                }
+            })
+        })
+    }
+
+    /// Draws the tile, letting `overlay` draw any cell in place of the tile
+    #[cfg(feature = "render")]
+    fn render_overlaid(&self, render_style: &RenderStyle, overlay: impl Fn(TileCoordinate, &RenderCell) -> Option<String>) -> Vec<String> {
+        self.render_cells(|cell, row_idx, column_idx, meeple| {
+            overlay(TileCoordinate::new(column_idx, row_idx), cell).unwrap_or_else(|| cell.render(render_style, row_idx, column_idx, meeple))
+        })
+    }
+
+    /// Draws each cell of the rotated tile with `render_cell`, given its row, column and the meeple
+    /// drawn in it, if any
+    #[cfg(feature = "render")]
+    pub(crate) fn render_cells(&self, render_cell: impl Fn(&RenderCell, usize, usize, Option<&Meeple>) -> String) -> Vec<String> {
+        let meeple_render_coordinate = self.meeple_render_coordinate();
 
         self.tile
             .render
-            .rotated(self.placement.rotations)
+            .rotated(self.placement.rotation)
             .enumerate()
             .map(|(row_idx, row)| {
-                let chars: String = row
-                    .enumerate()
-                    .map(|(column_idx, cell): (usize, &RenderCell)| {
-                        let tile_coord = TileCoordinate::new(column_idx, row_idx);
-
+                row.enumerate()
+                    .map(|(column_idx, cell)| {
                         let meeple = match &meeple_render_coordinate {
-                            Some((coordinate, meeple)) if coordinate == &tile_coord => Some(meeple),
+                            Some((coordinate, meeple)) if coordinate == &TileCoordinate::new(column_idx, row_idx) => Some(*meeple),
                             _ => None,
                         };
 
-                        match render_style {
-                            RenderStyle::Ascii => {
-                                cell.render_ascii(row_idx, column_idx, meeple.map(|v| &**v))
-                            }
-                            RenderStyle::Ansi => {
-                                cell.render_ansi(row_idx, column_idx, meeple.map(|v| &**v))
-                            }
-                            RenderStyle::TrueColor => {
-                                cell.render_true_color(row_idx, column_idx, meeple.map(|v| &**v))
-                            }
-                        }
+                        render_cell(cell, row_idx, column_idx, meeple)
                     })
-                    .collect();
-
-                chars
+                    .collect()
             })
             .collect()
     }
 }
 
+#[cfg(feature = "render")]
+impl TileDefinition {
+    /// The tile at each of the rotations side by side, with every region's index drawn where a meeple
+    /// on it would stand, and a key to the regions underneath. Handy when writing tile definitions
+    pub fn render_preview(&'static self, rotations: &[Rotation], style: &RenderStyle) -> String {
+        let width = match style {
+            RenderStyle::Ascii | RenderStyle::Ansi => TILE_WIDTH * 2 - 2,
+            RenderStyle::TrueColor | RenderStyle::Kitty | RenderStyle::Sixel => TILE_WIDTH * 2,
+        };
+
+        let tiles: Vec<_> = rotations
+            .iter()
+            .map(|rotation| PlacedTile::new(self, 0, 0, *rotation).render_region_labels(style))
+            .collect();
+
+        let header: Vec<_> = rotations
+            .iter()
+            .map(|rotation| format!("{:<width$}", rotation.to_string()))
+            .collect();
+
+        let mut lines = vec![header.join("  ").trim_end().to_string()];
+
+        lines.extend((0..TILE_WIDTH).map(|row| tiles.iter().map(|tile| tile[row].as_str()).collect::<Vec<_>>().join("  ")));
+        lines.push(String::new());
+
+        lines.extend(self.regions.iter().enumerate().map(|(index, region)| {
+            let kind = format!("{:?}", region.region_type()).to_lowercase();
+
+            match region {
+                Region::City { pennant: true, .. } => format!("{}: {} with pennant", index, kind),
+                Region::Water { .. } => format!("{}: {}, no meeple spot", index, kind),
+                _ => format!("{}: {}", index, kind),
+            }
+        }));
+
+        lines.join("\n")
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct TileRenderRepresentation(pub [[RenderCell; 7]; 7]);
 
+#[cfg(feature = "render")]
 impl TileRenderRepresentation {
     pub(crate) fn rotated(
         &self,
-        rotations: u8,
+        rotation: Rotation,
     ) -> impl Iterator<Item = impl Iterator<Item = &RenderCell>> {
-        let rotations = rotations % 4; // Normalize rotations to 0..3
-
         (0..TILE_WIDTH).map(move |r| {
-            (0..TILE_WIDTH).map(move |c| match rotations {
-                1 => &self.0[TILE_WIDTH - c - 1][r], // 90 degrees clockwise
-                2 => &self.0[TILE_WIDTH - r - 1][TILE_WIDTH - c - 1], // 180 degrees
-                3 => &self.0[c][TILE_WIDTH - r - 1], // 270 degrees clockwise
-                _ => &self.0[r][c],                  // No rotation
+            (0..TILE_WIDTH).map(move |c| match rotation {
+                Rotation::R0 => &self.0[r][c],
+                Rotation::R90 => &self.0[TILE_WIDTH - c - 1][r],
+                Rotation::R180 => &self.0[TILE_WIDTH - r - 1][TILE_WIDTH - c - 1],
+                Rotation::R270 => &self.0[c][TILE_WIDTH - r - 1],
             })
         })
     }
@@ -784,6 +1430,26 @@ mod tests {
     use super::*;
     use crate::tile_definitions::{CORNER_ROAD, SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE};
 
+    #[test]
+    fn should_count_the_rotations_that_turn_a_tiles_edges_a_different_way() {
+        use crate::tile_definitions::{ALL_TILE_DEFINITIONS, CLOISTER_IN_FIELD, CROSS_INTERSECTION, SIDE_CITY, STRAIGHT_ROAD};
+        use std::collections::HashSet;
+
+        // stored as the tile is defined, so it can be read in a const
+        const CROSS: u8 = CROSS_INTERSECTION.symmetry_class();
+
+        assert_eq!(CROSS, 1);
+        assert_eq!(CLOISTER_IN_FIELD.distinct_rotations(), [Rotation::R0]);
+        assert_eq!(STRAIGHT_ROAD.distinct_rotations(), [Rotation::R0, Rotation::R90]);
+        assert_eq!(SIDE_CITY.distinct_rotations(), [Rotation::R0, Rotation::R90, Rotation::R180, Rotation::R270]);
+
+        for tile in ALL_TILE_DEFINITIONS.iter() {
+            let edge_signatures: HashSet<_> = Rotation::ALL.into_iter().map(|rotation| tile.edge_signature(rotation)).collect();
+
+            assert_eq!(tile.symmetry_class() as usize, edge_signatures.len(), "{}", tile.name);
+        }
+    }
+
     #[test]
     fn test_perimeter_regions_returns_expected_result() {
         let perimeter = SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.perimeter_regions();
@@ -795,7 +1461,7 @@ mod tests {
     }
     #[test]
     fn test_oriented_regions_returns_expected_result() {
-        let perimeter = SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.list_oriented_region_types(3);
+        let perimeter = SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.list_oriented_region_types(Rotation::R270);
 
         assert_eq!(
             perimeter,
@@ -803,12 +1469,47 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_edge_signature_matches_only_known_edges() {
+        let tile = SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.edge_signature(Rotation::R0);
+
+        let below_city = EdgeSignature::default().with_edge(&CardinalDirection::North, &[City, City, City]);
+        let beside_road = EdgeSignature::default().with_edge(&CardinalDirection::South, &[Field, City, Field]);
+
+        assert!(tile.fits(&EdgeSignature::default()));
+        assert!(tile.fits(&below_city));
+        assert!(!tile.fits(&beside_road));
+        assert!(EdgeSignature::default().is_unknown());
+        assert!(!below_city.is_unknown());
+    }
+
+    #[test]
+    fn test_edge_signature_rotates_with_the_tile() {
+        let rotated = EdgeSignature::from_regions(SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.list_oriented_region_types(Rotation::R270).iter().map(Some));
+
+        assert_eq!(SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.edge_signature(Rotation::R270), rotated);
+        assert_ne!(SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.edge_signature(Rotation::R0), rotated);
+    }
+
+    #[test]
+    fn test_edge_signature_finds_joining_water() {
+        let tile = SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE.edge_signature(Rotation::R0);
+
+        let river_to_the_east = EdgeSignature::default().with_edge(&CardinalDirection::East, &[Field, Water, Field]);
+        let road_to_the_south = EdgeSignature::default().with_edge(&CardinalDirection::South, &[Field, Road, Field]);
+
+        assert!(tile.has_water());
+        assert!(!CORNER_ROAD.edge_signature(Rotation::R0).has_water());
+        assert!(tile.joins_water(&river_to_the_east));
+        assert!(!tile.joins_water(&road_to_the_south));
+    }
+
     #[test]
     fn test_list_regions_on_edge() {
         let tile = PlacedTile {
             tile: &SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE,
             placement: TilePlacement {
-                rotations: 0,
+                rotation: Rotation::R0,
                 coordinate: BoardCoordinate::new(0, 0),
             },
             meeple: Default::default(),
@@ -837,7 +1538,7 @@ mod tests {
         let tile = PlacedTile {
             tile: &CORNER_ROAD,
             placement: TilePlacement {
-                rotations: 1,
+                rotation: Rotation::R90,
                 coordinate: BoardCoordinate::new(0, 0),
             },
             meeple: Default::default(),
@@ -854,30 +1555,29 @@ mod tests {
         assert_eq!(
             BoardCoordinate { x: 0, y: 0 }
                 .direction_to_adjacent_coordinate(BoardCoordinate { x: 0, y: -1 }),
-            CardinalDirection::North
+            Some(CardinalDirection::North)
         );
         assert_eq!(
             BoardCoordinate { x: 0, y: 0 }
                 .direction_to_adjacent_coordinate(BoardCoordinate { x: 1, y: 0 }),
-            CardinalDirection::East
+            Some(CardinalDirection::East)
         );
         assert_eq!(
             BoardCoordinate { x: 0, y: 0 }
                 .direction_to_adjacent_coordinate(BoardCoordinate { x: 0, y: 1 }),
-            CardinalDirection::South
+            Some(CardinalDirection::South)
         );
         assert_eq!(
             BoardCoordinate { x: 0, y: 0 }
                 .direction_to_adjacent_coordinate(BoardCoordinate { x: -1, y: 0 }),
-            CardinalDirection::West
+            Some(CardinalDirection::West)
         );
     }
 
     #[test]
-    #[should_panic]
-    fn test_direction_to_adjacent_coordinate_panics_on_non_adjacent() {
-        BoardCoordinate { x: 0, y: 0 }
-            .direction_to_adjacent_coordinate(BoardCoordinate { x: 1, y: 1 });
+    fn test_direction_to_adjacent_coordinate_is_none_for_non_adjacent() {
+        assert_eq!(BoardCoordinate { x: 0, y: 0 }.direction_to_adjacent_coordinate(BoardCoordinate { x: 1, y: 1 }), None);
+        assert_eq!(BoardCoordinate { x: i8::MAX, y: 0 }.direction_to_adjacent_coordinate(BoardCoordinate { x: i8::MIN, y: 0 }), None);
     }
 
     #[test]
@@ -896,14 +1596,30 @@ mod tests {
         ]);
     }
 
+    #[test]
+    fn test_compatibility_matrix() {
+        assert_eq!(Expansion::compatibility_matrix(), vec![(Expansion::River, vec![])]);
+        assert!(!Expansion::River.compatible_with(&Expansion::River));
+    }
+
+    #[test]
+    fn test_parse_expansions() {
+        assert_eq!(parse_expansions("river"), Ok(vec![Expansion::River]));
+        assert_eq!(parse_expansions(" river , "), Ok(vec![Expansion::River]));
+        assert_eq!(parse_expansions(""), Ok(vec![]));
+        assert_eq!(parse_expansions("river,inns"), Err(ExpansionError::Unknown("inns".to_string())));
+        assert_eq!(parse_expansions("river,river"), Err(ExpansionError::Repeated(Expansion::River)));
+    }
+
     #[test]
     fn test_rotate_cardinal_direction() {
-        assert_eq!(CardinalDirection::North.rotate(0), CardinalDirection::North);
-        assert_eq!(CardinalDirection::North.rotate(1), CardinalDirection::East);
+        assert_eq!(CardinalDirection::North.rotate(Rotation::R0), CardinalDirection::North);
+        assert_eq!(CardinalDirection::North.rotate(Rotation::R90), CardinalDirection::East);
         assert_eq!(
-            CardinalDirection::NorthNorthWest.rotate(2),
+            CardinalDirection::NorthNorthWest.rotate(Rotation::R180),
             CardinalDirection::NorthNorthWest.compass_opposite()
         );
+        assert_eq!(CardinalDirection::West.rotate(Rotation::R180 + Rotation::R270), CardinalDirection::North);
     }
 
     #[test]
@@ -920,4 +1636,81 @@ mod tests {
             (CardinalDirection::East, CardinalDirection::SouthSouthEast)
         );
     }
+
+    #[test]
+    fn test_opposite_cardinal_directions() {
+        use CardinalDirection::*;
+
+        let expected = [
+            (North, South, South),
+            (NorthNorthEast, SouthSouthWest, SouthSouthEast),
+            (EastNorthEast, WestSouthWest, WestNorthWest),
+            (East, West, West),
+            (EastSouthEast, WestNorthWest, WestSouthWest),
+            (SouthSouthEast, NorthNorthWest, NorthNorthEast),
+            (SouthSouthWest, NorthNorthEast, NorthNorthWest),
+            (NorthNorthWest, SouthSouthEast, SouthSouthWest),
+        ];
+
+        for (direction, compass_opposite, tile_opposite) in expected {
+            assert_eq!(direction.compass_opposite(), compass_opposite, "compass opposite of {:?}", direction);
+            assert_eq!(direction.tile_opposite(), tile_opposite, "tile opposite of {:?}", direction);
+        }
+
+        for direction in PERIMETER_REGION_DIRECTIONS {
+            assert_eq!(direction.tile_opposite().tile_opposite(), *direction);
+            assert_eq!(direction.rotate(Rotation::R90).rotate(Rotation::R90.inverse()), *direction);
+        }
+    }
+
+    #[test]
+    fn test_rotation_arithmetic() {
+        assert_eq!(Rotation::try_from(3), Ok(Rotation::R270));
+        assert_eq!(Rotation::try_from(4), Err(InvalidRotation(4)));
+        assert_eq!(Rotation::R270 + Rotation::R180, Rotation::R90);
+        assert_eq!(Rotation::R90 - Rotation::R180, Rotation::R270);
+        assert_eq!(Rotation::R0.turned(5), Rotation::R90);
+        assert_eq!(Rotation::R90.inverse(), Rotation::R270);
+        assert_eq!(Rotation::R0.inverse(), Rotation::R0);
+        assert_eq!(Rotation::from_degrees(180), Some(Rotation::R180));
+        assert_eq!(Rotation::from_degrees(45), None);
+        assert_eq!(Rotation::R270.degrees(), 270);
+        assert_eq!(serde_json::to_string(&Rotation::R180).unwrap(), "2");
+        assert!(serde_json::from_str::<Rotation>("4").is_err());
+    }
+
+    #[test]
+    fn test_display_placement() {
+        let placement = TilePlacement { coordinate: BoardCoordinate::new(1, -2), rotation: Rotation::R90 };
+
+        assert_eq!(BoardCoordinate::new(1, -2).to_string(), "(1, -2)");
+        assert_eq!(placement.to_string(), "(1, -2) r90");
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_render_preview_labels_regions() {
+        let preview = crate::tile_definitions::SIDE_CITY.render_preview(&[Rotation::R0, Rotation::R180], &RenderStyle::Ascii);
+        let lines: Vec<_> = preview.lines().collect();
+
+        assert_eq!(lines[0], "r0            r180");
+        assert_eq!(lines[1].chars().count(), 12 * 2 + 2);
+        assert!(lines[1..=TILE_WIDTH].iter().any(|line| line.contains("1 ")));
+        assert!(lines.contains(&"0: field"));
+        assert!(lines.contains(&"1: city"));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn test_region_labels_only_replace_meeple_spots() {
+        let tile = PlacedTile::new(&CORNER_ROAD, 0, 0, Rotation::R90);
+        let plain = tile.render_to_lines(&RenderStyle::Ascii);
+        let labelled = tile.render_region_labels(&RenderStyle::Ascii);
+
+        let differing = plain.iter().zip(&labelled).filter(|(plain, labelled)| plain != labelled).count();
+
+        assert!(differing > 0);
+        assert!(labelled.iter().zip(&plain).all(|(labelled, plain)| labelled.chars().count() == plain.chars().count()));
+        assert!((0..CORNER_ROAD.regions.len()).all(|index| labelled.iter().any(|line| line.contains(&format!("{:<2}", index)))));
+    }
 }
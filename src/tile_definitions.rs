@@ -2,12 +2,44 @@ use crate::tile::CardinalDirection::{
     East, EastNorthEast, EastSouthEast, North, NorthNorthEast, NorthNorthWest, South,
     SouthSouthEast, SouthSouthWest, West, WestNorthWest, WestSouthWest,
 };
-use crate::tile::{
-    Expansion, Region, RenderCell, TileCoordinate, TileDefinition, TileRenderRepresentation,
-};
+use crate::tile::{Expansion, Region, RegionType, RenderCell, Rotation, TileCoordinate, TileDefinition, TileId, TileRenderRepresentation};
 
 // Definitions copied from https://cad.onshape.com/documents/04cfee738b84b4699685349a/w/f6c7a218fb2ae3244c5e18ee/e/e45463d6dd17036cc38b1be6
 
+const CROSS_INTERSECTION_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+    },
+    Region::Road {
+        edges: &[South],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+    },
+    Region::Road {
+        edges: &[East],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+    Region::Road {
+        edges: &[West],
+        meeple_coordinate: TileCoordinate { x: 1, y: 3 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 4, y: 2 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    },
+    Region::Field {
+        edges: &[EastSouthEast, SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 4, y: 4 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 2, y: 4 },
+    },
+];
 pub const CROSS_INTERSECTION: TileDefinition = TileDefinition {
     expansion: None,
     count: 1,
@@ -23,41 +55,40 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-        },
-        Region::Road {
-            edges: &[South],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-        },
-        Region::Road {
-            edges: &[East],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-        Region::Road {
-            edges: &[West],
-            meeple_coordinate: TileCoordinate { x: 1, y: 3 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 4, y: 2 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        },
-        Region::Field {
-            edges: &[EastSouthEast, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 4, y: 4 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest, SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 2, y: 4 },
-        },
-    ],
+    regions: CROSS_INTERSECTION_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CROSS_INTERSECTION_REGIONS),
 };
+const THREE_WAY_JUNCTION_WITH_CITY_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+    },
+    Region::Road {
+        edges: &[East],
+        meeple_coordinate: TileCoordinate { x: 4, y: 3 },
+    },
+    Region::Road {
+        edges: &[West],
+        meeple_coordinate: TileCoordinate { x: 1, y: 3 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 4, y: 2 },
+    },
+    Region::Field {
+        edges: &[EastSouthEast, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 4 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+    Region::City {
+        edges: &[SouthSouthEast, South, SouthSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+        pennant: false,
+    },
+];
 pub const THREE_WAY_JUNCTION_WITH_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -73,38 +104,31 @@ FFCCCFF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-        },
-        Region::Road {
-            edges: &[East],
-            meeple_coordinate: TileCoordinate { x: 4, y: 3 },
-        },
-        Region::Road {
-            edges: &[West],
-            meeple_coordinate: TileCoordinate { x: 1, y: 3 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 4, y: 2 },
-        },
-        Region::Field {
-            edges: &[EastSouthEast, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 4 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 1 },
-        },
-        Region::City {
-            edges: &[SouthSouthEast, South, SouthSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-            pennant: false,
-        },
-    ],
+    regions: THREE_WAY_JUNCTION_WITH_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(THREE_WAY_JUNCTION_WITH_CITY_REGIONS),
 };
+const STRAIGHT_CITY_WITH_SIDE_FIELDS_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 4 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[WestSouthWest, West, WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 3 },
+    },
+    Region::Field {
+        edges: &[EastNorthEast, East, EastSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+];
 pub const STRAIGHT_CITY_WITH_SIDE_FIELDS: TileDefinition = TileDefinition {
     expansion: None,
     count: 1,
@@ -120,29 +144,31 @@ FCCCCCF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 4 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[WestSouthWest, West, WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 3 },
-        },
-        Region::Field {
-            edges: &[EastNorthEast, East, EastSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-    ],
+    regions: STRAIGHT_CITY_WITH_SIDE_FIELDS_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_CITY_WITH_SIDE_FIELDS_REGIONS),
 };
+const STRAIGHT_CITY_WITH_SIDE_FIELDS_AND_PENNANT_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 2 },
+        pennant: true,
+    },
+    Region::Field {
+        edges: &[WestSouthWest, West, WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 3 },
+    },
+    Region::Field {
+        edges: &[EastNorthEast, East, EastSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+];
 pub const STRAIGHT_CITY_WITH_SIDE_FIELDS_AND_PENNANT: TileDefinition = TileDefinition {
     expansion: None,
     count: 2,
@@ -158,29 +184,34 @@ FCCCCCF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 2 },
-            pennant: true,
-        },
-        Region::Field {
-            edges: &[WestSouthWest, West, WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 3 },
-        },
-        Region::Field {
-            edges: &[EastNorthEast, East, EastSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-    ],
+    regions: STRAIGHT_CITY_WITH_SIDE_FIELDS_AND_PENNANT_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_CITY_WITH_SIDE_FIELDS_AND_PENNANT_REGIONS),
 };
+const STRAIGHT_ROAD_WITH_SIDE_CITY_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[EastNorthEast, WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 4, y: 2 },
+    },
+    Region::Field {
+        edges: &[
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 5 },
+    },
+    Region::Road {
+        edges: &[East, West],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+];
 pub const STRAIGHT_ROAD_WITH_SIDE_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 4,
@@ -196,32 +227,32 @@ FFFFFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[EastNorthEast, WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 4, y: 2 },
-        },
-        Region::Field {
-            edges: &[
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 5 },
-        },
-        Region::Road {
-            edges: &[East, West],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-    ],
+    regions: STRAIGHT_ROAD_WITH_SIDE_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_ROAD_WITH_SIDE_CITY_REGIONS),
 };
+const FIELD_WITH_TWO_CORNER_CITIES_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[EastNorthEast, East, EastSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+        pennant: false,
+    },
+    Region::City {
+        edges: &[SouthSouthEast, South, SouthSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    },
+];
 pub const FIELD_WITH_TWO_CORNER_CITIES: TileDefinition = TileDefinition {
     expansion: None,
     count: 2,
@@ -237,30 +268,35 @@ FFCCCFC
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[EastNorthEast, East, EastSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-            pennant: false,
-        },
-        Region::City {
-            edges: &[SouthSouthEast, South, SouthSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        },
-    ],
+    regions: FIELD_WITH_TWO_CORNER_CITIES_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(FIELD_WITH_TWO_CORNER_CITIES_REGIONS),
 };
+const CORNER_ROAD_WITH_PENNANTED_CORNER_CITY_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 5, y: 2 },
+        pennant: true,
+    },
+    Region::Field {
+        edges: &[SouthSouthEast, WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 5 },
+    },
+    Region::Road {
+        edges: &[South, West],
+        meeple_coordinate: TileCoordinate { x: 2, y: 4 },
+    },
+];
 pub const CORNER_ROAD_WITH_PENNANTED_CORNER_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 2,
@@ -276,33 +312,41 @@ FFRRFFC
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 5, y: 2 },
-            pennant: true,
-        },
-        Region::Field {
-            edges: &[SouthSouthEast, WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 5 },
-        },
-        Region::Road {
-            edges: &[South, West],
-            meeple_coordinate: TileCoordinate { x: 2, y: 4 },
-        },
-    ],
+    regions: CORNER_ROAD_WITH_PENNANTED_CORNER_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_ROAD_WITH_PENNANTED_CORNER_CITY_REGIONS),
 };
+const THREE_WAY_JUNCTION_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+    },
+    Region::Road {
+        edges: &[East],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+    Region::Road {
+        edges: &[West],
+        meeple_coordinate: TileCoordinate { x: 1, y: 3 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 1 },
+    },
+    Region::Field {
+        edges: &[
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 4 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+];
 pub const THREE_WAY_JUNCTION: TileDefinition = TileDefinition {
     expansion: None,
     count: 4,
@@ -318,39 +362,28 @@ FFFFFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-        },
-        Region::Road {
-            edges: &[East],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-        Region::Road {
-            edges: &[West],
-            meeple_coordinate: TileCoordinate { x: 1, y: 3 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 1 },
-        },
-        Region::Field {
-            edges: &[
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 4 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 1 },
-        },
-    ],
+    regions: THREE_WAY_JUNCTION_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(THREE_WAY_JUNCTION_REGIONS),
 };
+const RIVER_TERMINATOR_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 4, y: 1 },
+    },
+    Region::Water { edges: &[East] },
+];
 pub const RIVER_TERMINATOR: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 2,
@@ -366,26 +399,32 @@ FFFFFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 4, y: 1 },
-        },
-        Region::Water { edges: &[East] },
-    ],
+    regions: RIVER_TERMINATOR_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(RIVER_TERMINATOR_REGIONS),
 };
+const CORNER_ROAD_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[EastSouthEast, SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 5 },
+    },
+    Region::Field {
+        edges: &[
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    },
+    Region::Road {
+        edges: &[East, South],
+        meeple_coordinate: TileCoordinate { x: 4, y: 3 },
+    },
+];
 pub const CORNER_ROAD: TileDefinition = TileDefinition {
     expansion: None,
     count: 9,
@@ -401,30 +440,30 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[EastSouthEast, SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 5 },
-        },
-        Region::Field {
-            edges: &[
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        },
-        Region::Road {
-            edges: &[East, South],
-            meeple_coordinate: TileCoordinate { x: 4, y: 3 },
-        },
-    ],
+    regions: CORNER_ROAD_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_ROAD_REGIONS),
 };
+const RIVER_CORNER_WITH_ROAD_CORNER_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North, West],
+        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, EastNorthEast, SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 4, y: 2 },
+    },
+    Region::Water {
+        edges: &[East, South],
+    },
+    Region::Field {
+        edges: &[EastSouthEast, SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 5 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+];
 pub const RIVER_CORNER_WITH_ROAD_CORNER: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 1,
@@ -440,28 +479,34 @@ FFFWWFF
 +FFWFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North, West],
-            meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, EastNorthEast, SouthSouthWest, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 4, y: 2 },
-        },
-        Region::Water {
-            edges: &[East, South],
-        },
-        Region::Field {
-            edges: &[EastSouthEast, SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 5 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 1 },
-        },
-    ],
+    regions: RIVER_CORNER_WITH_ROAD_CORNER_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(RIVER_CORNER_WITH_ROAD_CORNER_REGIONS),
 };
+const CORNER_CITY_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+    Region::City {
+        edges: &[
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 5 },
+        pennant: false,
+    },
+];
 pub const CORNER_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -477,32 +522,38 @@ CCCCFFF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-        Region::City {
-            edges: &[
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 5 },
-            pennant: false,
-        },
-    ],
+    regions: CORNER_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_CITY_REGIONS),
 };
+const SIDE_CITY_WITH_SIDE_ROAD_AND_PENNANT_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 3 },
+        pennant: true,
+    },
+    Region::Field {
+        edges: &[SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 4, y: 5 },
+    },
+    Region::Road {
+        edges: &[South],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest],
+        meeple_coordinate: TileCoordinate { x: 2, y: 5 },
+    },
+];
 pub const SIDE_CITY_WITH_SIDE_ROAD_AND_PENNANT: TileDefinition = TileDefinition {
     expansion: None,
     count: 2,
@@ -518,36 +569,35 @@ CCFRFCC
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 3 },
-            pennant: true,
-        },
-        Region::Field {
-            edges: &[SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 4, y: 5 },
-        },
-        Region::Road {
-            edges: &[South],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest],
-            meeple_coordinate: TileCoordinate { x: 2, y: 5 },
-        },
-    ],
+    regions: SIDE_CITY_WITH_SIDE_ROAD_AND_PENNANT_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(SIDE_CITY_WITH_SIDE_ROAD_AND_PENNANT_REGIONS),
 };
+const CORNER_ROAD_WITH_CORNER_CITY_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North, West],
+        meeple_coordinate: TileCoordinate { x: 2, y: 1 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+    Region::City {
+        edges: &[
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 4, y: 5 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+];
 pub const CORNER_ROAD_WITH_CORNER_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -563,33 +613,32 @@ FCCCCCC
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North, West],
-            meeple_coordinate: TileCoordinate { x: 2, y: 1 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-        Region::City {
-            edges: &[
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 4, y: 5 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 1 },
-        },
-    ],
+    regions: CORNER_ROAD_WITH_CORNER_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_ROAD_WITH_CORNER_CITY_REGIONS),
 };
+const STRAIGHT_RIVER_WITH_TWO_SIDE_CITIES_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[EastNorthEast, WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    },
+    Region::Water {
+        edges: &[East, West],
+    },
+    Region::Field {
+        edges: &[EastSouthEast, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 4, y: 4 },
+    },
+    Region::City {
+        edges: &[SouthSouthEast, South, SouthSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+        pennant: false,
+    },
+];
 pub const STRAIGHT_RIVER_WITH_TWO_SIDE_CITIES: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 1,
@@ -605,30 +654,34 @@ FFCCCFF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[EastNorthEast, WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        },
-        Region::Water {
-            edges: &[East, West],
-        },
-        Region::Field {
-            edges: &[EastSouthEast, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 4, y: 4 },
-        },
-        Region::City {
-            edges: &[SouthSouthEast, South, SouthSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-            pennant: false,
-        },
-    ],
+    regions: STRAIGHT_RIVER_WITH_TWO_SIDE_CITIES_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_RIVER_WITH_TWO_SIDE_CITIES_REGIONS),
 };
+const CORNER_ROAD_WITH_SIDE_CITY_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+            WestNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 4, y: 3 },
+    },
+    Region::Road {
+        edges: &[South, West],
+        meeple_coordinate: TileCoordinate { x: 2, y: 3 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 5 },
+    },
+];
 pub const CORNER_ROAD_WITH_SIDE_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -644,32 +697,39 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-                WestNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 4, y: 3 },
-        },
-        Region::Road {
-            edges: &[South, West],
-            meeple_coordinate: TileCoordinate { x: 2, y: 3 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 5 },
-        },
-    ],
+    regions: CORNER_ROAD_WITH_SIDE_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_ROAD_WITH_SIDE_CITY_REGIONS),
 };
+const CLOISTER_WITH_ROAD_AND_RIVER_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North],
+        meeple_coordinate: TileCoordinate { x: 3, y: 2 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 4, y: 1 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 2, y: 1 },
+    },
+    Region::Water {
+        edges: &[East, West],
+    },
+    Region::Field {
+        edges: &[
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 1, y: 4 },
+    },
+    Region::Cloister {
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+    },
+];
 pub const CLOISTER_WITH_ROAD_AND_RIVER: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 1,
@@ -685,37 +745,31 @@ FFFTFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North],
-            meeple_coordinate: TileCoordinate { x: 3, y: 2 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 4, y: 1 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 2, y: 1 },
-        },
-        Region::Water {
-            edges: &[East, West],
-        },
-        Region::Field {
-            edges: &[
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 1, y: 4 },
-        },
-        Region::Cloister {
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-        },
-    ],
+    regions: CLOISTER_WITH_ROAD_AND_RIVER_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CLOISTER_WITH_ROAD_AND_RIVER_REGIONS),
 };
+const CLOISTER_IN_FIELD_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+    },
+    Region::Cloister {
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+];
 pub const CLOISTER_IN_FIELD: TileDefinition = TileDefinition {
     expansion: None,
     count: 4,
@@ -731,29 +785,30 @@ FFFFFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-        },
-        Region::Cloister {
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-    ],
+    regions: CLOISTER_IN_FIELD_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CLOISTER_IN_FIELD_REGIONS),
 };
+const THREE_SIDED_CITY_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+    },
+    Region::City {
+        edges: &[
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 4 },
+        pennant: false,
+    },
+];
 pub const THREE_SIDED_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -769,28 +824,27 @@ CCCCCCC
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-        },
-        Region::City {
-            edges: &[
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 4 },
-            pennant: false,
-        },
-    ],
+    regions: THREE_SIDED_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(THREE_SIDED_CITY_REGIONS),
 };
+const CENTRE_CITY_WITH_PENNANT_REGIONS: &[Region] = &[Region::City {
+    edges: &[
+        North,
+        NorthNorthEast,
+        EastNorthEast,
+        East,
+        EastSouthEast,
+        SouthSouthEast,
+        South,
+        SouthSouthWest,
+        WestSouthWest,
+        West,
+        WestNorthWest,
+        NorthNorthWest,
+    ],
+    meeple_coordinate: TileCoordinate { x: 2, y: 2 },
+    pennant: true,
+}];
 pub const CENTRE_CITY_WITH_PENNANT: TileDefinition = TileDefinition {
     expansion: None,
     count: 1,
@@ -806,25 +860,39 @@ CCCCCCC
 +CCCCC+
 ",
     ),
-    regions: &[Region::City {
-        edges: &[
-            North,
-            NorthNorthEast,
-            EastNorthEast,
-            East,
-            EastSouthEast,
-            SouthSouthEast,
-            South,
-            SouthSouthWest,
-            WestSouthWest,
-            West,
-            WestNorthWest,
-            NorthNorthWest,
-        ],
-        meeple_coordinate: TileCoordinate { x: 2, y: 2 },
-        pennant: true,
-    }],
+    regions: CENTRE_CITY_WITH_PENNANT_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CENTRE_CITY_WITH_PENNANT_REGIONS),
 };
+const SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 2 },
+    },
+    Region::Water {
+        edges: &[East, West],
+    },
+    Region::Field {
+        edges: &[EastSouthEast, SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 5 },
+    },
+    Region::Road {
+        edges: &[South],
+        meeple_coordinate: TileCoordinate { x: 3, y: 4 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 5 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 2 },
+    },
+];
 pub const SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 1,
@@ -840,37 +908,35 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 2 },
-        },
-        Region::Water {
-            edges: &[East, West],
-        },
-        Region::Field {
-            edges: &[EastSouthEast, SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 5 },
-        },
-        Region::Road {
-            edges: &[South],
-            meeple_coordinate: TileCoordinate { x: 3, y: 4 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 5 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 2 },
-        },
-    ],
+    regions: SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(SIDE_CITY_WITH_STRAIGHT_RIVER_AND_BRIDGE_REGIONS),
 };
+const STRAIGHT_ROAD_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North, South],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+    Region::Field {
+        edges: &[
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 5, y: 2 },
+    },
+    Region::Field {
+        edges: &[
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 1, y: 4 },
+    },
+];
 pub const STRAIGHT_ROAD: TileDefinition = TileDefinition {
     expansion: None,
     count: 8,
@@ -886,33 +952,34 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North, South],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-        Region::Field {
-            edges: &[
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 5, y: 2 },
-        },
-        Region::Field {
-            edges: &[
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 1, y: 4 },
-        },
-    ],
+    regions: STRAIGHT_ROAD_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_ROAD_REGIONS),
 };
+const STRAIGHT_RIVER_WITH_STRAIGHT_ROAD_AND_BRIDGE_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North, South],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+    Region::Field {
+        edges: &[NorthNorthEast, EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 1 },
+    },
+    Region::Water {
+        edges: &[East, West],
+    },
+    Region::Field {
+        edges: &[EastSouthEast, SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 5 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 5 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+];
 pub const STRAIGHT_RIVER_WITH_STRAIGHT_ROAD_AND_BRIDGE: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 1,
@@ -928,32 +995,34 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North, South],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-        Region::Field {
-            edges: &[NorthNorthEast, EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 1 },
-        },
-        Region::Water {
-            edges: &[East, West],
-        },
-        Region::Field {
-            edges: &[EastSouthEast, SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 5 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 5 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 1 },
-        },
-    ],
+    regions: STRAIGHT_RIVER_WITH_STRAIGHT_ROAD_AND_BRIDGE_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_RIVER_WITH_STRAIGHT_ROAD_AND_BRIDGE_REGIONS),
 };
+const CORNER_CITY_WITH_PENNANT_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 4, y: 2 },
+    },
+    Region::City {
+        edges: &[
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 1, y: 4 },
+        pennant: true,
+    },
+];
 pub const CORNER_CITY_WITH_PENNANT: TileDefinition = TileDefinition {
     expansion: None,
     count: 2,
@@ -969,32 +1038,30 @@ CCPCCFF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 4, y: 2 },
-        },
-        Region::City {
-            edges: &[
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 1, y: 4 },
-            pennant: true,
-        },
-    ],
+    regions: CORNER_CITY_WITH_PENNANT_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_CITY_WITH_PENNANT_REGIONS),
 };
+const THREE_SIDED_CITY_WITH_PENNANT_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 2 },
+        pennant: true,
+    },
+    Region::Field {
+        edges: &[EastNorthEast, East, EastSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+];
 pub const THREE_SIDED_CITY_WITH_PENNANT: TileDefinition = TileDefinition {
     expansion: None,
     count: 1,
@@ -1010,28 +1077,34 @@ CCCCCCF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 2 },
-            pennant: true,
-        },
-        Region::Field {
-            edges: &[EastNorthEast, East, EastSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-    ],
+    regions: THREE_SIDED_CITY_WITH_PENNANT_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(THREE_SIDED_CITY_WITH_PENNANT_REGIONS),
 };
+const CORNER_CITY_WITH_CORNER_RIVER_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 5, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[SouthSouthEast, WestNorthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+    Region::Field {
+        edges: &[SouthSouthWest, WestSouthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 5 },
+    },
+    Region::Water {
+        edges: &[South, West],
+    },
+];
 pub const CORNER_CITY_WITH_CORNER_RIVER: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 1,
@@ -1047,32 +1120,34 @@ FFWWFFC
 +FFWFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 5, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[SouthSouthEast, WestNorthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-        Region::Field {
-            edges: &[SouthSouthWest, WestSouthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 5 },
-        },
-        Region::Water {
-            edges: &[South, West],
-        },
-    ],
+    regions: CORNER_CITY_WITH_CORNER_RIVER_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_CITY_WITH_CORNER_RIVER_REGIONS),
 };
+const STRAIGHT_RIVER_REGIONS: &[Region] = &[
+    Region::Water {
+        edges: &[North, South],
+    },
+    Region::Field {
+        edges: &[
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 5, y: 2 },
+    },
+    Region::Field {
+        edges: &[
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 1, y: 4 },
+    },
+];
 pub const STRAIGHT_RIVER: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 2,
@@ -1088,32 +1163,31 @@ FFFWWFF
 +FFWFF+
 ",
     ),
-    regions: &[
-        Region::Water {
-            edges: &[North, South],
-        },
-        Region::Field {
-            edges: &[
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 5, y: 2 },
-        },
-        Region::Field {
-            edges: &[
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 1, y: 4 },
-        },
-    ],
+    regions: STRAIGHT_RIVER_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_RIVER_REGIONS),
 };
+const CORNER_RIVER_REGIONS: &[Region] = &[
+    Region::Water {
+        edges: &[North, West],
+    },
+    Region::Field {
+        edges: &[
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+    },
+    Region::Field {
+        edges: &[WestNorthWest, NorthNorthWest],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+];
 pub const CORNER_RIVER: TileDefinition = TileDefinition {
     expansion: Some(Expansion::River),
     count: 2,
@@ -1129,29 +1203,30 @@ FFFFFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::Water {
-            edges: &[North, West],
-        },
-        Region::Field {
-            edges: &[
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-        },
-        Region::Field {
-            edges: &[WestNorthWest, NorthNorthWest],
-            meeple_coordinate: TileCoordinate { x: 1, y: 1 },
-        },
-    ],
+    regions: CORNER_RIVER_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CORNER_RIVER_REGIONS),
 };
+const SIDE_CITY_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 2 },
+    },
+    Region::City {
+        edges: &[SouthSouthEast, South, SouthSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+        pennant: false,
+    },
+];
 pub const SIDE_CITY: TileDefinition = TileDefinition {
     expansion: None,
     count: 5,
@@ -1167,28 +1242,32 @@ FFCCCFF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::Field {
-            edges: &[
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 2 },
-        },
-        Region::City {
-            edges: &[SouthSouthEast, South, SouthSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-            pennant: false,
-        },
-    ],
+    regions: SIDE_CITY_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(SIDE_CITY_REGIONS),
 };
+const OPPOSING_SIDE_CITIES_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+    Region::City {
+        edges: &[SouthSouthEast, South, SouthSouthWest],
+        meeple_coordinate: TileCoordinate { x: 3, y: 5 },
+        pennant: false,
+    },
+];
 pub const OPPOSING_SIDE_CITIES: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -1204,30 +1283,38 @@ FFCCCFF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-        Region::City {
-            edges: &[SouthSouthEast, South, SouthSouthWest],
-            meeple_coordinate: TileCoordinate { x: 3, y: 5 },
-            pennant: false,
-        },
-    ],
+    regions: OPPOSING_SIDE_CITIES_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(OPPOSING_SIDE_CITIES_REGIONS),
 };
+const THREE_SIDED_CITY_WITH_ROAD_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 3 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[EastNorthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 2 },
+    },
+    Region::Road {
+        edges: &[East],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+    Region::Field {
+        edges: &[EastSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 4 },
+    },
+];
 pub const THREE_SIDED_CITY_WITH_ROAD: TileDefinition = TileDefinition {
     expansion: None,
     count: 1,
@@ -1243,36 +1330,34 @@ CCCCCCF
 +CCCCC+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-                North,
-                NorthNorthEast,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 3 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[EastNorthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 2 },
-        },
-        Region::Road {
-            edges: &[East],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-        Region::Field {
-            edges: &[EastSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 4 },
-        },
-    ],
+    regions: THREE_SIDED_CITY_WITH_ROAD_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(THREE_SIDED_CITY_WITH_ROAD_REGIONS),
 };
+const STRAIGHT_ROAD_WITH_RIGHT_CORNER_ROAD_REGIONS: &[Region] = &[
+    Region::City {
+        edges: &[NorthNorthWest, North, NorthNorthEast],
+        meeple_coordinate: TileCoordinate { x: 3, y: 1 },
+        pennant: false,
+    },
+    Region::Field {
+        edges: &[
+            EastNorthEast,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 2, y: 3 },
+    },
+    Region::Road {
+        edges: &[East, South],
+        meeple_coordinate: TileCoordinate { x: 4, y: 4 },
+    },
+    Region::Field {
+        edges: &[EastSouthEast, SouthSouthEast],
+        meeple_coordinate: TileCoordinate { x: 5, y: 5 },
+    },
+];
 pub const STRAIGHT_ROAD_WITH_RIGHT_CORNER_ROAD: TileDefinition = TileDefinition {
     expansion: None,
     count: 3,
@@ -1288,32 +1373,34 @@ FFFRFFF
 +FFRFF+
 ",
     ),
-    regions: &[
-        Region::City {
-            edges: &[NorthNorthWest, North, NorthNorthEast],
-            meeple_coordinate: TileCoordinate { x: 3, y: 1 },
-            pennant: false,
-        },
-        Region::Field {
-            edges: &[
-                EastNorthEast,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 2, y: 3 },
-        },
-        Region::Road {
-            edges: &[East, South],
-            meeple_coordinate: TileCoordinate { x: 4, y: 4 },
-        },
-        Region::Field {
-            edges: &[EastSouthEast, SouthSouthEast],
-            meeple_coordinate: TileCoordinate { x: 5, y: 5 },
-        },
-    ],
+    regions: STRAIGHT_ROAD_WITH_RIGHT_CORNER_ROAD_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(STRAIGHT_ROAD_WITH_RIGHT_CORNER_ROAD_REGIONS),
 };
+const CLOISTER_WITH_ROAD_REGIONS: &[Region] = &[
+    Region::Road {
+        edges: &[North],
+        meeple_coordinate: TileCoordinate { x: 3, y: 2 },
+    },
+    Region::Field {
+        edges: &[
+            NorthNorthEast,
+            EastNorthEast,
+            East,
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+        ],
+        meeple_coordinate: TileCoordinate { x: 5, y: 3 },
+    },
+    Region::Cloister {
+        meeple_coordinate: TileCoordinate { x: 3, y: 3 },
+    },
+];
 pub const CLOISTER_WITH_ROAD: TileDefinition = TileDefinition {
     expansion: None,
     count: 2,
@@ -1329,33 +1416,49 @@ FFFFFFF
 +FFFFF+
 ",
     ),
-    regions: &[
-        Region::Road {
-            edges: &[North],
-            meeple_coordinate: TileCoordinate { x: 3, y: 2 },
-        },
-        Region::Field {
-            edges: &[
-                NorthNorthEast,
-                EastNorthEast,
-                East,
-                EastSouthEast,
-                SouthSouthEast,
-                South,
-                SouthSouthWest,
-                WestSouthWest,
-                West,
-                WestNorthWest,
-                NorthNorthWest,
-            ],
-            meeple_coordinate: TileCoordinate { x: 5, y: 3 },
-        },
-        Region::Cloister {
-            meeple_coordinate: TileCoordinate { x: 3, y: 3 },
-        },
-    ],
+    regions: CLOISTER_WITH_ROAD_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(CLOISTER_WITH_ROAD_REGIONS),
 };
-pub const ALL_TILE_DEFINITIONS: [TileDefinition; 33] = [
+/// Not one of the river's tiles: it takes the place of the second terminator when the rules end the
+/// river in a lake
+const LAKE_REGIONS: &[Region] = &[
+    Region::Field {
+        edges: &[
+            EastSouthEast,
+            SouthSouthEast,
+            South,
+            SouthSouthWest,
+            WestSouthWest,
+            West,
+            WestNorthWest,
+            NorthNorthWest,
+            North,
+            NorthNorthEast,
+            EastNorthEast,
+        ],
+        meeple_coordinate: TileCoordinate { x: 1, y: 1 },
+    },
+    Region::Water { edges: &[East] },
+];
+pub const LAKE: TileDefinition = TileDefinition {
+    expansion: Some(Expansion::River),
+    count: 0,
+    name: "Lake",
+    render: ascii_to_tile(
+        "
++FFFFF+
+FFWWWFF
+FWWWWWF
+FWWWWWW
+FWWWWWF
+FFWWWFF
++FFFFF+
+",
+    ),
+    regions: LAKE_REGIONS,
+    symmetry_class: TileDefinition::symmetry_class_of(LAKE_REGIONS),
+};
+pub const ALL_TILE_DEFINITIONS: [TileDefinition; 34] = [
     CROSS_INTERSECTION,
     THREE_WAY_JUNCTION_WITH_CITY,
     STRAIGHT_CITY_WITH_SIDE_FIELDS,
@@ -1389,9 +1492,97 @@ pub const ALL_TILE_DEFINITIONS: [TileDefinition; 33] = [
     THREE_SIDED_CITY_WITH_ROAD,
     STRAIGHT_ROAD_WITH_RIGHT_CORNER_ROAD,
     CLOISTER_WITH_ROAD,
+    // after the others, so adding it left their ids as they were
+    LAKE,
 ];
 
-const fn ascii_to_tile(ascii: &'static str) -> TileRenderRepresentation {
+/// Looks up a tile definition by its exact name
+pub fn find_by_name(name: &str) -> Option<&'static TileDefinition> {
+    ALL_TILE_DEFINITIONS.iter().find(|definition| definition.name == name)
+}
+
+/// Looks up a tile definition by its [TileId]
+pub fn find_by_id(id: TileId) -> Option<&'static TileDefinition> {
+    ALL_TILE_DEFINITIONS.get(id as usize)
+}
+
+/// Fingerprint of the tile definitions, in order: their names, counts, expansions, edges and the
+/// regions the edges make up. Saved games record it, as tile ids are positions in
+/// [ALL_TILE_DEFINITIONS] and change with it
+pub fn tile_set_hash() -> String {
+    hash_tile_set(&ALL_TILE_DEFINITIONS)
+}
+
+fn hash_tile_set<'a, I: IntoIterator<Item = &'a TileDefinition>>(definitions: I) -> String {
+    // FNV-1a, as std's hasher isn't guaranteed to hash the same way between releases
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut write = |bytes: &[u8]| {
+        for byte in bytes {
+            hash = (hash ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    };
+    let region_code = |region_type: RegionType| match region_type {
+        RegionType::City => 1,
+        RegionType::Field => 2,
+        RegionType::Cloister => 3,
+        RegionType::Road => 4,
+        RegionType::Water => 5,
+    };
+
+    for definition in definitions {
+        write(definition.name.as_bytes());
+        write(&[0, definition.count, definition.regions.len() as u8]);
+        write(definition.expansion.map_or("", |expansion| expansion.name()).as_bytes());
+
+        for region_type in definition.list_oriented_region_types(Rotation::R0) {
+            write(&[region_code(region_type)]);
+        }
+
+        // two tiles can agree on every edge and still join them into different regions
+        for region in definition.regions {
+            write(&[u8::MAX, region_code(region.region_type())]);
+            write(&region.edges().iter().map(|edge| *edge as u8).collect::<Vec<_>>());
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Looks up a tile definition as a person would type it: by id, by name ignoring case and
+/// punctuation, or by the start of a name. When several names start with the query the shortest
+/// wins, so `three way` is the plain three-way junction rather than the one with a city
+pub fn find(name_or_id: &str) -> Option<&'static TileDefinition> {
+    if let Ok(id) = name_or_id.trim().parse::<TileId>() {
+        return find_by_id(id);
+    }
+
+    let query = normalise_name(name_or_id);
+
+    if query.is_empty() {
+        return None;
+    }
+
+    ALL_TILE_DEFINITIONS
+        .iter()
+        .find(|definition| normalise_name(definition.name) == query)
+        .or_else(|| {
+            ALL_TILE_DEFINITIONS
+                .iter()
+                .filter(|definition| normalise_name(definition.name).starts_with(&query))
+                .min_by_key(|definition| definition.name.len())
+        })
+}
+
+/// Lowercase words separated by single spaces, so `Three-way`, `three_way` and `THREE  WAY` agree
+fn normalise_name(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) const fn ascii_to_tile(ascii: &'static str) -> TileRenderRepresentation {
     let mut repr: [[RenderCell; 7]; 7] = [[RenderCell::Corner; 7]; 7];
 
     // @todo once iterators are allowed in const fns, the following can be greatly simplified to
@@ -1472,4 +1663,69 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_tile_ids_round_trip() {
+        for tile in ALL_TILE_DEFINITIONS.iter() {
+            assert_eq!(find_by_id(tile.id()), Some(tile));
+        }
+    }
+
+    #[test]
+    fn test_find_tile_by_name_or_id() {
+        assert_eq!(find("Side city"), Some(&SIDE_CITY));
+        assert_eq!(find("  side CITY "), Some(&SIDE_CITY));
+        assert_eq!(find("three way"), Some(&THREE_WAY_JUNCTION));
+        assert_eq!(find("three_way junction with"), Some(&THREE_WAY_JUNCTION_WITH_CITY));
+        assert_eq!(find(&CLOISTER_IN_FIELD.id().to_string()), Some(&CLOISTER_IN_FIELD));
+        assert_eq!(find("cloister in"), Some(&CLOISTER_IN_FIELD));
+        assert_eq!(find("castle"), None);
+        assert_eq!(find(""), None);
+        assert_eq!(find("255"), None);
+    }
+
+    #[test]
+    fn test_tile_set_hash_is_stable() {
+        assert_eq!(tile_set_hash(), tile_set_hash());
+        assert_eq!(tile_set_hash().len(), 16);
+    }
+
+    #[test]
+    fn test_cross_intersection_fields_each_lie_between_two_of_its_roads() {
+        let fields: Vec<_> = CROSS_INTERSECTION.regions.iter().filter(|region| region.region_type() == RegionType::Field).map(|region| region.edges()).collect();
+
+        assert_eq!(fields.len(), 4);
+
+        // the edges either side of a corner are a step apart clockwise, with no road between them
+        for edges in fields {
+            let [first, second] = edges else {
+                panic!("a corner field should have two edges, not {:?}", edges);
+            };
+
+            assert!((*first as u8 + 1) % 12 == *second as u8 || (*second as u8 + 1) % 12 == *first as u8, "{:?} aren't either side of a corner", edges);
+        }
+    }
+
+    #[test]
+    fn test_tile_set_hash_covers_the_regions_edges_make_up() {
+        // the crossroads as it was first defined, with its southern fields' edges swapped over
+        const SWAPPED_FIELDS: TileDefinition = TileDefinition {
+            regions: &[
+                Region::Road { edges: &[North], meeple_coordinate: TileCoordinate { x: 3, y: 1 } },
+                Region::Road { edges: &[South], meeple_coordinate: TileCoordinate { x: 3, y: 5 } },
+                Region::Road { edges: &[East], meeple_coordinate: TileCoordinate { x: 5, y: 3 } },
+                Region::Road { edges: &[West], meeple_coordinate: TileCoordinate { x: 1, y: 3 } },
+                Region::Field { edges: &[NorthNorthEast, EastNorthEast], meeple_coordinate: TileCoordinate { x: 4, y: 2 } },
+                Region::Field { edges: &[WestNorthWest, NorthNorthWest], meeple_coordinate: TileCoordinate { x: 2, y: 2 } },
+                Region::Field { edges: &[EastSouthEast, WestSouthWest], meeple_coordinate: TileCoordinate { x: 4, y: 4 } },
+                Region::Field { edges: &[SouthSouthWest, SouthSouthEast], meeple_coordinate: TileCoordinate { x: 2, y: 4 } },
+            ],
+            ..CROSS_INTERSECTION
+        };
+
+        let swapped = ALL_TILE_DEFINITIONS.iter().map(|definition| if *definition == CROSS_INTERSECTION { &SWAPPED_FIELDS } else { definition });
+
+        assert_eq!(hash_tile_set(&ALL_TILE_DEFINITIONS), tile_set_hash());
+        assert_ne!(hash_tile_set(swapped), tile_set_hash());
+    }
 }
@@ -0,0 +1,656 @@
+use crate::bot_strategy::{BotPlayer, BotStrategy, ParameterError};
+use crate::game_logic::GameBuildError;
+use crate::player::{Player, PlayerIdentifier};
+use crate::rng::GameRng;
+use crate::simulate::{play_game, GameResult, Seed};
+use rand::Rng;
+use rayon::prelude::*;
+use std::fmt::{Display, Formatter};
+
+/// A head-to-head between two bots where every sampled deck is played twice, once from each seat.
+/// Pairing the games cancels out most of the luck of the draw and of seat order, so a genuine
+/// strength difference shows up in far fewer games than independent sampling would need
+///
+/// Pairs are played in parallel. Each pair's deck comes from its own stream of the seed, so the
+/// games are the same whatever the number of threads
+#[derive(Clone)]
+pub struct PairedMatchup {
+    first: BotPlayer,
+    second: BotPlayer,
+    include_river: bool,
+    /// Threads to play on, rather than rayon's global pool
+    threads: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PairedGame {
+    pub seed: Seed,
+    /// The game played with the first bot in the first seat
+    pub original: GameResult,
+    /// The same deck played with the seats swapped
+    pub mirrored: GameResult,
+}
+
+/// Sequential probability ratio test over the decisive paired games of a matchup. Each pair counts
+/// as a win or loss for the first bot (drawn pairs are ignored), and the test weighs the hypothesis
+/// that the first bot wins a pair with probability `0.5 + margin` against `0.5 - margin`
+#[derive(Debug, Clone)]
+pub struct Sprt {
+    /// Chance of wrongly concluding the first bot is stronger
+    pub alpha: f64,
+    /// Chance of wrongly concluding the second bot is stronger
+    pub beta: f64,
+    pub margin: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    FirstIsStronger,
+    SecondIsStronger,
+    /// The pair limit was reached before either hypothesis could be accepted
+    Inconclusive,
+}
+
+#[derive(Debug, Clone)]
+pub struct SprtReport {
+    pub decision: SprtDecision,
+    pub log_likelihood_ratio: f64,
+    pub paired: PairedReport,
+}
+
+#[derive(Debug, Clone)]
+pub struct PairedReport {
+    pub first: PlayerIdentifier,
+    pub second: PlayerIdentifier,
+    pub games: Vec<PairedGame>,
+}
+
+impl PairedMatchup {
+    /// The bots need meeple of different colours, as their scores are told apart by them
+    pub fn new(first: BotPlayer, second: BotPlayer) -> Result<Self, GameBuildError> {
+        if first.player.meeple_color == second.player.meeple_color {
+            return Err(GameBuildError::DuplicatePlayer(second.player.meeple_color));
+        }
+
+        Ok(Self {
+            first,
+            second,
+            include_river: true,
+            threads: None,
+        })
+    }
+
+    pub fn with_river(mut self, include_river: bool) -> Self {
+        self.include_river = include_river;
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Runs `f` on the matchup's thread pool. Parallel hint generation inside the games shares the
+    /// same pool
+    fn in_pool<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        match self.threads {
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("should be able to start a thread pool")
+                .install(f),
+            None => f(),
+        }
+    }
+
+    /// Plays the pairs with the given indices in parallel, in order
+    fn play_pairs(&self, pairs: std::ops::Range<usize>, seed: Seed) -> PairedReport {
+        let empty = || PairedReport { first: self.first.player.meeple_color, second: self.second.player.meeple_color, games: vec![] };
+
+        pairs
+            .into_par_iter()
+            .map(|index| PairedReport { games: vec![self.play(pair_seed(seed, index))], ..empty() })
+            .reduce(empty, PairedReport::merge)
+    }
+
+    pub fn play(&self, seed: Seed) -> PairedGame {
+        let seated = |a: &BotPlayer, b: &BotPlayer| {
+            [a.clone(), b.clone()]
+                .into_iter()
+                .map(|p| (p.player.meeple_color, p))
+                .collect()
+        };
+
+        PairedGame {
+            seed,
+            original: play_game(seated(&self.first, &self.second), seed, self.include_river),
+            mirrored: play_game(seated(&self.second, &self.first), seed, self.include_river),
+        }
+    }
+
+    /// Plays pairs until the test reaches a decision, or `max_pairs` have been played. Pairs are
+    /// played a batch at a time, a pair for each thread, and the test is checked after each pair in
+    /// order, so any played past the decision are dropped and the result doesn't depend on the
+    /// batch size
+    pub fn run_sprt(&self, sprt: &Sprt, max_pairs: usize, seed: Seed) -> SprtReport {
+        self.run_sprt_observed(sprt, max_pairs, seed, &mut |_| {})
+    }
+
+    /// As [PairedMatchup::run_sprt], handing each pair that counts towards the test to `on_pair`
+    /// as it is, in order and on the calling thread
+    pub fn run_sprt_observed(&self, sprt: &Sprt, max_pairs: usize, seed: Seed, on_pair: &mut (dyn FnMut(&PairedGame) + Send)) -> SprtReport {
+        self.in_pool(|| {
+            let mut paired = PairedReport {
+                first: self.first.player.meeple_color,
+                second: self.second.player.meeple_color,
+                games: Vec::new(),
+            };
+
+            let (lower, upper) = sprt.bounds();
+            let batch_size = rayon::current_num_threads().max(1);
+
+            while paired.games.len() < max_pairs {
+                let played = paired.games.len();
+                let batch = self.play_pairs(played..(played + batch_size).min(max_pairs), seed);
+
+                for game in batch.games {
+                    on_pair(&game);
+                    paired.games.push(game);
+
+                    let (wins, losses, _) = paired.win_loss_draw();
+                    let log_likelihood_ratio = sprt.log_likelihood_ratio(wins, losses);
+
+                    let decision = if log_likelihood_ratio >= upper {
+                        SprtDecision::FirstIsStronger
+                    } else if log_likelihood_ratio <= lower {
+                        SprtDecision::SecondIsStronger
+                    } else {
+                        continue;
+                    };
+
+                    return SprtReport { decision, log_likelihood_ratio, paired };
+                }
+            }
+
+            let (wins, losses, _) = paired.win_loss_draw();
+
+            SprtReport {
+                decision: SprtDecision::Inconclusive,
+                log_likelihood_ratio: sprt.log_likelihood_ratio(wins, losses),
+                paired,
+            }
+        })
+    }
+
+    pub fn run(&self, pairs: usize, seed: Seed) -> PairedReport {
+        self.in_pool(|| self.play_pairs(0..pairs, seed))
+    }
+}
+
+impl PairedGame {
+    /// Points scored by `player` minus points scored by `opponent`, summed over both seatings
+    pub fn differential(&self, player: PlayerIdentifier, opponent: PlayerIdentifier) -> i32 {
+        [&self.original, &self.mirrored]
+            .into_iter()
+            .map(|game| points(game, player) - points(game, opponent))
+            .sum()
+    }
+}
+
+impl Default for Sprt {
+    fn default() -> Self {
+        Self {
+            alpha: 0.05,
+            beta: 0.05,
+            margin: 0.05,
+        }
+    }
+}
+
+impl Sprt {
+    /// The (lower, upper) log-likelihood ratio thresholds at which the test stops
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    pub fn log_likelihood_ratio(&self, wins: usize, losses: usize) -> f64 {
+        let stronger = 0.5 + self.margin;
+        let weaker = 0.5 - self.margin;
+
+        wins as f64 * (stronger / weaker).ln() + losses as f64 * (weaker / stronger).ln()
+    }
+}
+
+impl PairedReport {
+    /// The pairs of both reports, this one's first
+    pub fn merge(mut self, other: PairedReport) -> Self {
+        self.games.extend(other.games);
+        self
+    }
+
+    /// Count of pairs won, lost and drawn by the first bot
+    pub fn win_loss_draw(&self) -> (usize, usize, usize) {
+        self.differentials()
+            .into_iter()
+            .fold((0, 0, 0), |(wins, losses, draws), differential| match differential {
+                d if d > 0 => (wins + 1, losses, draws),
+                d if d < 0 => (wins, losses + 1, draws),
+                _ => (wins, losses, draws + 1),
+            })
+    }
+
+    /// Differential of the first bot over the second for each pair of games
+    pub fn differentials(&self) -> Vec<i32> {
+        self.games
+            .iter()
+            .map(|game| game.differential(self.first, self.second))
+            .collect()
+    }
+
+    pub fn mean_differential(&self) -> f64 {
+        mean(&self.differentials())
+    }
+
+    /// Standard error of the mean paired differential
+    pub fn standard_error(&self) -> f64 {
+        let differentials = self.differentials();
+
+        if differentials.len() < 2 {
+            return f64::INFINITY;
+        }
+
+        let mean = mean(&differentials);
+        let variance = differentials
+            .iter()
+            .map(|&d| (d as f64 - mean).powi(2))
+            .sum::<f64>()
+            / (differentials.len() - 1) as f64;
+
+        (variance / differentials.len() as f64).sqrt()
+    }
+
+    /// Approximate 95% confidence interval for the mean paired differential
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let mean = self.mean_differential();
+        let margin = 1.96 * self.standard_error();
+
+        (mean - margin, mean + margin)
+    }
+}
+
+/// The values to try a bot's parameter at in a [Sweep]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterRange {
+    pub name: String,
+    pub values: Vec<f64>,
+}
+
+/// Why a [Sweep] couldn't be set up
+#[derive(Debug, Clone, PartialEq)]
+pub enum SweepError {
+    /// The range isn't written as [ParameterRange::parse] reads them
+    InvalidRange(String),
+    Parameter(ParameterError),
+    NoOpponents,
+}
+
+impl Display for SweepError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepError::InvalidRange(range) => write!(f, "invalid parameter range [{}], expected e.g. samples=1..8, horizon=0..40step10 or budget=50,100,200", range),
+            SweepError::Parameter(error) => write!(f, "{}", error),
+            SweepError::NoOpponents => write!(f, "a sweep needs at least one opponent"),
+        }
+    }
+}
+
+impl ParameterRange {
+    /// Reads `name=start..end`, counting up in ones, `name=start..endstepS`, or a list of values such
+    /// as `name=1,2,4`. Both ends of a range are included
+    pub fn parse(range: &str) -> Result<Self, SweepError> {
+        let invalid = || SweepError::InvalidRange(range.to_string());
+        let number = |text: &str| text.trim().parse::<f64>().ok().filter(|value| value.is_finite()).ok_or_else(invalid);
+
+        let (name, values) = range.split_once('=').ok_or_else(invalid)?;
+
+        let values = match values.split_once("..") {
+            Some((start, rest)) => {
+                let (end, step) = rest.split_once("step").unwrap_or((rest, "1"));
+                let (start, end, step) = (number(start)?, number(end)?, number(step)?);
+
+                if step <= 0.0 || end < start {
+                    return Err(invalid());
+                }
+
+                // counted in steps from the start rather than added up, so the values don't drift
+                let steps = ((end - start) / step + 1e-9).floor() as usize;
+                (0..=steps).map(|index| ((start + index as f64 * step) * 1e9).round() / 1e9).collect()
+            }
+            None => values.split(',').map(number).collect::<Result<Vec<_>, _>>()?,
+        };
+
+        match name.trim() {
+            "" => Err(invalid()),
+            name => Ok(Self { name: name.to_string(), values }),
+        }
+    }
+}
+
+/// A grid search over one of a bot's parameters: the bot is set up with each value in turn and
+/// plays paired games (see [PairedMatchup]) against every bot of a fixed pool of opponents. Every
+/// value plays the same decks, so the differences between them are down to the parameter
+pub struct Sweep {
+    bot: BotStrategy,
+    range: ParameterRange,
+    opponents: Vec<BotStrategy>,
+    include_river: bool,
+    /// Threads to play on, rather than rayon's global pool
+    threads: Option<usize>,
+}
+
+/// How the bot did with its parameter set to `value`, over the pairs played against every opponent
+#[derive(Debug, Clone)]
+pub struct SweepPoint {
+    pub value: f64,
+    pub report: PairedReport,
+}
+
+impl Sweep {
+    /// Checks the bot takes every value of the range before any games are played
+    pub fn new(bot: BotStrategy, range: ParameterRange, opponents: Vec<BotStrategy>) -> Result<Self, SweepError> {
+        if opponents.is_empty() {
+            return Err(SweepError::NoOpponents);
+        }
+
+        for value in &range.values {
+            bot.clone().with_parameter(&range.name, *value).map_err(SweepError::Parameter)?;
+        }
+
+        Ok(Self { bot, range, opponents, include_river: true, threads: None })
+    }
+
+    pub fn with_river(mut self, include_river: bool) -> Self {
+        self.include_river = include_river;
+        self
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Plays `pairs` pairs of games against each opponent for every value, the values, opponents
+    /// and pairs all in parallel, returning the values best first by their mean paired differential
+    pub fn run(&self, pairs: usize, seed: Seed) -> Vec<SweepPoint> {
+        let sweep = || {
+            let mut points: Vec<SweepPoint> = self.range.values.par_iter().map(|value| self.play(*value, pairs, seed)).collect();
+            points.sort_by(|a, b| b.report.mean_differential().total_cmp(&a.report.mean_differential()));
+            points
+        };
+
+        match self.threads {
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("should be able to start a thread pool")
+                .install(sweep),
+            None => sweep(),
+        }
+    }
+
+    fn play(&self, value: f64, pairs: usize, seed: Seed) -> SweepPoint {
+        let bot = self.bot.clone().with_parameter(&self.range.name, value).expect("values are checked when the sweep is set up");
+
+        let report = self
+            .opponents
+            .par_iter()
+            .map(|opponent| {
+                PairedMatchup::new(Player::red().with_bot(bot.clone()), Player::green().with_bot(opponent.clone()))
+                    .expect("red and green are different colours")
+                    .with_river(self.include_river)
+                    .run(pairs, seed)
+            })
+            .reduce_with(PairedReport::merge)
+            .expect("a sweep has opponents");
+
+        SweepPoint { value, report }
+    }
+}
+
+impl SweepPoint {
+    /// Whether the value did worse than `best` by more than chance would account for, their 95%
+    /// confidence intervals not overlapping
+    pub fn clearly_worse_than(&self, best: &SweepPoint) -> bool {
+        self.report.confidence_interval().1 < best.report.confidence_interval().0
+    }
+}
+
+/// The deck for the pair at `index`, drawn from the pair's own stream of the seed
+fn pair_seed(seed: Seed, index: usize) -> Seed {
+    GameRng::stream(seed, index as u64).gen()
+}
+
+fn points(game: &GameResult, player: PlayerIdentifier) -> i32 {
+    game.score
+        .iter()
+        .find(|(id, _)| **id == player)
+        .map(|(_, score)| *score)
+        .unwrap_or(0)
+}
+
+fn mean(values: &[i32]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    values.iter().map(|&v| v as f64).sum::<f64>() / values.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot_strategy::{LazyBot, MonteCarloBot, MyopicBot};
+    use crate::player::MeepleColor;
+    use rand::SeedableRng;
+    use crate::rules::TieBreak;
+    use crate::score::Score;
+    use indexmap::IndexMap;
+    use std::time::Duration;
+
+    fn result(score: Score) -> GameResult {
+        GameResult {
+            seed: [0; 32],
+            score,
+            duration: Duration::ZERO,
+            placed_tile_count: 0,
+            teams: vec![],
+            resigned: vec![],
+            aborted: false,
+            turn_count: 72,
+            think_time: IndexMap::new(),
+            phase_think_time: IndexMap::new(),
+            features: IndexMap::new(),
+            tie_break: TieBreak::Shared,
+        }
+    }
+
+    #[test]
+    fn should_play_each_deck_from_both_seats() {
+        let matchup = PairedMatchup::new(
+            Player::red().with_bot(BotStrategy::Lazy(LazyBot)),
+            Player::green().with_bot(BotStrategy::Lazy(LazyBot)),
+        )
+        .unwrap()
+        .with_river(false);
+
+        let report = matchup.run(1, [3; 32]);
+        let pair = &report.games[0];
+
+        assert_eq!(pair.original.seed, pair.mirrored.seed);
+        assert!(pair.original.placed_tile_count > 0);
+        assert!(pair.mirrored.placed_tile_count > 0);
+        assert_eq!(report.differentials().len(), 1);
+    }
+
+    #[test]
+    fn should_play_the_same_decks_whatever_the_thread_count() {
+        let matchup = PairedMatchup::new(
+            Player::red().with_bot(BotStrategy::Lazy(LazyBot)),
+            Player::green().with_bot(BotStrategy::Lazy(LazyBot)),
+        )
+        .unwrap()
+        .with_river(false);
+
+        let seeds = |report: PairedReport| report.games.iter().map(|pair| pair.seed).collect::<Vec<_>>();
+
+        let single = seeds(matchup.clone().with_threads(1).run(4, [3; 32]));
+        let several = seeds(matchup.with_threads(3).run(4, [3; 32]));
+
+        assert_eq!(single, several);
+        assert_eq!(single, (0..4).map(|index| pair_seed([3; 32], index)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn should_read_a_parameter_range() {
+        let range = |text| ParameterRange::parse(text).map(|range| (range.name, range.values));
+
+        assert_eq!(range("samples=1..4"), Ok(("samples".to_string(), vec![1.0, 2.0, 3.0, 4.0])));
+        assert_eq!(range("deny_opponent=0..1step0.25"), Ok(("deny_opponent".to_string(), vec![0.0, 0.25, 0.5, 0.75, 1.0])));
+        assert_eq!(range("weight=0..0.3step0.1").unwrap().1, [0.0, 0.1, 0.2, 0.3]);
+        assert_eq!(range("budget=50,100,200").unwrap().1, [50.0, 100.0, 200.0]);
+
+        for invalid in ["samples", "=1..4", "samples=4..1", "samples=1..4step0", "samples=one"] {
+            assert_eq!(ParameterRange::parse(invalid), Err(SweepError::InvalidRange(invalid.to_string())));
+        }
+    }
+
+    #[test]
+    fn should_play_the_same_decks_against_every_opponent_of_a_sweep() {
+        let bot = BotStrategy::MonteCarlo(MonteCarloBot::new(GameRng::seed_from_u64(0)).with_horizon(0));
+        let opponents = vec![BotStrategy::Lazy(LazyBot), BotStrategy::Myopic(MyopicBot)];
+
+        let sweep = Sweep::new(bot, ParameterRange::parse("samples=1").unwrap(), opponents).unwrap().with_river(false);
+        let points = sweep.run(1, [3; 32]);
+
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].value, 1.0);
+        assert_eq!(points[0].report.games.len(), 2);
+        assert_eq!(points[0].report.games[0].seed, points[0].report.games[1].seed);
+        assert!(!points[0].clearly_worse_than(&points[0]));
+    }
+
+    #[test]
+    fn should_refuse_a_sweep_the_bot_cant_take() {
+        let lazy = || BotStrategy::Lazy(LazyBot);
+        let monte_carlo = || BotStrategy::MonteCarlo(MonteCarloBot::new(GameRng::seed_from_u64(0)));
+
+        assert!(matches!(
+            Sweep::new(lazy(), ParameterRange::parse("samples=1..2").unwrap(), vec![lazy()]),
+            Err(SweepError::Parameter(ParameterError::Unknown { bot: "lazy", .. }))
+        ));
+        assert!(matches!(
+            Sweep::new(monte_carlo(), ParameterRange::parse("samples=0..1step0.5").unwrap(), vec![lazy()]),
+            Err(SweepError::Parameter(ParameterError::NotWhole { value, .. })) if value == 0.5
+        ));
+        assert_eq!(
+            Sweep::new(monte_carlo(), ParameterRange::parse("samples=0..1step0.5").unwrap(), vec![lazy()]).err().unwrap().to_string(),
+            "samples only takes whole numbers, so not 0.5"
+        );
+        assert!(matches!(Sweep::new(monte_carlo(), ParameterRange::parse("samples=1").unwrap(), vec![]), Err(SweepError::NoOpponents)));
+    }
+
+    #[test]
+    fn should_sum_differential_across_both_seatings() {
+        let alice = Player::red();
+        let bob = Player::green();
+
+        let pair = PairedGame {
+            seed: [0; 32],
+            original: result(Score::from_iter([(&alice, 10), (&bob, 4)])),
+            mirrored: result(Score::from_iter([(&alice, 3), (&bob, 7)])),
+        };
+
+        assert_eq!(pair.differential(alice.meeple_color, bob.meeple_color), 2);
+        assert_eq!(pair.differential(bob.meeple_color, alice.meeple_color), -2);
+    }
+
+    #[test]
+    fn should_compute_confidence_interval_of_the_mean_differential() {
+        let alice = Player::red();
+        let bob = Player::green();
+
+        let pair = |alice_score, bob_score| PairedGame {
+            seed: [0; 32],
+            original: result(Score::from_iter([(&alice, alice_score), (&bob, bob_score)])),
+            mirrored: result(Score::new()),
+        };
+
+        let report = PairedReport {
+            first: alice.meeple_color,
+            second: bob.meeple_color,
+            games: vec![pair(4, 0), pair(6, 0), pair(8, 0)],
+        };
+
+        assert_eq!(report.mean_differential(), 6.0);
+        assert!((report.standard_error() - (4.0f64 / 3.0).sqrt()).abs() < 1e-9);
+
+        let (low, high) = report.confidence_interval();
+        assert!(low < 6.0 && high > 6.0);
+    }
+
+    #[test]
+    fn sprt_should_accept_a_hypothesis_once_the_evidence_crosses_a_bound() {
+        let sprt = Sprt::default();
+        let (lower, upper) = sprt.bounds();
+
+        assert!((lower + upper).abs() < 1e-9, "symmetric error rates give symmetric bounds");
+
+        // a win and a loss cancel out
+        assert_eq!(sprt.log_likelihood_ratio(1, 1), 0.0);
+
+        let wins_needed = (1..).find(|&w| sprt.log_likelihood_ratio(w, 0) >= upper).unwrap();
+        assert_eq!(wins_needed, 15);
+        assert!(sprt.log_likelihood_ratio(0, wins_needed) <= lower);
+    }
+
+    #[test]
+    fn should_refuse_to_pair_bots_of_the_same_colour() {
+        let matchup = PairedMatchup::new(Player::red().with_bot(BotStrategy::Lazy(LazyBot)), Player::red().with_bot(BotStrategy::Lazy(LazyBot)));
+
+        assert!(matches!(matchup, Err(GameBuildError::DuplicatePlayer(MeepleColor::Red))));
+    }
+
+    #[test]
+    fn sprt_should_be_inconclusive_when_the_pair_limit_is_reached() {
+        let matchup = PairedMatchup::new(
+            Player::red().with_bot(BotStrategy::Lazy(LazyBot)),
+            Player::green().with_bot(BotStrategy::Lazy(LazyBot)),
+        )
+        .unwrap()
+        .with_river(false);
+
+        let report = matchup.run_sprt(&Sprt::default(), 1, [5; 32]);
+
+        assert_eq!(report.decision, SprtDecision::Inconclusive);
+        assert_eq!(report.paired.games.len(), 1);
+    }
+
+    #[test]
+    fn sprt_should_hand_over_each_pair_it_counts() {
+        let matchup = PairedMatchup::new(
+            Player::red().with_bot(BotStrategy::Lazy(LazyBot)),
+            Player::green().with_bot(BotStrategy::Lazy(LazyBot)),
+        )
+        .unwrap()
+        .with_river(false)
+        .with_threads(2);
+
+        let mut seeds = vec![];
+        let report = matchup.run_sprt_observed(&Sprt::default(), 3, [5; 32], &mut |pair| seeds.push(pair.seed));
+
+        assert_eq!(seeds, report.paired.games.iter().map(|pair| pair.seed).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,314 @@
+use crate::observer::{GameEvent, GameObserver};
+use crate::player::PlayerIdentifier;
+use crate::score::Score;
+use crate::simulate::GameResult;
+use indexmap::IndexMap;
+use std::fmt::Write;
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long a post may take before it is given up on, so a slow webhook can't hold up the games
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The chat services a [WebhookNotifier] can post to, which want the message in different fields
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WebhookFormat {
+    Discord,
+    Slack,
+}
+
+impl WebhookFormat {
+    /// Slack for Slack's webhook URLs, otherwise Discord
+    pub fn for_url(url: &str) -> Self {
+        match url.contains("hooks.slack.com") {
+            true => WebhookFormat::Slack,
+            false => WebhookFormat::Discord,
+        }
+    }
+
+    /// The JSON body posting the message
+    pub fn payload(&self, message: &str) -> String {
+        let field = match self {
+            WebhookFormat::Discord => "content",
+            WebhookFormat::Slack => "text",
+        };
+
+        serde_json::json!({ field: message }).to_string()
+    }
+}
+
+#[derive(Debug, Default)]
+struct PlayerTally {
+    name: String,
+    points: i64,
+    wins: usize,
+}
+
+/// Posts how a batch of games is getting on to a Discord or Slack webhook: the games and turns
+/// played with each player's mean score and wins so far, at most once per interval, and the same
+/// for the whole batch once its last game completes. Meant for runs left going for hours on a
+/// server, where nobody is watching the progress bar. Posts are made on a thread of their own, so
+/// a slow webhook doesn't hold up the games
+pub struct WebhookNotifier {
+    format: WebhookFormat,
+    game_count: usize,
+    interval: Duration,
+    started: Instant,
+    last_post: Instant,
+    games: usize,
+    turns: usize,
+    players: IndexMap<PlayerIdentifier, PlayerTally>,
+    /// The messages for the posting thread, until [WebhookNotifier::finish]
+    posts: Option<Sender<String>>,
+    /// The posting thread, which gives back why each post it couldn't make failed. A failed post is
+    /// dropped rather than stopping the games, so these are for reporting once they finish
+    poster: Option<JoinHandle<Vec<String>>>,
+}
+
+impl WebhookNotifier {
+    /// Posts to `url` about a batch of `game_count` games, in the format its service wants
+    pub fn new(url: &str, game_count: usize) -> Self {
+        let now = Instant::now();
+        let format = WebhookFormat::for_url(url);
+        let (posts, messages) = mpsc::channel::<String>();
+        let url = url.to_string();
+
+        let poster = thread::spawn(move || {
+            let mut failures = vec![];
+
+            for body in messages {
+                if let Err(error) = ureq::post(&url).timeout(TIMEOUT).set("Content-Type", "application/json").send_string(&body) {
+                    failures.push(error.to_string());
+                }
+            }
+
+            failures
+        });
+
+        Self {
+            format,
+            game_count,
+            interval: Duration::from_secs(15 * 60),
+            started: now,
+            last_post: now,
+            games: 0,
+            turns: 0,
+            players: IndexMap::new(),
+            posts: Some(posts),
+            poster: Some(poster),
+        }
+    }
+
+    pub fn with_format(mut self, format: WebhookFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The least time between posts about progress, 15 minutes unless set. The final result is
+    /// posted however soon it follows
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// The games and turns played so far and how each player is doing over them
+    fn summary(&self) -> String {
+        let mut message = match self.games == self.game_count {
+            true => format!("Finished {} games", self.games),
+            false => format!("Played {} of {} games", self.games, self.game_count),
+        };
+
+        write!(message, " ({} turns) in {}", self.turns, render_elapsed(self.started.elapsed())).expect("writing to a string can't fail");
+
+        for tally in self.players.values() {
+            let mean = tally.points as f64 / self.games.max(1) as f64;
+
+            write!(message, "\n{}: {:.1} points on average, {} wins", tally.name, mean, tally.wins).expect("writing to a string can't fail");
+        }
+
+        message
+    }
+
+    /// The player's name in posts, in place of their colour, for games the notifier doesn't see
+    /// start, e.g. those given to [WebhookNotifier::record_result]
+    pub fn with_player_name(mut self, player: PlayerIdentifier, name: &str) -> Self {
+        self.players.entry(player).or_default().name = name.to_string();
+        self
+    }
+
+    /// Counts a game played where the notifier couldn't watch it, e.g. one of a tournament's games
+    /// played on another thread, posting as a game it had watched would
+    pub fn record_result(&mut self, result: &GameResult) {
+        self.turns += result.turn_count;
+        self.game_completed(&result.score);
+    }
+
+    /// Waits for the posts already made, returning why any of them failed. Posts the result of
+    /// the games played so far first, if the batch stopped short of its last game, e.g. once a
+    /// test reached its decision
+    pub fn finish(&mut self) -> Vec<String> {
+        if self.games > 0 && self.games < self.game_count {
+            self.game_count = self.games;
+
+            let message = self.summary();
+            self.post(&message);
+        }
+
+        // the posting thread stops once it has made the posts it was given
+        self.posts = None;
+
+        self.poster.take().map(|poster| poster.join().expect("posting thread shouldn't panic")).unwrap_or_default()
+    }
+
+    fn post(&mut self, message: &str) {
+        if let Some(posts) = &self.posts {
+            // the thread only stops once the sender is dropped
+            posts.send(self.format.payload(message)).expect("posting thread should still be running");
+        }
+
+        self.last_post = Instant::now();
+    }
+
+    fn game_completed(&mut self, score: &Score) {
+        self.record_game(score);
+
+        if self.games == self.game_count || self.last_post.elapsed() >= self.interval {
+            let message = self.summary();
+            self.post(&message);
+        }
+    }
+
+    fn record_game(&mut self, score: &Score) {
+        self.games += 1;
+
+        for (player_id, points) in score.iter() {
+            self.players.entry(*player_id).or_insert_with(|| PlayerTally { name: player_id.to_string(), ..Default::default() }).points += *points as i64;
+        }
+
+        // a tied game is nobody's win
+        let best = score.iter().map(|(_, points)| *points).max();
+        let leaders: Vec<_> = score.iter().filter(|(_, points)| Some(**points) == best).collect();
+
+        if let [(winner, _)] = leaders[..] {
+            if let Some(tally) = self.players.get_mut(winner) {
+                tally.wins += 1;
+            }
+        }
+    }
+}
+
+impl GameObserver for WebhookNotifier {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::GameStarted { players, .. } => {
+                for (player_id, name, bot) in players {
+                    let name = name.clone().or_else(|| bot.map(str::to_string)).unwrap_or_else(|| player_id.to_string());
+
+                    self.players.entry(*player_id).or_default().name = name;
+                }
+            }
+            GameEvent::TurnCompleted { .. } => self.turns += 1,
+            GameEvent::GameCompleted { score, .. } => self.game_completed(score),
+            GameEvent::RegionCompleted { .. } | GameEvent::MeepleRecalled { .. } | GameEvent::TileDead { .. } | GameEvent::ObviousMovePlayed { .. } | GameEvent::ControllerChanged { .. } | GameEvent::PlayerResigned { .. } => {}
+        }
+    }
+}
+
+impl Drop for WebhookNotifier {
+    /// Sees the last posts made, so a run that ends as its last game does still reports it
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Whole hours and minutes, or minutes and seconds for runs shorter than an hour
+fn render_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+
+    match seconds >= 3600 {
+        true => format!("{}h {:02}m", seconds / 3600, seconds % 3600 / 60),
+        false => format!("{}m {:02}s", seconds / 60, seconds % 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::MeepleColor;
+    use crate::rules::RuleSet;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use std::thread;
+
+    fn score(points: &[(MeepleColor, i32)]) -> Score {
+        points.iter().copied().collect()
+    }
+
+    #[test]
+    fn should_put_the_message_where_the_service_wants_it() {
+        assert_eq!(WebhookFormat::for_url("https://discord.com/api/webhooks/1/abc"), WebhookFormat::Discord);
+        assert_eq!(WebhookFormat::for_url("https://hooks.slack.com/services/T0/B0/x"), WebhookFormat::Slack);
+
+        assert_eq!(WebhookFormat::Discord.payload("hi \"there\""), r#"{"content":"hi \"there\""}"#);
+        assert_eq!(WebhookFormat::Slack.payload("hi"), r#"{"text":"hi"}"#);
+    }
+
+    #[test]
+    fn should_post_the_result_once_the_last_game_completes() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            std::io::Write::write_all(&mut &stream, b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+            String::from_utf8(body).unwrap()
+        });
+
+        // progress is never posted, only the result
+        let mut notifier = WebhookNotifier::new(&url, 2).with_interval(Duration::MAX);
+        let players = vec![(MeepleColor::Red, Some("Alice".to_string()), None), (MeepleColor::Blue, None, Some("myopic"))];
+
+        for (game_index, points) in [[(MeepleColor::Red, 10), (MeepleColor::Blue, 4)], [(MeepleColor::Red, 6), (MeepleColor::Blue, 6)]].iter().enumerate() {
+            notifier.notify(&GameEvent::GameStarted { game_index, seed: [0; 32], players: players.clone(), deck: vec![], rules: Box::new(RuleSet::standard()) });
+            notifier.notify(&GameEvent::GameCompleted { game_index, score: score(points) });
+        }
+
+        let body: serde_json::Value = serde_json::from_str(&server.join().unwrap()).unwrap();
+        let message = body["content"].as_str().unwrap();
+
+        assert!(message.starts_with("Finished 2 games (0 turns) in 0m 0"), "{}", message);
+        assert!(message.contains("\nAlice: 8.0 points on average, 1 wins\nmyopic: 5.0 points on average, 0 wins"), "{}", message);
+        assert!(notifier.finish().is_empty());
+    }
+
+    #[test]
+    fn should_keep_going_when_the_webhook_cannot_be_reached() {
+        // nothing listens on a port once its listener is dropped
+        let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let mut notifier = WebhookNotifier::new(&format!("http://{}/hook", address), 1);
+
+        notifier.notify(&GameEvent::GameCompleted { game_index: 0, score: score(&[(MeepleColor::Red, 3)]) });
+
+        assert_eq!(notifier.finish().len(), 1);
+    }
+}